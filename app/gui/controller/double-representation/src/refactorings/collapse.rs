@@ -4,6 +4,7 @@
 
 use crate::prelude::*;
 
+use crate::alias_analysis;
 use crate::connection::Connection;
 use crate::connection::Endpoint;
 use crate::definition;
@@ -42,7 +43,28 @@ pub fn collapse(
     parser: &Parser,
     module_name: String,
 ) -> FallibleResult<Collapsed> {
-    Collapser::new(graph.clone(), selected_nodes, parser.clone_ref(), module_name)?.collapse(name)
+    collapse_with_parameter_names(graph, selected_nodes, name, &default(), parser, module_name)
+}
+
+/// Run the "collapse node" refactoring like [`collapse`], naming the parameters of the new method
+/// as given.
+///
+/// The `parameter_names` map the identifiers used by the selected nodes from the outside scope to
+/// the names of the corresponding parameters. The usages of the identifiers in the extracted nodes
+/// are renamed accordingly, while the call to the new method still passes the original identifiers.
+/// The parameters missing in the map are named after their identifiers. Fails with
+/// [`ParameterNameCollision`] if the parameter names are not unique or collide with the names used
+/// or introduced by the extracted nodes.
+pub fn collapse_with_parameter_names(
+    graph: &GraphInfo,
+    selected_nodes: impl IntoIterator<Item = node::Id>,
+    name: Identifier,
+    parameter_names: &HashMap<Identifier, Identifier>,
+    parser: &Parser,
+    module_name: String,
+) -> FallibleResult<Collapsed> {
+    let collapser = Collapser::new(graph.clone(), selected_nodes, parser.clone_ref(), module_name)?;
+    collapser.collapse_with_parameter_names(name, parameter_names)
 }
 
 
@@ -94,6 +116,14 @@ output from the collapsed function. Found more than one output: `{}` and `{}`.",
 )]
 pub struct MultipleOutputIdentifiers(String, String);
 
+#[allow(missing_docs)]
+#[derive(Clone, Debug, Fail)]
+#[fail(
+    display = "Cannot name the parameter `{}`: the name is already used in the collapsed nodes.",
+    _0
+)]
+pub struct ParameterNameCollision(String);
+
 
 
 // ===================
@@ -255,19 +285,68 @@ impl Extracted {
         self.output.as_ref().map(|out| out.identifier.with_new_id().into())
     }
 
+    /// The identifiers used in the collapsed nodes from the outside scope, without duplicates. The
+    /// extracted method takes them as arguments in this order.
+    pub fn unique_inputs(&self) -> BTreeSet<&Identifier> {
+        self.inputs.iter().collect()
+    }
+
     /// Generate the description for the new method's definition with the extracted nodes.
-    pub fn generate(&self, name: Identifier) -> definition::ToAdd {
+    ///
+    /// The parameters are named according to the `parameter_names`, see
+    /// [`collapse_with_parameter_names`].
+    pub fn generate(
+        &self,
+        name: Identifier,
+        parameter_names: &HashMap<Identifier, Identifier>,
+    ) -> FallibleResult<definition::ToAdd> {
         let name = definition::DefinitionName::new_plain(name);
-        let inputs = self.inputs.iter().collect::<BTreeSet<_>>();
-        let return_line = self.return_line();
-        let mut selected_nodes_iter = self.extracted_nodes.iter().map(|node| node.ast().clone());
-        let body_head = selected_nodes_iter.next().unwrap();
-        let body_tail = selected_nodes_iter.chain(return_line).map(Some).collect();
-        let explicit_parameter_names = inputs.iter().map(|input| input.name().into()).collect();
-        definition::ToAdd { name, explicit_parameter_names, body_head, body_tail }
+        let explicit_parameter_names = self.parameter_names(parameter_names)?;
+        let renames = parameter_names.iter().map(|(input, name)| (input.name(), name)).collect();
+        let rename = |node: &NodeInfo| rename_outside_usages(node.ast(), &renames);
+        let renamed = self.extracted_nodes.iter().map(rename);
+        let mut body = renamed.collect::<FallibleResult<Vec<_>>>()?.into_iter();
+        let body_head = body.next().ok_or(NoNodesSelected)?;
+        let body_tail = body.chain(self.return_line()).map(Some).collect();
+        Ok(definition::ToAdd { name, explicit_parameter_names, body_head, body_tail })
+    }
+
+    /// The names of the parameters of the extracted method, in the order of
+    /// [`Self::unique_inputs`]. Fails if any name repeats or collides with a name used or
+    /// introduced by the extracted nodes, other than the inputs themselves.
+    fn parameter_names(
+        &self,
+        parameter_names: &HashMap<Identifier, Identifier>,
+    ) -> FallibleResult<Vec<String>> {
+        let inputs = self.unique_inputs();
+        let is_input = |name: &String| inputs.iter().any(|input| input.name() == name);
+        let bound_names = self.extracted_nodes.iter().flat_map(|node| {
+            let usage = alias_analysis::analyze_ast(node.ast());
+            usage.introduced.into_iter().chain(usage.used).map(|name| name.item)
+        });
+        let mut taken = bound_names.filter(|name| !is_input(name)).collect::<HashSet<_>>();
+        let parameter_name = |input: &Identifier| {
+            let name = parameter_names.get(input).unwrap_or(input).name().to_owned();
+            match taken.insert(name.clone()) {
+                true => Ok(name),
+                false => Err(ParameterNameCollision(name).into()),
+            }
+        };
+        inputs.iter().copied().map(parameter_name).collect()
     }
 }
 
+/// Replace the usages of the identifiers from the outside scope with the names given in the map.
+fn rename_outside_usages(ast: &Ast, names: &HashMap<&str, &Identifier>) -> FallibleResult<Ast> {
+    let usages = alias_analysis::analyze_ast(ast).used;
+    usages.into_iter().try_fold(ast.clone_ref(), |ast, usage| {
+        match names.get(usage.item.as_str()) {
+            Some(name) => ast.set_traversing(&usage.crumbs, name.with_new_id().into()),
+            None => Ok(ast),
+        }
+    })
+}
+
 
 
 // =================
@@ -325,7 +404,8 @@ impl Collapser {
         let mut target = extracted.name.clone();
         target.extended_target.insert(0, Located::new_root(self.module_name.clone()));
         let base = target.ast(&self.parser)?;
-        let args = extracted.explicit_parameter_names.iter().map(Ast::var);
+        // The parameters may be renamed, but the arguments are the identifiers from this scope.
+        let args = self.extracted.unique_inputs().into_iter().map(|input| Ast::var(input.name()));
         let chain = ast::prefix::Chain::new(base, args);
         Ok(chain.into_ast())
     }
@@ -366,7 +446,17 @@ impl Collapser {
 
     /// Run the collapsing refactoring on this input.
     pub fn collapse(&self, name: Identifier) -> FallibleResult<Collapsed> {
-        let new_method = self.extracted.generate(name);
+        self.collapse_with_parameter_names(name, &default())
+    }
+
+    /// Run the collapsing refactoring on this input, naming the parameters of the new method as
+    /// given. See [`collapse_with_parameter_names`].
+    pub fn collapse_with_parameter_names(
+        &self,
+        name: Identifier,
+        parameter_names: &HashMap<Identifier, Identifier>,
+    ) -> FallibleResult<Collapsed> {
+        let new_method = self.extracted.generate(name, parameter_names)?;
         let updated_definition =
             self.graph.rewrite_definition(|line| self.rewrite_line(line, &new_method))?;
         let collapsed_node = self.collapsed_node;
@@ -394,6 +484,7 @@ mod tests {
         module_name:         String,
         refactored_name:     DefinitionName,
         introduced_name:     Identifier,
+        parameter_names:     HashMap<Identifier, Identifier>,
         initial_method_code: &'static str,
         extracted_lines:     Range<usize>,
         expected_generated:  &'static str,
@@ -411,7 +502,15 @@ mod tests {
                 let selection = selection.iter().copied();
                 let new_name = self.introduced_name.clone();
                 let module_name = self.module_name.clone();
-                let collapsed = collapse(&graph, selection, new_name, parser, module_name).unwrap();
+                let collapsed = collapse_with_parameter_names(
+                    &graph,
+                    selection,
+                    new_name,
+                    &self.parameter_names,
+                    parser,
+                    module_name,
+                )
+                .unwrap();
                 let new_method = collapsed.new_method.ast(0, parser).unwrap();
                 let placement = module::Placement::Before(self.refactored_name.clone());
                 let new_main = &collapsed.updated_definition.ast;
@@ -468,6 +567,7 @@ mod tests {
             module_name,
             refactored_name,
             introduced_name,
+            parameter_names: default(),
             initial_method_code,
             extracted_lines,
             expected_generated,
@@ -545,4 +645,60 @@ mod tests {
     vector = Main.custom_new number1 number2";
         case.run(&parser);
     }
+
+    #[test]
+    fn collapse_with_renamed_parameters() {
+        let parser = Parser::new();
+        let rename = |from: &str, to: &str| {
+            (Identifier::try_from(from).unwrap(), Identifier::try_from(to).unwrap())
+        };
+        let case = Case {
+            module_name:         "Main".to_owned(),
+            refactored_name:     DefinitionName::new_plain("custom_old"),
+            introduced_name:     Identifier::try_from("custom_new").unwrap(),
+            parameter_names:     [rename("a", "first"), rename("b", "second")].into(),
+            initial_method_code: r"custom_old =
+    a = 1
+    b = 2
+    c = a + b
+    d = c * a
+    d + 1",
+            extracted_lines:     2..4,
+            expected_generated:  r"custom_new first second =
+    c = first + second
+    d = c * first
+    d",
+            expected_refactored: r"custom_old =
+    a = 1
+    b = 2
+    d = Main.custom_new a b
+    d + 1",
+        };
+        case.run(&parser);
+    }
+
+    #[test]
+    fn collapse_with_colliding_parameter_names() {
+        let parser = Parser::new();
+        let code = r"custom_old =
+    a = 1
+    b = 2
+    c = a + b
+    c + 1";
+        let ast = parser.parse_module(code, default()).unwrap();
+        let main = module::locate_child(&ast, &DefinitionName::new_plain("custom_old")).unwrap();
+        let graph = graph::GraphInfo::from_definition(main.item.clone());
+        let selection = [graph.nodes()[2].id()];
+        let identifier = |name: &str| Identifier::try_from(name).unwrap();
+        let collapse = |renames: &[(&str, &str)]| {
+            let renames = renames.iter().map(|(from, to)| (identifier(from), identifier(to)));
+            let renames = renames.collect();
+            let name = identifier("custom_new");
+            collapse_with_parameter_names(&graph, selection, name, &renames, &parser, "Main".into())
+        };
+        assert!(collapse(&[("a", "b")]).is_err());
+        assert!(collapse(&[("a", "c")]).is_err());
+        assert!(collapse(&[("a", "b"), ("b", "a")]).is_ok());
+        assert!(collapse(&[("a", "first")]).is_ok());
+    }
 }
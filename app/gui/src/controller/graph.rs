@@ -16,6 +16,7 @@ use double_representation::definition;
 use double_representation::definition::DefinitionProvider;
 use double_representation::graph::GraphInfo;
 use double_representation::identifier::generate_name;
+use double_representation::identifier::Identifier;
 use double_representation::import;
 use double_representation::module;
 use double_representation::name::project;
@@ -45,6 +46,8 @@ pub mod widget;
 pub use double_representation::graph::Id;
 pub use double_representation::graph::LocationHint;
 
+pub use clipboard::PastedContent;
+
 
 
 mod clipboard;
@@ -661,6 +664,23 @@ impl Handle {
         EndpointInfo::new(&source, pattern, context)
     }
 
+    /// Get the identifier by which the source endpoint is referred to in the graph, e.g. the
+    /// node's variable. Unlike [`Self::source_info`], it does not introduce the missing pattern.
+    pub fn source_identifier(
+        &self,
+        source: &Endpoint,
+        context: &impl SpanTreeContext,
+    ) -> FallibleResult<Identifier> {
+        let source_node = self.node_info(source.node)?;
+        let pattern = source_node.pattern().ok_or(NoPatternOnNode { node: source.node })?.clone();
+        let mut source = *source;
+        if source.port == PortId::Root {
+            source.port = PortId::Ast(pattern.id.ok_or(EndpointNotFound(source))?);
+        }
+        let identifier = EndpointInfo::new(&source, pattern, context)?.target_ast()?.clone();
+        Ok(Identifier::new(identifier).ok_or(UnsupportedPatternOnNode)?)
+    }
+
     /// If the node has no pattern, introduces a new pattern with a single variable name.
     pub fn introduce_pattern_if_missing(&self, node: node::Id) -> FallibleResult<Ast> {
         let source_node = self.node_info(node)?;
@@ -951,6 +971,18 @@ impl Handle {
         clipboard::paste_node(self, cursor_pos, on_error);
     }
 
+    /// Paste a node from clipboard at the given position, or create the nodes of the content
+    /// pasted from other applications if there is no node in the clipboard. See `clipboard` module
+    /// documentation for details.
+    pub fn paste_node_or_content(
+        &self,
+        position: Vector2,
+        content: PastedContent,
+        on_error: fn(String),
+    ) {
+        clipboard::paste_node_or_content(self, position, content, on_error);
+    }
+
     /// Sets the given's node expression.
     #[profile(Debug)]
     pub fn set_expression(&self, id: ast::Id, expression_text: impl Str) -> FallibleResult {
@@ -1075,10 +1107,26 @@ impl Handle {
         &self,
         nodes: impl IntoIterator<Item = node::Id>,
         new_method_name_base: &str,
+    ) -> FallibleResult<node::Id> {
+        self.collapse_with_parameter_names(nodes, new_method_name_base, &default())
+    }
+
+    /// Collapses the selected nodes, naming the parameters of the new method as given.
+    ///
+    /// The `parameter_names` map the identifiers used by the collapsed nodes from the outside
+    /// scope (see [`Self::source_identifier`]) to the names of the corresponding parameters. The
+    /// other parameters are named after their identifiers. Fails if the names are not unique or
+    /// collide with the names used in the collapsed nodes.
+    #[profile(Task)]
+    pub fn collapse_with_parameter_names(
+        &self,
+        nodes: impl IntoIterator<Item = node::Id>,
+        new_method_name_base: &str,
+        parameter_names: &HashMap<Identifier, Identifier>,
     ) -> FallibleResult<node::Id> {
         let _transaction_guard = self.get_or_open_transaction("Collapse nodes");
         analytics::remote_log_event("graph::collapse");
-        use double_representation::refactorings::collapse::collapse;
+        use double_representation::refactorings::collapse::collapse_with_parameter_names;
         use double_representation::refactorings::collapse::Collapsed;
         let nodes = nodes.into_iter().map(|id| self.node(id)).collect::<Result<Vec<_>, _>>()?;
         info!("Collapsing {nodes:?}.");
@@ -1091,7 +1139,14 @@ impl Handle {
         let node_ids = nodes.iter().map(|node| node.info.id());
         let graph = self.graph_info()?;
         let module_name = self.module.name().to_owned();
-        let collapsed = collapse(&graph, node_ids, introduced_name, &self.parser, module_name)?;
+        let collapsed = collapse_with_parameter_names(
+            &graph,
+            node_ids,
+            introduced_name,
+            parameter_names,
+            &self.parser,
+            module_name,
+        )?;
         let Collapsed { new_method, updated_definition, collapsed_node } = collapsed;
 
         let graph = self.graph_info()?;
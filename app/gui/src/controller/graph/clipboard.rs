@@ -12,6 +12,10 @@
 //!
 //! To copy the node as plain text, the user can enter the editing node, select the node expression,
 //! and copy it to the clipboard using the [`ensogl::Text`] functionality.
+//!
+//! The content pasted from other applications onto the graph canvas is classified by the view (e.g.
+//! tabular data or file URLs) and passed as the [`PastedContent`], created instead of the node in
+//! our custom format if the latter is not present in the clipboard.
 
 use crate::prelude::*;
 
@@ -19,7 +23,10 @@ use crate::controller::graph::Handle;
 use crate::controller::graph::NewNodeInfo;
 use crate::model::module::NodeMetadata;
 
+use double_representation::name::QualifiedName;
+
 use ensogl::system::web::clipboard;
+use parser::Parser;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -38,6 +45,8 @@ use serde::Serialize;
 const MIME_TYPE: &str = "web application/enso";
 /// Whether to allow pasting nodes from plain text.
 const PLAIN_TEXT_PASTING_ENABLED: bool = true;
+/// The vertical distance between the nodes pasted at once, e.g. when pasting several file URLs.
+const PASTED_NODES_GAP: f32 = 48.0;
 
 
 
@@ -51,6 +60,10 @@ const PLAIN_TEXT_PASTING_ENABLED: bool = true;
 )]
 pub struct InvalidFormatError;
 
+#[derive(Debug, Clone, PartialEq, failure::Fail)]
+#[fail(display = "The pasted text `{}` is not a single-line node expression.", _0)]
+pub struct InvalidPastedExpression(String);
+
 /// Clipboard payload.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 enum ClipboardContent {
@@ -89,6 +102,49 @@ pub fn paste_node(graph: &Handle, position: Vector2, on_error: fn(String)) {
     );
 }
 
+/// The nodes to be created from the content pasted from other applications.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PastedContent {
+    /// The expressions of the created nodes, placed one below another.
+    pub expressions:     Vec<String>,
+    /// The entity which must be imported for the expressions to work.
+    pub required_import: Option<QualifiedName>,
+}
+
+impl PastedContent {
+    /// Check whether all the expressions can be pasted as nodes, i.e. each of them is a single line
+    /// of valid code. The content is pasted only if all of them are valid, so no nodes are created
+    /// from a partially valid paste.
+    pub fn validate(&self, parser: &Parser) -> FallibleResult {
+        let invalid = self.expressions.iter().find(|expression| {
+            let is_single_line = expression.lines().count() == 1;
+            !is_single_line || parser.parse_line_ast(expression.as_str()).is_err()
+        });
+        match invalid {
+            Some(expression) => Err(InvalidPastedExpression(expression.clone()).into()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Paste the node from the clipboard at a specific position. If the clipboard does not contain
+/// a node in our custom format, the nodes of the `content` are created instead.
+///
+/// As pasting is an asynchronous operation, we need to provide a callback for handling possible
+/// errors.
+pub fn paste_node_or_content(
+    graph: &Handle,
+    position: Vector2,
+    content: PastedContent,
+    on_error: fn(String),
+) {
+    clipboard::read(
+        MIME_TYPE.to_string(),
+        paste_node_from_custom_format(graph, position, on_error),
+        pasted_content_fallback(graph, position, content, on_error),
+    );
+}
+
 /// A standard callback for pasting node using our custom format.
 fn paste_node_from_custom_format(
     graph: &Handle,
@@ -144,6 +200,38 @@ fn plain_text_fallback(
 }
 
 
+/// An alternative callback for pasting the [`PastedContent`]. It is used when [`MIME_TYPE`] is not
+/// available in the clipboard, and only if [`PLAIN_TEXT_PASTING_ENABLED`]. The plain text read
+/// from the clipboard is ignored, as it was already classified into the content.
+fn pasted_content_fallback(
+    graph: &Handle,
+    position: Vector2,
+    content: PastedContent,
+    on_error: impl Fn(String) + 'static,
+) -> impl Fn(String) + 'static {
+    let graph = graph.clone_ref();
+    let closure = move || -> FallibleResult {
+        if PLAIN_TEXT_PASTING_ENABLED {
+            content.validate(&graph.parser)?;
+            let _transaction = graph.module.get_or_open_transaction("Paste node");
+            if let Some(import) = content.required_import.clone() {
+                graph.add_import_if_missing(import)?;
+            }
+            for (index, expression) in content.expressions.iter().enumerate() {
+                let position = position - Vector2(0.0, index as f32 * PASTED_NODES_GAP);
+                graph.new_node_at_position(position, expression.clone(), None)?;
+            }
+        }
+        Ok(())
+    };
+    move |_text| {
+        if let Err(err) = closure() {
+            on_error(format!("Failed to paste node. {err}"));
+        }
+    }
+}
+
+
 
 // ===============
 // === Helpers ===
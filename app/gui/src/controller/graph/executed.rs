@@ -16,6 +16,7 @@ use crate::model::execution_context::VisualizationId;
 use crate::model::execution_context::VisualizationUpdateData;
 use crate::retry::retry_operation_errors_cap;
 
+use double_representation::identifier::Identifier;
 use double_representation::name::QualifiedName;
 use engine_protocol::language_server::ExecutionEnvironment;
 use engine_protocol::language_server::MethodPointer;
@@ -466,6 +467,12 @@ impl Handle {
         }
     }
 
+    /// Get the identifier by which the source endpoint is referred to in the graph. See
+    /// [`controller::Graph::source_identifier`].
+    pub fn source_identifier(&self, source: &Endpoint) -> FallibleResult<Identifier> {
+        self.graph.borrow().source_identifier(source, self)
+    }
+
     /// Remove the connections from the graph.
     ///
     /// ### Errors
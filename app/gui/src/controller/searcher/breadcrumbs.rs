@@ -81,6 +81,7 @@ pub struct BreadcrumbEntry {
     component_id:   suggestion_database::entry::Id,
     qualified_name: QualifiedName,
     icon:           Option<icon::Id>,
+    kind:           suggestion_database::entry::Kind,
 }
 
 impl BreadcrumbEntry {
@@ -113,6 +114,23 @@ impl BreadcrumbEntry {
     pub fn view_with_icon(&self) -> ensogl_breadcrumbs::Breadcrumb {
         ensogl_breadcrumbs::Breadcrumb::new(self.name().as_str(), self.icon())
     }
+
+    /// Return a [`ensogl_breadcrumbs::Breadcrumb`] with the entries name and the icon of the
+    /// entry's kind, showing what kind of scope the entry is.
+    pub fn view_with_kind_icon(&self) -> ensogl_breadcrumbs::Breadcrumb {
+        ensogl_breadcrumbs::Breadcrumb::new_with_kind(self.name().as_str(), self.breadcrumb_kind())
+    }
+
+    /// The kind of the breadcrumb representing the entry.
+    pub fn breadcrumb_kind(&self) -> ensogl_breadcrumbs::Kind {
+        use suggestion_database::entry::Kind;
+        match self.kind {
+            Kind::Module => ensogl_breadcrumbs::Kind::Module,
+            Kind::Type => ensogl_breadcrumbs::Kind::Type,
+            Kind::Constructor | Kind::Method | Kind::Function => ensogl_breadcrumbs::Kind::Function,
+            Kind::Local => ensogl_breadcrumbs::Kind::Local,
+        }
+    }
 }
 
 impl From<(suggestion_database::entry::Id, Rc<Entry>)> for BreadcrumbEntry {
@@ -120,7 +138,8 @@ impl From<(suggestion_database::entry::Id, Rc<Entry>)> for BreadcrumbEntry {
         let qualified_name = entry.qualified_name();
         let displayed_name = entry.name.clone();
         let icon = Some(entry.as_ref().icon());
-        BreadcrumbEntry { displayed_name, component_id, qualified_name, icon }
+        let kind = entry.kind;
+        BreadcrumbEntry { displayed_name, component_id, qualified_name, icon, kind }
     }
 }
 
@@ -40,6 +40,11 @@ pub struct ChecksumMismatch {
 // =================
 
 const DATA_DIR_NAME: &str = "data";
+/// The placeholder in the reader templates standing for the expression of the uploaded file's path.
+/// It is the same as the placeholder of the templates configured in the graph editor.
+pub const PATH_PLACEHOLDER: &str = "{path}";
+/// The expression of the node reading the uploaded file, used if no other template was given.
+pub const DEFAULT_READER_TEMPLATE: &str = "{path} . read";
 
 
 
@@ -177,14 +182,24 @@ impl<DP: DataProvider> FileUploadProcess<DP> {
 /// It is responsible for creating node, uploading file and updating the node's metadata.
 #[derive(Clone, CloneRef, Debug)]
 pub struct NodeFromDroppedFileHandler {
-    project: model::Project,
-    graph:   controller::Graph,
+    project:         model::Project,
+    graph:           controller::Graph,
+    /// The expression of the node once the file is uploaded, with the [`PATH_PLACEHOLDER`].
+    reader_template: ImString,
 }
 
 impl NodeFromDroppedFileHandler {
     /// Constructor
     pub fn new(project: model::Project, graph: controller::Graph) -> Self {
-        Self { project, graph }
+        let reader_template = DEFAULT_READER_TEMPLATE.into();
+        Self { project, graph, reader_template }
+    }
+
+    /// Set the expression of the node once the file is uploaded. The [`PATH_PLACEHOLDER`] in the
+    /// template is replaced with the expression of the uploaded file's path.
+    pub fn with_reader_template(mut self, template: impl Into<ImString>) -> Self {
+        self.reader_template = template.into();
+        self
     }
 
     /// Create a node from dropped file and start uploading file.
@@ -264,7 +279,8 @@ impl NodeFromDroppedFileHandler {
         while process.upload_chunk().await? == UploadingState::NotFinished {
             self.update_metadata(node, |md| md.bytes_uploaded = process.bytes_uploaded);
         }
-        self.update_expression(node, Self::uploaded_node_expression(&remote_name))?;
+        let expression = Self::uploaded_node_expression(&self.reader_template, &remote_name);
+        self.update_expression(node, expression)?;
         if let Err(err) =
             self.graph.module.with_node_metadata(node, Box::new(|md| md.uploading_file = None))
         {
@@ -331,8 +347,8 @@ impl NodeFromDroppedFileHandler {
         format!("File_Uploading.file_uploading Enso_Project.data/\"{name}\"")
     }
 
-    fn uploaded_node_expression(name: &str) -> String {
-        format!("enso_project.data/\"{name}\" . read")
+    fn uploaded_node_expression(template: &str, name: &str) -> String {
+        template.replace(PATH_PLACEHOLDER, &format!("enso_project.data/\"{name}\""))
     }
 
     fn data_path(&self) -> Path {
@@ -595,6 +611,16 @@ mod test {
         assert_eq!(fixture.module.ast().repr(), module_code_uploaded(TEST_FILE));
     }
 
+    #[test]
+    fn reader_templates() {
+        let expression = NodeFromDroppedFileHandler::uploaded_node_expression;
+        let expected = "enso_project.data/\"sales.csv\" . read";
+        assert_eq!(expression(DEFAULT_READER_TEMPLATE, "sales.csv"), expected);
+        let template = "Data.read {path} (Delimited ',')";
+        let expected = "Data.read enso_project.data/\"sales.csv\" (Delimited ',')";
+        assert_eq!(expression(template, "sales.csv"), expected);
+    }
+
     #[test]
     fn recreating_data_directory() {
         let mut fixture = mock::Unified::new().fixture_customize(|_, json_rpc, _| {
@@ -344,6 +344,20 @@ impl Manager {
         })
     }
 
+    /// Check whether the data received from the visualization are up to date: the visualization is
+    /// attached, and no other visualization or preprocessor was requested since. The data received
+    /// while the visualization is being modified may be computed with the previous preprocessor,
+    /// so they should be dropped.
+    fn is_up_to_date(&self, target: ast::Id, visualization_id: VisualizationId) -> bool {
+        let Some(description) = self.visualizations.get_cloned(&target) else { return false };
+        let desired = description.desired.and_then(|d| self.prepare_visualization(d).ok());
+        match description.status.get_cloned() {
+            Status::Attached(attached) =>
+                attached.id == visualization_id && desired.contains(&attached),
+            _ => false,
+        }
+    }
+
     /// Remove (set desired state to None) each visualization not attached to any of the `targets`.
     pub fn retain_visualizations(self: &Rc<Self>, targets: &HashSet<ast::Id>) {
         let to_remove = self.visualizations.keys().into_iter().filter(|id| !targets.contains(id));
@@ -407,9 +421,16 @@ impl Manager {
                 let visualization_id = new_visualization.id;
                 let status = Status::Attached(new_visualization);
                 self.update_status(target, status);
+                let weak = Rc::downgrade(&self);
                 spawn(update_receiver.for_each(move |data| {
-                    let notification = Notification::ValueUpdate { target, visualization_id, data };
-                    let _ = notifier.unbounded_send(notification);
+                    let this = weak.upgrade();
+                    if this.map_or(false, |this| this.is_up_to_date(target, visualization_id)) {
+                        let notification =
+                            Notification::ValueUpdate { target, visualization_id, data };
+                        let _ = notifier.unbounded_send(notification);
+                    } else {
+                        debug!("Dropping a stale update of visualization {visualization_id}.");
+                    }
                     ready(())
                 }))
             }
@@ -143,12 +143,13 @@ impl Fixture {
         profiler::join(self.compile_new_shaders(), self.backend_execution()).await;
     }
 
-    /// Collapse the selected nodes; doesn't complete until the action is completed and the graph
-    /// has been recolored.
+    /// Collapse the selected nodes, confirming the collapse preview right away; doesn't complete
+    /// until the action is completed and the graph has been recolored.
     #[profile(Objective)]
     pub async fn collapse_selected_nodes(&self) -> crate::view::graph_editor::NodeId {
         let expect_node_added = self.graph_editor().node_added.next_event();
         self.graph_editor().collapse_selected_nodes();
+        self.graph_editor().confirm_collapse_preview();
         profiler::join(self.compile_new_shaders(), self.backend_execution()).await;
         expect_node_added.expect().0
     }
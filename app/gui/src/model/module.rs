@@ -362,7 +362,11 @@ impl Default for Metadata {
 pub struct ProjectMetadata {
     /// The execution context of the displayed graph editor.
     #[serde(default, deserialize_with = "enso_prelude::deserialize_or_default")]
-    pub call_stack: Vec<model::execution_context::LocalCall>,
+    pub call_stack:  Vec<model::execution_context::LocalCall>,
+    /// The layout of the panels docked at the edges of the project view. Exact format is defined
+    /// by the integration layer.
+    #[serde(default)]
+    pub dock_layout: serde_json::Value,
 }
 
 /// Metadata that belongs to ide.
@@ -430,6 +434,10 @@ pub struct NodeMetadata {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default, deserialize_with = "enso_prelude::deserialize_or_default")]
     pub edit_status:     Option<NodeEditStatus>,
+    /// The width of the node, if it was resized by the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "enso_prelude::deserialize_or_default")]
+    pub width:           Option<f32>,
 }
 
 /// Used for storing node position.
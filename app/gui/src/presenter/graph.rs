@@ -13,12 +13,21 @@ use crate::presenter::graph::state::State;
 use double_representation::context_switch::Context;
 use double_representation::context_switch::ContextSwitch;
 use double_representation::context_switch::ContextSwitchExpression;
+use double_representation::graph::GraphInfo;
+use double_representation::identifier::Identifier;
 use engine_protocol::language_server::ExpressionUpdatePayload;
 use enso_frp as frp;
+use double_representation::name::QualifiedName;
 use futures::future::LocalBoxFuture;
 use ide_view as view;
+use ide_view::graph_editor::clipboard::ClipboardContent;
+use ide_view::graph_editor::component::collapse_preview;
+use ide_view::graph_editor::component::file_drop::ExpressionTemplate;
+use ide_view::graph_editor::component::node_peek;
+use ide_view::graph_editor::component::node_search::MatchKind;
 use ide_view::graph_editor::component::node as node_view;
 use ide_view::graph_editor::component::visualization as visualization_view;
+use ide_view::search_everywhere::SearchResult;
 use span_tree::generate::Context as _;
 use view::graph_editor::CallWidgetsConfig;
 use view::notification::logged as notification;
@@ -81,6 +90,22 @@ pub fn default_node_position() -> Vector2 {
 
 
 
+// ==============
+// === Errors ===
+// ==============
+
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug, Fail)]
+#[fail(display = "The names of the collapsed function's parameters are invalid or not unique.")]
+pub struct InvalidParameterNames;
+
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug, Fail)]
+#[fail(display = "The argument of the collapsed function comes from an unknown node {:?}.", _0)]
+pub struct ParameterSourceNotFound(ViewNodeId);
+
+
+
 // =============
 // === Model ===
 // =============
@@ -94,6 +119,9 @@ struct Model {
     _visualization:   Visualization,
     widget:           controller::Widget,
     _execution_stack: CallStack,
+    /// The search result chosen in a method other than the displayed one. Its node is focused
+    /// once the method is entered.
+    search_target:    RefCell<Option<SearchResult>>,
 }
 
 impl Model {
@@ -125,6 +153,7 @@ impl Model {
             _visualization: visualization,
             widget,
             _execution_stack: execution_stack,
+            search_target: default(),
         }
     }
 
@@ -157,6 +186,17 @@ impl Model {
         );
     }
 
+    fn node_width_changed(&self, id: ViewNodeId, width: Option<f32>) {
+        self.log_action(
+            || {
+                let ast_id = self.state.update_from_view().set_node_width(id, width)?;
+                let module = self.controller.graph().module;
+                Some(module.with_node_metadata(ast_id, Box::new(move |md| md.width = width)))
+            },
+            "update node width",
+        );
+    }
+
     /// Update a part of node expression under specific span tree crumbs. Preserves identity of
     /// unaffected parts of the expression.
     fn node_expression_span_set(
@@ -256,11 +296,28 @@ impl Model {
         );
     }
 
+    /// The user skipped all the selected nodes at once. The nodes are updated in a single
+    /// transaction, so they can be undone together.
+    fn nodes_action_skip(&self, ids: &[ViewNodeId], enabled: bool) {
+        let _transaction = self.controller.get_or_open_transaction("Skip nodes");
+        for id in ids {
+            self.node_action_skip(*id, enabled);
+        }
+    }
+
+    /// The user froze all the selected nodes at once. The nodes are updated in a single
+    /// transaction, so they can be undone together.
+    fn nodes_action_freeze(&self, ids: &[ViewNodeId], enabled: bool) {
+        let _transaction = self.controller.get_or_open_transaction("Freeze nodes");
+        for id in ids {
+            self.node_action_freeze(*id, enabled);
+        }
+    }
+
     fn add_import_if_missing(&self, import_path: &str) {
         self.log_action(
             || {
-                let qualified_name =
-                    double_representation::name::QualifiedName::from_text(import_path);
+                let qualified_name = QualifiedName::from_text(import_path);
                 let result = qualified_name
                     .and_then(|name| self.controller.graph().add_import_if_missing(name));
                 Some(result)
@@ -327,12 +384,28 @@ impl Model {
         )
     }
 
-    fn nodes_collapsed(&self, collapsed: &[ViewNodeId]) {
+    fn nodes_collapsed(&self, collapsed: &[ViewNodeId], signature: &collapse_preview::Signature) {
         self.log_action(
             || {
                 debug!("Collapsing node.");
+                if !signature.is_valid() {
+                    return Some(Err(InvalidParameterNames.into()));
+                }
                 let ids = collapsed.iter().filter_map(|node| self.state.ast_node_id_of_view(*node));
-                let new_node_id = self.controller.graph().collapse(ids, COLLAPSED_FUNCTION_NAME);
+                let parameter_names = signature.renamed_parameters().map(|parameter| {
+                    let source = parameter.source;
+                    let source_node = self.state.ast_node_id_of_view(source.node_id);
+                    let source_node = source_node.ok_or(ParameterSourceNotFound(source.node_id))?;
+                    let source = AstEndpoint::new(source_node, source.port);
+                    let identifier = self.controller.source_identifier(&source)?;
+                    let name = Identifier::from_text(parameter.name.to_string())?;
+                    Ok((identifier, name))
+                });
+                let parameter_names = parameter_names.collect::<FallibleResult<HashMap<_, _>>>();
+                let graph = self.controller.graph();
+                let new_node_id = parameter_names.and_then(|names| {
+                    graph.collapse_with_parameter_names(ids, COLLAPSED_FUNCTION_NAME, &names)
+                });
                 // TODO [mwu] https://github.com/enso-org/ide/issues/760
                 //   As part of this issue, storing relation between new node's controller and view
                 //   ids will be necessary.
@@ -424,6 +497,15 @@ impl Model {
         self.state.update_from_controller().set_node_error_from_payload(expression, payload)
     }
 
+    fn refresh_node_warnings(
+        &self,
+        expression: ast::Id,
+    ) -> Option<(ViewNodeId, Rc<Vec<node_view::warnings::Warning>>)> {
+        let registry = self.controller.computed_value_info_registry();
+        let payload = registry.get(&expression).map(|info| info.payload.clone());
+        self.state.update_from_controller().set_node_warnings_from_payload(expression, payload)
+    }
+
     fn refresh_node_pending(&self, expression: ast::Id) -> Option<(ViewNodeId, bool)> {
         let registry = self.controller.computed_value_info_registry();
         let is_pending = registry
@@ -433,6 +515,29 @@ impl Model {
         self.state.update_from_controller().set_node_pending(expression, is_pending)
     }
 
+    /// The view of the node whose whole expression was recomputed by the engine, i.e. its update
+    /// is not pending.
+    fn node_recomputed(&self, expression: ast::Id) -> Option<ViewNodeId> {
+        let registry = self.controller.computed_value_info_registry();
+        let info = registry.get(&expression)?;
+        let is_pending = matches!(info.payload, ExpressionUpdatePayload::Pending { .. });
+        if is_pending {
+            None
+        } else {
+            self.state.view_id_of_ast_node(expression)
+        }
+    }
+
+    fn refresh_node_method_pointer(
+        &self,
+        expression: ast::Id,
+    ) -> Option<(ViewNodeId, Option<view::graph_editor::MethodPointer>)> {
+        let view_id = self.state.view_id_of_ast_node(expression)?;
+        let registry = self.controller.computed_value_info_registry();
+        let method_pointer = registry.get(&expression).and_then(|info| info.method_call.clone());
+        Some((view_id, method_pointer.map(Into::into)))
+    }
+
     /// Extract the expression's current type from controllers.
     fn expression_type(&self, id: ast::Id) -> Option<view::graph_editor::Type> {
         let registry = self.controller.computed_value_info_registry();
@@ -440,7 +545,12 @@ impl Model {
         Some(view::graph_editor::Type(info.typename.as_ref()?.clone_ref()))
     }
 
-    fn file_dropped(&self, file: ensogl_drop_manager::File, position: Vector2<f32>) {
+    fn file_dropped(
+        &self,
+        file: ensogl_drop_manager::File,
+        position: Vector2<f32>,
+        template: Option<ExpressionTemplate>,
+    ) {
         let project = self.project.clone_ref();
         let graph = self.controller.graph();
         let to_upload = controller::upload::FileToUpload {
@@ -449,7 +559,10 @@ impl Model {
             data: file,
         };
         let position = model::module::Position { vector: position };
-        let handler = NodeFromDroppedFileHandler::new(project, graph);
+        let mut handler = NodeFromDroppedFileHandler::new(project, graph);
+        if let Some(ExpressionTemplate(template)) = template {
+            handler = handler.with_reader_template(template);
+        }
         if let Err(err) = handler.create_node_and_start_uploading(to_upload, position) {
             error!("Error when creating node from dropped file: {err}");
         }
@@ -463,6 +576,18 @@ impl Model {
         self.controller.graph().paste_node(cursor_pos, on_error);
     }
 
+    fn paste_content(&self, content: &ClipboardContent, position: Vector2) {
+        fn on_error(msg: String) {
+            error!("Error when pasting node. {}", msg);
+            notification::error(msg, &None);
+        }
+        let required_import =
+            content.required_import().and_then(|name| QualifiedName::from_text(name).ok());
+        let expressions = content.expressions();
+        let content = controller::graph::PastedContent { expressions, required_import };
+        self.controller.graph().paste_node_or_content(position, content, on_error);
+    }
+
     /// Look through all graph's nodes in AST and set position where it is missing.
     #[profile(Debug)]
     fn initialize_nodes_positions(&self, default_gap_between_nodes: f32) {
@@ -506,6 +631,95 @@ impl Model {
         }
     }
 
+    /// Find the nodes of the module's methods other than the displayed one whose expressions or
+    /// comments contain the query, ignoring the letter case. An empty query matches nothing.
+    fn search_other_methods(&self, query: &str) -> Vec<SearchResult> {
+        use double_representation::definition::DefinitionProvider;
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return default();
+        }
+        let graph = self.controller.graph();
+        let module_name = self.project.qualified_module_name(graph.module.path());
+        let module_ast = graph.module.ast();
+        let other_methods = module_ast.def_iter().filter(|definition| {
+            graph.id.crumbs.as_slice() != [definition.item.name.item.clone()]
+        });
+        let matches = |text: &str| text.to_lowercase().contains(&query);
+        let mut results = vec![];
+        for definition in other_methods {
+            let method: ImString = format!("{module_name}.{}", definition.item.name.item).into();
+            let result = |kind, text| SearchResult {
+                method: Some(method.clone()),
+                node_id: None,
+                kind,
+                text,
+            };
+            for node in GraphInfo::from_definition(definition.item).nodes() {
+                let expression = node.expression().repr();
+                if matches(&expression) {
+                    results.push(result(MatchKind::Expression, expression.into()));
+                }
+                if let Some(comment) = node.documentation_text().filter(|c| matches(c)) {
+                    results.push(result(MatchKind::Comment, comment));
+                }
+            }
+        }
+        results
+    }
+
+    /// Enter the method of the search result through a node of the displayed graph calling it.
+    /// Returns the calling node, or [`None`] if no node calls the method.
+    fn enter_search_result(&self, result: &SearchResult) -> Option<ViewNodeId> {
+        let method = result.method.as_ref()?;
+        let registry = self.controller.computed_value_info_registry();
+        let nodes = self.controller.graph().nodes().ok()?;
+        let calls_method = |id: &ast::Id| {
+            let pointer = registry.get(id).and_then(|info| info.method_call.clone());
+            pointer.map_or(false, |pointer| {
+                // The module methods are defined on the module itself.
+                format!("{}.{}", pointer.defined_on_type, pointer.name) == method.as_str()
+            })
+        };
+        let call = nodes.iter().map(|node| node.info.id()).find(calls_method);
+        let view_id = call.and_then(|call| self.state.view_id_of_ast_node(call));
+        if view_id.is_some() {
+            self.search_target.replace(Some(result.clone()));
+        } else {
+            warn!("Cannot enter {method}: no node in the displayed graph calls it.");
+        }
+        view_id
+    }
+
+    /// Check whether the newly displayed node is the node of the search result chosen before
+    /// entering its method. The search target is forgotten once found.
+    fn is_search_target(&self, view_id: ViewNodeId) -> bool {
+        let ast_id = self.state.ast_node_id_of_view(view_id);
+        let node = ast_id.and_then(|id| self.controller.graph().node_info(id).ok());
+        let is_target = self.search_target.borrow().as_ref().zip(node).map_or(false, |(t, n)| {
+            match t.kind {
+                MatchKind::Expression => n.expression().repr() == t.text.as_str(),
+                MatchKind::Comment => n.documentation_text().as_ref() == Some(&t.text),
+            }
+        });
+        if is_target {
+            self.search_target.take();
+        }
+        is_target
+    }
+
+    fn peek_node(&self, node: ViewNodeId, method: &view::graph_editor::MethodPointer) {
+        let project = self.project.clone_ref();
+        let view = self.view.clone_ref();
+        let method = method.0.clone_ref();
+        executor::global::spawn(async move {
+            match peeked_graph(&project, &method).await {
+                Ok(graph) => view.set_node_peek((node, Rc::new(graph))),
+                Err(err) => error!("Cannot peek into {node:?}: {err}"),
+            }
+        });
+    }
+
     fn reopen_file_in_ls(&self) {
         let module = self.controller.graph().module.clone_ref();
         executor::global::spawn(async move {
@@ -517,6 +731,29 @@ impl Model {
 }
 
 
+/// Read the graph of the method for displaying it in the node peek overlay. The nodes without a
+/// position in the metadata are placed in a column below the origin.
+async fn peeked_graph(
+    project: &model::Project,
+    method: &engine_protocol::language_server::MethodPointer,
+) -> FallibleResult<node_peek::PeekedGraph> {
+    const DEFAULT_NODE_SPACING: f32 = 40.0;
+    let graph = controller::Graph::new_method(project, method).await?;
+    let nodes = graph.nodes()?;
+    let connections = graph.connections(&span_tree::generate::context::Empty)?;
+    let index_of = |id: ast::Id| nodes.iter().position(|node| node.id() == id);
+    let connections = connections.connections.iter().filter_map(|connection| {
+        Some((index_of(connection.source.node)?, index_of(connection.target.node)?))
+    });
+    let connections = connections.collect();
+    let nodes = nodes.iter().enumerate().map(|(index, node)| {
+        let default_position = || Vector2::new(0.0, -DEFAULT_NODE_SPACING * index as f32);
+        let position = node.position().map_or_else(default_position, |position| position.vector);
+        node_peek::PeekedNode { expression: node.info.expression().repr().into(), position }
+    });
+    Ok(node_peek::PeekedGraph { nodes: nodes.collect(), connections })
+}
+
 
 // ==================
 // === ViewUpdate ===
@@ -663,6 +900,17 @@ impl ViewUpdate {
             .collect()
     }
 
+    #[profile(Debug)]
+    fn set_node_widths(&self) -> Vec<(ViewNodeId, Option<f32>)> {
+        self.nodes
+            .iter()
+            .filter_map(|node| {
+                let width = node.metadata.as_ref().and_then(|md| md.width);
+                self.state.update_from_controller().set_node_width(node.id(), width)
+            })
+            .collect()
+    }
+
     /// Get all current connections from the updated state, and return them in a form suitable for
     /// passing to the Graph Editor view.
     #[profile(Debug)]
@@ -749,6 +997,7 @@ impl Graph {
             set_node_visualization <= update_data.map(|update| update.set_node_visualizations());
             enable_vis <- set_node_visualization.filter_map(|(id,path)| path.is_some().as_some(*id));
             disable_vis <- set_node_visualization.filter_map(|(id,path)| path.is_none().as_some(*id));
+            set_node_width <= update_data.map(|update| update.set_node_widths());
             view.remove_node <+ remove_node;
             view.set_node_expression <+ update_node_expression;
             view.set_node_skip <+ set_node_skip;
@@ -758,6 +1007,7 @@ impl Graph {
             view.set_visualization <+ set_node_visualization;
             view.enable_visualization <+ enable_vis;
             view.disable_visualization <+ disable_vis;
+            view.set_node_width_override <+ set_node_width;
 
             view.add_node <+ update_data.map(|update| update.count_nodes_to_add()).repeat();
             added_node_update <- view.node_added.filter_map(f!(((view_id, _, _))
@@ -768,6 +1018,11 @@ impl Graph {
             view.set_node_position <+ added_node_update.filter_map(|update| Some((update.view_id?, update.position)));
             view.set_visualization <+ added_node_update.filter_map(|update| Some((update.view_id?, Some(update.visualization.clone()?))));
             view.enable_visualization <+ added_node_update.filter_map(|update| update.visualization.is_some().and_option(update.view_id));
+            init_node_width <- added_node_update.filter_map(|update|
+                Some((update.view_id?, update.width))
+            );
+            view.set_node_width_override <+ init_node_width;
+            view.set_node_stable_id <+ view.node_added.filter_map(f!(((view_id, _, _)) Some((*view_id, model.state.ast_node_id_of_view(*view_id)?))));
 
 
             // === Refreshing Connections ===
@@ -784,7 +1039,12 @@ impl Graph {
             update_expression <= update_expressions;
             view.set_expression_usage_type <+ update_expression.filter_map(f!((id) model.refresh_expression_type(*id)));
             view.set_node_error_status <+ update_expression.filter_map(f!((id) model.refresh_node_error(*id)));
-            view.set_node_pending_status <+ update_expression.filter_map(f!((id) model.refresh_node_pending(*id)));
+            node_pending <- update_expression.filter_map(f!((id) model.refresh_node_pending(*id)));
+            view.set_node_pending_status <+ node_pending;
+            view.set_node_pending <+ node_pending;
+            view.set_node_warnings <+ update_expression.filter_map(f!((id) model.refresh_node_warnings(*id)));
+            view.set_node_method_pointer <+ update_expression.filter_map(f!((id) model.refresh_node_method_pointer(*id)));
+            view.clear_node_stale <+ update_expression.filter_map(f!((id) model.node_recomputed(*id)));
 
             self.init_widgets(reset_node_types, update_expression.clone_ref());
 
@@ -793,23 +1053,57 @@ impl Graph {
             eval view.node_copied((node_id) model.node_copied(*node_id));
             eval view.node_position_set_batched(((node_id, position)) model.node_position_changed(*node_id, *position));
             eval view.node_removed((node_id) model.node_removed(*node_id));
-            eval view.nodes_collapsed(((nodes, _)) model.nodes_collapsed(nodes));
+            eval view.nodes_collapsed(((nodes, signature)) model.nodes_collapsed(nodes, signature));
+            eval view.node_peek_requested(((node, method)) model.peek_node(*node, method));
             eval view.enabled_visualization_path(((node_id, path)) model.node_visualization_changed(*node_id, path.clone()));
+            eval view.node_width_overridden(((id, width)) model.node_width_changed(*id, *width));
             eval view.node_expression_span_set(((node_id, crumbs, expression)) model.node_expression_span_set(*node_id, crumbs, expression.clone_ref()));
+            // The values computed before the edit are outdated until the engine recomputes them.
+            view.mark_subtree_stale <+ view.node_expression_span_set._0();
+            view.mark_subtree_stale <+ view.connection_made.map(|c| c.target.node_id);
+            view.mark_subtree_stale <+ view.connection_broken.map(|c| c.target.node_id);
             eval view.connection_made((connection) model.connection_made(connection));
             eval view.connection_broken((connection) model.connection_broken(connection));
             eval view.node_action_context_switch(((node_id, active)) model.node_action_context_switch(*node_id, *active));
             eval view.node_action_skip(((node_id, enabled)) model.node_action_skip(*node_id, *enabled));
             eval view.node_action_freeze(((node_id, enabled)) model.node_action_freeze(*node_id, *enabled));
+            eval view.nodes_action_skip(((node_ids, enabled)) model.nodes_action_skip(node_ids, *enabled));
+            eval view.nodes_action_freeze(((node_ids, enabled)) model.nodes_action_freeze(node_ids, *enabled));
             eval view.request_import((import_path) model.add_import_if_missing(import_path));
             eval_ view.reopen_file_in_language_server (model.reopen_file_in_ls());
 
+            // === Search Everywhere ===
+
+            let search = project_view.search_everywhere();
+            search.set_results <+ search.query.map(
+                f!((query) Rc::new(model.search_other_methods(query)))
+            );
+            search_call <- search.result_chosen.filter_map(
+                f!((result) model.enter_search_result(result))
+            );
+            view.deselect_all_nodes <+ search_call.constant(());
+            view.select_node <+ search_call;
+            view.enter_selected_node <+ search_call.constant(());
+            search_target <- view.node_added._0().filter(f!((id) model.is_search_target(*id)));
+            view.select_node <+ search_target;
+            view.pan_camera_to_node <+ search_target;
+
+            // When the user clicks the frame of the error's stack trace, we focus on the node
+            // containing the expression, if it is in the displayed graph.
+            error_frame_node <- view.error_frame_clicked.filter_map(f!((id) model.state.view_id_of_expression(*id)));
+            view.deselect_all_nodes <+ error_frame_node.constant(());
+            view.select_node <+ error_frame_node;
+            view.pan_camera_to_node <+ error_frame_node;
+
 
             // === Dropping Files and Pasting Node ===
 
             eval view.request_paste_node((pos) model.paste_node(*pos));
+            eval view.paste_requested (((content, pos)) model.paste_content(content, *pos));
             file_upload_requested <- view.file_dropped.gate(&project_view.drop_files_enabled);
-            eval file_upload_requested (((file,position)) model.file_dropped(file.clone_ref(),*position));
+            eval file_upload_requested (((file, position, template))
+                model.file_dropped(file.clone_ref(), *position, template.clone())
+            );
         }
 
         // Forcefully update the view to match the state of the presenter.
@@ -830,12 +1124,18 @@ impl Graph {
             view.enable_visualization <+ enable_vis;
             view.disable_visualization <+ disable_vis;
             view.set_visualization <+ vis_update;
+            view.set_node_width_override <+ displayed_node.map(Node::width_update).unwrap();
             expression_type <- node_and_ast_id.filter_map(f!([model]((node, ast))
                 Some((node.view_id?, *ast, model.expression_type(*ast))))
             );
             view.set_expression_usage_type <+ expression_type;
             view.set_node_error_status <+ displayed_node.map(Node::error_update).unwrap();
-            view.set_node_pending_status <+ displayed_node.map(Node::pending_update).unwrap();
+            displayed_node_pending <- displayed_node.map(Node::pending_update).unwrap();
+            view.set_node_pending_status <+ displayed_node_pending;
+            view.set_node_pending <+ displayed_node_pending;
+            view.set_node_warnings <+ displayed_node.map(Node::warnings_update).unwrap();
+            ast_id <- node_and_ast_id._1();
+            view.set_node_method_pointer <+ ast_id.filter_map(f!((id) model.refresh_node_method_pointer(*id)));
         }
 
         view.remove_all_nodes();
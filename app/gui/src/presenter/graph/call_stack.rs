@@ -10,6 +10,7 @@ use crate::presenter::graph::ViewNodeId;
 use enso_frp as frp;
 use ensogl_breadcrumbs::Breadcrumb;
 use ide_view as view;
+use ide_view::graph_editor::component::tab_bar::TabId;
 
 
 
@@ -25,6 +26,12 @@ struct Model {
     // Full stack displayed in the breadcrumbs. Includes the deeper levels, that might not be
     // active due to the current selection.
     stack_history: Rc<RefCell<Vec<view::project_view_top_bar::LocalCall>>>,
+    // The call stack of the graph displayed in each tab of the graph editor's tab bar.
+    tabs:          RefCell<HashMap<TabId, Vec<view::project_view_top_bar::LocalCall>>>,
+    current_tab:   Cell<Option<TabId>>,
+    // Set while the breadcrumbs are replaced with the stack of the selected tab, so the
+    // breadcrumbs selection does not modify the call stack on its own.
+    switching_tab: Cell<bool>,
 }
 
 impl Model {
@@ -34,7 +41,11 @@ impl Model {
         view: view::project::View,
         state: Rc<State>,
     ) -> Self {
-        Self { controller, view, state, stack_history: default() }
+        let stack_history = default();
+        let tabs = default();
+        let current_tab = default();
+        let switching_tab = default();
+        Self { controller, view, state, stack_history, tabs, current_tab, switching_tab }
     }
 
     /// Initialize the breadcrumbs view. Initially there is only the main module.
@@ -93,6 +104,13 @@ impl Model {
         self.view.top_bar().breadcrumbs.set_entries_from((stack, breadcrumb_index));
     }
 
+    /// Replace the whole content of the breadcrumbs with the given stack.
+    fn reset_stack_levels(&self, stack: Vec<view::project_view_top_bar::LocalCall>) {
+        self.stack_history.borrow_mut().clear();
+        self.init_breadcrumb_view();
+        self.add_stack_levels(stack, 1); // 1 because of the main module
+    }
+
     /// Move the selection in the breadcrumbs to the left. This will result in the controller
     /// exiting the stack by the given number of levels.
     fn shift_breadcrumb_selection_left(&self, count: usize) {
@@ -125,15 +143,7 @@ impl Model {
         if let Some(call) = self.state.ast_node_id_of_view(node_id) {
             if let Ok(computed_value) = self.controller.node_computed_value(call) {
                 if let Some(method_pointer) = computed_value.method_call.as_ref() {
-                    let local_call = LocalCall { call, definition: method_pointer.clone() };
-                    let stack_pointer = self.controller.call_stack().len();
-                    self.add_stack_levels(
-                        vec![view::project_view_top_bar::LocalCall {
-                            call:       local_call.call,
-                            definition: local_call.definition.into(),
-                        }],
-                        stack_pointer,
-                    );
+                    self.enter_local_call(LocalCall { call, definition: method_pointer.clone() });
                 } else {
                     debug!("Ignoring request to enter non-enterable node {call}.")
                 }
@@ -145,9 +155,46 @@ impl Model {
         }
     }
 
+    /// Method to call when the graph editor requests opening the definition of the method called
+    /// by the selected node. The method is entered as if the selected node was entered.
+    fn method_entered(
+        &self,
+        node_id: ViewNodeId,
+        method_pointer: &view::graph_editor::MethodPointer,
+    ) {
+        analytics::remote_log_event("integration::method_entered");
+        if let Some(call) = self.state.ast_node_id_of_view(node_id) {
+            let definition = (*method_pointer.0).clone();
+            let mut stack = to_local_call_stack(self.controller.call_stack());
+            stack.push(view::project_view_top_bar::LocalCall {
+                call,
+                definition: definition.clone().into(),
+            });
+            self.open_tab(stack);
+            self.enter_local_call(LocalCall { call, definition });
+        } else {
+            error!("Cannot enter definition called by {node_id:?}: no AST node bound to the view.")
+        }
+    }
+
+    fn enter_local_call(&self, local_call: LocalCall) {
+        let stack_pointer = self.controller.call_stack().len();
+        self.add_stack_levels(
+            vec![view::project_view_top_bar::LocalCall {
+                call:       local_call.call,
+                definition: local_call.definition.into(),
+            }],
+            stack_pointer,
+        );
+    }
+
     /// Method to call when a breadcrumb is selected. This will update the call stack to match the
     /// selection.
     fn breadcrumb_selected(&self, index: usize) {
+        if self.switching_tab.get() {
+            return;
+        }
+        self.update_current_tab(index);
         let current_stack = self.controller.call_stack();
         if current_stack.len() >= index {
             self.pop_stack(current_stack.len() - index);
@@ -161,6 +208,51 @@ impl Model {
         }
     }
 
+    /// Open a new tab displaying the graph of the given stack, and select it. The stack itself is
+    /// not entered.
+    fn open_tab(&self, stack: Vec<view::project_view_top_bar::LocalCall>) {
+        let tab_bar = &self.view.graph().model.tab_bar;
+        tab_bar.open_tab(tab_label(&stack));
+        let tab = tab_bar.tab_opened.value();
+        self.tabs.borrow_mut().insert(tab, stack);
+        self.current_tab.set(Some(tab));
+    }
+
+    /// Store the stack of the given number of frames from the breadcrumbs as the stack of the
+    /// current tab.
+    fn update_current_tab(&self, frame_count: usize) {
+        if let Some(tab) = self.current_tab.get() {
+            let history = self.stack_history.borrow();
+            let stack = history.iter().take(frame_count).cloned().collect_vec();
+            self.view.graph().model.tab_bar.set_tab_label((tab, tab_label(&stack)));
+            self.tabs.borrow_mut().insert(tab, stack);
+        }
+    }
+
+    /// Method to call when a tab is selected. The call stack is replaced with the stack of the
+    /// graph displayed in the tab.
+    fn tab_selected(&self, tab: TabId) {
+        // A newly opened tab is selected before it is registered in `open_tab`.
+        let Some(stack) = self.tabs.borrow().get(&tab).cloned() else { return };
+        self.current_tab.set(Some(tab));
+        let current_stack = to_local_call_stack(self.controller.call_stack());
+        if current_stack != stack {
+            let common = current_stack.iter().zip(&stack).take_while(|(a, b)| a == b).count();
+            self.replace_stack(current_stack.len() - common, stack[common..].to_vec());
+            self.switching_tab.set(true);
+            self.reset_stack_levels(stack);
+            self.switching_tab.set(false);
+        }
+    }
+
+    /// Method to call when a tab is closed.
+    fn tab_closed(&self, tab: TabId) {
+        self.tabs.borrow_mut().remove(&tab);
+        if self.current_tab.get() == Some(tab) {
+            self.current_tab.set(None);
+        }
+    }
+
     fn visible_breadcrumbs(&self, entries: &[Breadcrumb]) {
         debug!("Visible Breadcrumbs changed to {entries:?}");
         debug_assert_eq!(
@@ -209,6 +301,36 @@ impl Model {
         });
     }
 
+    /// Exit the given number of frames and enter the new ones. This will notify the controller
+    /// to replace the top part of the stack in one step.
+    pub fn replace_stack(
+        &self,
+        frame_count: usize,
+        stack: Vec<view::project_view_top_bar::LocalCall>,
+    ) {
+        let controller = self.controller.clone_ref();
+        let store_stack = self.store_updated_stack_task();
+        executor::global::spawn(async move {
+            let stack = stack
+                .into_iter()
+                .map(|local_call| LocalCall {
+                    definition: (**local_call.definition).clone(),
+                    call:       local_call.call,
+                })
+                .collect_vec();
+            info!("Replacing {frame_count} frames of the stack with {stack:?}.");
+            let exited = controller.exit_stack(frame_count).await;
+            let result = match exited {
+                Ok(()) if !stack.is_empty() => controller.enter_stack(stack).await,
+                other => other,
+            };
+            match result {
+                Ok(()) => store_stack(),
+                Err(error) => error!("Replacing stack failed: {error}"),
+            }
+        });
+    }
+
     /// Pop a part of the call stack. This will notify the controller to exit the stack.
     pub fn pop_stack(&self, frame_count: usize) {
         analytics::remote_log_event("integration::node_exited");
@@ -236,6 +358,11 @@ impl Model {
 // === CallStack ===
 // ======================
 
+/// The label of the tab displaying the graph of the stack: the name of the entered method.
+fn tab_label(stack: &[view::project_view_top_bar::LocalCall]) -> ImString {
+    stack.last().map_or_else(|| "main".into(), |call| call.definition.name.as_str().into())
+}
+
 fn to_local_call_stack(stack: Vec<LocalCall>) -> Vec<view::project_view_top_bar::LocalCall> {
     stack
         .into_iter()
@@ -266,15 +393,22 @@ impl CallStack {
         let model = Rc::new(Model::new(controller, project_view, state));
         let view = &model.view;
         let breadcrumbs = &view.top_bar().breadcrumbs;
+        let tab_bar = &graph_editor_view.model.tab_bar;
 
         frp::extend! { network
             eval graph_editor_view.node_entered ((node) model.node_entered(*node));
             eval_ graph_editor_view.node_exited (model.node_exited());
+            eval graph_editor_view.request_enter_method (((node, pointer))
+                model.method_entered(*node, pointer)
+            );
 
             selected_update <- breadcrumbs.selected.on_change();
             eval selected_update ((index) model.breadcrumb_selected(*index));
             entried_update <- breadcrumbs.entries.on_change();
             eval entried_update ((entries) model.visible_breadcrumbs(entries));
+
+            eval tab_bar.tab_selected ((tab) model.tab_selected(*tab));
+            eval tab_bar.tab_closed ((tab) model.tab_closed(*tab));
         }
 
         Self { _network: network, model }.initialize_breadcrumbs()
@@ -285,7 +419,8 @@ impl CallStack {
         self.model.init_breadcrumb_view();
         let stack = self.model.controller.call_stack();
         let call_stack = to_local_call_stack(stack);
-        self.model.add_stack_levels(call_stack, 1); // 1 because of the main module
+        self.model.add_stack_levels(call_stack.clone(), 1); // 1 because of the main module
+        self.model.open_tab(call_stack);
         self
     }
 }
@@ -13,6 +13,7 @@ use double_representation::context_switch::ContextSwitchExpression;
 use engine_protocol::language_server::ExpressionUpdatePayload;
 use engine_protocol::language_server::SuggestionId;
 use ide_view as view;
+use ide_view::graph_editor::builtin::visualization::native::error::StackFrame;
 use ide_view::graph_editor::component::node as node_view;
 use ide_view::graph_editor::component::visualization as visualization_view;
 use ide_view::graph_editor::EdgeEndpoint;
@@ -34,8 +35,10 @@ pub struct Node {
     pub is_frozen: bool,
     pub context_switch: Option<ContextSwitchExpression>,
     pub error: Option<node_view::Error>,
+    pub warnings: Rc<Vec<node_view::warnings::Warning>>,
     pub is_pending: bool,
     pub visualization: Option<visualization_view::Path>,
+    pub width: Option<f32>,
     /// Indicate whether this node view is updated automatically by changes from the controller
     /// or view, or will be explicitly updated.
     disable_expression_auto_update: bool,
@@ -71,10 +74,18 @@ impl Node {
         Some((self.view_id?, self.error.clone()))
     }
 
+    pub fn warnings_update(&self) -> Option<(ViewNodeId, Rc<Vec<node_view::warnings::Warning>>)> {
+        Some((self.view_id?, self.warnings.clone()))
+    }
+
     pub fn pending_update(&self) -> Option<(ViewNodeId, bool)> {
         Some((self.view_id?, self.is_pending))
     }
 
+    pub fn width_update(&self) -> Option<(ViewNodeId, Option<f32>)> {
+        Some((self.view_id?, self.width))
+    }
+
     pub fn visualization_update(&self) -> Option<(ViewNodeId, Option<visualization_view::Path>)> {
         Some((self.view_id?, self.visualization.clone()))
     }
@@ -260,6 +271,11 @@ impl Expressions {
     pub fn expressions_of_node(&self, id: AstNodeId) -> &[ast::Id] {
         self.expressions_of_node.get(&id).map_or(&[], |v| v.as_slice())
     }
+
+    /// Get the node whose line contains the given expression.
+    pub fn node_of(&self, id: ast::Id) -> Option<AstNodeId> {
+        self.expressions.get(&id).map(|expression| expression.node)
+    }
 }
 
 
@@ -290,6 +306,13 @@ impl State {
         self.nodes.borrow().get(node).and_then(|n| n.view_id)
     }
 
+    /// Get view id of the node whose line contains the given expression. The expression may also be
+    /// the node's own AST ID. Returns `None` if the expression is not a part of any displayed node.
+    pub fn view_id_of_expression(&self, expression: ast::Id) -> Option<ViewNodeId> {
+        let node = self.expressions.borrow().node_of(expression).unwrap_or(expression);
+        self.view_id_of_ast_node(node)
+    }
+
     /// Get node's AST ID by the view id.
     pub fn ast_node_id_of_view(&self, node: ViewNodeId) -> Option<AstNodeId> {
         self.nodes.borrow().ast_id_of_view(node)
@@ -495,6 +518,25 @@ impl<'a> ControllerChange<'a> {
         }
     }
 
+    /// Set the node warnings basing of the given expression's payload. If the warnings are
+    /// actually changed, the to-be-updated node view is returned with the new warnings. If the
+    /// expression is not a whole expression of any node, nothing is updated and `None` is returned.
+    pub fn set_node_warnings_from_payload(
+        &self,
+        expression: ast::Id,
+        payload: Option<ExpressionUpdatePayload>,
+    ) -> Option<(ViewNodeId, Rc<Vec<node_view::warnings::Warning>>)> {
+        let new_warnings = Rc::new(Self::convert_payload_to_warnings(payload));
+        let mut nodes = self.nodes.borrow_mut();
+        let displayed = nodes.get_mut(expression)?;
+        if displayed.warnings != new_warnings {
+            displayed.warnings = new_warnings.clone();
+            Some((displayed.view_id?, new_warnings))
+        } else {
+            None
+        }
+    }
+
     /// Set whether this node is currently awaiting completion of execution.
     pub fn set_node_pending(
         &self,
@@ -518,22 +560,23 @@ impl<'a> ControllerChange<'a> {
     ) -> Option<node_view::error::Error> {
         use node_view::error::Kind;
         use ExpressionUpdatePayload::*;
-        let is_propagated = |trace: Vec<AstNodeId>| {
+        let is_propagated = |trace: &[AstNodeId]| {
             let nodes = self.nodes.borrow();
             let root_cause = trace.iter().find(|id| nodes.get(**id).is_some());
             !root_cause.contains(&&node_id)
         };
-        let (kind, message, propagated) = match payload {
+        let (kind, message, propagated, stack) = match payload {
             Some(Value { warnings: Some(warnings) }) if warnings.count > 0 => {
                 // We return `None` as message, even though we have a warning text available. We
                 // don't want to replace the visualization of the value with a warning text though.
-                Some((Kind::Warning, None, false))
+                Some((Kind::Warning, None, false, default()))
             }
-            Some(DataflowError { trace }) => Some((Kind::Dataflow, None, is_propagated(trace))),
+            Some(DataflowError { trace }) =>
+                Some((Kind::Dataflow, None, is_propagated(&trace), default())),
             Some(Panic { message, trace }) => {
                 let message = Some(message);
-                let is_propagated = is_propagated(trace);
-                Some((Kind::Panic, message, is_propagated))
+                let is_propagated = is_propagated(&trace);
+                Some((Kind::Panic, message, is_propagated, self.stack_frames(&trace)))
             }
             _ => None,
         }?;
@@ -541,7 +584,40 @@ impl<'a> ControllerChange<'a> {
         let kind = Immutable(kind);
         let message = Rc::new(message);
         let propagated = Immutable(propagated);
-        Some(node_view::error::Error { kind, message, propagated })
+        let stack = Rc::new(stack);
+        Some(node_view::error::Error { kind, message, propagated, stack })
+    }
+
+    /// Describe the frames of the error's stack trace. The frames pointing to the expressions of
+    /// the displayed graph are labelled with the code of the node containing the expression.
+    fn stack_frames(&self, trace: &[ast::Id]) -> Vec<StackFrame> {
+        let nodes = self.nodes.borrow();
+        let expressions = self.expressions.borrow();
+        let frame = |&id: &ast::Id| {
+            let node = expressions.node_of(id).unwrap_or(id);
+            let code = nodes.get(node).map(|node| node.expression.code.to_string());
+            let label = code.unwrap_or_else(|| format!("<{id}>"));
+            StackFrame { label, expression_id: Some(id) }
+        };
+        trace.iter().map(frame).collect()
+    }
+
+    /// The engine sends only the number of warnings attached to the value, and the text of the
+    /// warning if there is a single one.
+    fn convert_payload_to_warnings(
+        payload: Option<ExpressionUpdatePayload>,
+    ) -> Vec<node_view::warnings::Warning> {
+        use node_view::warnings::Warning;
+        match payload {
+            Some(ExpressionUpdatePayload::Value { warnings: Some(warnings) }) => {
+                let mut converted = vec![Warning::default(); warnings.count];
+                if let Some(first) = converted.first_mut() {
+                    first.message = warnings.value.map(ImString::new);
+                }
+                converted
+            }
+            _ => default(),
+        }
     }
 
     /// Set the node's attached visualization. The `visualization_data` should be the content of
@@ -567,6 +643,23 @@ impl<'a> ControllerChange<'a> {
             None
         }
     }
+
+    /// Set the node's width override read from the node's metadata. If the width actually
+    /// changes, the to-be-updated node view is returned with the new width.
+    pub fn set_node_width(
+        &self,
+        node_id: AstNodeId,
+        width: Option<f32>,
+    ) -> Option<(ViewNodeId, Option<f32>)> {
+        let mut nodes = self.state.nodes.borrow_mut();
+        let displayed = nodes.get_mut_or_create(node_id);
+        if displayed.width != width {
+            displayed.width = width;
+            Some((displayed.view_id?, width))
+        } else {
+            None
+        }
+    }
 }
 
 
@@ -689,6 +782,20 @@ impl<'a> ViewChange<'a> {
         }
     }
 
+    /// Set the node's width override. If the width actually changes, the AST ID of the affected
+    /// node is returned.
+    pub fn set_node_width(&self, id: ViewNodeId, new_width: Option<f32>) -> Option<AstNodeId> {
+        let mut nodes = self.nodes.borrow_mut();
+        let ast_id = nodes.ast_id_of_view(id)?;
+        let displayed = nodes.get_mut(ast_id)?;
+        if displayed.width != new_width {
+            displayed.width = new_width;
+            Some(ast_id)
+        } else {
+            None
+        }
+    }
+
     /// Mark the node as skipped and return its AST ID. Returns `None` if no changes to the
     /// expression are needed.
     pub fn set_node_skip(&self, id: ViewNodeId, skip: bool) -> Option<AstNodeId> {
@@ -967,4 +1074,38 @@ mod tests {
         assert_eq!(updater.set_expression_suggestion(expr, Some(5)), None);
         assert_eq!(updater.set_expression_suggestion(expr, None), Some((view, None)));
     }
+
+    #[wasm_bindgen_test]
+    fn finding_node_of_expression() {
+        use ast::crumbs::InfixCrumb;
+        let Fixture { state, nodes } = Fixture::setup_nodes(&["2 + 3", "foo"]);
+        let node_ast = nodes[0].node.expression();
+        let operand = node_ast.get(&InfixCrumb::RightOperand.into()).unwrap().id.unwrap();
+
+        assert_eq!(state.view_id_of_expression(operand), Some(nodes[0].view));
+        assert_eq!(state.view_id_of_expression(nodes[1].node.id()), Some(nodes[1].view));
+        assert_eq!(state.view_id_of_expression(ast::Id::new_v4()), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn stack_of_panic_payload() {
+        use ast::crumbs::InfixCrumb;
+        let Fixture { state, nodes } = Fixture::setup_nodes(&["2 + 3", "foo"]);
+        let node_ast = nodes[0].node.expression();
+        let operand = node_ast.get(&InfixCrumb::RightOperand.into()).unwrap().id.unwrap();
+        let unknown = ast::Id::new_v4();
+        let trace = vec![operand, nodes[1].node.id(), unknown];
+        let payload = ExpressionUpdatePayload::Panic { message: "Boom".into(), trace };
+        let updater = state.update_from_controller();
+        let update = updater.set_node_error_from_payload(nodes[1].node.id(), Some(payload));
+        let (view, error) = update.unwrap();
+        let stack = error.unwrap().stack;
+
+        assert_eq!(view, nodes[1].view);
+        let labels = stack.iter().map(|frame| frame.label.as_str()).collect_vec();
+        let unknown_label = format!("<{unknown}>");
+        assert_eq!(labels, ["2 + 3", "foo", unknown_label.as_str()]);
+        let ids = stack.iter().map(|frame| frame.expression_id).collect_vec();
+        assert_eq!(ids, [Some(operand), Some(nodes[1].node.id()), Some(unknown)]);
+    }
 }
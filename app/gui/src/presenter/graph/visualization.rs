@@ -12,9 +12,62 @@ use crate::presenter::graph::AstNodeId;
 use crate::presenter::graph::ViewNodeId;
 
 use enso_frp as frp;
+use ensogl::system::web::sleep;
 use ide_view as view;
 use ide_view::graph_editor::component::node as node_view;
 use ide_view::graph_editor::component::visualization as visualization_view;
+use std::collections::hash_map::Entry;
+use std::time::Duration;
+use visualization_view::instance::PreprocessorConfiguration;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The time the preprocessor requested by a visualization must stay unchanged before it is sent to
+/// the engine. Scrubbing through the nodes or dragging a visualization control would otherwise
+/// flood the engine with the requests.
+const PREPROCESSOR_DEBOUNCE: Duration = Duration::from_millis(150);
+
+
+
+// ============================
+// === PendingPreprocessors ===
+// ============================
+
+/// The preprocessor changes not sent to the engine yet. Each scheduled change gets a new
+/// generation number, and is applied only if it was not replaced by a newer change of the same
+/// node, or cancelled, in the meantime.
+#[derive(Debug, Default)]
+struct PendingPreprocessors {
+    pending:         HashMap<ViewNodeId, (u64, PreprocessorConfiguration)>,
+    last_generation: u64,
+}
+
+impl PendingPreprocessors {
+    /// Schedule the change, replacing the pending change of the same node. Returns the generation
+    /// of the scheduled change.
+    fn schedule(&mut self, node_id: ViewNodeId, preprocessor: PreprocessorConfiguration) -> u64 {
+        self.last_generation += 1;
+        self.pending.insert(node_id, (self.last_generation, preprocessor));
+        self.last_generation
+    }
+
+    /// Take the pending change of the node, if it has the given generation.
+    fn take(&mut self, node_id: ViewNodeId, generation: u64) -> Option<PreprocessorConfiguration> {
+        match self.pending.entry(node_id) {
+            Entry::Occupied(entry) if entry.get().0 == generation => Some(entry.remove().1),
+            _ => None,
+        }
+    }
+
+    /// Cancel the pending change of the node. Returns `true` if there was any.
+    fn cancel(&mut self, node_id: ViewNodeId) -> bool {
+        self.pending.remove(&node_id).is_some()
+    }
+}
 
 
 
@@ -26,46 +79,102 @@ use ide_view::graph_editor::component::visualization as visualization_view;
 struct Model {
     controller:    controller::Visualization,
     graph_view:    view::graph_editor::GraphEditor,
-    manager:       Rc<Manager>,
-    error_manager: Rc<Manager>,
-    state:         Rc<graph::state::State>,
-    shown:         RefCell<HashSet<ViewNodeId>>,
+    manager:         Rc<Manager>,
+    error_manager:   Rc<Manager>,
+    preview_manager: Rc<Manager>,
+    state:           Rc<graph::state::State>,
+    shown:           RefCell<HashSet<ViewNodeId>>,
+    previews_shown:  Cell<bool>,
+    pending:         RefCell<PendingPreprocessors>,
 }
 
 impl Model {
     /// Handle the showing visualization UI.
     fn visualization_shown(&self, node_id: ViewNodeId, metadata: visualization_view::Metadata) {
+        self.cancel_pending_updates(node_id);
         self.shown.borrow_mut().insert(node_id);
         self.update_visualization(node_id, &self.manager, Some(metadata));
     }
 
     /// Handle the hiding in UI.
     fn visualization_hidden(&self, node_id: view::graph_editor::NodeId) {
+        self.cancel_pending_updates(node_id);
         self.shown.borrow_mut().remove(&node_id);
         self.update_visualization(node_id, &self.manager, None);
     }
 
     /// Handle the node removal in UI.
     fn node_removed(&self, node_id: view::graph_editor::NodeId) {
+        self.cancel_pending_updates(node_id);
         self.shown.borrow_mut().remove(&node_id);
         if self.state.ast_node_id_of_view(node_id).is_some() {
             self.update_visualization(node_id, &self.manager, None);
             self.update_visualization(node_id, &self.error_manager, None);
+            self.update_visualization(node_id, &self.preview_manager, None);
+        }
+    }
+
+    /// Handle showing or hiding the value previews in UI: attach/detach the value preview
+    /// preprocessor on all nodes.
+    fn value_previews_visibility_changed(&self, visible: bool) {
+        self.previews_shown.set(visible);
+        for node_id in self.graph_view.model.nodes.all.keys() {
+            if self.state.ast_node_id_of_view(node_id).is_some() {
+                self.update_value_preview(node_id);
+            }
+        }
+    }
+
+    /// Handle the expression change on given node. The node's value preview is attached if
+    /// needed, as the node may have been just created.
+    fn node_expression_set(&self, node_id: ViewNodeId) {
+        if self.previews_shown.get() {
+            self.update_value_preview(node_id);
         }
     }
 
-    /// Handle the preprocessor change requested by visualization.
+    fn update_value_preview(&self, node_id: ViewNodeId) {
+        use node_view::value_preview;
+        let metadata = self.previews_shown.get().then(value_preview::metadata);
+        self.update_visualization(node_id, &self.preview_manager, metadata);
+    }
+
+    /// Handle the preprocessor change requested by visualization. The change is sent to the
+    /// engine after [`PREPROCESSOR_DEBOUNCE`], unless the node requests another change or the
+    /// change is cancelled in the meantime.
     fn visualization_preprocessor_changed(
-        &self,
+        self: &Rc<Self>,
         node_id: ViewNodeId,
-        preprocessor: visualization_view::instance::PreprocessorConfiguration,
+        preprocessor: PreprocessorConfiguration,
     ) {
         if self.shown.borrow().contains(&node_id) {
+            let generation = self.pending.borrow_mut().schedule(node_id, preprocessor);
+            let weak = Rc::downgrade(self);
+            executor::global::spawn(async move {
+                sleep(PREPROCESSOR_DEBOUNCE).await;
+                if let Some(model) = weak.upgrade() {
+                    model.apply_pending_preprocessor(node_id, generation);
+                }
+            });
+        }
+    }
+
+    fn apply_pending_preprocessor(&self, node_id: ViewNodeId, generation: u64) {
+        let preprocessor = self.pending.borrow_mut().take(node_id, generation);
+        let preprocessor = preprocessor.filter(|_| self.shown.borrow().contains(&node_id));
+        if let Some(preprocessor) = preprocessor {
             let metadata = visualization_view::Metadata { preprocessor };
             self.update_visualization(node_id, &self.manager, Some(metadata))
         }
     }
 
+    /// Drop the preprocessor change of the node that was not sent to the engine yet.
+    fn cancel_pending_updates(&self, node_id: ViewNodeId) {
+        if self.pending.borrow_mut().cancel(node_id) {
+            debug!("Cancelled the pending visualization update of {node_id:?}.");
+        }
+    }
+
     /// Handle the error change on given node: attach/detach the error visualization if needed.
     fn error_on_node_changed(&self, node_id: ViewNodeId, error: &Option<node_view::Error>) {
         use view::graph_editor::builtin::visualization::native::error as error_visualization;
@@ -178,13 +287,17 @@ impl Visualization {
         let controller = project.visualization().clone_ref();
         let (manager, notifications) = Manager::new(graph.clone_ref());
         let (error_manager, error_notifications) = Manager::new(graph.clone_ref());
+        let (preview_manager, preview_notifications) = Manager::new(graph.clone_ref());
         let model = Rc::new(Model {
             controller,
             graph_view: view.clone_ref(),
             manager: manager.clone_ref(),
             error_manager: error_manager.clone_ref(),
+            preview_manager: preview_manager.clone_ref(),
             state,
             shown: default(),
+            previews_shown: default(),
+            pending: default(),
         });
 
         frp::extend! { network
@@ -192,15 +305,21 @@ impl Visualization {
             eval view.visualization_hidden ((node) model.visualization_hidden(*node));
             eval view.node_removed ((node) model.node_removed(*node));
             eval view.visualization_preprocessor_changed (((node, preprocessor)) model.visualization_preprocessor_changed(*node, preprocessor.clone_ref()));
+            eval view.cancel_pending_visualization_updates ((node) model.cancel_pending_updates(*node));
             eval view.set_node_error_status (((node, error)) model.error_on_node_changed(*node, error));
+            eval view.value_previews_visible ((visible) model.value_previews_visibility_changed(*visible));
+            eval view.set_node_expression (((node, _)) model.node_expression_set(*node));
 
             set_data <- source::<(ViewNodeId, visualization_view::Data)>();
             error_update <- source::<(ViewNodeId, visualization_view::Data)>();
             visualization_failure <- source::<(ViewNodeId,String)>();
             error_vis_failure <- source::<(ViewNodeId,String)>();
+            preview_update <- source::<(ViewNodeId, visualization_view::Data)>();
+            preview_failure <- source::<(ViewNodeId,String)>();
 
             view.set_visualization_data <+ set_data;
             view.set_error_visualization_data <+ error_update;
+            view.set_value_preview_data <+ preview_update;
             view.visualization_update_failed <+ visualization_failure;
 
             eval_ view.visualization_registry_reload_requested (model.load_visualizations());
@@ -214,6 +333,12 @@ impl Visualization {
                 error_update,
                 error_vis_failure,
             )
+            .spawn_visualization_handler(
+                preview_notifications,
+                preview_manager,
+                preview_update,
+                preview_failure,
+            )
             .setup_graph_listener(graph)
     }
 
@@ -291,6 +416,7 @@ impl Visualization {
                         let nodes_set = nodes.into_iter().map(|n| n.id()).collect();
                         model.manager.retain_visualizations(&nodes_set);
                         model.error_manager.retain_visualizations(&nodes_set);
+                        model.preview_manager.retain_visualizations(&nodes_set);
                     }
                     Err(err) => {
                         error!("Cannot update visualization after graph change: {err}");
@@ -303,3 +429,33 @@ impl Visualization {
         self
     }
 }
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[wasm_bindgen_test]
+    fn scheduling_preprocessor_changes() {
+        let [a, b] = [1, 2].map(|n: usize| ensogl::display::object::Id::from(n).into());
+        let no_arguments = Vec::<String>::new;
+        let preprocessor =
+            |method: &str| PreprocessorConfiguration::new("Main", method, no_arguments());
+        let mut pending = PendingPreprocessors::default();
+        let first = pending.schedule(a, preprocessor("first"));
+        let second = pending.schedule(a, preprocessor("second"));
+        let other = pending.schedule(b, preprocessor("other"));
+        assert_eq!(pending.take(a, first), None);
+        assert_eq!(pending.take(a, second), Some(preprocessor("second")));
+        assert_eq!(pending.take(a, second), None);
+
+        assert!(pending.cancel(b));
+        assert!(!pending.cancel(b));
+        assert_eq!(pending.take(b, other), None);
+    }
+}
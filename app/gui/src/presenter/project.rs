@@ -14,6 +14,8 @@ use engine_protocol::language_server::ExecutionEnvironment;
 use enso_frp as frp;
 use ensogl::system::js;
 use ide_view as view;
+use ide_view::docking::DockLayout;
+use ide_view::execution_environment_selector::ExecutionEnvironmentDef;
 use ide_view::project::SearcherParams;
 use ide_view::project::SearcherType;
 use model::module::NotificationKind;
@@ -182,6 +184,28 @@ impl Model {
         }
     }
 
+    /// The layout of the docked panels stored in the project's metadata.
+    fn stored_dock_layout(&self) -> Option<DockLayout> {
+        let layout = self.module_model.with_project_metadata(|md| md.dock_layout.clone());
+        // The layout is missing in the projects saved before the panels could be docked.
+        serde_json::from_value(layout).ok()
+    }
+
+    fn dock_layout_changed(&self, layout: &DockLayout) {
+        let _transaction = self
+            .module_model
+            .undo_redo_repository()
+            .open_ignored_transaction_or_ignore_current("Updating dock layout metadata");
+        let result = match serde_json::to_value(layout) {
+            Ok(serialized) =>
+                self.module_model.update_project_metadata(|md| md.dock_layout = serialized),
+            Err(err) => FallibleResult::Err(err.into()),
+        };
+        if let Err(error) = result {
+            error!("Failed to store the dock layout: {error}");
+        }
+    }
+
     fn save_project_snapshot(&self) {
         let controller = self.controller.clone_ref();
         let project_name = self.view.top_bar().project_name().clone_ref();
@@ -350,11 +374,9 @@ impl Model {
         });
     }
 
-    fn execution_environment_changed(
-        &self,
-        execution_environment: ide_view::execution_environment_selector::ExecutionEnvironment,
-    ) {
+    fn execution_environment_changed(&self, execution_environment: &ExecutionEnvironmentDef) {
         let graph_controller = self.graph_controller.clone_ref();
+        let execution_environment = execution_environment.engine_environment();
         executor::global::spawn(async move {
             if let Err(err) =
                 graph_controller.set_execution_environment(execution_environment).await
@@ -460,9 +482,13 @@ impl Project {
                 }
             });
 
-            graph_view.remove_node <+ view.editing_committed.filter_map(f!([model]((node_view, entry)) {
-                model.editing_committed(*node_view,*entry).as_some(*node_view)
+            editing_committed <- view.editing_committed.map(f!([model]((node_view, entry)) {
+                (*node_view, model.editing_committed(*node_view,*entry))
             }));
+            graph_view.remove_node <+ editing_committed.filter_map(|(node, removed)| removed.as_some(*node));
+            // The values computed before the edit are outdated until the engine recomputes them.
+            node_edited <- editing_committed.filter_map(|(node, removed)| (!removed).as_some(*node));
+            graph_view.mark_subtree_stale <+ node_edited;
             eval_ view.editing_aborted(model.editing_aborted());
 
             eval_ view.undo (model.undo());
@@ -484,8 +510,9 @@ impl Project {
             eval_ view.execution_context_reload_and_restart(model.execution_context_reload_and_restart());
 
             view.set_read_only <+ view.toggle_read_only.map(f_!(model.toggle_read_only()));
-            eval graph_view.execution_environment((env) model.execution_environment_changed(*env));
+            eval graph_view.execution_environment((env) model.execution_environment_changed(env));
             eval_ graph_view.execution_environment_play_button_pressed( model.trigger_clean_live_execution());
+            eval_ graph_view.execution_environment_cancel_pressed(model.execution_context_interrupt());
 
             eval_ view.start_language_server_profiling(model.start_language_server_profiling());
             eval_ view.stop_language_server_profiling(model.stop_language_server_profiling());
@@ -497,6 +524,7 @@ impl Project {
 
         self.init_analytics()
             .init_execution_environments()
+            .init_docking()
             .setup_notification_handler()
             .attach_frp_to_values_computed_notifications(graph_controller, values_computed)
     }
@@ -504,8 +532,23 @@ impl Project {
     /// Initialises execution environment.
     fn init_execution_environments(self) -> Self {
         let graph = &self.model.view.graph();
-        let entries = Rc::new(ExecutionEnvironment::list_all());
-        graph.set_available_execution_environments(entries);
+        let entries = ExecutionEnvironment::list_all().into_iter().map(ExecutionEnvironmentDef::from);
+        graph.set_available_execution_environments(Rc::new(entries.collect_vec()));
+        self
+    }
+
+    /// Restores the layout of the docked panels from the project's metadata, and stores it there
+    /// after each change.
+    fn init_docking(self) -> Self {
+        let network = &self.network;
+        let model = &self.model;
+        let docking = model.view.docking().frp();
+        frp::extend! { network
+            eval docking.layout_changed ((layout) model.dock_layout_changed(layout));
+        }
+        if let Some(layout) = model.stored_dock_layout() {
+            docking.restore_layout(layout);
+        }
         self
     }
 
@@ -158,11 +158,12 @@ impl Model {
         if let Some(breadcrumbs) = breadcrumbs {
             let browser = &self.view;
             let breadcrumbs_count = breadcrumbs.len();
-            let without_icon =
-                breadcrumbs[0..breadcrumbs_count - 1].iter().map(|crumb| crumb.view_without_icon());
+            let with_kind_icon = breadcrumbs[0..breadcrumbs_count - 1]
+                .iter()
+                .map(|crumb| crumb.view_with_kind_icon());
             let with_icon =
                 breadcrumbs[breadcrumbs_count - 1..].iter().map(|crumb| crumb.view_with_icon());
-            let all = without_icon.chain(with_icon).collect_vec();
+            let all = with_kind_icon.chain(with_icon).collect_vec();
             browser.model().documentation.breadcrumbs.set_entries(all);
         }
     }
@@ -208,6 +208,24 @@ impl EntryDocumentation {
         }
     }
 
+    /// The qualified name of the documented entry. Returns [`None`] for the placeholder and the
+    /// builtin documentation, which are not bound to any entry.
+    pub fn name(&self) -> Option<Rc<QualifiedName>> {
+        match self {
+            EntryDocumentation::Docs(docs) => match docs {
+                Documentation::Module(docs) => Some(docs.name.clone_ref()),
+                Documentation::Type { docs, .. } => Some(docs.name.clone_ref()),
+                Documentation::Constructor { docs, .. } => Some(docs.name.clone_ref()),
+                Documentation::Method { docs, .. } => Some(docs.name.clone_ref()),
+                Documentation::ModuleMethod { docs, .. } => Some(docs.name.clone_ref()),
+                Documentation::Function(docs) => Some(docs.name.clone_ref()),
+                Documentation::Local(docs) => Some(docs.name.clone_ref()),
+                Documentation::Builtin(_) => None,
+            },
+            EntryDocumentation::Placeholder => None,
+        }
+    }
+
     fn parent_module(
         db: &SuggestionDatabase,
         entry: &Entry,
@@ -124,12 +124,10 @@ impl component::Frp<Model> for Frp {
         let grid = &model.list.model().grid;
 
         let gap = style.get_number(theme::panels_gap);
-        let doc_width = style.get_number(theme::documentation::width);
-        let doc_height = style.get_number(theme::documentation::height);
         frp::extend! { network
             init <- source_();
 
-            doc_size <- all_with3(&init, &doc_width, &doc_height, |_, w, h| Vector2(*w, *h));
+            doc_size <- all_with(&init, &documentation.frp.size, |_, size| *size);
             size <- all_with4(&init, &list_panel.size, &doc_size, &gap, |(), list_size, doc_size, gap| {
                 let width = list_size.x + gap + doc_size.x;
                 let height = max(list_size.y, doc_size.y);
@@ -142,6 +140,8 @@ impl component::Frp<Model> for Frp {
                 |sz, doc_sz, snap| sz.x / 2.0 - doc_sz.x + snap.x
             );
             eval list_position_x ((x) model.list.set_x(*x));
+            // The popped out documentation is placed independently of the component browser.
+            doc_position_x <- doc_position_x.gate_not(&documentation.frp.is_popped_out);
             eval doc_position_x ((x) model.documentation.set_x(*x));
 
             model.list.input.show <+ input.show;
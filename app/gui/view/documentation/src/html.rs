@@ -3,6 +3,8 @@
 use enso_prelude::*;
 use horrorshow::prelude::*;
 
+use crate::tabs::Tabs;
+
 use double_representation::name::QualifiedName;
 use enso_doc_parser::DocSection;
 use enso_doc_parser::Mark;
@@ -20,6 +22,7 @@ use enso_suggestion_database::documentation_ir::Tag;
 use enso_suggestion_database::documentation_ir::TypeDocumentation;
 use enso_suggestion_database::documentation_ir::Types;
 use enso_suggestion_database::entry::Argument;
+use ensogl::application::i18n::Translator;
 use horrorshow::box_html;
 use horrorshow::labels;
 use horrorshow::owned_html;
@@ -52,18 +55,19 @@ fn svg_icon(content: &'static str, class: &'static str) -> impl Render {
 // === Render ===
 // ==============
 
-/// Render entry documentation to HTML code.
+/// Render entry documentation to HTML code. The captions are translated with the `translator`.
 #[profile(Detail)]
-pub fn render(docs: &EntryDocumentation) -> String {
+pub fn render(docs: &EntryDocumentation, translator: &Translator) -> String {
     let html = match docs {
-        EntryDocumentation::Placeholder => String::from("No documentation available."),
-        EntryDocumentation::Docs(docs) => render_documentation(docs.clone_ref()),
+        EntryDocumentation::Placeholder =>
+            translator.translate("No documentation available.").to_string(),
+        EntryDocumentation::Docs(docs) => render_documentation(docs.clone_ref(), translator),
     };
     match validate_utf8(&html) {
         Ok(_) => html,
         Err(_) => {
             error!("Internal error. Generated HTML is not valid utf-8. This is bug #5813.");
-            String::from("Failed to load documentation.")
+            translator.translate("Failed to load documentation.").to_string()
         }
     }
 }
@@ -74,15 +78,15 @@ fn validate_utf8(s: &str) -> Result<&str, std::str::Utf8Error> {
     std::str::from_utf8(bytes)
 }
 
-fn render_documentation(docs: Documentation) -> String {
+fn render_documentation(docs: Documentation, t: &Translator) -> String {
     match docs {
-        Documentation::Module(module_docs) => render_module_documentation(&module_docs),
-        Documentation::Type { docs, .. } => render_type_documentation(&docs),
-        Documentation::Function(docs) => render_function_documentation(&docs),
-        Documentation::Local(docs) => render_local_documentation(&docs),
-        Documentation::Constructor { docs, .. } => render_function_documentation(&docs),
-        Documentation::Method { docs, .. } => render_function_documentation(&docs),
-        Documentation::ModuleMethod { docs, .. } => render_function_documentation(&docs),
+        Documentation::Module(module_docs) => render_module_documentation(&module_docs, t),
+        Documentation::Type { docs, .. } => render_type_documentation(&docs, t),
+        Documentation::Function(docs) => render_function_documentation(&docs, t),
+        Documentation::Local(docs) => render_local_documentation(&docs, t),
+        Documentation::Constructor { docs, .. } => render_function_documentation(&docs, t),
+        Documentation::Method { docs, .. } => render_function_documentation(&docs, t),
+        Documentation::ModuleMethod { docs, .. } => render_function_documentation(&docs, t),
         Documentation::Builtin(builtin_docs) => render_builtin_documentation(&builtin_docs),
     }
 }
@@ -97,7 +101,7 @@ fn render_documentation(docs: Documentation) -> String {
 /// - Synopsis and a list of constructors.
 /// - Methods.
 /// - Examples.
-fn render_type_documentation(docs: &TypeDocumentation) -> String {
+fn render_type_documentation(docs: &TypeDocumentation, t: &Translator) -> String {
     let constructors_exist = !docs.constructors.is_empty();
     let methods_exist = !docs.methods.is_empty();
     let examples_exist = !docs.examples.is_empty();
@@ -113,35 +117,35 @@ fn render_type_documentation(docs: &TypeDocumentation) -> String {
         : &tags;
         : &synopsis;
         @ if constructors_exist {
-            : constructors_header();
+            : constructors_header(t);
             : &constructors;
         }
         @ if methods_exist {
-            : methods_header();
+            : methods_header(t);
             : &methods;
         }
         @ if examples_exist {
-            : examples_header();
+            : examples_header(t);
             : &examples;
         }
     };
     docs_content(content).into_string().unwrap()
 }
 
-fn constructors_header() -> impl Render {
-    header(ICON_METHODS, "Constructors", "section-header methods-header")
+fn constructors_header(t: &Translator) -> impl Render {
+    header(ICON_METHODS, t.translate("Constructors").to_string(), "section-header methods-header")
 }
 
-fn methods_header() -> impl Render {
-    header(ICON_METHODS, "Methods", "section-header methods-header")
+fn methods_header(t: &Translator) -> impl Render {
+    header(ICON_METHODS, t.translate("Methods").to_string(), "section-header methods-header")
 }
 
-fn examples_header() -> impl Render {
-    header(ICON_EXAMPLES, "Examples", "section-header examples-header")
+fn examples_header(t: &Translator) -> impl Render {
+    header(ICON_EXAMPLES, t.translate("Examples").to_string(), "section-header examples-header")
 }
 
-fn types_header() -> impl Render {
-    header(ICON_METHODS, "Types", "section-header types-header")
+fn types_header(t: &Translator) -> impl Render {
+    header(ICON_METHODS, t.translate("Types").to_string(), "section-header types-header")
 }
 
 /// A synopsis of the type. Contains a list of constructors, if it is not empty.
@@ -196,7 +200,7 @@ fn single_function<'a>(function: &'a Function) -> Box<dyn Render + 'a> {
 /// - Types.
 /// - Functions.
 /// - Examples.
-fn render_module_documentation(docs: &ModuleDocumentation) -> String {
+fn render_module_documentation(docs: &ModuleDocumentation, t: &Translator) -> String {
     let types_exist = !docs.types.is_empty();
     let methods_exist = !docs.methods.is_empty();
     let examples_exist = !docs.examples.is_empty();
@@ -209,15 +213,15 @@ fn render_module_documentation(docs: &ModuleDocumentation) -> String {
         : &tags;
         : &synopsis;
         @ if types_exist {
-            : types_header();
+            : types_header(t);
             : &types;
         }
         @ if methods_exist {
-            : methods_header();
+            : methods_header(t);
             : &methods;
         }
         @ if examples_exist {
-            : examples_header();
+            : examples_header(t);
             : &examples;
         }
     };
@@ -278,7 +282,7 @@ fn module_synopsis<'a>(synopsis: &'a Synopsis) -> Box<dyn Render + 'a> {
 // === Functions ===
 
 /// Render documentation of a function.
-fn render_function_documentation(docs: &Function) -> String {
+fn render_function_documentation(docs: &Function, t: &Translator) -> String {
     let Function { synopsis, tags, .. } = docs;
 
     let examples_exist = !docs.examples.is_empty();
@@ -289,7 +293,7 @@ fn render_function_documentation(docs: &Function) -> String {
         : &tags;
         : &synopsis;
         @ if examples_exist {
-            : examples_header();
+            : examples_header(t);
             : &examples;
         }
     };
@@ -309,7 +313,7 @@ fn function_synopsis<'a>(synopsis: &'a Synopsis) -> Box<dyn Render + 'a> {
 // === Locals ===
 
 /// Render documentation of a function.
-fn render_local_documentation(docs: &LocalDocumentation) -> String {
+fn render_local_documentation(docs: &LocalDocumentation, t: &Translator) -> String {
     let LocalDocumentation { synopsis, tags, .. } = docs;
 
     let examples_exist = !docs.examples.is_empty();
@@ -321,7 +325,7 @@ fn render_local_documentation(docs: &LocalDocumentation) -> String {
         : &tags;
         : &synopsis;
         @ if examples_exist {
-            : examples_header();
+            : examples_header(t);
             : &examples;
         }
     };
@@ -495,6 +499,58 @@ fn single_tag<'a>(tag: &'a Tag) -> Box<dyn Render + 'a> {
     }
 }
 
+
+
+// =================
+// === Tab Strip ===
+// =================
+
+/// Render the strip of the tabs opened in the documentation panel. Every tab has a title and a
+/// close button, identified by [`tab_anchor_name`] and [`tab_close_anchor_name`] respectively.
+pub fn render_tab_strip(tabs: &Tabs, translator: &Translator) -> String {
+    let selected = tabs.selected_index();
+    let titles = tabs.iter().map(|tab| tab_title(&tab.docs, translator)).collect_vec();
+    let html = owned_html! {
+        div(class="tab-strip") {
+            @ for (index, title) in titles.iter().enumerate() {
+                div(class=labels!("tab", "selected-tab" => index == selected)) {
+                    span(class="tab-title", id=tab_anchor_name(index)) {
+                        : title.as_str();
+                    }
+                    span(class="tab-close", id=tab_close_anchor_name(index)) {
+                        : Raw("&times;");
+                    }
+                }
+            }
+        }
+    };
+    html.into_string().unwrap()
+}
+
+/// The title of the tab: the name of the documented entry, without the module path.
+fn tab_title(docs: &EntryDocumentation, translator: &Translator) -> String {
+    match docs.name() {
+        Some(name) => name.name().to_owned(),
+        None => translator.translate("Documentation").to_string(),
+    }
+}
+
+/// Anchor name for the title of the tab with the given index.
+pub fn tab_anchor_name(index: usize) -> String {
+    format!("enso-docs-tab-{index}")
+}
+
+/// Anchor name for the close button of the tab with the given index.
+pub fn tab_close_anchor_name(index: usize) -> String {
+    format!("enso-docs-tab-close-{index}")
+}
+
+
+
+// ===============
+// === Anchors ===
+// ===============
+
 /// Anchor name for the provided qualified name. It is used to set the unique `id` attribute for the
 /// generated HTML elements.
 pub fn anchor_name(name: &QualifiedName) -> String {
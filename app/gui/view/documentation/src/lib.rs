@@ -26,6 +26,7 @@ use enso_frp as frp;
 use enso_suggestion_database::documentation_ir::EntryDocumentation;
 use enso_suggestion_database::documentation_ir::LinkedDocPage;
 use ensogl::application::Application;
+use ensogl::control::io::mouse;
 use ensogl::data::color;
 use ensogl::display;
 use ensogl::display::style::FromTheme;
@@ -42,6 +43,7 @@ use ide_view_graph_editor as graph_editor;
 // ==============
 
 pub mod html;
+pub mod tabs;
 
 pub use ensogl_breadcrumbs as breadcrumbs;
 
@@ -56,6 +58,11 @@ pub use ensogl_breadcrumbs as breadcrumbs;
 const INITIAL_SECTION_NAME: &str = "Popular";
 /// Delay before updating the displayed documentation.
 const DISPLAY_DELAY_MS: i32 = 0;
+/// The width of the areas along the right and the top edges, which may be dragged to resize the
+/// panel.
+const RESIZE_HANDLE_WIDTH: f32 = 8.0;
+/// The minimum size of the panel resized by dragging its edges.
+const MIN_SIZE: Vector2 = Vector2(200.0, 150.0);
 
 
 // === Style ===
@@ -76,6 +83,82 @@ pub struct Style {
     breadcrumbs_padding_y: f32,
 }
 
+impl Style {
+    /// Override the size set in the theme, e.g. with the size set by the user resizing the panel.
+    fn with_size(self, size: Option<Vector2>) -> Self {
+        match size {
+            Some(size) => Self { width: size.x, height: size.y, ..self },
+            None => self,
+        }
+    }
+}
+
+/// The size of the panel after dragging its edges to the pointer position, relative to the panel's
+/// origin. The `edges` flags tell if the right and the top edge are dragged, respectively.
+fn resized(size: Vector2, pointer: Vector2, edges: (bool, bool)) -> Vector2 {
+    let (resize_width, resize_height) = edges;
+    let width = if resize_width { pointer.x.max(MIN_SIZE.x) } else { size.x };
+    let height = if resize_height { pointer.y.max(MIN_SIZE.y) } else { size.y };
+    Vector2(width, height)
+}
+
+
+// =================
+// === Callbacks ===
+// =================
+
+/// The FRP sources emitting the events of the DOM elements of the documentation panel.
+#[derive(Clone, CloneRef, Debug)]
+struct Callbacks {
+    /// Emitted when a link to another documentation page is clicked.
+    open_tab:   frp::Source<EntryDocumentation>,
+    /// Emitted when the title of a tab is clicked.
+    select_tab: frp::Source<usize>,
+    /// Emitted when the close button of a tab is clicked.
+    close_tab:  frp::Source<usize>,
+    /// Emitted when the next (`true`) or the previous (`false`) tab is selected with a keyboard.
+    cycle_tabs: frp::Source<bool>,
+    /// Emitted when the close button of the popped out panel is clicked.
+    pop_in:     frp::Source,
+}
+
+impl Callbacks {
+    fn new(network: &frp::Network) -> Self {
+        frp::extend! { network
+            open_tab <- source();
+            select_tab <- source();
+            close_tab <- source();
+            cycle_tabs <- source();
+            pop_in <- source();
+        }
+        Self { open_tab, select_tab, close_tab, cycle_tabs, pop_in }
+    }
+}
+
+/// Register a handler of the clicks on the element with the given `id`. Returns [`None`] if there
+/// is no such element.
+fn on_click(id: &str, callback: impl Fn() + 'static) -> Option<web::EventListenerHandle> {
+    let element = web::document.get_element_by_id(id)?;
+    let closure: web::JsEventHandler = web::Closure::new(move |_: JsValue| callback());
+    Some(web::add_event_listener(&element, "click", closure))
+}
+
+
+
+// ================
+// === Floating ===
+// ================
+
+/// The place of the popped out panel in the display object hierarchy before popping out.
+#[derive(Debug)]
+struct Floating {
+    /// The parent of the panel, or [`None`] if the panel had no parent.
+    parent:   Option<display::object::WeakInstance>,
+    /// The position of the panel relative to its parent.
+    position: Vector2,
+}
+
+
 
 // =============
 // === Model ===
@@ -86,15 +169,28 @@ pub struct Style {
 #[derive(Clone, CloneRef, Debug, display::Object)]
 #[allow(missing_docs)]
 pub struct Model {
+    app:             Application,
     style_container: DomSymbol,
+    /// The scrollable container of the tab strip and the displayed documentation page.
     dom:             DomSymbol,
+    /// The button closing the popped out panel, displayed only when the panel is popped out.
+    pop_in_button:   web::HtmlDivElement,
+    tab_strip:       web::HtmlDivElement,
+    content:         web::HtmlDivElement,
+    tabs:            Rc<RefCell<tabs::Tabs>>,
     pub breadcrumbs: breadcrumbs::Breadcrumbs,
     /// The purpose of this overlay is stop propagating mouse events under the documentation panel
     /// to EnsoGL shapes, and pass them to the DOM instead.
     overlay:         Rectangle,
     background:      Rectangle,
+    right_handle:    Rectangle,
+    top_handle:      Rectangle,
     display_object:  display::object::Instance,
+    floating:        Rc<RefCell<Option<Floating>>>,
     event_handlers:  Rc<RefCell<Vec<web::EventListenerHandle>>>,
+    tab_handlers:    Rc<RefCell<Vec<web::EventListenerHandle>>>,
+    /// The handlers of the events of the whole panel, registered once.
+    panel_handlers:  Rc<RefCell<Vec<web::EventListenerHandle>>>,
 }
 
 impl Model {
@@ -106,10 +202,25 @@ impl Model {
         let style_container = DomSymbol::new(&style_div);
         let div = web::document.create_div_or_panic();
         let dom = DomSymbol::new(&div);
+        let pop_in_button = web::document.create_div_or_panic();
+        pop_in_button.set_class_name("pop-in-button");
+        pop_in_button.set_inner_html("&times;");
+        pop_in_button.set_style_or_warn("display", "none");
+        let tab_strip = web::document.create_div_or_panic();
+        let content = web::document.create_div_or_panic();
+        dom.append_or_warn(&pop_in_button);
+        dom.append_or_warn(&tab_strip);
+        dom.append_or_warn(&content);
         let background = Rectangle::new();
         let overlay = Rectangle::new().build(|r| {
             r.set_color(INVISIBLE_HOVER_COLOR);
         });
+        let right_handle = Rectangle::new().build(|r| {
+            r.set_color(INVISIBLE_HOVER_COLOR);
+        });
+        let top_handle = Rectangle::new().build(|r| {
+            r.set_color(INVISIBLE_HOVER_COLOR);
+        });
 
         let breadcrumbs = app.new_view::<breadcrumbs::Breadcrumbs>();
         breadcrumbs.set_base_layer(&app.display.default_scene.layers.node_searcher);
@@ -120,23 +231,38 @@ impl Model {
         dom.dom().set_style_or_warn("overflow-y", "auto");
         dom.dom().set_style_or_warn("overflow-x", "auto");
         dom.dom().set_style_or_warn("pointer-events", "auto");
+        // Make the panel focusable, so it receives the keyboard events after being clicked.
+        dom.dom().set_attribute_or_warn("tabindex", "-1");
+        dom.dom().set_style_or_warn("outline", "none");
 
         display_object.add_child(&background);
         display_object.add_child(&style_container);
         display_object.add_child(&dom);
         display_object.add_child(&overlay);
+        display_object.add_child(&right_handle);
+        display_object.add_child(&top_handle);
 
         scene.dom.layers.node_searcher.manage(&style_container);
         scene.dom.layers.node_searcher.manage(&dom);
 
         Model {
+            app: app.clone_ref(),
             style_container,
             dom,
+            pop_in_button,
+            tab_strip,
+            content,
+            tabs: default(),
             breadcrumbs,
             overlay,
             background,
+            right_handle,
+            top_handle,
             display_object,
+            floating: default(),
             event_handlers: default(),
+            tab_handlers: default(),
+            panel_handlers: default(),
         }
         .init()
     }
@@ -155,7 +281,8 @@ impl Model {
     }
 
     fn set_initial_breadcrumbs(&self) {
-        let breadcrumb = breadcrumbs::Breadcrumb::new(INITIAL_SECTION_NAME, None);
+        let section_name = self.app.translator().translate(INITIAL_SECTION_NAME);
+        let breadcrumb = breadcrumbs::Breadcrumb::new(&section_name, None);
         self.breadcrumbs.set_entries(vec![breadcrumb]);
         self.breadcrumbs.show_ellipsis(false);
     }
@@ -172,6 +299,48 @@ impl Model {
             .set_xy(Vector2(style.breadcrumbs_padding_x, size.y - style.breadcrumbs_height));
         self.breadcrumbs.frp().set_size(Vector2(visible_part.x, style.breadcrumbs_height));
         self.background.set_size(visible_part);
+        self.right_handle.set_size(Vector2(RESIZE_HANDLE_WIDTH, size.y));
+        self.right_handle.set_xy(Vector2(visible_part.x - RESIZE_HANDLE_WIDTH / 2.0, 0.0));
+        self.top_handle.set_size(Vector2(visible_part.x, RESIZE_HANDLE_WIDTH));
+        self.top_handle.set_xy(Vector2(0.0, size.y - RESIZE_HANDLE_WIDTH / 2.0));
+    }
+
+    /// Check if the mouse pointer is over the panel.
+    fn is_this_target(&self, target: display::scene::PointerTargetId) -> bool {
+        let shapes = [&self.overlay, &self.right_handle, &self.top_handle];
+        shapes.iter().any(|shape| shape.is_this_target(target))
+    }
+
+    /// Detach the panel from its parent and display it in the same place of the scene, so it stays
+    /// visible when its parent is hidden. Returns `false` if the panel is already popped out.
+    fn pop_out(&self) -> bool {
+        if self.floating.borrow().is_some() {
+            return false;
+        }
+        let parent = self.display_object.parent().map(|parent| parent.downgrade());
+        let position = self.display_object.position().xy();
+        let global_position = self.display_object.global_position().xy();
+        self.app.display.default_scene.add_child(&self.display_object);
+        self.display_object.set_xy(global_position);
+        self.pop_in_button.set_style_or_warn("display", "block");
+        *self.floating.borrow_mut() = Some(Floating { parent, position });
+        true
+    }
+
+    /// Return the popped out panel to its previous parent. If the parent no longer exists, the
+    /// panel is removed from the scene. Returns `false` if the panel is not popped out.
+    fn pop_in(&self) -> bool {
+        let floating = self.floating.borrow_mut().take();
+        let Some(floating) = floating else { return false };
+        match floating.parent.and_then(|parent| parent.upgrade()) {
+            Some(parent) => {
+                parent.add_child(&self.display_object);
+                self.display_object.set_xy(floating.position);
+            }
+            None => self.display_object.unset_parent(),
+        }
+        self.pop_in_button.set_style_or_warn("display", "none");
+        true
     }
 
     /// Set the fraction of visible documentation panel. Used to animate showing/hiding the panel.
@@ -183,42 +352,119 @@ impl Model {
         self.size_changed(size, fraction, style);
     }
 
-    /// Display the documentation and scroll to default position.
-    fn display_doc(&self, docs: EntryDocumentation, display_doc: &frp::Source<EntryDocumentation>) {
-        let linked_pages = docs.linked_doc_pages();
-        let html = html::render(&docs);
-        self.dom.dom().set_inner_html(&html);
-        self.set_link_handlers(linked_pages, display_doc);
-        // Scroll to the top of the page.
-        self.dom.dom().set_scroll_top(0);
+    /// Close all the tabs and display the documentation in a single one.
+    fn display_doc(&self, docs: EntryDocumentation, callbacks: &Callbacks) {
+        self.tabs.borrow_mut().reset(docs);
+        self.display_selected_tab(callbacks);
+    }
+
+    /// Open the linked documentation page in a new tab, or select its tab if it is already open.
+    fn open_tab(&self, docs: EntryDocumentation, callbacks: &Callbacks) {
+        self.update_tabs(callbacks, |tabs| tabs.open(docs));
+    }
+
+    fn select_tab(&self, index: usize, callbacks: &Callbacks) {
+        self.update_tabs(callbacks, |tabs| tabs.select(index));
+    }
+
+    fn close_tab(&self, index: usize, callbacks: &Callbacks) {
+        self.update_tabs(callbacks, |tabs| tabs.close(index));
+    }
+
+    fn cycle_tabs(&self, forward: bool, callbacks: &Callbacks) {
+        self.update_tabs(callbacks, |tabs| tabs.cycle(forward));
+    }
+
+    /// Modify the tabs, remembering the scroll position of the selected one first. The tabs are
+    /// displayed again if `f` returns `true`.
+    fn update_tabs(&self, callbacks: &Callbacks, f: impl FnOnce(&mut tabs::Tabs) -> bool) {
+        let scroll_top = self.dom.dom().scroll_top();
+        let changed = {
+            let mut tabs = self.tabs.borrow_mut();
+            tabs.set_scroll_top(scroll_top);
+            f(&mut tabs)
+        };
+        if changed {
+            self.display_selected_tab(callbacks);
+        }
+    }
+
+    /// Display the tab strip and the documentation of the selected tab, and restore its scroll
+    /// position.
+    fn display_selected_tab(&self, callbacks: &Callbacks) {
+        let tabs = self.tabs.borrow();
+        let tab = tabs.selected();
+        let linked_pages = tab.docs.linked_doc_pages();
+        let html = html::render(&tab.docs, &self.app.translator());
+        self.content.set_inner_html(&html);
+        self.set_link_handlers(linked_pages, &callbacks.open_tab);
+        self.display_tab_strip(&tabs, callbacks);
+        self.dom.dom().set_scroll_top(tab.scroll_top);
+    }
+
+    /// Display the tab strip, unless there is only a single tab open.
+    fn display_tab_strip(&self, tabs: &tabs::Tabs, callbacks: &Callbacks) {
+        if tabs.is_single() {
+            self.tab_strip.set_inner_html("");
+            self.tab_handlers.borrow_mut().clear();
+        } else {
+            let html = html::render_tab_strip(tabs, &self.app.translator());
+            self.tab_strip.set_inner_html(&html);
+            let select_tab = &callbacks.select_tab;
+            let close_tab = &callbacks.close_tab;
+            let new_handlers = (0..tabs.iter().count()).flat_map(|index| {
+                let select_anchor = html::tab_anchor_name(index);
+                let close_anchor = html::tab_close_anchor_name(index);
+                let select = on_click(&select_anchor, f!([select_tab] select_tab.emit(index)));
+                let close = on_click(&close_anchor, f!([close_tab] close_tab.emit(index)));
+                select.into_iter().chain(close)
+            });
+            let _ = self.tab_handlers.replace(new_handlers.collect());
+        }
     }
 
     /// Setup event handlers for links on the documentation page.
     fn set_link_handlers(
         &self,
         linked_pages: Vec<LinkedDocPage>,
-        display_doc: &frp::Source<EntryDocumentation>,
+        open_tab: &frp::Source<EntryDocumentation>,
     ) {
         let new_handlers = linked_pages.into_iter().filter_map(|page| {
             let content = page.page.clone_ref();
             let anchor = html::anchor_name(&page.name);
-            if let Some(element) = web::document.get_element_by_id(&anchor) {
-                let closure: web::JsEventHandler = web::Closure::new(f_!([display_doc, content] {
-                    display_doc.emit(content.clone_ref());
-                }));
-                Some(web::add_event_listener(&element, "click", closure))
-            } else {
-                None
-            }
+            on_click(&anchor, f!([open_tab, content] open_tab.emit(content.clone_ref())))
         });
         let _ = self.event_handlers.replace(new_handlers.collect());
     }
 
+    /// Setup event handlers of the whole panel: the keyboard shortcuts cycling the tabs, which
+    /// work when the panel is focused, and the button closing the popped out panel.
+    fn set_panel_handlers(&self, callbacks: &Callbacks) {
+        let cycle_tabs = callbacks.cycle_tabs.clone_ref();
+        let closure: web::JsEventHandler = web::Closure::new(move |event: JsValue| {
+            let event = event.unchecked_into::<web::KeyboardEvent>();
+            let forward = match event.key().as_str() {
+                "ArrowRight" => Some(true),
+                "ArrowLeft" => Some(false),
+                _ => None,
+            };
+            if let Some(forward) = forward.filter(|_| event.alt_key()) {
+                event.prevent_default();
+                cycle_tabs.emit(forward);
+            }
+        });
+        let key_handler = web::add_event_listener(self.dom.dom(), "keydown", closure);
+        let pop_in = &callbacks.pop_in;
+        let closure: web::JsEventHandler = web::Closure::new(f_!([pop_in] pop_in.emit(())));
+        let pop_in_handler = web::add_event_listener(&self.pop_in_button, "click", closure);
+        let _ = self.panel_handlers.replace(vec![key_handler, pop_in_handler]);
+    }
+
     /// Load an HTML file into the documentation view when user is waiting for data to be received.
     /// TODO(#5214): This should be replaced with a EnsoGL spinner.
     fn load_waiting_screen(&self) {
         let spinner = include_str!("../assets/spinner.html");
-        self.dom.dom().set_inner_html(spinner)
+        self.content.set_inner_html(spinner)
     }
 
     fn update_style(&self, style: Style) {
@@ -245,6 +491,22 @@ ensogl::define_endpoints! {
         set_visible(bool),
         /// Skip show/hide animation.
         skip_animation(),
+        /// Select the tab with the given index.
+        select_tab (usize),
+        /// Close the tab with the given index. The last open tab cannot be closed.
+        close_tab (usize),
+        /// Select the next tab. The selection wraps around at the end of the tab strip.
+        select_next_tab (),
+        /// Select the previous tab. The selection wraps around at the beginning of the tab strip.
+        select_previous_tab (),
+        /// Override the size of the panel set in the theme, as if the panel was resized by the
+        /// user.
+        set_size (Vector2),
+        /// Detach the panel from its parent and display it as a floating panel, which stays
+        /// visible when the parent is hidden.
+        pop_out (),
+        /// Return the floating panel to its previous parent.
+        pop_in (),
     }
     Output {
         /// Indicates whether the documentation panel has been selected through clicking into
@@ -252,6 +514,10 @@ ensogl::define_endpoints! {
         is_selected(bool),
         /// Indicates whether the documentation panel has been hovered.
         is_hovered(bool),
+        /// The size of the panel, either set in the theme or resized by the user.
+        size(Vector2),
+        /// Indicates whether the documentation panel is popped out.
+        is_popped_out(bool),
     }
 }
 
@@ -297,8 +563,14 @@ impl View {
         let frp = &self.frp;
         let display_delay = frp::io::timer::Timeout::new(network);
         let style_frp = StyleWatchFrp::new(&scene.style_sheet);
-        let style = Style::from_theme(network, &style_frp);
+        let theme_style = Style::from_theme(network, &style_frp);
         let width_anim = Animation::new(network);
+        let callbacks = Callbacks::new(network);
+        model.set_panel_handlers(&callbacks);
+        let mouse = &scene.mouse.frp_deprecated;
+        let mouse_up = scene.on_event::<mouse::Up>();
+        let right_handle_down = model.right_handle.on_event::<mouse::Down>();
+        let top_handle_down = model.top_handle.on_event::<mouse::Down>();
         frp::extend! { network
 
             init <- source_();
@@ -309,16 +581,59 @@ impl View {
             docs <+ frp.display_documentation;
             display_delay.restart <+ frp.display_documentation.constant(DISPLAY_DELAY_MS);
             display_docs <- display_delay.on_expired.map2(&docs,|_,docs| docs.clone_ref());
-            display_docs_callback <- source();
-            display_docs <- any(&display_docs, &display_docs_callback);
-            eval display_docs([model, display_docs_callback]
-                (docs) model.display_doc(docs.clone_ref(), &display_docs_callback)
+            eval display_docs([model, callbacks]
+                (docs) model.display_doc(docs.clone_ref(), &callbacks)
             );
 
 
+            // === Tabs ===
+
+            eval callbacks.open_tab([model, callbacks]
+                (docs) model.open_tab(docs.clone_ref(), &callbacks)
+            );
+            select_tab <- any(&frp.select_tab, &callbacks.select_tab);
+            eval select_tab([model, callbacks](index) model.select_tab(*index, &callbacks));
+            close_tab <- any(&frp.close_tab, &callbacks.close_tab);
+            eval close_tab([model, callbacks](index) model.close_tab(*index, &callbacks));
+            cycle_tabs <- any(...);
+            cycle_tabs <+ frp.select_next_tab.constant(true);
+            cycle_tabs <+ frp.select_previous_tab.constant(false);
+            cycle_tabs <+ callbacks.cycle_tabs;
+            eval cycle_tabs([model, callbacks](forward) model.cycle_tabs(*forward, &callbacks));
+
+
             // === Size ===
 
+            user_size <- any_mut::<Option<Vector2>>();
+            user_size <+ init.constant(None);
+            user_size <+ frp.set_size.some();
+            style <- all_with(&theme_style, &user_size, |style, size| style.with_size(*size));
             size <- style.map(|s| Vector2(s.width, s.height));
+            frp.source.size <+ size.on_change();
+
+
+            // === Resizing ===
+
+            resize_width_start <- right_handle_down.filter(|e| e.button() == mouse::PrimaryButton);
+            resize_height_start <- top_handle_down.filter(|e| e.button() == mouse::PrimaryButton);
+            resizing_width <- bool(&mouse_up, &resize_width_start);
+            resizing_height <- bool(&mouse_up, &resize_height_start);
+            is_resizing <- resizing_width || resizing_height;
+            resized_edges <- all(&resizing_width, &resizing_height);
+            resize_pointer <- mouse.position.gate(&is_resizing).map(f!([scene, model](position) {
+                scene.screen_to_object_space(model.display_object(), *position)
+            }));
+            user_size <+ resize_pointer.map3(&size, &resized_edges, |pointer, size, edges| {
+                Some(resized(*size, *pointer, *edges))
+            });
+
+
+            // === Pop-out ===
+
+            pop_in <- any(&frp.pop_in, &callbacks.pop_in);
+            popped_out <- frp.pop_out.map(f_!(model.pop_out())).on_true();
+            popped_in <- pop_in.map(f_!(model.pop_in())).on_true();
+            frp.source.is_popped_out <+ bool(&popped_in, &popped_out);
 
 
             // === Style ===
@@ -338,7 +653,7 @@ impl View {
 
             mouse_down_target <- scene.mouse.frp_deprecated.down.map(f_!(scene.mouse.target.get()));
             selected <- mouse_down_target.map(f!([model,visualization] (target){
-                if !model.overlay.is_this_target(*target) {
+                if !model.is_this_target(*target) {
                     visualization.deactivate.emit(());
                     false
                 } else {
@@ -392,3 +707,26 @@ impl From<View> for visualization::Instance {
         )
     }
 }
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resizing_panel() {
+        let size = Vector2(400.0, 300.0);
+        let pointer = Vector2(500.0, 50.0);
+        assert_eq!(resized(size, pointer, (true, false)), Vector2(500.0, 300.0));
+        assert_eq!(resized(size, pointer, (false, true)), Vector2(400.0, MIN_SIZE.y));
+        assert_eq!(resized(size, pointer, (true, true)), Vector2(500.0, MIN_SIZE.y));
+        let style = Style { width: 1.0, height: 2.0, ..default() };
+        assert_eq!(style.with_size(None).width, 1.0);
+        assert_eq!(style.with_size(Some(size)).height, 300.0);
+    }
+}
@@ -0,0 +1,169 @@
+//! The tabs of the documentation panel. Every page opened by following a link is displayed in its
+//! own tab, so the user can go back to the previous pages, e.g. to compare two types.
+
+use ensogl::prelude::*;
+
+use enso_suggestion_database::documentation_ir::EntryDocumentation;
+
+
+
+// ===========
+// === Tab ===
+// ===========
+
+/// A single documentation page opened in the panel.
+#[derive(Clone, Debug, Default)]
+pub struct Tab {
+    /// The displayed documentation.
+    pub docs:       EntryDocumentation,
+    /// The vertical scroll position of the page, restored when the tab is selected again.
+    pub scroll_top: i32,
+}
+
+impl Tab {
+    /// Constructor. The page is scrolled to the top.
+    pub fn new(docs: EntryDocumentation) -> Self {
+        Self { docs, scroll_top: 0 }
+    }
+}
+
+
+
+// ============
+// === Tabs ===
+// ============
+
+/// The list of the open tabs, with one of them selected. There is always at least one tab open.
+///
+/// The methods modifying the tabs return `true` if anything has changed, and the tabs should be
+/// displayed again.
+#[derive(Clone, Debug)]
+pub struct Tabs {
+    tabs:     Vec<Tab>,
+    selected: usize,
+}
+
+impl Default for Tabs {
+    fn default() -> Self {
+        Self::new(default())
+    }
+}
+
+impl Tabs {
+    /// Constructor. The only open tab displays the given documentation.
+    pub fn new(docs: EntryDocumentation) -> Self {
+        Self { tabs: vec![Tab::new(docs)], selected: 0 }
+    }
+
+    /// Close all the tabs and open a single one, displaying the given documentation.
+    pub fn reset(&mut self, docs: EntryDocumentation) {
+        *self = Self::new(docs);
+    }
+
+    /// Open a tab with the given documentation next to the selected one, and select it. If the
+    /// documentation is already open, its tab is selected instead.
+    pub fn open(&mut self, docs: EntryDocumentation) -> bool {
+        match self.tabs.iter().position(|tab| tab.docs == docs) {
+            Some(index) => self.select(index),
+            None => {
+                self.selected += 1;
+                self.tabs.insert(self.selected, Tab::new(docs));
+                true
+            }
+        }
+    }
+
+    /// Close the tab with the given index. The last open tab cannot be closed.
+    pub fn close(&mut self, index: usize) -> bool {
+        if self.tabs.len() <= 1 || index >= self.tabs.len() {
+            return false;
+        }
+        self.tabs.remove(index);
+        if index < self.selected || self.selected == self.tabs.len() {
+            self.selected -= 1;
+        }
+        true
+    }
+
+    /// Select the tab with the given index.
+    pub fn select(&mut self, index: usize) -> bool {
+        let changed = index < self.tabs.len() && index != self.selected;
+        if changed {
+            self.selected = index;
+        }
+        changed
+    }
+
+    /// Select the next tab, or the previous one if `forward` is `false`. The selection wraps
+    /// around at the ends of the list.
+    pub fn cycle(&mut self, forward: bool) -> bool {
+        let count = self.tabs.len();
+        let offset = if forward { 1 } else { count - 1 };
+        self.select((self.selected + offset) % count)
+    }
+
+    /// The selected tab.
+    pub fn selected(&self) -> &Tab {
+        &self.tabs[self.selected]
+    }
+
+    /// The index of the selected tab.
+    pub fn selected_index(&self) -> usize {
+        self.selected
+    }
+
+    /// Remember the scroll position of the selected tab.
+    pub fn set_scroll_top(&mut self, scroll_top: i32) {
+        self.tabs[self.selected].scroll_top = scroll_top;
+    }
+
+    /// Iterate over the open tabs, in the displayed order.
+    pub fn iter(&self) -> impl Iterator<Item = &Tab> {
+        self.tabs.iter()
+    }
+
+    /// Check if there is only a single tab open. Such a tab is displayed without the tab strip.
+    pub fn is_single(&self) -> bool {
+        self.tabs.len() == 1
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use enso_doc_parser::DocSection;
+
+    fn page(text: &str) -> EntryDocumentation {
+        EntryDocumentation::builtin(&[DocSection::Paragraph { body: text.into() }])
+    }
+
+    #[test]
+    fn opening_and_closing_tabs() {
+        let mut tabs = Tabs::new(page("a"));
+        assert!(!tabs.close(0));
+        assert!(tabs.open(page("b")));
+        tabs.set_scroll_top(100);
+        assert!(tabs.select(0));
+        assert!(tabs.open(page("c")));
+        assert_eq!(tabs.selected_index(), 1);
+        assert!(tabs.open(page("b")));
+        assert_eq!(tabs.selected_index(), 2);
+        assert_eq!(tabs.selected().scroll_top, 100);
+        assert!(tabs.cycle(true));
+        assert_eq!(tabs.selected().docs, page("a"));
+        assert!(tabs.cycle(false));
+        assert_eq!(tabs.selected().docs, page("b"));
+        assert!(tabs.close(1));
+        assert_eq!(tabs.selected().docs, page("b"));
+        assert!(tabs.close(1));
+        assert_eq!(tabs.selected().docs, page("a"));
+        assert!(tabs.is_single());
+    }
+}
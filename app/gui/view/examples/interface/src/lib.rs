@@ -116,21 +116,24 @@ fn init(app: &Application) {
     let kind = Immutable(graph_editor::component::node::error::Kind::Panic);
     let message = Rc::new(Some("Runtime Error".to_owned()));
     let propagated = Immutable(false);
-    let error = graph_editor::component::node::Error { kind, message, propagated };
+    let stack = default();
+    let error = graph_editor::component::node::Error { kind, message, propagated, stack };
     graph_editor.frp.set_node_error_status.emit((node3_id, Some(error)));
 
     let foo_node = graph_editor.model.add_node_below(node3_id);
     graph_editor.set_node_expression.emit((foo_node, Expression::new_plain("foo")));
     let kind = Immutable(graph_editor::component::node::error::Kind::Dataflow);
     let message = Rc::new(Some("Dataflow Error".to_owned()));
-    let error = graph_editor::component::node::Error { kind, message, propagated };
+    let stack = default();
+    let error = graph_editor::component::node::Error { kind, message, propagated, stack };
     graph_editor.frp.set_node_error_status.emit((foo_node, Some(error)));
 
     let baz_node = graph_editor.model.add_node_below(node3_id);
     graph_editor.set_node_expression.emit((baz_node, Expression::new_plain("baz")));
     let kind = Immutable(graph_editor::component::node::error::Kind::Warning);
     let message = Rc::new(Some("Warning".to_owned()));
-    let error = graph_editor::component::node::Error { kind, message, propagated };
+    let stack = default();
+    let error = graph_editor::component::node::Error { kind, message, propagated, stack };
     graph_editor.frp.set_node_error_status.emit((baz_node, Some(error)));
     let (_, baz_position) = graph_editor.node_position_set.value();
     let styles = StyleWatch::new(&scene.style_sheet);
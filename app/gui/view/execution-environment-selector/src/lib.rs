@@ -26,6 +26,7 @@ use enso_prelude::*;
 use ensogl::prelude::*;
 
 use enso_frp as frp;
+use ensogl::application::tooltip;
 use ensogl::application::Application;
 use ensogl::data::color::Rgba;
 use ensogl::data::text;
@@ -73,26 +74,98 @@ impl Style {
 // === FRP ===
 // ===========
 
-/// An identifier of a execution environment.
+/// An identifier of a execution environment supported by the engine.
 pub type ExecutionEnvironment = engine_protocol::language_server::ExecutionEnvironment;
 
+/// A named execution environment available for selection.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExecutionEnvironmentDef {
+    /// The name displayed in the selector.
+    pub name:                   ImString,
+    /// Whether the functions with side effects are executed in this environment.
+    pub output_context_enabled: bool,
+    /// The reason why the environment cannot be selected, like "Requires cloud credentials". The
+    /// unavailable environments are greyed out in the selector, with the reason in their tooltip.
+    pub unavailable_reason:     Option<ImString>,
+}
+
+impl ExecutionEnvironmentDef {
+    /// Constructor.
+    pub fn new(name: impl Into<ImString>, output_context_enabled: bool) -> Self {
+        Self { name: name.into(), output_context_enabled, unavailable_reason: None }
+    }
+
+    /// Mark the environment as unavailable for the given reason.
+    pub fn unavailable(self, reason: impl Into<ImString>) -> Self {
+        Self { unavailable_reason: Some(reason.into()), ..self }
+    }
+
+    /// Check whether the environment can be selected.
+    pub fn is_available(&self) -> bool {
+        self.unavailable_reason.is_none()
+    }
+
+    /// The engine's execution environment with the same output context setting. The engine
+    /// distinguishes the environments only by the output context, so every user-defined
+    /// environment is run in one of them.
+    pub fn engine_environment(&self) -> ExecutionEnvironment {
+        if self.output_context_enabled {
+            ExecutionEnvironment::Live
+        } else {
+            ExecutionEnvironment::Design
+        }
+    }
+}
+
+impl From<ExecutionEnvironment> for ExecutionEnvironmentDef {
+    fn from(environment: ExecutionEnvironment) -> Self {
+        Self::new(environment.to_string(), environment.output_context_enabled())
+    }
+}
+
 /// A list of execution environments.
-pub type ExecutionEnvironments = Rc<Vec<ExecutionEnvironment>>;
+pub type ExecutionEnvironments = Rc<Vec<ExecutionEnvironmentDef>>;
 
 /// Provide a dummy list of execution environments. Used for testing and demo scenes.
 pub fn make_dummy_execution_environments() -> ExecutionEnvironments {
-    Rc::new(ExecutionEnvironment::list_all())
+    Rc::new(ExecutionEnvironment::list_all().into_iter().map(Into::into).collect())
+}
+
+/// The index of the first available environment following the `current` one, wrapping around
+/// after the last one.
+fn next_available(entries: &[ExecutionEnvironmentDef], current: usize) -> Option<usize> {
+    let following = (1..=entries.len()).map(|offset| (current + offset) % entries.len());
+    following.find(|&index| entries[index].is_available())
 }
 
 ensogl::define_endpoints_2! {
     Input {
         set_available_execution_environments      (ExecutionEnvironments),
-        set_execution_environment                 (ExecutionEnvironment),
+        /// Display a spinner instead of the selected environment until the available execution
+        /// environments are set. Used when they are not known immediately, e.g. when they depend
+        /// on the credentials checked by the backend.
+        mark_execution_environments_loading       (),
+        set_execution_environment                 (ExecutionEnvironmentDef),
+        /// Select the available execution environment following the currently selected one. After
+        /// the last environment, the first one is selected.
+        cycle_execution_environment               (),
         reset_play_button_state (),
+        /// Display the play button as running, e.g. when the execution was started by other means
+        /// than the play button.
+        set_execution_in_progress (bool),
+        /// Set the progress of the running execution, from 0 to 1, or `None` if unknown.
+        set_execution_progress    (Option<f32>),
     }
     Output {
-        selected_execution_environment (ExecutionEnvironment),
+        /// The selected environment, either chosen by the user or changed through the inputs.
+        selected_execution_environment      (ExecutionEnvironmentDef),
+        /// The environment chosen by the user in the dropdown.
+        user_selected_execution_environment (ExecutionEnvironmentDef),
+        /// Whether the available execution environments are being loaded.
+        loading                             (bool),
         play_press(),
+        /// The play button was pressed while the execution was running.
+        execution_cancel_pressed(),
         size(Vector2),
     }
 }
@@ -115,6 +188,7 @@ pub struct Model {
     divider:        display::shape::compound::rectangle::Rectangle,
     play_button:    play_button::PlayButton,
     dropdown:       ensogl_drop_down_menu::DropDownMenu,
+    spinner:        play_button::spinner_icon::View,
 }
 
 impl Model {
@@ -128,6 +202,9 @@ impl Model {
         self.dropdown.set_icon_size(Vector2::new(1.0, 1.0));
         self.dropdown.set_menu_alignment(ensogl_drop_down_menu::Alignment::Right);
         self.dropdown.set_label_alignment(ensogl_drop_down_menu::Alignment::Left);
+        let spinner_size = style.height - 2.0 * style.divider_padding;
+        self.spinner.set_size(Vector2(spinner_size, spinner_size));
+        self.spinner.set_xy(Vector2(style.divider_padding, style.divider_padding));
     }
 
     fn update_background_style(&self, style: &Style) {
@@ -149,12 +226,26 @@ impl Model {
         self.play_button.set_x(width);
     }
 
-    fn set_entries(&self, entries: Rc<Vec<ExecutionEnvironment>>) {
-        let labels = entries.iter().map(ToString::to_string).collect_vec();
-        let labels = Rc::new(labels);
+    fn set_entries(&self, entries: &ExecutionEnvironments) {
+        let labels = entries.iter().map(|entry| {
+            let label = entry.name.to_string();
+            ensogl_list_view::entry::DisableableLabelModel::new(label, !entry.is_available())
+        });
+        let labels = Rc::new(labels.collect_vec());
         let provider = ensogl_list_view::entry::AnyModelProvider::from(labels);
         self.dropdown.set_entries(provider);
-        self.dropdown.set_selected(0);
+        let first_available = entries.iter().position(|entry| entry.is_available());
+        self.dropdown.set_selected(first_available.unwrap_or_default());
+    }
+
+    fn set_loading(&self, loading: bool) {
+        if loading {
+            self.inner_root.remove_child(&self.dropdown);
+            self.inner_root.add_child(&self.spinner);
+        } else {
+            self.inner_root.remove_child(&self.spinner);
+            self.inner_root.add_child(&self.dropdown);
+        }
     }
 
     fn set_play_button_visibility(&self, visible: bool) {
@@ -188,6 +279,7 @@ impl component::Model for Model {
         let divider = default();
         let play_button = play_button::PlayButton::new(app);
         let dropdown = ensogl_drop_down_menu::DropDownMenu::new(app);
+        let spinner = play_button::spinner_icon::View::new();
 
         display_object.add_child(&inner_root);
         inner_root.add_child(&dropdown);
@@ -198,10 +290,11 @@ impl component::Model for Model {
         scene.layers.panel.add(&inner_root);
         scene.layers.panel.add(&dropdown);
         scene.layers.panel.add(&divider);
+        scene.layers.panel.add(&spinner);
         scene.layers.panel_background_rect_level_1.add(&background);
         dropdown.set_label_layer(&scene.layers.panel_text);
 
-        Self { display_object, background, play_button, dropdown, inner_root, divider }
+        Self { display_object, background, play_button, dropdown, inner_root, divider, spinner }
     }
 }
 
@@ -209,7 +302,7 @@ impl component::Frp<Model> for Frp {
     fn init(
         network: &enso_frp::Network,
         frp: &<Self as ensogl::application::frp::API>::Private,
-        _app: &Application,
+        app: &Application,
         model: &Model,
         style_watch: &StyleWatchFrp,
     ) {
@@ -217,6 +310,7 @@ impl component::Frp<Model> for Frp {
         let play_button = &model.play_button;
         let input = &frp.input;
         let output = &frp.output;
+        let available_environments = &input.set_available_execution_environments;
 
         let style = Style::from_theme(network, style_watch);
 
@@ -232,28 +326,58 @@ impl component::Frp<Model> for Frp {
 
             // == Inputs ==
 
-            eval input.set_available_execution_environments ((entries) model.set_entries(entries.clone()));
+            eval input.set_available_execution_environments ((entries) model.set_entries(entries));
+
+            loading <- bool(available_environments, &input.mark_execution_environments_loading);
+            loading <- loading.on_change();
+            eval loading ((loading) model.set_loading(*loading));
+            output.loading <+ loading;
 
             update_selected_entry <- input.set_execution_environment.map2(&input.set_available_execution_environments, |entry, entries| {
-                    entries.iter().position(|mode| mode == entry)
+                    entries.iter().position(|env| env.name == entry.name)
             });
             dropdown.frp.set_selected <+ update_selected_entry;
 
             selected_id <- dropdown.frp.chosen_entry.unwrap();
             selection <- all(input.set_available_execution_environments, selected_id);
-            selected_entry <- selection.map(|(entries, entry_id)| entries[*entry_id]);
+            selected_entry <- selection.filter_map(|(entries, entry_id)| entries.get(*entry_id).cloned());
             output.selected_execution_environment <+ selected_entry.on_change();
 
-            eval selected_entry ([model] (execution_mode) {
-                let play_button_visibility = matches!(execution_mode, ExecutionEnvironment::Design);
-                model.set_play_button_visibility(play_button_visibility);
+            user_selection <- dropdown.frp.entry_chosen_by_user.map2(available_environments,
+                |entry_id, entries| entries.get(*entry_id).cloned()
+            );
+            output.user_selected_execution_environment <+ user_selection.unwrap();
+
+            cycle_from <- selection.sample(&input.cycle_execution_environment).gate_not(&loading);
+            dropdown.frp.set_selected <+ cycle_from.map(|(entries, entry_id)| {
+                next_available(entries, *entry_id)
+            });
+
+
+            // == Unavailable Environments Tooltip ==
+
+            hovered_entry <- dropdown.frp.hovered_entry.map2(available_environments,
+                |entry_id, entries| entry_id.and_then(|entry_id| entries.get(entry_id)).cloned()
+            );
+            app.frp.set_tooltip <+ hovered_entry.map(|entry| {
+                match entry.as_ref().and_then(|entry| entry.unavailable_reason.as_ref()) {
+                    Some(reason) => tooltip::Style::set_label(reason.to_string()),
+                    None => tooltip::Style::unset_label(),
+                }
+            });
+
+            eval selected_entry ([model] (environment) {
+                model.set_play_button_visibility(!environment.output_context_enabled);
             });
             play_button.reset <+ selected_entry.constant(());
             play_button.reset <+ input.reset_play_button_state;
+            play_button.set_execution_in_progress <+ input.set_execution_in_progress;
+            play_button.set_execution_progress <+ input.set_execution_progress;
 
             // == Outputs ==
 
             output.play_press <+ play_button.pressed;
+            output.execution_cancel_pressed <+ play_button.cancel_pressed;
             output.size <+ style.map(|style| {
                 Vector2::new(style.overall_width(), style.height)
             }).on_change();
@@ -264,3 +388,27 @@ impl component::Frp<Model> for Frp {
 /// ExecutionEnvironmentSelector is a component that allows the user to select the execution
 /// environment of the graph.
 pub type ExecutionEnvironmentSelector = component::ComponentView<Model, Frp>;
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycling_skips_unavailable_environments() {
+        let entries = [
+            ExecutionEnvironmentDef::new("design", false),
+            ExecutionEnvironmentDef::new("cloud", true).unavailable("Requires cloud credentials"),
+            ExecutionEnvironmentDef::new("live", true),
+        ];
+        assert_eq!(next_available(&entries, 0), Some(2));
+        assert_eq!(next_available(&entries, 2), Some(0));
+        assert_eq!(next_available(&entries[..2], 0), Some(0));
+        assert_eq!(next_available(&[], 0), None);
+    }
+}
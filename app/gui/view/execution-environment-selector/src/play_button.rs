@@ -50,7 +50,9 @@ mod play_icon {
     }
 }
 
-mod spinner_icon {
+/// The spinner displayed while the workflow is executed and its progress is unknown. Also used by
+/// the selector while the available execution environments are being loaded.
+pub(crate) mod spinner_icon {
     use super::*;
 
     use std::f32::consts::FRAC_PI_3;
@@ -65,11 +67,91 @@ mod spinner_icon {
             let unit = &width / 16.0;
             let arc = RoundedArc(&unit * 5.0, (4.0 * FRAC_PI_3).radians(), &unit * 2.0);
             let rotated_arc = arc.rotate(time * speed);
-            rotated_arc.fill(color).into()
+            let bg = Rect(Var::canvas_size()).fill(INVISIBLE_HOVER_COLOR);
+            (bg + rotated_arc.fill(color)).into()
         }
     }
 }
 
+/// The ring filled proportionally to the progress of the execution.
+mod progress_icon {
+    use super::*;
+
+    use std::f32::consts::PI;
+
+    ensogl::shape! {
+        above = [display::shape::compound::rectangle::shape];
+        (style: Style, progress: f32) {
+            let color = style.get_color(theme::spinner::color);
+            let track_color = style.get_color(theme::progress::track);
+            let width = Var::<Pixels>::from("input_size.x");
+            let unit = &width / 16.0;
+            let radius = &unit * 5.0;
+            let line_width = &unit * 2.0;
+            let track = RoundedArc(radius.clone(), (2.0 * PI).radians(), line_width.clone());
+            let angle = &progress * (2.0 * PI);
+            let arc = RoundedArc(radius, angle.clone(), line_width);
+            // The arc is symmetric around the Y axis, so it is rotated to start at the top.
+            let arc = arc.rotate(&angle / 2.0);
+            let bg = Rect(Var::canvas_size()).fill(INVISIBLE_HOVER_COLOR);
+            (bg + track.fill(track_color) + arc.fill(color)).into()
+        }
+    }
+}
+
+/// The icon replacing the progress indicator when the running execution can be cancelled.
+mod stop_icon {
+    use super::*;
+
+    ensogl::shape! {
+        above = [progress_icon, spinner_icon];
+        (style: Style) {
+            let color = style.get_color(theme::color);
+            let width = Var::<Pixels>::from("input_size.x");
+            let unit = &width / 16.0;
+            let square = Rect((&unit * 7.0, &unit * 7.0)).corners_radius(unit);
+            let bg = Rect(Var::canvas_size()).fill(INVISIBLE_HOVER_COLOR);
+            (bg + square.fill(color)).into()
+        }
+    }
+}
+
+
+
+// =============
+// === State ===
+// =============
+
+/// The state of the play button.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum State {
+    /// No execution is in progress. Pressing the button starts it.
+    #[default]
+    Idle,
+    /// The execution is in progress. The progress is a number from 0 to 1, if known.
+    Running {
+        /// The part of the execution already done.
+        progress: Option<f32>,
+    },
+    /// The execution is in progress and the button is hovered. Pressing the button cancels it.
+    Cancellable,
+}
+
+impl State {
+    fn new(in_progress: bool, progress: Option<f32>, hovered: bool) -> Self {
+        match (in_progress, hovered) {
+            (false, _) => Self::Idle,
+            (true, true) => Self::Cancellable,
+            (true, false) => Self::Running { progress: progress.map(|p| p.clamp(0.0, 1.0)) },
+        }
+    }
+
+    /// Whether any execution is in progress.
+    pub fn is_running(self) -> bool {
+        self != Self::Idle
+    }
+}
+
 
 
 // ===========
@@ -78,10 +160,19 @@ mod spinner_icon {
 
 ensogl::define_endpoints_2! {
     Input {
-        reset   (),
+        /// Return to the idle state, e.g. when the execution is finished.
+        reset                     (),
+        set_execution_in_progress (bool),
+        /// Set the progress of the running execution, from 0 to 1. If unknown, a spinner is
+        /// displayed instead of the progress ring.
+        set_execution_progress    (Option<f32>),
     }
     Output {
-        pressed (),
+        /// The button was pressed to start the execution.
+        pressed        (),
+        /// The button was pressed to cancel the running execution.
+        cancel_pressed (),
+        state          (State),
     }
 }
 
@@ -96,6 +187,8 @@ pub struct Model {
     display_object: display::object::Instance,
     play_icon:      play_icon::View,
     spinner_icon:   spinner_icon::View,
+    progress_icon:  progress_icon::View,
+    stop_icon:      stop_icon::View,
 }
 
 impl Model {
@@ -103,20 +196,36 @@ impl Model {
         let triangle_size = Vector2::new(style.triangle_size, style.triangle_size);
         let padding = Vector2::new(style.padding_x, style.padding_y);
         let size = triangle_size + 2.0 * padding;
-        self.play_icon.set_size(size);
-        self.spinner_icon.set_size(size);
-        self.play_icon.set_x(-size.x / 2.0 - style.offset);
-        self.spinner_icon.set_x(-size.x / 2.0 - style.offset);
+        let x = -size.x / 2.0 - style.offset;
+        for icon in self.icons() {
+            icon.set_size(size);
+            icon.set_x(x);
+        }
+    }
+
+    fn icons(&self) -> [&display::object::Instance; 4] {
+        [
+            self.play_icon.display_object(),
+            self.spinner_icon.display_object(),
+            self.progress_icon.display_object(),
+            self.stop_icon.display_object(),
+        ]
     }
 
-    fn set_playing(&self, playing: bool) {
-        if playing {
-            self.display_object.remove_child(&self.play_icon);
-            self.display_object.add_child(&self.spinner_icon);
-        } else {
-            self.display_object.remove_child(&self.spinner_icon);
-            self.display_object.add_child(&self.play_icon);
+    fn set_state(&self, state: State) {
+        let visible_icon = match state {
+            State::Idle => self.play_icon.display_object(),
+            State::Running { progress: None } => self.spinner_icon.display_object(),
+            State::Running { progress: Some(progress) } => {
+                self.progress_icon.progress.set(progress);
+                self.progress_icon.display_object()
+            }
+            State::Cancellable => self.stop_icon.display_object(),
+        };
+        for icon in self.icons() {
+            self.display_object.remove_child(icon);
         }
+        self.display_object.add_child(visible_icon);
     }
 }
 
@@ -135,10 +244,12 @@ impl component::Model for Model {
         let display_object = display::object::Instance::new();
         let play_icon = play_icon::View::new();
         let spinner_icon = spinner_icon::View::new();
+        let progress_icon = progress_icon::View::new();
+        let stop_icon = stop_icon::View::new();
 
         display_object.add_child(&play_icon);
 
-        Self { display_object, play_icon, spinner_icon }
+        Self { display_object, play_icon, spinner_icon, progress_icon, stop_icon }
     }
 }
 
@@ -150,7 +261,7 @@ impl component::Frp<Model> for Frp {
         model: &Model,
         style_watch: &StyleWatchFrp,
     ) {
-        let play_icon = &model.play_icon;
+        let display_object = &model.display_object;
         let input = &frp.input;
         let output = &frp.output;
 
@@ -159,17 +270,57 @@ impl component::Frp<Model> for Frp {
         frp::extend! { network
             eval style ((style) model.update_style(style));
 
-            eval_ input.reset (model.set_playing(false));
-
-            let play_icon_mouse_down = play_icon.on_event::<mouse::Down>();
-            output.pressed <+ play_icon_mouse_down.constant(());
-
-            eval_ output.pressed (model.set_playing(true));
+            let mouse_down = display_object.on_event::<mouse::Down>();
+            let mouse_enter = display_object.on_event::<mouse::Enter>();
+            let mouse_leave = display_object.on_event::<mouse::Leave>();
+            hovered <- bool(&mouse_leave, &mouse_enter);
+
+            in_progress <- any(...);
+            in_progress <+ input.set_execution_in_progress;
+            in_progress <+ input.reset.constant(false);
+            progress <- any(...);
+            progress <+ input.set_execution_progress;
+            progress <+ in_progress.on_false().constant(None);
+
+            state <- all_with3(&in_progress, &progress, &hovered, |in_progress, progress, hovered| {
+                State::new(*in_progress, *progress, *hovered)
+            });
+            output.state <+ state.on_change();
+            eval output.state ((state) model.set_state(*state));
+
+            press <- output.state.sample(&mouse_down);
+            output.pressed <+ press.filter(|state| !state.is_running()).constant(());
+            output.cancel_pressed <+ press.filter(|state| state.is_running()).constant(());
+            in_progress <+ output.pressed.constant(true);
+            in_progress <+ output.cancel_pressed.constant(false);
         }
+        in_progress.emit(false);
     }
 }
 
 /// A button to execute the workflow in a fully enabled way within the current execution
 /// environment. The button should be visible in any execution environment where one or more
-/// contexts are disabled.
+/// contexts are disabled. While the execution is running, the button displays its progress and
+/// allows cancelling it.
 pub type PlayButton = component::ComponentView<Model, Frp>;
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_of_running_execution() {
+        assert_eq!(State::new(false, Some(0.5), true), State::Idle);
+        assert_eq!(State::new(true, Some(0.5), true), State::Cancellable);
+        assert_eq!(State::new(true, None, false), State::Running { progress: None });
+        assert_eq!(State::new(true, Some(1.5), false), State::Running { progress: Some(1.0) });
+        assert!(State::Cancellable.is_running());
+        assert!(!State::Idle.is_running());
+    }
+}
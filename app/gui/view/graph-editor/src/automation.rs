@@ -8,6 +8,13 @@ use super::*;
 use enso_frp::future::EventOutputExt;
 
 
+// ==============
+// === Export ===
+// ==============
+
+pub mod recorder;
+
+
 
 // =====================
 // === Node creation ===
@@ -0,0 +1,298 @@
+//! Recording the user interactions with the Graph Editor and replaying them against another Graph
+//! Editor instance. The recorded [`InteractionLog`] is serializable, so it can be attached to a bug
+//! report, or used as a script of an integration test.
+//!
+//! Only the interactions that can be reproduced through the Graph Editor's FRP inputs are
+//! recorded: the commands (usually triggered by shortcuts), and the results of the direct
+//! manipulation, like the node selection and the node positions after dragging. The raw mouse
+//! events are not recorded, as they depend on the camera position and the screen size.
+
+use crate::prelude::*;
+
+use crate::component::node;
+use crate::GraphEditor;
+use crate::NodeId;
+
+use enso_frp as frp;
+use ensogl::system::web;
+use serde::Deserialize;
+use serde::Serialize;
+use std::time::Duration;
+
+
+
+// ===============
+// === NodeRef ===
+// ===============
+
+/// A node referenced in the [`InteractionLog`]. The [`NodeId`]s differ between the Graph Editor
+/// instances, so the nodes are referenced by their ordinal: first the nodes present when the
+/// recording started, ordered by their positions, then the nodes added during the recording, in the
+/// order of addition.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct NodeRef(pub usize);
+
+/// The mapping between the [`NodeRef`]s and the [`NodeId`]s of a single Graph Editor instance.
+#[derive(Debug, Default)]
+struct NodeRefs {
+    nodes: RefCell<Vec<NodeId>>,
+}
+
+impl NodeRefs {
+    /// Reference the nodes currently present in the Graph Editor, in the order of their positions:
+    /// from the top to the bottom, and from the left to the right.
+    fn reset(&self, graph_editor: &GraphEditor) {
+        let mut nodes = graph_editor.model.nodes.all.entries();
+        nodes.sort_by(|(_, a), (_, b)| {
+            let (a, b) = (a.position(), b.position());
+            b.y.total_cmp(&a.y).then(a.x.total_cmp(&b.x))
+        });
+        *self.nodes.borrow_mut() = nodes.into_iter().map(|(id, _)| id).collect();
+    }
+
+    fn add(&self, node_id: NodeId) {
+        self.nodes.borrow_mut().push(node_id);
+    }
+
+    fn node_ref(&self, node_id: NodeId) -> Option<NodeRef> {
+        self.nodes.borrow().iter().position(|id| *id == node_id).map(NodeRef)
+    }
+
+    fn node_id(&self, node_ref: NodeRef) -> Option<NodeId> {
+        self.nodes.borrow().get(node_ref.0).copied()
+    }
+}
+
+
+
+// =============
+// === Event ===
+// =============
+
+/// A single recorded interaction.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[allow(missing_docs)]
+pub enum Event {
+    AddNode,
+    StartNodeCreation,
+    RemoveSelectedNodes,
+    CopySelectedNode,
+    PasteNode,
+    CollapseSelectedNodes,
+    EnterSelectedNode,
+    ExitNode,
+    StopEditing,
+    ToggleBreakpointForSelectedNodes,
+    CycleVisualizationForSelectedNode,
+    OpenFullscreenVisualization,
+    CloseFullscreenVisualization,
+    SelectNode(NodeRef),
+    DeselectNode(NodeRef),
+    SetNodePosition(NodeRef, (f32, f32)),
+    SetNodeExpression(NodeRef, String),
+}
+
+/// An [`Event`] with the time of its occurrence.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Entry {
+    /// The number of milliseconds since the recording started.
+    pub time:  f64,
+    /// The recorded interaction.
+    pub event: Event,
+}
+
+/// The recorded session.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct InteractionLog {
+    /// The recorded interactions, in the order of occurrence.
+    pub entries: Vec<Entry>,
+}
+
+
+
+// ================
+// === Recorder ===
+// ================
+
+#[derive(Debug, Default)]
+struct RecorderState {
+    recording:  Cell<bool>,
+    started_at: Cell<f64>,
+    nodes:      NodeRefs,
+    log:        RefCell<InteractionLog>,
+}
+
+impl RecorderState {
+    fn record(&self, event: Event) {
+        if self.recording.get() {
+            let time = web::time_from_start() - self.started_at.get();
+            self.log.borrow_mut().entries.push(Entry { time, event });
+        }
+    }
+
+    fn record_for_node(&self, node_id: NodeId, event: impl FnOnce(NodeRef) -> Event) {
+        if let Some(node_ref) = self.nodes.node_ref(node_id) {
+            self.record(event(node_ref));
+        }
+    }
+}
+
+/// Records the interactions with the Graph Editor. See the module docs.
+#[derive(Debug)]
+pub struct Recorder {
+    graph_editor: GraphEditor,
+    state:        Rc<RecorderState>,
+    _network:     frp::Network,
+}
+
+impl Recorder {
+    /// Constructor. The recorder does not record anything until [`Self::start_recording`] is
+    /// called.
+    pub fn new(graph_editor: &GraphEditor) -> Self {
+        let graph_editor = graph_editor.clone_ref();
+        let state = Rc::new(RecorderState::default());
+        let network = frp::Network::new("GraphEditorRecorder");
+        let editor = &graph_editor;
+        let input = &editor.frp.public.input;
+        frp::extend! { network
+            eval_ input.add_node (state.record(Event::AddNode));
+            eval_ input.start_node_creation (state.record(Event::StartNodeCreation));
+            eval_ input.remove_selected_nodes (state.record(Event::RemoveSelectedNodes));
+            eval_ input.copy_selected_node (state.record(Event::CopySelectedNode));
+            eval_ input.paste_node (state.record(Event::PasteNode));
+            eval_ input.collapse_selected_nodes (state.record(Event::CollapseSelectedNodes));
+            eval_ input.enter_selected_node (state.record(Event::EnterSelectedNode));
+            eval_ input.exit_node (state.record(Event::ExitNode));
+            eval_ input.stop_editing (state.record(Event::StopEditing));
+            eval_ input.toggle_breakpoint_for_selected_nodes
+                (state.record(Event::ToggleBreakpointForSelectedNodes));
+            eval_ input.cycle_visualization_for_selected_node
+                (state.record(Event::CycleVisualizationForSelectedNode));
+            eval_ input.open_fullscreen_visualization
+                (state.record(Event::OpenFullscreenVisualization));
+            eval_ input.close_fullscreen_visualization
+                (state.record(Event::CloseFullscreenVisualization));
+
+            eval editor.node_added (((id, _, _)) state.nodes.add(*id));
+            eval editor.node_selected ((id) state.record_for_node(*id, Event::SelectNode));
+            eval editor.node_deselected ((id) state.record_for_node(*id, Event::DeselectNode));
+            eval editor.node_position_set_batched ([state] ((id, position)) {
+                let position = (position.x, position.y);
+                state.record_for_node(*id, |node| Event::SetNodePosition(node, position))
+            });
+            eval editor.node_expression_set ([state] ((id, expression)) {
+                let expression = expression.to_string();
+                state.record_for_node(*id, |node| Event::SetNodeExpression(node, expression))
+            });
+        }
+        Self { graph_editor, state, _network: network }
+    }
+
+    /// Start a new recording, discarding the interactions recorded so far.
+    pub fn start_recording(&self) {
+        self.state.nodes.reset(&self.graph_editor);
+        self.state.log.take();
+        self.state.started_at.set(web::time_from_start());
+        self.state.recording.set(true);
+    }
+
+    /// Stop the recording and return the recorded interactions.
+    pub fn stop_recording(&self) -> InteractionLog {
+        self.state.recording.set(false);
+        self.state.log.take()
+    }
+
+    /// Whether the interactions are being recorded.
+    pub fn is_recording(&self) -> bool {
+        self.state.recording.get()
+    }
+}
+
+
+
+// ==============
+// === Replay ===
+// ==============
+
+/// Replay the recorded interactions against the Graph Editor, keeping the original delays between
+/// them. The Graph Editor should be in the same state as the recorded one was when the recording
+/// started. The events referencing nodes which cannot be found are skipped.
+pub async fn replay(graph_editor: &GraphEditor, log: InteractionLog) {
+    let nodes = Rc::new(NodeRefs::default());
+    nodes.reset(graph_editor);
+    let network = frp::Network::new("GraphEditorReplay");
+    frp::extend! { network
+        eval graph_editor.node_added (((id, _, _)) nodes.add(*id));
+    }
+    let started_at = web::time_from_start();
+    for entry in log.entries {
+        let elapsed = web::time_from_start() - started_at;
+        let delay = entry.time - elapsed;
+        if delay > 0.0 {
+            web::sleep(Duration::from_secs_f64(delay / 1000.0)).await;
+        }
+        replay_event(graph_editor, &nodes, entry.event);
+    }
+}
+
+fn replay_event(graph_editor: &GraphEditor, nodes: &NodeRefs, event: Event) {
+    let input = &graph_editor.frp.public.input;
+    let node = |node_ref| nodes.node_id(node_ref);
+    match event {
+        Event::AddNode => input.add_node.emit(()),
+        Event::StartNodeCreation => input.start_node_creation.emit(()),
+        Event::RemoveSelectedNodes => input.remove_selected_nodes.emit(()),
+        Event::CopySelectedNode => input.copy_selected_node.emit(()),
+        Event::PasteNode => input.paste_node.emit(()),
+        Event::CollapseSelectedNodes => input.collapse_selected_nodes.emit(()),
+        Event::EnterSelectedNode => input.enter_selected_node.emit(()),
+        Event::ExitNode => input.exit_node.emit(()),
+        Event::StopEditing => input.stop_editing.emit(()),
+        Event::ToggleBreakpointForSelectedNodes =>
+            input.toggle_breakpoint_for_selected_nodes.emit(()),
+        Event::CycleVisualizationForSelectedNode =>
+            input.cycle_visualization_for_selected_node.emit(()),
+        Event::OpenFullscreenVisualization => input.open_fullscreen_visualization.emit(()),
+        Event::CloseFullscreenVisualization => input.close_fullscreen_visualization.emit(()),
+        Event::SelectNode(node_ref) =>
+            if let Some(id) = node(node_ref) {
+                input.select_node.emit(id)
+            },
+        Event::DeselectNode(node_ref) =>
+            if let Some(id) = node(node_ref) {
+                input.deselect_node.emit(id)
+            },
+        Event::SetNodePosition(node_ref, (x, y)) =>
+            if let Some(id) = node(node_ref) {
+                input.set_node_position.emit((id, Vector2(x, y)))
+            },
+        Event::SetNodeExpression(node_ref, expression) =>
+            if let Some(id) = node(node_ref) {
+                let expression = node::Expression::new_plain(expression);
+                input.set_node_expression.emit((id, expression))
+            },
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interaction_log_roundtrips_through_json() {
+        let entries = vec![
+            Entry { time: 0.0, event: Event::AddNode },
+            Entry { time: 120.5, event: Event::SetNodePosition(NodeRef(2), (10.0, -4.5)) },
+            Entry { time: 300.0, event: Event::SetNodeExpression(NodeRef(2), "1 + 2".into()) },
+        ];
+        let log = InteractionLog { entries };
+        let json = serde_json::to_string(&log).unwrap();
+        assert_eq!(serde_json::from_str::<InteractionLog>(&json).unwrap(), log);
+    }
+}
@@ -1,4 +1,6 @@
-//! Example visualization showing the provided data as text.
+//! The visualization of the node's error. Displays the error message and, if the diagnostics
+//! contain the stack trace, an expandable list of its frames. Clicking a frame pointing to some
+//! expression is reported by the [`Error::frame_clicked`] output.
 
 use crate::component::visualization::*;
 use crate::prelude::*;
@@ -67,10 +69,24 @@ pub fn metadata() -> Metadata {
 
 /// The input for Error Visualization.
 #[allow(missing_docs)]
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct Input {
     pub kind:    Option<Kind>,
     pub message: String,
+    /// The stack trace of the error, starting with the innermost frame. Empty if the diagnostics
+    /// contain the message only.
+    #[serde(default)]
+    pub stack:   Vec<StackFrame>,
+}
+
+/// A single frame of the error's stack trace.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct StackFrame {
+    /// The description of the frame, e.g. the called method and its source location.
+    pub label:         String,
+    /// The expression evaluated in this frame, if it is known.
+    #[serde(default)]
+    pub expression_id: Option<ast::Id>,
 }
 
 
@@ -83,10 +99,12 @@ pub struct Input {
 #[derive(Clone, CloneRef, Debug, display::Object)]
 #[allow(missing_docs)]
 pub struct Error {
-    pub frp: visualization::instance::Frp,
+    pub frp:           visualization::instance::Frp,
+    /// The user clicked the stack frame pointing to the expression with the given id.
+    pub frame_clicked: frp::Source<ast::Id>,
     #[display_object]
-    model:   Model,
-    network: frp::Network,
+    model:             Model,
+    network:           frp::Network,
 }
 
 impl Deref for Error {
@@ -116,8 +134,11 @@ impl Error {
         let scene = &app.display.default_scene;
         let network = frp::Network::new("js_visualization_raw_text");
         let frp = visualization::instance::Frp::new(&network);
-        let model = Model::new(scene.clone_ref());
-        Self { frp, model, network }.init()
+        frp::extend! { network
+            frame_clicked <- source::<ast::Id>();
+        }
+        let model = Model::new(scene.clone_ref(), frame_clicked.clone_ref());
+        Self { frp, frame_clicked, model, network }.init()
     }
 
     fn init(self) -> Self {
@@ -157,27 +178,39 @@ impl Error {
 #[allow(missing_docs)]
 pub struct Model {
     #[display_object]
-    dom:       DomSymbol,
-    size:      Rc<Cell<Vector2>>,
+    dom:            DomSymbol,
+    message:        web::HtmlDivElement,
+    /// The expandable stack trace, hidden if there are no stack frames.
+    trace:          web::HtmlElement,
+    size:           Rc<Cell<Vector2>>,
     // FIXME : StyleWatch is unsuitable here, as it was designed as an internal tool for shape
     // system (#795)
-    styles:    StyleWatch,
+    styles:         StyleWatch,
     // Because the payloads (with panic messages) and visualization updates (with dataflow error
     // messages) are not synchronized, we need to keep both versions, always ready to switch them
     // when payload changes.
-    displayed: Rc<CloneCell<Kind>>,
-    messages:  SharedHashMap<Kind, ImString>,
-    scene:     Scene,
+    displayed:      Rc<CloneCell<Kind>>,
+    diagnostics:    SharedHashMap<Kind, Rc<Input>>,
+    frame_clicked:  frp::Source<ast::Id>,
+    frame_handlers: Rc<RefCell<Vec<web::EventListenerHandle>>>,
+    scene:          Scene,
 }
 
 impl Model {
     /// Constructor.
-    fn new(scene: Scene) -> Self {
+    fn new(scene: Scene, frame_clicked: frp::Source<ast::Id>) -> Self {
         let div = web::document.create_div_or_panic();
         let dom = DomSymbol::new(&div);
+        let message = web::document.create_div_or_panic();
+        let trace = web::document.create_html_element_or_panic("details");
+        trace.set_style_or_warn("display", "none");
+        trace.set_style_or_warn("margin-top", "6px");
+        dom.dom().append_or_warn(&message);
+        dom.dom().append_or_warn(&trace);
         let size = Rc::new(Cell::new(Vector2(200.0, 200.0)));
         let displayed = Rc::new(CloneCell::new(Kind::Panic));
-        let messages = default();
+        let diagnostics = default();
+        let frame_handlers = default();
 
         let styles = StyleWatch::new(&scene.style_sheet);
         let padding_text = format!("{PADDING_TEXT}px");
@@ -193,7 +226,19 @@ impl Model {
         dom.dom().set_style_or_warn("pointer-events", "auto");
 
         scene.dom.layers.back.manage(&dom);
-        Model { dom, size, styles, displayed, messages, scene }.init()
+        Model {
+            dom,
+            message,
+            trace,
+            size,
+            styles,
+            displayed,
+            diagnostics,
+            frame_clicked,
+            frame_handlers,
+            scene,
+        }
+        .init()
     }
 
     fn init(self) -> Self {
@@ -218,10 +263,11 @@ impl Model {
 
     fn set_data(&self, input: Input) {
         if let Some(kind) = input.kind {
+            let input = Rc::new(input);
             if kind == self.displayed.get() {
-                self.dom.dom().set_inner_text(&input.message);
+                self.render(Some(&input));
             }
-            self.messages.insert(kind, input.message.into());
+            self.diagnostics.insert(kind, input);
         }
         // else we don't update the text, as the node does not contain error anymore. The
         // visualization will be hidden once we receive expression update message.
@@ -234,14 +280,45 @@ impl Model {
             Kind::Dataflow => theme::dataflow::text,
             Kind::Warning => theme::warning::text,
         };
-        let default = "";
-        let opt_message = self.messages.get_cloned_ref(&new);
-        let message = opt_message.as_ref().map_or(default, |s| s.as_str());
-        self.dom.dom().set_inner_text(message);
+        let diagnostic = self.diagnostics.get_cloned_ref(&new);
+        self.render(diagnostic.as_deref());
         self.set_text_color(color_style);
         self.displayed.set(new);
     }
 
+    /// Display the message and the stack trace of the given diagnostic, or nothing if it is not
+    /// known yet.
+    fn render(&self, input: Option<&Input>) {
+        let message = input.map_or("", |input| input.message.as_str());
+        self.message.set_inner_text(message);
+        let stack = input.map_or(&[][..], |input| &input.stack[..]);
+        self.trace.set_inner_html("");
+        let mut frame_handlers = Vec::new();
+        if !stack.is_empty() {
+            let summary = web::document.create_html_element_or_panic("summary");
+            summary.set_inner_text(&format!("Stack trace ({} frames)", stack.len()));
+            summary.set_style_or_warn("cursor", "pointer");
+            self.trace.append_or_warn(&summary);
+            for frame in stack {
+                let frame_dom = web::document.create_div_or_panic();
+                frame_dom.set_inner_text(&frame.label);
+                frame_dom.set_style_or_warn("padding-left", "12px");
+                if let Some(expression_id) = frame.expression_id {
+                    frame_dom.set_style_or_warn("cursor", "pointer");
+                    frame_dom.set_style_or_warn("text-decoration", "underline");
+                    let frame_clicked = &self.frame_clicked;
+                    let closure: web::JsEventHandler =
+                        web::Closure::new(f_!([frame_clicked] frame_clicked.emit(expression_id)));
+                    frame_handlers.push(web::add_event_listener(&frame_dom, "click", closure));
+                }
+                self.trace.append_or_warn(&frame_dom);
+            }
+        }
+        let display = if stack.is_empty() { "none" } else { "block" };
+        self.trace.set_style_or_warn("display", display);
+        self.frame_handlers.replace(frame_handlers);
+    }
+
     fn reload_style(&self) {
         self.dom.set_dom_size(self.size.get());
     }
@@ -265,3 +342,32 @@ impl From<Error> for Instance {
         Self::new(&t, &t.frp, &t.network, Some(t.model.dom.clone_ref()))
     }
 }
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_preprocessor_payload() {
+        let payload = r#"{
+            "kind": "Dataflow",
+            "message": "No such method.",
+            "stack": [{"label": "at Main.foo(Main.enso:3:5-9)"}, {"label": "at Main.main"}]
+        }"#;
+        let input: Input = serde_json::from_str(payload).unwrap();
+        assert_eq!(input.kind, Some(Kind::Dataflow));
+        assert_eq!(input.message, "No such method.");
+        let labels = input.stack.iter().map(|frame| frame.label.as_str()).collect_vec();
+        assert_eq!(labels, ["at Main.foo(Main.enso:3:5-9)", "at Main.main"]);
+        assert!(input.stack.iter().all(|frame| frame.expression_id.is_none()));
+
+        let without_stack: Input = serde_json::from_str(r#"{"message": ""}"#).unwrap();
+        assert!(without_stack.stack.is_empty());
+    }
+}
@@ -0,0 +1,299 @@
+//! Pasting the data copied from other applications onto the graph canvas. The `paste` DOM events
+//! are classified into the [`ClipboardContent`] kinds, each of which is pasted as different nodes:
+//! plain code as a node with that expression, tabular data (CSV or TSV) as a table literal, and
+//! file URLs as the nodes reading the files.
+//!
+//! The nodes copied from the application itself are pasted by the controller, which reads them
+//! from the clipboard in a custom format. The content classified here is used only when the custom
+//! format is not present.
+
+use crate::prelude::*;
+
+use enso_frp as frp;
+use ensogl::system::web;
+use wasm_bindgen::JsCast;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The scheme of the URLs pointing to the local files.
+const FILE_URL_SCHEME: &str = "file://";
+/// The fully qualified name of the type used by the table literals.
+pub const TABLE_IMPORT: &str = "Standard.Table.Data.Table.Table";
+
+
+
+// ========================
+// === ClipboardContent ===
+// ========================
+
+/// The content of the clipboard pasted onto the graph canvas.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ClipboardContent {
+    /// A code snippet, pasted as a node with that expression.
+    Code(ImString),
+    /// Tabular data, pasted as a table literal. The first row is the header.
+    Table(Rc<Vec<Vec<String>>>),
+    /// The paths of the local files, each pasted as a node reading the file.
+    Files(Rc<Vec<String>>),
+}
+
+impl Default for ClipboardContent {
+    fn default() -> Self {
+        Self::Code(default())
+    }
+}
+
+impl ClipboardContent {
+    /// Classify the pasted text. Returns [`None`] if there is nothing to paste, or if the text is
+    /// neither tabular data, nor file URLs, nor a single line of code.
+    pub fn from_text(text: &str) -> Option<Self> {
+        let text = text.trim();
+        if text.is_empty() {
+            None
+        } else if let Some(files) = parse_file_urls(text) {
+            Some(Self::Files(Rc::new(files)))
+        } else if let Some(rows) = parse_table(text) {
+            Some(Self::Table(Rc::new(rows)))
+        } else if text.lines().count() == 1 {
+            Some(Self::Code(text.into()))
+        } else {
+            None
+        }
+    }
+
+    /// The expressions of the nodes the content is pasted as.
+    pub fn expressions(&self) -> Vec<String> {
+        match self {
+            Self::Code(code) => vec![code.to_string()],
+            Self::Table(rows) => {
+                let vector = |cells: &[String]| {
+                    format!("[{}]", cells.iter().map(|cell| text_literal(cell)).join(", "))
+                };
+                let mut rows = rows.iter().map(|row| vector(row));
+                let header = rows.next().unwrap_or_else(|| "[]".to_owned());
+                vec![format!("Table.from_rows {header} [{}]", rows.join(", "))]
+            }
+            Self::Files(paths) =>
+                paths.iter().map(|path| format!("Data.read {}", text_literal(path))).collect(),
+        }
+    }
+
+    /// The fully qualified name of the entity which must be imported for the expressions to work.
+    pub fn required_import(&self) -> Option<&'static str> {
+        match self {
+            Self::Table(_) => Some(TABLE_IMPORT),
+            Self::Code(_) | Self::Files(_) => None,
+        }
+    }
+}
+
+/// An Enso text literal with the given content.
+fn text_literal(text: &str) -> String {
+    let escaped = text.replace('\\', "\\\\").replace('\'', "\\'");
+    format!("'{escaped}'")
+}
+
+
+
+// ===============
+// === Parsing ===
+// ===============
+
+/// Parse the `text/uri-list`-like content, where every line is a `file://` URL. Returns [`None`]
+/// if any of the lines is not a file URL.
+fn parse_file_urls(text: &str) -> Option<Vec<String>> {
+    // Lines starting with `#` are comments in the `text/uri-list` format.
+    let urls = text.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#'));
+    let paths = urls.map(|url| url.strip_prefix(FILE_URL_SCHEME).map(file_url_path));
+    let paths: Option<Vec<String>> = paths.collect();
+    paths.filter(|paths| !paths.is_empty())
+}
+
+/// Convert the part of the file URL following the scheme to the path of the file.
+fn file_url_path(url_path: &str) -> String {
+    // The host part of the URL is empty for the local files, e.g. `file:///home/user/data.csv`.
+    let path = url_path.strip_prefix("localhost").unwrap_or(url_path);
+    let path = percent_decode(path);
+    // The Windows paths are represented as `file:///C:/data.csv`.
+    let is_windows_path = match path.as_bytes() {
+        [b'/', drive, b':', ..] => drive.is_ascii_alphabetic(),
+        _ => false,
+    };
+    if is_windows_path {
+        path[1..].to_owned()
+    } else {
+        path
+    }
+}
+
+/// Decode the `%XX` escape sequences of the URL. Invalid sequences are left as is.
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escaped = bytes.get(index + 1..index + 3).filter(|_| bytes[index] == b'%');
+        let escaped = escaped.and_then(|hex| std::str::from_utf8(hex).ok());
+        match escaped.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Parse the tab- or comma-separated values. Returns [`None`] unless there are at least two rows,
+/// each having the same number of cells, at least two.
+fn parse_table(text: &str) -> Option<Vec<Vec<String>>> {
+    let lines = text.lines().filter(|line| !line.trim().is_empty()).collect_vec();
+    let parse = |separator: char| {
+        let rows = lines.iter().map(|line| split_row(line, separator)).collect_vec();
+        let columns = rows.first()?.len();
+        let is_table = rows.len() >= 2 && columns >= 2 && rows.iter().all(|r| r.len() == columns);
+        is_table.then_some(rows)
+    };
+    parse('\t').or_else(|| parse(','))
+}
+
+/// Split the row into cells. The cells may be quoted with double quotes, to contain the separator.
+/// Double quotes inside the quoted cell are escaped by doubling them.
+fn split_row(line: &str, separator: char) -> Vec<String> {
+    let mut cells = vec![];
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(character) = chars.next() {
+        match character {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' if quoted || cell.trim().is_empty() => quoted = !quoted,
+            _ if character == separator && !quoted => {
+                cells.push(cell.trim().to_owned());
+                cell.clear();
+            }
+            _ => cell.push(character),
+        }
+    }
+    cells.push(cell.trim().to_owned());
+    cells
+}
+
+
+
+// =====================
+// === PasteListener ===
+// =====================
+
+type PasteClosure = web::Closure<dyn FnMut(web_sys::ClipboardEvent)>;
+
+/// Listens to the `paste` DOM events, emitting the pasted [`ClipboardContent`]. Only the events
+/// targeting the application canvas are handled, i.e. when no other DOM element, like the text
+/// inputs of the visualizations or of the dashboard, is focused.
+// NOTE[allow_dead] We allow dead fields here, because they keep living closures and network.
+#[derive(Clone, CloneRef, Debug)]
+pub struct PasteListener {
+    #[allow(dead_code)]
+    network:      frp::Network,
+    /// The content pasted by the user.
+    pub pasted:   frp::Source<ClipboardContent>,
+    #[allow(dead_code)]
+    paste_handle: web::EventListenerHandle,
+}
+
+impl PasteListener {
+    /// Constructor, adding the listener to the window.
+    pub fn new() -> Self {
+        let network = frp::Network::new("PasteListener");
+        frp::extend! { network
+            pasted <- source();
+        }
+        let paste: PasteClosure = web::Closure::new(f!([pasted](event: web_sys::ClipboardEvent) {
+            if Self::targets_canvas(&event) {
+                let text = event.clipboard_data().and_then(|data| data.get_data("text/plain").ok());
+                if let Some(content) = text.and_then(|text| ClipboardContent::from_text(&text)) {
+                    pasted.emit(content);
+                }
+            }
+        }));
+        let paste_handle = web::add_event_listener(&web::window, "paste", paste);
+        Self { network, pasted, paste_handle }
+    }
+
+    /// Check whether the event targets the canvas, or the document body when nothing is focused.
+    fn targets_canvas(event: &web_sys::ClipboardEvent) -> bool {
+        let target = event.target().and_then(|target| target.dyn_into::<web::HtmlElement>().ok());
+        target.map_or(false, |element| {
+            let is_body = web::document.body().as_ref() == Some(&element);
+            is_body || element.is_instance_of::<web::HtmlCanvasElement>()
+        })
+    }
+}
+
+impl Default for PasteListener {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(rows: &[&[&str]]) -> ClipboardContent {
+        let rows = rows.iter().map(|row| row.iter().map(|cell| cell.to_string()).collect());
+        ClipboardContent::Table(Rc::new(rows.collect()))
+    }
+
+    fn files(paths: &[&str]) -> ClipboardContent {
+        ClipboardContent::Files(Rc::new(paths.iter().map(|path| path.to_string()).collect()))
+    }
+
+    #[test]
+    fn classifying_pasted_text() {
+        let classify = ClipboardContent::from_text;
+        assert_eq!(classify("  \n "), None);
+        assert_eq!(classify(" 1 + 2\n"), Some(ClipboardContent::Code("1 + 2".into())));
+        assert_eq!(classify("[1, 2]"), Some(ClipboardContent::Code("[1, 2]".into())));
+        let tsv = "Name\tAge\nAlice\t30\nBob\t25";
+        let expected = table(&[&["Name", "Age"], &["Alice", "30"], &["Bob", "25"]]);
+        assert_eq!(classify(tsv), Some(expected));
+        let csv = "Name, Comment\n\"Smith, J.\", \"Said \"\"hi\"\"\"";
+        let expected = table(&[&["Name", "Comment"], &["Smith, J.", "Said \"hi\""]]);
+        assert_eq!(classify(csv), Some(expected));
+        assert_eq!(classify("a, b\nc"), None);
+        let urls = "# Dropped files\nfile:///home/user/My%20Data.csv\nfile:///C:/data.xlsx";
+        assert_eq!(classify(urls), Some(files(&["/home/user/My Data.csv", "C:/data.xlsx"])));
+        assert_eq!(classify("file:///data.csv\nhttps://example.com/data.csv"), None);
+    }
+
+    #[test]
+    fn expressions_of_pasted_content() {
+        let expressions = |content: ClipboardContent| content.expressions();
+        assert_eq!(expressions(ClipboardContent::Code("1 + 2".into())), vec!["1 + 2"]);
+        let content = table(&[&["Name", "Age"], &["O'Brien", "30"]]);
+        assert_eq!(content.required_import(), Some(TABLE_IMPORT));
+        let expected = r"Table.from_rows ['Name', 'Age'] [['O\'Brien', '30']]";
+        assert_eq!(expressions(content), vec![expected]);
+        let content = files(&["/data.csv", r"C:\data.csv"]);
+        let expected = vec![r"Data.read '/data.csv'", r"Data.read 'C:\\data.csv'"];
+        assert_eq!(expressions(content), expected);
+    }
+}
@@ -0,0 +1,134 @@
+//! A simple force-based relaxation resolving overlaps between nodes.
+//!
+//! This is used in Graph Editor to push the nodes out of the way of the dragged ones when the
+//! collision avoidance is enabled.
+
+use crate::prelude::*;
+
+use ensogl::data::bounding_box::BoundingBox;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The minimal gap kept between the pushed boxes and their obstacles.
+const MARGIN: f32 = 10.0;
+/// The maximum number of relaxation steps. Each step resolves the overlaps found in the previous
+/// one, possibly introducing new ones further away.
+const MAX_ITERATIONS: usize = 8;
+
+
+
+// ==================
+// === Relaxation ===
+// ==================
+
+/// Compute the displacements moving the `movable` boxes out of the `fixed` ones and out of each
+/// other. Overlapping movable boxes are pushed apart symmetrically, while the fixed boxes are never
+/// moved. Returns the total displacement of every movable box that had to be moved.
+pub fn relax<Id: Copy>(
+    fixed: &[BoundingBox],
+    movable: &[(Id, BoundingBox)],
+) -> Vec<(Id, Vector2)> {
+    let mut offsets = vec![Vector2::zero(); movable.len()];
+    for _ in 0..MAX_ITERATIONS {
+        let boxes = movable.iter().zip(&offsets);
+        let boxes = boxes.map(|((_, bbox), offset)| translated(bbox, *offset)).collect_vec();
+        let mut forces = vec![Vector2::zero(); movable.len()];
+        for (index, bbox) in boxes.iter().enumerate() {
+            for obstacle in fixed {
+                forces[index] += separation(obstacle, bbox);
+            }
+            for (other_index, other) in boxes.iter().enumerate().skip(index + 1) {
+                let push = separation(other, bbox) / 2.0;
+                forces[index] += push;
+                forces[other_index] -= push;
+            }
+        }
+        if forces.iter().all(|force| force.norm_squared() < f32::EPSILON) {
+            break;
+        }
+        for (offset, force) in offsets.iter_mut().zip(forces) {
+            *offset += force;
+        }
+    }
+    let displacements = movable.iter().zip(offsets);
+    let displacements = displacements.filter(|(_, offset)| offset.norm_squared() >= f32::EPSILON);
+    displacements.map(|((id, _), offset)| (*id, offset)).collect()
+}
+
+/// The shortest translation moving `bbox` out of `obstacle`, keeping the [`MARGIN`] between them.
+/// The box is always pushed along the axis of the least penetration, away from the obstacle's
+/// center. Returns zero if the boxes do not overlap.
+fn separation(obstacle: &BoundingBox, bbox: &BoundingBox) -> Vector2 {
+    let push_right = obstacle.right() + MARGIN - bbox.left();
+    let push_left = bbox.right() - obstacle.left() + MARGIN;
+    let push_up = obstacle.top() + MARGIN - bbox.bottom();
+    let push_down = bbox.top() - obstacle.bottom() + MARGIN;
+    let overlaps = [push_right, push_left, push_up, push_down].iter().all(|push| *push > 0.0);
+    if !overlaps {
+        return Vector2::zero();
+    }
+    let x = if obstacle.center().x <= bbox.center().x { push_right } else { -push_left };
+    let y = if obstacle.center().y <= bbox.center().y { push_up } else { -push_down };
+    if x.abs() < y.abs() {
+        Vector2(x, 0.0)
+    } else {
+        Vector2(0.0, y)
+    }
+}
+
+/// Return the bounding box moved by the given offset.
+pub fn translated(bbox: &BoundingBox, offset: Vector2) -> BoundingBox {
+    let bottom_left = Vector2(bbox.left(), bbox.bottom()) + offset;
+    let top_right = Vector2(bbox.right(), bbox.top()) + offset;
+    BoundingBox::from_corners(bottom_left, top_right)
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(x: f32, y: f32) -> BoundingBox {
+        BoundingBox::from_bottom_left_position_and_size(Vector2(x, y), Vector2(100.0, 100.0))
+    }
+
+    #[test]
+    fn separated_boxes_are_not_moved() {
+        let fixed = [square(0.0, 0.0)];
+        let movable = [(1, square(200.0, 0.0)), (2, square(0.0, -200.0))];
+        assert!(relax(&fixed, &movable).is_empty());
+    }
+
+    #[test]
+    fn overlapping_box_is_pushed_along_shallowest_axis() {
+        let fixed = [square(0.0, 0.0)];
+        let movable = [(1, square(80.0, 10.0))];
+        assert_eq!(relax(&fixed, &movable), vec![(1, Vector2(20.0 + MARGIN, 0.0))]);
+        let movable = [(1, square(-10.0, -90.0))];
+        assert_eq!(relax(&fixed, &movable), vec![(1, Vector2(0.0, -10.0 - MARGIN))]);
+    }
+
+    #[test]
+    fn pushes_propagate_to_neighbors() {
+        let fixed = [square(0.0, 0.0)];
+        let movable = [(1, square(80.0, 0.0)), (2, square(200.0, 0.0))];
+        let displacements = relax(&fixed, &movable);
+        let boxes = movable.iter().map(|(id, bbox)| {
+            let offset = displacements.iter().find(|(moved, _)| moved == id).map(|(_, o)| *o);
+            translated(bbox, offset.unwrap_or_default())
+        });
+        let boxes = boxes.collect_vec();
+        assert!(!boxes[0].interior_intersects(&fixed[0]));
+        assert!(!boxes[0].interior_intersects(&boxes[1]));
+        assert!(displacements.iter().any(|(id, _)| *id == 2));
+    }
+}
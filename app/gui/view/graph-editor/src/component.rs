@@ -5,11 +5,26 @@
 // === Export ===
 // ==============
 
+pub mod accessibility;
 pub mod add_node_button;
+pub mod canvas_background;
+pub mod collapse_preview;
 pub mod edge;
+pub mod file_drop;
+pub mod grid;
+pub mod hover_card;
+pub mod lasso;
+pub mod navigation_transition;
 pub mod node;
+pub mod node_peek;
+pub mod node_search;
+pub mod portal;
+pub mod remote_presence;
+pub mod selection_count;
+pub mod tab_bar;
 pub mod type_coloring;
 pub mod visualization;
+pub mod watch_panel;
 
 pub use edge::Edge;
 pub use node::Node;
@@ -0,0 +1,378 @@
+//! An off-screen DOM tree mirroring the nodes and connections of the graph. The scene is rendered
+//! with WebGL only, so it is invisible to the assistive technologies. The tree gives the nodes and
+//! connections their ARIA roles and labels, and keeps the screen reader focus on the selected node.
+
+use crate::prelude::*;
+use ensogl::system::web::traits::*;
+
+use crate::NodeId;
+
+use enso_frp as frp;
+use ensogl::application::Application;
+use ensogl::system::web;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The label of a node with an empty expression.
+const EMPTY_NODE_LABEL: &str = "Empty node";
+/// The styles moving the tree out of the screen, while keeping it available to screen readers.
+/// Hiding it with `display: none` would hide it from the screen readers as well.
+const VISUALLY_HIDDEN_STYLES: &[(&str, &str)] = &[
+    ("position", "absolute"),
+    ("left", "-10000px"),
+    ("width", "1px"),
+    ("height", "1px"),
+    ("overflow", "hidden"),
+];
+
+/// The DOM id of the element describing the node, referenced by the nodes list's focus.
+fn node_element_id(node_id: NodeId) -> String {
+    format!("graph-editor-node-{node_id}")
+}
+
+
+
+// =======================
+// === NodeDescription ===
+// =======================
+
+/// The state of a node, as described to the screen readers.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NodeDescription {
+    /// The expression of the node.
+    pub expression: ImString,
+    /// Whether the node is selected.
+    pub selected:   bool,
+    /// Whether the node displays an error.
+    pub error:      bool,
+}
+
+impl NodeDescription {
+    /// The label announced by the screen readers.
+    pub fn label(&self) -> &str {
+        if self.expression.is_empty() {
+            EMPTY_NODE_LABEL
+        } else {
+            self.expression.as_str()
+        }
+    }
+}
+
+/// The label of the connection between two nodes with the given labels.
+pub fn connection_label(source: &str, target: &str) -> String {
+    format!("Connection from {source} to {target}")
+}
+
+
+
+// ========================
+// === GraphDescription ===
+// ========================
+
+/// A node of the graph, as described in [`describe_graph`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DescribedNode {
+    /// The expression of the node.
+    pub expression: ImString,
+    /// The description of the error displayed by the node, if any.
+    pub error:      Option<String>,
+}
+
+/// A textual description of the whole graph, to be read by the screen readers or pasted into bug
+/// reports. The nodes are referred to by their numbers, i.e. their positions in the `nodes` slice,
+/// and the `connections` are the pairs of the source and target node index. The description does
+/// not depend on the order of the connections, so it is the same for the same graph.
+pub fn describe_graph(nodes: &[DescribedNode], connections: &[(usize, usize)]) -> String {
+    let node_name = |index: usize| format!("Node {}", index + 1);
+    let count = |n: usize, noun: &str| format!("{n} {noun}{}", if n == 1 { "" } else { "s" });
+    let node_count = count(nodes.len(), "node");
+    let connection_count = count(connections.len(), "connection");
+    let mut lines = vec![format!("Graph with {node_count} and {connection_count}.")];
+    for (index, node) in nodes.iter().enumerate() {
+        let expression = node.expression.as_str();
+        let label = if expression.is_empty() { EMPTY_NODE_LABEL } else { expression };
+        let line = match &node.error {
+            Some(error) => format!("{}: {label} (error: {error})", node_name(index)),
+            None => format!("{}: {label}", node_name(index)),
+        };
+        lines.push(line);
+    }
+    let connections = connections.iter().sorted();
+    lines.extend(connections.map(|(source, target)| {
+        connection_label(&node_name(*source), &node_name(*target))
+    }));
+    lines.join("\n")
+}
+
+
+
+// ===========
+// === Frp ===
+// ===========
+
+ensogl::define_endpoints_2! {
+    Input {
+        /// Describe the new node.
+        add_node            (NodeId),
+        /// Remove the description of the node.
+        remove_node         (NodeId),
+        set_node_expression (NodeId, ImString),
+        set_node_selected   (NodeId, bool),
+        set_node_error      (NodeId, bool),
+        /// Set the connections between the nodes, as the pairs of the source and target node.
+        set_connections     (Rc<Vec<(NodeId, NodeId)>>),
+    }
+    Output {
+        /// The node the screen reader focus is on: the most recently selected node, or another
+        /// selected node if it was deselected.
+        focused_node (Option<NodeId>),
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+#[derive(Debug)]
+struct NodeEntry {
+    element:     web::HtmlDivElement,
+    description: NodeDescription,
+}
+
+#[derive(Debug)]
+struct Model {
+    root:         web::HtmlDivElement,
+    nodes_list:   web::HtmlDivElement,
+    edges_list:   web::HtmlDivElement,
+    /// A live region announcing the focused node to the screen readers which do not follow the
+    /// `aria-activedescendant` focus of the nodes list.
+    announcement: web::HtmlDivElement,
+    nodes:        RefCell<HashMap<NodeId, NodeEntry>>,
+    connections:  RefCell<Rc<Vec<(NodeId, NodeId)>>>,
+    focused:      Cell<Option<NodeId>>,
+}
+
+impl Model {
+    fn new(app: &Application) -> Self {
+        let root = web::document.create_div_or_panic();
+        root.set_attribute_or_warn("role", "region");
+        root.set_attribute_or_warn("aria-label", "Graph editor");
+        for (name, value) in VISUALLY_HIDDEN_STYLES {
+            root.set_style_or_warn(name, value);
+        }
+        let nodes_list = web::document.create_div_or_panic();
+        nodes_list.set_attribute_or_warn("role", "listbox");
+        nodes_list.set_attribute_or_warn("aria-label", "Nodes");
+        nodes_list.set_attribute_or_warn("aria-multiselectable", "true");
+        nodes_list.set_attribute_or_warn("tabindex", "0");
+        let edges_list = web::document.create_div_or_panic();
+        edges_list.set_attribute_or_warn("role", "list");
+        edges_list.set_attribute_or_warn("aria-label", "Connections");
+        let announcement = web::document.create_div_or_panic();
+        announcement.set_attribute_or_warn("aria-live", "polite");
+        root.append_or_warn(&nodes_list);
+        root.append_or_warn(&edges_list);
+        root.append_or_warn(&announcement);
+        app.display.default_scene.dom.root.append_or_warn(&root);
+        let nodes = default();
+        let connections = default();
+        let focused = default();
+        Self { root, nodes_list, edges_list, announcement, nodes, connections, focused }
+    }
+
+    fn add_node(&self, node_id: NodeId) {
+        let element = web::document.create_div_or_panic();
+        element.set_id(&node_element_id(node_id));
+        element.set_attribute_or_warn("role", "option");
+        self.nodes_list.append_or_warn(&element);
+        let entry = NodeEntry { element, description: default() };
+        Self::update_node_element(&entry);
+        self.nodes.borrow_mut().insert(node_id, entry);
+    }
+
+    /// Remove the node's description. Returns the node focused afterwards.
+    fn remove_node(&self, node_id: NodeId) -> Option<NodeId> {
+        let removed = self.nodes.borrow_mut().remove(&node_id);
+        if let Some(entry) = removed {
+            entry.element.remove();
+        }
+        if self.focused.get() == Some(node_id) {
+            self.update_focus(node_id, false);
+        }
+        self.focused.get()
+    }
+
+    /// Update the node's selection state. Returns the node focused afterwards.
+    fn set_node_selected(&self, node_id: NodeId, selected: bool) -> Option<NodeId> {
+        if self.with_node(node_id, |node| node.selected = selected) {
+            self.update_focus(node_id, selected);
+        }
+        self.focused.get()
+    }
+
+    /// Update the description of the node. Returns `false` if the node is not described.
+    fn with_node(&self, node_id: NodeId, f: impl FnOnce(&mut NodeDescription)) -> bool {
+        let mut nodes = self.nodes.borrow_mut();
+        let entry = nodes.get_mut(&node_id);
+        entry
+            .map(|entry| {
+                f(&mut entry.description);
+                Self::update_node_element(entry);
+            })
+            .is_some()
+    }
+
+    fn update_node_element(entry: &NodeEntry) {
+        let description = &entry.description;
+        entry.element.set_text_content(Some(description.label()));
+        entry.element.set_attribute_or_warn("aria-selected", description.selected.to_string());
+        entry.element.set_attribute_or_warn("aria-invalid", description.error.to_string());
+    }
+
+    /// Re-create the descriptions of the connections. They contain the labels of the nodes, so
+    /// they are updated also when the nodes change.
+    fn update_connections(&self) {
+        self.edges_list.set_inner_html("");
+        let nodes = self.nodes.borrow();
+        let label = |id: &NodeId| {
+            nodes.get(id).map_or(EMPTY_NODE_LABEL, |node| node.description.label())
+        };
+        for (source, target) in self.connections.borrow().iter() {
+            let element = web::document.create_div_or_panic();
+            element.set_attribute_or_warn("role", "listitem");
+            element.set_text_content(Some(&connection_label(label(source), label(target))));
+            self.edges_list.append_or_warn(&element);
+        }
+    }
+
+    /// Choose the node the screen reader focus is on after the node selection changed.
+    fn update_focus(&self, changed: NodeId, selected: bool) {
+        let focused = if selected {
+            Some(changed)
+        } else {
+            let nodes = self.nodes.borrow();
+            let still_focused = self.focused.get().filter(|id| nodes.contains_key(id));
+            let still_focused = still_focused.filter(|id| *id != changed);
+            let other_selected = nodes.iter().filter(|(_, n)| n.description.selected);
+            still_focused.or_else(|| other_selected.map(|(id, _)| *id).min())
+        };
+        self.set_focus(focused);
+    }
+
+    fn set_focus(&self, focused: Option<NodeId>) {
+        self.focused.set(focused);
+        let element_id = focused.map(node_element_id).unwrap_or_default();
+        self.nodes_list.set_attribute_or_warn("aria-activedescendant", element_id);
+        let nodes = self.nodes.borrow();
+        let label = focused.and_then(|id| nodes.get(&id)).map(|n| n.description.label());
+        let announcement = label.map(|label| format!("Selected {label}"));
+        self.announcement.set_text_content(announcement.as_deref());
+    }
+}
+
+impl Drop for Model {
+    fn drop(&mut self) {
+        self.root.remove();
+    }
+}
+
+
+
+// =========================
+// === AccessibilityTree ===
+// =========================
+
+/// The accessibility tree of the graph. See the module docs.
+#[derive(Clone, CloneRef, Debug, Deref)]
+#[allow(missing_docs)]
+pub struct AccessibilityTree {
+    model:   Rc<Model>,
+    #[deref]
+    pub frp: Frp,
+}
+
+impl AccessibilityTree {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let model = Rc::new(Model::new(app));
+        let frp = Frp::new();
+        Self { model, frp }.init()
+    }
+
+    fn init(self) -> Self {
+        let network = self.frp.network();
+        let input = &self.frp.private.input;
+        let out = &self.frp.private.output;
+        let model = &self.model;
+
+        frp::extend! { network
+            eval input.add_node ((id) model.add_node(*id));
+            eval input.set_node_expression (((id, expr))
+                model.with_node(*id, |node| node.expression = expr.clone())
+            );
+            eval input.set_node_error (((id, error))
+                model.with_node(*id, |node| node.error = *error)
+            );
+            focus_on_selection <- input.set_node_selected.map(
+                f!(((id, selected)) model.set_node_selected(*id, *selected))
+            );
+            focus_on_removal <- input.remove_node.map(f!((id) model.remove_node(*id)));
+            out.focused_node <+ any(focus_on_selection, focus_on_removal).on_change();
+
+            eval input.set_connections ((conns) model.connections.replace(conns.clone_ref()));
+            connections_dirty <- any_(...);
+            connections_dirty <+ input.set_connections;
+            connections_dirty <+ input.remove_node;
+            connections_dirty <+ input.set_node_expression;
+            // Many nodes changing in the same frame result in a single update.
+            update_connections <- connections_dirty.debounce();
+            eval_ update_connections (model.update_connections());
+        }
+        self
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn node_labels() {
+        let node = |expr: &str| NodeDescription { expression: expr.into(), ..default() };
+        assert_eq!(node("").label(), EMPTY_NODE_LABEL);
+        assert_eq!(node("a + b").label(), "a + b");
+        assert_eq!(connection_label("a", "b"), "Connection from a to b");
+    }
+
+    #[test]
+    fn describing_graph() {
+        let node = |expr: &str, error: Option<&str>| DescribedNode {
+            expression: expr.into(),
+            error:      error.map(Into::into),
+        };
+        let nodes = [node("a = 1", None), node("", None), node("b = a / 0", Some("Division by 0"))];
+        let expected = "Graph with 3 nodes and 2 connections.
+Node 1: a = 1
+Node 2: Empty node
+Node 3: b = a / 0 (error: Division by 0)
+Connection from Node 1 to Node 2
+Connection from Node 1 to Node 3";
+        assert_eq!(describe_graph(&nodes, &[(0, 2), (0, 1)]), expected);
+        assert_eq!(describe_graph(&nodes, &[(0, 1), (0, 2)]), expected);
+        assert_eq!(describe_graph(&[], &[]), "Graph with 0 nodes and 0 connections.");
+        let single_node = "Graph with 1 node and 0 connections.\nNode 1: a = 1";
+        assert_eq!(describe_graph(&nodes[..1], &[]), single_node);
+    }
+}
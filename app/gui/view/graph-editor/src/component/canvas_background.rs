@@ -0,0 +1,320 @@
+//! A customizable background of the graph canvas, and the shaded regions drawn on it. The regions
+//! allow partitioning the graph visually, e.g. into the stages of a data pipeline.
+//!
+//! The solid and dot grid backgrounds, as well as the regions, are drawn on sublayers of the
+//! `below_main` layer. The layers are created in the component constructor, so the component must
+//! be created before the snap [`Grid`](crate::component::grid::Grid), whose dots are drawn above
+//! the background. The image background is displayed by the scene's DOM element instead, as it
+//! is not moved nor scaled with the camera.
+
+use crate::prelude::*;
+use ensogl::display::shape::*;
+use ensogl::system::web::traits::*;
+
+use crate::component::grid;
+
+use enso_frp as frp;
+use ensogl::application::Application;
+use ensogl::data::color;
+use ensogl::display;
+use ensogl::display::camera::Camera2d;
+use ensogl::display::scene::Layer;
+use ensogl_component::text;
+use ensogl_hardcoded_theme::graph_editor::canvas_background as theme;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// Text size of the region labels.
+const LABEL_TEXT_SIZE: f32 = 12.0;
+/// Padding between the region label and the top left corner of the region.
+const LABEL_PADDING: f32 = 8.0;
+/// The corner radius of the regions.
+const REGION_CORNER_RADIUS: f32 = 12.0;
+/// The radius of the dots of the dot grid background.
+const DOT_RADIUS: f32 = 1.0;
+
+
+
+// ======================
+// === BackgroundSpec ===
+// ======================
+
+/// The background of the graph canvas.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum BackgroundSpec {
+    /// The flat background defined by the `application.background` theme color.
+    #[default]
+    Theme,
+    /// A flat background of the given color.
+    Solid(color::Rgba),
+    /// A pattern of dots.
+    DotGrid {
+        /// The distance between the neighbouring dots in the scene coordinates. The dots are
+        /// placed at the multiples of the spacing.
+        spacing: f32,
+        /// The color of the dots.
+        color:   color::Rgba,
+    },
+    /// An image covering the whole canvas, loaded from the given URL. It is displayed above the
+    /// theme background, which remains visible through its transparent parts.
+    Image(ImString),
+}
+
+
+
+// ==============
+// === Region ===
+// ==============
+
+/// A named region of the canvas, shaded with the given color.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Region {
+    /// The label displayed in the top left corner of the region.
+    pub label:    ImString,
+    /// The position of the bottom left corner of the region in the scene coordinates.
+    pub position: Vector2,
+    /// The size of the region in the scene coordinates.
+    pub size:     Vector2,
+    /// The color the region is shaded with. It should be transparent, so the region does not
+    /// hide the background patterns.
+    pub color:    color::Rgba,
+}
+
+impl Region {
+    /// The position of the label, which is aligned to the top left corner of the region.
+    fn label_position(&self) -> Vector2 {
+        let top_left = self.position + Vector2(0.0, self.size.y);
+        top_left + Vector2(LABEL_PADDING, -LABEL_PADDING)
+    }
+}
+
+/// The shapes displaying a single region.
+#[derive(Clone, Debug)]
+struct RegionView {
+    background: Rectangle,
+    label:      text::Text,
+}
+
+
+
+// ===========
+// === Frp ===
+// ===========
+
+ensogl::define_endpoints_2! {
+    Input {
+        /// Set the background of the canvas.
+        set_background (BackgroundSpec),
+        /// Set the regions shaded on the background, replacing the previously set ones.
+        set_regions    (Rc<Vec<Region>>),
+    }
+    Output {
+        /// The current background of the canvas.
+        background (BackgroundSpec),
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+#[derive(Clone, Debug, display::Object)]
+struct Model {
+    display_object: display::object::Instance,
+    app:            Application,
+    fill:           Rectangle,
+    dots:           grid::dots::View,
+    regions:        RefCell<Vec<RegionView>>,
+    label_color:    Cell<color::Rgba>,
+    _fill_layer:    Layer,
+    regions_layer:  Layer,
+    labels_layer:   Layer,
+}
+
+impl Model {
+    fn new(app: &Application) -> Self {
+        let app = app.clone_ref();
+        let below_main = &app.display.default_scene.layers.below_main;
+        let display_object = display::object::Instance::new_named("CanvasBackground");
+        let fill_layer = below_main.create_sublayer("canvas_background");
+        let regions_layer = below_main.create_sublayer("background_regions");
+        let labels_layer = below_main.create_sublayer("background_region_labels");
+        let fill = Rectangle::new();
+        fill.set_pointer_events(false);
+        let dots = grid::dots::View::new();
+        dots.radius.set(DOT_RADIUS);
+        fill_layer.add(&fill);
+        fill_layer.add(&dots);
+        let regions = default();
+        let label_color = default();
+        Self {
+            display_object,
+            app,
+            fill,
+            dots,
+            regions,
+            label_color,
+            _fill_layer: fill_layer,
+            regions_layer,
+            labels_layer,
+        }
+    }
+
+    fn set_background(&self, spec: &BackgroundSpec) {
+        self.fill.unset_parent();
+        self.dots.unset_parent();
+        let image = match spec {
+            BackgroundSpec::Theme => None,
+            BackgroundSpec::Solid(color) => {
+                self.fill.set_color(*color);
+                self.display_object.add_child(&self.fill);
+                None
+            }
+            BackgroundSpec::DotGrid { spacing, color } => {
+                self.dots.spacing.set(*spacing);
+                self.dots.color_rgba.set((*color).into());
+                self.display_object.add_child(&self.dots);
+                None
+            }
+            BackgroundSpec::Image(url) => Some(url),
+        };
+        let root = &self.app.display.default_scene.dom.root;
+        let image = image.map_or_else(|| "none".to_owned(), |url| format!("url(\"{url}\")"));
+        root.set_style_or_warn("background-image", image);
+        root.set_style_or_warn("background-size", "cover");
+        root.set_style_or_warn("background-position", "center");
+    }
+
+    /// Cover the visible part of the scene with the solid or dotted background. The dots are
+    /// centered at the grid point nearest to the camera, so they stay in place when panning.
+    fn update_layout(&self, camera: &Camera2d, spec: &BackgroundSpec) {
+        let screen = camera.screen();
+        let visible_area = Vector2(screen.width, screen.height) / camera.zoom();
+        let camera_position = camera.position().xy();
+        match spec {
+            BackgroundSpec::Solid(_) => {
+                self.fill.set_size(visible_area);
+                self.fill.set_xy(camera_position - visible_area / 2.0);
+            }
+            BackgroundSpec::DotGrid { spacing, .. } => {
+                let margin = Vector2(*spacing, *spacing) * 2.0;
+                self.dots.set_size(visible_area + margin);
+                self.dots.set_xy(grid::snap(camera_position, *spacing));
+            }
+            BackgroundSpec::Theme | BackgroundSpec::Image(_) => {}
+        }
+    }
+
+    fn set_regions(&self, regions: &[Region]) {
+        let mut views = self.regions.borrow_mut();
+        while views.len() < regions.len() {
+            let background = Rectangle::new();
+            background.set_corner_radius(REGION_CORNER_RADIUS).set_pointer_events(false);
+            let label = self.app.new_view::<text::Text>();
+            label.set_property_default(text::Size(LABEL_TEXT_SIZE));
+            label.set_property_default(self.label_color.get());
+            label.remove_all_cursors();
+            self.regions_layer.add(&background);
+            self.labels_layer.add(&label);
+            self.display_object.add_child(&background);
+            self.display_object.add_child(&label);
+            views.push(RegionView { background, label });
+        }
+        views.truncate(regions.len());
+        for (region, view) in regions.iter().zip(views.iter()) {
+            view.background.set_xy(region.position);
+            view.background.set_size(region.size);
+            view.background.set_color(region.color);
+            view.label.set_content(region.label.clone_ref());
+            view.label.set_xy(region.label_position());
+        }
+    }
+
+    fn set_label_color(&self, color: color::Rgba) {
+        self.label_color.set(color);
+        for view in self.regions.borrow().iter() {
+            view.label.set_property_default(color);
+        }
+    }
+}
+
+
+
+// ========================
+// === CanvasBackground ===
+// ========================
+
+/// The background of the graph canvas. See the module docs.
+#[derive(Clone, CloneRef, Debug, Deref, display::Object)]
+#[allow(missing_docs)]
+pub struct CanvasBackground {
+    #[display_object]
+    model:   Rc<Model>,
+    #[deref]
+    pub frp: Frp,
+}
+
+impl CanvasBackground {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let model = Rc::new(Model::new(app));
+        let frp = Frp::new();
+        Self { model, frp }.init(app)
+    }
+
+    fn init(self, app: &Application) -> Self {
+        let network = self.frp.network();
+        let input = &self.frp.private.input;
+        let out = &self.frp.private.output;
+        let model = &self.model;
+        let scene = &app.display.default_scene;
+        let camera = scene.camera();
+        let style = StyleWatchFrp::new(&scene.style_sheet);
+        let label_color = style.get_color(theme::region_label);
+
+        frp::extend! { network
+            init <- source_();
+            out.background <+ input.set_background.on_change();
+            eval out.background ((spec) model.set_background(spec));
+            let camera_changed = scene.frp.camera_changed.clone_ref();
+            layout <- all(init, camera_changed, out.background);
+            eval layout ([model, camera] ((_, _, spec)) model.update_layout(&camera, spec));
+
+            eval input.set_regions ((regions) model.set_regions(regions));
+            label_color <- all(&label_color, &init)._0();
+            eval label_color ((color) model.set_label_color(*color));
+        }
+
+        init.emit(());
+        self
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn region_label_is_placed_in_top_left_corner() {
+        let region = Region {
+            label: "Cleanup".into(),
+            position: Vector2(-100.0, 50.0),
+            size: Vector2(300.0, 200.0),
+            color: default(),
+        };
+        let expected = Vector2(-100.0 + LABEL_PADDING, 250.0 - LABEL_PADDING);
+        assert_eq!(region.label_position(), expected);
+    }
+}
@@ -0,0 +1,543 @@
+//! A preview of the function created by collapsing the selected nodes, displayed before the nodes
+//! are collapsed.
+//!
+//! The preview lists the parameters and outputs of the function, derived from the edges crossing
+//! the boundary of the collapsed nodes. The parameters are named after the values passed to them,
+//! and the user may rename them by clicking on their names. Nothing is collapsed until the user
+//! confirms the preview; the confirmed [`Signature`] is emitted through the `confirmed` output.
+//! The preview can't be confirmed while any of the parameter names is invalid, see
+//! [`Signature::invalid_parameters`].
+
+use crate::prelude::*;
+use ensogl::display::shape::*;
+
+use crate::Connection;
+use crate::EdgeEndpoint;
+use crate::NodeId;
+
+use enso_frp as frp;
+use ensogl::application::Application;
+use ensogl::control::io::mouse;
+use ensogl::display;
+use ensogl_component::text;
+use ensogl_hardcoded_theme::graph_editor::collapse_preview as theme;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+const WIDTH: f32 = 320.0;
+const ROW_HEIGHT: f32 = 24.0;
+const TEXT_SIZE: f32 = 12.0;
+const PADDING: f32 = 10.0;
+/// The distance between the top of the screen and the preview.
+const TOP_MARGIN: f32 = 80.0;
+const PARAMETERS_HEADER: &str = "Inputs";
+const OUTPUTS_HEADER: &str = "Outputs";
+const NO_PARAMETERS: &str = "No inputs";
+const CONFIRM_LABEL: &str = "Collapse";
+const CANCEL_LABEL: &str = "Cancel";
+
+
+
+// =================
+// === Signature ===
+// =================
+
+/// A parameter of the collapsed function. There is one parameter for each port outside the
+/// collapsed nodes that is connected to any of them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Parameter {
+    /// The port providing the argument.
+    pub source: EdgeEndpoint,
+    /// The code of the argument, usually the name of the node's variable.
+    pub code:   ImString,
+    /// The name of the parameter. Equal to the `code`, unless renamed by the user.
+    pub name:   ImString,
+}
+
+impl Parameter {
+    /// Check whether the user changed the name of the parameter.
+    pub fn is_renamed(&self) -> bool {
+        self.name != self.code
+    }
+}
+
+/// A value computed by the collapsed nodes and used outside of them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Output {
+    /// The port of a collapsed node providing the value.
+    pub source: EdgeEndpoint,
+    /// The code of the value, usually the name of the node's variable.
+    pub code:   ImString,
+}
+
+/// The interface of the function created by collapsing the nodes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Signature {
+    /// The parameters, sorted by their code, like the parameters of the generated function.
+    pub parameters:  Vec<Parameter>,
+    /// The outputs, sorted by their code.
+    pub outputs:     Vec<Output>,
+    /// The names bound in the collapsed nodes. The parameters can't be named like them.
+    pub bound_names: HashSet<ImString>,
+}
+
+impl Signature {
+    /// Derive the signature from the connections crossing the boundary of the `collapsed` nodes.
+    /// The `code_of` function provides the code of the connections' source ports.
+    pub fn new(
+        collapsed: &HashSet<NodeId>,
+        connections: impl IntoIterator<Item = Connection>,
+        code_of: impl Fn(EdgeEndpoint) -> ImString,
+        bound_names: HashSet<ImString>,
+    ) -> Self {
+        let mut parameters: Vec<Parameter> = vec![];
+        let mut outputs: Vec<Output> = vec![];
+        for Connection { source, target } in connections {
+            let starts_inside = collapsed.contains(&source.node_id);
+            let ends_inside = collapsed.contains(&target.node_id);
+            if !starts_inside && ends_inside && !parameters.iter().any(|p| p.source == source) {
+                let code = code_of(source);
+                parameters.push(Parameter { source, name: code.clone(), code });
+            } else if starts_inside && !ends_inside && !outputs.iter().any(|o| o.source == source)
+            {
+                outputs.push(Output { source, code: code_of(source) });
+            }
+        }
+        parameters.sort_by(|a, b| a.code.cmp(&b.code));
+        outputs.sort_by(|a, b| a.code.cmp(&b.code));
+        Self { parameters, outputs, bound_names }
+    }
+
+    /// Set the name of the parameter. An empty name restores the default name of the parameter.
+    pub fn rename_parameter(&mut self, index: usize, name: &str) {
+        if let Some(parameter) = self.parameters.get_mut(index) {
+            let name = name.trim();
+            parameter.name = if name.is_empty() { parameter.code.clone() } else { name.into() };
+        }
+    }
+
+    /// The parameters renamed by the user.
+    pub fn renamed_parameters(&self) -> impl Iterator<Item = &Parameter> {
+        self.parameters.iter().filter(|parameter| parameter.is_renamed())
+    }
+
+    /// The indices of the parameters with invalid names: the renamed ones not being variable
+    /// names, and the ones named like another parameter or like a name bound in the collapsed
+    /// nodes.
+    pub fn invalid_parameters(&self) -> Vec<usize> {
+        let is_taken = |index: usize, name: &ImString| {
+            let mut others = self.parameters.iter().enumerate().filter(|(i, _)| *i != index);
+            others.any(|(_, other)| other.name == *name) || self.bound_names.contains(name)
+        };
+        let is_invalid = |(index, parameter): &(usize, &Parameter)| {
+            let is_malformed = parameter.is_renamed() && !is_variable_name(&parameter.name);
+            is_malformed || is_taken(*index, &parameter.name)
+        };
+        let invalid = self.parameters.iter().enumerate().filter(is_invalid);
+        invalid.map(|(index, _)| index).collect()
+    }
+
+    /// Check whether all the parameter names are valid, so the nodes can be collapsed.
+    pub fn is_valid(&self) -> bool {
+        self.invalid_parameters().is_empty()
+    }
+}
+
+/// Check whether the name is a valid variable name, like `table` or `first_row`.
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    let first_valid = chars.next().map_or(false, |c| c.is_lowercase() || c == '_');
+    first_valid && name != "_" && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// The names bound by the pattern of a node, e.g. `table` in `table = Table.new`.
+pub fn pattern_names(pattern: &str) -> impl Iterator<Item = ImString> + '_ {
+    let names = pattern.split(|c: char| !(c.is_alphanumeric() || c == '_'));
+    names.filter(|name| is_variable_name(name)).map(ImString::from)
+}
+
+
+
+// ===========
+// === Frp ===
+// ===========
+
+ensogl::define_endpoints_2! {
+    Input {
+        /// Show the preview of collapsing the nodes into a function with the given signature.
+        show (Rc<Signature>),
+        /// Hide the preview, confirming the signature with the parameter names entered by the user.
+        confirm (),
+        /// Hide the preview without collapsing the nodes.
+        cancel (),
+    }
+    Output {
+        visible   (bool),
+        /// Whether all the parameter names are valid. The preview can't be confirmed otherwise.
+        valid     (bool),
+        /// The user confirmed the preview. The parameters have the names entered by the user.
+        confirmed (Rc<Signature>),
+    }
+}
+
+
+
+// ===========
+// === Row ===
+// ===========
+
+/// The effect of pressing a row of the preview.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RowAction {
+    /// Start editing the name of the parameter with the given index.
+    EditParameter(usize),
+    Confirm,
+    Cancel,
+}
+
+/// A single row of the preview, displaying a label over a background. The pressed rows emit their
+/// actions through the model's `row_pressed` stream.
+#[derive(Clone, CloneRef, Debug, display::Object)]
+struct Row {
+    display_object: display::object::Instance,
+    label:          text::Text,
+    _background:    Rectangle,
+    _network:       frp::Network,
+}
+
+impl Row {
+    fn new(model: &Model, content: &str, width: f32, action: Option<RowAction>) -> Self {
+        let scene = &model.app.display.default_scene;
+        let display_object = display::object::Instance::new_named("CollapsePreviewRow");
+        let background = Rectangle();
+        let label = model.app.new_view::<text::Text>();
+        display_object.add_child(&background);
+        display_object.add_child(&label);
+        scene.layers.panel_text.add(&label);
+        let (background_color, text_color) = match action {
+            Some(RowAction::EditParameter(_)) => (theme::field_background, theme::text),
+            Some(RowAction::Confirm | RowAction::Cancel) => (theme::button_background, theme::text),
+            None => (theme::background, theme::header),
+        };
+        background.set_size(Vector2(width, ROW_HEIGHT));
+        background.set_color(model.style.get_color(background_color));
+        background.set_pointer_events(action.is_some());
+        label.set_single_line_mode(true);
+        label.set_property_default(text::Size(TEXT_SIZE));
+        label.set_property_default(model.style.get_color(text_color));
+        label.set_xy(Vector2(PADDING, (ROW_HEIGHT + TEXT_SIZE) / 2.0));
+        label.set_content(content);
+
+        let network = frp::Network::new("CollapsePreviewRow");
+        let down = background.on_event::<mouse::Down>();
+        let row_pressed = &model.row_pressed;
+        let parameter_renamed = &model.parameter_renamed;
+        if let Some(action) = action {
+            frp::extend! { network
+                row_pressed <+ down.filter(mouse::is_primary).constant(action);
+            }
+        }
+        if let Some(RowAction::EditParameter(index)) = action {
+            frp::extend! { network
+                parameter_renamed <+ label.content.map(move |name| (index, name.to_string()));
+            }
+        }
+        Self { display_object, label, _background: background, _network: network }
+    }
+
+    fn set_text_color(&self, color: color::Rgba) {
+        self.label.set_property(.., color);
+        self.label.set_property_default(color);
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+#[derive(Clone, Debug, display::Object)]
+struct Model {
+    app:               Application,
+    display_object:    display::object::Instance,
+    /// The visible part of the preview, attached only when the preview is shown.
+    content:           display::object::Instance,
+    style:             StyleWatch,
+    background:        Rectangle,
+    rows:              RefCell<Vec<Row>>,
+    /// The rows with the editable parameter names, in the order of the parameters.
+    parameter_rows:    RefCell<Vec<Row>>,
+    confirm_row:       RefCell<Option<Row>>,
+    signature:         RefCell<Signature>,
+    /// The index of the parameter whose name is edited.
+    edited_parameter:  Cell<Option<usize>>,
+    row_pressed:       frp::Any<RowAction>,
+    parameter_renamed: frp::Any<(usize, String)>,
+}
+
+impl Model {
+    fn new(app: &Application, network: &frp::Network) -> Self {
+        let app = app.clone_ref();
+        let scene = &app.display.default_scene;
+        let display_object = display::object::Instance::new_named("CollapsePreview");
+        let content = display::object::Instance::new();
+        let style = StyleWatch::new(&scene.style_sheet);
+        let background = Rectangle();
+        background.set_color(style.get_color(theme::background));
+        content.add_child(&background);
+        scene.layers.panel.add(&content);
+        frp::extend! { network
+            row_pressed <- any_mut::<RowAction>();
+            parameter_renamed <- any_mut::<(usize, String)>();
+        }
+        let rows = default();
+        let parameter_rows = default();
+        let confirm_row = default();
+        let signature = default();
+        let edited_parameter = default();
+        Self {
+            app,
+            display_object,
+            content,
+            style,
+            background,
+            rows,
+            parameter_rows,
+            confirm_row,
+            signature,
+            edited_parameter,
+            row_pressed,
+            parameter_renamed,
+        }
+    }
+
+    fn show(&self, signature: &Signature) {
+        self.stop_editing();
+        self.signature.replace(signature.clone());
+        self.update_rows();
+        self.display_object.add_child(&self.content);
+        if !signature.parameters.is_empty() {
+            self.edit_parameter(0);
+        }
+    }
+
+    fn hide(&self) {
+        self.stop_editing();
+        self.content.unset_parent();
+    }
+
+    /// Recreate the rows for the current signature and resize the background to fit them.
+    fn update_rows(&self) {
+        let signature = self.signature.borrow();
+        let row_width = WIDTH - 2.0 * PADDING;
+        let header = |label: &str| Row::new(self, label, row_width, None);
+        let parameter_rows = signature.parameters.iter().enumerate().map(|(index, parameter)| {
+            let action = Some(RowAction::EditParameter(index));
+            Row::new(self, &parameter.name, row_width, action)
+        });
+        let parameter_rows = parameter_rows.collect_vec();
+        let mut rows = vec![header(PARAMETERS_HEADER)];
+        if parameter_rows.is_empty() {
+            rows.push(header(NO_PARAMETERS));
+        }
+        rows.extend(parameter_rows.iter().cloned());
+        rows.push(header(OUTPUTS_HEADER));
+        rows.extend(signature.outputs.iter().map(|output| header(&output.code)));
+        for (index, row) in rows.iter().enumerate() {
+            let y = -PADDING - (index + 1) as f32 * ROW_HEIGHT;
+            row.set_xy(Vector2(-WIDTH / 2.0 + PADDING, y));
+            self.content.add_child(row);
+        }
+
+        let button_width = row_width / 2.0 - PADDING / 2.0;
+        let buttons_y = -2.0 * PADDING - (rows.len() + 1) as f32 * ROW_HEIGHT;
+        let confirm = Row::new(self, CONFIRM_LABEL, button_width, Some(RowAction::Confirm));
+        let cancel = Row::new(self, CANCEL_LABEL, button_width, Some(RowAction::Cancel));
+        confirm.set_xy(Vector2(-WIDTH / 2.0 + PADDING, buttons_y));
+        cancel.set_xy(Vector2(PADDING / 2.0, buttons_y));
+        self.content.add_child(&confirm);
+        self.content.add_child(&cancel);
+        self.confirm_row.replace(Some(confirm.clone_ref()));
+        rows.extend([confirm, cancel]);
+
+        let height = -buttons_y + PADDING;
+        self.background.set_size(Vector2(WIDTH, height));
+        self.background.set_xy(Vector2(-WIDTH / 2.0, -height));
+        for old in self.rows.replace(rows) {
+            old.unset_parent();
+        }
+        self.parameter_rows.replace(parameter_rows);
+    }
+
+    fn edit_parameter(&self, index: usize) {
+        self.stop_editing();
+        if let Some(row) = self.parameter_rows.borrow().get(index) {
+            row.label.focus();
+            row.label.set_cursor_at_text_end();
+            self.edited_parameter.set(Some(index));
+        }
+    }
+
+    fn stop_editing(&self) {
+        let edited = self.edited_parameter.take();
+        let edited_row = edited.and_then(|index| self.parameter_rows.borrow().get(index).cloned());
+        if let Some(row) = edited_row {
+            row.label.blur();
+        }
+    }
+
+    fn rename_parameter(&self, index: usize, name: &str) {
+        self.signature.borrow_mut().rename_parameter(index, name);
+    }
+
+    /// Highlight the invalid parameter names, and dim the confirm button if there are any.
+    fn update_validity(&self) -> bool {
+        let invalid = self.signature.borrow().invalid_parameters();
+        for (index, row) in self.parameter_rows.borrow().iter().enumerate() {
+            let color = if invalid.contains(&index) { theme::invalid_text } else { theme::text };
+            row.set_text_color(self.style.get_color(color));
+        }
+        let valid = invalid.is_empty();
+        if let Some(confirm) = self.confirm_row.borrow().as_ref() {
+            let color = if valid { theme::text } else { theme::header };
+            confirm.set_text_color(self.style.get_color(color));
+        }
+        valid
+    }
+
+    fn signature(&self) -> Rc<Signature> {
+        Rc::new(self.signature.borrow().clone())
+    }
+}
+
+
+
+// =======================
+// === CollapsePreview ===
+// =======================
+
+/// The collapse preview component. See the module docs.
+#[derive(Clone, CloneRef, Debug, Deref, display::Object)]
+#[allow(missing_docs)]
+pub struct CollapsePreview {
+    #[display_object]
+    model:   Rc<Model>,
+    #[deref]
+    pub frp: Frp,
+}
+
+impl CollapsePreview {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let frp = Frp::new();
+        let model = Rc::new(Model::new(app, frp.network()));
+        Self { model, frp }.init(app)
+    }
+
+    fn init(self, app: &Application) -> Self {
+        let network = self.frp.network();
+        let input = &self.frp.private.input;
+        let out = &self.frp.private.output;
+        let model = &self.model;
+        let scene = &app.display.default_scene;
+
+        frp::extend! { network
+            init <- source_();
+            eval input.show ((signature) model.show(signature));
+            eval model.parameter_renamed (((index, name)) model.rename_parameter(*index, name));
+            signature_changed <- any_(&input.show, &model.parameter_renamed);
+            out.valid <+ signature_changed.map(f_!(model.update_validity()));
+
+            edited_parameter <- model.row_pressed.filter_map(|action| match action {
+                RowAction::EditParameter(index) => Some(*index),
+                _ => None,
+            });
+            eval edited_parameter ((index) model.edit_parameter(*index));
+            confirm_pressed <- model.row_pressed.filter(|action| *action == RowAction::Confirm);
+            cancel_pressed <- model.row_pressed.filter(|action| *action == RowAction::Cancel);
+            confirm <- any_(&input.confirm, &confirm_pressed).gate(&out.visible).gate(&out.valid);
+            cancel <- any_(&input.cancel, &cancel_pressed).gate(&out.visible);
+            out.confirmed <+ confirm.map(f_!(model.signature()));
+            hide <- any(&confirm, &cancel);
+            eval_ hide (model.hide());
+            out.visible <+ bool(&hide, &input.show);
+
+            position <- all_with(scene.shape(), &init, |shape, _| {
+                Vector2(0.0, shape.height / 2.0 - TOP_MARGIN)
+            });
+            eval position ((position) model.content.set_xy(*position));
+        }
+        init.emit(());
+        self
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signature_of_collapsed_nodes() {
+        let node = |id: usize| NodeId(display::object::Id::from(id));
+        let [outside, first, second, user] = [1, 2, 3, 4].map(node);
+        let port = |node_id| EdgeEndpoint::new(node_id, default());
+        let connection = |source, target| Connection { source: port(source), target: port(target) };
+        let connections = [
+            connection(outside, first),
+            connection(outside, second),
+            connection(first, second),
+            connection(second, user),
+            connection(user, outside),
+        ];
+        let collapsed = [first, second].into_iter().collect();
+        let code_of = |endpoint: EdgeEndpoint| {
+            let code = if endpoint.node_id == outside { "table" } else { "result" };
+            ImString::from(code)
+        };
+        let bound_names = pattern_names("result").collect();
+        let mut signature = Signature::new(&collapsed, connections, code_of, bound_names);
+        let parameter_codes = signature.parameters.iter().map(|p| p.code.as_str()).collect_vec();
+        let output_codes = signature.outputs.iter().map(|o| o.code.as_str()).collect_vec();
+        assert_eq!(parameter_codes, vec!["table"]);
+        assert_eq!(output_codes, vec!["result"]);
+        assert_eq!(signature.renamed_parameters().count(), 0);
+
+        signature.rename_parameter(0, " data ");
+        assert_eq!(signature.parameters[0].name, "data");
+        assert_eq!(signature.renamed_parameters().count(), 1);
+        signature.rename_parameter(0, "");
+        assert_eq!(signature.parameters[0].name, "table");
+    }
+
+    #[test]
+    fn validating_parameter_names() {
+        let parameter =
+            |code: &str| Parameter { code: code.into(), name: code.into(), ..default() };
+        let parameters = vec![parameter("a"), parameter("b")];
+        let bound_names = pattern_names("sum").collect();
+        let mut signature = Signature { parameters, bound_names, ..default() };
+        assert!(signature.is_valid());
+
+        signature.rename_parameter(0, "b");
+        assert_eq!(signature.invalid_parameters(), [0, 1]);
+        signature.rename_parameter(0, "sum");
+        assert_eq!(signature.invalid_parameters(), [0]);
+        for invalid_name in ["First", "1st", "a b", "_", "a+b"] {
+            signature.rename_parameter(0, invalid_name);
+            assert!(!signature.is_valid(), "{invalid_name} should be invalid");
+        }
+        signature.rename_parameter(0, "first_2");
+        assert!(signature.is_valid());
+    }
+}
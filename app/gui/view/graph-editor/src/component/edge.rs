@@ -1,9 +1,22 @@
 //! Definition of the Edge component.
+//!
+//! # Performance
+//!
+//! The edges do not own any shape systems: every part of an edge is an instance of the
+//! [`Rectangle`] or the split-arc shape systems shared by the whole scene, so the number of draw
+//! calls doesn't grow with the number of edges. However, every corner of an edge is a separate
+//! instance with its own parameters, and there is no dedicated edge shape system drawing a whole
+//! edge from per-instance endpoints, colors and focus split. The edge geometry is computed on the
+//! CPU into a [`Layout`] whenever the inputs of the edge change. The target is to handle graphs
+//! with ten times more edges than the typical ones (i.e. thousands of edges) without dropping
+//! frames; the dedicated instanced edge shape system is left for a follow-up.
 
 use crate::prelude::*;
 use ensogl::display::shape::*;
 use ensogl::display::traits::*;
 
+use crate::component::portal;
+use crate::component::portal::PortalId;
 use crate::GraphLayers;
 
 use enso_frp as frp;
@@ -15,6 +28,7 @@ use ensogl::data::color;
 use ensogl::define_endpoints_2;
 use ensogl::display;
 use ensogl::display::scene::Scene;
+use ensogl_component::text;
 use ensogl_hardcoded_theme as theme;
 
 
@@ -24,6 +38,8 @@ mod layout;
 mod render;
 mod state;
 
+pub(crate) use layout::Layout;
+
 use inputs::Inputs;
 use layout::EndPoint;
 use render::ShapeParent;
@@ -32,6 +48,31 @@ use state::*;
 
 
 
+// =================
+// === Constants ===
+// =================
+
+/// The time in milliseconds the flow indicator takes to travel from the source to the target.
+const FLOW_INDICATOR_PERIOD_MS: f32 = 1500.0;
+/// The time in milliseconds the recomputation pulse takes to travel from the source to the target.
+const PULSE_DURATION_MS: f32 = 600.0;
+/// The number of dots forming the pulse, including its head.
+const PULSE_DOT_COUNT: usize = 5;
+/// The distance between the consecutive dots of the pulse, as a fraction of the edge's length.
+const PULSE_DOT_SPACING: f32 = 0.025;
+/// Text size of the edge label.
+const LABEL_TEXT_SIZE: f32 = 10.0;
+/// The distance between the edge label and the edge line.
+const LABEL_OFFSET: f32 = 6.0;
+/// The camera zoom below which the edge labels are fully transparent.
+const LABEL_HIDDEN_BELOW_ZOOM: f32 = 0.4;
+/// The camera zoom above which the edge labels are fully opaque.
+const LABEL_VISIBLE_ABOVE_ZOOM: f32 = 0.7;
+/// The gap between the portal markers and the node or port they are attached to.
+const PORTAL_MARKER_GAP: f32 = 4.0;
+
+
+
 // ===========
 // === FRP ===
 // ===========
@@ -51,16 +92,39 @@ define_endpoints_2! {
         /// dragged by the mouse.)
         source_attached(bool),
         set_disabled(bool),
+        /// Dim the edge, indicating it is not related to the currently focused part of the graph.
+        set_dimmed(bool),
+        /// Mark the edge as carrying a value poisoned by an error in one of the upstream nodes. Such
+        /// edges are drawn desaturated and dimmed.
+        set_poisoned(bool),
+        /// Show a dot moving along the edge from its source to its target, indicating the direction
+        /// of data flow.
+        set_flow_indicator(bool),
+        /// Send a single pulse traveling along the edge from its source to its target, indicating
+        /// that the source node was recomputed.
+        pulse(),
         /// Whether the edge should stop responding to mouse movement.
         set_hover_disabled(bool),
         /// The typical color of the node; also used to derive the focus color.
         set_color(color::Lcha),
+        /// Set the label displayed next to the midpoint of the edge. The label is shown only while
+        /// the edge is attached at both ends.
+        set_label(Option<ImString>),
+        /// Collapse the edge into the portal, or expand it back if `None`. See
+        /// [`component::portal`](crate::component::portal).
+        set_portal(Option<PortalId>),
+        /// Hide the edge, as it is replaced by the edge stubs of one of the nodes it connects.
+        set_hidden(bool),
     }
     Output {
-        /// The edge was clicked close to the source end.
+        /// The edge was grabbed to detach its source end: it was pressed closer to the source end,
+        /// or closer to the target end with the `ctrl` key held.
         source_click(),
-        /// The edge was clicked close to the target end.
+        /// The edge was grabbed to detach its target end: it was pressed closer to the target end,
+        /// or closer to the source end with the `ctrl` key held.
         target_click(),
+        /// Whether the mouse is over the edge. The hovered edge is drawn with a thicker line.
+        hovered(bool),
     }
 }
 
@@ -84,24 +148,37 @@ impl Edge {
     #[profile(Detail)]
     pub fn new(app: &Application, layers: &GraphLayers) -> Self {
         let frp = Frp::new();
-        let model = Rc::new(EdgeModel::new(&app.display.default_scene, layers));
+        let scene = &app.display.default_scene;
+        let model = Rc::new(EdgeModel::new(app, layers));
         let network = &frp.network;
         let display_object = &model.display_object;
         let output = &frp.private.output;
 
         let edge_color = color::Animation::new(network);
+        let styles = StyleWatchFrp::new(&scene.style_sheet);
+        let dimmed_alpha = styles.get_number(theme::graph_editor::focus_mode::dimmed_alpha);
+        let poisoned_alpha =
+            styles.get_number(theme::graph_editor::error_propagation::poisoned_alpha);
+        let label_color = styles.get_color(theme::graph_editor::edge::label);
         let mouse_move = display_object.on_event::<mouse::Move>();
         let mouse_down = display_object.on_event::<mouse::Down>();
         let mouse_out = display_object.on_event::<mouse::Out>();
+        let fonts = scene.extension::<text::font::Registry>();
 
         frp::extend! { network
             // Setters.
+            label_resized <- source_();
             eval frp.target_position ((t) model.inputs.set_target_position(ParentCoords(*t)));
             eval frp.source_attached ((t) model.inputs.set_source_attached(*t));
             eval frp.target_attached ((t) model.inputs.set_target_attached(*t));
             eval frp.source_size ((t) model.inputs.set_source_size(*t));
             eval frp.target_size ((t) model.inputs.set_target_size(*t));
             eval frp.set_disabled ((t) model.inputs.set_disabled(*t));
+            eval frp.set_label ([model, label_resized](label)
+                model.set_label(label, &label_resized)
+            );
+            eval frp.set_portal ((portal) model.set_portal(*portal));
+            eval frp.set_hidden ((hidden) model.set_hidden(*hidden));
 
             // Mouse events.
             gated_mouse_move <- mouse_move.gate_not(&frp.set_hover_disabled);
@@ -115,10 +192,12 @@ impl Edge {
                 model.inputs.set_mouse_position(pos);
             });
             eval_ clear_focus (model.inputs.clear_focus.set(true));
+            hovered <- bool(&clear_focus, &gated_mouse_move);
+            output.hovered <+ hovered.on_change();
             eval gated_mouse_down ([model, output] (e) {
                 let pos = model.screen_pos_to_scene_pos(e.client_centered());
                 let pos = model.scene_pos_to_parent_pos(pos);
-                match model.closer_end(pos) {
+                match model.port_to_detach_for_position(pos, e.ctrl_key()) {
                     Some(EndPoint::Source) => output.source_click.emit(()),
                     Some(EndPoint::Target) => output.target_click.emit(()),
                     // Ignore click events that were delivered to our display object inaccurately.
@@ -127,7 +206,15 @@ impl Edge {
             });
 
             // Colors.
-            edge_color.target <+ frp.set_color;
+            poisoned_color <- all_with3(&frp.set_color, &frp.set_poisoned, &poisoned_alpha,
+                |color, poisoned, alpha| match *poisoned {
+                    true => color.to_grayscale().multiply_alpha(*alpha),
+                    false => *color,
+                }
+            );
+            edge_color.target <+ all_with3(&poisoned_color, &frp.set_dimmed, &dimmed_alpha,
+                |color, dimmed, alpha| if *dimmed { color.multiply_alpha(*alpha) } else { *color }
+            );
             eval edge_color.value ((color) model.inputs.set_color(color.into()));
 
             // Invalidation.
@@ -137,13 +224,51 @@ impl Edge {
             redraw_needed <+ frp.target_attached;
             redraw_needed <+ frp.source_size;
             redraw_needed <+ frp.set_disabled;
+            redraw_needed <+ frp.set_label;
+            redraw_needed <+ frp.set_portal;
+            redraw_needed <+ frp.set_hidden;
+            redraw_needed <+ model.source_portal.width;
+            redraw_needed <+ label_resized;
             redraw_needed <+ gated_mouse_move;
             redraw_needed <+ gated_mouse_out;
             redraw_needed <+ edge_color.value;
             redraw_needed <+ display_object.on_transformed;
             redraw <- redraw_needed.debounce();
             eval_ redraw (model.redraw());
+
+            // Flow indicator.
+            flow_time <- scene.frp.frame_time.gate(&frp.set_flow_indicator);
+            flow_phase <- flow_time.map(|t| {
+                Some(t % FLOW_INDICATOR_PERIOD_MS / FLOW_INDICATOR_PERIOD_MS)
+            });
+            flow_hidden <- frp.set_flow_indicator.on_false().constant(None);
+            flow_indicator <- any(flow_phase, flow_hidden);
+            eval flow_indicator ((phase) model.redraw_flow_indicator(*phase));
+
+            // Recomputation pulse.
+            pulse_active <- any_mut::<bool>();
+            pulse_active <+ frp.pulse.constant(true);
+            pulse_start <- scene.frp.frame_time.sample(&frp.pulse);
+            pulse_time <- scene.frp.frame_time.gate(&pulse_active);
+            pulse_progress <- pulse_time.map2(&pulse_start, |t, start| {
+                (t - start) / PULSE_DURATION_MS
+            });
+            pulse_finished <- pulse_progress.filter(|progress| *progress >= 1.0);
+            pulse_active <+ pulse_finished.constant(false);
+            eval pulse_progress ((progress) model.redraw_pulse(*progress));
+
+            // Label. It fades out when zooming out, as it would be unreadable anyway. The opacity
+            // is updated only when the zoom crosses a bucket boundary.
+            label_init <- source_();
+            zoom_bucket <- all(&fonts.zoom_bucket, &label_init)._0();
+            label_opacity <- zoom_bucket.map(|bucket| {
+                let range = LABEL_VISIBLE_ABOVE_ZOOM - LABEL_HIDDEN_BELOW_ZOOM;
+                ((bucket.zoom() - LABEL_HIDDEN_BELOW_ZOOM) / range).clamp(0.0, 1.0)
+            }).on_change();
+            label_color <- all_with(&label_color, &label_opacity, |c, o| c.multiply_alpha(*o));
+            eval label_color ((color) model.set_label_color(*color));
         }
+        label_init.emit(());
         Self { model, frp }
     }
 
@@ -151,6 +276,25 @@ impl Edge {
     pub fn network(&self) -> &frp::Network {
         &self.frp.network
     }
+
+    /// Check whether the point in the scene coordinates lies on the edge, within the width of its
+    /// hover area. The collapsed and hidden edges contain no points.
+    pub fn contains_scene_position(&self, position: Vector2) -> bool {
+        self.model.contains_position(SceneCoords(position))
+    }
+
+    /// Bring the edge back to the state of a newly created edge, so it can be reused to display
+    /// another connection. The endpoints and the color are not reset, as they are always set by
+    /// the Graph Editor when the edge is displayed.
+    pub fn reset(&self) {
+        self.set_disabled(false);
+        self.set_dimmed(false);
+        self.set_poisoned(false);
+        self.set_flow_indicator(false);
+        self.set_label(None);
+        self.set_portal(None);
+        self.set_hidden(false);
+    }
 }
 
 
@@ -168,25 +312,54 @@ struct EdgeModel {
     scene:          Scene,
     /// The [`GraphLayers`], used for special layer assignments.
     layers:         GraphLayers,
+    /// The style sheet watcher, used to read the theme colors when redrawing.
+    styles:         StyleWatch,
     /// The raw inputs the state is computed from.
     inputs:         Inputs,
     /// The state, as of the last redraw.
     state:          RefCell<Option<State>>,
     /// The currently-rendered shapes implementing the state.
     shapes:         Shapes,
+    /// The application, used to create the label when it is first needed.
+    app:            Application,
+    /// The label displayed next to the midpoint of the edge. Most edges have no label, so it is
+    /// created only when the first label is set.
+    label:          RefCell<Option<text::Text>>,
+    /// The color of the label, applied also to the label created later.
+    label_color:    Cell<color::Rgba>,
+    /// Whether the label has any content to display.
+    has_label:      Cell<bool>,
+    /// The marker displayed below the source node when the edge is collapsed into a portal.
+    source_portal:  portal::PortalMarker,
+    /// The marker displayed above the target port when the edge is collapsed into a portal.
+    target_portal:  portal::PortalMarker,
 }
 
 impl EdgeModel {
     /// Constructor.
     #[profile(Debug)]
-    pub fn new(scene: &Scene, layers: &GraphLayers) -> Self {
+    pub fn new(app: &Application, layers: &GraphLayers) -> Self {
+        let display_object = display::object::Instance::new_named("Edge");
+        let source_portal = portal::PortalMarker::new(app);
+        let target_portal = portal::PortalMarker::new(app);
+        display_object.add_child(&source_portal);
+        display_object.add_child(&target_portal);
+        let scene = app.display.default_scene.clone_ref();
+        let styles = StyleWatch::new(&scene.style_sheet);
         Self {
-            display_object: display::object::Instance::new_named("Edge"),
-            scene:          scene.clone_ref(),
-            layers:         layers.clone_ref(),
-            inputs:         default(),
-            state:          default(),
-            shapes:         default(),
+            display_object,
+            scene,
+            layers: layers.clone_ref(),
+            styles,
+            inputs: default(),
+            state: default(),
+            shapes: default(),
+            app: app.clone_ref(),
+            label: default(),
+            label_color: default(),
+            has_label: default(),
+            source_portal,
+            target_portal,
         }
     }
 
@@ -196,6 +369,96 @@ impl EdgeModel {
         let state = self.calculate_state();
         self.apply_state(&state);
         self.state.replace(Some(state));
+        self.redraw_label();
+        self.redraw_portal_markers();
+    }
+
+    fn set_portal(&self, portal: Option<PortalId>) {
+        self.inputs.set_portal(portal);
+        self.update_portal_markers();
+    }
+
+    fn set_hidden(&self, hidden: bool) {
+        self.inputs.set_hidden(hidden);
+        self.update_portal_markers();
+    }
+
+    /// Show the portal markers of the edge collapsed into a portal, unless the edge is hidden.
+    fn update_portal_markers(&self) {
+        let portal = self.inputs.portal.get().filter(|_| !self.inputs.hidden.get());
+        self.source_portal.set_portal(portal);
+        self.target_portal.set_portal(portal);
+    }
+
+    /// Place the portal markers below the source node and above the target port.
+    fn redraw_portal_markers(&self) {
+        if self.inputs.portal.get().is_some() {
+            let source_size = self.inputs.source_size.get();
+            let target_size = self.inputs.target_size.get();
+            let marker_offset = PORTAL_MARKER_GAP + portal::MARKER_HEIGHT / 2.0;
+            let source_y = -source_size.y() / 2.0 - marker_offset;
+            self.source_portal.set_xy(Vector2(0.0, source_y));
+            let target_y = target_size.y() / 2.0 + marker_offset;
+            self.target_portal.set_xy(self.target_offset() + Vector2(0.0, target_y));
+        }
+    }
+
+    /// Set the label content, creating the label text if needed. The `resized` endpoint is
+    /// emitted whenever the width of the created label changes.
+    fn set_label(&self, label: &Option<ImString>, resized: &frp::Source) {
+        self.has_label.set(label.is_some());
+        if let Some(label) = label {
+            let mut text = self.label.borrow_mut();
+            let text = text.get_or_insert_with(|| self.new_label(resized));
+            text.set_content(label.to_string());
+        }
+    }
+
+    fn new_label(&self, resized: &frp::Source) -> text::Text {
+        let label = self.app.new_view::<text::Text>();
+        label.set_single_line_mode(true);
+        label.set_property_default(text::Size(LABEL_TEXT_SIZE));
+        label.set_property_default(self.label_color.get());
+        label.remove_all_cursors();
+        let network = label.frp.network();
+        let resized = resized.clone_ref();
+        frp::extend! { network
+            eval_ label.width (resized.emit(()));
+        }
+        label
+    }
+
+    fn set_label_color(&self, color: color::Rgba) {
+        self.label_color.set(color);
+        if let Some(label) = &*self.label.borrow() {
+            label.set_property_default(color);
+        }
+    }
+
+    /// Place the label next to the midpoint of the edge, on the side where it does not overlap the
+    /// edge line: above the horizontal parts of the edge, and to the right of the vertical ones.
+    fn redraw_label(&self) {
+        let state = self.state.borrow();
+        let state = state.as_ref().filter(|state| state.is_attached.is_attached);
+        let midpoint = state.and_then(|state| state.layout.point_and_direction_along(0.5));
+        let label = self.label.borrow();
+        let Some(label) = label.as_ref() else { return };
+        match midpoint.filter(|_| self.has_label.get()) {
+            Some((position, direction)) => {
+                let width = label.width.value();
+                let is_horizontal = direction.x().abs() > direction.y().abs();
+                let offset = if is_horizontal {
+                    Vector2(-width / 2.0, LABEL_OFFSET + LABEL_TEXT_SIZE)
+                } else {
+                    Vector2(LABEL_OFFSET, LABEL_TEXT_SIZE / 2.0)
+                };
+                label.set_xy(position + offset);
+                if !label.has_parent() {
+                    self.display_object.add_child(label);
+                }
+            }
+            None => label.unset_parent(),
+        }
     }
 
     fn calculate_state(&self) -> State {
@@ -207,13 +470,13 @@ impl EdgeModel {
         let source_attached = self.inputs.source_attached.get();
         let source_size = self.inputs.source_size.get();
         let target_size = self.inputs.target_size.get();
-        let layout = layout::layout(
-            target_offset,
-            source_size,
-            target_size,
-            source_attached,
-            target_attached,
-        );
+        let layout = if self.inputs.portal.get().is_some() || self.inputs.hidden.get() {
+            // The edge collapsed into a portal is represented by the portal markers only, and the
+            // hidden edge is not represented at all.
+            Layout::collapsed(source_size)
+        } else {
+            Layout::new(target_offset, source_size, target_size, source_attached, target_attached)
+        };
         let is_attached = target_attached && source_attached;
         let focus_split = is_attached
             .then(|| {
@@ -225,11 +488,11 @@ impl EdgeModel {
                 self.inputs.hover_position.get().and_then(|position| {
                     let position = self.scene_pos_to_parent_pos(position);
                     let source_height = source_size.y();
-                    layout::find_position(position, &layout, source_height, render::HOVER_WIDTH)
+                    layout.find_position(position, source_height, render::HOVER_WIDTH)
                 })
             })
             .flatten();
-        let styles = StyleWatch::new(&self.scene.style_sheet);
+        let styles = &self.styles;
         let normal_color = if self.inputs.disabled.get() {
             styles.get_color(theme::graph_editor::edge::disabled_color)
         } else {
@@ -250,6 +513,38 @@ impl EdgeModel {
         }
     }
 
+    /// Draw the flow indicator at the given fraction of the edge's length, or hide it.
+    fn redraw_flow_indicator(&self, phase: Option<f32>) {
+        let state = self.state.borrow();
+        let state = state.as_ref().filter(|state| state.is_attached.is_attached);
+        let position = phase.zip(state).and_then(|(phase, state)| state.layout.point_along(phase));
+        let styles = &self.styles;
+        let bg_color = styles.get_color(theme::application::background);
+        let color = state.map_or(bg_color, |state| state.colors.source_color);
+        let color = color::mix(bg_color, color, 0.25);
+        self.shapes.redraw_flow_indicator(self, position, color);
+    }
+
+    /// Draw the recomputation pulse at the given progress of its travel. The pulse is hidden once
+    /// the progress reaches 1.0.
+    fn redraw_pulse(&self, progress: f32) {
+        let state = self.state.borrow();
+        let state = state.as_ref().filter(|state| state.is_attached.is_attached && progress < 1.0);
+        let dots = state.into_iter().flat_map(|state| {
+            let color = state.colors.source_color;
+            (0..PULSE_DOT_COUNT).filter_map(move |index| {
+                let fraction = progress - index as f32 * PULSE_DOT_SPACING;
+                if fraction < 0.0 {
+                    return None;
+                }
+                let position = state.layout.point_along(fraction)?;
+                let fade = 1.0 - index as f32 / PULSE_DOT_COUNT as f32;
+                Some((position, color.multiply_alpha(fade)))
+            })
+        });
+        self.shapes.redraw_pulse(self, dots);
+    }
+
     fn apply_state(&self, state: &State) {
         let StateUpdate { layout, colors, is_attached, focus_split } =
             state.compare(&self.state.borrow());
@@ -284,9 +579,18 @@ impl EdgeModel {
                     self.shapes.redraw_cutout(self, *is_attached, *source_size);
                 },
             ))
-            .or(any(layout, colors).changed(
-                |(Layout { target_attachment, .. }, Colors { target_color, .. })| {
-                    self.shapes.redraw_target_attachment(self, *target_attachment, *target_color);
+            .or(any3(layout, colors, focus_split).changed(
+                |(
+                    Layout { target_attachment, .. },
+                    Colors { target_color, .. },
+                    FocusSplit { focus_split, .. },
+                )| {
+                    self.shapes.redraw_target_attachment(
+                        self,
+                        *target_attachment,
+                        *target_color,
+                        *focus_split,
+                    );
                 },
             ))
             .is_some();
@@ -316,12 +620,23 @@ impl EdgeModel {
         ParentCoords(*scene_pos - self.display_object.xy())
     }
 
-    fn closer_end(&self, pos: ParentCoords) -> Option<EndPoint> {
+    /// The end of the edge detached when the edge is grabbed at the given position: the end closer
+    /// to the position, or the farther one if `farther` is set.
+    fn port_to_detach_for_position(&self, pos: ParentCoords, farther: bool) -> Option<EndPoint> {
         let state = self.state.borrow();
         let state = state.as_ref()?;
         let source_height = self.inputs.source_size.get().y();
-        layout::find_position(pos, &state.layout, source_height, render::HOVER_WIDTH)
-            .map(|split| split.closer_end)
+        let split = state.layout.find_position(pos, source_height, render::HOVER_WIDTH)?;
+        Some(if farther { split.closer_end.opposite() } else { split.closer_end })
+    }
+
+    fn contains_position(&self, pos: SceneCoords) -> bool {
+        let state = self.state.borrow();
+        let state = state.as_ref().filter(|state| state.is_attached.is_attached);
+        let Some(state) = state else { return false };
+        let source_height = self.inputs.source_size.get().y();
+        let pos = self.scene_pos_to_parent_pos(pos);
+        state.layout.find_position(pos, source_height, render::HOVER_WIDTH).is_some()
     }
 
     fn target_offset(&self) -> Vector2 {
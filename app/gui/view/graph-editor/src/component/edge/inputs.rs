@@ -1,6 +1,8 @@
 use super::coords::*;
 use crate::prelude::*;
 
+use crate::component::portal::PortalId;
+
 use ensogl::data::color;
 
 
@@ -31,6 +33,10 @@ pub(super) struct Inputs {
     pub disabled:        Cell<bool>,
     /// Reset the hover position at next redraw.
     pub clear_focus:     Cell<bool>,
+    /// The portal the edge is collapsed into. Such edge is displayed as a pair of portal markers.
+    pub portal:          Cell<Option<PortalId>>,
+    /// Whether the edge is hidden. Such edge is not displayed at all.
+    pub hidden:          Cell<bool>,
 }
 
 impl Inputs {
@@ -54,6 +60,14 @@ impl Inputs {
         self.disabled.set(disabled);
     }
 
+    pub(super) fn set_portal(&self, portal: Option<PortalId>) {
+        self.portal.set(portal);
+    }
+
+    pub(super) fn set_hidden(&self, hidden: bool) {
+        self.hidden.set(hidden);
+    }
+
     pub(super) fn set_target_position(&self, position: ParentCoords) {
         self.target_position.set(position);
     }
@@ -123,19 +123,39 @@ mod three_corner {
 // === Layout ===
 // ==============
 
-/// Determine the positions and shapes of all the components of the edge.
-pub(super) fn layout(
-    target: Vector2,
-    source_size: Vector2,
-    target_size: Vector2,
-    source_attached: bool,
-    target_attached: bool,
-) -> Layout {
-    let (junction_points, max_radius, target_attachment) =
-        junction_points(target, source_size, target_size, source_attached, target_attached);
-    let corners = corners(&junction_points, max_radius).collect_vec();
-    let arrow = arrow(target, &junction_points);
-    Layout { corners, arrow, target_attachment, source_size }
+/// An edge's layout: the positions and shapes of all the components of the edge.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Layout {
+    /// The corners composing the main part of the edge.
+    pub(super) corners:           Vec<Oriented<Corner>>,
+    /// The center of the backward-edge arrow.
+    pub(super) arrow:             Option<Vector2>,
+    /// The target-attachment end.
+    pub(super) target_attachment: Option<TargetAttachment>,
+    /// The size of the source node.
+    pub(super) source_size:       Vector2,
+}
+
+impl Layout {
+    /// Determine the positions and shapes of all the components of the edge.
+    pub(super) fn new(
+        target: Vector2,
+        source_size: Vector2,
+        target_size: Vector2,
+        source_attached: bool,
+        target_attached: bool,
+    ) -> Self {
+        let (junction_points, max_radius, target_attachment) =
+            junction_points(target, source_size, target_size, source_attached, target_attached);
+        let corners = corners(&junction_points, max_radius).collect_vec();
+        let arrow = arrow(target, &junction_points);
+        Self { corners, arrow, target_attachment, source_size }
+    }
+
+    /// The layout of an edge with no visible components.
+    pub(super) fn collapsed(source_size: Vector2) -> Self {
+        Self { corners: default(), arrow: None, target_attachment: None, source_size }
+    }
 }
 
 
@@ -271,6 +291,16 @@ pub(super) enum EndPoint {
     Target,
 }
 
+impl EndPoint {
+    /// The other end of the edge.
+    pub fn opposite(self) -> Self {
+        match self {
+            EndPoint::Source => EndPoint::Target,
+            EndPoint::Target => EndPoint::Source,
+        }
+    }
+}
+
 
 
 // =======================
@@ -284,46 +314,77 @@ pub(super) struct EdgeSplit {
     pub split_corner: SplitCorner,
 }
 
-/// Find a point along the edge. Return the index of the corner the point occurs in, and which end
-/// is closer to the point, and information about how the corner under the point has been split.
-///
-/// Returns [`None`] if the point is not on the edge.
-pub(super) fn find_position(
-    position: ParentCoords,
-    layout: &Layout,
-    source_height: f32,
-    input_width: f32,
-) -> Option<EdgeSplit> {
-    let position = *position;
-    let corners = &layout.corners;
-    let corner_index = corners
-        .iter()
-        .position(|&corner| corner.bounding_box(input_width).contains_inclusive(position))?;
-    let split_corner = corners[corner_index].split(position, input_width)?;
-    let (full_corners, following_corners) = corners.split_at(corner_index);
-    let full_corners_distance: f32 =
-        full_corners.iter().map(|&corner| corner.rectilinear_length()).sum();
-    let following_distance: f32 =
-        following_corners.iter().map(|&corner| corner.rectilinear_length()).sum();
-    let target_attachment_distance =
-        layout.target_attachment.map(|bit| bit.length).unwrap_or_default();
-    // The source end of the edge is on a horizontal line through the center of the source node
-    // (this gives nice behavior when the edge exits the end at an angle). To accurately determine
-    // which end a point appears closer to, we must exclude the portion of the edge that is hidden
-    // under the source node.
-    let hidden_source_distance = source_height / 2.0;
-    let total_distance = full_corners_distance + following_distance - hidden_source_distance
-        + target_attachment_distance;
-    let offset_from_partial_corner = position - corners[corner_index].source_end();
-    let partial_corner_distance =
-        offset_from_partial_corner.x().abs() + offset_from_partial_corner.y().abs();
-    let distance_from_source =
-        full_corners_distance + partial_corner_distance - hidden_source_distance;
-    let closer_end = match distance_from_source * 2.0 < total_distance {
-        true => EndPoint::Source,
-        false => EndPoint::Target,
-    };
-    Some(EdgeSplit { corner_index, closer_end, split_corner })
+impl Layout {
+    /// Find a point along the edge. Return the index of the corner the point occurs in, and which
+    /// end is closer to the point, and information about how the corner under the point has been
+    /// split.
+    ///
+    /// Returns [`None`] if the point is not on the edge.
+    pub(super) fn find_position(
+        &self,
+        position: ParentCoords,
+        source_height: f32,
+        input_width: f32,
+    ) -> Option<EdgeSplit> {
+        let position = *position;
+        let corners = &self.corners;
+        let corner_index = corners
+            .iter()
+            .position(|&corner| corner.bounding_box(input_width).contains_inclusive(position))?;
+        let split_corner = corners[corner_index].split(position, input_width)?;
+        let (full_corners, following_corners) = corners.split_at(corner_index);
+        let full_corners_distance: f32 =
+            full_corners.iter().map(|&corner| corner.rectilinear_length()).sum();
+        let following_distance: f32 =
+            following_corners.iter().map(|&corner| corner.rectilinear_length()).sum();
+        let target_attachment_distance =
+            self.target_attachment.map(|bit| bit.length).unwrap_or_default();
+        // The source end of the edge is on a horizontal line through the center of the source
+        // node (this gives nice behavior when the edge exits the end at an angle). To accurately
+        // determine which end a point appears closer to, we must exclude the portion of the edge
+        // that is hidden under the source node.
+        let hidden_source_distance = source_height / 2.0;
+        let total_distance = full_corners_distance + following_distance - hidden_source_distance
+            + target_attachment_distance;
+        let offset_from_partial_corner = position - corners[corner_index].source_end();
+        let partial_corner_distance =
+            offset_from_partial_corner.x().abs() + offset_from_partial_corner.y().abs();
+        let distance_from_source =
+            full_corners_distance + partial_corner_distance - hidden_source_distance;
+        let closer_end = match distance_from_source * 2.0 < total_distance {
+            true => EndPoint::Source,
+            false => EndPoint::Target,
+        };
+        Some(EdgeSplit { corner_index, closer_end, split_corner })
+    }
+
+    /// Find the point located at the given fraction (in the range `0..=1`) of the edge's length,
+    /// measured from the source end. The rounded parts of the corners are approximated by their
+    /// rectilinear knees.
+    pub(super) fn point_along(&self, fraction: f32) -> Option<Vector2> {
+        self.point_and_direction_along(fraction).map(|(point, _)| point)
+    }
+
+    /// Find the point located at the given fraction of the edge's length, like
+    /// [`Self::point_along`], and the direction of the edge at that point: a unit vector pointing
+    /// towards the target end. The direction is zero if the edge has no length.
+    pub(super) fn point_and_direction_along(&self, fraction: f32) -> Option<(Vector2, Vector2)> {
+        let corners = &self.corners;
+        let total_distance: f32 = corners.iter().map(|&corner| corner.rectilinear_length()).sum();
+        let mut remaining = total_distance * fraction.clamp(0.0, 1.0);
+        for &corner in corners {
+            let knee = Vector2(corner.vertical.x(), corner.horizontal.y());
+            for (start, end) in [(corner.source_end(), knee), (knee, corner.target_end())] {
+                let length = (end - start).norm();
+                if remaining <= length && length > f32::EPSILON {
+                    let direction = (end - start) / length;
+                    return Some((start + direction * remaining, direction));
+                }
+                remaining -= length;
+            }
+        }
+        corners.last().map(|corner| (corner.target_end(), Vector2::zero()))
+    }
 }
 
 
@@ -610,7 +671,6 @@ impl Oriented<Corner> {
         }
     }
 
-    #[allow(unused)]
     fn target_end(self) -> Vector2 {
         match self.direction {
             CornerDirection::VerticalToHorizontal => self.value.horizontal,
@@ -28,6 +28,8 @@ use std::f32::consts::TAU;
 // =================
 
 const LINE_WIDTH: f32 = 4.0;
+/// The width of the line of the hovered edge, which is drawn thicker to make it easier to trace.
+const EMPHASIZED_LINE_WIDTH: f32 = 6.0;
 const HOVER_EXTENSION: f32 = 10.0;
 pub(super) const HOVER_WIDTH: f32 = LINE_WIDTH + HOVER_EXTENSION;
 
@@ -36,6 +38,16 @@ mod arrow {
     pub(super) const SIZE: Vector2 = Vector2(18.75, 18.75);
 }
 
+mod flow_indicator {
+    use super::*;
+    pub(super) const SIZE: Vector2 = Vector2(8.0, 8.0);
+}
+
+mod pulse {
+    use super::*;
+    pub(super) const SIZE: Vector2 = Vector2(10.0, 10.0);
+}
+
 mod attachment {
     /// Extra length to add to the top and bottom of the target-attachment bit, to ensure that it
     /// appears to pass through the top of the node. Without this adjustment, inexact
@@ -69,6 +81,14 @@ pub(super) struct Shapes {
     /// An rectangle representing the source node shape when the edge is in detached state. Used
     /// to mask out the edge fragment that would otherwise be drawn over the source node.
     source_cutout:     RefCell<Option<Rectangle>>,
+    /// A dot moving along the edge to indicate the data flow direction, shown when the edge is a
+    /// part of the highlighted lineage.
+    flow_indicator:    RefCell<Option<Rectangle>>,
+    /// The dots forming a pulse traveling along the edge after the source node was recomputed.
+    /// The first dot is the head of the pulse, the following ones form its fading tail.
+    pulse:             RefCell<Vec<Rectangle>>,
+    /// The line width the [`sections`] are currently drawn with.
+    line_width:        Cell<f32>,
 }
 
 impl Shapes {
@@ -96,6 +116,42 @@ impl Shapes {
         }
     }
 
+    /// Redraw the dot indicating the data flow direction, or remove it if no position is given.
+    pub(super) fn redraw_flow_indicator(
+        &self,
+        parent: &impl ShapeParent,
+        position: Option<Vector2>,
+        color: color::Rgba,
+    ) {
+        let shape = self.flow_indicator.take();
+        if let Some(position) = position {
+            let shape = shape.unwrap_or_else(|| parent.new_flow_indicator());
+            shape.set_xy(position - flow_indicator::SIZE / 2.0);
+            shape.set_color(color);
+            self.flow_indicator.replace(Some(shape));
+        }
+    }
+
+    /// Redraw the dots of the recomputation pulse. Each dot is given by its position and color;
+    /// the dots not used anymore are removed.
+    pub(super) fn redraw_pulse(
+        &self,
+        parent: &impl ShapeParent,
+        dots: impl IntoIterator<Item = (Vector2, color::Rgba)>,
+    ) {
+        let pulse_factory =
+            self.pulse.take().into_iter().chain(iter::repeat_with(|| parent.new_pulse_dot()));
+        *self.pulse.borrow_mut() = dots
+            .into_iter()
+            .zip(pulse_factory)
+            .map(|((position, color), shape)| {
+                shape.set_xy(position - pulse::SIZE / 2.0);
+                shape.set_color(color);
+                shape
+            })
+            .collect();
+    }
+
     /// Redraw the invisible mouse-event-catching edges.
     pub(super) fn redraw_hover_sections(
         &self,
@@ -122,20 +178,29 @@ impl Shapes {
         let corner_index =
             focus_split.map(|split| split.corner_index).unwrap_or_else(|| corners.len());
         let split_corner = focus_split.map(|split| split.split_corner);
+        let line_width = line_width(focus_split);
+        let line_width_changed = self.line_width.replace(line_width) != line_width;
+        let reused_sections = self.sections.take().into_iter().map(|shape| {
+            if line_width_changed {
+                shape.set_inner_border(line_width, 0.0);
+            }
+            shape
+        });
         let mut section_factory =
-            self.sections.take().into_iter().chain(iter::repeat_with(|| parent.new_section()));
+            reused_sections.chain(iter::repeat_with(|| parent.new_section(line_width)));
         let mut new_sections = self.redraw_complete_sections(
             &mut section_factory,
             corners,
             corner_index,
             source_color,
             target_color,
+            line_width,
         );
         let arc_shapes = self.split_arc.take();
         if let Some(split_corner) = split_corner {
             if let Some(split_arc) = split_corner.split_arc {
                 let arc_shapes = arc_shapes.unwrap_or_else(|| [parent.new_arc(), parent.new_arc()]);
-                let arc_shapes = draw_split_arc(arc_shapes, split_arc);
+                let arc_shapes = draw_split_arc(arc_shapes, split_arc, line_width);
                 arc_shapes[0].color.set(source_color.into());
                 arc_shapes[1].color.set(target_color.into());
                 self.split_arc.replace(Some(arc_shapes));
@@ -143,8 +208,8 @@ impl Shapes {
             let (source_shape, target_shape) =
                 (section_factory.next().unwrap(), section_factory.next().unwrap());
             new_sections.extend([
-                draw_corner(source_shape, *split_corner.source_end, source_color, LINE_WIDTH),
-                draw_corner(target_shape, *split_corner.target_end, target_color, LINE_WIDTH),
+                draw_corner(source_shape, *split_corner.source_end, source_color, line_width),
+                draw_corner(target_shape, *split_corner.target_end, target_color, line_width),
             ]);
         }
 
@@ -177,6 +242,7 @@ impl Shapes {
         corner_index: usize,
         source_color: color::Rgba,
         target_color: color::Rgba,
+        line_width: f32,
     ) -> Vec<Rectangle> {
         corners
             .iter()
@@ -193,7 +259,7 @@ impl Shapes {
                 }
             })
             .zip(section_factory)
-            .map(|((color, corner), shape)| draw_corner(shape, **corner, color, LINE_WIDTH))
+            .map(|((color, corner), shape)| draw_corner(shape, **corner, color, line_width))
             .collect()
     }
 
@@ -203,13 +269,15 @@ impl Shapes {
         parent: &impl ShapeParent,
         target_attachment: Option<TargetAttachment>,
         color: color::Rgba,
+        focus_split: Option<EdgeSplit>,
     ) {
         let shape = self.target_attachment.take();
         if let Some(TargetAttachment { target, length }) = target_attachment
                 && length > f32::EPSILON {
             let shape = shape.unwrap_or_else(|| parent.new_target_attachment());
-            shape.set_size_y(length + attachment::LENGTH_ADJUSTMENT * 2.0);
-            let offset = Vector2(-LINE_WIDTH / 2.0, - length - attachment::LENGTH_ADJUSTMENT);
+            let line_width = line_width(focus_split);
+            shape.set_size(Vector2(line_width, length + attachment::LENGTH_ADJUSTMENT * 2.0));
+            let offset = Vector2(-line_width / 2.0, - length - attachment::LENGTH_ADJUSTMENT);
             shape.set_xy(target + offset);
             shape.set_color(color);
             self.target_attachment.replace(Some(shape));
@@ -311,9 +379,9 @@ pub(super) trait ShapeParent: display::Object {
     fn layers(&self) -> &GraphLayers;
 
     /// Create a shape object to render one of the [`Corner`]s making up the edge.
-    fn new_section(&self) -> Rectangle {
+    fn new_section(&self, line_width: f32) -> Rectangle {
         let new = Rectangle::new();
-        new.set_inner_border(LINE_WIDTH, 0.0);
+        new.set_inner_border(line_width, 0.0);
         new.set_color(color::Rgba::transparent());
         new.set_pointer_events(false);
         self.display_object().add_child(&new);
@@ -335,7 +403,6 @@ pub(super) trait ShapeParent: display::Object {
     /// in the rounded part of a [`Corner`].
     fn new_arc(&self) -> arc::View {
         let arc = arc::View::new();
-        arc.stroke_width.set(LINE_WIDTH);
         self.display_object().add_child(&arc);
         self.layers().edge_below_nodes.add(&arc);
         arc
@@ -345,7 +412,6 @@ pub(super) trait ShapeParent: display::Object {
     /// top of the node.
     fn new_target_attachment(&self) -> Rectangle {
         let new = Rectangle::new();
-        new.set_size_x(LINE_WIDTH);
         new.set_border_color(color::Rgba::transparent());
         new.set_pointer_events(false);
         self.display_object().add_child(&new);
@@ -362,6 +428,29 @@ pub(super) trait ShapeParent: display::Object {
         new.into()
     }
 
+    /// Create a shape object to render the dot moving along the edge to show the direction of
+    /// data flow.
+    fn new_flow_indicator(&self) -> Rectangle {
+        let new = Rectangle::new();
+        new.set_size(flow_indicator::SIZE);
+        new.set_corner_radius_max();
+        new.set_pointer_events(false);
+        self.display_object().add_child(&new);
+        self.layers().edge_below_nodes.add(&new);
+        new
+    }
+
+    /// Create a shape object to render a single dot of the recomputation pulse.
+    fn new_pulse_dot(&self) -> Rectangle {
+        let new = Rectangle::new();
+        new.set_size(pulse::SIZE);
+        new.set_corner_radius_max();
+        new.set_pointer_events(false);
+        self.display_object().add_child(&new);
+        self.layers().edge_below_nodes.add(&new);
+        new
+    }
+
     /// Create a shape object to render the cutout mask for the edge nearby the source node.
     fn new_cutout(&self) -> Rectangle {
         let cutout = Rectangle::new();
@@ -383,6 +472,15 @@ pub(super) trait ShapeParent: display::Object {
 // === Rendering Corners ===
 // =========================
 
+/// The width of the edge line. The edge with a focus split is hovered, and is emphasized by a
+/// thicker line.
+fn line_width(focus_split: Option<EdgeSplit>) -> f32 {
+    match focus_split {
+        Some(_) => EMPHASIZED_LINE_WIDTH,
+        None => LINE_WIDTH,
+    }
+}
+
 /// Set the given [`Rectangle`]'s geometry to draw this corner shape.
 ///
 /// Note that the shape's `inset` and `border` should be the same value as the provided
@@ -409,8 +507,12 @@ pub(super) fn draw_corner(
 // ==============================
 
 /// Apply the specified arc-splitting parameters to the given arc shapes.
-pub(super) fn draw_split_arc(arc_shapes: [arc::View; 2], split_arc: SplitArc) -> [arc::View; 2] {
-    let outer_radius = split_arc.radius + LINE_WIDTH / 2.0;
+pub(super) fn draw_split_arc(
+    arc_shapes: [arc::View; 2],
+    split_arc: SplitArc,
+    line_width: f32,
+) -> [arc::View; 2] {
+    let outer_radius = split_arc.radius + line_width / 2.0;
     let arc_box = Vector2(outer_radius * 2.0, outer_radius * 2.0);
     let arc_offset = Vector2(-outer_radius, -outer_radius);
     let geometry = ArcGeometry::bisection(
@@ -422,6 +524,7 @@ pub(super) fn draw_split_arc(arc_shapes: [arc::View; 2], split_arc: SplitArc) ->
         shape.set_xy(split_arc.origin + arc_offset);
         shape.set_size(arc_box);
         shape.outer_radius.set(outer_radius);
+        shape.stroke_width.set(line_width);
         shape.start_angle.set(geometry.start);
         shape.sector_angle.set(geometry.sector);
     }
@@ -2,6 +2,7 @@ use crate::prelude::*;
 
 use super::layout::Corner;
 use super::layout::EdgeSplit;
+use super::layout::Layout;
 use super::layout::Oriented;
 use super::layout::TargetAttachment;
 
@@ -26,19 +27,6 @@ pub(super) struct State {
     pub focus_split: FocusSplit,
 }
 
-/// An edge's layout.
-#[derive(Debug, Clone, PartialEq)]
-pub(super) struct Layout {
-    /// The corners composing the main part of the edge.
-    pub corners:           Vec<Oriented<Corner>>,
-    /// The center of the backward-edge arrow.
-    pub arrow:             Option<Vector2>,
-    /// The target-attachment end.
-    pub target_attachment: Option<TargetAttachment>,
-    /// The size of the source node.
-    pub source_size:       Vector2,
-}
-
 /// An edge's color scheme.
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub(super) struct Colors {
@@ -0,0 +1,236 @@
+//! Creating nodes from the files dropped on the canvas. The nodes of the dropped files are arranged
+//! in a column below the drop position, and read the files with the expression configured for the
+//! file's extension. The column is highlighted while the files are dragged over the canvas.
+
+use crate::prelude::*;
+use ensogl::display::shape::*;
+
+use crate::component::node;
+
+use enso_frp as frp;
+use ensogl::application::Application;
+use ensogl::data::color;
+use ensogl::display;
+use ensogl_drop_manager::DragOverEventData;
+use ensogl_hardcoded_theme::graph_editor::file_drop as theme;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The placeholder in the [`ExpressionTemplate`] standing for the expression of the file's path.
+pub const PATH_PLACEHOLDER: &str = "{path}";
+/// The width of the highlight of the dropped nodes' column.
+const HIGHLIGHT_WIDTH: f32 = 160.0;
+/// The padding between the highlight and the nodes it covers.
+const HIGHLIGHT_PADDING: f32 = 6.0;
+/// The width of the highlight's border.
+const HIGHLIGHT_BORDER: f32 = 2.0;
+
+
+
+// =================
+// === Templates ===
+// =================
+
+/// The extension of the dropped file, in lowercase and without the leading dot, e.g. `csv`.
+pub type Extension = ImString;
+
+/// The expression of the node reading the dropped file, with the [`PATH_PLACEHOLDER`] standing for
+/// the expression of the file's path, e.g. `Data.read {path} (Delimited ',')`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deref)]
+pub struct ExpressionTemplate(pub ImString);
+
+/// The templates of the dropped files' nodes, by the file extension.
+pub type ExpressionTemplates = HashMap<Extension, ExpressionTemplate>;
+
+impl ExpressionTemplate {
+    /// Replace the placeholder with the given expression of the file's path.
+    pub fn instantiate(&self, path_expression: &str) -> String {
+        self.0.replace(PATH_PLACEHOLDER, path_expression)
+    }
+}
+
+/// The extension of the file with the given name. Returns [`None`] if the name has no extension.
+pub fn extension(file_name: &str) -> Option<Extension> {
+    let (stem, extension) = file_name.rsplit_once('.')?;
+    let is_valid = !stem.is_empty() && !extension.is_empty();
+    is_valid.then(|| extension.to_lowercase().into())
+}
+
+/// The template configured for the extension of the file with the given name. Templates without
+/// the [`PATH_PLACEHOLDER`] are ignored, as they would not read the dropped file.
+pub fn template_for(
+    templates: &ExpressionTemplates,
+    file_name: &str,
+) -> Option<ExpressionTemplate> {
+    let template = templates.get(&extension(file_name)?)?;
+    template.contains(PATH_PLACEHOLDER).then(|| template.clone())
+}
+
+/// The positions of the nodes of `count` files dropped at the given position, arranged in a column
+/// from top to bottom, separated by the `gap`.
+pub fn column_layout(position: Vector2, count: usize, gap: f32) -> Vec<Vector2> {
+    let step = node::HEIGHT + gap;
+    (0..count).map(|index| position - Vector2(0.0, step * index as f32)).collect()
+}
+
+
+
+// ===========
+// === Frp ===
+// ===========
+
+ensogl::define_endpoints_2! {
+    Input {
+        /// Set the files dragged over the canvas, or [`None`] if no files are dragged.
+        set_hovered (Option<DragOverEventData>),
+        /// Set the vertical gap between the nodes of the dropped files.
+        set_gap     (f32),
+    }
+    Output {
+        /// Whether the highlight is currently displayed.
+        visible (bool),
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+#[derive(Clone, Debug, display::Object)]
+struct Model {
+    display_object: display::object::Instance,
+    highlight:      Rectangle,
+}
+
+impl Model {
+    fn new() -> Self {
+        let display_object = display::object::Instance::new_named("FileDropHighlight");
+        let highlight = Rectangle::new();
+        highlight.set_corner_radius(node::CORNER_RADIUS + HIGHLIGHT_PADDING);
+        highlight.set_border_and_inset(HIGHLIGHT_BORDER).set_pointer_events(false);
+        Self { display_object, highlight }
+    }
+
+    /// Cover the column of the nodes which would be created if the files were dropped.
+    fn update(&self, hovered: Option<DragOverEventData>, gap: f32) {
+        match hovered.filter(|hovered| hovered.count > 0) {
+            Some(DragOverEventData { position, count }) => {
+                let positions = column_layout(position, count, gap);
+                let last = positions.last().copied().unwrap_or(position);
+                let top = position.y + node::HEIGHT / 2.0 + HIGHLIGHT_PADDING;
+                let bottom = last.y - node::HEIGHT / 2.0 - HIGHLIGHT_PADDING;
+                let left = position.x - HIGHLIGHT_PADDING;
+                self.highlight.set_xy(Vector2(left, bottom));
+                self.highlight.set_size(Vector2(HIGHLIGHT_WIDTH, top - bottom));
+                self.display_object.add_child(&self.highlight);
+            }
+            None => self.highlight.unset_parent(),
+        }
+    }
+
+    fn set_colors(&self, background: color::Rgba, border: color::Rgba) {
+        self.highlight.set_color(background).set_border_color(border);
+    }
+}
+
+
+
+// =====================
+// === DropHighlight ===
+// =====================
+
+/// The highlight of the place where the dragged files would be dropped.
+#[derive(Clone, CloneRef, Debug, Deref, display::Object)]
+#[allow(missing_docs)]
+pub struct DropHighlight {
+    #[display_object]
+    model:   Rc<Model>,
+    #[deref]
+    pub frp: Frp,
+}
+
+impl DropHighlight {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let model = Rc::new(Model::new());
+        let frp = Frp::new();
+        Self { model, frp }.init(app)
+    }
+
+    fn init(self, app: &Application) -> Self {
+        let network = self.frp.network();
+        let input = &self.frp.private.input;
+        let out = &self.frp.private.output;
+        let model = &self.model;
+        let style = StyleWatchFrp::new(&app.display.default_scene.style_sheet);
+        let background = style.get_color(theme::highlight_background);
+        let border = style.get_color(theme::highlight_border);
+
+        frp::extend! { network
+            init <- source_();
+            layout <- all(&input.set_hovered, &input.set_gap);
+            eval layout (((hovered, gap)) model.update(*hovered, *gap));
+            out.visible <+ input.set_hovered.map(|h| h.map_or(false, |h| h.count > 0)).on_change();
+
+            colors <- all3(&background, &border, &init);
+            eval colors (((background, border, _)) model.set_colors(*background, *border));
+        }
+
+        init.emit(());
+        input.set_hovered.emit(None);
+        self
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_extensions() {
+        assert_eq!(extension("Data.CSV"), Some("csv".into()));
+        assert_eq!(extension("archive.tar.gz"), Some("gz".into()));
+        assert_eq!(extension("README"), None);
+        assert_eq!(extension(".gitignore"), None);
+        assert_eq!(extension("trailing."), None);
+    }
+
+    #[test]
+    fn choosing_templates() {
+        let template = |code: &str| ExpressionTemplate(code.into());
+        let templates: ExpressionTemplates = [
+            ("csv".into(), template("Data.read {path} (Delimited ',')")),
+            ("json".into(), template("Data.read")),
+        ]
+        .into_iter()
+        .collect();
+        let chosen = template_for(&templates, "sales.CSV");
+        assert_eq!(chosen, Some(template("Data.read {path} (Delimited ',')")));
+        let path = "enso_project.data/\"sales.csv\"";
+        let expected = "Data.read enso_project.data/\"sales.csv\" (Delimited ',')";
+        assert_eq!(chosen.unwrap().instantiate(path), expected);
+        assert_eq!(template_for(&templates, "config.json"), None);
+        assert_eq!(template_for(&templates, "image.png"), None);
+    }
+
+    #[test]
+    fn arranging_dropped_files_in_column() {
+        let step = node::HEIGHT + 10.0;
+        let positions = column_layout(Vector2(5.0, 20.0), 3, 10.0);
+        let expected =
+            [Vector2(5.0, 20.0), Vector2(5.0, 20.0 - step), Vector2(5.0, 20.0 - step * 2.0)];
+        assert_eq!(positions, expected);
+    }
+}
@@ -0,0 +1,209 @@
+//! A fixed-spacing snap grid. Unlike the Magnet Alignment grid, which only aligns the dragged nodes
+//! to the other nodes, this grid quantizes the node positions to the multiples of its spacing,
+//! allowing for pixel-perfect layouts. The grid is optionally displayed as a faint dot pattern in
+//! the background of the scene.
+
+use crate::prelude::*;
+use ensogl::display::shape::*;
+
+use enso_frp as frp;
+use ensogl::application::Application;
+use ensogl::display;
+use ensogl::display::camera::Camera2d;
+use ensogl::display::scene::Layer;
+use ensogl_hardcoded_theme::graph_editor::grid as theme;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The dots are hidden if the distance between them on the screen is smaller than this, as such
+/// a dense pattern would only blur the background.
+const MIN_VISIBLE_SPACING_ON_SCREEN: f32 = 6.0;
+
+
+
+// ================
+// === Snapping ===
+// ================
+
+/// Return the grid point nearest to the given position.
+pub fn snap(position: Vector2, spacing: f32) -> Vector2 {
+    if spacing > 0.0 {
+        (position / spacing).map(f32::round) * spacing
+    } else {
+        position
+    }
+}
+
+
+
+// =============
+// === Shape ===
+// =============
+
+/// The infinite dot pattern, with a dot in the center of the shape.
+pub(crate) mod dots {
+    use super::*;
+
+    ensogl::shape! {
+        pointer_events = false;
+        alignment = center;
+        (style: Style, spacing: f32, radius: f32, color_rgba: Vector4<f32>) {
+            let tile = spacing.px();
+            let dots = Circle(radius.px()).repeat((tile.clone(), tile));
+            dots.fill(color_rgba).into()
+        }
+    }
+}
+
+
+
+// ===========
+// === Frp ===
+// ===========
+
+ensogl::define_endpoints_2! {
+    Input {
+        /// Set the distance between the grid points. `None` disables the grid.
+        set_spacing (Option<f32>),
+        /// Set whether the grid should be displayed. The grid is never displayed when disabled.
+        set_visible (bool),
+    }
+    Output {
+        /// Whether the dots are currently displayed.
+        visible (bool),
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+#[derive(Clone, Debug, display::Object)]
+struct Model {
+    display_object: display::object::Instance,
+    dots:           dots::View,
+    _layer:         Layer,
+}
+
+impl Model {
+    fn new(app: &Application) -> Self {
+        let scene = &app.display.default_scene;
+        let display_object = display::object::Instance::new_named("Grid");
+        let dots = dots::View::new();
+        // The dots are displayed above the customized canvas background, whose layers are created
+        // before the grid. See the `canvas_background` module docs.
+        let layer = scene.layers.below_main.create_sublayer("grid");
+        layer.add(&dots);
+        Self { display_object, dots, _layer: layer }
+    }
+
+    fn set_visibility(&self, visible: bool) {
+        if visible {
+            self.display_object.add_child(&self.dots);
+        } else {
+            self.dots.unset_parent();
+        }
+    }
+
+    /// Cover the visible part of the scene with the dots. The shape is centered at the grid point
+    /// nearest to the camera, so the dots are placed exactly at the grid points.
+    fn update_layout(&self, camera: &Camera2d, spacing: f32) {
+        let screen = camera.screen();
+        let visible_area = Vector2(screen.width, screen.height) / camera.zoom();
+        let margin = Vector2(spacing, spacing) * 2.0;
+        self.dots.set_size(visible_area + margin);
+        self.dots.set_xy(snap(camera.position().xy(), spacing));
+        self.dots.spacing.set(spacing);
+    }
+}
+
+
+
+// ============
+// === Grid ===
+// ============
+
+/// The snap grid component. See the module docs.
+#[derive(Clone, CloneRef, Debug, Deref, display::Object)]
+#[allow(missing_docs)]
+pub struct Grid {
+    #[display_object]
+    model:   Rc<Model>,
+    #[deref]
+    pub frp: Frp,
+}
+
+impl Grid {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let model = Rc::new(Model::new(app));
+        let frp = Frp::new();
+        Self { model, frp }.init(app)
+    }
+
+    fn init(self, app: &Application) -> Self {
+        let network = self.frp.network();
+        let input = &self.frp.private.input;
+        let out = &self.frp.private.output;
+        let model = &self.model;
+        let scene = &app.display.default_scene;
+        let camera = scene.camera();
+        let style = StyleWatchFrp::new(&scene.style_sheet);
+        let dot_color = style.get_color(theme::dot_color);
+        let dot_radius = style.get_number(theme::dot_radius);
+
+        frp::extend! { network
+            init <- source_();
+            let camera_changed = scene.frp.camera_changed.clone_ref();
+            spacing <- input.set_spacing.unwrap();
+            layout <- all(init, camera_changed, spacing);
+            eval layout ([model, camera] (&((), (), spacing)) {
+                model.update_layout(&camera, spacing)
+            });
+
+            dense <- layout.map(f!([camera] ((_, _, spacing)) {
+                spacing * camera.zoom() < MIN_VISIBLE_SPACING_ON_SCREEN
+            }));
+            enabled <- input.set_spacing.map(Option::is_some);
+            visible <- input.set_visible.all_with3(&enabled, &dense,
+                |visible, enabled, dense| *visible && *enabled && !*dense
+            );
+            out.visible <+ visible.on_change();
+            eval out.visible ((visible) model.set_visibility(*visible));
+
+            dot_color <- all(&dot_color, &init)._0();
+            eval dot_color ((color) model.dots.color_rgba.set((*color).into()));
+            dot_radius <- all(&dot_radius, &init)._0();
+            eval dot_radius ((radius) model.dots.radius.set(*radius));
+        }
+
+        init.emit(());
+        input.set_spacing.emit(None);
+        input.set_visible.emit(false);
+        self
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions_snap_to_nearest_grid_point() {
+        assert_eq!(snap(Vector2(14.0, -26.0), 20.0), Vector2(20.0, -20.0));
+        assert_eq!(snap(Vector2(9.0, 31.0), 20.0), Vector2(0.0, 40.0));
+        assert_eq!(snap(Vector2(9.0, 31.0), 0.0), Vector2(9.0, 31.0));
+    }
+}
@@ -0,0 +1,346 @@
+//! A card displayed next to the mouse cursor after hovering a node for a while, aggregating the
+//! information about the node which does not fit into it: the full type of its value, the details
+//! of its error, the duration of its last execution and the summary of its documentation.
+//!
+//! The card is placed relative to the cursor like the application tooltip, and it appears with the
+//! tooltip's delay. Unlike the tooltip, it displays several lines of text: the long type names and
+//! error messages are wrapped, and only the documentation is collapsed to its summary line.
+
+use crate::prelude::*;
+use ensogl::display::shape::*;
+
+use crate::component::node::error;
+use crate::Type;
+
+use enso_frp as frp;
+use ensogl::animation::hysteretic::HystereticAnimation;
+use ensogl::application::tooltip::Placement;
+use ensogl::application::Application;
+use ensogl::display;
+use ensogl_component::text;
+use ensogl_hardcoded_theme::application::tooltip as tooltip_theme;
+use ensogl_hardcoded_theme::graph_editor::hover_card as theme;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+const TEXT_SIZE: f32 = 12.0;
+const PADDING: f32 = 8.0;
+const CORNER_RADIUS: f32 = 6.0;
+/// The distance between the mouse cursor and the card.
+const CURSOR_OFFSET: f32 = 16.0;
+/// The lines longer than this many characters are wrapped.
+const MAX_LINE_LENGTH: usize = 80;
+
+
+
+// ========================
+// === HoverCardContent ===
+// ========================
+
+/// The information about the hovered node displayed in the card.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HoverCardContent {
+    /// The full type of the node's value.
+    pub node_type:          Option<Type>,
+    #[allow(missing_docs)]
+    pub error:              Option<error::Error>,
+    /// The duration of the node's last execution in milliseconds.
+    pub execution_duration: Option<f32>,
+    /// The summary of the documentation of the method called by the node.
+    pub doc_summary:        Option<ImString>,
+}
+
+impl HoverCardContent {
+    /// The lines of text displayed in the card, wrapped at [`MAX_LINE_LENGTH`] characters. Empty
+    /// if there is nothing to display.
+    pub fn lines(&self) -> Vec<String> {
+        let node_type = self.node_type.as_ref().map(|tp| format!("Type: {tp}"));
+        let error = self.error.as_ref().map(error_text);
+        let duration = self.execution_duration.map(|ms| format!("Executed in {}", duration(ms)));
+        let doc_summary = self.doc_summary.as_ref().and_then(|summary| summary_line(summary));
+        let texts = [node_type, error, duration, doc_summary].into_iter().flatten();
+        texts.flat_map(|text| wrap(&text)).collect()
+    }
+}
+
+fn error_text(error: &error::Error) -> String {
+    let kind = match *error.kind {
+        error::Kind::Panic => "Panic",
+        error::Kind::Dataflow => "Dataflow error",
+        error::Kind::Warning => "Warning",
+    };
+    let propagated = if *error.propagated { " (propagated)" } else { "" };
+    match error.message.as_ref() {
+        Some(message) => format!("{kind}{propagated}: {message}"),
+        None => format!("{kind}{propagated}"),
+    }
+}
+
+fn duration(ms: f32) -> String {
+    if ms < 1000.0 {
+        format!("{ms:.1} ms")
+    } else {
+        format!("{:.2} s", ms / 1000.0)
+    }
+}
+
+/// Split the text into lines of at most [`MAX_LINE_LENGTH`] characters, breaking them at the
+/// whitespace. The words longer than a line are split. The empty lines are skipped.
+fn wrap(text: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        let mut wrapped = String::new();
+        let mut length = 0;
+        let words = line.split_whitespace().flat_map(|word| {
+            let chars = word.chars().collect_vec();
+            chars.chunks(MAX_LINE_LENGTH).map(String::from_iter).collect_vec()
+        });
+        for word in words {
+            let word_length = word.chars().count();
+            if length > 0 && length + 1 + word_length > MAX_LINE_LENGTH {
+                lines.push(mem::take(&mut wrapped));
+                length = 0;
+            }
+            if length > 0 {
+                wrapped.push(' ');
+                length += 1;
+            }
+            wrapped.push_str(&word);
+            length += word_length;
+        }
+        if length > 0 {
+            lines.push(wrapped);
+        }
+    }
+    lines
+}
+
+/// The first non-empty line of the documentation, truncated to [`MAX_LINE_LENGTH`] characters.
+fn summary_line(text: &str) -> Option<String> {
+    let line = text.lines().map(str::trim).find(|line| !line.is_empty())?;
+    let truncated = line.chars().count() > MAX_LINE_LENGTH;
+    let mut line = line.chars().take(MAX_LINE_LENGTH).collect::<String>();
+    if truncated {
+        line.push('…');
+    }
+    Some(line)
+}
+
+/// The position of the card's top-left corner relative to the mouse cursor.
+fn offset_from_cursor(placement: Placement, size: Vector2) -> Vector2 {
+    match placement {
+        Placement::Top => Vector2(-size.x / 2.0, size.y + CURSOR_OFFSET),
+        Placement::Bottom => Vector2(-size.x / 2.0, -CURSOR_OFFSET),
+        Placement::Left => Vector2(-size.x - CURSOR_OFFSET, size.y / 2.0),
+        Placement::Right => Vector2(CURSOR_OFFSET, size.y / 2.0),
+    }
+}
+
+
+
+// ===========
+// === Frp ===
+// ===========
+
+ensogl::define_endpoints_2! {
+    Input {
+        /// Set the information displayed in the card. The card appears after a delay, and hides
+        /// once `None` is set or the content has no lines.
+        set_content   (Option<Rc<HoverCardContent>>),
+        set_placement (Placement),
+    }
+    Output {
+        visible (bool),
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+#[derive(Clone, Debug, display::Object)]
+struct Model {
+    display_object: display::object::Instance,
+    /// The visible part of the card, attached only when the card is displayed.
+    card:           display::object::Instance,
+    background:     Rectangle,
+    label:          text::Text,
+}
+
+impl Model {
+    fn new(app: &Application) -> Self {
+        let scene = &app.display.default_scene;
+        let display_object = display::object::Instance::new_named("HoverCard");
+        let card = display::object::Instance::new();
+        let background = Rectangle();
+        background.set_corner_radius(CORNER_RADIUS).set_pointer_events(false);
+        let label = app.new_view::<text::Text>();
+        label.set_property_default(text::Size(TEXT_SIZE));
+        label.remove_all_cursors();
+        label.set_xy(Vector2(PADDING, -PADDING));
+        card.add_child(&background);
+        card.add_child(&label);
+        scene.layers.tooltip.add(&background);
+        scene.layers.tooltip_text.add(&label);
+        Self { display_object, card, background, label }
+    }
+
+    /// Set the displayed lines. Returns `false` if there are none.
+    fn set_content(&self, content: &Option<Rc<HoverCardContent>>) -> bool {
+        let lines = content.as_ref().map(|content| content.lines()).unwrap_or_default();
+        if !lines.is_empty() {
+            self.label.set_content(lines.join("\n"));
+        }
+        !lines.is_empty()
+    }
+
+    fn set_text_size(&self, text_size: Vector2) -> Vector2 {
+        let size = text_size + Vector2(PADDING, PADDING) * 2.0;
+        self.background.set_size(size);
+        self.background.set_y(-size.y);
+        size
+    }
+
+    fn set_visibility(&self, visible: bool) {
+        if visible {
+            self.display_object.add_child(&self.card);
+        } else {
+            self.card.unset_parent();
+        }
+    }
+}
+
+
+
+// =================
+// === HoverCard ===
+// =================
+
+/// The hover card component. See the module docs. It should be placed in the scene's coordinates.
+#[derive(Clone, CloneRef, Debug, Deref, display::Object)]
+#[allow(missing_docs)]
+pub struct HoverCard {
+    #[display_object]
+    model:   Rc<Model>,
+    #[deref]
+    pub frp: Frp,
+}
+
+impl HoverCard {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let model = Rc::new(Model::new(app));
+        let frp = Frp::new();
+        Self { model, frp }.init(app)
+    }
+
+    fn init(self, app: &Application) -> Self {
+        let network = self.frp.network();
+        let input = &self.frp.private.input;
+        let out = &self.frp.private.output;
+        let model = &self.model;
+        let style = StyleWatchFrp::new(&app.display.default_scene.style_sheet);
+        let background_color = style.get_color(theme::background);
+        let text_color = style.get_color(theme::text);
+        let static_style = StyleWatch::new(&app.display.default_scene.style_sheet);
+        let show_delay = static_style.get_number_or(tooltip_theme::show_delay_duration_ms, 0.0);
+        let hide_delay = static_style.get_number_or(tooltip_theme::hide_delay_duration_ms, 0.0);
+        let transition = HystereticAnimation::new(network, show_delay, hide_delay);
+
+        frp::extend! { network
+            init <- source_();
+            has_content <- input.set_content.map(f!((content) model.set_content(content)));
+            transition.to_start <+ has_content.on_true();
+            transition.to_end <+ has_content.on_false();
+            visible <- transition.value.map(|opacity| *opacity > 0.0);
+            out.visible <+ visible.on_change();
+            eval out.visible ((visible) model.set_visibility(*visible));
+
+            text_size <- all_with(&model.label.width, &model.label.height, |w, h| Vector2(*w, *h));
+            size <- text_size.map(f!((size) model.set_text_size(*size)));
+            cursor_position <- app.cursor.frp.scene_position.map(|position| position.xy());
+            position <- all_with3(&cursor_position, &size, &input.set_placement,
+                |cursor, size, placement| cursor + offset_from_cursor(*placement, *size)
+            );
+            eval position ((position) model.card.set_xy(*position));
+
+            background_color <- all_with3(&background_color, &transition.value, &init,
+                |color, opacity, _| color.multiply_alpha(*opacity)
+            );
+            eval background_color ((color) model.background.set_color(*color));
+            text_color <- all_with3(&text_color, &transition.value, &init,
+                |color, opacity, _| color.multiply_alpha(*opacity)
+            );
+            eval text_color ((color) model.label.set_property_default(color));
+        }
+
+        init.emit(());
+        self.frp.set_placement(Placement::Bottom);
+        self.frp.set_content(None);
+        self
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hover_card_lines() {
+        assert!(HoverCardContent::default().lines().is_empty());
+        let error = error::Error {
+            kind:       Immutable(error::Kind::Panic),
+            message:    Rc::new(Some("Division by zero\nat Main.main".to_owned())),
+            propagated: Immutable(true),
+            stack:      default(),
+        };
+        let content = HoverCardContent {
+            node_type:          Some("Standard.Table.Data.Table.Table".into()),
+            error:              Some(error),
+            execution_duration: Some(1520.0),
+            doc_summary:        Some("\n  Sorts the rows of the table.\n\n Arguments:".into()),
+        };
+        let expected = [
+            "Type: Standard.Table.Data.Table.Table",
+            "Panic (propagated): Division by zero",
+            "at Main.main",
+            "Executed in 1.52 s",
+            "Sorts the rows of the table.",
+        ];
+        assert_eq!(content.lines(), expected);
+    }
+
+    #[test]
+    fn formatting_durations_and_long_lines() {
+        assert_eq!(duration(12.34), "12.3 ms");
+        assert_eq!(duration(2000.0), "2.00 s");
+        assert_eq!(summary_line("   "), None);
+        let long = "a".repeat(100);
+        assert_eq!(summary_line(&long), Some(format!("{}…", "a".repeat(MAX_LINE_LENGTH))));
+        assert_eq!(wrap(&long), ["a".repeat(MAX_LINE_LENGTH), "a".repeat(20)]);
+        let words = ["word"; 20].join(" ");
+        let first_line = ["word"; 16].join(" ");
+        let expected = [first_line, ["word"; 4].join(" "), "x".to_owned()];
+        assert_eq!(wrap(&format!("{words}\n\n  x")), expected);
+    }
+
+    #[test]
+    fn placing_the_card_next_to_the_cursor() {
+        let size = Vector2(100.0, 40.0);
+        assert_eq!(offset_from_cursor(Placement::Bottom, size), Vector2(-50.0, -CURSOR_OFFSET));
+        assert_eq!(offset_from_cursor(Placement::Top, size), Vector2(-50.0, 40.0 + CURSOR_OFFSET));
+        assert_eq!(offset_from_cursor(Placement::Right, size), Vector2(CURSOR_OFFSET, 20.0));
+    }
+}
@@ -0,0 +1,122 @@
+//! The path dragged by the mouse during the lasso selection. The path is drawn as a polyline,
+//! closed by a line connecting its last point with the first one.
+
+use crate::prelude::*;
+use ensogl::display::shape::*;
+
+use enso_frp as frp;
+use ensogl::application::Application;
+use ensogl::display;
+use ensogl_hardcoded_theme::graph_editor::lasso as theme;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+const LINE_WIDTH: f32 = 1.5;
+
+
+
+// ===========
+// === Frp ===
+// ===========
+
+ensogl::define_endpoints_2! {
+    Input {
+        /// Set the dragged path, in the scene coordinates. An empty path hides the lasso.
+        set_path (Rc<Vec<Vector2>>),
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+#[derive(Debug, display::Object)]
+struct Model {
+    display_object: display::object::Instance,
+    style:          StyleWatch,
+    segments:       RefCell<Vec<Rectangle>>,
+    /// The line connecting the last point of the path with the first one.
+    closing:        Rectangle,
+}
+
+impl Model {
+    fn new(app: &Application) -> Self {
+        let scene = &app.display.default_scene;
+        let display_object = display::object::Instance::new_named("Lasso");
+        let style = StyleWatch::new(&scene.style_sheet);
+        let closing = Rectangle();
+        closing.set_color(style.get_color(theme::closing_line)).set_pointer_events(false);
+        scene.layers.above_nodes.add(&display_object);
+        let segments = default();
+        Self { display_object, style, segments, closing }
+    }
+
+    /// Update the drawn path. The path is usually extended by a single point, so only the
+    /// segments of the new points are created.
+    fn set_path(&self, path: &[Vector2]) {
+        let mut segments = self.segments.borrow_mut();
+        let segment_count = path.len().saturating_sub(1);
+        let kept_segments = segment_count.min(segments.len());
+        for segment in segments.drain(kept_segments..) {
+            segment.unset_parent();
+        }
+        let line_color = self.style.get_color(theme::line);
+        for points in path[segments.len()..].windows(2) {
+            let segment = Rectangle();
+            segment.set_color(line_color).set_pointer_events(false);
+            set_line(&segment, points[0], points[1]);
+            self.display_object.add_child(&segment);
+            segments.push(segment);
+        }
+        match (path.first(), path.last()) {
+            (Some(first), Some(last)) if path.len() > 2 => {
+                set_line(&self.closing, *last, *first);
+                self.display_object.add_child(&self.closing);
+            }
+            _ => self.closing.unset_parent(),
+        }
+    }
+}
+
+fn set_line(line: &Rectangle, start: Vector2, end: Vector2) {
+    let direction = end - start;
+    line.set_size(Vector2(direction.norm(), LINE_WIDTH));
+    line.set_xy(start);
+    line.set_rotation_z(direction.y.atan2(direction.x));
+}
+
+
+
+// =============
+// === Lasso ===
+// =============
+
+/// The lasso path component. See the module docs. It should be placed in the scene's coordinates.
+#[derive(Clone, CloneRef, Debug, Deref, display::Object)]
+#[allow(missing_docs)]
+pub struct Lasso {
+    #[display_object]
+    model:   Rc<Model>,
+    #[deref]
+    pub frp: Frp,
+}
+
+impl Lasso {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let model = Rc::new(Model::new(app));
+        let frp = Frp::new();
+        let network = frp.network();
+        let input = &frp.private.input;
+        frp::extend! { network
+            eval input.set_path ([model] (path) model.set_path(path));
+        }
+        Self { model, frp }
+    }
+}
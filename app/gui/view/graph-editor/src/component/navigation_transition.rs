@@ -0,0 +1,188 @@
+//! The animated transition played when the user enters or exits a node.
+//!
+//! The transition consists of two halves. In the first one, the camera zooms into the entered node
+//! (or away from the exited graph) while a curtain in the scene background color fades in, hiding
+//! the old graph. In the second one, the camera jumps to the opposite zoom and settles back at its
+//! original position while the curtain fades out, revealing the new graph. Thanks to that, the new
+//! graph appears to emerge from the entered node, or to shrink back into the parent graph.
+
+use crate::prelude::*;
+use ensogl::display::shape::*;
+
+use enso_frp as frp;
+use ensogl::animation::easing::EndStatus;
+use ensogl::application::Application;
+use ensogl::data::color;
+use ensogl::display;
+use ensogl::display::camera::Camera2d;
+use ensogl::display::navigation::navigator::Navigator;
+use ensogl::Easing;
+use ensogl_hardcoded_theme as theme;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The duration of a single half of the transition.
+const HALF_DURATION_MS: f32 = 180.0;
+/// How many times the camera zooms in (when entering) or out (when exiting) during the transition.
+const ZOOM_FACTOR: f32 = 1.6;
+
+
+
+// ===========
+// === Frp ===
+// ===========
+
+ensogl::define_endpoints_2! {
+    Input {
+        /// Enable or disable the transition. When disabled, entering and exiting nodes switches the
+        /// displayed graph instantly.
+        set_enabled (bool),
+        /// Play the transition of entering the node centered at the given scene position.
+        enter       (Vector2),
+        /// Play the transition of exiting the current node.
+        exit        (),
+    }
+    Output {
+        /// Whether the transition is currently playing.
+        in_progress (bool),
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+#[derive(Debug, display::Object)]
+struct Model {
+    display_object: display::object::Instance,
+    curtain:        Rectangle,
+    camera:         Camera2d,
+    navigator:      Navigator,
+    /// The camera position from before the transition, to which the camera returns at its end.
+    origin:         Cell<Vector3>,
+    /// Whether the currently played transition is entering a node.
+    entering:       Cell<bool>,
+}
+
+impl Model {
+    fn new(app: &Application, navigator: &Navigator) -> Self {
+        let scene = &app.display.default_scene;
+        let display_object = display::object::Instance::new_named("NavigationTransition");
+        let curtain = Rectangle::new();
+        curtain.set_pointer_events(false);
+        scene.layers.panel_background_rect_level_0.add(&curtain);
+        let camera = scene.camera();
+        let navigator = navigator.clone_ref();
+        let origin = default();
+        let entering = default();
+        Self { display_object, curtain, camera, navigator, origin, entering }
+    }
+
+    fn set_screen_size(&self, size: Vector2) {
+        self.curtain.set_size(size);
+        self.curtain.set_xy(-size / 2.0);
+    }
+
+    fn set_curtain_opacity(&self, color: color::Rgba, opacity: f32) {
+        if opacity > 0.0 {
+            self.display_object.add_child(&self.curtain);
+        } else {
+            self.curtain.unset_parent();
+        }
+        self.curtain.set_color(color.multiply_alpha(opacity));
+    }
+
+    /// Start the first half of the transition. `focus` is the position of the entered node, or
+    /// `None` if the node is exited.
+    fn zoom_away(&self, focus: Option<Vector2>) {
+        let origin = self.camera.position();
+        self.origin.set(origin);
+        self.entering.set(focus.is_some());
+        let target = match focus {
+            Some(focus) => Vector3(focus.x, focus.y, origin.z / ZOOM_FACTOR),
+            None => Vector3(origin.x, origin.y, origin.z * ZOOM_FACTOR),
+        };
+        self.navigator.animate_to(target);
+    }
+
+    /// Start the second half of the transition, once the old graph is hidden by the curtain.
+    fn zoom_back(&self) {
+        let origin = self.origin.get();
+        let factor = if self.entering.get() { ZOOM_FACTOR } else { ZOOM_FACTOR.recip() };
+        self.navigator.jump_to(Vector3(origin.x, origin.y, origin.z * factor));
+        self.navigator.animate_to(origin);
+    }
+}
+
+
+
+// ============================
+// === NavigationTransition ===
+// ============================
+
+/// The node navigation transition. See the module docs.
+#[derive(Clone, CloneRef, Debug, Deref, display::Object)]
+#[allow(missing_docs)]
+pub struct NavigationTransition {
+    #[display_object]
+    model:   Rc<Model>,
+    #[deref]
+    pub frp: Frp,
+}
+
+impl NavigationTransition {
+    /// Constructor. The transition moves the camera using the Graph Editor's `navigator`, so it
+    /// does not conflict with the camera movements made by the user.
+    pub fn new(app: &Application, navigator: &Navigator) -> Self {
+        let model = Rc::new(Model::new(app, navigator));
+        let frp = Frp::new();
+        Self { model, frp }.init(app)
+    }
+
+    fn init(self, app: &Application) -> Self {
+        let network = self.frp.network();
+        let input = &self.frp.private.input;
+        let out = &self.frp.private.output;
+        let model = &self.model;
+        let scene = &app.display.default_scene;
+        let style = StyleWatchFrp::new(&scene.style_sheet);
+        let background = style.get_color(theme::application::background);
+        let curtain = Easing::new(network);
+        curtain.set_duration(HALF_DURATION_MS);
+
+        frp::extend! { network
+            init <- source_();
+            screen_size <- all_with(&scene.frp.shape, &init, |s, _| Vector2(s.width, s.height));
+            eval screen_size ((size) model.set_screen_size(*size));
+
+            enter <- input.enter.gate(&input.set_enabled);
+            exit <- input.exit.gate(&input.set_enabled);
+            eval enter ((focus) model.zoom_away(Some(*focus)));
+            eval_ exit (model.zoom_away(None));
+            started <- any_(enter, exit);
+            curtain.target <+ started.constant(1.0);
+
+            opacity <- curtain.value.sampler();
+            curtain_finished <- curtain.on_end.filter(|status| *status == EndStatus::Normal);
+            covered <- curtain_finished.map2(&opacity, |_, opacity| *opacity >= 1.0).on_true();
+            revealed <- curtain_finished.map2(&opacity, |_, opacity| *opacity <= 0.0).on_true();
+            eval_ covered (model.zoom_back());
+            curtain.target <+ covered.constant(0.0);
+            in_progress <- bool(&revealed, &started);
+            out.in_progress <+ in_progress.on_change();
+
+            curtain_color <- all(&background, &curtain.value);
+            eval curtain_color (((color, opacity)) model.set_curtain_opacity(*color, *opacity));
+        }
+
+        init.emit(());
+        input.set_enabled.emit(true);
+        self
+    }
+}
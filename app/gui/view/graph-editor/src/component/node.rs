@@ -4,7 +4,9 @@ use crate::prelude::*;
 use ensogl::display::shape::*;
 use ensogl::display::traits::*;
 
+use crate::component::type_coloring;
 use crate::component::visualization;
+use crate::selection;
 use crate::selection::BoundingBox;
 use crate::tooltip;
 use crate::view;
@@ -12,7 +14,6 @@ use crate::CallWidgetsConfig;
 use crate::GraphLayers;
 use crate::Type;
 
-use engine_protocol::language_server::ExecutionEnvironment;
 use enso_frp as frp;
 use enso_frp;
 use ensogl::animation::delayed::DelayedAnimation;
@@ -22,9 +23,11 @@ use ensogl::data::color;
 use ensogl::display;
 use ensogl::display::style::FromTheme;
 use ensogl::gui;
+use ensogl::gui::cursor;
 use ensogl::Animation;
 use ensogl_component::text;
 use ensogl_hardcoded_theme as theme;
+use ide_view_execution_environment_selector::ExecutionEnvironmentDef;
 
 
 // ==============
@@ -33,14 +36,28 @@ use ensogl_hardcoded_theme as theme;
 
 #[deny(missing_docs)]
 pub mod action_bar;
+#[deny(missing_docs)]
+pub mod conflict;
+#[deny(missing_docs)]
+pub mod description;
+#[deny(missing_docs)]
+pub mod edge_stubs;
 #[warn(missing_docs)]
 pub mod error;
+#[deny(missing_docs)]
+pub mod execution_count;
 pub mod expression;
 pub mod growth_animation;
 pub mod input;
 pub mod output;
 #[deny(missing_docs)]
+pub mod stale;
+#[deny(missing_docs)]
+pub mod value_preview;
+#[deny(missing_docs)]
 pub mod vcs;
+#[deny(missing_docs)]
+pub mod warnings;
 
 pub use error::Error;
 pub use expression::Expression;
@@ -66,6 +83,22 @@ pub const CORNER_RADIUS: f32 = HEIGHT / 2.0;
 
 /// Space between the documentation comment and the node.
 pub const COMMENT_MARGIN: f32 = 10.0;
+/// Diameter of the breakpoint indicator dot.
+pub const BREAKPOINT_INDICATOR_SIZE: f32 = 10.0;
+/// Distance between the left boundary of the node and the center of the breakpoint indicator.
+pub const BREAKPOINT_INDICATOR_OFFSET: f32 = 12.0;
+/// The minimum width the node can be resized to by the user.
+pub const MIN_WIDTH: f32 = 2.0 * HEIGHT;
+/// The width of the area along the right edge of the node which can be dragged to resize it.
+const RESIZE_HANDLE_WIDTH: f32 = 8.0;
+/// The maximum time between two presses of the resize handle for them to reset the node's width.
+const RESIZE_HANDLE_DOUBLE_PRESS_MS: f32 = 300.0;
+/// The width of the outline marking the nodes affected by the ongoing area selection.
+const SELECTION_PREVIEW_WIDTH: f32 = 2.0;
+/// The width of the outlines marking the node and the port connected by the hovered edge.
+const EDGE_EMPHASIS_WIDTH: f32 = 1.5;
+/// The height of the mark below the node the hovered edge originates from.
+const SOURCE_EMPHASIS_HEIGHT: f32 = 3.0;
 
 const ERROR_VISUALIZATION_SIZE: Vector2 = visualization::container::DEFAULT_SIZE;
 
@@ -76,6 +109,13 @@ const VISUALIZATION_OFFSET: Vector2 = Vector2(0.0, -VISUALIZATION_OFFSET_Y);
 const ENABLE_VIS_PREVIEW: bool = false;
 const VIS_PREVIEW_ONSET_MS: f32 = 4000.0;
 const ERROR_PREVIEW_ONSET_MS: f32 = 0000.0;
+/// Maximum horizontal displacement of the rejection indicator while it shakes.
+const REJECTION_SHAKE_AMPLITUDE: f32 = 3.0;
+/// The number of radians the rejection indicator shake phase advances while the flash fades out.
+/// The flash strength decays from 1.0 to 0.0, so this controls how many times the indicator swings.
+const REJECTION_SHAKE_FREQUENCY: f32 = 4.0 * std::f32::consts::TAU;
+/// The duration of a single pulse of the outline of the node being computed.
+const COMPUTING_PULSE_PERIOD_MS: f32 = 1200.0;
 /// A type of unresolved methods. We filter them out, because we don't want to treat them as types
 /// for ports and edges coloring (due to bad UX otherwise).
 const UNRESOLVED_SYMBOL_TYPE: &str = "Builtins.Main.Unresolved_Symbol";
@@ -93,6 +133,10 @@ pub struct Background {
     #[display_object]
     shape:               Rectangle,
     selection_shape:     Rectangle,
+    /// The outline marking the node affected by the ongoing area selection.
+    preview_shape:       Rectangle,
+    /// The outline marking the node connected by the hovered edge.
+    emphasis_shape:      Rectangle,
     selection_animation: Animation<f32>,
     color_animation:     color::Animation,
     node_is_hovered:     frp::Any<bool>,
@@ -115,6 +159,12 @@ impl Background {
         let style = BackgroundStyle::from_theme(&network, style);
         let shape = Rectangle();
         let selection_shape = Rectangle();
+        let preview_shape = Rectangle();
+        preview_shape.set_color(color::Rgba::transparent());
+        preview_shape.set_inner_border(SELECTION_PREVIEW_WIDTH, 0.0).set_pointer_events(false);
+        let emphasis_shape = Rectangle();
+        emphasis_shape.set_color(color::Rgba::transparent());
+        emphasis_shape.set_inner_border(EDGE_EMPHASIS_WIDTH, 0.0).set_pointer_events(false);
         let color_animation = color::Animation::new(&network);
         let selection_animation = Animation::new(&network);
         let hover_animation = Animation::new(&network);
@@ -151,13 +201,21 @@ impl Background {
                 |(size, _), style| (*size, style.selection_size)
             ).on_change();
 
-            eval size_and_selection([selection_shape] (size_and_selection) {
+            eval size_and_selection([selection_shape, preview_shape, emphasis_shape]
+                (size_and_selection) {
                 let (size, total_selection_size) = *size_and_selection;
                 let total_selection_vec = Vector2::from_element(total_selection_size);
                 // selection shape is positioned relative to the background shape.
                 selection_shape.set_xy(-total_selection_vec);
                 selection_shape.set_size(size + total_selection_vec * 2.0);
                 selection_shape.set_corner_radius(CORNER_RADIUS + total_selection_size);
+                let preview_vec = total_selection_vec / 2.0;
+                preview_shape.set_xy(-preview_vec);
+                preview_shape.set_size(size + preview_vec * 2.0);
+                preview_shape.set_corner_radius(CORNER_RADIUS + total_selection_size / 2.0);
+                emphasis_shape.set_xy(-total_selection_vec);
+                emphasis_shape.set_size(size + total_selection_vec * 2.0);
+                emphasis_shape.set_corner_radius(CORNER_RADIUS + total_selection_size);
             });
 
             eval color_animation.value((color) shape.set_color(color.into()););
@@ -176,6 +234,8 @@ impl Background {
             _network: network,
             shape,
             selection_shape,
+            preview_shape,
+            emphasis_shape,
             selection_animation,
             node_is_hovered,
             color_animation,
@@ -191,6 +251,30 @@ impl Background {
         self.color_animation.target.emit(color);
     }
 
+    /// Display the outline marking the node affected by the ongoing area selection. `None` hides
+    /// the outline.
+    fn set_selection_preview(&self, color: Option<color::Rgba>) {
+        match color {
+            Some(color) => {
+                self.preview_shape.set_border_color(color);
+                self.shape.add_child(&self.preview_shape);
+            }
+            None => self.preview_shape.unset_parent(),
+        }
+    }
+
+    /// Display the outline marking the node connected by the hovered edge. `None` hides the
+    /// outline.
+    fn set_edge_emphasis(&self, color: Option<color::Rgba>) {
+        match color {
+            Some(color) => {
+                self.emphasis_shape.set_border_color(color);
+                self.shape.add_child(&self.emphasis_shape);
+            }
+            None => self.emphasis_shape.unset_parent(),
+        }
+    }
+
     fn set_size_and_center_xy(&self, size: Vector2<f32>, center: Vector2<f32>) {
         self.size_and_center.emit((size, center));
     }
@@ -206,12 +290,36 @@ ensogl::define_endpoints_2! {
     Input {
         select                (),
         deselect              (),
+        /// Mark the node as affected by the ongoing area selection, displaying an outline. `None`
+        /// removes the mark.
+        set_selection_preview (Option<selection::Change>),
+        /// Mark the node as an end of the hovered edge, outlining the node and the port the edge
+        /// is attached to. `None` removes the marks.
+        set_edge_emphasis     (Option<EdgeEmphasis>),
+        /// Replace the node's edges with the stubs displaying the numbers of the hidden edges.
+        /// `None` removes the stubs.
+        set_hidden_edges      (Option<edge_stubs::HiddenEdges>),
         enable_visualization  (),
         enable_fullscreen_visualization  (),
         disable_visualization (),
         set_visualization     (Option<visualization::Definition>),
         set_disabled          (bool),
         set_pending           (bool),
+        /// Dim the node, indicating it is not related to the currently focused part of the graph.
+        set_dimmed            (bool),
+        /// Briefly flash and shake the node outline, signalling that an action involving this node
+        /// was rejected.
+        flash_rejection       (),
+        /// Mark the node as using a value poisoned by an error in one of its upstream nodes. Such
+        /// nodes are drawn desaturated and dimmed.
+        set_poisoned          (bool),
+        /// Show or hide the breakpoint indicator next to the node.
+        set_breakpoint        (bool),
+        /// Mark the node as being computed by the backend. Such nodes have a pulsing outline.
+        set_computing         (bool),
+        /// Mark the node's value as stale, i.e. computed before the node or one of its upstream
+        /// nodes was edited. Such nodes have a dashed output port and a desaturated value preview.
+        set_stale             (bool),
         set_connections       (HashMap<span_tree::PortId, color::Lcha>),
         set_expression        (Expression),
         edit_expression       (text::Range<text::Byte>, ImString),
@@ -221,6 +329,16 @@ ensogl::define_endpoints_2! {
         /// enabled/disabled; `None` for no context switch expression.
         set_context_switch    (Option<bool>),
         set_comment           (ImString),
+        /// Set the user-assigned color tag of the node. The tag tints the node background. `None`
+        /// removes the tag.
+        set_color_tag         (Option<color::Rgba>),
+        /// Set the palette the node and port colors are adjusted to.
+        set_color_palette     (type_coloring::Palette),
+        /// Collapse the node, hiding its comment and value preview.
+        set_collapsed         (bool),
+        /// Set the name of the user-defined group the node belongs to. `None` removes the node
+        /// from its group.
+        set_group             (Option<ImString>),
         set_error             (Option<Error>),
         /// Set the expression USAGE type. This is not the definition type, which can be set with
         /// `set_expression` instead. In case the usage type is set to None, ports still may be
@@ -238,13 +356,45 @@ ensogl::define_endpoints_2! {
         set_view_mode                     (view::Mode),
         set_profiling_min_global_duration (f32),
         set_profiling_max_global_duration (f32),
+        /// Set the number of times the node was evaluated in the current run. Displayed only in
+        /// the profiling view mode.
+        set_execution_count               (Option<u64>),
+        /// Mark the node's expression as concurrently edited by another user. `None` removes the
+        /// mark.
+        set_edit_conflict                 (Option<conflict::ConflictInfo>),
+        /// Set the preview of the node's most recent value, displayed next to the output port.
+        set_value_preview                 (Option<ImString>),
+        /// Set the warnings attached to the node's value. Unlike errors, the warnings do not poison
+        /// the nodes using the value.
+        set_warnings                      (Rc<Vec<warnings::Warning>>),
+        /// Set whether the node's description is being generated, displaying the loading state.
+        set_description_pending           (bool),
+        /// Set the generated description of the node, which the user may accept as the node's
+        /// comment or discard. `None` hides the description.
+        set_description_suggestion        (Option<ImString>),
+        /// Set the duration of the node's last execution in milliseconds, displayed in the node's
+        /// hover card.
+        set_execution_duration            (Option<f32>),
+        /// Set the one-line summary of the documentation of the method called by the node,
+        /// displayed in the node's hover card.
+        set_doc_summary                   (Option<ImString>),
+        /// Set whether the value preview is displayed.
+        show_value_preview                (bool),
         /// Indicate whether on hover the quick action icons should appear.
         show_quick_action_bar_on_hover    (bool),
-        set_execution_environment         (ExecutionEnvironment),
+        set_execution_environment         (ExecutionEnvironmentDef),
 
         /// Set read-only mode for input ports.
         set_read_only                     (bool),
 
+        /// Set the maximum length of a displayed expression line, in bytes of code. Longer
+        /// expressions are wrapped into multiple lines, growing the node vertically. `None`
+        /// disables wrapping.
+        set_expression_wrap               (Option<usize>),
+        /// Set the width of the node, overriding the width fitting its expression. The width is
+        /// never smaller than [`MIN_WIDTH`]. `None` restores the automatic width.
+        set_width_override                (Option<f32>),
+
         /// Set the mode in which the cursor will indicate that editing of the node is possible.
         set_edit_ready_mode (bool),
     }
@@ -266,6 +416,44 @@ ensogl::define_endpoints_2! {
         context_switch           (bool),
         skip                     (bool),
         freeze                   (bool),
+        /// Whether the node is marked as being computed, see `set_computing`.
+        computing                (bool),
+        /// Whether the node's value is marked as stale, see `set_stale`.
+        stale                    (bool),
+        /// The color tag picked by the user in the action bar.
+        color_tag                (Option<color::Rgba>),
+        /// The current color tag of the node, either set with `set_color_tag` or picked by the
+        /// user.
+        current_color_tag        (Option<color::Rgba>),
+        /// Whether the node is collapsed, see `set_collapsed`.
+        collapsed                (bool),
+        /// The group the node belongs to, see `set_group`.
+        group                    (Option<ImString>),
+        /// The concurrent edit of the node's expression, set with `set_edit_conflict`.
+        edit_conflict            (Option<conflict::ConflictInfo>),
+        /// The user clicked the edit conflict badge to accept the other user's version.
+        accept_remote_requested  (),
+        /// The name of the custom action triggered by the user in the action bar.
+        custom_action            (ImString),
+        /// The user requested generating the node's description using the action bar.
+        description_requested    (),
+        /// The type of the node's whole expression.
+        output_type              (Option<Type>),
+        /// The duration of the node's last execution, set with `set_execution_duration`.
+        execution_duration       (Option<f32>),
+        /// The documentation summary, set with `set_doc_summary`.
+        doc_summary              (Option<ImString>),
+        /// The current width override, either set with `set_width_override` or by the user.
+        width_override           (Option<f32>),
+        /// The user finished resizing the node by dragging its right edge, or reset its width by
+        /// double-pressing the edge. `None` means the width fits the expression again.
+        width_overridden         (Option<f32>),
+        /// Whether the mouse is over the stubs of the hidden edges. See `set_hidden_edges`.
+        edge_stubs_hovered       (bool),
+        /// The user clicked the frame of the error's stack trace pointing to the expression with
+        /// the given id.
+        error_frame_clicked      (ast::Id),
+        warnings                 (Rc<Vec<warnings::Warning>>),
         hover                    (bool),
         error                    (Option<Error>),
         /// The [`display::object::Model::position`] of the Node. Emitted when the Display Object
@@ -373,20 +561,34 @@ impl Deref for Node {
 #[derive(Clone, Debug, display::Object)]
 #[allow(missing_docs)]
 pub struct NodeModel {
-    pub layers:              GraphLayers,
-    pub display_object:      display::object::Instance,
-    pub background:          Background,
-    pub error_indicator:     Rectangle,
-    pub input:               input::Area,
-    pub output:              output::Area,
-    pub visualization:       visualization::Container,
-    pub error_visualization: error::Container,
-    pub action_bar_wrapper:  display::object::Instance,
-    pub action_bar:          action_bar::ActionBar,
-    pub vcs_indicator:       vcs::StatusIndicator,
-    pub style:               StyleWatchFrp,
-    pub comment:             text::Text,
-    pub interaction_state:   Cell<InteractionState>,
+    pub layers:               GraphLayers,
+    pub display_object:       display::object::Instance,
+    pub background:           Background,
+    pub error_indicator:      Rectangle,
+    pub rejection_indicator:  Rectangle,
+    pub computing_indicator:  Rectangle,
+    pub breakpoint_indicator: Rectangle,
+    pub resize_handle:        Rectangle,
+    /// The outline marking the port the hovered edge is attached to.
+    pub port_emphasis:        Rectangle,
+    pub input:                input::Area,
+    pub output:               output::Area,
+    pub visualization:        visualization::Container,
+    pub error_visualization:  error::Container,
+    pub action_bar_wrapper:   display::object::Instance,
+    pub action_bar:           action_bar::ActionBar,
+    pub vcs_indicator:        vcs::StatusIndicator,
+    pub execution_count:      execution_count::ExecutionCountBadge,
+    pub edit_conflict:        conflict::EditConflictBadge,
+    pub value_preview:        value_preview::ValuePreviewChip,
+    pub description:          description::DescriptionSuggestion,
+    pub edge_stubs:           edge_stubs::EdgeStubs,
+    pub warnings:             warnings::WarningsBadge,
+    pub stale_indicator:      stale::StaleIndicator,
+    pub style:                StyleWatchFrp,
+    pub comment:              text::Text,
+    pub interaction_state:    Cell<InteractionState>,
+    pub width_override:       Cell<Option<f32>>,
 }
 
 impl NodeModel {
@@ -401,18 +603,58 @@ impl NodeModel {
             .set_pointer_events(false)
             .set_color(color::Rgba::transparent())
             .set_border_and_inset(ERROR_BORDER_WIDTH);
+        let rejection_indicator = Rectangle();
+        rejection_indicator
+            .set_corner_radius_max()
+            .set_pointer_events(false)
+            .set_color(color::Rgba::transparent())
+            .set_border_and_inset(ERROR_BORDER_WIDTH);
+        let computing_indicator = Rectangle();
+        computing_indicator
+            .set_corner_radius_max()
+            .set_pointer_events(false)
+            .set_color(color::Rgba::transparent())
+            .set_border_and_inset(ERROR_BORDER_WIDTH);
+        let breakpoint_indicator = Rectangle();
+        breakpoint_indicator
+            .set_size((BREAKPOINT_INDICATOR_SIZE, BREAKPOINT_INDICATOR_SIZE))
+            .set_corner_radius_max()
+            .set_pointer_events(false);
+        let resize_handle = Rectangle();
+        resize_handle.set_color(color::Rgba::transparent()).set_pointer_events(true);
+        let port_emphasis = Rectangle();
+        port_emphasis
+            .set_corner_radius_max()
+            .set_pointer_events(false)
+            .set_color(color::Rgba::transparent())
+            .set_inner_border(EDGE_EMPHASIS_WIDTH, 0.0);
         let background = Background::new(&style);
         let vcs_indicator = vcs::StatusIndicator::new(app);
+        let execution_count = execution_count::ExecutionCountBadge::new(app);
+        let edit_conflict = conflict::EditConflictBadge::new(app);
+        let value_preview = value_preview::ValuePreviewChip::new(app);
+        let description = description::DescriptionSuggestion::new(app);
+        let edge_stubs = edge_stubs::EdgeStubs::new(app);
+        let warnings = warnings::WarningsBadge::new(app);
+        let stale_indicator = stale::StaleIndicator::new(app);
         let display_object = display::object::Instance::new_named("Node");
 
         display_object.add_child(&background);
         display_object.add_child(&vcs_indicator);
+        display_object.add_child(&execution_count);
+        display_object.add_child(&edit_conflict);
+        display_object.add_child(&value_preview);
+        display_object.add_child(&description);
+        display_object.add_child(&edge_stubs);
+        display_object.add_child(&warnings);
+        display_object.add_child(&stale_indicator);
 
         let input = input::Area::new(app, layers);
         let visualization = visualization::Container::new(app, registry);
 
         display_object.add_child(&visualization);
         display_object.add_child(&input);
+        display_object.add_child(&resize_handle);
 
         let error_visualization = error::Container::new(app);
         error_visualization.frp.set_size.emit(ERROR_VISUALIZATION_SIZE);
@@ -431,12 +673,18 @@ impl NodeModel {
         display_object.add_child(&comment);
 
         let interaction_state = default();
+        let width_override = default();
 
         Self {
             layers: layers.clone(),
             display_object,
             background,
             error_indicator,
+            rejection_indicator,
+            computing_indicator,
+            breakpoint_indicator,
+            resize_handle,
+            port_emphasis,
             input,
             output,
             visualization,
@@ -444,9 +692,17 @@ impl NodeModel {
             action_bar_wrapper,
             action_bar,
             vcs_indicator,
+            execution_count,
+            edit_conflict,
+            value_preview,
+            description,
+            edge_stubs,
+            warnings,
+            stale_indicator,
             style,
             comment,
             interaction_state,
+            width_override,
         }
         .init()
     }
@@ -474,20 +730,30 @@ impl NodeModel {
         main.body.add(&self.display_object);
         below.backdrop.add(&self.background.selection_shape);
         below.backdrop.add(&self.error_indicator);
+        below.backdrop.add(&self.rejection_indicator);
+        below.backdrop.add(&self.computing_indicator);
         main.action_bar.add(&self.action_bar_wrapper);
         main.below_body.add(&self.output);
         main.output_hover.add(self.output.hover_root());
         self.action_bar.set_layers(main);
     }
 
-    #[allow(missing_docs)] // FIXME[everyone] All pub functions should have docs.
+    /// The width of the node. Fits the expression, unless overridden by the user.
     pub fn width(&self) -> f32 {
-        self.input.width.value()
+        node_width(self.input.width.value(), self.width_override.get())
     }
 
-    #[allow(missing_docs)] // FIXME[everyone] All pub functions should have docs.
+    /// The height of the node. Grows with every additional line of a wrapped expression.
     pub fn height(&self) -> f32 {
-        HEIGHT
+        let extra_lines = self.input.line_count.value().saturating_sub(1) as f32;
+        HEIGHT + extra_lines * input::area::NODE_HEIGHT
+    }
+
+    /// An offset from the node's origin to the center of its body.
+    pub fn offset_to_center(&self) -> Vector2 {
+        let x = x_offset_to_node_center(self.width());
+        let y = y_offset_to_node_center(self.height());
+        Vector2(x, y)
     }
 
     #[profile(Debug)]
@@ -512,26 +778,75 @@ impl NodeModel {
         let error_size = size + Vector2(error_padding, error_padding) * 2.0;
         self.output.frp.set_size(size);
         self.error_indicator.set_size(error_size);
+        self.rejection_indicator.set_size(error_size);
+        self.computing_indicator.set_size(error_size);
         self.vcs_indicator.frp.set_size(padded_size);
         let x_offset_to_node_center = x_offset_to_node_center(width);
-        let background_origin = Vector2(x_offset_to_node_center, 0.0);
+        let y_offset_to_node_center = y_offset_to_node_center(height);
+        let background_origin = Vector2(x_offset_to_node_center, y_offset_to_node_center);
         self.background.set_size_and_center_xy(size, background_origin);
-        self.error_indicator.set_xy((-error_padding, -height / 2.0 - error_padding));
-        self.vcs_indicator.set_x(x_offset_to_node_center);
-
-        self.visualization.set_xy(VISUALIZATION_OFFSET);
+        let error_indicator_y = y_offset_to_node_center - height / 2.0 - error_padding;
+        self.error_indicator.set_xy((-error_padding, error_indicator_y));
+        self.rejection_indicator.set_xy((-error_padding, error_indicator_y));
+        self.computing_indicator.set_xy((-error_padding, error_indicator_y));
+        let breakpoint_x = -BREAKPOINT_INDICATOR_OFFSET - BREAKPOINT_INDICATOR_SIZE / 2.0;
+        let breakpoint_y = y_offset_to_node_center - BREAKPOINT_INDICATOR_SIZE / 2.0;
+        self.breakpoint_indicator.set_xy((breakpoint_x, breakpoint_y));
+        self.resize_handle.set_size((RESIZE_HANDLE_WIDTH, height));
+        let resize_handle_y = y_offset_to_node_center - height / 2.0;
+        self.resize_handle.set_xy((width - RESIZE_HANDLE_WIDTH / 2.0, resize_handle_y));
+        self.vcs_indicator.set_xy((x_offset_to_node_center, y_offset_to_node_center));
+        self.output.set_y(y_offset_to_node_center);
+        self.execution_count.set_node_width(width);
+        self.edge_stubs.set_node_size(size);
+        self.warnings.set_node_size(size);
+        self.stale_indicator.set_node_size(size);
+        self.value_preview.set_anchor(Vector2(width, y_offset_to_node_center - height / 2.0));
+
+        let visualization_offset = visualization_offset(height);
+        self.visualization.set_xy(visualization_offset);
         // Error visualization has origin in the center, while regular visualization has it at the
         // top left corner.
         let error_vis_offset_y = -ERROR_VISUALIZATION_SIZE.y / 2.0;
         let error_vis_offset_x = ERROR_VISUALIZATION_SIZE.x / 2.0;
         let error_vis_offset = Vector2(error_vis_offset_x, error_vis_offset_y);
-        let error_vis_pos = VISUALIZATION_OFFSET + error_vis_offset;
+        let error_vis_pos = visualization_offset + error_vis_offset;
         self.error_visualization.set_xy(error_vis_pos);
         self.visualization.frp.set_width(width);
 
         size
     }
 
+    /// Outline the node and the port connected by the hovered edge, or remove the outlines.
+    fn set_edge_emphasis(
+        &self,
+        emphasis: Option<EdgeEmphasis>,
+        (outline_color, port_color): (color::Rgba, color::Rgba),
+    ) {
+        self.background.set_edge_emphasis(emphasis.map(|_| outline_color));
+        let port_bounds = emphasis.map(|emphasis| match emphasis {
+            EdgeEmphasis::Source => {
+                let width = self.width() - CORNER_RADIUS * 2.0;
+                let y = y_offset_to_node_center(self.height()) - self.height() / 2.0;
+                let size = Vector2(width, SOURCE_EMPHASIS_HEIGHT);
+                (Vector2(CORNER_RADIUS, y - SOURCE_EMPHASIS_HEIGHT), size)
+            }
+            EdgeEmphasis::Target(port) => {
+                let size = self.input.port_size(port);
+                (self.input.port_offset(port) - size / 2.0, size)
+            }
+        });
+        match port_bounds {
+            Some((position, size)) => {
+                self.port_emphasis.set_xy(position);
+                self.port_emphasis.set_size(size);
+                self.port_emphasis.set_border_color(port_color);
+                self.display_object.add_child(&self.port_emphasis);
+            }
+            None => self.port_emphasis.unset_parent(),
+        }
+    }
+
     #[profile(Debug)]
     fn set_error(&self, error: Option<&Error>) {
         if let Some(error) = error {
@@ -557,6 +872,42 @@ impl NodeModel {
         }
     }
 
+    fn set_breakpoint(&self, breakpoint: bool, color: color::Lcha) {
+        if breakpoint {
+            self.breakpoint_indicator.set_color(color.into());
+            self.display_object.add_child(&self.breakpoint_indicator);
+        } else {
+            self.breakpoint_indicator.unset_parent();
+        }
+    }
+
+    /// Update the rejection indicator for the given flash `strength`, fading from 1.0 (just
+    /// rejected) to 0.0 (hidden).
+    fn set_rejection_flash(&self, strength: f32, color: color::Lcha) {
+        if strength < f32::EPSILON {
+            self.rejection_indicator.unset_parent();
+        } else {
+            let shake = (strength * REJECTION_SHAKE_FREQUENCY).sin() * REJECTION_SHAKE_AMPLITUDE;
+            let error_padding = ERROR_BORDER_WIDTH + ERROR_BORDER_DISTANCE;
+            let color = color.multiply_alpha(strength);
+            self.rejection_indicator.set_x(-error_padding + shake * strength);
+            self.rejection_indicator.set_border_color(color.into());
+            self.display_object.add_child(&self.rejection_indicator);
+        }
+    }
+
+    /// Update the computing indicator for the given pulse `phase`, going from 0.0 to 1.0 during
+    /// each pulse. `None` hides the indicator.
+    fn set_computing_pulse(&self, phase: Option<f32>, color: color::Lcha) {
+        if let Some(phase) = phase {
+            let strength = (1.0 - (phase * std::f32::consts::TAU).cos()) / 2.0;
+            self.computing_indicator.set_border_color(color.multiply_alpha(strength).into());
+            self.display_object.add_child(&self.computing_indicator);
+        } else {
+            self.computing_indicator.unset_parent();
+        }
+    }
+
     #[profile(Debug)]
     fn update_colors(&self, color: color::Lcha, port_color: color::Lcha) {
         self.background.set_color(color);
@@ -566,6 +917,18 @@ impl NodeModel {
     fn set_selected(&self, selected: bool) {
         self.background.set_selected(selected);
     }
+
+    fn set_collapsed(&self, collapsed: bool) {
+        if collapsed {
+            self.comment.unset_parent();
+        } else {
+            self.display_object.add_child(&self.comment);
+        }
+    }
+
+    fn screen_to_object_space(&self, screen_pos: Vector2) -> Vector2 {
+        scene().screen_to_object_space(&self.display_object, screen_pos)
+    }
 }
 
 impl Node {
@@ -583,6 +946,7 @@ impl Node {
         //      in https://github.com/enso-org/ide/issues/1031
         // let comment_color    = color::Animation::new(network);
         let error_color_anim = color::Animation::new(network);
+        let rejection_flash = Animation::<f32>::new(network);
         let style = StyleWatch::new(&app.display.default_scene.style_sheet);
         let style_frp = &model.style;
         let action_bar = &model.action_bar.frp;
@@ -632,6 +996,27 @@ impl Node {
 
             selected <- bool(&input.deselect, &input.select);
             eval selected ((selected) model.set_selected(*selected));
+
+            let select_color = theme::graph_editor::node::selection::preview_select;
+            let select_color = style_frp.get_color(select_color);
+            let deselect_color = theme::graph_editor::node::selection::preview_deselect;
+            let deselect_color = style_frp.get_color(deselect_color);
+            let preview = &input.set_selection_preview;
+            selection_preview <- all_with3(preview, &select_color, &deselect_color,
+                |change, select, deselect| change.map(|change| match change {
+                    selection::Change::Select => *select,
+                    selection::Change::Deselect => *deselect,
+                })
+            );
+            eval selection_preview ((color) model.background.set_selection_preview(*color));
+
+            let outline_color = theme::graph_editor::node::edge_emphasis::outline;
+            let outline_color = style_frp.get_color(outline_color);
+            let port_color = theme::graph_editor::node::edge_emphasis::port;
+            let port_color = style_frp.get_color(port_color);
+            edge_emphasis_colors <- all(&outline_color, &port_color);
+            edge_emphasis <- all(&input.set_edge_emphasis, &edge_emphasis_colors);
+            eval edge_emphasis (((emphasis, colors)) model.set_edge_emphasis(*emphasis, *colors));
         }
 
         frp::extend! { network
@@ -681,11 +1066,77 @@ impl Node {
             out.comment <+ model.comment.content.map(|text| text.to_im_string());
         }
 
+        frp::extend! { network
+            // === Description ===
+
+            out.description_requested <+ action_bar.action_describe;
+            action_bar.set_action_describe_pending <+ input.set_description_pending;
+            model.description.set_pending <+ input.set_description_pending;
+            model.description.set_suggestion <+ input.set_description_suggestion;
+            model.comment.set_content <+ model.description.accepted;
+            description_accepted <- model.description.accepted.constant(());
+            description_closed <- any(&description_accepted, &model.description.discarded);
+            model.description.set_suggestion <+ description_closed.constant(None);
+        }
+
         frp::extend! { network
             // === Size ===
 
+            model.input.set_expression_wrap <+ input.set_expression_wrap;
             input_width <- all(&model.input.frp.width, &init)._0();
-            new_size <- input_width.map(f!((w) model.set_width(*w)));
+            input_line_count <- all(&model.input.frp.line_count, &init)._0();
+            width_override <- any(...);
+            width_override <+ input.set_width_override.map(|width| width.map(|w| w.max(MIN_WIDTH)));
+            out.width_override <+ width_override;
+            eval width_override ((width) model.width_override.set(*width));
+            current_width_override <- all(&width_override, &init)._0();
+            node_width <- all_with(&input_width, &current_width_override,
+                |width, width_override| node_width(*width, *width_override)
+            );
+            size_changed <- all(&node_width, &input_line_count)._0();
+            new_size <- size_changed.map(f!((w) model.set_width(*w)));
+        }
+
+        frp::extend! { network
+            // === Manual Resize ===
+
+            let scene = &app.display.default_scene;
+            let handle_down = model.resize_handle.on_event::<mouse::Down>();
+            let handle_enter = model.resize_handle.on_event::<mouse::Enter>();
+            let handle_leave = model.resize_handle.on_event::<mouse::Leave>();
+            let on_up = scene.on_event::<mouse::Up>();
+            let on_move = scene.on_event::<mouse::Move>();
+            handle_press <- handle_down.filter(mouse::event::is_primary);
+            handle_press <- handle_press.gate_not(&input.set_read_only);
+            is_resizing <- bool(&on_up, &handle_press);
+            was_resizing <- is_resizing.previous();
+            on_move_resizing <- on_move.gate(&is_resizing);
+            screen_pos_on_press <- handle_press.map(|event| event.client_centered());
+            screen_pos_on_move <- on_move_resizing.map(|event| event.client_centered());
+            pos_on_press <- screen_pos_on_press.map(f!((p) model.screen_to_object_space(*p)));
+            pos_on_move <- screen_pos_on_move.map(f!((p) model.screen_to_object_space(*p)));
+            width_on_press <- new_size.sample(&handle_press).map(|size| size.x);
+            dragged_width <- pos_on_move.map3(&pos_on_press, &width_on_press,
+                |pos, start, width| (width + pos.x - start.x).max(MIN_WIDTH)
+            );
+            width_override <+ dragged_width.some();
+            resized <- bool(&handle_press, &dragged_width);
+            resize_finished <- on_up.gate(&was_resizing).gate(&resized);
+            out.width_overridden <+ dragged_width.sample(&resize_finished).some();
+
+            press_time <- scene.frp.frame_time.sample(&handle_press);
+            previous_press_time <- press_time.previous();
+            double_press <- press_time.map2(&previous_press_time,
+                |time, previous| time - previous < RESIZE_HANDLE_DOUBLE_PRESS_MS
+            ).on_true();
+            width_override <+ double_press.constant(None);
+            out.width_overridden <+ double_press.constant(None);
+
+            handle_hovered <- bool(&handle_leave, &handle_enter).gate_not(&input.set_read_only);
+            show_resize_cursor <- handle_hovered || is_resizing;
+            app.cursor.frp.set_style_override <+ show_resize_cursor.on_change().map(|shown| {
+                shown.then(|| cursor::Style::double_arrow(0.0))
+            });
         }
 
         frp::extend! { network
@@ -701,6 +1152,16 @@ impl Node {
             action_bar.set_action_skip_state <+ input.set_skip_macro;
             action_bar.set_action_context_switch_state <+ input.set_context_switch;
             action_bar.set_execution_environment <+ input.set_execution_environment;
+            out.color_tag <+ action_bar.action_color_tag;
+            out.custom_action <+ action_bar.action_custom;
+            output_type <- input.set_expression_usage_type.filter_map(f!(((id, tp))
+                (model.output.whole_expr_id() == Some(*id)).then(|| tp.clone())
+            ));
+            out.output_type <+ input.set_expression.constant(None);
+            out.output_type <+ output_type;
+            action_bar.set_action_context <+ output_type.map(|output_type| {
+                action_bar::ActionContext { output_type: output_type.clone() }
+            });
         }
 
         frp::extend! { network
@@ -711,6 +1172,33 @@ impl Node {
             model.vcs_indicator.set_visibility  <+ input.set_view_mode.map(|&mode| {
                 !matches!(mode,view::Mode::Profiling {..})
             });
+            model.execution_count.set_view_mode <+ input.set_view_mode;
+            model.execution_count.set_count <+ input.set_execution_count;
+            model.edge_stubs.set_hidden_edges <+ input.set_hidden_edges;
+            out.edge_stubs_hovered <+ model.edge_stubs.hovered;
+            out.error_frame_clicked <+ model.error_visualization.frame_clicked;
+            model.warnings.set_warnings <+ input.set_warnings;
+            out.warnings <+ input.set_warnings;
+            out.stale <+ input.set_stale.on_change();
+            model.stale_indicator.set_stale <+ out.stale;
+            model.value_preview.set_stale <+ out.stale;
+            model.edit_conflict.set_conflict <+ input.set_edit_conflict;
+            out.edit_conflict <+ input.set_edit_conflict;
+            out.accept_remote_requested <+ model.edit_conflict.accept_requested;
+            out.execution_duration <+ input.set_execution_duration;
+            out.doc_summary <+ input.set_doc_summary;
+            model.value_preview.set_preview <+ input.set_value_preview;
+            out.group <+ input.set_group;
+
+
+            // === Collapsing ===
+
+            out.collapsed <+ input.set_collapsed.on_change();
+            collapsed <- all(&out.collapsed, &init)._0();
+            eval collapsed ((collapsed) model.set_collapsed(*collapsed));
+            model.value_preview.set_enabled <+ input.show_value_preview.map2(&collapsed,
+                |show, collapsed| *show && !collapsed
+            );
         }
 
         frp::extend! { network
@@ -828,6 +1316,43 @@ impl Node {
 
         }
 
+        frp::extend! { network
+            // === Breakpoint ===
+
+            let breakpoint_color = style_frp.get_color_lcha(theme::graph_editor::node::breakpoint);
+            _eval <- all_with(&input.set_breakpoint, &breakpoint_color,
+                f!((breakpoint, color) model.set_breakpoint(*breakpoint, *color))
+            );
+        }
+
+        frp::extend! { network
+            // === Computing Indicator ===
+
+            let scene = &app.display.default_scene;
+            let computing_color = style_frp.get_color_lcha(theme::graph_editor::node::computing);
+            out.computing <+ input.set_computing.on_change();
+            computing_time <- scene.frp.frame_time.gate(&out.computing);
+            computing_phase <- computing_time.map(|t| {
+                Some(t % COMPUTING_PULSE_PERIOD_MS / COMPUTING_PULSE_PERIOD_MS)
+            });
+            computing_hidden <- out.computing.on_false().constant(None);
+            computing_pulse <- any(computing_phase, computing_hidden);
+            _eval <- computing_pulse.map2(&computing_color,
+                f!((phase, color) model.set_computing_pulse(*phase, *color))
+            );
+        }
+
+        frp::extend! { network
+            // === Rejection Feedback ===
+
+            let rejection_color = style_frp.get_color_lcha(theme::graph_editor::node::rejection);
+            rejection_flash.set_value <+ input.flash_rejection.constant(1.0);
+            rejection_flash.target <+ input.flash_rejection.constant(0.0);
+            _eval <- rejection_flash.value.map2(&rejection_color,
+                f!((strength, color) model.set_rejection_flash(*strength, *color))
+            );
+        }
+
         frp::extend! { network
             // === Tooltip ===
 
@@ -836,6 +1361,7 @@ impl Node {
             // Propagate output tooltip. Only if it is not hidden, or to disable it.
             block_tooltip      <- hide_tooltip && has_tooltip;
             app.frp.set_tooltip <+ model.output.frp.tooltip.gate_not(&block_tooltip);
+            app.frp.set_tooltip <+ model.edit_conflict.tooltip;
         }
 
         frp::extend! { network
@@ -869,9 +1395,21 @@ impl Node {
             let editing_color = style_frp.get_color_lcha(theme::graph_editor::node::background);
             let pending_alpha_factor =
                 style_frp.get_number(theme::graph_editor::node::pending::alpha_factor);
+            let color_tag_tint_alpha =
+                style_frp.get_number(theme::graph_editor::node::color_tag::tint_alpha);
             base_color_source <- source();
+            color_tag <- any(&input.set_color_tag, &action_bar.action_color_tag);
+            action_bar.set_action_color_tag_state <+ color_tag;
+            out.current_color_tag <+ color_tag;
+            tagged_base_color <- all_with3(
+                &base_color_source, &color_tag, &color_tag_tint_alpha,
+                |c: &color::Lcha, tag, tint_alpha| match tag {
+                    Some(tag) => color::Lcha::from(*tag).multiply_alpha(*tint_alpha).over(*c),
+                    None => *c,
+                }
+            );
             adjusted_base_color <- all_with3(
-                &base_color_source, &frp.set_pending, &pending_alpha_factor,
+                &tagged_base_color, &frp.set_pending, &pending_alpha_factor,
                 |c: &color::Lcha, pending, factor| {
                     match *pending {
                         true => c.multiply_alpha(*factor),
@@ -879,7 +1417,25 @@ impl Node {
                     }
                 }
             );
-            out.base_color <+ adjusted_base_color;
+            let dimmed_alpha = style_frp.get_number(theme::graph_editor::focus_mode::dimmed_alpha);
+            dimmed_base_color <- all_with3(
+                &adjusted_base_color, &input.set_dimmed, &dimmed_alpha,
+                |c, dimmed, alpha| if *dimmed { c.multiply_alpha(*alpha) } else { *c }
+            );
+            let poisoned_alpha =
+                style_frp.get_number(theme::graph_editor::error_propagation::poisoned_alpha);
+            poisoned_base_color <- all_with3(
+                &dimmed_base_color, &input.set_poisoned, &poisoned_alpha,
+                |c, poisoned, alpha| match *poisoned {
+                    true => c.to_grayscale().multiply_alpha(*alpha),
+                    false => *c,
+                }
+            );
+            palette_base_color <- all_with(
+                &poisoned_base_color, &input.set_color_palette,
+                |c, palette| palette.adjust(*c)
+            );
+            out.base_color <+ palette_base_color;
             out.port_color <+ out.base_color.all_with(&port_color_tint, |c, tint| tint.over(*c));
             background_color <- model.input.frp.editing.switch(&frp.base_color, &editing_color);
             node_colors <- all(background_color, frp.port_color);
@@ -896,6 +1452,10 @@ impl Node {
         frp.set_error.emit(None);
         frp.set_disabled.emit(false);
         frp.set_pending.emit(false);
+        frp.set_dimmed.emit(false);
+        frp.set_poisoned.emit(false);
+        frp.set_color_tag.emit(None);
+        frp.set_color_palette.emit(type_coloring::Palette::default());
         frp.show_quick_action_bar_on_hover.emit(true);
 
         let widget = gui::Widget::new(app, frp, model);
@@ -922,16 +1482,76 @@ impl Node {
     pub fn visualization(&self) -> &visualization::container::Frp {
         &self.model().visualization.frp
     }
+
+    /// Add the custom action to the node's action bar. See [`action_bar::CustomAction`].
+    pub fn add_custom_action(&self, action: &action_bar::CustomAction) {
+        self.model().action_bar.add_custom_action(action)
+    }
+
+    /// Bring the node back to the state of a newly created node, so its view can be reused to
+    /// display another node. The settings common to all nodes, like the view mode, the color
+    /// palette, or the read-only mode, are not reset, as the Graph Editor keeps them up to date
+    /// also for the nodes that are not displayed.
+    pub fn reset(&self) {
+        self.deselect();
+        self.set_selection_preview(None);
+        self.set_edge_emphasis(None);
+        self.set_hidden_edges(None);
+        self.disable_visualization();
+        self.set_visualization(None);
+        self.set_disabled(false);
+        self.set_pending(false);
+        self.set_dimmed(false);
+        self.set_poisoned(false);
+        self.set_breakpoint(false);
+        self.set_computing(false);
+        self.set_stale(false);
+        self.set_connections(default());
+        self.set_skip_macro(false);
+        self.set_freeze_macro(false);
+        self.set_context_switch(None);
+        self.set_comment(ImString::default());
+        self.set_color_tag(None);
+        self.set_collapsed(false);
+        self.set_group(None);
+        self.set_width_override(None);
+        self.set_error(None);
+        self.set_vcs_status(None);
+        self.set_execution_count(None);
+        self.set_edit_conflict(None);
+        self.set_value_preview(None);
+        self.set_warnings(default());
+        self.set_description_pending(false);
+        self.set_description_suggestion(None);
+        self.set_execution_duration(None);
+        self.set_doc_summary(None);
+        self.set_expression(Expression::default());
+    }
 }
 
 
 
 // === Positioning ===
 
+/// The width of the node, given the width fitting its expression and the width set by the user.
+fn node_width(input_width: f32, width_override: Option<f32>) -> f32 {
+    width_override.unwrap_or(input_width)
+}
+
 fn x_offset_to_node_center(node_width: f32) -> f32 {
     node_width / 2.0
 }
 
+/// The node's origin stays in the middle of its first line, so a node taller than [`HEIGHT`] is
+/// extended downwards.
+fn y_offset_to_node_center(node_height: f32) -> f32 {
+    -(node_height - HEIGHT) / 2.0
+}
+
+fn visualization_offset(node_height: f32) -> Vector2 {
+    VISUALIZATION_OFFSET - Vector2(0.0, node_height - HEIGHT)
+}
+
 #[profile(Debug)]
 fn bounding_box(
     node_position: Vector2,
@@ -939,10 +1559,12 @@ fn bounding_box(
     visualization_size: Option<Vector2>,
 ) -> BoundingBox {
     let x_offset_to_node_center = x_offset_to_node_center(node_size.x);
-    let node_bbox_pos = node_position + Vector2(x_offset_to_node_center, 0.0) - node_size / 2.0;
+    let y_offset_to_node_center = y_offset_to_node_center(node_size.y);
+    let offset_to_node_center = Vector2(x_offset_to_node_center, y_offset_to_node_center);
+    let node_bbox_pos = node_position + offset_to_node_center - node_size / 2.0;
     let node_bbox = BoundingBox::from_bottom_left_position_and_size(node_bbox_pos, node_size);
     if let Some(visualization_size) = visualization_size {
-        let visualization_pos = node_position + VISUALIZATION_OFFSET;
+        let visualization_pos = node_position + visualization_offset(node_size.y);
         let visualization_bbox =
             BoundingBox::from_top_left_position_and_size(visualization_pos, visualization_size);
         node_bbox.concat_ref(visualization_bbox)
@@ -952,6 +1574,18 @@ fn bounding_box(
 }
 
 
+// === Edge emphasis ===
+
+/// The end of the hovered edge the node is, used to emphasize the edge's connection.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EdgeEmphasis {
+    /// The edge originates from the node's output.
+    Source,
+    /// The edge is attached to the given input port of the node.
+    Target(span_tree::PortId),
+}
+
+
 // === Interaction state ===
 
 /// Information about how the node is being interacted with.
@@ -4,18 +4,21 @@ use crate::prelude::*;
 use ensogl::display::shape::*;
 
 use crate::layers::MainNodeLayers;
+use crate::Type;
 
-use engine_protocol::language_server::ExecutionEnvironment;
 use enso_config::ARGS;
 use enso_frp as frp;
 use ensogl::application::tooltip;
 use ensogl::application::Application;
 use ensogl::control::io::mouse;
+use ensogl::data::color;
 use ensogl::display;
 use ensogl_component::toggle_button;
 use ensogl_component::toggle_button::ColorableShape;
 use ensogl_component::toggle_button::ToggleButton;
 use ensogl_hardcoded_theme::graph_editor::node::actions as theme;
+use ensogl_hardcoded_theme::graph_editor::node::color_tag as theme_color_tag;
+use ide_view_execution_environment_selector::ExecutionEnvironmentDef;
 
 
 // ==============
@@ -39,11 +42,20 @@ const HOVER_EXTENSION: Vector2 = Vector2(15.0, 11.0);
 /// easily losing the hover state when moving the mouse towards the action bar.
 const HOVER_BRIDGE_SIZE: Vector2 = Vector2(10.0, 26.0);
 const HOVER_HIDE_DELAY_MS: i32 = 20;
+// The tooltip labels are the English messages, translated to the current locale when the button
+// is created. See [`ensogl::application::i18n`].
 const VISIBILITY_TOOLTIP_LABEL: &str = "Show preview";
 const DISABLE_OUTPUT_CONTEXT_TOOLTIP_LABEL: &str = "Don't write to files and databases";
 const ENABLE_OUTPUT_CONTEXT_TOOLTIP_LABEL: &str = "Allow writing to files and databases";
 const FREEZE_TOOLTIP_LABEL: &str = "Freeze";
 const SKIP_TOOLTIP_LABEL: &str = "Skip";
+const COLOR_TAG_TOOLTIP_LABEL: &str = "Color tag";
+const DESCRIBE_TOOLTIP_LABEL: &str = "Generate description";
+const SWATCH_SIZE: f32 = 11.0;
+const SWATCH_GAP: f32 = 4.0;
+const SWATCH_NONE_BORDER: f32 = 1.5;
+/// Alpha multiplier of the swatches not matching the currently assigned color tag.
+const SWATCH_INACTIVE_ALPHA: f32 = 0.5;
 
 
 
@@ -62,9 +74,17 @@ ensogl::define_endpoints! {
         /// enabled/disabled; `None` for no context switch expression.
         set_action_context_switch_state (Option<bool>),
         show_on_hover                   (bool),
-        set_execution_environment       (ExecutionEnvironment),
+        set_execution_environment       (ExecutionEnvironmentDef),
         /// Set the read-only mode for the buttons.
         set_read_only                   (bool),
+        /// Set the color tag currently assigned to the node. The matching swatch is highlighted
+        /// in the color tag picker.
+        set_action_color_tag_state      (Option<color::Rgba>),
+        /// Set the information about the node, deciding which custom actions are displayed.
+        set_action_context              (ActionContext),
+        /// Set whether the node's description is being generated. The `describe` icon stays
+        /// toggled on until the description is generated.
+        set_action_describe_pending     (bool),
     }
 
     Output {
@@ -77,6 +97,13 @@ ensogl::define_endpoints! {
         action_context_switch (bool),
         action_freeze         (bool),
         action_skip           (bool),
+        /// The color tag picked by the user from the swatch picker. `None` clears the tag.
+        action_color_tag      (Option<color::Rgba>),
+        /// The name of the custom action triggered by the user.
+        action_custom         (ImString),
+        /// The user requested generating the node's description. Not emitted while the description
+        /// is being generated.
+        action_describe       (),
     }
 }
 
@@ -93,6 +120,9 @@ struct Icons {
     context_switch: ContextSwitchButton,
     freeze:         ToggleButton<icon::freeze::Shape>,
     skip:           ToggleButton<icon::skip::Shape>,
+    custom:         CustomActionButtons,
+    describe:       ToggleButton<icon::describe::Shape>,
+    color_tag:      ColorTagPicker,
 }
 
 impl Icons {
@@ -109,6 +139,9 @@ impl Icons {
         let context_switch = ContextSwitchButton::enable(app);
         let freeze = labeled_button(app, FREEZE_TOOLTIP_LABEL);
         let skip = labeled_button(app, SKIP_TOOLTIP_LABEL);
+        let custom = CustomActionButtons::new();
+        let describe = labeled_button(app, DESCRIBE_TOOLTIP_LABEL);
+        let color_tag = ColorTagPicker::new(app);
 
         display_object.add_child(&visibility);
         display_object.add_child(&context_switch);
@@ -116,13 +149,25 @@ impl Icons {
             display_object.add_child(&freeze);
             display_object.add_child(&skip);
         }
+        display_object.add_child(&custom);
+        display_object.add_child(&describe);
+        display_object.add_child(&color_tag);
 
         // The visibility icon looks smaller than the other ones, so we make it bigger. This is a
         // purely aesthetic adjustment.
         visibility.set_size((BUTTON_SIZE * 1.2, BUTTON_SIZE * 1.2));
         visibility.set_margin_all(-BUTTON_SIZE * 0.2);
 
-        Self { display_object, visibility, context_switch, freeze, skip }
+        Self {
+            display_object,
+            visibility,
+            context_switch,
+            freeze,
+            skip,
+            custom,
+            describe,
+            color_tag,
+        }
     }
 
     fn set_visibility(&self, visible: bool) {
@@ -130,21 +175,29 @@ impl Icons {
         self.context_switch.set_visibility(visible);
         self.freeze.set_visibility(visible);
         self.skip.set_visibility(visible);
+        self.custom.set_visibility(visible);
+        self.describe.set_visibility(visible);
+        self.color_tag.set_visibility(visible);
         let pointer_events_val = if visible { 0.0 } else { 1.0 };
         self.visibility.view().disable_pointer_events.set(pointer_events_val);
         self.freeze.view().disable_pointer_events.set(pointer_events_val);
         self.skip.view().disable_pointer_events.set(pointer_events_val);
+        self.describe.view().disable_pointer_events.set(pointer_events_val);
     }
 
     fn set_read_only(&self, read_only: bool) {
         self.context_switch.set_read_only(read_only);
         self.freeze.set_read_only(read_only);
         self.skip.set_read_only(read_only);
+        self.custom.set_read_only(read_only);
+        self.describe.set_read_only(read_only);
+        self.color_tag.set_read_only(read_only);
     }
 }
 
 fn labeled_button<Icon: ColorableShape>(app: &Application, label: &str) -> ToggleButton<Icon> {
-    let tooltip_style = tooltip::Style::set_label(label.to_owned());
+    let label = app.translator().translate(label);
+    let tooltip_style = tooltip::Style::set_label(label.to_string());
     let button = ToggleButton::new(app, tooltip_style);
     button.set_size((BUTTON_SIZE, BUTTON_SIZE));
     button
@@ -190,9 +243,9 @@ impl ContextSwitchButton {
     }
 
     /// Swap the buttons if the execution environment changed.
-    fn set_execution_environment(&self, environment: &ExecutionEnvironment) {
-        if environment.output_context_enabled() != self.globally_enabled.get() {
-            if environment.output_context_enabled() {
+    fn set_execution_environment(&self, environment: &ExecutionEnvironmentDef) {
+        if environment.output_context_enabled != self.globally_enabled.get() {
+            if environment.output_context_enabled {
                 self.replace_children(&[&self.disable_button]);
                 self.globally_enabled.set(true);
             } else {
@@ -223,12 +276,266 @@ impl ContextSwitchButton {
 
 
 
+// ========================
+// === Color Tag Picker ===
+// ========================
+
+/// A single color swatch of the [`ColorTagPicker`]. The swatch clearing the tag is drawn as an
+/// empty ring.
+#[derive(Clone, CloneRef, Debug, display::Object)]
+struct Swatch {
+    #[display_object]
+    shape: Rectangle,
+    tag:   Immutable<Option<color::Rgba>>,
+    color: Immutable<color::Rgba>,
+}
+
+impl Swatch {
+    fn new(tag: Option<color::Rgba>, none_border_color: color::Rgba) -> Self {
+        let shape = Rectangle::new();
+        shape.set_size((SWATCH_SIZE, SWATCH_SIZE)).set_corner_radius_max();
+        if tag.is_none() {
+            shape.set_color(color::Rgba::transparent()).set_border_and_inset(SWATCH_NONE_BORDER);
+        }
+        let color = Immutable(tag.unwrap_or(none_border_color));
+        let swatch = Self { shape, tag: Immutable(tag), color };
+        swatch.set_active(true);
+        swatch
+    }
+
+    fn set_active(&self, active: bool) {
+        let alpha = if active { 1.0 } else { SWATCH_INACTIVE_ALPHA };
+        let color = self.color.multiply_alpha(alpha);
+        match *self.tag {
+            Some(_) => self.shape.set_color(color),
+            None => self.shape.set_border_color(color),
+        };
+    }
+}
+
+/// A button revealing a row of color swatches, allowing the user to assign a color tag to the
+/// node. The first swatch clears the tag.
+#[derive(Clone, CloneRef, Debug, display::Object)]
+struct ColorTagPicker {
+    display_object: display::object::Instance,
+    button:         ToggleButton<icon::color_tag::Shape>,
+    swatches_row:   display::object::Instance,
+    swatches:       Rc<Vec<Swatch>>,
+}
+
+impl ColorTagPicker {
+    fn new(app: &Application) -> Self {
+        let display_object = display::object::Instance::new_named("ColorTagPicker");
+        display_object
+            .use_auto_layout()
+            .reverse_columns()
+            .set_gap((BUTTON_GAP, 0.0))
+            .set_children_alignment_left_center();
+        let button = labeled_button(app, COLOR_TAG_TOOLTIP_LABEL);
+        let swatches_row = display::object::Instance::new_named("swatches");
+        swatches_row
+            .use_auto_layout()
+            .reverse_columns()
+            .set_gap((SWATCH_GAP, 0.0))
+            .set_children_alignment_left_center();
+
+        let styles = StyleWatch::new(&app.display.default_scene.style_sheet);
+        let none_border_color = styles.get_color(theme_color_tag::none_border);
+        let tags = [
+            None,
+            Some(styles.get_color(theme_color_tag::todo)),
+            Some(styles.get_color(theme_color_tag::verified)),
+            Some(styles.get_color(theme_color_tag::experimental)),
+        ];
+        let swatches = tags.into_iter().map(|tag| Swatch::new(tag, none_border_color)).collect_vec();
+        for swatch in &swatches {
+            swatches_row.add_child(swatch);
+        }
+        display_object.add_child(&button);
+        let swatches = Rc::new(swatches);
+        Self { display_object, button, swatches_row, swatches }
+    }
+
+    fn set_expanded(&self, expanded: bool) {
+        if expanded {
+            self.display_object.add_child(&self.swatches_row);
+        } else {
+            self.swatches_row.unset_parent();
+        }
+    }
+
+    /// Mark the swatch of the currently assigned tag, by dimming all the other ones.
+    fn set_state(&self, tag: Option<color::Rgba>) {
+        for swatch in self.swatches.iter() {
+            swatch.set_active(*swatch.tag == tag);
+        }
+    }
+
+    fn set_visibility(&self, visible: bool) {
+        self.button.set_visibility(visible);
+        let pointer_events_val = if visible { 0.0 } else { 1.0 };
+        self.button.view().disable_pointer_events.set(pointer_events_val);
+        if !visible {
+            self.button.set_state(false);
+        }
+    }
+
+    fn set_read_only(&self, read_only: bool) {
+        self.button.set_read_only(read_only);
+        if read_only {
+            self.button.set_state(false);
+        }
+    }
+}
+
+
+
+// ======================
+// === Custom Actions ===
+// ======================
+
+/// The information about the node, based on which the custom actions are displayed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ActionContext {
+    /// The type of the node's output value, if known.
+    pub output_type: Option<Type>,
+}
+
+/// A quick action added to the action bar by the crates using the Graph Editor, e.g. "open in
+/// table editor" for the nodes returning tables. The action's button is displayed only on the
+/// nodes matching the action's predicate.
+///
+/// The icon shape must be drawn above the action bar's hover area, so the crate defining it should
+/// declare the `compound::rectangle::shape -> Icon` order dependency, like the built-in icons do.
+#[derive(Clone, CloneRef, Derivative)]
+#[derivative(Debug)]
+pub struct CustomAction {
+    /// The unique name identifying the action in the [`Frp::action_custom`] output.
+    pub name:    ImString,
+    /// The label of the button's tooltip.
+    pub tooltip: ImString,
+    #[derivative(Debug = "ignore")]
+    new_button:  Rc<dyn Fn(&Application, &str) -> CustomActionButton>,
+    #[derivative(Debug = "ignore")]
+    predicate:   Rc<dyn Fn(&ActionContext) -> bool>,
+}
+
+impl CustomAction {
+    /// Constructor. The action's button displays the `Icon` shape.
+    pub fn new<Icon: ColorableShape + 'static>(
+        name: impl Into<ImString>,
+        tooltip: impl Into<ImString>,
+        predicate: impl Fn(&ActionContext) -> bool + 'static,
+    ) -> Self {
+        let name = name.into();
+        let tooltip = tooltip.into();
+        let new_button = Rc::new(|app: &Application, label: &str| {
+            CustomActionButton::new(labeled_button::<Icon>(app, label))
+        });
+        let predicate = Rc::new(predicate);
+        Self { name, tooltip, new_button, predicate }
+    }
+
+    /// Check whether the action should be displayed on the node.
+    pub fn is_available(&self, context: &ActionContext) -> bool {
+        (self.predicate)(context)
+    }
+}
+
+/// The button of a [`CustomAction`], with the type of its icon shape erased.
+#[derive(Clone, CloneRef, Derivative, display::Object)]
+#[derivative(Debug)]
+struct CustomActionButton {
+    display_object:     display::object::Instance,
+    frp:                toggle_button::Frp,
+    #[derivative(Debug = "ignore")]
+    set_pointer_events: Rc<dyn Fn(bool)>,
+}
+
+impl CustomActionButton {
+    fn new<Icon: ColorableShape + 'static>(button: ToggleButton<Icon>) -> Self {
+        let display_object = button.display_object().clone_ref();
+        let frp = button.frp.clone_ref();
+        let set_pointer_events = Rc::new(move |enabled| {
+            let pointer_events_val = if enabled { 0.0 } else { 1.0 };
+            button.view().disable_pointer_events.set(pointer_events_val);
+        });
+        Self { display_object, frp, set_pointer_events }
+    }
+}
+
+/// The buttons of all custom actions added to the action bar. Only the buttons of the actions
+/// available for the current [`ActionContext`] are displayed.
+#[derive(Clone, CloneRef, Debug, display::Object)]
+struct CustomActionButtons {
+    display_object: display::object::Instance,
+    buttons:        Rc<RefCell<Vec<(CustomAction, CustomActionButton)>>>,
+    context:        Rc<RefCell<ActionContext>>,
+    visible:        Rc<Cell<bool>>,
+    read_only:      Rc<Cell<bool>>,
+}
+
+impl CustomActionButtons {
+    fn new() -> Self {
+        let display_object = display::object::Instance::new_named("CustomActions");
+        display_object
+            .use_auto_layout()
+            .reverse_columns()
+            .set_gap((BUTTON_GAP, 0.0))
+            .set_children_alignment_left_center();
+        let buttons = default();
+        let context = default();
+        let visible = default();
+        let read_only = default();
+        Self { display_object, buttons, context, visible, read_only }
+    }
+
+    fn add(&self, action: &CustomAction, button: CustomActionButton) {
+        button.frp.set_visibility(self.visible.get());
+        (button.set_pointer_events)(self.visible.get());
+        button.frp.set_read_only(self.read_only.get());
+        self.buttons.borrow_mut().push((action.clone_ref(), button));
+        self.update_displayed_buttons();
+    }
+
+    fn set_context(&self, context: &ActionContext) {
+        *self.context.borrow_mut() = context.clone();
+        self.update_displayed_buttons();
+    }
+
+    fn update_displayed_buttons(&self) {
+        let context = self.context.borrow();
+        let buttons = self.buttons.borrow();
+        let available = buttons.iter().filter(|(action, _)| action.is_available(&context));
+        let displayed = available.map(|(_, button)| button).collect_vec();
+        self.display_object.replace_children(&displayed);
+    }
+
+    fn set_visibility(&self, visible: bool) {
+        self.visible.set(visible);
+        for (_, button) in self.buttons.borrow().iter() {
+            button.frp.set_visibility(visible);
+            (button.set_pointer_events)(visible);
+        }
+    }
+
+    fn set_read_only(&self, read_only: bool) {
+        self.read_only.set(read_only);
+        for (_, button) in self.buttons.borrow().iter() {
+            button.frp.set_read_only(read_only);
+        }
+    }
+}
+
+
+
 // ========================
 // === Action Bar Model ===
 // ========================
 
 #[derive(Clone, CloneRef, Debug, display::Object)]
 struct Model {
+    app:                    Application,
     display_object:         display::object::Instance,
     hover_area:             Rectangle,
     /// Additional hover area that is drawn below the node background. Serves as an always active
@@ -271,10 +578,13 @@ impl Model {
                 compound::rectangle::shape -> icon::enable_output_context;
                 compound::rectangle::shape -> icon::freeze;
                 compound::rectangle::shape -> icon::skip;
+                compound::rectangle::shape -> icon::color_tag;
+                compound::rectangle::shape -> icon::describe;
             }
         }
 
-        Self { display_object, hover_area, hover_area_below_nodes, icons, styles }
+        let app = app.clone_ref();
+        Self { app, display_object, hover_area, hover_area_below_nodes, icons, styles }
     }
 
     fn set_visibility(&self, visible: bool) {
@@ -337,6 +647,7 @@ impl ActionBar {
             eval frp.set_execution_environment ((environment)
                 model.icons.context_switch.set_execution_environment(environment)
             );
+            eval frp.set_action_context ((context) model.icons.custom.set_context(context));
 
 
             // === Mouse Interactions ===
@@ -370,6 +681,19 @@ impl ActionBar {
             output_context_enabled <- model.icons.context_switch.enable_button.state
                 .sample(&enable_context_button_clicked);
             frp.source.action_context_switch <+ any(&output_context_disabled, &output_context_enabled);
+
+            // The `describe` button is one-shot, but stays toggled on while the description is
+            // being generated, indicating the loading state.
+            describe_triggered <- model.icons.describe.last_user_state.constant(());
+            describe_pending <- frp.set_action_describe_pending.identity();
+            frp.source.action_describe <+ describe_triggered.gate_not(&describe_pending);
+            describe_state <- describe_pending.sample(&describe_triggered);
+            describe_state <+ describe_pending;
+            eval describe_state ((pending) model.icons.describe.set_state(pending));
+
+            let color_tag = &model.icons.color_tag;
+            eval color_tag.button.state ((expanded) color_tag.set_expanded(*expanded));
+            eval frp.set_action_color_tag_state ((tag) color_tag.set_state(*tag));
             // Setting the state of the context switch button is necessary because e.g. toggling
             // the "enable" button when there's a "disable" expression should cause the "disable"
             // button to change state as well.
@@ -401,6 +725,17 @@ impl ActionBar {
             );
         }
 
+        for swatch in model.icons.color_tag.swatches.iter() {
+            let tag = *swatch.tag;
+            let color_tag = &model.icons.color_tag;
+            let swatch_press = swatch.on_event::<mouse::Down>();
+            frp::extend! { network
+                swatch_picked <- swatch_press.filter(mouse::event::is_primary).constant(tag);
+                frp.source.action_color_tag <+ swatch_picked;
+                eval_ swatch_picked (color_tag.button.set_state(false));
+            }
+        }
+
         let scene = &app.display.default_scene;
         let context_switch_color_scheme = toggle_button::ColorScheme {
             toggled: Some(model.styles.get_color(theme::context_switch::toggled).into()),
@@ -414,6 +749,22 @@ impl ActionBar {
         self
     }
 
+    /// Add the button of the custom action. It is displayed only while the action is available for
+    /// the context set with [`Frp::set_action_context`].
+    pub fn add_custom_action(&self, action: &CustomAction) {
+        let network = &self.frp.network;
+        let frp = &self.frp;
+        let button = (action.new_button)(&self.model.app, &action.tooltip);
+        let name = action.name.clone_ref();
+        frp::extend! { network
+            // The custom actions are one-shot, so the button is never left toggled.
+            triggered <- button.frp.last_user_state.constant(());
+            frp.source.action_custom <+ triggered.constant(name);
+            button.frp.set_state <+ triggered.constant(false);
+        }
+        self.model.icons.custom.add(action, button);
+    }
+
     /// Configure this action bar to use specific node layers.
     pub fn set_layers(&self, main: &MainNodeLayers) {
         main.action_bar_icons.add(&self.model.icons);
@@ -430,6 +781,7 @@ impl ActionBar {
 #[cfg(test)]
 mod test {
     use super::*;
+    use ensogl::application::i18n;
 
     #[test]
     fn test_tooltips() {
@@ -455,4 +807,16 @@ mod test {
         // We expect the tooltip to be gone
         assert_eq!(app.frp.tooltip.value().content(), None);
     }
+
+    #[test]
+    fn test_translated_tooltips() {
+        let app = Application::new("root");
+        let polish = i18n::LanguageTag::new("pl");
+        app.i18n.add_translation(&polish, VISIBILITY_TOOLTIP_LABEL, "Pokaż podgląd");
+        app.frp.set_locale(polish);
+        let action_bar = ActionBar::new(&app);
+        let visibility_icon = &action_bar.model.icons.visibility;
+        visibility_icon.view().display_object().emit_event(mouse::Enter::default());
+        assert_eq!(app.frp.tooltip.value().content(), Some("Pokaż podgląd"));
+    }
 }
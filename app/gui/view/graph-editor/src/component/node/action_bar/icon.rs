@@ -8,6 +8,7 @@ use ensogl_component::toggle_button::ColorableShape;
 use ensogl_hardcoded_theme::graph_editor::node::actions as theme;
 use std::f32::consts::FRAC_PI_2;
 use std::f32::consts::FRAC_PI_6;
+use std::f32::consts::PI;
 
 
 
@@ -263,6 +264,65 @@ pub mod enable_output_context {
     }
 }
 
+/// Icon for the color tag button. Looks like a paint drop.
+pub mod color_tag {
+    use super::*;
+
+    ensogl::shape! {
+        above = [compound::rectangle];
+        pointer_events_instanced = true;
+        (style: Style, color_rgba: Vector4<f32>) {
+            let fill_color = Var::<color::Rgba>::from(color_rgba);
+            let width = Var::<Pixels>::from("input_size.x");
+            let height = Var::<Pixels>::from("input_size.y");
+            let unit = &width / 16.0;
+            let body = Circle(&unit * 5.0).translate_y(&unit * -2.0);
+            let tip = Triangle(&unit * 8.6, &unit * 7.0).translate_y(&unit * 3.5);
+            let icon = (body + tip).fill(fill_color);
+            let hover_area = Rect((width,height)).fill(INVISIBLE_HOVER_COLOR);
+            (icon + hover_area).into()
+        }
+    }
+
+    impl ColorableShape for Shape {
+        fn set_color(&self, color: color::Rgba) {
+            self.color_rgba.set(Vector4::new(color.red, color.green, color.blue, color.alpha));
+        }
+    }
+}
+
+/// Icon for the button generating the node's description. Looks like a speech bubble with lines of
+/// text.
+pub mod describe {
+    use super::*;
+
+    ensogl::shape! {
+        above = [compound::rectangle];
+        pointer_events_instanced = true;
+        (style: Style, color_rgba: Vector4<f32>) {
+            let fill_color = Var::<color::Rgba>::from(color_rgba);
+            let width = Var::<Pixels>::from("input_size.x");
+            let height = Var::<Pixels>::from("input_size.y");
+            let unit = &width / 16.0;
+            let bubble = Rect((&unit * 14.0, &unit * 10.0)).corners_radius(&unit * 3.0);
+            let bubble = bubble.translate_y(&unit * 1.5);
+            let tail = Triangle(&unit * 5.0, &unit * 4.0).rotate(PI.radians());
+            let tail = tail.translate_x(&unit * -3.0).translate_y(&unit * -4.5);
+            let line = Rect((&unit * 8.0, &unit * 1.5));
+            let lines = line.translate_y(&unit * 3.0) + line.translate_y(&unit * 0.0);
+            let icon = (bubble + tail - lines).fill(fill_color);
+            let hover_area = Rect((width, height)).fill(INVISIBLE_HOVER_COLOR);
+            (icon + hover_area).into()
+        }
+    }
+
+    impl ColorableShape for Shape {
+        fn set_color(&self, color: color::Rgba) {
+            self.color_rgba.set(Vector4::new(color.red, color.green, color.blue, color.alpha));
+        }
+    }
+}
+
 /// Draw a right-turning arrow loop with the arrow at the top.
 fn arrow_loop(unit: &Var<Pixels>) -> AnyShape {
     let outer_rect = Rect((unit * 14.0, unit * 12.0)).corners_radius(unit * 6.0);
@@ -0,0 +1,189 @@
+//! A badge marking a node whose expression was concurrently edited by another user. Hovering the
+//! badge displays the other user's version of the expression in a tooltip, and clicking it accepts
+//! that version, see the `accept_remote` input of the Graph Editor.
+
+use crate::prelude::*;
+use ensogl::display::shape::*;
+
+use crate::component::node;
+use crate::tooltip;
+
+use enso_frp as frp;
+use ensogl::application::Application;
+use ensogl::control::io::mouse;
+use ensogl::display;
+use ensogl_component::text;
+use ensogl_hardcoded_theme::graph_editor::node::edit_conflict as theme;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// Text size of the badge label.
+const TEXT_SIZE: f32 = 10.0;
+/// Horizontal padding between the badge label and the badge border.
+const PADDING_X: f32 = 5.0;
+/// Height of the badge.
+const BADGE_HEIGHT: f32 = 16.0;
+/// Offset of the badge's left border from the top left corner of the node.
+const BADGE_OFFSET: Vector2 = Vector2(0.0, 4.0);
+/// The label of the badge. The placeholder is substituted after the message is translated.
+const LABEL: &str = "Edited by {author}";
+/// The tooltip displayed when the badge is hovered. The placeholders are substituted after the
+/// message is translated.
+const TOOLTIP: &str = "{author}'s version: {expression}. Click to accept it.";
+
+
+
+// ====================
+// === ConflictInfo ===
+// ====================
+
+/// The concurrent edit of the node's expression made by another user.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConflictInfo {
+    /// The name of the user who made the edit.
+    pub author:            ImString,
+    /// The expression of the node in the other user's version.
+    pub remote_expression: ImString,
+}
+
+
+
+// ===========
+// === Frp ===
+// ===========
+
+ensogl::define_endpoints_2! {
+    Input {
+        /// Set the conflicting edit. `None` hides the badge.
+        set_conflict (Option<ConflictInfo>),
+    }
+    Output {
+        /// The tooltip to display, emitted when the badge is hovered or unhovered.
+        tooltip          (tooltip::Style),
+        /// The user clicked the badge to accept the other user's version of the expression.
+        accept_requested (),
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+#[derive(Clone, Debug, display::Object)]
+struct Model {
+    display_object: display::object::Instance,
+    root:           display::object::Instance,
+    background:     Rectangle,
+    label:          text::Text,
+}
+
+impl Model {
+    fn new(app: &Application) -> Self {
+        let display_object = display::object::Instance::new_named("EditConflictBadge");
+        let root = display::object::Instance::new();
+        let background = Rectangle::new();
+        background.set_corner_radius_max();
+        let label = app.new_view::<text::Text>();
+        label.set_single_line_mode(true);
+        label.set_property_default(text::Size(TEXT_SIZE));
+        label.remove_all_cursors();
+        root.add_child(&background);
+        root.add_child(&label);
+        root.set_xy(Vector2(BADGE_OFFSET.x, node::HEIGHT / 2.0 + BADGE_OFFSET.y));
+        Self { display_object, root, background, label }
+    }
+
+    fn set_conflict(&self, app: &Application, conflict: &Option<ConflictInfo>) {
+        if let Some(conflict) = conflict {
+            let args = [("author", conflict.author.as_str())];
+            self.label.set_content(app.translator().translate_with(LABEL, &args));
+            self.display_object.add_child(&self.root);
+        } else {
+            self.root.unset_parent();
+        }
+    }
+
+    fn set_label_width(&self, label_width: f32) {
+        let width = label_width + PADDING_X * 2.0;
+        self.background.set_size(Vector2(width, BADGE_HEIGHT));
+        self.background.set_xy(Vector2(0.0, -BADGE_HEIGHT / 2.0));
+        self.label.set_xy(Vector2(PADDING_X, TEXT_SIZE / 2.0));
+    }
+}
+
+
+
+// =========================
+// === EditConflictBadge ===
+// =========================
+
+/// A badge attached to the top left corner of the node.
+#[derive(Clone, CloneRef, Debug, Deref, display::Object)]
+#[allow(missing_docs)]
+pub struct EditConflictBadge {
+    #[display_object]
+    model:   Rc<Model>,
+    #[deref]
+    pub frp: Frp,
+}
+
+impl EditConflictBadge {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let model = Rc::new(Model::new(app));
+        let frp = Frp::new();
+        Self { model, frp }.init(app)
+    }
+
+    fn init(self, app: &Application) -> Self {
+        let network = self.frp.network();
+        let input = &self.frp.private.input;
+        let out = &self.frp.private.output;
+        let model = &self.model;
+        let style = StyleWatchFrp::new(&app.display.default_scene.style_sheet);
+        let background_color = style.get_color(theme::background);
+        let text_color = style.get_color(theme::text);
+
+        frp::extend! { network
+            init <- source_();
+            eval input.set_conflict ([app, model](conflict) model.set_conflict(&app, conflict));
+            eval model.label.width ((width) model.set_label_width(*width));
+
+            let mouse_enter = model.background.on_event::<mouse::Enter>();
+            let mouse_leave = model.background.on_event::<mouse::Leave>();
+            let mouse_down = model.background.on_event::<mouse::Down>();
+            hovered <- bool(&mouse_leave, &mouse_enter);
+            has_conflict <- input.set_conflict.map(Option::is_some);
+            show_tooltip <- (hovered && has_conflict).on_change();
+            accept_press <- mouse_down.filter(mouse::event::is_primary).constant(());
+            out.accept_requested <+ accept_press.gate(&has_conflict);
+            out.tooltip <+ show_tooltip.map2(&input.set_conflict, f!([app](show, conflict) {
+                match conflict.as_ref().filter(|_| *show) {
+                    Some(conflict) => {
+                        let args = [
+                            ("author", conflict.author.as_str()),
+                            ("expression", conflict.remote_expression.as_str()),
+                        ];
+                        tooltip::Style::set_label(app.translator().translate_with(TOOLTIP, &args))
+                    }
+                    None => tooltip::Style::unset_label(),
+                }
+            }));
+
+            background_color <- all(&background_color, &init)._0();
+            eval background_color ((color) model.background.set_color(*color));
+            text_color <- all(&text_color, &init)._0();
+            eval text_color ((color) model.label.set_property_default(color));
+        }
+
+        init.emit(());
+        self.frp.set_conflict(None);
+        self
+    }
+}
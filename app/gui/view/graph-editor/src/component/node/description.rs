@@ -0,0 +1,258 @@
+//! A panel attached above the node, displaying the description of the node generated outside of
+//! the Graph Editor. The user may accept the description, making it the node's comment, or discard
+//! it. While the description is being generated, the panel displays a loading message.
+
+use crate::prelude::*;
+use ensogl::display::shape::*;
+
+use crate::component::node;
+
+use enso_frp as frp;
+use ensogl::application::Application;
+use ensogl::control::io::mouse;
+use ensogl::display;
+use ensogl_component::text;
+use ensogl_hardcoded_theme::graph_editor::node::description as theme;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// Text size of the panel labels.
+const TEXT_SIZE: f32 = 10.0;
+/// Horizontal padding between the labels and the borders of the panel and its buttons.
+const PADDING_X: f32 = 6.0;
+/// Height of the panel.
+const PANEL_HEIGHT: f32 = 20.0;
+/// Height of the buttons inside the panel.
+const BUTTON_HEIGHT: f32 = 14.0;
+/// Offset of the panel's bottom left corner from the top left corner of the node. Leaves space for
+/// the edit conflict badge.
+const PANEL_OFFSET: Vector2 = Vector2(0.0, 24.0);
+/// The label displayed while the description is being generated.
+const LOADING_LABEL: &str = "Generating description…";
+const ACCEPT_LABEL: &str = "Accept";
+const DISCARD_LABEL: &str = "Discard";
+
+
+
+// ===========
+// === Frp ===
+// ===========
+
+ensogl::define_endpoints_2! {
+    Input {
+        /// Set whether the description is being generated. The loading message is displayed until
+        /// the description is set.
+        set_pending    (bool),
+        /// Set the generated description. `None` hides the panel.
+        set_suggestion (Option<ImString>),
+        /// Accept the displayed description, as if the Accept button was pressed.
+        accept         (),
+        /// Discard the displayed description, as if the Discard button was pressed.
+        discard        (),
+    }
+    Output {
+        /// The user accepted the generated description.
+        accepted  (ImString),
+        /// The user discarded the generated description.
+        discarded (),
+        /// Whether the panel is currently displayed.
+        visible   (bool),
+    }
+}
+
+
+
+// ==============
+// === Button ===
+// ==============
+
+/// A text button inside the panel.
+#[derive(Clone, Debug, display::Object)]
+struct Button {
+    display_object: display::object::Instance,
+    background:     Rectangle,
+    label:          text::Text,
+}
+
+impl Button {
+    fn new(app: &Application, label: &str) -> Self {
+        let display_object = display::object::Instance::new();
+        let background = Rectangle::new();
+        background.set_corner_radius_max().set_pointer_events(true);
+        let label_view = app.new_view::<text::Text>();
+        label_view.set_single_line_mode(true);
+        label_view.set_property_default(text::Size(TEXT_SIZE));
+        label_view.remove_all_cursors();
+        label_view.set_content(app.translator().translate(label));
+        display_object.add_child(&background);
+        display_object.add_child(&label_view);
+        Self { display_object, background, label: label_view }
+    }
+
+    /// Lay out the button for the given label width. Returns the width of the button.
+    fn set_label_width(&self, label_width: f32) -> f32 {
+        let width = label_width + PADDING_X * 2.0;
+        self.background.set_size(Vector2(width, BUTTON_HEIGHT));
+        self.background.set_y(-BUTTON_HEIGHT / 2.0);
+        self.label.set_xy(Vector2(PADDING_X, TEXT_SIZE / 2.0));
+        width
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+#[derive(Clone, Debug, display::Object)]
+struct Model {
+    display_object: display::object::Instance,
+    root:           display::object::Instance,
+    background:     Rectangle,
+    label:          text::Text,
+    buttons:        display::object::Instance,
+    accept:         Button,
+    discard:        Button,
+}
+
+impl Model {
+    fn new(app: &Application) -> Self {
+        let display_object = display::object::Instance::new_named("DescriptionSuggestion");
+        let root = display::object::Instance::new();
+        let background = Rectangle::new();
+        background.set_corner_radius_max().set_pointer_events(false);
+        let label = app.new_view::<text::Text>();
+        label.set_single_line_mode(true);
+        label.set_property_default(text::Size(TEXT_SIZE));
+        label.remove_all_cursors();
+        let buttons = display::object::Instance::new();
+        let accept = Button::new(app, ACCEPT_LABEL);
+        let discard = Button::new(app, DISCARD_LABEL);
+        buttons.add_child(&accept);
+        buttons.add_child(&discard);
+        root.add_child(&background);
+        root.add_child(&label);
+        root.set_xy(Vector2(0.0, node::HEIGHT / 2.0) + PANEL_OFFSET);
+        Self { display_object, root, background, label, buttons, accept, discard }
+    }
+
+    fn update(&self, app: &Application, pending: bool, suggestion: &Option<ImString>) -> bool {
+        match (pending, suggestion) {
+            (_, Some(suggestion)) => {
+                self.label.set_content(suggestion.clone_ref());
+                self.root.add_child(&self.buttons);
+            }
+            (true, None) => {
+                self.label.set_content(app.translator().translate(LOADING_LABEL));
+                self.buttons.unset_parent();
+            }
+            (false, None) => {
+                self.root.unset_parent();
+                return false;
+            }
+        }
+        self.display_object.add_child(&self.root);
+        true
+    }
+
+    fn set_widths(&self, label_width: f32, accept_width: f32, discard_width: f32) {
+        let accept_width = self.accept.set_label_width(accept_width);
+        let discard_width = self.discard.set_label_width(discard_width);
+        let buttons_x = label_width + PADDING_X * 2.0;
+        self.accept.set_x(buttons_x);
+        self.discard.set_x(buttons_x + accept_width + PADDING_X);
+        let buttons_width = accept_width + discard_width + PADDING_X * 2.0;
+        let has_buttons = self.buttons.has_parent();
+        let width = buttons_x + if has_buttons { buttons_width } else { 0.0 };
+        self.background.set_size(Vector2(width, PANEL_HEIGHT));
+        self.label.set_xy(Vector2(PADDING_X, PANEL_HEIGHT / 2.0 + TEXT_SIZE / 2.0));
+        self.buttons.set_y(PANEL_HEIGHT / 2.0);
+    }
+}
+
+
+
+// =============================
+// === DescriptionSuggestion ===
+// =============================
+
+/// A panel attached above the top left corner of the node.
+#[derive(Clone, CloneRef, Debug, Deref, display::Object)]
+#[allow(missing_docs)]
+pub struct DescriptionSuggestion {
+    #[display_object]
+    model:   Rc<Model>,
+    #[deref]
+    pub frp: Frp,
+}
+
+impl DescriptionSuggestion {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let model = Rc::new(Model::new(app));
+        let frp = Frp::new();
+        Self { model, frp }.init(app)
+    }
+
+    fn init(self, app: &Application) -> Self {
+        let network = self.frp.network();
+        let input = &self.frp.private.input;
+        let out = &self.frp.private.output;
+        let model = &self.model;
+        let style = StyleWatchFrp::new(&app.display.default_scene.style_sheet);
+        let background_color = style.get_color(theme::background);
+        let button_color = style.get_color(theme::button_background);
+        let text_color = style.get_color(theme::text);
+        let accept_press = model.accept.background.on_event::<mouse::Down>();
+        let discard_press = model.discard.background.on_event::<mouse::Down>();
+
+        frp::extend! { network
+            init <- source_();
+            state <- all(&input.set_pending, &input.set_suggestion);
+            visible <- state.map(f!([app, model]((pending, suggestion))
+                model.update(&app, *pending, suggestion)
+            ));
+            out.visible <+ visible.on_change();
+
+            let accept_label_width = &model.accept.label.width;
+            let discard_label_width = &model.discard.label.width;
+            widths <- all3(&model.label.width, accept_label_width, discard_label_width);
+            // The buttons are attached or detached when the panel's state changes.
+            widths <- all(&widths, &visible)._0();
+            eval widths (((label, accept, discard)) model.set_widths(*label, *accept, *discard));
+
+            suggestion <- input.set_suggestion.unwrap();
+            has_suggestion <- input.set_suggestion.map(|suggestion| suggestion.is_some());
+            accept <- accept_press.filter(mouse::event::is_primary).constant(());
+            accept <- any(&accept, &input.accept).gate(&has_suggestion);
+            discard <- discard_press.filter(mouse::event::is_primary).constant(());
+            discard <- any(&discard, &input.discard).gate(&has_suggestion);
+            out.accepted <+ suggestion.sample(&accept);
+            out.discarded <+ discard;
+
+            background_color <- all(&background_color, &init)._0();
+            eval background_color ((color) model.background.set_color(*color));
+            button_color <- all(&button_color, &init)._0();
+            eval button_color ((color) {
+                model.accept.background.set_color(*color);
+                model.discard.background.set_color(*color);
+            });
+            text_color <- all(&text_color, &init)._0();
+            eval text_color ((color) {
+                model.label.set_property_default(color);
+                model.accept.label.set_property_default(color);
+                model.discard.label.set_property_default(color);
+            });
+        }
+
+        init.emit(());
+        self.frp.set_pending(false);
+        self.frp.set_suggestion(None);
+        self
+    }
+}
@@ -0,0 +1,234 @@
+//! The stubs replacing the edges of a node whose edges are hidden, reducing the clutter around the
+//! nodes with many connections. The stub of the incoming edges is attached above the node, and the
+//! stub of the outgoing ones below it. Each stub displays the number of the edges it replaces.
+//! Hovering any of the stubs is reported, so the Graph Editor can temporarily reveal the edges.
+
+use crate::prelude::*;
+use ensogl::display::shape::*;
+
+use crate::component::node;
+
+use enso_frp as frp;
+use ensogl::application::Application;
+use ensogl::control::io::mouse;
+use ensogl::data::color;
+use ensogl::display;
+use ensogl_component::text;
+use ensogl_hardcoded_theme::graph_editor::node::edge_stubs as theme;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// Text size of the edge count label.
+const TEXT_SIZE: f32 = 10.0;
+/// Horizontal padding between the edge count label and the badge border.
+const PADDING_X: f32 = 5.0;
+/// Height of the badge displaying the edge count.
+const BADGE_HEIGHT: f32 = 16.0;
+/// The length of the line between the node and the badge.
+const LINE_LENGTH: f32 = 10.0;
+/// The width of the line between the node and the badge.
+const LINE_WIDTH: f32 = 2.0;
+
+
+
+// ===================
+// === HiddenEdges ===
+// ===================
+
+/// The numbers of the node's hidden edges, displayed on its stubs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HiddenEdges {
+    /// The number of the edges connected to the node's input ports.
+    pub incoming: usize,
+    /// The number of the edges connected to the node's output.
+    pub outgoing: usize,
+}
+
+
+
+// ===========
+// === Frp ===
+// ===========
+
+ensogl::define_endpoints_2! {
+    Input {
+        /// Set the numbers of the hidden edges. `None` hides the stubs.
+        set_hidden_edges (Option<HiddenEdges>),
+        /// Set the size of the node the stubs are attached to.
+        set_node_size    (Vector2),
+    }
+    Output {
+        /// Whether the mouse is over any of the stubs.
+        hovered (bool),
+    }
+}
+
+
+
+// ============
+// === Stub ===
+// ============
+
+/// A single stub: a short line leading from the node to a badge with the edge count.
+#[derive(Clone, Debug, display::Object)]
+struct Stub {
+    display_object: display::object::Instance,
+    /// The visible part of the stub, attached only when there are edges to count.
+    root:           display::object::Instance,
+    line:           Rectangle,
+    background:     Rectangle,
+    label:          text::Text,
+    /// The direction the stub leads from the node: `1.0` upwards, or `-1.0` downwards.
+    direction:      f32,
+}
+
+impl Stub {
+    fn new(app: &Application, direction: f32) -> Self {
+        let display_object = display::object::Instance::new();
+        let root = display::object::Instance::new();
+        let line = Rectangle::new();
+        line.set_pointer_events(false);
+        let background = Rectangle::new();
+        background.set_corner_radius_max().set_pointer_events(true);
+        let label = app.new_view::<text::Text>();
+        label.set_single_line_mode(true);
+        label.set_property_default(text::Size(TEXT_SIZE));
+        label.remove_all_cursors();
+        root.add_child(&line);
+        root.add_child(&background);
+        root.add_child(&label);
+        Self { display_object, root, line, background, label, direction }
+    }
+
+    fn set_count(&self, count: usize) {
+        if count > 0 {
+            self.label.set_content(count.to_string());
+            self.display_object.add_child(&self.root);
+        } else {
+            self.root.unset_parent();
+        }
+    }
+
+    fn set_label_width(&self, label_width: f32) {
+        let width = label_width + PADDING_X * 2.0;
+        let line_y = if self.direction > 0.0 { 0.0 } else { -LINE_LENGTH };
+        self.line.set_size(Vector2(LINE_WIDTH, LINE_LENGTH));
+        self.line.set_xy(Vector2(-LINE_WIDTH / 2.0, line_y));
+        let badge_center_y = (LINE_LENGTH + BADGE_HEIGHT / 2.0) * self.direction;
+        self.background.set_size(Vector2(width, BADGE_HEIGHT));
+        self.background.set_xy(Vector2(-width / 2.0, badge_center_y - BADGE_HEIGHT / 2.0));
+        self.label.set_xy(Vector2(-label_width / 2.0, badge_center_y + TEXT_SIZE / 2.0));
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+#[derive(Clone, Debug, display::Object)]
+struct Model {
+    display_object: display::object::Instance,
+    incoming:       Stub,
+    outgoing:       Stub,
+}
+
+impl Model {
+    fn new(app: &Application) -> Self {
+        let display_object = display::object::Instance::new_named("EdgeStubs");
+        let incoming = Stub::new(app, 1.0);
+        let outgoing = Stub::new(app, -1.0);
+        display_object.add_child(&incoming);
+        display_object.add_child(&outgoing);
+        Self { display_object, incoming, outgoing }
+    }
+
+    fn set_hidden_edges(&self, hidden_edges: Option<HiddenEdges>) {
+        let hidden_edges = hidden_edges.unwrap_or_default();
+        self.incoming.set_count(hidden_edges.incoming);
+        self.outgoing.set_count(hidden_edges.outgoing);
+    }
+
+    /// Attach the stubs to the middle of the top and the bottom border of the node.
+    fn set_node_size(&self, size: Vector2) {
+        let top = node::HEIGHT / 2.0;
+        self.incoming.set_xy(Vector2(size.x / 2.0, top));
+        self.outgoing.set_xy(Vector2(size.x / 2.0, top - size.y));
+    }
+
+    fn set_colors(&self, line: color::Rgba, background: color::Rgba, text: color::Rgba) {
+        for stub in [&self.incoming, &self.outgoing] {
+            stub.line.set_color(line);
+            stub.background.set_color(background);
+            stub.label.set_property_default(text);
+        }
+    }
+}
+
+
+
+// =================
+// === EdgeStubs ===
+// =================
+
+/// The pair of edge stubs attached to the node.
+#[derive(Clone, CloneRef, Debug, Deref, display::Object)]
+#[allow(missing_docs)]
+pub struct EdgeStubs {
+    #[display_object]
+    model:   Rc<Model>,
+    #[deref]
+    pub frp: Frp,
+}
+
+impl EdgeStubs {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let model = Rc::new(Model::new(app));
+        let frp = Frp::new();
+        Self { model, frp }.init(app)
+    }
+
+    fn init(self, app: &Application) -> Self {
+        let network = self.frp.network();
+        let input = &self.frp.private.input;
+        let out = &self.frp.private.output;
+        let model = &self.model;
+        let style = StyleWatchFrp::new(&app.display.default_scene.style_sheet);
+        let line_color = style.get_color(theme::line);
+        let background_color = style.get_color(theme::background);
+        let text_color = style.get_color(theme::text);
+        let incoming_enter = model.incoming.background.on_event::<mouse::Enter>();
+        let incoming_leave = model.incoming.background.on_event::<mouse::Leave>();
+        let outgoing_enter = model.outgoing.background.on_event::<mouse::Enter>();
+        let outgoing_leave = model.outgoing.background.on_event::<mouse::Leave>();
+
+        frp::extend! { network
+            init <- source_();
+            eval input.set_hidden_edges ((hidden) model.set_hidden_edges(*hidden));
+            eval input.set_node_size ((size) model.set_node_size(*size));
+            eval model.incoming.label.width ((width) model.incoming.set_label_width(*width));
+            eval model.outgoing.label.width ((width) model.outgoing.set_label_width(*width));
+
+            incoming_hovered <- bool(&incoming_leave, &incoming_enter);
+            outgoing_hovered <- bool(&outgoing_leave, &outgoing_enter);
+            has_stubs <- input.set_hidden_edges.map(|hidden| hidden.is_some());
+            // The stubs may be hidden while hovered, without emitting the leave events.
+            hovered <- incoming_hovered || outgoing_hovered;
+            hovered <- hovered && has_stubs;
+            out.hovered <+ hovered.on_change();
+
+            colors <- all4(&line_color, &background_color, &text_color, &init);
+            eval colors (((line, background, text, _)) model.set_colors(*line, *background, *text));
+        }
+
+        init.emit(());
+        self.frp.set_hidden_edges(None);
+        self
+    }
+}
@@ -41,6 +41,8 @@ pub struct Error {
     pub message:    Rc<Option<String>>,
     /// Flag indicating that the error is propagated from another node visible on the scene.
     pub propagated: Immutable<bool>,
+    /// The stack trace of the error, starting with the innermost frame. Empty if unknown.
+    pub stack:      Rc<Vec<error_visualization::StackFrame>>,
 }
 
 impl Error {
@@ -50,6 +52,7 @@ impl Error {
         Some(error_visualization::Input {
             kind:    Some(*self.kind),
             message: self.message.as_ref().as_ref()?.clone(),
+            stack:   self.stack.as_ref().clone(),
         })
     }
 
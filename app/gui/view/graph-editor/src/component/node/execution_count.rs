@@ -0,0 +1,162 @@
+//! A badge displaying how many times the node was evaluated during the current run. Useful to spot
+//! accidental re-computation of expensive nodes. The badge is only shown in profiling view mode.
+
+use crate::prelude::*;
+use ensogl::display::shape::*;
+
+use crate::component::node;
+use crate::view;
+
+use enso_frp as frp;
+use ensogl::application::Application;
+use ensogl::display;
+use ensogl_component::text;
+use ensogl_hardcoded_theme::graph_editor::node::execution_count as theme;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// Text size of the counter label.
+const TEXT_SIZE: f32 = 10.0;
+/// Horizontal padding between the counter label and the badge border.
+const PADDING_X: f32 = 5.0;
+/// Height of the badge.
+const BADGE_HEIGHT: f32 = 16.0;
+/// Offset of the badge center from the top right corner of the node.
+const BADGE_OFFSET: Vector2 = Vector2(0.0, 4.0);
+
+
+
+// ===========
+// === Frp ===
+// ===========
+
+ensogl::define_endpoints_2! {
+    Input {
+        /// Set the number of times the node was evaluated in the current run. `None` means that no
+        /// evaluation was reported yet.
+        set_count     (Option<u64>),
+        set_view_mode (view::Mode),
+        /// Set the width of the node the badge is attached to.
+        set_node_width (f32),
+    }
+    Output {
+        /// Whether the badge is currently displayed.
+        visible (bool),
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+#[derive(Clone, Debug, display::Object)]
+struct Model {
+    display_object: display::object::Instance,
+    root:           display::object::Instance,
+    background:     Rectangle,
+    label:          text::Text,
+}
+
+impl Model {
+    fn new(app: &Application) -> Self {
+        let display_object = display::object::Instance::new_named("ExecutionCountBadge");
+        let root = display::object::Instance::new();
+        let background = Rectangle::new();
+        background.set_corner_radius_max().set_pointer_events(false);
+        let label = app.new_view::<text::Text>();
+        label.set_single_line_mode(true);
+        label.set_property_default(text::Size(TEXT_SIZE));
+        label.remove_all_cursors();
+        root.add_child(&background);
+        root.add_child(&label);
+        Self { display_object, root, background, label }
+    }
+
+    fn set_visibility(&self, visible: bool) {
+        if visible {
+            self.display_object.add_child(&self.root);
+        } else {
+            self.root.unset_parent();
+        }
+    }
+
+    fn set_label_width(&self, label_width: f32) -> f32 {
+        let width = label_width + PADDING_X * 2.0;
+        self.background.set_size(Vector2(width, BADGE_HEIGHT));
+        self.background.set_xy(Vector2(-width / 2.0, -BADGE_HEIGHT / 2.0));
+        self.label.set_xy(Vector2(-label_width / 2.0, TEXT_SIZE / 2.0));
+        width
+    }
+
+    fn set_position(&self, node_width: f32, badge_width: f32) {
+        let x = node_width - badge_width / 2.0 + BADGE_OFFSET.x;
+        let y = node::HEIGHT / 2.0 + BADGE_OFFSET.y;
+        self.root.set_xy(Vector2(x, y));
+    }
+}
+
+
+
+// ===========================
+// === ExecutionCountBadge ===
+// ===========================
+
+/// A small counter badge attached to the top right corner of the node.
+#[derive(Clone, CloneRef, Debug, Deref, display::Object)]
+#[allow(missing_docs)]
+pub struct ExecutionCountBadge {
+    #[display_object]
+    model:   Rc<Model>,
+    #[deref]
+    pub frp: Frp,
+}
+
+impl ExecutionCountBadge {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let model = Rc::new(Model::new(app));
+        let frp = Frp::new();
+        Self { model, frp }.init(app)
+    }
+
+    fn init(self, app: &Application) -> Self {
+        let network = self.frp.network();
+        let input = &self.frp.private.input;
+        let out = &self.frp.private.output;
+        let model = &self.model;
+        let style = StyleWatchFrp::new(&app.display.default_scene.style_sheet);
+        let background_color = style.get_color(theme::background);
+        let text_color = style.get_color(theme::text);
+
+        frp::extend! { network
+            init <- source_();
+            label <- input.set_count.unwrap().map(|count| format!("×{count}"));
+            eval label ((label) model.label.set_content(label));
+            is_profiling <- input.set_view_mode.map(|mode| mode.is_profiling());
+            has_count <- input.set_count.map(Option::is_some);
+            visible <- is_profiling && has_count;
+            out.visible <+ visible.on_change();
+            eval out.visible ((visible) model.set_visibility(*visible));
+
+            badge_width <- model.label.width.map(f!((w) model.set_label_width(*w)));
+            position <- all(&input.set_node_width, &badge_width);
+            eval position (((node_width, badge_width)) model.set_position(*node_width, *badge_width));
+
+            background_color <- all(&background_color, &init)._0();
+            eval background_color ((color) model.background.set_color(*color));
+            text_color <- all(&text_color, &init)._0();
+            eval text_color ((color) model.label.set_property_default(color));
+        }
+
+        init.emit(());
+        self.frp.set_count(None);
+        self.frp.set_view_mode(view::Mode::default());
+        self
+    }
+}
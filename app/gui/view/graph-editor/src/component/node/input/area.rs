@@ -241,14 +241,21 @@ impl Model {
         self.edit_mode_label.set_property_default(text::Size(TEXT_SIZE));
         self.edit_mode_label.remove_all_cursors();
 
-        let widgets_origin = Vector2(0.0, -NODE_HEIGHT / 2.0);
         let label_origin = Vector2(TEXT_OFFSET, TEXT_SIZE / 2.0);
-        self.widget_tree.set_xy(widgets_origin);
         self.edit_mode_label.set_xy(label_origin);
+        self.set_line_count(1);
         self.set_edit_mode(false);
         self
     }
 
+    /// Position the widget tree so that its first line stays in place, while the subsequent lines
+    /// of a wrapped expression extend downwards.
+    fn set_line_count(&self, line_count: usize) {
+        let extra_lines = line_count.saturating_sub(1) as f32;
+        let widgets_origin = Vector2(0.0, -NODE_HEIGHT / 2.0 - extra_lines * NODE_HEIGHT);
+        self.widget_tree.set_xy(widgets_origin);
+    }
+
     fn set_connections(&self, map: &HashMap<PortId, color::Lcha>) {
         self.widget_tree.set_connections(map);
     }
@@ -395,11 +402,18 @@ ensogl::define_endpoints_2! {
 
         /// Set the primary (background) and secondary (port) node colors.
         set_node_colors ((color::Lcha, color::Lcha)),
+
+        /// Set the maximum length of a displayed expression line, in bytes of code. Longer
+        /// expressions are wrapped into multiple lines at span-tree child boundaries. `None`
+        /// disables wrapping.
+        set_expression_wrap (Option<usize>),
     }
 
     Output {
         pointer_style       (cursor::Style),
         width               (f32),
+        /// The number of lines the expression is displayed in. Always `1` in edit mode.
+        line_count          (usize),
         /// Changes done when nodes is in edit mode.
         expression_edit     (ImString, Vec<Selection<Byte>>),
 
@@ -509,6 +523,11 @@ impl Area {
                 &widget_tree_width,
                 &padded_edit_label_width
             );
+            line_count <- all_with(&model.widget_tree.line_count, &set_editing,
+                |lines, editing| if *editing { 1 } else { (*lines).max(1) }
+            );
+            frp.private.output.line_count <+ line_count.on_change();
+            eval frp.line_count ((lines) model.set_line_count(*lines));
 
             // === Expression ===
 
@@ -548,6 +567,7 @@ impl Area {
             eval frp.set_expression_usage_type(((id,tp)) model.set_expression_usage_type(*id,tp.clone()));
             eval frp.set_disabled ((disabled) model.widget_tree.set_disabled(*disabled));
             eval frp.set_pending ((pending) model.widget_tree.set_pending(*pending));
+            eval frp.set_expression_wrap ((wrap) model.widget_tree.set_expression_wrap(*wrap));
             eval_ model.widget_tree.rebuild_required(model.rebuild_widget_tree_if_dirty());
             frp.private.output.widget_tree_rebuilt <+ model.widget_tree.on_rebuild_finished;
 
@@ -572,6 +592,11 @@ impl Area {
         self.model.widget_tree.get_port_display_object(port).is_some()
     }
 
+    /// The identifiers of all ports of the node.
+    pub fn port_ids(&self) -> Vec<PortId> {
+        self.model.widget_tree.port_ids()
+    }
+
     /// An offset from node position to a specific port.
     pub fn port_offset(&self, port: PortId) -> Vector2<f32> {
         let object = self.model.widget_tree.get_port_display_object(port);
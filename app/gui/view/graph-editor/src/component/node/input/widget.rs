@@ -151,6 +151,9 @@ ensogl::define_endpoints_2! {
         marked_dirty_sync (),
         /// The widget tree has been rebuilt. Its port structure has potentially been updated.
         on_rebuild_finished (),
+        /// The number of lines the expression is displayed in. Greater than one only when the
+        /// expression wrapping is enabled and the expression does not fit in a single line.
+        line_count (usize),
     }
 }
 
@@ -722,9 +725,17 @@ impl Tree {
         self.notify_dirty(self.model.set_pending(pending));
     }
 
+    /// Set the maximum length of a displayed expression line, in bytes of code. When set, the
+    /// top-level expression widgets are distributed into multiple lines at span-tree child
+    /// boundaries, so that each line fits within the limit. `None` disables wrapping.
+    pub fn set_expression_wrap(&self, max_line_length: Option<usize>) {
+        self.notify_dirty(self.model.set_expression_wrap(max_line_length));
+    }
+
     /// Rebuild tree if it has been marked as dirty. The dirty flag is marked whenever more data
     /// external to the span-tree is provided, using `set_config_override`, `set_usage_type`,
-    /// `set_connections`, `set_disabled`, or `set_pending` methods of the widget tree.
+    /// `set_connections`, `set_disabled`, `set_pending` or `set_expression_wrap` methods of the
+    /// widget tree.
     pub fn rebuild_tree_if_dirty(
         &self,
         tree: &span_tree::SpanTree,
@@ -748,13 +759,16 @@ impl Tree {
         layers: &GraphLayers,
         styles: &StyleWatchFrp,
     ) {
-        self.model.rebuild_tree(
+        let line_count = self.model.rebuild_tree(
             self.widgets_frp.clone_ref(),
             tree,
             node_expression,
             layers,
             styles,
         );
+        if line_count != self.frp.line_count.value() {
+            self.frp.private.output.line_count.emit(line_count);
+        }
         self.frp.private.output.on_rebuild_finished.emit(());
         debug!("Widget tree:\n{:?}", self.pretty_printer());
     }
@@ -765,6 +779,11 @@ impl Tree {
         self.model.with_port(port_id, |w| w.display_object().clone())
     }
 
+    /// Get the identifiers of all ports currently present in the tree.
+    pub fn port_ids(&self) -> Vec<PortId> {
+        self.model.ports_map.borrow().keys().copied().collect()
+    }
+
     /// Get hover shapes for all ports in the tree. Used in tests to manually dispatch mouse events.
     pub fn port_hover_shapes(&self) -> Vec<Rectangle> {
         let nodes = self.model.nodes_map.borrow();
@@ -936,21 +955,23 @@ pub struct EdgeData {
 
 #[derive(Debug, display::Object)]
 struct TreeModel {
-    app:            Application,
-    display_object: display::object::Instance,
+    app:             Application,
+    display_object:  display::object::Instance,
     /// A map from widget identity to the tree node and its index in the `hierarchy` vector.
-    nodes_map:      RefCell<HashMap<WidgetIdentity, TreeEntry>>,
+    nodes_map:       RefCell<HashMap<WidgetIdentity, TreeEntry>>,
     /// Hierarchy data for nodes, stored in node insertion order (effectively depth-first). It can
     /// be used to quickly find the parent of a node, or iterate over all children or descendants
     /// of a node.
-    hierarchy:      RefCell<Vec<NodeHierarchy>>,
-    ports_map:      RefCell<HashMap<PortId, WidgetIdentity>>,
-    override_map:   Rc<RefCell<HashMap<OverrideKey, Configuration>>>,
-    connected_map:  Rc<RefCell<HashMap<PortId, color::Lcha>>>,
-    usage_type_map: Rc<RefCell<HashMap<ast::Id, crate::Type>>>,
-    node_disabled:  Cell<bool>,
-    node_pending:   Cell<bool>,
-    tree_dirty:     Cell<bool>,
+    hierarchy:       RefCell<Vec<NodeHierarchy>>,
+    ports_map:       RefCell<HashMap<PortId, WidgetIdentity>>,
+    override_map:    Rc<RefCell<HashMap<OverrideKey, Configuration>>>,
+    connected_map:   Rc<RefCell<HashMap<PortId, color::Lcha>>>,
+    usage_type_map:  Rc<RefCell<HashMap<ast::Id, crate::Type>>>,
+    node_disabled:   Cell<bool>,
+    node_pending:    Cell<bool>,
+    /// Maximum length of a displayed expression line. See [`Tree::set_expression_wrap`].
+    expression_wrap: Cell<Option<usize>>,
+    tree_dirty:      Cell<bool>,
 }
 
 impl TreeModel {
@@ -970,6 +991,7 @@ impl TreeModel {
             display_object,
             node_disabled: default(),
             node_pending: default(),
+            expression_wrap: default(),
             nodes_map: default(),
             hierarchy: default(),
             ports_map: default(),
@@ -1026,6 +1048,12 @@ impl TreeModel {
         self.mark_dirty_flag(prev_pending != pending)
     }
 
+    /// Set the maximum expression line length. It may cause the tree to be marked as dirty.
+    fn set_expression_wrap(&self, max_line_length: Option<usize>) -> bool {
+        let prev_max_line_length = self.expression_wrap.replace(max_line_length);
+        self.mark_dirty_flag(prev_max_line_length != max_line_length)
+    }
+
     /// Get parent of a node under given pointer, if exists.
     #[allow(dead_code)]
     pub fn parent(&self, pointer: WidgetIdentity) -> Option<WidgetIdentity> {
@@ -1098,6 +1126,7 @@ impl TreeModel {
         }
     }
 
+    /// Rebuild the widget tree. Returns the number of lines the expression is displayed in.
     #[profile(Task)]
     fn rebuild_tree(
         &self,
@@ -1106,7 +1135,7 @@ impl TreeModel {
         node_expression: &str,
         layers: &GraphLayers,
         styles: &StyleWatchFrp,
-    ) {
+    ) -> usize {
         self.tree_dirty.set(false);
         let app = self.app.clone();
         let override_map = self.override_map.borrow();
@@ -1115,6 +1144,7 @@ impl TreeModel {
         let old_nodes = self.nodes_map.take();
         let node_disabled = self.node_disabled.get();
         let node_pending = self.node_pending.get();
+        let expression_wrap = self.expression_wrap.get();
 
         // Old hierarchy is not used during the rebuild, so we might as well reuse the allocation.
         let mut hierarchy = self.hierarchy.take();
@@ -1127,6 +1157,8 @@ impl TreeModel {
             frp,
             node_disabled,
             node_pending,
+            expression_wrap,
+            line_count: 1,
             node_expression,
             layers,
             styles,
@@ -1147,6 +1179,8 @@ impl TreeModel {
 
         let child = builder.child_widget(tree.root_ref(), default());
         self.display_object.replace_children(&[child.root_object]);
+        let line_count = builder.line_count;
+        self.display_object.set_size_y(NODE_HEIGHT * line_count as f32);
 
         self.nodes_map.replace(builder.new_nodes);
         self.hierarchy.replace(builder.hierarchy);
@@ -1156,6 +1190,7 @@ impl TreeModel {
             let (port_id, index) = v.assigned_port?;
             Some((port_id, WidgetIdentity { main: k, index }))
         }));
+        line_count
     }
 
     /// Perform an operation on a shared reference to a tree port under given pointer. When there is
@@ -1537,6 +1572,11 @@ struct TreeBuilder<'a> {
     frp:             WidgetsFrp,
     node_disabled:   bool,
     node_pending:    bool,
+    /// Maximum length of a displayed expression line. See [`Tree::set_expression_wrap`].
+    expression_wrap: Option<usize>,
+    /// The number of lines the expression is displayed in. Updated by widgets that wrap their
+    /// children into multiple lines.
+    line_count:      usize,
     node_expression: &'a str,
     layers:          &'a GraphLayers,
     styles:          &'a StyleWatchFrp,
@@ -4,7 +4,11 @@
 use super::prelude::*;
 use crate::prelude::*;
 
+use crate::component::node::input::area::NODE_HEIGHT;
+
+use enso_text as text;
 use span_tree::node::Kind;
+use text::index::Byte;
 
 
 
@@ -23,6 +27,9 @@ pub struct Widget {
     /// A temporary list of display object children to insert. Reused across reconfigurations to
     /// avoid allocations.
     children_vec:   SmallVec<[object::Instance; 4]>,
+    /// Row containers used when the expression is wrapped into multiple lines. Reused across
+    /// reconfigurations, so that the line objects are not recreated on every rebuild.
+    lines:          Vec<object::Instance>,
 }
 
 impl SpanWidget for Widget {
@@ -47,14 +54,118 @@ impl SpanWidget for Widget {
         let display_object = object::Instance::new_named("widget::Hierarchy");
         display_object.use_auto_layout();
         display_object.set_children_alignment_left_center().justify_content_center_y();
-        Self { display_object, children_vec: default() }
+        Self { display_object, children_vec: default(), lines: default() }
     }
 
     fn configure(&mut self, _: &Config, ctx: ConfigContext) {
         let level = ctx.info.nesting_level.next_if(ctx.span_node.kind.is_prefix_argument());
+        // Only the top-level expression is wrapped. Nested hierarchies are always kept in a single
+        // line, as they are already contained within a line of their parent.
+        let is_root = ctx.span_node.crumbs.is_empty();
+        let max_line_length = ctx.builder.expression_wrap.filter(|_| is_root);
+        let line_breaks = max_line_length.map_or_default(|max| {
+            let spans = ctx.span_node.clone().children_iter().map(|n| n.span());
+            line_breaks(spans, ctx.span_node.span().start, max)
+        });
+
         let iter = ctx.span_node.children_iter();
         self.children_vec.extend(iter.map(|n| ctx.builder.child_widget(n, level).root_object));
-        self.display_object.replace_children(&self.children_vec);
+        if line_breaks.is_empty() {
+            if !self.lines.is_empty() {
+                self.lines.clear();
+                self.display_object.set_row_flow().set_rows_reversed(false);
+                self.display_object.set_children_alignment_left_center();
+            }
+            self.display_object.replace_children(&self.children_vec);
+        } else {
+            self.configure_lines(&line_breaks);
+            ctx.builder.line_count = ctx.builder.line_count.max(self.lines.len());
+        }
         self.children_vec.clear();
     }
 }
+
+impl Widget {
+    /// Distribute already built children into separate row containers, starting a new line at
+    /// each of the given child indices. The lines are stacked vertically from top to bottom.
+    fn configure_lines(&mut self, line_breaks: &[usize]) {
+        let line_count = line_breaks.len() + 1;
+        let was_wrapped = !self.lines.is_empty();
+        self.lines.resize_with(line_count, || {
+            let line = object::Instance::new_named("widget::Hierarchy::line");
+            line.use_auto_layout();
+            line.set_children_alignment_left_center().justify_content_center_y();
+            line.set_size_y(NODE_HEIGHT);
+            line
+        });
+        let starts = iter::once(0).chain(line_breaks.iter().copied());
+        let ends = line_breaks.iter().copied().chain(iter::once(self.children_vec.len()));
+        for (line, (start, end)) in self.lines.iter().zip(starts.zip(ends)) {
+            let children = &self.children_vec[start..end];
+            // The first widget in a wrapped line should be aligned with the line start, not offset
+            // by the whitespace that separated it from the previous widget in the code.
+            if start > 0 {
+                if let Some(first) = children.first() {
+                    first.set_margin_left(0.0);
+                }
+            }
+            line.replace_children(children);
+        }
+        if !was_wrapped {
+            self.display_object.set_column_flow().set_rows_reversed(true);
+            self.display_object.set_children_alignment_left_top();
+        }
+        self.display_object.replace_children(&self.lines);
+    }
+}
+
+/// Compute the indices of children at which a new line should start, so that no line exceeds the
+/// given maximum length in bytes. The line length is measured from the start of the first child in
+/// the line to the end of the last one, using the span-tree offsets. A child longer than the limit
+/// is always placed in its own line, as the individual widgets are never split.
+fn line_breaks(
+    spans: impl IntoIterator<Item = text::Range<Byte>>,
+    line_start: Byte,
+    max_line_length: usize,
+) -> Vec<usize> {
+    let mut breaks = Vec::new();
+    let mut line_start = line_start;
+    let mut line_is_empty = true;
+    for (index, span) in spans.into_iter().enumerate() {
+        let line_length = span.end.value.saturating_sub(line_start.value);
+        if line_length > max_line_length && !line_is_empty {
+            breaks.push(index);
+            line_start = span.start;
+        }
+        line_is_empty = false;
+    }
+    breaks
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(start: usize, end: usize) -> text::Range<Byte> {
+        (Byte(start)..Byte(end)).into()
+    }
+
+    #[test]
+    fn breaking_lines_at_span_offsets() {
+        // Expression: `foo bar baz quux`.
+        let spans = [span(0, 3), span(4, 7), span(8, 11), span(12, 16)];
+        assert_eq!(line_breaks(spans, Byte(0), 100), Vec::<usize>::new());
+        assert_eq!(line_breaks(spans, Byte(0), 7), vec![2, 3]);
+        assert_eq!(line_breaks(spans, Byte(0), 8), vec![2]);
+        assert_eq!(line_breaks(spans, Byte(0), 11), vec![3]);
+        assert_eq!(line_breaks(spans, Byte(0), 3), vec![1, 2, 3]);
+        // Children longer than the limit are never split and do not produce empty lines.
+        assert_eq!(line_breaks(spans, Byte(0), 1), vec![1, 2, 3]);
+    }
+}
@@ -437,6 +437,11 @@ impl Area {
         }
     }
 
+    /// Get the code of the node's pattern, e.g. `table` for the node `table = Table.new`.
+    pub fn pattern(&self) -> Option<String> {
+        self.model.expression.borrow().code.clone()
+    }
+
     #[allow(missing_docs)] // FIXME[everyone] All pub functions should have docs.
     pub fn whole_expr_id(&self) -> Option<ast::Id> {
         self.model.expression.borrow().whole_expr_id
@@ -0,0 +1,159 @@
+//! A dashed line drawn along the output port of a node whose value is stale, i.e. computed before
+//! the node or one of its upstream nodes was edited. The value of such node should not be trusted
+//! until the node is recomputed.
+
+use crate::prelude::*;
+use ensogl::display::shape::*;
+
+use crate::component::node;
+
+use enso_frp as frp;
+use ensogl::application::Application;
+use ensogl::data::color;
+use ensogl::display;
+use ensogl_hardcoded_theme::graph_editor::node::stale as theme;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The length of a single dash.
+const DASH_LENGTH: f32 = 6.0;
+/// The gap between the neighbouring dashes.
+const DASH_GAP: f32 = 4.0;
+/// The thickness of the dashes.
+const DASH_WIDTH: f32 = 2.0;
+/// The distance between the bottom border of the node and the dashed line.
+const OFFSET_Y: f32 = 4.0;
+
+
+
+// ===========
+// === Frp ===
+// ===========
+
+ensogl::define_endpoints_2! {
+    Input {
+        /// Set whether the node's value is stale.
+        set_stale     (bool),
+        /// Set the size of the node the line is attached to.
+        set_node_size (Vector2),
+    }
+    Output {
+        /// Whether the dashed line is currently displayed.
+        visible (bool),
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+#[derive(Clone, Debug, display::Object)]
+struct Model {
+    display_object: display::object::Instance,
+    /// The dashed line, attached only when the value is stale.
+    root:           display::object::Instance,
+    dashes:         RefCell<Vec<Rectangle>>,
+    color:          Cell<color::Rgba>,
+}
+
+impl Model {
+    fn new() -> Self {
+        let display_object = display::object::Instance::new_named("StaleIndicator");
+        let root = display::object::Instance::new();
+        let dashes = default();
+        let color = default();
+        Self { display_object, root, dashes, color }
+    }
+
+    fn set_visibility(&self, visible: bool) {
+        if visible {
+            self.display_object.add_child(&self.root);
+        } else {
+            self.root.unset_parent();
+        }
+    }
+
+    /// Lay out the dashes along the straight part of the node's bottom border, between its rounded
+    /// corners.
+    fn set_node_size(&self, size: Vector2) {
+        let length = (size.x - node::CORNER_RADIUS * 2.0).max(0.0);
+        let count = ((length + DASH_GAP) / (DASH_LENGTH + DASH_GAP)).floor() as usize;
+        let dashes_length = count as f32 * (DASH_LENGTH + DASH_GAP) - DASH_GAP;
+        let start_x = (size.x - dashes_length) / 2.0;
+        let y = node::HEIGHT / 2.0 - size.y - OFFSET_Y - DASH_WIDTH / 2.0;
+        let mut dashes = self.dashes.borrow_mut();
+        while dashes.len() < count {
+            let dash = Rectangle::new();
+            dash.set_corner_radius_max().set_pointer_events(false);
+            dash.set_size(Vector2(DASH_LENGTH, DASH_WIDTH));
+            dash.set_color(self.color.get());
+            self.root.add_child(&dash);
+            dashes.push(dash);
+        }
+        dashes.truncate(count);
+        for (index, dash) in dashes.iter().enumerate() {
+            dash.set_xy(Vector2(start_x + index as f32 * (DASH_LENGTH + DASH_GAP), y));
+        }
+    }
+
+    fn set_color(&self, color: color::Rgba) {
+        self.color.set(color);
+        for dash in self.dashes.borrow().iter() {
+            dash.set_color(color);
+        }
+    }
+}
+
+
+
+// ======================
+// === StaleIndicator ===
+// ======================
+
+/// The dashed line attached below the node.
+#[derive(Clone, CloneRef, Debug, Deref, display::Object)]
+#[allow(missing_docs)]
+pub struct StaleIndicator {
+    #[display_object]
+    model:   Rc<Model>,
+    #[deref]
+    pub frp: Frp,
+}
+
+impl StaleIndicator {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let model = Rc::new(Model::new());
+        let frp = Frp::new();
+        Self { model, frp }.init(app)
+    }
+
+    fn init(self, app: &Application) -> Self {
+        let network = self.frp.network();
+        let input = &self.frp.private.input;
+        let out = &self.frp.private.output;
+        let model = &self.model;
+        let style = StyleWatchFrp::new(&app.display.default_scene.style_sheet);
+        let dash_color = style.get_color(theme::dash);
+
+        frp::extend! { network
+            init <- source_();
+            out.visible <+ input.set_stale.on_change();
+            eval out.visible ((visible) model.set_visibility(*visible));
+            eval input.set_node_size ((size) model.set_node_size(*size));
+
+            dash_color <- all(&dash_color, &init)._0();
+            eval dash_color ((color) model.set_color(*color));
+        }
+
+        init.emit(());
+        self.frp.set_stale(false);
+        self
+    }
+}
@@ -0,0 +1,270 @@
+//! A chip attached to the node's output port, displaying a short preview of the most recent value
+//! of the node: the truncated text of the value, or the dimensions of a table. The data is provided
+//! by a lightweight preprocessor, distinct from the preprocessors of the full visualizations.
+
+use crate::prelude::*;
+use ensogl::display::shape::*;
+
+use crate::component::visualization;
+use crate::component::visualization::instance::PreprocessorConfiguration;
+
+use enso_frp as frp;
+use ensogl::application::Application;
+use ensogl::data::color;
+use ensogl::display;
+use ensogl_component::text;
+use ensogl_hardcoded_theme::graph_editor::node::value_preview as theme;
+use serde::Deserialize;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The module containing the value preview preprocessor.
+const PREPROCESSOR_MODULE: &str = "Standard.Visualization.Preprocessor";
+/// The method name of the value preview preprocessor.
+const PREPROCESSOR_METHOD: &str = "value_preview_preprocessor";
+/// The maximum number of characters of the displayed text. Longer texts are truncated.
+pub const MAX_TEXT_LENGTH: usize = 16;
+/// Text size of the preview label.
+const TEXT_SIZE: f32 = 10.0;
+/// Horizontal padding between the preview label and the chip border.
+const PADDING_X: f32 = 5.0;
+/// Height of the chip.
+const CHIP_HEIGHT: f32 = 16.0;
+/// Offset of the chip's left edge from the node's bottom right corner.
+const CHIP_OFFSET: Vector2 = Vector2(6.0, 0.0);
+
+
+
+// ====================
+// === Preprocessor ===
+// ====================
+
+/// Get the configuration of the value preview preprocessor.
+pub fn preprocessor() -> PreprocessorConfiguration {
+    PreprocessorConfiguration::new(PREPROCESSOR_MODULE, PREPROCESSOR_METHOD, Vec::<String>::new())
+}
+
+/// Get the metadata of the visualization attached to provide the value previews.
+pub fn metadata() -> visualization::Metadata {
+    visualization::Metadata { preprocessor: preprocessor() }
+}
+
+/// The data produced by the value preview preprocessor.
+#[derive(Clone, Debug, Default, Deserialize)]
+struct Summary {
+    rows:    Option<usize>,
+    columns: Option<usize>,
+    text:    Option<String>,
+}
+
+impl Summary {
+    fn label(&self) -> Option<String> {
+        match (self.rows, self.columns, &self.text) {
+            (Some(rows), Some(columns), _) => Some(format!("{rows} × {columns}")),
+            (Some(rows), None, _) => Some(format!("{rows} rows")),
+            (None, _, Some(text)) => Some(truncated(text)),
+            (None, _, None) => None,
+        }
+    }
+}
+
+/// Format the value preview preprocessor data as the chip's label. Returns [`None`] if the data
+/// cannot be previewed.
+pub fn format(data: &visualization::Data) -> Option<ImString> {
+    let summary = data.as_json().ok()?.deserialize::<Summary>().ok()?;
+    summary.label().map(Into::into)
+}
+
+fn truncated(text: &str) -> String {
+    let line = text.split_whitespace().collect_vec().join(" ");
+    if line.chars().count() > MAX_TEXT_LENGTH {
+        let mut truncated = line.chars().take(MAX_TEXT_LENGTH - 1).collect::<String>();
+        truncated.push('…');
+        truncated
+    } else {
+        line
+    }
+}
+
+
+
+// ===========
+// === Frp ===
+// ===========
+
+ensogl::define_endpoints_2! {
+    Input {
+        /// Set the label of the preview. `None` means that no value was received yet.
+        set_preview    (Option<ImString>),
+        /// Set whether the value previews are enabled.
+        set_enabled    (bool),
+        /// Set the position of the node's bottom right corner, to which the chip is attached.
+        set_anchor     (Vector2),
+        /// Set whether the previewed value is stale. Stale previews are desaturated.
+        set_stale      (bool),
+    }
+    Output {
+        /// Whether the chip is currently displayed.
+        visible (bool),
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+#[derive(Clone, Debug, display::Object)]
+struct Model {
+    display_object: display::object::Instance,
+    root:           display::object::Instance,
+    background:     Rectangle,
+    label:          text::Text,
+}
+
+impl Model {
+    fn new(app: &Application) -> Self {
+        let display_object = display::object::Instance::new_named("ValuePreviewChip");
+        let root = display::object::Instance::new();
+        let background = Rectangle::new();
+        background.set_corner_radius_max().set_pointer_events(false);
+        let label = app.new_view::<text::Text>();
+        label.set_single_line_mode(true);
+        label.set_property_default(text::Size(TEXT_SIZE));
+        label.remove_all_cursors();
+        root.add_child(&background);
+        root.add_child(&label);
+        Self { display_object, root, background, label }
+    }
+
+    fn set_visibility(&self, visible: bool) {
+        if visible {
+            self.display_object.add_child(&self.root);
+        } else {
+            self.root.unset_parent();
+        }
+    }
+
+    fn set_label_width(&self, label_width: f32) -> f32 {
+        let width = label_width + PADDING_X * 2.0;
+        self.background.set_size(Vector2(width, CHIP_HEIGHT));
+        self.background.set_xy(Vector2(-width / 2.0, -CHIP_HEIGHT / 2.0));
+        self.label.set_xy(Vector2(-label_width / 2.0, TEXT_SIZE / 2.0));
+        width
+    }
+
+    fn set_position(&self, anchor: Vector2, chip_width: f32) {
+        let x = anchor.x + CHIP_OFFSET.x + chip_width / 2.0;
+        let y = anchor.y + CHIP_OFFSET.y;
+        self.root.set_xy(Vector2(x, y));
+    }
+}
+
+/// Desaturate the color of the stale preview.
+fn desaturated_if(color: color::Rgba, stale: bool) -> color::Rgba {
+    if stale {
+        color::Lcha::from(color).to_grayscale().into()
+    } else {
+        color
+    }
+}
+
+
+
+// ========================
+// === ValuePreviewChip ===
+// ========================
+
+/// A small chip attached to the node's output port, displaying the preview of the node's value.
+#[derive(Clone, CloneRef, Debug, Deref, display::Object)]
+#[allow(missing_docs)]
+pub struct ValuePreviewChip {
+    #[display_object]
+    model:   Rc<Model>,
+    #[deref]
+    pub frp: Frp,
+}
+
+impl ValuePreviewChip {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let model = Rc::new(Model::new(app));
+        let frp = Frp::new();
+        Self { model, frp }.init(app)
+    }
+
+    fn init(self, app: &Application) -> Self {
+        let network = self.frp.network();
+        let input = &self.frp.private.input;
+        let out = &self.frp.private.output;
+        let model = &self.model;
+        let style = StyleWatchFrp::new(&app.display.default_scene.style_sheet);
+        let background_color = style.get_color(theme::background);
+        let text_color = style.get_color(theme::text);
+
+        frp::extend! { network
+            init <- source_();
+            eval input.set_preview ((preview) if let Some(preview) = preview {
+                model.label.set_content(preview.to_string())
+            });
+            has_preview <- input.set_preview.map(Option::is_some);
+            visible <- input.set_enabled && has_preview;
+            out.visible <+ visible.on_change();
+            eval out.visible ((visible) model.set_visibility(*visible));
+
+            chip_width <- model.label.width.map(f!((w) model.set_label_width(*w)));
+            position <- all(&input.set_anchor, &chip_width);
+            eval position (((anchor, chip_width)) model.set_position(*anchor, *chip_width));
+
+            background_color <- all_with3(&background_color, &input.set_stale, &init,
+                |color, stale, _| desaturated_if(*color, *stale)
+            );
+            eval background_color ((color) model.background.set_color(*color));
+            text_color <- all_with3(&text_color, &input.set_stale, &init,
+                |color, stale, _| desaturated_if(*color, *stale)
+            );
+            eval text_color ((color) model.label.set_property_default(color));
+        }
+
+        init.emit(());
+        self.frp.set_preview(None);
+        self.frp.set_enabled(false);
+        self.frp.set_stale(false);
+        self
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(json: &str) -> Option<String> {
+        serde_json::from_str::<Summary>(json).unwrap().label()
+    }
+
+    #[test]
+    fn tables_are_summarized_by_dimensions() {
+        assert_eq!(label(r#"{"rows": 1200, "columns": 4}"#).as_deref(), Some("1200 × 4"));
+        assert_eq!(label(r#"{"rows": 7}"#).as_deref(), Some("7 rows"));
+    }
+
+    #[test]
+    fn long_texts_are_truncated() {
+        assert_eq!(label(r#"{"text": "42"}"#).as_deref(), Some("42"));
+        let long = label(r#"{"text": "The quick brown\nfox jumps over the lazy dog"}"#).unwrap();
+        assert_eq!(long, "The quick brown…");
+        assert_eq!(long.chars().count(), MAX_TEXT_LENGTH);
+        assert_eq!(label("{}"), None);
+    }
+}
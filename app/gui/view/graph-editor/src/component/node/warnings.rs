@@ -0,0 +1,320 @@
+//! A badge displaying the number of warnings attached to the node's value. Hovering the badge lists
+//! the warnings. Unlike errors, the warnings do not poison the nodes using the value, as the value
+//! is still valid.
+
+use crate::prelude::*;
+use ensogl::display::shape::*;
+
+use crate::component::node;
+
+use enso_frp as frp;
+use ensogl::application::Application;
+use ensogl::control::io::mouse;
+use ensogl::data::color;
+use ensogl::display;
+use ensogl_component::text;
+use ensogl_hardcoded_theme::graph_editor::node::warnings as theme;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// Text size of the badge label and the listing.
+const TEXT_SIZE: f32 = 10.0;
+/// Horizontal padding between the badge label and the badge border.
+const PADDING_X: f32 = 5.0;
+/// Height of the badge.
+const BADGE_HEIGHT: f32 = 16.0;
+/// Offset of the badge's left border from the bottom left corner of the node.
+const BADGE_OFFSET: Vector2 = Vector2(0.0, -4.0);
+/// Padding between the listing text and the listing border.
+const LISTING_PADDING: f32 = 6.0;
+/// Gap between the badge and the listing displayed below it.
+const LISTING_GAP: f32 = 4.0;
+/// The maximum number of warnings listed. The remaining ones are only counted.
+const MAX_LISTED: usize = 5;
+/// The listed messages longer than this many characters are truncated.
+const MAX_LINE_LENGTH: usize = 80;
+
+
+
+// ===============
+// === Warning ===
+// ===============
+
+/// A single warning attached to the node's value.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Warning {
+    /// The textual representation of the warning. The engine does not always provide it, e.g.
+    /// when the value has several warnings attached.
+    pub message: Option<ImString>,
+}
+
+/// The lines listing the warnings, displayed when the badge is hovered.
+fn listing(warnings: &[Warning]) -> Vec<String> {
+    let listed = warnings.iter().take(MAX_LISTED).map(|warning| {
+        let message = warning.message.as_ref().map(|message| message.as_str());
+        let first_line = message.and_then(|message| message.lines().next()).unwrap_or_default();
+        let first_line = first_line.trim();
+        if first_line.is_empty() {
+            "Warning without a description".to_owned()
+        } else if first_line.chars().count() > MAX_LINE_LENGTH {
+            let truncated = first_line.chars().take(MAX_LINE_LENGTH).collect::<String>();
+            format!("{truncated}…")
+        } else {
+            first_line.to_owned()
+        }
+    });
+    let hidden = warnings.len().saturating_sub(MAX_LISTED);
+    let more = (hidden > 0).then(|| format!("… and {hidden} more"));
+    listed.chain(more).collect()
+}
+
+
+
+// =======================
+// === WarningsSummary ===
+// =======================
+
+/// The numbers of the warnings in the whole graph.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WarningsSummary {
+    /// The number of all the warnings attached to the values of the nodes.
+    pub warnings: usize,
+    /// The number of the nodes with at least one warning.
+    pub nodes:    usize,
+}
+
+impl WarningsSummary {
+    /// Summarize the numbers of warnings of the nodes.
+    pub fn from_counts(counts: impl IntoIterator<Item = usize>) -> Self {
+        counts.into_iter().filter(|count| *count > 0).fold(default(), |summary: Self, count| Self {
+            warnings: summary.warnings + count,
+            nodes:    summary.nodes + 1,
+        })
+    }
+}
+
+
+
+// ===========
+// === Frp ===
+// ===========
+
+ensogl::define_endpoints_2! {
+    Input {
+        /// Set the warnings attached to the node's value. An empty list hides the badge.
+        set_warnings  (Rc<Vec<Warning>>),
+        /// Set the size of the node the badge is attached to.
+        set_node_size (Vector2),
+    }
+    Output {
+        /// Whether the warnings are listed, i.e. the badge is hovered.
+        listing_visible (bool),
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+#[derive(Clone, Debug, display::Object)]
+struct Model {
+    display_object:     display::object::Instance,
+    /// The visible part of the badge, attached only when there are warnings.
+    root:               display::object::Instance,
+    background:         Rectangle,
+    label:              text::Text,
+    /// The listing of the warnings, attached only when the badge is hovered.
+    listing:            display::object::Instance,
+    listing_background: Rectangle,
+    listing_label:      text::Text,
+}
+
+impl Model {
+    fn new(app: &Application) -> Self {
+        let layers = &app.display.default_scene.layers;
+        let display_object = display::object::Instance::new_named("WarningsBadge");
+        let root = display::object::Instance::new();
+        let background = Rectangle::new();
+        background.set_corner_radius_max().set_pointer_events(true);
+        let label = app.new_view::<text::Text>();
+        label.set_single_line_mode(true);
+        label.set_property_default(text::Size(TEXT_SIZE));
+        label.remove_all_cursors();
+        root.add_child(&background);
+        root.add_child(&label);
+        let listing = display::object::Instance::new();
+        let listing_background = Rectangle::new();
+        listing_background.set_corner_radius(LISTING_PADDING).set_pointer_events(false);
+        let listing_label = app.new_view::<text::Text>();
+        listing_label.set_property_default(text::Size(TEXT_SIZE));
+        listing_label.remove_all_cursors();
+        listing_label.set_xy(Vector2(LISTING_PADDING, -LISTING_PADDING));
+        listing.set_y(-BADGE_HEIGHT / 2.0 - LISTING_GAP);
+        listing.add_child(&listing_background);
+        listing.add_child(&listing_label);
+        layers.tooltip.add(&listing_background);
+        layers.tooltip_text.add(&listing_label);
+        Self { display_object, root, background, label, listing, listing_background, listing_label }
+    }
+
+    fn set_warnings(&self, warnings: &[Warning]) {
+        if warnings.is_empty() {
+            self.root.unset_parent();
+        } else {
+            self.label.set_content(format!("⚠ {}", warnings.len()));
+            self.listing_label.set_content(listing(warnings).join("\n"));
+            self.display_object.add_child(&self.root);
+        }
+    }
+
+    fn set_label_width(&self, label_width: f32) {
+        let width = label_width + PADDING_X * 2.0;
+        self.background.set_size(Vector2(width, BADGE_HEIGHT));
+        self.background.set_xy(Vector2(0.0, -BADGE_HEIGHT / 2.0));
+        self.label.set_xy(Vector2(PADDING_X, TEXT_SIZE / 2.0));
+    }
+
+    fn set_listing_text_size(&self, text_size: Vector2) {
+        let size = text_size + Vector2(LISTING_PADDING, LISTING_PADDING) * 2.0;
+        self.listing_background.set_size(size);
+        self.listing_background.set_y(-size.y);
+    }
+
+    /// Attach the badge to the bottom left corner of the node.
+    fn set_node_size(&self, size: Vector2) {
+        let bottom = node::HEIGHT / 2.0 - size.y;
+        self.root.set_xy(Vector2(BADGE_OFFSET.x, bottom + BADGE_OFFSET.y));
+    }
+
+    fn set_listing_visibility(&self, visible: bool) {
+        if visible {
+            self.root.add_child(&self.listing);
+        } else {
+            self.listing.unset_parent();
+        }
+    }
+
+    fn set_colors(&self, badge: (color::Rgba, color::Rgba), listing: (color::Rgba, color::Rgba)) {
+        let (background, text) = badge;
+        let (listing_background, listing_text) = listing;
+        self.background.set_color(background);
+        self.label.set_property_default(text);
+        self.listing_background.set_color(listing_background);
+        self.listing_label.set_property_default(listing_text);
+    }
+}
+
+
+
+// =====================
+// === WarningsBadge ===
+// =====================
+
+/// A badge attached to the bottom left corner of the node.
+#[derive(Clone, CloneRef, Debug, Deref, display::Object)]
+#[allow(missing_docs)]
+pub struct WarningsBadge {
+    #[display_object]
+    model:   Rc<Model>,
+    #[deref]
+    pub frp: Frp,
+}
+
+impl WarningsBadge {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let model = Rc::new(Model::new(app));
+        let frp = Frp::new();
+        Self { model, frp }.init(app)
+    }
+
+    fn init(self, app: &Application) -> Self {
+        let network = self.frp.network();
+        let input = &self.frp.private.input;
+        let out = &self.frp.private.output;
+        let model = &self.model;
+        let style = StyleWatchFrp::new(&app.display.default_scene.style_sheet);
+        let background_color = style.get_color(theme::background);
+        let text_color = style.get_color(theme::text);
+        let listing_background_color = style.get_color(theme::listing_background);
+        let listing_text_color = style.get_color(theme::listing_text);
+        let mouse_enter = model.background.on_event::<mouse::Enter>();
+        let mouse_leave = model.background.on_event::<mouse::Leave>();
+
+        frp::extend! { network
+            init <- source_();
+            eval input.set_warnings ((warnings) model.set_warnings(warnings));
+            eval input.set_node_size ((size) model.set_node_size(*size));
+            eval model.label.width ((width) model.set_label_width(*width));
+            listing_text_size <- all_with(&model.listing_label.width, &model.listing_label.height,
+                |width, height| Vector2(*width, *height)
+            );
+            eval listing_text_size ((size) model.set_listing_text_size(*size));
+
+            hovered <- bool(&mouse_leave, &mouse_enter);
+            has_warnings <- input.set_warnings.map(|warnings| !warnings.is_empty());
+            // The badge may be hidden while hovered, without emitting the leave event.
+            listing_visible <- hovered && has_warnings;
+            out.listing_visible <+ listing_visible.on_change();
+            eval out.listing_visible ((visible) model.set_listing_visibility(*visible));
+
+            badge_colors <- all(&background_color, &text_color);
+            listing_colors <- all(&listing_background_color, &listing_text_color);
+            colors <- all3(&badge_colors, &listing_colors, &init);
+            eval colors (((badge, listing, _)) model.set_colors(*badge, *listing));
+        }
+
+        init.emit(());
+        self.frp.set_warnings(default());
+        self
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn warning(message: Option<&str>) -> Warning {
+        Warning { message: message.map(ImString::new) }
+    }
+
+    #[test]
+    fn listing_warnings() {
+        let long = "a".repeat(100);
+        let warnings = [
+            warning(Some("Inexact float conversion\nat Main.main")),
+            warning(None),
+            warning(Some(&long)),
+        ];
+        let expected = [
+            "Inexact float conversion".to_owned(),
+            "Warning without a description".to_owned(),
+            format!("{}…", "a".repeat(MAX_LINE_LENGTH)),
+        ];
+        assert_eq!(listing(&warnings), expected);
+
+        let warnings = vec![warning(Some("Lossy conversion")); MAX_LISTED + 2];
+        let lines = listing(&warnings);
+        assert_eq!(lines.len(), MAX_LISTED + 1);
+        assert_eq!(lines.last().map(String::as_str), Some("… and 2 more"));
+    }
+
+    #[test]
+    fn summarizing_warnings() {
+        assert_eq!(WarningsSummary::from_counts([]), WarningsSummary::default());
+        let summary = WarningsSummary::from_counts([2, 0, 1, 0, 4]);
+        assert_eq!(summary, WarningsSummary { warnings: 7, nodes: 3 });
+    }
+}
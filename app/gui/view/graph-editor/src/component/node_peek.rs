@@ -0,0 +1,327 @@
+//! A small, read-only overlay displayed below a node, showing the graph of the method called by the
+//! node without entering it.
+//!
+//! The peeked graph is provided by the controller, as the Graph Editor knows only the displayed
+//! graph. The nodes are scaled down to fit the overlay and displayed as boxes with their
+//! expressions, connected by straight lines. The overlay is hidden when the user clicks anywhere
+//! outside of it.
+
+use crate::prelude::*;
+use ensogl::display::shape::*;
+
+use enso_frp as frp;
+use ensogl::application::Application;
+use ensogl::control::io::mouse;
+use ensogl::display;
+use ensogl_component::text;
+use ensogl_hardcoded_theme::graph_editor::node_peek as theme;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The maximum width of the peeked graph in the overlay, excluding padding.
+const MAX_WIDTH: f32 = 320.0;
+/// The maximum height of the peeked graph in the overlay, excluding padding.
+const MAX_HEIGHT: f32 = 240.0;
+/// The peeked graph is never displayed larger than this fraction of its original size.
+const MAX_SCALE: f32 = 0.5;
+const PADDING: f32 = 10.0;
+/// The distance between the bottom of the peeked node and the overlay.
+const NODE_GAP: f32 = 8.0;
+/// The size of a node box before scaling.
+const NODE_WIDTH: f32 = 200.0;
+const NODE_HEIGHT: f32 = 28.0;
+const NODE_CORNER_RADIUS: f32 = 14.0;
+/// The text size of the expressions before scaling.
+const TEXT_SIZE: f32 = 12.0;
+const CONNECTION_WIDTH: f32 = 1.5;
+/// The expressions longer than this many characters are truncated.
+const MAX_EXPRESSION_LENGTH: usize = 24;
+
+
+
+// ===================
+// === PeekedGraph ===
+// ===================
+
+/// A node of the peeked graph.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PeekedNode {
+    /// The node's expression.
+    pub expression: ImString,
+    /// The position of the node's top-left corner in its graph.
+    pub position:   Vector2,
+}
+
+impl PeekedNode {
+    fn label(&self) -> String {
+        let truncated = self.expression.chars().count() > MAX_EXPRESSION_LENGTH;
+        let mut label = self.expression.chars().take(MAX_EXPRESSION_LENGTH).collect::<String>();
+        if truncated {
+            label.push('…');
+        }
+        label
+    }
+}
+
+/// The graph of the method called by the peeked node.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PeekedGraph {
+    #[allow(missing_docs)]
+    pub nodes:       Vec<PeekedNode>,
+    /// The connections between the nodes, as pairs of the source and target node indices.
+    pub connections: Vec<(usize, usize)>,
+}
+
+
+
+// ==============
+// === Layout ===
+// ==============
+
+/// The placement of the peeked graph in the overlay. The graph is scaled down to fit within
+/// [`MAX_WIDTH`] and [`MAX_HEIGHT`], but never displayed larger than [`MAX_SCALE`] of its size.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Layout {
+    /// The scale of the displayed graph.
+    pub scale:          f32,
+    /// The size of the overlay, including padding.
+    pub size:           Vector2,
+    /// The positions of the nodes' top-left corners, relative to the overlay's top-left corner.
+    pub node_positions: Vec<Vector2>,
+}
+
+impl Layout {
+    /// Place the graph's nodes in the overlay.
+    pub fn new(graph: &PeekedGraph) -> Self {
+        let positions = graph.nodes.iter().map(|node| node.position);
+        let min = positions.clone().reduce(|a, b| a.inf(&b)).unwrap_or_default();
+        let max = positions.reduce(|a, b| a.sup(&b)).unwrap_or_default();
+        let extent = Vector2(max.x - min.x + NODE_WIDTH, max.y - min.y + NODE_HEIGHT);
+        let scale = (MAX_WIDTH / extent.x).min(MAX_HEIGHT / extent.y).min(MAX_SCALE);
+        let size = extent * scale + Vector2(PADDING, PADDING) * 2.0;
+        let node_positions = graph.nodes.iter().map(|node| {
+            let x = PADDING + (node.position.x - min.x) * scale;
+            let y = -PADDING - (max.y - node.position.y) * scale;
+            Vector2(x, y)
+        });
+        Self { scale, size, node_positions: node_positions.collect() }
+    }
+}
+
+
+
+// ===========
+// === Frp ===
+// ===========
+
+ensogl::define_endpoints_2! {
+    Input {
+        /// Display the graph in the overlay.
+        show (Rc<PeekedGraph>),
+        hide (),
+    }
+    Output {
+        visible (bool),
+        hovered (bool),
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+/// The shapes displaying the peeked graph. Recreated each time a graph is shown.
+#[derive(Debug, Default)]
+struct Shapes {
+    nodes:       Vec<Rectangle>,
+    labels:      Vec<text::Text>,
+    connections: Vec<Rectangle>,
+}
+
+#[derive(Debug, display::Object)]
+struct Model {
+    app:            Application,
+    display_object: display::object::Instance,
+    /// The visible part of the overlay, attached only when a graph is shown.
+    content:        display::object::Instance,
+    style:          StyleWatch,
+    background:     Rectangle,
+    shapes:         RefCell<Shapes>,
+}
+
+impl Model {
+    fn new(app: &Application) -> Self {
+        let app = app.clone_ref();
+        let scene = &app.display.default_scene;
+        let display_object = display::object::Instance::new_named("NodePeek");
+        let content = display::object::Instance::new();
+        let style = StyleWatch::new(&scene.style_sheet);
+        let background = Rectangle();
+        background.set_corner_radius(PADDING).set_pointer_events(true);
+        content.add_child(&background);
+        content.set_y(-NODE_GAP);
+        scene.layers.above_nodes.add(&content);
+        let shapes = default();
+        Self { app, display_object, content, style, background, shapes }
+    }
+
+    fn show(&self, graph: &PeekedGraph) {
+        let scene = &self.app.display.default_scene;
+        let layout = Layout::new(graph);
+        let scale = layout.scale;
+        let node_size = Vector2(NODE_WIDTH, NODE_HEIGHT) * scale;
+        self.background.set_color(self.style.get_color(theme::background));
+        self.background.set_size(layout.size);
+        self.background.set_y(-layout.size.y);
+
+        let node_color = self.style.get_color(theme::node);
+        let text_color = self.style.get_color(theme::text);
+        let connection_color = self.style.get_color(theme::connection);
+        let mut shapes = Shapes::default();
+        for (source, target) in &graph.connections {
+            let source = layout.node_positions.get(*source);
+            let target = layout.node_positions.get(*target);
+            if let (Some(source), Some(target)) = (source, target) {
+                let start = source + Vector2(node_size.x / 2.0, -node_size.y);
+                let end = target + Vector2(node_size.x / 2.0, 0.0);
+                let connection = Rectangle();
+                let direction = end - start;
+                connection.set_color(connection_color).set_pointer_events(false);
+                connection.set_size(Vector2(direction.norm(), CONNECTION_WIDTH));
+                connection.set_xy(start);
+                connection.set_rotation_z(direction.y.atan2(direction.x));
+                self.content.add_child(&connection);
+                shapes.connections.push(connection);
+            }
+        }
+        for (node, position) in graph.nodes.iter().zip(&layout.node_positions) {
+            let shape = Rectangle();
+            shape.set_corner_radius(NODE_CORNER_RADIUS * scale);
+            shape.set_color(node_color).set_pointer_events(false);
+            shape.set_size(node_size);
+            shape.set_xy(position - Vector2(0.0, node_size.y));
+            self.content.add_child(&shape);
+            shapes.nodes.push(shape);
+
+            let label = self.app.new_view::<text::Text>();
+            label.set_single_line_mode(true);
+            label.set_property_default(text::Size(TEXT_SIZE * scale));
+            label.set_property_default(text_color);
+            label.set_content(node.label());
+            let padding_x = node_size.y / 2.0;
+            label.set_xy(position + Vector2(padding_x, -(node_size.y - TEXT_SIZE * scale) / 2.0));
+            scene.layers.above_nodes_text.add(&label);
+            self.content.add_child(&label);
+            shapes.labels.push(label);
+        }
+        let old_shapes = self.shapes.replace(shapes);
+        for shape in old_shapes.nodes.iter().chain(&old_shapes.connections) {
+            shape.unset_parent();
+        }
+        for label in &old_shapes.labels {
+            label.unset_parent();
+        }
+        self.display_object.add_child(&self.content);
+    }
+
+    fn hide(&self) {
+        self.content.unset_parent();
+        let shapes = self.shapes.take();
+        for shape in shapes.nodes.iter().chain(&shapes.connections) {
+            shape.unset_parent();
+        }
+        for label in &shapes.labels {
+            label.unset_parent();
+        }
+    }
+}
+
+
+
+// ================
+// === NodePeek ===
+// ================
+
+/// The node peek overlay component. See the module docs. The overlay is placed below its origin,
+/// which should be set to the bottom-left corner of the peeked node.
+#[derive(Clone, CloneRef, Debug, Deref, display::Object)]
+#[allow(missing_docs)]
+pub struct NodePeek {
+    #[display_object]
+    model:   Rc<Model>,
+    #[deref]
+    pub frp: Frp,
+}
+
+impl NodePeek {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let model = Rc::new(Model::new(app));
+        let frp = Frp::new();
+        Self { model, frp }.init(app)
+    }
+
+    fn init(self, app: &Application) -> Self {
+        let network = self.frp.network();
+        let input = &self.frp.private.input;
+        let out = &self.frp.private.output;
+        let model = &self.model;
+        let scene = &app.display.default_scene;
+        let mouse_down = scene.on_event::<mouse::Down>();
+        let background_enter = model.background.on_event::<mouse::Enter>();
+        let background_leave = model.background.on_event::<mouse::Leave>();
+
+        frp::extend! { network
+            eval input.show ((graph) model.show(graph));
+            out.hovered <+ bool(&background_leave, &background_enter);
+            out.hovered <+ input.hide.constant(false);
+            clicked_outside <- mouse_down.gate(&out.visible).gate_not(&out.hovered);
+            hide <- any_(&input.hide, &clicked_outside);
+            eval_ hide (model.hide());
+            out.visible <+ bool(&hide, &input.show);
+        }
+        self
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(x: f32, y: f32) -> PeekedNode {
+        PeekedNode { expression: "operator1.foo".into(), position: Vector2(x, y) }
+    }
+
+    #[test]
+    fn scaling_down_the_peeked_graph() {
+        let graph = PeekedGraph { nodes: vec![node(0.0, 0.0), node(400.0, -100.0)], ..default() };
+        let layout = Layout::new(&graph);
+        assert_eq!(layout.scale, MAX_SCALE);
+        assert_eq!(layout.size, Vector2(320.0, 84.0));
+        assert_eq!(layout.node_positions, vec![Vector2(10.0, -10.0), Vector2(210.0, -60.0)]);
+
+        let graph = PeekedGraph { nodes: vec![node(0.0, 0.0), node(2200.0, 0.0)], ..default() };
+        let layout = Layout::new(&graph);
+        assert_eq!(layout.scale, 0.125);
+        assert_eq!(layout.size.x, MAX_WIDTH + 2.0 * PADDING);
+    }
+
+    #[test]
+    fn truncating_long_expressions() {
+        assert_eq!(node(0.0, 0.0).label(), "operator1.foo");
+        let long = PeekedNode { expression: "a".repeat(30).into(), ..default() };
+        assert_eq!(long.label(), format!("{}…", "a".repeat(MAX_EXPRESSION_LENGTH)));
+    }
+}
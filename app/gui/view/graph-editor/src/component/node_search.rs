@@ -0,0 +1,118 @@
+//! The search of the nodes of the displayed graph whose expressions or comments match a query.
+
+use crate::prelude::*;
+
+use crate::NodeId;
+
+
+
+// =================
+// === NodeMatch ===
+// =================
+
+/// The part of the node matching the query.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum MatchKind {
+    #[default]
+    Expression,
+    Comment,
+}
+
+/// A node matching the search query.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NodeMatch {
+    #[allow(missing_docs)]
+    pub node_id: NodeId,
+    #[allow(missing_docs)]
+    pub kind:    MatchKind,
+    /// The whole expression or comment containing the match.
+    pub text:    ImString,
+}
+
+
+
+// =================
+// === NodeIndex ===
+// =================
+
+/// The expressions and comments of the nodes, maintained by the Graph Editor.
+#[derive(Debug, Default)]
+pub struct NodeIndex {
+    expressions: RefCell<HashMap<NodeId, ImString>>,
+    comments:    RefCell<HashMap<NodeId, ImString>>,
+}
+
+impl NodeIndex {
+    /// Remember the node's expression.
+    pub fn set_expression(&self, node_id: NodeId, expression: &ImString) {
+        self.expressions.borrow_mut().insert(node_id, expression.clone());
+    }
+
+    /// The last remembered expression of the node.
+    pub fn expression(&self, node_id: NodeId) -> Option<ImString> {
+        self.expressions.borrow().get(&node_id).cloned()
+    }
+
+    /// Remember the node's comment.
+    pub fn set_comment(&self, node_id: NodeId, comment: &ImString) {
+        self.comments.borrow_mut().insert(node_id, comment.clone());
+    }
+
+    /// Forget the removed node.
+    pub fn forget(&self, node_id: NodeId) {
+        self.expressions.borrow_mut().remove(&node_id);
+        self.comments.borrow_mut().remove(&node_id);
+    }
+
+    /// The nodes whose expressions or comments contain the query, ignoring the letter case. The
+    /// expression matches are listed before the comment matches. An empty query matches nothing.
+    pub fn search(&self, query: &str) -> Vec<NodeMatch> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return default();
+        }
+        let find = |texts: &HashMap<NodeId, ImString>, kind| {
+            let matching = texts.iter().filter(|(_, text)| text.to_lowercase().contains(&query));
+            let matches = matching.map(|(node_id, text)| NodeMatch {
+                node_id: *node_id,
+                kind,
+                text: text.clone(),
+            });
+            matches.sorted_by_key(|m| m.text.clone()).collect_vec()
+        };
+        let mut matches = find(&self.expressions.borrow(), MatchKind::Expression);
+        matches.extend(find(&self.comments.borrow(), MatchKind::Comment));
+        matches
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ensogl::display;
+
+    #[test]
+    fn searching_nodes() {
+        let index = NodeIndex::default();
+        let first = NodeId(display::object::Id::from(1));
+        let second = NodeId(display::object::Id::from(2));
+        index.set_expression(first, &"Data.read 'sales.csv'".into());
+        index.set_expression(second, &"table.aggregate".into());
+        index.set_comment(second, &"Total sales per month".into());
+
+        let matches = index.search("SALES");
+        let found = matches.iter().map(|m| (m.node_id, m.kind)).collect_vec();
+        assert_eq!(found, vec![(first, MatchKind::Expression), (second, MatchKind::Comment)]);
+        assert!(index.search("  ").is_empty());
+
+        index.forget(second);
+        assert_eq!(index.search("sales").len(), 1);
+    }
+}
@@ -0,0 +1,270 @@
+//! Portals replace long edges with a pair of small markers: an output portal placed below the
+//! source node, and an input portal placed above the target port. All portals routing the value of
+//! the same output port share the number and the color, so the matching ones are easy to find even
+//! if they are far apart on the canvas.
+
+use crate::prelude::*;
+use ensogl::display::shape::*;
+
+use crate::EdgeEndpoint;
+use crate::EdgeId;
+
+use enso_frp as frp;
+use ensogl::application::Application;
+use ensogl::data::color;
+use ensogl::display;
+use ensogl_component::text;
+use ensogl_hardcoded_theme::graph_editor::portal as theme;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// Text size of the portal number.
+const TEXT_SIZE: f32 = 10.0;
+/// Horizontal padding between the portal number and the marker border.
+const PADDING_X: f32 = 5.0;
+/// Height of the marker.
+pub const MARKER_HEIGHT: f32 = 16.0;
+/// The hue increment between consecutive portals. The golden ratio conjugate spreads the hues of
+/// any number of portals evenly around the color wheel.
+const HUE_STEP: f32 = 0.618_034;
+
+
+
+// ================
+// === PortalId ===
+// ================
+
+/// The number identifying the portal pair, displayed on its markers.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct PortalId(pub usize);
+
+impl PortalId {
+    /// The hue of the portal markers' color.
+    pub fn hue(self) -> f32 {
+        (self.0 as f32 * HUE_STEP).fract()
+    }
+}
+
+impl Display for PortalId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+
+
+// ===============
+// === Portals ===
+// ===============
+
+/// The edges collapsed into portals, maintained by the Graph Editor. The edges having the same
+/// source share the portal number, which is released once all of them are expanded back.
+#[derive(Debug, Default)]
+pub struct Portals {
+    by_source: RefCell<HashMap<EdgeEndpoint, PortalId>>,
+    by_edge:   RefCell<HashMap<EdgeId, (EdgeEndpoint, PortalId)>>,
+}
+
+impl Portals {
+    /// Collapse the edge with the given source into a portal. Returns the portal number, reusing
+    /// the number of the other portals of the same source.
+    pub fn collapse(&self, edge_id: EdgeId, source: EdgeEndpoint) -> PortalId {
+        self.expand(edge_id);
+        let mut by_source = self.by_source.borrow_mut();
+        let portal = match by_source.get(&source) {
+            Some(portal) => *portal,
+            None => {
+                let used = by_source.values().copied().collect::<HashSet<_>>();
+                let free = (1..).map(PortalId).find(|id| !used.contains(id)).unwrap_or_default();
+                by_source.insert(source, free);
+                free
+            }
+        };
+        self.by_edge.borrow_mut().insert(edge_id, (source, portal));
+        portal
+    }
+
+    /// Expand the portal back into the edge. Returns `false` if the edge was not collapsed.
+    pub fn expand(&self, edge_id: EdgeId) -> bool {
+        let removed = self.by_edge.borrow_mut().remove(&edge_id);
+        if let Some((source, _)) = removed {
+            let by_edge = self.by_edge.borrow();
+            let source_still_used = by_edge.values().any(|(other, _)| *other == source);
+            if !source_still_used {
+                self.by_source.borrow_mut().remove(&source);
+            }
+        }
+        removed.is_some()
+    }
+
+    /// The portal the edge is collapsed into, if any.
+    pub fn portal_of(&self, edge_id: EdgeId) -> Option<PortalId> {
+        self.by_edge.borrow().get(&edge_id).map(|(_, portal)| *portal)
+    }
+}
+
+
+
+// ===========
+// === Frp ===
+// ===========
+
+ensogl::define_endpoints_2! {
+    Input {
+        /// Set the portal displayed by the marker. `None` hides the marker.
+        set_portal (Option<PortalId>),
+    }
+    Output {
+        /// The width of the marker.
+        width (f32),
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+#[derive(Clone, Debug, display::Object)]
+struct Model {
+    display_object: display::object::Instance,
+    root:           display::object::Instance,
+    background:     Rectangle,
+    label:          text::Text,
+}
+
+impl Model {
+    fn new(app: &Application) -> Self {
+        let display_object = display::object::Instance::new_named("PortalMarker");
+        let root = display::object::Instance::new();
+        let background = Rectangle::new();
+        background.set_corner_radius_max().set_pointer_events(false);
+        let label = app.new_view::<text::Text>();
+        label.set_single_line_mode(true);
+        label.set_property_default(text::Size(TEXT_SIZE));
+        label.remove_all_cursors();
+        root.add_child(&background);
+        root.add_child(&label);
+        Self { display_object, root, background, label }
+    }
+
+    fn set_portal(&self, portal: Option<PortalId>) {
+        if let Some(portal) = portal {
+            self.label.set_content(portal.to_string());
+            self.display_object.add_child(&self.root);
+        } else {
+            self.root.unset_parent();
+        }
+    }
+
+    fn set_label_width(&self, label_width: f32) -> f32 {
+        let width = (label_width + PADDING_X * 2.0).max(MARKER_HEIGHT);
+        self.background.set_size(Vector2(width, MARKER_HEIGHT));
+        self.background.set_xy(Vector2(-width / 2.0, -MARKER_HEIGHT / 2.0));
+        self.label.set_xy(Vector2(-label_width / 2.0, TEXT_SIZE / 2.0));
+        width
+    }
+}
+
+
+
+// ====================
+// === PortalMarker ===
+// ====================
+
+/// A marker of one end of the portal pair, centered at its origin.
+#[derive(Clone, CloneRef, Debug, Deref, display::Object)]
+#[allow(missing_docs)]
+pub struct PortalMarker {
+    #[display_object]
+    model:   Rc<Model>,
+    #[deref]
+    pub frp: Frp,
+}
+
+impl PortalMarker {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let model = Rc::new(Model::new(app));
+        let frp = Frp::new();
+        Self { model, frp }.init(app)
+    }
+
+    fn init(self, app: &Application) -> Self {
+        let network = self.frp.network();
+        let input = &self.frp.private.input;
+        let out = &self.frp.private.output;
+        let model = &self.model;
+        let style = StyleWatchFrp::new(&app.display.default_scene.style_sheet);
+        let lightness = style.get_number(theme::lightness);
+        let chroma = style.get_number(theme::chroma);
+        let text_color = style.get_color(theme::text);
+
+        frp::extend! { network
+            init <- source_();
+            eval input.set_portal ((portal) model.set_portal(*portal));
+            out.width <+ model.label.width.map(f!((w) model.set_label_width(*w)));
+
+            portal <- input.set_portal.unwrap();
+            tone <- all(&lightness, &chroma, &init);
+            background_color <- portal.all_with(&tone, |portal, (lightness, chroma, _)| {
+                color::Lcha::from(color::Lch::new(*lightness, *chroma, portal.hue()))
+            });
+            eval background_color ((color) model.background.set_color(color.into()));
+            text_color <- all(&text_color, &init)._0();
+            eval text_color ((color) model.label.set_property_default(color));
+        }
+
+        init.emit(());
+        self.frp.set_portal(None);
+        self
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeId;
+
+    fn endpoint(node: usize) -> EdgeEndpoint {
+        EdgeEndpoint::new(NodeId(display::object::Id::from(node)), default())
+    }
+
+    fn edge(id: usize) -> EdgeId {
+        EdgeId(display::object::Id::from(id))
+    }
+
+    #[test]
+    fn portals_of_the_same_source_share_the_number() {
+        let portals = Portals::default();
+        assert_eq!(portals.collapse(edge(1), endpoint(1)), PortalId(1));
+        assert_eq!(portals.collapse(edge(2), endpoint(2)), PortalId(2));
+        assert_eq!(portals.collapse(edge(3), endpoint(1)), PortalId(1));
+        assert_eq!(portals.portal_of(edge(3)), Some(PortalId(1)));
+        assert_eq!(portals.portal_of(edge(4)), None);
+    }
+
+    #[test]
+    fn numbers_are_released_when_all_portals_are_expanded() {
+        let portals = Portals::default();
+        portals.collapse(edge(1), endpoint(1));
+        portals.collapse(edge(2), endpoint(1));
+        portals.collapse(edge(3), endpoint(2));
+        assert!(portals.expand(edge(1)));
+        assert!(!portals.expand(edge(1)));
+        assert_eq!(portals.collapse(edge(4), endpoint(3)), PortalId(3));
+        assert!(portals.expand(edge(2)));
+        assert_eq!(portals.collapse(edge(5), endpoint(4)), PortalId(1));
+    }
+}
@@ -0,0 +1,286 @@
+//! The presence of the other users editing the same graph: their cursors, labeled with the users'
+//! names, and the outlines around the nodes they have selected. Each user has their own color, and
+//! the outlines are drawn outside the local selection, so both are visible at once.
+//!
+//! The presences are only displayed. They are provided by the controller through the
+//! `set_remote_presences` input of the Graph Editor, and never affect the local state.
+
+use crate::prelude::*;
+use ensogl::display::shape::*;
+
+use crate::selection::BoundingBox;
+use crate::NodeId;
+
+use enso_frp as frp;
+use ensogl::application::Application;
+use ensogl::data::color;
+use ensogl::display;
+use ensogl_component::text;
+use ensogl_hardcoded_theme::graph_editor::remote_presence as theme;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The diameter of the dot marking the cursor position.
+const CURSOR_SIZE: f32 = 10.0;
+/// Text size of the user name.
+const TEXT_SIZE: f32 = 11.0;
+/// Padding between the user name and the border of its label.
+const LABEL_PADDING: f32 = 4.0;
+/// The offset of the name label's top-left corner from the cursor position.
+const LABEL_OFFSET: Vector2 = Vector2(CURSOR_SIZE / 2.0, -CURSOR_SIZE / 2.0);
+/// The width of the selection outline.
+const OUTLINE_WIDTH: f32 = 2.0;
+/// The distance between a selected node and the outline of the first user selecting it. The
+/// outlines of the other users selecting the same node are drawn around it.
+const OUTLINE_PADDING: f32 = 6.0;
+const OUTLINE_CORNER_RADIUS: f32 = 16.0;
+
+
+
+// ======================
+// === RemotePresence ===
+// ======================
+
+/// The state of another user editing the same graph.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RemotePresence {
+    /// The name displayed next to the user's cursor.
+    pub name:           ImString,
+    /// The color of the user's cursor and selection outlines.
+    pub color:          color::Rgba,
+    /// The position of the user's cursor in the scene, or [`None`] if the cursor is not over the
+    /// graph.
+    pub cursor:         Option<Vector2>,
+    /// The nodes selected by the user.
+    pub selected_nodes: Vec<NodeId>,
+}
+
+/// Compute the outlines of the nodes selected by the remote users, given the bounding boxes of the
+/// nodes. The outlines around the same node are nested, in the order of the presences. The nodes
+/// without a bounding box (e.g. removed ones) are skipped.
+fn selection_outlines(
+    presences: &[RemotePresence],
+    node_bounding_box: impl Fn(NodeId) -> Option<BoundingBox>,
+) -> Vec<(BoundingBox, color::Rgba)> {
+    let mut outlines_per_node = HashMap::<NodeId, usize>::new();
+    let selections = presences.iter().flat_map(|presence| {
+        presence.selected_nodes.iter().unique().map(move |node_id| (*node_id, presence.color))
+    });
+    let selections = selections.collect_vec();
+    let outlines = selections.into_iter().filter_map(|(node_id, color)| {
+        let mut bounding_box = node_bounding_box(node_id)?;
+        let nesting = outlines_per_node.entry(node_id).or_default();
+        let padding = OUTLINE_PADDING + *nesting as f32 * OUTLINE_WIDTH;
+        *nesting += 1;
+        bounding_box.grow_x(2.0 * padding);
+        bounding_box.grow_y(2.0 * padding);
+        Some((bounding_box, color))
+    });
+    outlines.collect()
+}
+
+
+
+// ====================
+// === RemoteCursor ===
+// ====================
+
+/// The cursor of a remote user, with the user's name label.
+#[derive(Debug, display::Object)]
+struct RemoteCursor {
+    display_object:   display::object::Instance,
+    pointer:          Rectangle,
+    label_background: Rectangle,
+    label:            text::Text,
+    _network:         frp::Network,
+}
+
+impl RemoteCursor {
+    fn new(app: &Application, text_color: color::Rgba) -> Self {
+        let scene = &app.display.default_scene;
+        let display_object = display::object::Instance::new_named("RemoteCursor");
+        let pointer = Rectangle::new();
+        pointer.set_corner_radius_max().set_pointer_events(false);
+        pointer.set_size(Vector2(CURSOR_SIZE, CURSOR_SIZE));
+        pointer.set_xy(Vector2(-CURSOR_SIZE / 2.0, -CURSOR_SIZE / 2.0));
+        let label_background = Rectangle::new();
+        label_background.set_corner_radius_max().set_pointer_events(false);
+        let label = app.new_view::<text::Text>();
+        label.set_single_line_mode(true);
+        label.set_property_default(text::Size(TEXT_SIZE));
+        label.set_property_default(text_color);
+        label.remove_all_cursors();
+        display_object.add_child(&pointer);
+        display_object.add_child(&label_background);
+        display_object.add_child(&label);
+        scene.layers.above_nodes.add(&display_object);
+        scene.layers.above_nodes_text.add(&label);
+
+        let network = frp::Network::new("RemoteCursor");
+        frp::extend! { network
+            eval label.width ([label_background, label](width) {
+                let height = TEXT_SIZE + LABEL_PADDING * 2.0;
+                let size = Vector2(width + LABEL_PADDING * 2.0, height);
+                label_background.set_size(size);
+                label_background.set_xy(LABEL_OFFSET - Vector2(0.0, height));
+                label.set_xy(LABEL_OFFSET + Vector2(LABEL_PADDING, -LABEL_PADDING));
+            });
+        }
+        Self { display_object, pointer, label_background, label, _network: network }
+    }
+
+    fn set_presence(&self, presence: &RemotePresence) {
+        self.pointer.set_color(presence.color);
+        self.label_background.set_color(presence.color);
+        self.label.set_content(presence.name.to_string());
+        if let Some(position) = presence.cursor {
+            self.display_object.set_xy(position);
+        }
+    }
+}
+
+
+
+// =======================
+// === RemotePresences ===
+// =======================
+
+#[derive(Debug, display::Object)]
+struct Model {
+    app:            Application,
+    display_object: display::object::Instance,
+    /// The cursors of the presences, in the order of the presences. The cursor of a presence
+    /// without the cursor position is detached.
+    cursors:        RefCell<Vec<RemoteCursor>>,
+    outlines:       RefCell<Vec<Rectangle>>,
+    presences:      RefCell<Rc<Vec<RemotePresence>>>,
+    text_color:     Cell<color::Rgba>,
+}
+
+impl Model {
+    fn set_presences(&self, presences: Rc<Vec<RemotePresence>>) {
+        let mut cursors = self.cursors.borrow_mut();
+        cursors.truncate(presences.len());
+        while cursors.len() < presences.len() {
+            cursors.push(RemoteCursor::new(&self.app, self.text_color.get()));
+        }
+        for (cursor, presence) in cursors.iter().zip(presences.iter()) {
+            cursor.set_presence(presence);
+            if presence.cursor.is_some() {
+                self.display_object.add_child(cursor);
+            } else {
+                cursor.unset_parent();
+            }
+        }
+        *self.presences.borrow_mut() = presences;
+    }
+
+    fn update_outlines(&self, node_bounding_box: impl Fn(NodeId) -> Option<BoundingBox>) {
+        let presences = self.presences.borrow().clone_ref();
+        let outlines = selection_outlines(&presences, node_bounding_box);
+        let mut shapes = self.outlines.borrow_mut();
+        shapes.truncate(outlines.len());
+        while shapes.len() < outlines.len() {
+            let shape = Rectangle::new();
+            shape.set_color(color::Rgba::transparent()).set_pointer_events(false);
+            shape.set_border_and_inset(OUTLINE_WIDTH).set_corner_radius(OUTLINE_CORNER_RADIUS);
+            self.app.display.default_scene.layers.above_nodes.add(&shape);
+            self.display_object.add_child(&shape);
+            shapes.push(shape);
+        }
+        for (shape, (bounding_box, color)) in shapes.iter().zip(outlines) {
+            shape.set_border_color(color);
+            shape.set_size(Vector2(bounding_box.width(), bounding_box.height()));
+            shape.set_xy(Vector2(bounding_box.left(), bounding_box.bottom()));
+        }
+    }
+
+    fn set_text_color(&self, color: color::Rgba) {
+        self.text_color.set(color);
+        for cursor in self.cursors.borrow().iter() {
+            cursor.label.set_property_default(color);
+        }
+    }
+}
+
+/// The cursors and selection outlines of the remote users. See the module docs to learn more.
+#[derive(Clone, CloneRef, Debug, display::Object)]
+pub struct RemotePresences {
+    #[display_object]
+    model:    Rc<Model>,
+    _style:   StyleWatchFrp,
+    _network: frp::Network,
+}
+
+impl RemotePresences {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let model = Rc::new(Model {
+            app:            app.clone_ref(),
+            display_object: display::object::Instance::new_named("RemotePresences"),
+            cursors:        default(),
+            outlines:       default(),
+            presences:      default(),
+            text_color:     default(),
+        });
+        let style = StyleWatchFrp::new(&app.display.default_scene.style_sheet);
+        let text_color = style.get_color(theme::text);
+        let network = frp::Network::new("RemotePresences");
+        frp::extend! { network
+            eval text_color ((color) model.set_text_color(*color));
+        }
+        model.set_text_color(text_color.value());
+        Self { model, _style: style, _network: network }
+    }
+
+    /// Display the given presences, replacing the previous ones. The selection outlines are not
+    /// updated until [`Self::update_outlines`] is called.
+    pub fn set_presences(&self, presences: Rc<Vec<RemotePresence>>) {
+        self.model.set_presences(presences)
+    }
+
+    /// Place the selection outlines around the selected nodes, given their current bounding boxes.
+    /// Should be called after the presences or the nodes' bounding boxes change.
+    pub fn update_outlines(&self, node_bounding_box: impl Fn(NodeId) -> Option<BoundingBox>) {
+        self.model.update_outlines(node_bounding_box)
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: usize) -> NodeId {
+        NodeId(display::object::Id::from(id))
+    }
+
+    #[test]
+    fn nesting_selection_outlines() {
+        let red = color::Rgba::red();
+        let white = color::Rgba::white();
+        let presence = |color, nodes: &[usize]| RemotePresence {
+            color,
+            selected_nodes: nodes.iter().copied().map(node).collect(),
+            ..default()
+        };
+        let presences = [presence(red, &[1, 2, 2]), presence(white, &[1, 3])];
+        let node_bounding_box = |id: NodeId| {
+            (id != node(3)).as_some(BoundingBox::from_size(Vector2(100.0, 20.0)))
+        };
+        let outlines = selection_outlines(&presences, node_bounding_box);
+        let summary = outlines.iter().map(|(bbox, color)| (bbox.width(), *color)).collect_vec();
+        let first = 100.0 + 2.0 * OUTLINE_PADDING;
+        let second = first + 2.0 * OUTLINE_WIDTH;
+        assert_eq!(summary, vec![(first, red), (first, red), (second, white)]);
+    }
+}
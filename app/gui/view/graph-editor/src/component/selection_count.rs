@@ -0,0 +1,136 @@
+//! A badge displayed next to the mouse cursor during the area selection, counting the nodes the
+//! selection area will select and deselect once the mouse is released.
+
+use crate::prelude::*;
+use ensogl::display::shape::*;
+
+use enso_frp as frp;
+use ensogl::application::Application;
+use ensogl::display;
+use ensogl_component::text;
+use ensogl_hardcoded_theme::graph_editor::selection_count as theme;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+const TEXT_SIZE: f32 = 11.0;
+const HEIGHT: f32 = 18.0;
+const PADDING_X: f32 = 6.0;
+/// The offset of the badge's left-top corner from the mouse cursor.
+const CURSOR_OFFSET: Vector2 = Vector2(12.0, -12.0);
+
+
+
+// ===========
+// === Frp ===
+// ===========
+
+ensogl::define_endpoints_2! {
+    Input {
+        /// Set the label of the badge. `None` hides the badge.
+        set_label (Option<ImString>),
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+#[derive(Clone, Debug, display::Object)]
+struct Model {
+    display_object: display::object::Instance,
+    /// The visible part of the badge, attached only when the label is set.
+    badge:          display::object::Instance,
+    background:     Rectangle,
+    label:          text::Text,
+}
+
+impl Model {
+    fn new(app: &Application) -> Self {
+        let scene = &app.display.default_scene;
+        let display_object = display::object::Instance::new_named("SelectionCount");
+        let badge = display::object::Instance::new();
+        let background = Rectangle();
+        background.set_corner_radius_max().set_pointer_events(false);
+        background.set_y(-HEIGHT);
+        let label = app.new_view::<text::Text>();
+        label.set_single_line_mode(true);
+        label.set_property_default(text::Size(TEXT_SIZE));
+        label.remove_all_cursors();
+        label.set_xy(Vector2(PADDING_X, -(HEIGHT - TEXT_SIZE) / 2.0));
+        badge.add_child(&background);
+        badge.add_child(&label);
+        scene.layers.tooltip.add(&background);
+        scene.layers.tooltip_text.add(&label);
+        Self { display_object, badge, background, label }
+    }
+
+    fn set_label(&self, label: &Option<ImString>) {
+        match label {
+            Some(label) => {
+                self.label.set_content(label.clone_ref());
+                self.display_object.add_child(&self.badge);
+            }
+            None => self.badge.unset_parent(),
+        }
+    }
+
+    fn set_label_width(&self, width: f32) {
+        self.background.set_size(Vector2(width + PADDING_X * 2.0, HEIGHT));
+    }
+}
+
+
+
+// ======================
+// === SelectionCount ===
+// ======================
+
+/// The selection count badge. See the module docs. It should be placed in the scene's coordinates.
+#[derive(Clone, CloneRef, Debug, Deref, display::Object)]
+#[allow(missing_docs)]
+pub struct SelectionCount {
+    #[display_object]
+    model:   Rc<Model>,
+    #[deref]
+    pub frp: Frp,
+}
+
+impl SelectionCount {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let model = Rc::new(Model::new(app));
+        let frp = Frp::new();
+        Self { model, frp }.init(app)
+    }
+
+    fn init(self, app: &Application) -> Self {
+        let network = self.frp.network();
+        let input = &self.frp.private.input;
+        let model = &self.model;
+        let style = StyleWatchFrp::new(&app.display.default_scene.style_sheet);
+        let background_color = style.get_color(theme::background);
+        let text_color = style.get_color(theme::text);
+
+        frp::extend! { network
+            init <- source_();
+            eval input.set_label ((label) model.set_label(label));
+            eval model.label.width ((width) model.set_label_width(*width));
+            position <- app.cursor.frp.scene_position.map(|position| position.xy() + CURSOR_OFFSET);
+            eval position ((position) model.badge.set_xy(*position));
+
+            background_color <- all(&background_color, &init)._0();
+            eval background_color ((color) model.background.set_color(*color));
+            text_color <- all(&text_color, &init)._0();
+            eval text_color ((color) model.label.set_property_default(color));
+        }
+
+        init.emit(());
+        self
+    }
+}
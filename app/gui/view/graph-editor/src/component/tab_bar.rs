@@ -0,0 +1,462 @@
+//! A bar of tabs at the top of the scene, one for each method graph opened in the Graph Editor.
+//!
+//! The tab bar only tracks the tabs; the displayed graph is swapped by the controller in reaction
+//! to the `tab_selected` output. A tab may be closed with its close button or with the middle mouse
+//! button, and dragged along the bar to change the order of the tabs.
+
+use crate::prelude::*;
+use ensogl::display::shape::*;
+
+use enso_frp as frp;
+use ensogl::application::Application;
+use ensogl::control::io::mouse;
+use ensogl::display;
+use ensogl::display::camera::Camera2d;
+use ensogl_component::text;
+use ensogl_hardcoded_theme::graph_editor::tab_bar as theme;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The width of a single tab.
+pub const TAB_WIDTH: f32 = 160.0;
+/// The height of the tab bar.
+pub const HEIGHT: f32 = 28.0;
+const TEXT_SIZE: f32 = 12.0;
+const PADDING_X: f32 = 10.0;
+/// The size of the close button area at the right side of a tab.
+const CLOSE_BUTTON_SIZE: f32 = 20.0;
+/// The distance the pointer must travel with a pressed tab to start reordering the tabs.
+const DRAG_THRESHOLD: f32 = 4.0;
+
+
+
+// =============
+// === TabId ===
+// =============
+
+/// The identifier of a tab, assigned when the tab is opened.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct TabId(pub usize);
+
+
+
+// ===============
+// === TabList ===
+// ===============
+
+/// The opened tabs in their display order, together with the selected one.
+#[derive(Clone, Debug, Default)]
+pub struct TabList {
+    tabs:     Vec<(TabId, ImString)>,
+    selected: Option<TabId>,
+    next_id:  usize,
+}
+
+impl TabList {
+    /// Add a tab at the end of the bar and select it. Returns the id of the new tab.
+    pub fn open(&mut self, label: ImString) -> TabId {
+        let id = TabId(self.next_id);
+        self.next_id += 1;
+        self.tabs.push((id, label));
+        self.selected = Some(id);
+        id
+    }
+
+    /// Select the tab. Returns `false` if there is no such tab or it is already selected.
+    pub fn select(&mut self, id: TabId) -> bool {
+        let changed = self.contains(id) && self.selected != Some(id);
+        if changed {
+            self.selected = Some(id);
+        }
+        changed
+    }
+
+    /// Remove the tab. If it was selected, its right neighbour (or the left one, for the last tab)
+    /// becomes selected. Returns `false` if there is no such tab.
+    pub fn close(&mut self, id: TabId) -> bool {
+        let index = self.index_of(id);
+        if let Some(index) = index {
+            self.tabs.remove(index);
+            if self.selected == Some(id) {
+                let neighbour = index.min(self.tabs.len().saturating_sub(1));
+                self.selected = self.tabs.get(neighbour).map(|(id, _)| *id);
+            }
+        }
+        index.is_some()
+    }
+
+    /// Change the label of the tab.
+    pub fn set_label(&mut self, id: TabId, label: ImString) {
+        if let Some(index) = self.index_of(id) {
+            self.tabs[index].1 = label;
+        }
+    }
+
+    /// Move the tab to the given position, clamped to the number of tabs. Returns `false` if the
+    /// order did not change.
+    pub fn move_to(&mut self, id: TabId, index: usize) -> bool {
+        let current = self.index_of(id);
+        let index = index.min(self.tabs.len().saturating_sub(1));
+        let changed = current.map_or(false, |current| current != index);
+        if let Some(current) = current.filter(|_| changed) {
+            let tab = self.tabs.remove(current);
+            self.tabs.insert(index, tab);
+        }
+        changed
+    }
+
+    /// The selected tab, if any tab is opened.
+    pub fn selected(&self) -> Option<TabId> {
+        self.selected
+    }
+
+    /// The ids of the tabs in their display order.
+    pub fn ids(&self) -> Vec<TabId> {
+        self.tabs.iter().map(|(id, _)| *id).collect()
+    }
+
+    /// Check whether the tab is opened.
+    pub fn contains(&self, id: TabId) -> bool {
+        self.index_of(id).is_some()
+    }
+
+    fn index_of(&self, id: TabId) -> Option<usize> {
+        self.tabs.iter().position(|(tab, _)| *tab == id)
+    }
+}
+
+
+
+// ===========
+// === Frp ===
+// ===========
+
+ensogl::define_endpoints_2! {
+    Input {
+        /// Open a new tab with the given label and select it. The id of the tab is emitted through
+        /// the `tab_opened` output.
+        open_tab (ImString),
+        select_tab (TabId),
+        close_tab (TabId),
+        set_tab_label (TabId, ImString),
+    }
+    Output {
+        /// The id assigned to the last tab opened with the `open_tab` input.
+        tab_opened (TabId),
+        /// The selected tab changed, either by the user or because the selected tab was closed.
+        tab_selected (TabId),
+        /// The tab was closed, either by the user or with the `close_tab` input.
+        tab_closed (TabId),
+        /// The user dragged a tab, changing the order of the tabs.
+        tabs_reordered (Rc<Vec<TabId>>),
+    }
+}
+
+
+
+// ===========
+// === Tab ===
+// ===========
+
+/// The view of a single tab.
+#[derive(Clone, CloneRef, Debug, display::Object)]
+struct Tab {
+    display_object: display::object::Instance,
+    background:     Rectangle,
+    _label:         text::Text,
+    _close_label:   text::Text,
+    _network:       frp::Network,
+}
+
+impl Tab {
+    fn new(app: &Application, id: TabId, label: &ImString, model: &Model) -> Self {
+        let scene = &app.display.default_scene;
+        let display_object = display::object::Instance::new_named("Tab");
+        let background = Rectangle();
+        let close_button = Rectangle();
+        let label_view = app.new_view::<text::Text>();
+        let close_label = app.new_view::<text::Text>();
+        display_object.add_child(&background);
+        display_object.add_child(&close_button);
+        display_object.add_child(&label_view);
+        display_object.add_child(&close_label);
+        scene.layers.panel_text.add(&label_view);
+        scene.layers.panel_text.add(&close_label);
+        let text_color = model.style.get_color(theme::text);
+        background.set_size(Vector2(TAB_WIDTH, HEIGHT));
+        background.set_pointer_events(true);
+        let close_x = TAB_WIDTH - CLOSE_BUTTON_SIZE;
+        close_button.set_size(Vector2(CLOSE_BUTTON_SIZE, HEIGHT));
+        close_button.set_x(close_x);
+        close_button.set_color(INVISIBLE_HOVER_COLOR).set_pointer_events(true);
+        for text in [&label_view, &close_label] {
+            text.set_single_line_mode(true);
+            text.set_property_default(text::Size(TEXT_SIZE));
+            text.set_property_default(text_color);
+            text.set_y((HEIGHT + TEXT_SIZE) / 2.0);
+        }
+        label_view.set_content(label.clone());
+        label_view.set_x(PADDING_X);
+        close_label.set_content("×");
+        close_label.set_x(close_x + (CLOSE_BUTTON_SIZE - TEXT_SIZE / 2.0) / 2.0);
+
+        let network = frp::Network::new("Tab");
+        let tab_down = background.on_event::<mouse::Down>();
+        let close_down = close_button.on_event::<mouse::Down>();
+        let pressed = &model.tab_pressed;
+        let close_requested = &model.close_requested;
+        frp::extend! { network
+            pressed <+ tab_down.filter(mouse::is_primary).constant(id);
+            close_requested <+ tab_down.filter(mouse::is_middle).constant(id);
+            close_requested <+ close_down.filter(mouse::is_primary).constant(id);
+        }
+        Self {
+            display_object,
+            background,
+            _label: label_view,
+            _close_label: close_label,
+            _network: network,
+        }
+    }
+
+    fn set_selected(&self, style: &StyleWatch, selected: bool) {
+        let color = if selected { theme::selected_background } else { theme::background };
+        self.background.set_color(style.get_color(color));
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+#[derive(Clone, Debug, display::Object)]
+struct Model {
+    app:             Application,
+    display_object:  display::object::Instance,
+    style:           StyleWatch,
+    list:            RefCell<TabList>,
+    views:           RefCell<Vec<(TabId, Tab)>>,
+    /// The tab pressed with the primary button, and the pointer position at which it was pressed.
+    dragged:         Cell<Option<(TabId, Vector2)>>,
+    tab_pressed:     frp::Any<TabId>,
+    close_requested: frp::Any<TabId>,
+}
+
+impl Model {
+    fn new(app: &Application, network: &frp::Network) -> Self {
+        let app = app.clone_ref();
+        let scene = &app.display.default_scene;
+        let display_object = display::object::Instance::new_named("TabBar");
+        let style = StyleWatch::new(&scene.style_sheet);
+        scene.layers.panel.add(&display_object);
+        frp::extend! { network
+            tab_pressed <- any_mut::<TabId>();
+            close_requested <- any_mut::<TabId>();
+        }
+        let list = default();
+        let views = default();
+        let dragged = default();
+        Self { app, display_object, style, list, views, dragged, tab_pressed, close_requested }
+    }
+
+    /// Recreate the views of the tabs.
+    fn update_views(&self) {
+        let list = self.list.borrow();
+        let views = list.tabs.iter().enumerate().map(|(index, (id, label))| {
+            let tab = Tab::new(&self.app, *id, label, self);
+            tab.set_x(index as f32 * TAB_WIDTH);
+            self.display_object.add_child(&tab);
+            (*id, tab)
+        });
+        let views = views.collect_vec();
+        for (_, old) in self.views.replace(views) {
+            old.unset_parent();
+        }
+        drop(list);
+        self.update_selection();
+    }
+
+    fn update_selection(&self) {
+        let selected = self.list.borrow().selected();
+        for (id, tab) in &*self.views.borrow() {
+            tab.set_selected(&self.style, selected == Some(*id));
+        }
+    }
+
+    fn update_position(&self, camera: &Camera2d, top_offset: f32, margin: f32) {
+        let screen = camera.screen();
+        let x = -screen.width / 2.0 + margin;
+        let y = screen.height / 2.0 - top_offset - HEIGHT;
+        self.display_object.set_xy(Vector2(x.round(), y.round()));
+    }
+
+    fn open(&self, label: &ImString) -> TabId {
+        let id = self.list.borrow_mut().open(label.clone());
+        self.update_views();
+        id
+    }
+
+    /// Select the tab. Returns the tab if the selection changed.
+    fn select(&self, id: TabId) -> Option<TabId> {
+        let changed = self.list.borrow_mut().select(id);
+        changed.then(|| {
+            self.update_selection();
+            id
+        })
+    }
+
+    /// Close the tab. Returns the closed tab and the newly selected one, if the selection changed.
+    fn close(&self, id: TabId) -> Option<(TabId, Option<TabId>)> {
+        let selected_before = self.list.borrow().selected();
+        let closed = self.list.borrow_mut().close(id);
+        closed.then(|| {
+            self.update_views();
+            let selected = self.list.borrow().selected();
+            (id, selected.filter(|_| selected != selected_before))
+        })
+    }
+
+    fn set_label(&self, id: TabId, label: &ImString) {
+        self.list.borrow_mut().set_label(id, label.clone());
+        self.update_views();
+    }
+
+    fn start_drag(&self, id: TabId, pointer: Vector2) {
+        self.dragged.set(Some((id, pointer)));
+    }
+
+    /// Move the dragged tab to the position under the pointer. Returns `true` if the order changed.
+    fn drag(&self, pointer: Vector2) -> bool {
+        let dragged = self.dragged.get();
+        let moved = dragged.filter(|(_, start)| (pointer - start).norm() >= DRAG_THRESHOLD);
+        let changed = moved.map_or(false, |(id, _)| {
+            let index = (pointer.x / TAB_WIDTH).max(0.0) as usize;
+            self.list.borrow_mut().move_to(id, index)
+        });
+        if changed {
+            self.update_views();
+        }
+        changed
+    }
+
+    fn end_drag(&self) {
+        self.dragged.set(None);
+    }
+
+    fn tab_ids(&self) -> Rc<Vec<TabId>> {
+        Rc::new(self.list.borrow().ids())
+    }
+}
+
+
+
+// ==============
+// === TabBar ===
+// ==============
+
+/// The tab bar component. See the module docs.
+#[derive(Clone, CloneRef, Debug, Deref, display::Object)]
+#[allow(missing_docs)]
+pub struct TabBar {
+    #[display_object]
+    model:   Rc<Model>,
+    #[deref]
+    pub frp: Frp,
+}
+
+impl TabBar {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let frp = Frp::new();
+        let model = Rc::new(Model::new(app, frp.network()));
+        Self { model, frp }.init(app)
+    }
+
+    fn init(self, app: &Application) -> Self {
+        let network = self.frp.network();
+        let input = &self.frp.private.input;
+        let out = &self.frp.private.output;
+        let model = &self.model;
+        let scene = &app.display.default_scene;
+        let camera = scene.camera();
+        let mouse = &scene.mouse.frp_deprecated;
+        let mouse_up = scene.on_event::<mouse::Up>();
+        let style = StyleWatchFrp::new(&scene.style_sheet);
+        let top_offset = style.get_number(theme::top_offset);
+        let margin = style.get_number(theme::margin);
+
+        frp::extend! { network
+            init <- source_();
+            let camera_changed = scene.frp.camera_changed.clone_ref();
+            position <- all(init, camera_changed, top_offset, margin);
+            eval position ([model, camera] (&((), (), top_offset, margin)) {
+                model.update_position(&camera, top_offset, margin)
+            });
+
+            out.tab_opened <+ input.open_tab.map(f!((label) model.open(label)));
+            out.tab_selected <+ out.tab_opened;
+            select <- any(&input.select_tab, &model.tab_pressed);
+            out.tab_selected <+ select.filter_map(f!((id) model.select(*id)));
+            close <- any(&input.close_tab, &model.close_requested);
+            closed <- close.filter_map(f!((id) model.close(*id)));
+            out.tab_closed <+ closed._0();
+            out.tab_selected <+ closed._1().unwrap();
+            eval input.set_tab_label (((id, label)) model.set_label(*id, label));
+
+            // === Reordering ===
+
+            pointer <- mouse.position.map(f!([scene, model](position) {
+                scene.screen_to_object_space(&model.display_object, *position)
+            }));
+            drag_start <- model.tab_pressed.map2(&pointer, |id, pointer| (*id, *pointer));
+            eval drag_start (((id, pointer)) model.start_drag(*id, *pointer));
+            reordered <- pointer.filter(f!((pointer) model.drag(*pointer)));
+            eval_ mouse_up (model.end_drag());
+            out.tabs_reordered <+ reordered.map(f_!(model.tab_ids()));
+        }
+        init.emit(());
+        self
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opening_closing_and_moving_tabs() {
+        let mut tabs = TabList::default();
+        let main = tabs.open("main".into());
+        let foo = tabs.open("foo".into());
+        let bar = tabs.open("bar".into());
+        assert_eq!(tabs.selected(), Some(bar));
+        assert!(tabs.select(foo));
+        assert!(!tabs.select(foo));
+
+        assert!(tabs.move_to(main, 5));
+        assert_eq!(tabs.ids(), vec![foo, bar, main]);
+        assert!(!tabs.move_to(main, 2));
+
+        assert!(tabs.close(foo));
+        assert_eq!(tabs.selected(), Some(bar));
+        assert!(!tabs.close(foo));
+        tabs.select(main);
+        tabs.close(main);
+        assert_eq!(tabs.selected(), Some(bar));
+        tabs.close(bar);
+        assert_eq!(tabs.selected(), None);
+        assert!(tabs.ids().is_empty());
+    }
+}
@@ -14,6 +14,91 @@ use std::hash::Hasher;
 
 
 
+// =================
+// === Constants ===
+// =================
+
+/// The maximum lightness of the colors in the high-contrast palette.
+const HIGH_CONTRAST_MAX_LIGHTNESS: f32 = 0.55;
+/// The minimum chroma of the colors in the high-contrast palette. Does not apply to the grays.
+const HIGH_CONTRAST_MIN_CHROMA: f32 = 0.8;
+
+
+
+// ===============
+// === Palette ===
+// ===============
+
+/// The palette of the type colors. The palettes other than the default one adjust the colors, so
+/// they stay distinguishable for the users with color vision deficiencies.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Palette {
+    /// The colors defined by the theme, using the full range of hues.
+    #[default]
+    Default,
+    /// Safe for deuteranopia, the reduced sensitivity to green. The red and green hues are
+    /// replaced with the ones distinguishable along the blue-yellow axis.
+    Deuteranopia,
+    /// Safe for protanopia, the reduced sensitivity to red. Like [`Palette::Deuteranopia`], but
+    /// also avoids the pinks, which are perceived as dark blues.
+    Protanopia,
+    /// Darker and more saturated colors, contrasting with the light background.
+    HighContrast,
+}
+
+impl Palette {
+    /// The hues the colors are snapped to, or [`None`] if any hue is allowed.
+    fn hues(self) -> Option<Vec<f32>> {
+        use color::Lch;
+        match self {
+            Self::Default | Self::HighContrast => None,
+            Self::Deuteranopia => Some(vec![
+                Lch::orange_hue(),
+                Lch::yellow_hue(),
+                Lch::blue_green_hue(),
+                Lch::blue_hue(),
+                Lch::violet_hue(),
+                Lch::pink_hue(),
+            ]),
+            Self::Protanopia => Some(vec![
+                Lch::orange_hue(),
+                Lch::yellow_hue(),
+                Lch::blue_green_hue(),
+                Lch::blue_hue(),
+                Lch::violet_hue(),
+            ]),
+        }
+    }
+
+    /// Adjust the color to the palette.
+    pub fn adjust(self, color: color::Lcha) -> color::Lcha {
+        let (mut lightness, mut chroma, mut hue) = (color.lightness, color.chroma, color.hue);
+        if let Some(hues) = self.hues() {
+            hue = nearest_hue(hue, &hues);
+        }
+        if self == Self::HighContrast {
+            lightness = lightness.min(HIGH_CONTRAST_MAX_LIGHTNESS);
+            if chroma > 0.0 {
+                chroma = chroma.max(HIGH_CONTRAST_MIN_CHROMA);
+            }
+        }
+        color::Lcha::new(lightness, chroma, hue, color.alpha)
+    }
+}
+
+/// Find the hue closest to the given one on the color wheel. Returns the given hue if `hues` is
+/// empty.
+fn nearest_hue(hue: f32, hues: &[f32]) -> f32 {
+    let distance = |other: f32| {
+        let diff = (hue - other).rem_euclid(1.0);
+        diff.min(1.0 - diff)
+    };
+    let nearest = hues.iter().copied().min_by(|a, b| distance(*a).total_cmp(&distance(*b)));
+    nearest.unwrap_or(hue)
+}
+
+
+
 // ================================
 // === Type to Color Conversion ===
 // ================================
@@ -80,3 +165,47 @@ fn hash(s: &str) -> u64 {
     s.hash(&mut hasher);
     hasher.finish()
 }
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use color::Lch;
+
+    #[test]
+    fn nearest_hue_wraps_around() {
+        let hues = [0.1, 0.5, 0.9];
+        assert_eq!(nearest_hue(0.2, &hues), 0.1);
+        assert_eq!(nearest_hue(0.45, &hues), 0.5);
+        assert_eq!(nearest_hue(0.98, &hues), 0.9);
+        assert_eq!(nearest_hue(0.02, &[0.5, 0.9]), 0.9);
+        assert_eq!(nearest_hue(0.3, &[]), 0.3);
+    }
+
+    #[test]
+    fn color_blind_palettes_avoid_red_and_green() {
+        let red = color::Lcha::from(Lch::red(0.85, 0.6));
+        let green = color::Lcha::from(Lch::green(0.85, 0.6));
+        for palette in [Palette::Deuteranopia, Palette::Protanopia] {
+            assert_ne!(palette.adjust(red).hue, Lch::red_hue());
+            assert_ne!(palette.adjust(green).hue, Lch::green_hue());
+            assert_eq!(palette.adjust(red).lightness, 0.85);
+        }
+        assert_eq!(Palette::Default.adjust(red), red);
+    }
+
+    #[test]
+    fn high_contrast_palette_darkens_and_saturates() {
+        let color = Palette::HighContrast.adjust(Lch::blue(0.85, 0.6).into());
+        assert_eq!(color.lightness, HIGH_CONTRAST_MAX_LIGHTNESS);
+        assert_eq!(color.chroma, HIGH_CONTRAST_MIN_CHROMA);
+        assert_eq!(color.hue, Lch::blue_hue());
+        let gray = Palette::HighContrast.adjust(Lch::new(0.85, 0.0, 0.0).into());
+        assert_eq!(gray.chroma, 0.0);
+    }
+}
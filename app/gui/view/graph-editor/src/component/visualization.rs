@@ -28,6 +28,7 @@
 // === Export ===
 // ==============
 
+pub mod bundle;
 pub mod container;
 pub mod data;
 pub mod definition;
@@ -0,0 +1,266 @@
+//! Visualization bundles, allowing third-party visualizations to be registered at runtime. A bundle
+//! is a JSON document containing the visualization [`Manifest`] and the JavaScript sources defining
+//! the visualization class, the same way as the visualizations defined in the project folder do.
+//! The sources may load any WebAssembly modules the visualization needs on their own.
+//!
+//! ```json
+//! {
+//!     "manifest": { "name": "Chart", "inputType": "Standard.Table.Table", "version": "1.0.0" },
+//!     "files": [{ "path": "chart.js", "content": "return class Chart extends Visualization {}" }]
+//! }
+//! ```
+
+use crate::prelude::*;
+
+use crate::component::visualization;
+use crate::component::visualization::java_script;
+
+use serde::Deserialize;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+
+
+// ================
+// === Manifest ===
+// ================
+
+/// The description of the visualization in the bundle.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Manifest {
+    /// The name of the visualization. It must be the same as the label of the visualization class.
+    pub name:       String,
+    /// The Enso type of the values the visualization can display.
+    pub input_type: String,
+    /// The version of the visualization, in the `major.minor.patch` format.
+    pub version:    String,
+}
+
+impl Manifest {
+    /// Check that all the fields are present and the version is well-formed.
+    pub fn validate(&self) -> Result<(), ManifestError> {
+        if self.name.trim().is_empty() {
+            Err(ManifestError::MissingName)
+        } else if self.input_type.trim().is_empty() {
+            Err(ManifestError::MissingInputType)
+        } else if !is_valid_version(&self.version) {
+            Err(ManifestError::InvalidVersion(self.version.clone()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn is_valid_version(version: &str) -> bool {
+    let parts = version.split('.').collect_vec();
+    let is_number = |part: &&str| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit());
+    parts.len() == 3 && parts.iter().all(is_number)
+}
+
+
+
+// ==============
+// === Bundle ===
+// ==============
+
+/// A single source file of the bundle.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub struct File {
+    pub path:    String,
+    pub content: String,
+}
+
+/// The visualization bundle. See the module docs.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub struct Bundle {
+    pub manifest: Manifest,
+    pub files:    Vec<File>,
+}
+
+impl Bundle {
+    /// Parse the bundle, and validate its manifest.
+    pub fn from_json(json: &str) -> Result<Self, LoadError> {
+        let bundle: Self =
+            serde_json::from_str(json).map_err(|err| LoadError::InvalidBundle(err.to_string()))?;
+        bundle.manifest.validate().map_err(LoadError::InvalidManifest)?;
+        if bundle.files.is_empty() {
+            return Err(LoadError::InvalidBundle("The bundle contains no source files.".into()));
+        }
+        Ok(bundle)
+    }
+
+    /// Fetch the bundle from the given URL.
+    pub async fn fetch(url: &str) -> Result<Self, LoadError> {
+        let fetch_error = |err| LoadError::Fetch(format!("{err:?}"));
+        let window = web_sys::window().ok_or_else(|| LoadError::Fetch("No window.".into()))?;
+        let response = JsFuture::from(window.fetch_with_str(url)).await.map_err(fetch_error)?;
+        let response: web_sys::Response = response.dyn_into().map_err(fetch_error)?;
+        if !response.ok() {
+            let status = format!("{} {}", response.status(), response.status_text());
+            return Err(LoadError::Fetch(status));
+        }
+        let text = JsFuture::from(response.text().map_err(fetch_error)?);
+        let text = text.await.map_err(fetch_error)?.as_string();
+        let text = text.ok_or_else(|| LoadError::Fetch("The response is not a text.".into()))?;
+        Self::from_json(&text)
+    }
+
+    /// Create the definition of the bundled visualization. The visualization path is owned by the
+    /// bundle's URL, and its input type is taken from the manifest.
+    pub fn into_definition(self, url: ImString) -> Result<visualization::Definition, LoadError> {
+        let project = visualization::Project::Bundle(url);
+        let files = self.files.iter().map(|f| (f.path.as_str(), f.content.as_str())).collect_vec();
+        let sources = java_script::Sources::from_files(&files);
+        let definition = java_script::Definition::new(project, sources)
+            .map_err(|err| LoadError::InvalidDefinition(err.to_string()))?;
+        let mut definition = visualization::Definition::from(definition);
+        let class_name = definition.signature.path.name.to_string();
+        if class_name != self.manifest.name {
+            let manifest_name = self.manifest.name;
+            return Err(LoadError::NameMismatch { manifest_name, class_name });
+        }
+        let path = definition.signature.path.clone_ref();
+        let input_format = *definition.signature.input_format;
+        let input_type = self.manifest.input_type;
+        definition.signature = visualization::Signature::new(path, input_type, input_format);
+        Ok(definition)
+    }
+}
+
+
+
+// ==============
+// === Result ===
+// ==============
+
+/// The path of the visualization registered from the bundle, or the reason it was not registered.
+pub type LoadResult = Result<visualization::Path, LoadError>;
+
+/// The result of loading the bundle from the given URL. The [`LoadResult`] is wrapped, as it has no
+/// default value, required by the FRP endpoints.
+#[derive(Clone, Debug, Deref)]
+#[allow(missing_docs)]
+pub struct BundleLoaded {
+    pub url:    ImString,
+    #[deref]
+    pub result: LoadResult,
+}
+
+impl Default for BundleLoaded {
+    fn default() -> Self {
+        Self { url: default(), result: Err(LoadError::Fetch(default())) }
+    }
+}
+
+
+
+// =============
+// === Error ===
+// =============
+
+/// The reason the bundle could not be loaded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum LoadError {
+    Fetch(String),
+    InvalidBundle(String),
+    InvalidManifest(ManifestError),
+    InvalidDefinition(String),
+    NameMismatch { manifest_name: String, class_name: String },
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Fetch(err) => write!(f, "Failed to fetch the bundle: {err}"),
+            LoadError::InvalidBundle(err) => write!(f, "Invalid bundle: {err}"),
+            LoadError::InvalidManifest(err) => write!(f, "Invalid bundle manifest: {err}"),
+            LoadError::InvalidDefinition(err) => write!(f, "Invalid visualization: {err}"),
+            LoadError::NameMismatch { manifest_name, class_name } => write!(
+                f,
+                "The manifest names the visualization {manifest_name:?}, but its class is labeled \
+                {class_name:?}."
+            ),
+        }
+    }
+}
+
+/// The reason the bundle manifest is invalid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum ManifestError {
+    MissingName,
+    MissingInputType,
+    InvalidVersion(String),
+}
+
+impl Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManifestError::MissingName => write!(f, "The name is missing."),
+            ManifestError::MissingInputType => write!(f, "The input type is missing."),
+            ManifestError::InvalidVersion(version) =>
+                write!(f, "The version {version:?} is not in the major.minor.patch format."),
+        }
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundle_json(name: &str, input_type: &str, version: &str, files: &str) -> String {
+        let manifest = format!(
+            r#"{{"name": "{name}", "inputType": "{input_type}", "version": "{version}"}}"#
+        );
+        format!(r#"{{"manifest": {manifest}, "files": {files}}}"#)
+    }
+
+    const FILES: &str = r#"[{"path": "chart.js", "content": "return class Chart {}"}]"#;
+
+    #[test]
+    fn parsing_bundle() {
+        let json = bundle_json("Chart", "Standard.Table.Table", "1.2.0", FILES);
+        let bundle = Bundle::from_json(&json).unwrap();
+        assert_eq!(bundle.manifest.name, "Chart");
+        assert_eq!(bundle.manifest.input_type, "Standard.Table.Table");
+        assert_eq!(bundle.manifest.version, "1.2.0");
+        assert_eq!(bundle.files.len(), 1);
+        assert_eq!(bundle.files[0].path, "chart.js");
+    }
+
+    #[test]
+    fn validating_manifest() {
+        let error = |name, input_type, version| {
+            let json = bundle_json(name, input_type, version, FILES);
+            Bundle::from_json(&json).err()
+        };
+        let invalid_manifest = |err| Some(LoadError::InvalidManifest(err));
+        assert_eq!(error("Chart", "Any", "0.1.12"), None);
+        assert_eq!(error(" ", "Any", "1.0.0"), invalid_manifest(ManifestError::MissingName));
+        assert_eq!(error("Chart", "", "1.0.0"), invalid_manifest(ManifestError::MissingInputType));
+        for version in ["1.0", "1.0.0.0", "1.x.0", "", "1..0", "v1.0.0"] {
+            let expected = invalid_manifest(ManifestError::InvalidVersion(version.into()));
+            assert_eq!(error("Chart", "Any", version), expected, "Version {version:?}");
+        }
+    }
+
+    #[test]
+    fn rejecting_malformed_bundles() {
+        let is_invalid_bundle = |json: &str| {
+            matches!(Bundle::from_json(json), Err(LoadError::InvalidBundle(_)))
+        };
+        assert!(is_invalid_bundle("not a json"));
+        assert!(is_invalid_bundle(r#"{"files": []}"#));
+        assert!(is_invalid_bundle(&bundle_json("Chart", "Any", "1.0.0", "[]")));
+    }
+}
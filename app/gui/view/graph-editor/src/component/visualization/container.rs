@@ -7,6 +7,8 @@
 //! is correctly positioned, sized and layouted in its different [ViewState]s (which include the
 //! `Enabled`, `Fullscreen` and `Preview` states). Importantly, this also includes EnsoGL layer
 //! management to ensure correct occlusion of the visualization with respect to other scene objects.
+//! In the `Fullscreen` state, the action bar is replaced by the [`FullscreenToolbar`], whose export
+//! actions are reported through the container's FRP outputs.
 
 // FIXME There is a serious performance problem in this implementation. It assumes that the
 // FIXME visualization is a child of the container. However, this is very inefficient. Consider a
@@ -26,6 +28,7 @@ use crate::data::enso;
 use crate::visualization;
 
 use action_bar::ActionBar;
+use fullscreen_toolbar::FullscreenToolbar;
 use enso_frp as frp;
 use ensogl::application::Application;
 use ensogl::control::io::mouse;
@@ -50,8 +53,11 @@ use ensogl_hardcoded_theme::graph_editor::visualization as theme;
 
 pub mod action_bar;
 pub mod fullscreen;
+pub mod fullscreen_toolbar;
 pub mod visualization_chooser;
 
+pub use fullscreen_toolbar::ExportFormat;
+
 
 
 // =================
@@ -157,6 +163,9 @@ ensogl::define_endpoints_2! {
         set_vis_input_type  (Option<enso::Type>),
         // Set width of the container, preserving the current height.
         set_width           (f32),
+        /// Move the container by the given offset from its place under the node, as if it was
+        /// dragged by the user. The zero offset pins the container back to the node.
+        set_drag_offset     (Vector2),
     }
     Output {
         preprocessor   (PreprocessorConfiguration),
@@ -168,6 +177,19 @@ ensogl::define_endpoints_2! {
         fullscreen     (bool),
         visible        (bool),
         view_state     (ViewState),
+        /// The offset by which the user dragged the container away from its place under the node.
+        drag_offset    (Vector2),
+        /// Whether the container is pinned to its place under the node, i.e. was not dragged away.
+        pinned         (bool),
+        /// The user requested copying the visualization data to the clipboard, using the
+        /// fullscreen toolbar.
+        copy_data_requested           (),
+        /// The user requested downloading the visualization in the given format, using the
+        /// fullscreen toolbar.
+        download_requested            (ExportFormat),
+        /// The user requested reloading the visualization preprocessor, using the fullscreen
+        /// toolbar. The preprocessor is not changed, but its data should be computed again.
+        preprocessor_reload_requested (),
     }
 }
 
@@ -312,6 +334,8 @@ pub struct ContainerModel {
     /// Internal root for all sub-objects. Will be moved when the visualization
     /// container position is changed by dragging.
     drag_root:          display::object::Instance,
+    /// The offset of the `drag_root` from its place under the node.
+    drag_offset:        Cell<Vector2>,
     visualization:      RefCell<Option<visualization::Instance>>,
     /// A network containing connection between currently set `visualization` FRP endpoints and
     /// container FRP. We keep a separate network for that, so we can manage life of such
@@ -323,6 +347,7 @@ pub struct ContainerModel {
     registry:           visualization::Registry,
     size:               Rc<Cell<Vector2>>,
     action_bar:         ActionBar,
+    fullscreen_toolbar: FullscreenToolbar,
 }
 
 impl ContainerModel {
@@ -331,6 +356,7 @@ impl ContainerModel {
         let scene = &app.display.default_scene;
         let display_object = display::object::Instance::new();
         let drag_root = display::object::Instance::new();
+        let drag_offset = default();
         let visualization = default();
         let vis_frp_connection = default();
         let view = View::new(scene.clone_ref());
@@ -339,10 +365,12 @@ impl ContainerModel {
         let size = default();
         let action_bar = ActionBar::new(app, registry.clone_ref());
         view.add_child(&action_bar);
+        let fullscreen_toolbar = FullscreenToolbar::new(app, registry.clone_ref());
 
         Self {
             display_object,
             drag_root,
+            drag_offset,
             visualization,
             vis_frp_connection,
             scene,
@@ -351,6 +379,7 @@ impl ContainerModel {
             registry,
             size,
             action_bar,
+            fullscreen_toolbar,
         }
         .init()
     }
@@ -359,6 +388,7 @@ impl ContainerModel {
         self.display_object.add_child(&self.drag_root);
         self.scene.layers.above_nodes.add(&self.action_bar);
         self.scene.layers.panel.add(&self.fullscreen_view);
+        self.scene.layers.panel.add(&self.fullscreen_toolbar);
         self.update_shape_sizes(ViewState::default());
         self.view.show_waiting_screen();
         self
@@ -393,6 +423,12 @@ impl ContainerModel {
         new_size
     }
 
+    /// Move the `drag_root` by the given offset from its place under the node.
+    fn set_drag_offset(&self, offset: Vector2) {
+        let old_offset = self.drag_offset.replace(offset);
+        self.drag_root.update_xy(|pos| pos - old_offset + offset);
+    }
+
     /// Convert the given position from screen space to object space of the container.
     fn screen_to_object_space(&self, screen_pos: Vector2) -> Vector2 {
         let object = &self.display_object;
@@ -422,6 +458,12 @@ impl ContainerModel {
         } else {
             self.action_bar.unset_parent();
         }
+        if view_state.is_fullscreen() {
+            self.fullscreen_view.add_child(&self.fullscreen_toolbar);
+        } else {
+            self.fullscreen_toolbar.unset_parent();
+            self.fullscreen_toolbar.reset_position();
+        }
 
         // Show or hide the visualization.
         if view_state.is_visible() {
@@ -515,7 +557,7 @@ impl ContainerModel {
             dom.set_style_or_warn("height", format!("{}px", size[1]));
             bg_dom.set_style_or_warn("width", "0");
             bg_dom.set_style_or_warn("height", "0");
-            self.drag_root.set_xy(Vector2(size.x / 2.0, -size.y / 2.0));
+            self.drag_root.set_xy(Vector2(size.x / 2.0, -size.y / 2.0) + self.drag_offset.get());
             self.view.hover_area.set_xy(-size / 2.0);
         }
         let action_bar_size = if matches!(view_state, ViewState::Enabled { has_error: false }) {
@@ -615,6 +657,7 @@ impl Container {
         let scene = &self.model.scene;
         let scene_shape = scene.shape();
         let action_bar = &model.action_bar.frp;
+        let toolbar = &model.fullscreen_toolbar.frp;
         let registry = &model.registry;
         let selection = Animation::new(network);
         let width_anim = Animation::new(network);
@@ -727,13 +770,18 @@ impl Container {
         // ===  Visualization Chooser Bindings ===
 
         frp::extend! { network
-            selected_definition <- action_bar.visualization_selection.map(f!([registry](path)
+            visualization_selection <- any(
+                &action_bar.visualization_selection,
+                &toolbar.visualization_selection
+            );
+            selected_definition <- visualization_selection.map(f!([registry](path)
                 path.as_ref().and_then(|path| registry.definition_from_path(path))
             ));
             action_bar.hide_icons <+ selected_definition.constant(());
             output.vis_input_type <+ input.set_vis_input_type;
             let chooser = &model.action_bar.visualization_chooser();
             chooser.frp.set_vis_input_type <+ input.set_vis_input_type;
+            toolbar.set_vis_input_type <+ input.set_vis_input_type;
         }
 
 
@@ -764,7 +812,8 @@ impl Container {
                         Ok(vis)  => {
                             model.set_visualization(vis,&preprocessor, *view_state);
                             let path = Some(definition.signature.path.clone());
-                            action_bar.set_selected_visualization.emit(path);
+                            action_bar.set_selected_visualization.emit(path.clone());
+                            model.fullscreen_toolbar.set_selected_visualization(path);
                         },
                         Err(err) => {
                             warn!("Failed to instantiate visualization: {err:?}");
@@ -852,9 +901,25 @@ impl Container {
         // ===  Action bar actions ===
 
         frp::extend! { network
-            eval_ action_bar.on_container_reset_position(model.drag_root.set_xy(Vector2::zero()));
             drag_action <- app.cursor.frp.scene_position_delta.gate(&action_bar.container_drag_state);
-            eval drag_action ((mouse) model.drag_root.update_xy(|pos| pos - mouse.xy()));
+            dragged_offset <- drag_action.map(f!((mouse) model.drag_offset.get() - mouse.xy()));
+            reset_offset <- any_(&action_bar.on_container_reset_position, &init);
+            reset_offset <- reset_offset.constant(Vector2::zero());
+            drag_offset <- any(&input.set_drag_offset, &dragged_offset, &reset_offset);
+            eval drag_offset ((offset) model.set_drag_offset(*offset));
+            output.drag_offset <+ drag_offset.on_change();
+            pinned <- output.drag_offset.map(|offset| *offset == Vector2::zero());
+            output.pinned <+ pinned.on_change();
+        }
+
+
+        // === Fullscreen toolbar actions ===
+
+        frp::extend! { network
+            toolbar.set_size <+ all(scene_shape, &init)._0().map(Vector2::from);
+            output.copy_data_requested <+ toolbar.copy_data;
+            output.download_requested <+ toolbar.download;
+            output.preprocessor_reload_requested <+ toolbar.reload_preprocessor;
         }
 
         // FIXME[mm]: If we set the size right here, we will see spurious shapes in some
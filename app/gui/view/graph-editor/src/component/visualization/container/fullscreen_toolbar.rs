@@ -0,0 +1,346 @@
+//! A toolbar displayed above the fullscreen visualization. It allows copying the visualization data
+//! to the clipboard, downloading it in one of the [`ExportFormat`]s, reloading the visualization
+//! preprocessor, and switching the visualization type. The toolbar is docked at the top of the
+//! screen, but it may be detached by dragging its background, e.g. when it covers the part of the
+//! visualization the user is interested in.
+
+use crate::prelude::*;
+use ensogl::display::shape::*;
+
+use crate::component::visualization;
+use crate::component::visualization::container::visualization_chooser::VisualizationChooser;
+use crate::data::enso;
+
+use enso_frp as frp;
+use ensogl::application::Application;
+use ensogl::control::io::mouse;
+use ensogl::data::color;
+use ensogl::display;
+use ensogl_component::text;
+use ensogl_hardcoded_theme::graph_editor::visualization::fullscreen_toolbar as theme;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// Text size of the button labels.
+const TEXT_SIZE: f32 = 11.0;
+/// Height of the toolbar.
+const HEIGHT: f32 = 28.0;
+/// Height of the buttons inside the toolbar.
+const BUTTON_HEIGHT: f32 = 20.0;
+/// Horizontal padding between the button labels and the button borders.
+const BUTTON_PADDING_X: f32 = 8.0;
+/// Gap between the neighbouring buttons, and between the buttons and the toolbar borders.
+const GAP: f32 = 4.0;
+/// Gap between the docked toolbar and the top border of the screen.
+const MARGIN_TOP: f32 = 10.0;
+/// Gap between the visualization chooser and its menu.
+const MENU_GAP: f32 = 5.0;
+
+
+
+// ====================
+// === ExportFormat ===
+// ====================
+
+/// The format in which the visualization may be downloaded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// The visualization data as comma-separated values.
+    #[default]
+    Csv,
+    /// The visualization data as JSON.
+    Json,
+    /// The rendered visualization as an image.
+    Png,
+}
+
+impl ExportFormat {
+    /// All the formats, in the order of their buttons on the toolbar.
+    pub const ALL: [ExportFormat; 3] = [ExportFormat::Csv, ExportFormat::Json, ExportFormat::Png];
+
+    /// The extension of the downloaded file.
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Png => "png",
+        }
+    }
+}
+
+
+
+// ==============
+// === Action ===
+// ==============
+
+/// The action triggered by one of the toolbar buttons.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Action {
+    #[default]
+    CopyData,
+    Download(ExportFormat),
+    ReloadPreprocessor,
+}
+
+impl Action {
+    /// All the actions, in the order of their buttons on the toolbar.
+    fn all() -> impl Iterator<Item = Action> {
+        let downloads = ExportFormat::ALL.into_iter().map(Action::Download);
+        let reload = iter::once(Action::ReloadPreprocessor);
+        iter::once(Action::CopyData).chain(downloads).chain(reload)
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Action::CopyData => "Copy",
+            Action::Download(ExportFormat::Csv) => "CSV",
+            Action::Download(ExportFormat::Json) => "JSON",
+            Action::Download(ExportFormat::Png) => "PNG",
+            Action::ReloadPreprocessor => "Reload",
+        }
+    }
+}
+
+
+
+// ===========
+// === Frp ===
+// ===========
+
+ensogl::define_endpoints_2! {
+    Input {
+        /// Set the size of the screen. The docked toolbar is centered at the top of the screen.
+        set_size                   (Vector2),
+        set_selected_visualization (Option<visualization::Path>),
+        set_vis_input_type         (Option<enso::Type>),
+        /// Dock the detached toolbar back at the top of the screen.
+        reset_position             (),
+    }
+    Output {
+        /// The user pressed the button copying the visualization data to the clipboard.
+        copy_data               (),
+        /// The user pressed the button downloading the visualization in the given format.
+        download                (ExportFormat),
+        /// The user pressed the button reloading the visualization preprocessor.
+        reload_preprocessor     (),
+        /// The user chose the visualization type.
+        visualization_selection (Option<visualization::Path>),
+        /// Whether the toolbar was dragged away from its docked position.
+        detached                (bool),
+    }
+}
+
+
+
+// ==============
+// === Button ===
+// ==============
+
+/// A text button on the toolbar.
+#[derive(Clone, Debug, display::Object)]
+struct Button {
+    display_object: display::object::Instance,
+    background:     Rectangle,
+    label:          text::Text,
+    action:         Action,
+}
+
+impl Button {
+    fn new(app: &Application, action: Action) -> Self {
+        let display_object = display::object::Instance::new();
+        let background = Rectangle::new();
+        background.set_corner_radius_max().set_pointer_events(true);
+        let label = app.new_view::<text::Text>();
+        label.set_single_line_mode(true);
+        label.set_property_default(text::Size(TEXT_SIZE));
+        label.remove_all_cursors();
+        label.set_content(app.translator().translate(action.label()));
+        display_object.add_child(&background);
+        display_object.add_child(&label);
+        Self { display_object, background, label, action }
+    }
+
+    /// Lay out the button for its current label width. Returns the width of the button.
+    fn update_layout(&self) -> f32 {
+        let label_width = self.label.width.value();
+        let width = label_width + BUTTON_PADDING_X * 2.0;
+        self.background.set_size(Vector2(width, BUTTON_HEIGHT));
+        self.background.set_y(-BUTTON_HEIGHT / 2.0);
+        self.label.set_xy(Vector2(BUTTON_PADDING_X, TEXT_SIZE / 2.0));
+        width
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+#[derive(Clone, Debug, display::Object)]
+struct Model {
+    display_object: display::object::Instance,
+    /// The toolbar itself, moved when the toolbar is dragged.
+    root:           display::object::Instance,
+    background:     Rectangle,
+    buttons:        Vec<Button>,
+    chooser:        VisualizationChooser,
+}
+
+impl Model {
+    fn new(app: &Application, registry: visualization::Registry) -> Self {
+        let layers = &app.display.default_scene.layers;
+        let display_object = display::object::Instance::new_named("FullscreenToolbar");
+        let root = display::object::Instance::new();
+        let background = Rectangle::new();
+        background.set_corner_radius_max().set_pointer_events(true);
+        root.add_child(&background);
+        let buttons = Action::all().map(|action| Button::new(app, action)).collect_vec();
+        for button in &buttons {
+            root.add_child(button);
+            layers.panel_text.add(&button.label);
+        }
+        let chooser = VisualizationChooser::new(app, registry);
+        chooser.set_layers(&layers.panel, &layers.panel_text);
+        chooser.frp.set_icon_size(Vector2(BUTTON_HEIGHT, BUTTON_HEIGHT));
+        chooser.frp.set_icon_padding(Vector2(BUTTON_HEIGHT / 3.0, BUTTON_HEIGHT / 3.0));
+        chooser.frp.set_menu_offset_y(MENU_GAP);
+        root.add_child(&chooser);
+        display_object.add_child(&root);
+        Self { display_object, root, background, buttons, chooser }
+    }
+
+    /// Lay out the buttons in a row, followed by the visualization chooser.
+    fn update_layout(&self) {
+        let widths = self.buttons.iter().map(|button| button.update_layout()).collect_vec();
+        let buttons_width: f32 = widths.iter().map(|width| width + GAP).sum();
+        let width = GAP + buttons_width + BUTTON_HEIGHT + GAP;
+        let mut x = -width / 2.0 + GAP;
+        for (button, button_width) in self.buttons.iter().zip(widths) {
+            button.set_x(x);
+            x += button_width + GAP;
+        }
+        self.chooser.set_x(width / 2.0 - GAP - BUTTON_HEIGHT / 2.0);
+        self.background.set_size(Vector2(width, HEIGHT));
+        self.background.set_xy(Vector2(-width / 2.0, -HEIGHT / 2.0));
+    }
+
+    fn set_position(&self, screen_size: Vector2, drag_offset: Vector2) {
+        let docked = Vector2(0.0, screen_size.y / 2.0 - MARGIN_TOP - HEIGHT / 2.0);
+        self.root.set_xy(docked + drag_offset);
+    }
+
+    fn set_colors(&self, background: color::Rgba, button: color::Rgba, text: color::Rgba) {
+        self.background.set_color(background);
+        for button_view in &self.buttons {
+            button_view.background.set_color(button);
+            button_view.label.set_property_default(text);
+        }
+    }
+}
+
+
+
+// =========================
+// === FullscreenToolbar ===
+// =========================
+
+/// The toolbar of the fullscreen visualization. See the module docs. It should be placed in the
+/// center of the fullscreen panel.
+#[derive(Clone, CloneRef, Debug, Deref, display::Object)]
+#[allow(missing_docs)]
+pub struct FullscreenToolbar {
+    #[display_object]
+    model:   Rc<Model>,
+    #[deref]
+    pub frp: Frp,
+}
+
+impl FullscreenToolbar {
+    /// Constructor.
+    pub fn new(app: &Application, registry: visualization::Registry) -> Self {
+        let model = Rc::new(Model::new(app, registry));
+        let frp = Frp::new();
+        Self { model, frp }.init(app)
+    }
+
+    fn init(self, app: &Application) -> Self {
+        let network = self.frp.network();
+        let input = &self.frp.private.input;
+        let out = &self.frp.private.output;
+        let model = &self.model;
+        let chooser = &model.chooser.frp;
+        let scene_mouse = &app.display.default_scene.mouse.frp_deprecated;
+        let style = StyleWatchFrp::new(&app.display.default_scene.style_sheet);
+        let background_color = style.get_color(theme::background);
+        let button_color = style.get_color(theme::button_background);
+        let text_color = style.get_color(theme::text);
+        let background_press = model.background.on_event::<mouse::Down>();
+
+        frp::extend! { network
+            init <- source_();
+            action <- any_mut::<Action>();
+            relayout <- any_mut::<()>();
+            relayout <+ init;
+        }
+        for button in &model.buttons {
+            let press = button.background.on_event::<mouse::Down>();
+            let button_action = button.action;
+            frp::extend! { network
+                action <+ press.filter(mouse::event::is_primary).constant(button_action);
+                relayout <+ button.label.width.constant(());
+            }
+        }
+
+        frp::extend! { network
+            eval_ relayout (model.update_layout());
+
+            out.copy_data <+ action.filter(|action| *action == Action::CopyData).constant(());
+            out.download <+ action.filter_map(|action| match action {
+                Action::Download(format) => Some(*format),
+                _ => None,
+            });
+            out.reload_preprocessor <+
+                action.filter(|action| *action == Action::ReloadPreprocessor).constant(());
+
+
+            // === Visualization Chooser ===
+
+            chooser.set_selected <+ input.set_selected_visualization;
+            chooser.set_vis_input_type <+ input.set_vis_input_type;
+            // The chooser does not allow deselecting the visualization, so we prohibit these
+            // events, which can occur on re-initialization.
+            has_selection <- chooser.chosen_entry.is_some();
+            out.visualization_selection <+ chooser.chosen_entry.gate(&has_selection);
+
+
+            // === Dragging ===
+
+            drag_start <- background_press.filter(mouse::event::is_primary).constant(());
+            is_dragging <- bool(&scene_mouse.up_primary, &drag_start);
+            drag_offset <- any_mut::<Vector2>();
+            drag_offset <+ init.constant(Vector2::zero());
+            drag_offset <+ input.reset_position.constant(Vector2::zero());
+            mouse_on_drag_start <- scene_mouse.position.sample(&drag_start);
+            offset_on_drag_start <- drag_offset.sample(&drag_start);
+            mouse_on_drag <- scene_mouse.position.gate(&is_dragging);
+            drag_offset <+ mouse_on_drag.map3(&mouse_on_drag_start, &offset_on_drag_start,
+                |position, start, offset| offset + position - start
+            );
+            position <- all(&input.set_size, &drag_offset);
+            eval position (((size, offset)) model.set_position(*size, *offset));
+            out.detached <+ drag_offset.map(|offset| *offset != Vector2::zero()).on_change();
+
+            colors <- all4(&background_color, &button_color, &text_color, &init);
+            eval colors (((background, button, text, _)) model.set_colors(*background, *button, *text));
+        }
+
+        init.emit(());
+        self
+    }
+}
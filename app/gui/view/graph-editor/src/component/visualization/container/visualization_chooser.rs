@@ -155,11 +155,19 @@ impl VisualizationChooser {
 
             frp.source.entries <+ input_type_changed.map(f!([model] (input_type){
                 let entries  = Rc::new(model.entries(input_type));
-                let provider = list_view::entry::AnyModelProvider::from(entries.clone_ref());
+                let labels   = Rc::new(entries.iter().cloned().map(String::from).collect_vec());
+                let provider = list_view::entry::AnyModelProvider::from(labels);
                 model.selection_menu.set_entries(provider);
                 entries
             }));
         }
         self
     }
+
+    /// Move the chooser and its menu to the given layers, e.g. to display it over the fullscreen
+    /// visualization. By default, the chooser is displayed above the nodes.
+    pub fn set_layers(&self, layer: &display::scene::Layer, text_layer: &display::scene::Layer) {
+        layer.add(&self.model.selection_menu);
+        self.model.selection_menu.set_label_layer(text_layer);
+    }
 }
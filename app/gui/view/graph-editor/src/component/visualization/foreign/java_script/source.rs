@@ -23,6 +23,7 @@ mod path_prefix {
     const BUILTIN: &str = "visualization/builtin";
     const CURRENT_PROJECT: &str = "visualization/project";
     const LIBRARY: &str = "visualization/library";
+    const BUNDLE: &str = "visualization/bundle";
 
     /// Turn `Project` enum into a path prefix.
     pub fn from_project(project: &Project) -> PathBuf {
@@ -33,6 +34,7 @@ mod path_prefix {
                 let name: &str = name.as_ref();
                 PathBuf::from(LIBRARY).join(name)
             }
+            Project::Bundle(_) => PathBuf::from(BUNDLE),
         }
     }
 }
@@ -29,6 +29,8 @@ pub enum Project {
     CurrentProject,
     /// An external library (i.e. the dependency of the current project).
     Library(enso::LibraryName),
+    /// A bundle loaded at runtime from the given URL. See [`crate::visualization::bundle`].
+    Bundle(ImString),
 }
 
 
@@ -1,11 +1,13 @@
 //! The `Registry` provides a mechanism to store `visualization::Class`es for all available
 //! visualizations. It provides functionality to register new factories, as well as get suitable
-//! factories for a specific data type.
+//! factories for a specific data type. Apart from the visualizations known upfront, the registry
+//! can load the visualization [`bundle`]s at runtime.
 
 use crate::prelude::*;
 
 use crate::builtin;
 use crate::component::visualization;
+use crate::component::visualization::bundle;
 use crate::data::enso;
 
 use enso_prelude::CloneRef;
@@ -62,6 +64,27 @@ impl Registry {
         };
     }
 
+    /// Fetch the visualization bundle from the given URL and register its visualization. Loading
+    /// a bundle of an already registered visualization replaces the previous definition, so a new
+    /// version of the bundle may be loaded without resetting the registry.
+    pub async fn load_bundle(&self, url: ImString) -> bundle::LoadResult {
+        let bundle = bundle::Bundle::fetch(&url).await?;
+        let definition = bundle.into_definition(url)?;
+        let path = definition.path();
+        self.remove(&path);
+        self.add(definition);
+        Ok(path)
+    }
+
+    /// Remove the visualization registered for the given path, if any.
+    fn remove(&self, path: &visualization::Path) {
+        if self.path_map.borrow_mut().remove(path).is_some() {
+            for definitions in self.type_map.borrow_mut().values_mut() {
+                definitions.retain(|definition| &definition.signature.path != path);
+            }
+        }
+    }
+
     /// Return all `visualization::Class`es that can create a visualization for the given datatype.
     pub fn valid_sources(&self, tp: &enso::Type) -> Vec<visualization::Definition> {
         let type_map = self.type_map.borrow();
@@ -154,4 +177,20 @@ mod tests {
             assert_no_duplicates(&signatures);
         }
     }
+
+    #[wasm_bindgen_test]
+    fn replacing_visualization() {
+        let registry = Registry::new();
+        let definition = Registry::default_visualization();
+        let path = definition.path();
+        registry.add(definition.clone_ref());
+        registry.remove(&path);
+        registry.add(definition);
+        assert!(registry.definition_from_path(&path).is_some());
+        let sources = registry.valid_sources(&enso::Type::any());
+        assert_eq!(sources.iter().filter(|def| def.path() == path).count(), 1);
+        registry.remove(&path);
+        assert!(registry.definition_from_path(&path).is_none());
+        assert!(registry.valid_sources(&enso::Type::any()).is_empty());
+    }
 }
@@ -0,0 +1,257 @@
+//! A panel docked to the bottom right corner of the scene, listing the watched nodes together with
+//! their expressions and short previews of their latest values. The previews are the data of the
+//! visualizations attached to the nodes, displayed as produced by their preprocessors.
+
+use crate::prelude::*;
+
+use crate::component::visualization;
+use crate::NodeId;
+
+use enso_frp as frp;
+use ensogl::application::Application;
+use ensogl::display;
+use ensogl::display::camera::Camera2d;
+use ensogl_component::list_view;
+use ensogl_component::list_view::ListView;
+use ensogl_hardcoded_theme::graph_editor::watch_panel as theme;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The maximum number of entries visible at once. The list scrolls if there are more watches.
+const MAX_VISIBLE_ENTRIES: usize = 8;
+/// Displayed in place of the value preview before any value arrives.
+const NO_PREVIEW: &str = "…";
+
+
+
+// =============
+// === Watch ===
+// =============
+
+/// A single entry of the watch panel.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Watch {
+    /// The watched node.
+    pub node_id:    NodeId,
+    /// The expression of the watched node.
+    pub expression: ImString,
+    /// The short preview of the latest value of the node, if any arrived yet.
+    pub preview:    Option<ImString>,
+}
+
+impl Watch {
+    /// The text displayed in the watch panel entry.
+    fn label(&self) -> String {
+        let preview = self.preview.as_ref().map_or(NO_PREVIEW, |preview| preview.as_str());
+        format!("#{}  {} = {preview}", self.node_id, self.expression)
+    }
+}
+
+/// The preview of the visualization data: the data produced by the visualization's preprocessor,
+/// as received. Returns [`None`] for the binary data.
+fn preview(data: &visualization::Data) -> Option<ImString> {
+    match data {
+        visualization::Data::Json { content } => {
+            let text = serde_json::from_str::<String>(content.raw());
+            Some(text.unwrap_or_else(|_| content.raw().to_owned()).into())
+        }
+        visualization::Data::Binary => None,
+    }
+}
+
+
+
+// ===============
+// === Watches ===
+// ===============
+
+/// The state of the watch panel entries, maintained by the Graph Editor. The expressions are
+/// remembered for all nodes, so the watch added later displays the current expression right away.
+#[derive(Debug, Default)]
+pub struct Watches {
+    entries:     RefCell<Vec<Watch>>,
+    expressions: RefCell<HashMap<NodeId, ImString>>,
+}
+
+impl Watches {
+    /// Add the watch of the node. Returns `false` if the node was already watched.
+    pub fn add(&self, node_id: NodeId) -> bool {
+        let mut entries = self.entries.borrow_mut();
+        let already_watched = entries.iter().any(|watch| watch.node_id == node_id);
+        if !already_watched {
+            let expression = self.expressions.borrow().get(&node_id).cloned().unwrap_or_default();
+            entries.push(Watch { node_id, expression, preview: None });
+        }
+        !already_watched
+    }
+
+    /// Remove the watch of the node. Returns `false` if the node was not watched.
+    pub fn remove(&self, node_id: NodeId) -> bool {
+        let mut entries = self.entries.borrow_mut();
+        let count_before = entries.len();
+        entries.retain(|watch| watch.node_id != node_id);
+        entries.len() != count_before
+    }
+
+    /// Forget the removed node. Returns `true` if the node was watched.
+    pub fn forget(&self, node_id: NodeId) -> bool {
+        self.expressions.borrow_mut().remove(&node_id);
+        self.remove(node_id)
+    }
+
+    /// Remember the node's expression. Returns `true` if the node is watched.
+    pub fn set_expression(&self, node_id: NodeId, expression: &ImString) -> bool {
+        self.expressions.borrow_mut().insert(node_id, expression.clone());
+        self.with_watch(node_id, |watch| watch.expression = expression.clone())
+    }
+
+    /// Update the value preview of the watched node. Returns `false` if the node is not watched.
+    pub fn set_preview(&self, node_id: NodeId, data: &visualization::Data) -> bool {
+        self.with_watch(node_id, |watch| watch.preview = preview(data))
+    }
+
+    /// The current watches, in the order they were added.
+    pub fn entries(&self) -> Rc<Vec<Watch>> {
+        Rc::new(self.entries.borrow().clone())
+    }
+
+    fn with_watch(&self, node_id: NodeId, f: impl FnOnce(&mut Watch)) -> bool {
+        let mut entries = self.entries.borrow_mut();
+        let watch = entries.iter_mut().find(|watch| watch.node_id == node_id);
+        watch.map(f).is_some()
+    }
+}
+
+
+// ===========
+// === Frp ===
+// ===========
+
+ensogl::define_endpoints_2! {
+    Input {
+        /// Set the displayed watches, in the display order.
+        set_watches (Rc<Vec<Watch>>),
+    }
+    Output {
+        /// The user chose the entry of the watched node.
+        watch_chosen (NodeId),
+        /// Whether the panel is displayed. The panel is hidden when there are no watches.
+        visible      (bool),
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+#[derive(Clone, Debug, display::Object)]
+struct Model {
+    display_object: display::object::Instance,
+    list:           ListView<list_view::entry::Label>,
+    watches:        RefCell<Rc<Vec<Watch>>>,
+}
+
+impl Model {
+    fn new(app: &Application) -> Self {
+        let scene = &app.display.default_scene;
+        let display_object = display::object::Instance::new_named("WatchPanel");
+        let list = app.new_view::<ListView<list_view::entry::Label>>();
+        list.set_label_layer(&scene.layers.panel_text);
+        scene.layers.panel.add(&list);
+        let watches = default();
+        Self { display_object, list, watches }
+    }
+
+    fn set_watches(&self, watches: &Rc<Vec<Watch>>) {
+        let labels = watches.iter().map(Watch::label).collect_vec();
+        self.list.set_entries(list_view::entry::AnyModelProvider::new(labels));
+        self.watches.replace(watches.clone_ref());
+    }
+
+    fn set_visibility(&self, visible: bool) {
+        if visible {
+            self.display_object.add_child(&self.list);
+        } else {
+            self.list.unset_parent();
+        }
+    }
+
+    fn watch_at(&self, index: list_view::entry::Id) -> Option<NodeId> {
+        self.watches.borrow().get(index).map(|watch| watch.node_id)
+    }
+
+    fn update_layout(&self, camera: &Camera2d, entry_count: usize, width: f32, margin: f32) {
+        let visible_entries = entry_count.min(MAX_VISIBLE_ENTRIES) as f32;
+        let height = visible_entries * list_view::entry::HEIGHT + list_view::SHAPE_MARGIN * 2.0;
+        let size = Vector2(width, height);
+        let screen = camera.screen();
+        let x = screen.width / 2.0 - margin - width / 2.0;
+        let y = -screen.height / 2.0 + margin + height / 2.0;
+        self.list.resize(size);
+        self.list.set_xy(Vector2(x.round(), y.round()));
+    }
+}
+
+
+
+// ==================
+// === WatchPanel ===
+// ==================
+
+/// The watch panel component. See the module docs.
+#[derive(Clone, CloneRef, Debug, Deref, display::Object)]
+#[allow(missing_docs)]
+pub struct WatchPanel {
+    #[display_object]
+    model:   Rc<Model>,
+    #[deref]
+    pub frp: Frp,
+}
+
+impl WatchPanel {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let model = Rc::new(Model::new(app));
+        let frp = Frp::new();
+        Self { model, frp }.init(app)
+    }
+
+    fn init(self, app: &Application) -> Self {
+        let network = self.frp.network();
+        let input = &self.frp.private.input;
+        let out = &self.frp.private.output;
+        let model = &self.model;
+        let scene = &app.display.default_scene;
+        let camera = scene.camera();
+        let style = StyleWatchFrp::new(&scene.style_sheet);
+        let width = style.get_number(theme::width);
+        let margin = style.get_number(theme::margin);
+
+        frp::extend! { network
+            init <- source_();
+            eval input.set_watches ((watches) model.set_watches(watches));
+            entry_count <- input.set_watches.map(|watches| watches.len());
+            out.visible <+ entry_count.map(|count| *count > 0).on_change();
+            eval out.visible ((visible) model.set_visibility(*visible));
+
+            let camera_changed = scene.frp.camera_changed.clone_ref();
+            dimensions <- all(width, margin);
+            layout <- all(init, camera_changed, entry_count, dimensions);
+            eval layout ([model, camera] (&((), (), count, (width, margin))) {
+                model.update_layout(&camera, count, width, margin)
+            });
+
+            chosen_entry <- model.list.chosen_entry.unwrap();
+            out.watch_chosen <+ chosen_entry.filter_map(f!((index) model.watch_at(*index)));
+        }
+        init.emit(());
+        input.set_watches.emit(Rc::new(default()));
+        self
+    }
+}
@@ -24,21 +24,21 @@ pub fn init_frp(
         // === Execution Environment Changes ===
 
         selector.set_available_execution_environments <+ frp.set_available_execution_environments;
+        selector.mark_execution_environments_loading <+ frp.mark_execution_environments_loading;
 
-        switch_to_live <-
-            frp.switch_to_live_execution_environment.constant(ExecutionEnvironment::Live);
-        switch_to_design <-
-            frp.switch_to_design_execution_environment.constant(ExecutionEnvironment::Design);
-        external_update <- any(switch_to_live,switch_to_design);
-        selector.set_execution_environment <+ external_update;
+        selector.cycle_execution_environment <+ frp.cycle_execution_environment;
 
         out.execution_environment <+ selector.selected_execution_environment.on_change();
         out.execution_environment_play_button_pressed <+ selector.play_press;
         frp.set_read_only <+ selector.play_press.constant(true);
+        out.execution_environment_cancel_pressed <+ selector.execution_cancel_pressed;
+        frp.set_read_only <+ selector.execution_cancel_pressed.constant(false);
 
 
         // === Play Button ===
 
         selector.reset_play_button_state <+ frp.execution_complete;
+        selector.set_execution_in_progress <+ frp.set_execution_in_progress;
+        selector.set_execution_progress <+ frp.set_execution_progress;
     }
 }
@@ -39,23 +39,29 @@ pub mod component;
 
 pub mod automation;
 pub mod builtin;
+#[warn(missing_docs)]
+pub mod clipboard;
 pub mod data;
 pub mod execution_environment;
 pub mod new_node_position;
 #[warn(missing_docs)]
+pub mod test_support;
+#[warn(missing_docs)]
 pub mod view;
 
+mod collision;
 mod layers;
 #[warn(missing_docs)]
 mod selection;
 mod shortcuts;
 
 use crate::application::command::FrpNetworkProvider;
+use crate::component::file_drop;
 use crate::component::node;
+use crate::component::type_coloring;
 use crate::component::visualization;
 use crate::component::visualization::instance::PreprocessorConfiguration;
 use crate::data::enso;
-use engine_protocol::language_server::ExecutionEnvironment;
 
 use application::tooltip;
 use enso_frp as frp;
@@ -63,6 +69,7 @@ use ensogl::application;
 use ensogl::application::Application;
 use ensogl::control::io::mouse;
 use ensogl::data::color;
+use ensogl::debug::frp_inspector::FrpInspector;
 use ensogl::display;
 use ensogl::display::navigation::navigator::Navigator;
 use ensogl::display::object::Id;
@@ -78,7 +85,9 @@ use ensogl_component::text;
 use ensogl_component::text::buffer::selection::Selection;
 use ensogl_component::tooltip::Tooltip;
 use ensogl_hardcoded_theme as theme;
+use ide_view_execution_environment_selector::ExecutionEnvironmentDef;
 use span_tree::PortId;
+use uuid::Uuid;
 
 
 
@@ -87,6 +96,8 @@ use span_tree::PortId;
 // ==============
 
 pub use layers::GraphLayers;
+pub use new_node_position::PlacementStrategy;
+pub use selection::Change as SelectionChange;
 
 
 
@@ -117,6 +128,24 @@ const VIZ_PREVIEW_MODE_TOGGLE_FRAMES: i32 =
 const MAX_ZOOM: f32 = 1.0;
 /// The amount of pixels that the dragged target edge overlaps with the cursor.
 const CURSOR_EDGE_OVERLAP: f32 = 2.0;
+/// The default distance from a compatible input port within which the dragged edge snaps to it.
+/// See the `set_port_snap_radius` input of [`GraphEditor`].
+const DEFAULT_PORT_SNAP_RADIUS: f32 = 24.0;
+/// The snapped edge is released only after the cursor moves this many times the snap radius away
+/// from the port, so it does not flicker on the radius border.
+const PORT_UNSNAP_FACTOR: f32 = 1.5;
+/// The distance from the dragged nodes within which other nodes are pushed away when the collision
+/// avoidance is enabled.
+const COLLISION_NEIGHBORHOOD: f32 = 300.0;
+/// The tooltip displayed when the dragged edge hovers an input port of an incompatible type. The
+/// placeholders are substituted after the message is translated to the current locale.
+const TYPE_MISMATCH_TOOLTIP: &str = "Expected {expected}, but got {actual}.";
+/// The maximum number of the views of removed nodes kept for reuse. See [`ViewPool`].
+const MAX_POOLED_NODES: usize = 256;
+/// The maximum number of the views of removed edges kept for reuse. See [`ViewPool`].
+const MAX_POOLED_EDGES: usize = 512;
+/// The margin around the nodes in the graph thumbnails, in the scene units.
+const THUMBNAIL_MARGIN: f32 = 20.0;
 
 
 
@@ -450,6 +479,8 @@ ensogl::define_endpoints_2! {
 
         // === Read-only mode ===
 
+        /// Disable node creation, editing, removal, collapsing, pasting, edge dragging and widget
+        /// edits. Selection, navigation and visualization toggling remain available.
         set_read_only(bool),
 
 
@@ -494,11 +525,20 @@ ensogl::define_endpoints_2! {
         /// Toggle nodes inverse selection mode.
         toggle_node_inverse_select(),
 
-        /// Set the node as selected. Ignores selection mode.
-        // WARNING: not implemented
+        /// Enable the lasso selection: dragging on the background selects the nodes whose centers
+        /// lie inside the dragged path, instead of the nodes inside the selection box. It is
+        /// combined with the current selection mode.
+        enable_node_lasso_select(),
+        /// Disable the lasso selection.
+        disable_node_lasso_select(),
+        /// Toggle the lasso selection.
+        toggle_node_lasso_select(),
+
+        /// Set the node as selected, keeping the other nodes selected. Ignores selection mode.
+        /// Emits `node_selected` if the node exists and was not selected.
         select_node                  (NodeId),
-        /// Set the node as deselected. Ignores selection mode.
-        // WARNING: not implemented
+        /// Set the node as deselected. Ignores selection mode. Emits `node_deselected` if the node
+        /// was selected.
         deselect_node                (NodeId),
         /// Set all nodes as selected. Ignores selection mode.
         select_all_nodes             (),
@@ -512,6 +552,23 @@ ensogl::define_endpoints_2! {
         enter_hovered_node(),
         /// Steps out of the current node, popping the topmost stack frame from the crumb list.
         exit_node(),
+        /// Enable or disable the animated transition played when entering or exiting nodes. When
+        /// disabled, the displayed graph is switched instantly.
+        set_navigation_animation_enabled(bool),
+        /// Request opening the graph of the method called by the last selected node. Unlike
+        /// entering the node, it works for any node whose call target is known, see
+        /// [`set_node_method_pointer`].
+        goto_definition_of_selected_node(),
+        /// Request peeking into the node: displaying the graph of the method it calls in a small,
+        /// read-only overlay, without entering the node. The graph is provided by the controller
+        /// through the `set_node_peek` input, after `node_peek_requested` is emitted.
+        peek_node(NodeId),
+        /// Peek into the last selected node. See [`peek_node`].
+        peek_selected_node(),
+        /// Set the graph displayed in the node peek overlay. Ignored unless the node is the one
+        /// most recently requested to be peeked into.
+        set_node_peek((NodeId, Rc<component::node_peek::PeekedGraph>)),
+        hide_node_peek(),
 
 
         // === Node Editing ===
@@ -532,10 +589,59 @@ ensogl::define_endpoints_2! {
         /// emitted in situations when the user wants to interactively create a node via the UI (as
         /// opposed to e.g. when loading a graph from a file).
         start_node_creation_from_port(),
+        /// Select the way of choosing positions for interactively created nodes.
+        set_node_placement_strategy(PlacementStrategy),
+        /// Enable or disable the collision avoidance. When enabled, dragged nodes push the
+        /// overlapping nodes in their neighborhood out of the way.
+        set_collision_avoidance(bool),
+        /// Set the distance between the points of the snap grid, to which the dragged nodes are
+        /// quantized. `None` disables the snap grid, leaving only the Magnet Alignment.
+        set_grid_spacing(Option<f32>),
+        /// Show or hide the dots of the snap grid in the background.
+        show_grid(bool),
+        /// Set the background of the graph canvas.
+        set_canvas_background(component::canvas_background::BackgroundSpec),
+        /// Set the named regions shaded on the canvas background, replacing the previously set
+        /// ones.
+        set_background_regions(Rc<Vec<component::canvas_background::Region>>),
+        /// Set the expressions of the nodes created for the dropped files, by the file extension.
+        /// The files of other extensions are read with the default expression. See the
+        /// [`file_drop`] module.
+        set_drop_templates(Rc<file_drop::ExpressionTemplates>),
+
+        // === Camera Bookmarks ===
+        /// Remember the current camera position and zoom in the bookmark slot.
+        save_camera_bookmark(u8),
+        /// Animate the camera back to the position and zoom remembered in the bookmark slot. Does
+        /// nothing if the slot is empty.
+        restore_camera_bookmark(u8),
+        // The variants of the above for the slots 1 to 9, bound to the `cmd shift <slot>` and
+        // `cmd <slot>` shortcuts.
+        save_camera_bookmark_1(),
+        save_camera_bookmark_2(),
+        save_camera_bookmark_3(),
+        save_camera_bookmark_4(),
+        save_camera_bookmark_5(),
+        save_camera_bookmark_6(),
+        save_camera_bookmark_7(),
+        save_camera_bookmark_8(),
+        save_camera_bookmark_9(),
+        restore_camera_bookmark_1(),
+        restore_camera_bookmark_2(),
+        restore_camera_bookmark_3(),
+        restore_camera_bookmark_4(),
+        restore_camera_bookmark_5(),
+        restore_camera_bookmark_6(),
+        restore_camera_bookmark_7(),
+        restore_camera_bookmark_8(),
+        restore_camera_bookmark_9(),
 
         // === Copy-Paste ===
         copy_selected_node(),
         paste_node(),
+        /// Copy the textual description of the graph to the clipboard. See
+        /// [`GraphEditorModel::describe_graph`].
+        copy_graph_description(),
 
 
         /// Remove all selected nodes from the graph.
@@ -548,12 +654,92 @@ ensogl::define_endpoints_2! {
         edit_mode_off(),
         /// Stop node editing, whatever node is currently edited.
         stop_editing(),
-        /// Collapse the selected nodes into a new node.
+        /// Show the preview of collapsing the selected nodes into a new node. The nodes are
+        /// collapsed once the preview is confirmed.
         collapse_selected_nodes(),
+        /// Collapse the nodes with the signature displayed in the collapse preview.
+        confirm_collapse_preview(),
+        /// Hide the collapse preview without collapsing the nodes.
+        cancel_collapse_preview(),
         /// Indicate whether this node had an error or not.
         set_node_error_status(NodeId, Option<node::error::Error>),
         /// Indicate whether this node has finished execution.
         set_node_pending_status(NodeId, bool),
+        /// Mark the node as being recomputed by the backend, showing a pulsing outline around it.
+        /// The mark is cleared automatically once the node's profiling information or data
+        /// arrives.
+        set_node_pending((NodeId, bool)),
+        /// Mark the values of the node and all nodes downstream of it as stale, e.g. after the node
+        /// was edited but not yet recomputed. The mark of each node is cleared automatically once
+        /// its profiling information or data arrives.
+        mark_subtree_stale(NodeId),
+        /// Clear the stale mark of the node, e.g. after the engine sent the update of its
+        /// expression.
+        clear_node_stale(NodeId),
+        /// Set or remove the breakpoint on the node.
+        toggle_node_breakpoint(NodeId),
+        /// Set or remove the breakpoints on all selected nodes.
+        toggle_breakpoint_for_selected_nodes(),
+        /// Freeze or unfreeze all selected nodes.
+        set_selected_nodes_frozen(bool),
+        /// Enable or disable skipping of all selected nodes.
+        set_selected_nodes_skip(bool),
+        /// Freeze all selected nodes, or unfreeze them if all are already frozen.
+        toggle_freeze_for_selected_nodes(),
+        /// Skip all selected nodes, or stop skipping them if all are already skipped.
+        toggle_skip_for_selected_nodes(),
+        /// Add the selected nodes to the watch panel.
+        add_selected_to_watch(),
+        /// Remove the node from the watch panel.
+        remove_watch(NodeId),
+        /// Search the nodes whose expressions or comments contain the query. The matches are
+        /// emitted through the `nodes_found` output, and all the other nodes are dimmed until
+        /// `clear_node_search` is emitted or the query is empty.
+        search_nodes(ImString),
+        /// Stop highlighting the nodes found by the last `search_nodes` query.
+        clear_node_search(),
+        /// Move the camera so the node is visible.
+        pan_camera_to_node(NodeId),
+        /// Find the node at the given position in the screen coordinates. The node is emitted
+        /// through the `node_at_screen_position` output.
+        request_node_at_screen_position(Vector2),
+        /// Find the input port at the given position in the screen coordinates. The port is
+        /// emitted through the `input_port_at_screen_position` output.
+        request_input_port_at_screen_position(Vector2),
+        /// Find the edge at the given position in the screen coordinates. The edge is emitted
+        /// through the `edge_at_screen_position` output.
+        request_edge_at_screen_position(Vector2),
+        /// Emit the view-only state of all nodes through the `view_metadata_exported` output.
+        export_view_metadata(),
+        /// Restore the view-only state of the nodes, previously emitted through the
+        /// `view_metadata_exported` output. The nodes not present in the graph are skipped.
+        import_view_metadata(Rc<HashMap<NodeId, ViewMetadata>>),
+        /// Display the cursors and the selected nodes of the other users editing the graph. See
+        /// [`component::remote_presence`].
+        set_remote_presences(Rc<Vec<component::remote_presence::RemotePresence>>),
+        /// Mark the node's expression as concurrently edited by another user, displaying a badge
+        /// with the other user's version. `None` removes the mark.
+        set_node_edit_conflict((NodeId, Option<node::conflict::ConflictInfo>)),
+        /// Resolve the node's edit conflict in favor of the other user's version. The conflict is
+        /// emitted through the `remote_edit_accepted` output and removed from the node. Does
+        /// nothing if the node has no conflict.
+        accept_remote(NodeId),
+        /// Set the number of times the node was evaluated in the current run. The counter is
+        /// displayed only in the profiling view mode.
+        set_node_execution_count((NodeId, u64)),
+        /// Set the duration of the node's last execution in milliseconds, displayed in the node's
+        /// hover card.
+        set_node_execution_duration((NodeId, Option<f32>)),
+        /// Set the warnings attached to the node's value, displayed on the node's badge. Unlike
+        /// the errors set by `set_node_error_status`, the warnings do not poison the nodes using
+        /// the value.
+        set_node_warnings((NodeId, Rc<Vec<node::warnings::Warning>>)),
+        /// Set the one-line summary of the documentation of the method called by the node,
+        /// displayed in the node's hover card.
+        set_node_doc_summary((NodeId, Option<ImString>)),
+        /// Enable or disable the card displayed after hovering a node for a while. See
+        /// [`component::hover_card`]. Enabled by default.
+        set_hover_cards_enabled(bool),
 
 
         // === Visualization ===
@@ -585,10 +771,66 @@ ensogl::define_endpoints_2! {
         // === Execution Environment ===
 
         /// Set the execution environments available to the graph.
-        set_available_execution_environments          (Rc<Vec<ExecutionEnvironment>>),
-        switch_to_design_execution_environment(),
-        switch_to_live_execution_environment(),
+        set_available_execution_environments          (Rc<Vec<ExecutionEnvironmentDef>>),
+        /// Display a loading state in the execution environment selector until the available
+        /// environments are set.
+        mark_execution_environments_loading (),
+        /// Switch to the next of the available execution environments.
+        cycle_execution_environment(),
         execution_complete(),
+        /// Display the execution environment play button as running or idle.
+        set_execution_in_progress (bool),
+        /// Set the progress of the running execution, from 0 to 1, or `None` if unknown.
+        set_execution_progress (Option<f32>),
+        /// Notify that the node finished computing its value. In the live execution environment, a
+        /// pulse travels along all outgoing edges of the node.
+        notify_node_recomputed(NodeId),
+
+
+        // === View Mode ===
+
+        /// Switch between the normal and profiling view modes.
+        set_view_mode(view::Mode),
+        /// Re-shape and redraw all text in the scene. Should be emitted after a change of the
+        /// theme or the fonts affecting the text layout. Zooming does not require it, as the
+        /// glyphs are scale-independent and the zoom-dependent values are cached per zoom bucket.
+        invalidate_text_cache(),
+
+
+        // === Color Palette ===
+
+        /// Set the palette the node, port and edge colors are adjusted to. See
+        /// [`type_coloring::Palette`].
+        set_color_palette(type_coloring::Palette),
+
+
+        // === Focus Mode ===
+
+        /// Enable or disable the focus mode. In focus mode, all nodes and edges not belonging to
+        /// the pipeline of the selected nodes (i.e. their upstream and downstream closure) are
+        /// dimmed.
+        set_focus_mode(bool),
+        toggle_focus_mode(),
+
+
+        // === Data Lineage ===
+
+        /// Highlight the node together with all nodes its value is computed from, dimming all
+        /// unrelated nodes and edges. Takes precedence over the focus mode.
+        highlight_upstream_of(NodeId),
+        /// Highlight the node together with all nodes using its value, dimming all unrelated nodes
+        /// and edges. Takes precedence over the focus mode.
+        highlight_downstream_of(NodeId),
+        /// Restore normal rendering after [`highlight_upstream_of`] or [`highlight_downstream_of`].
+        clear_lineage_highlight(),
+
+
+        // === Rejection Feedback ===
+
+        /// Signal that an action was rejected, e.g. an invalid connection or an edit attempted in
+        /// the read-only mode. The rejected node briefly flashes and shakes its outline. Every
+        /// rejection is also reported through the [`action_rejected`] output.
+        reject_feedback(RejectTarget),
 
 
         // === Debug ===
@@ -599,6 +841,9 @@ ensogl::define_endpoints_2! {
         /// Set a test visualization data for the selected nodes. Useful for testing visualizations
         /// during their development.
         debug_set_test_visualization_data_for_selected_node(),
+        /// Show or hide the overlay listing the FRP nodes of the Graph Editor emitting the most
+        /// events. Available in the debug mode only.
+        toggle_frp_inspector_overlay(),
         /// Reopen file in language server.
         ///
         /// Used as a debug or a fallback for the user when synchronization errors are spotted.
@@ -622,23 +867,63 @@ ensogl::define_endpoints_2! {
         /// enabled/disabled; `None` for no context switch expression.
         set_node_context_switch      ((NodeId, Option<bool>)),
         set_node_comment             ((NodeId,ImString)),
+        /// Set the user-assigned color tag of a node. `None` removes the tag.
+        set_node_color_tag           ((NodeId, Option<color::Rgba>)),
+        /// Collapse or expand a node. A collapsed node hides its comment and value preview.
+        set_node_collapsed           ((NodeId, bool)),
+        /// Set the name of the user-defined group a node belongs to. `None` removes the node from
+        /// its group.
+        set_node_group               ((NodeId, Option<ImString>)),
+        /// Set the width of a node, overriding the width fitting its expression. `None` restores
+        /// the automatic width.
+        set_node_width_override      ((NodeId, Option<f32>)),
+        /// Set the pointer to the definition of the method called by a node. Used by
+        /// [`goto_definition_of_selected_node`].
+        set_node_method_pointer      ((NodeId, Option<MethodPointer>)),
+        /// Request generating the description of a node. The description is generated outside of
+        /// the Graph Editor, after `node_description_requested` is emitted, and provided through
+        /// the `set_node_description` input. Meanwhile, the node displays the loading state.
+        request_node_description     (NodeId),
+        /// Set the generated description of a node, which the user may accept as the node's
+        /// comment or discard. `None` means the description could not be generated.
+        set_node_description         ((NodeId, Option<ImString>)),
+        /// Set the id identifying the node across the graph reloads, e.g. the id stored in the
+        /// node's metadata. When a selected node is removed and a node with the same stable id is
+        /// added in the same update, the new node is selected.
+        set_node_stable_id           ((NodeId, Uuid)),
         set_node_position            ((NodeId,Vector2)),
         set_expression_usage_type    ((NodeId,ast::Id,Option<Type>)),
+        /// Update the widgets of the node. The updates received in the same frame are coalesced,
+        /// keeping only the latest configuration of each argument, and applied before rendering.
         update_node_widgets          ((NodeId,CallWidgetsConfig)),
         cycle_visualization          (NodeId),
         set_visualization            ((NodeId, Option<visualization::Path>)),
         register_visualization       (Option<visualization::Definition>),
         set_visualization_data       ((NodeId, visualization::Data)),
         set_error_visualization_data ((NodeId, visualization::Data)),
+        /// Set the data of the value preview preprocessor, attached to the node when the value
+        /// previews are shown. See [`component::node::value_preview`].
+        set_value_preview_data       ((NodeId, visualization::Data)),
+        /// Show or hide the previews of the most recent values next to the nodes' output ports.
+        show_value_previews          (bool),
         enable_visualization         (NodeId),
         disable_visualization        (NodeId),
         /// Inform Graph Editor that attaching or updating visualization has resulted in error.
         visualization_update_failed  ((NodeId, String)),
+        /// Drop the visualization preprocessor changes of the node that were not sent to the engine
+        /// yet. The changes reported by the `visualization_preprocessor_changed` output are
+        /// debounced before being sent, and the data computed with a replaced preprocessor are
+        /// dropped.
+        cancel_pending_visualization_updates(NodeId),
 
         /// Remove from visualization registry all non-default visualizations.
         reset_visualization_registry (),
         /// Reload visualization registry
         reload_visualization_registry(),
+        /// Fetch the visualization bundle from the given URL and register its visualization. The
+        /// result is reported by the `visualization_bundle_loaded` output. See
+        /// [`visualization::bundle`].
+        load_visualization_bundle    (ImString),
         /// Show visualization previews on nodes without delay.
         enable_quick_visualization_preview(),
         /// Show visualization previews on nodes with delay.
@@ -648,6 +933,23 @@ ensogl::define_endpoints_2! {
 
         /// Drop an edge that is being dragged.
         drop_dragged_edge            (),
+        /// Set the distance from a compatible input port within which the dragged edge snaps to
+        /// that port. Zero disables the snapping.
+        set_port_snap_radius         (f32),
+        /// Set the label displayed at the midpoint of the edge. It takes precedence over the type
+        /// label displayed when `show_edge_types` is enabled.
+        set_edge_label               ((EdgeId, Option<ImString>)),
+        /// Show or hide the labels with the types of the values transmitted by the edges.
+        show_edge_types              (bool),
+        /// Collapse the edge into a pair of portals, so the value is routed across the canvas
+        /// without drawing the edge. See [`component::portal`].
+        create_portal_from_edge      (EdgeId),
+        /// Expand the portals of the edge back into the edge.
+        expand_portal                (EdgeId),
+        /// Hide the edges connected to the node, replacing them with the stubs displaying the
+        /// numbers of the hidden incoming and outgoing edges. Hovering the stubs temporarily
+        /// reveals the edges.
+        set_node_edges_hidden        ((NodeId, bool)),
     }
 
     Output {
@@ -656,22 +958,63 @@ ensogl::define_endpoints_2! {
         debug_mode (bool),
 
 
+        // === View Mode ===
+
+        view_mode (view::Mode),
+
+
+        // === Color Palette ===
+
+        color_palette (type_coloring::Palette),
+
+
+        // === Focus Mode ===
+
+        focus_mode (bool),
+        /// The node which lineage is currently highlighted, with the direction of the lineage.
+        lineage_highlight (Option<(NodeId, LineageDirection)>),
+
+
         // === Read-only mode ===
 
         read_only (bool),
 
+        // === Rejection Feedback ===
+
+        /// Emitted whenever an action was rejected. Can be used as a hook for additional feedback,
+        /// like playing a sound.
+        action_rejected (RejectTarget),
+
+        // === Statistics ===
+
+        /// The statistics of the graph, updated on every change of nodes, edges or selection.
+        graph_stats (GraphStats),
+
         // === Edge ===
 
         has_detached_edge (bool),
         hover_node_input (Option<EdgeEndpoint>),
         hover_node_output (Option<EdgeEndpoint>),
+        /// The detached edge was dropped on an input port of an incompatible type, so the
+        /// connection was not made. Contains the edge, the port it was dropped on, the type of the
+        /// edge's source and the type of the port.
+        edge_drop_rejected (EdgeId, EdgeEndpoint, Type, Type),
 
         // === Node ===
 
         node_added                 (NodeId, Option<NodeSource>, bool),
         node_removed               (NodeId),
-        nodes_collapsed            ((Vec<NodeId>, NodeId)),
+        /// The user confirmed collapsing the nodes into a new function with the given signature.
+        nodes_collapsed            ((Vec<NodeId>, Rc<component::collapse_preview::Signature>)),
+        collapse_preview_visible   (bool),
         node_hovered               (Switch<NodeId>),
+        /// The mouse entered or left the stubs of the node's hidden edges.
+        node_edge_stubs_hovered    ((NodeId, bool)),
+        /// The user clicked the frame of the node error's stack trace pointing to the expression
+        /// with the given id. The expression may belong to any node of the graph, or to none.
+        error_frame_clicked        (ast::Id),
+        /// The numbers of the warnings attached to the values of all the nodes in the graph.
+        warnings_summary           (node::warnings::WarningsSummary),
         node_selected              (NodeId),
         node_deselected            (NodeId),
         node_position_set          ((NodeId,Vector2)),
@@ -680,13 +1023,49 @@ ensogl::define_endpoints_2! {
         node_expression_span_set   ((NodeId, span_tree::Crumbs, ImString)),
         node_expression_edited     ((NodeId,ImString,Vec<Selection<text::Byte>>)),
         node_comment_set           ((NodeId,ImString)),
+        /// The user assigned a color tag to a node using the node's action bar.
+        node_color_tag_set         ((NodeId, Option<color::Rgba>)),
+        /// The user resized the node by dragging its right edge, or reset its width. `None` means
+        /// the node's width fits its expression again.
+        node_width_overridden      ((NodeId, Option<f32>)),
+        /// A description of the node with the given expression was requested. See
+        /// [`request_node_description`].
+        node_description_requested ((NodeId, ImString)),
+        /// The sorted list of all nodes with a breakpoint. Emitted at most once per frame, after
+        /// the breakpoints were toggled or a node with a breakpoint was removed.
+        breakpoints_changed        (Rc<Vec<NodeId>>),
+        /// The node's position, color tag, or visualization state, pinning or dock changed.
+        /// Emitted at most once per frame for each node, carrying all the node's visual metadata
+        /// at once.
+        visual_metadata_changed    ((NodeId, VisualMetadata)),
+        /// The view-only state of all nodes, emitted in response to the `export_view_metadata`
+        /// input.
+        view_metadata_exported     (Rc<HashMap<NodeId, ViewMetadata>>),
+        /// The node's edit conflict was resolved in favor of the other user's version. The
+        /// controller is responsible for setting the node's expression to the accepted one.
+        remote_edit_accepted       ((NodeId, node::conflict::ConflictInfo)),
         node_entered               (NodeId),
         node_exited                (),
+        /// The user requested to open the graph of the definition of the method called by the
+        /// given node.
+        request_enter_method       ((NodeId, MethodPointer)),
+        /// The user requested peeking into the node calling the given method. See [`peek_node`].
+        node_peek_requested        ((NodeId, MethodPointer)),
+        node_peek_visible          (bool),
         node_editing_started       (NodeId),
         node_editing_finished      (NodeId),
         node_action_context_switch ((NodeId, bool)),
         node_action_freeze         ((NodeId, bool)),
         node_action_skip           ((NodeId, bool)),
+        /// The user froze or unfroze all the selected nodes at once. Emitted before the
+        /// `node_action_freeze` events of the individual nodes.
+        nodes_action_freeze        ((Rc<Vec<NodeId>>, bool)),
+        /// The user enabled or disabled skipping of all the selected nodes at once. Emitted before
+        /// the `node_action_skip` events of the individual nodes.
+        nodes_action_skip          ((Rc<Vec<NodeId>>, bool)),
+        /// The user triggered the custom action with the given name on the node. See
+        /// [`GraphEditorModel::register_node_action`].
+        node_action_triggered      ((NodeId, ImString)),
         node_edit_mode             (bool),
         nodes_labels_visible       (bool),
         node_incoming_edge_updates (NodeId),
@@ -703,6 +1082,14 @@ ensogl::define_endpoints_2! {
         is_fs_visualization_displayed           (bool),
         visualization_preprocessor_changed      ((NodeId,PreprocessorConfiguration)),
         visualization_registry_reload_requested (),
+        visualization_bundle_loaded             (visualization::bundle::BundleLoaded),
+        /// The user requested copying the data of the node's visualization to the clipboard.
+        visualization_copy_data_requested (NodeId),
+        /// The user requested downloading the node's visualization in the given format.
+        visualization_download_requested ((NodeId,visualization::ExportFormat)),
+        /// The user requested computing the data of the node's visualization again, with the
+        /// unchanged preprocessor.
+        visualization_preprocessor_reload_requested (NodeId),
         visualization_update_error ((NodeId, String)),
 
         on_visualization_select     (Switch<NodeId>),
@@ -723,8 +1110,13 @@ ensogl::define_endpoints_2! {
         node_copied(NodeId),
         // Paste node at position.
         request_paste_node(Vector2),
+        /// The user pasted the content copied from another application onto the canvas, to be
+        /// created as new nodes at the given position. See the [`clipboard`] module.
+        paste_requested (clipboard::ClipboardContent, Vector2),
 
-        file_dropped     (ensogl_drop_manager::File,Vector2<f32>),
+        /// The file was dropped on the canvas, to be created as a node at the given position. The
+        /// node's expression is the template configured for the file's extension, if any.
+        file_dropped (ensogl_drop_manager::File, Vector2, Option<file_drop::ExpressionTemplate>),
 
         connection_made (Connection),
         connection_broken (Connection),
@@ -732,11 +1124,41 @@ ensogl::define_endpoints_2! {
         default_x_gap_between_nodes (f32),
         default_y_gap_between_nodes (f32),
         min_x_spacing_for_new_nodes (f32),
+        node_placement_strategy (PlacementStrategy),
+        collision_avoidance (bool),
+        /// The distance between the points of the snap grid, if the snap grid is enabled.
+        grid_spacing (Option<f32>),
+        /// Whether the dots of the snap grid are displayed.
+        grid_visible (bool),
+        /// Whether the previews of values are displayed next to the nodes' output ports.
+        value_previews_visible (bool),
+        /// Whether the edges are labeled with the types of the values they transmit.
+        edge_types_visible (bool),
+        /// The edge was collapsed into a portal with the given number.
+        portal_created (EdgeId, component::portal::PortalId),
+        /// The nodes matching the query passed to the `search_nodes` input.
+        nodes_found (Rc<Vec<component::node_search::NodeMatch>>),
+        /// The screen position passed to the `request_node_at_screen_position` input, and the
+        /// node found there, if any.
+        node_at_screen_position ((Vector2, Option<NodeId>)),
+        /// The screen position passed to the `request_input_port_at_screen_position` input, and
+        /// the input port found there, if any.
+        input_port_at_screen_position ((Vector2, Option<EdgeEndpoint>)),
+        /// The screen position passed to the `request_edge_at_screen_position` input, and the edge
+        /// found there, if any.
+        edge_at_screen_position ((Vector2, Option<EdgeId>)),
+        /// The total number of the widget configurations received through `update_node_widgets`
+        /// but never applied, because a newer configuration of the same argument was received in
+        /// the same frame. For diagnostics only.
+        widget_updates_dropped (usize),
 
         /// The selected environment mode.
-        execution_environment (ExecutionEnvironment),
+        execution_environment (ExecutionEnvironmentDef),
         /// A press of the execution environment selector play button.
         execution_environment_play_button_pressed (),
+        /// A press of the execution environment selector play button while the execution is
+        /// running, requesting to cancel it.
+        execution_environment_cancel_pressed (),
     }
 }
 
@@ -757,9 +1179,14 @@ impl FrpNetworkProvider for GraphEditor {
 pub struct Node {
     #[deref]
     #[display_object]
-    pub view:  component::Node,
-    in_edges:  SharedHashSet<EdgeId>,
-    out_edges: SharedHashSet<EdgeId>,
+    pub view:       component::Node,
+    id:             NodeId,
+    in_edges:       SharedHashSet<EdgeId>,
+    out_edges:      SharedHashSet<EdgeId>,
+    method_pointer: Rc<RefCell<Option<MethodPointer>>>,
+    /// The connections between the view and the Graph Editor network, made for this node's id.
+    /// Dropped when the node is removed, see [`ViewPool`].
+    bindings:       Rc<RefCell<Option<frp::Network>>>,
 }
 
 #[derive(Clone, CloneRef, Copy, Debug, Default, Eq, From, Hash, Into, PartialEq, Ord, PartialOrd)]
@@ -770,13 +1197,26 @@ impl Node {
     /// Create a new node state from a given Node view component. Note that the node assumes its
     /// view component is not shared with other nodes.
     pub fn new(view: component::Node) -> Self {
-        Self { view, in_edges: default(), out_edges: default() }
+        let id = view.id().into();
+        Self::new_with_id(view, id)
     }
 
+    /// Create a new node state with the given id, instead of the one derived from the view. See
+    /// [`GraphEditor::new_deterministic`].
+    pub fn new_with_id(view: component::Node, id: NodeId) -> Self {
+        Self {
+            view,
+            id,
+            in_edges: default(),
+            out_edges: default(),
+            method_pointer: default(),
+            bindings: default(),
+        }
+    }
 
-    /// Get the NodeId, created from the view of this node.
+    /// Get the NodeId, by default created from the view of this node.
     pub fn id(&self) -> NodeId {
-        self.view.id().into()
+        self.id
     }
 
     /// A list of edges connected to this node's input ports.
@@ -793,6 +1233,11 @@ impl Node {
     pub fn all_edges(&self) -> Vec<EdgeId> {
         self.in_edges.keys().extended(self.out_edges.keys())
     }
+
+    /// The pointer to the definition of the method called by this node, if known.
+    pub fn method_pointer(&self) -> Option<MethodPointer> {
+        self.method_pointer.borrow().clone()
+    }
 }
 
 impl Display for NodeId {
@@ -812,14 +1257,22 @@ impl Display for NodeId {
 struct Edge {
     #[deref]
     #[display_object]
-    view:       component::Edge,
-    color:      color::Lcha,
-    source:     Option<EdgeEndpoint>,
-    target:     Option<EdgeEndpoint>,
+    view:        component::Edge,
+    id:          EdgeId,
+    color:       color::Lcha,
+    source:      Option<EdgeEndpoint>,
+    target:      Option<EdgeEndpoint>,
     /// The connection that is backed by this edge view. Does not necessarily have to be the same
     /// as the `source` and `target` endpoints, as the edge can be modified purely on view
     /// layer, and the connection does not have to be immediately updated.
-    connection: Option<Connection>,
+    connection:  Option<Connection>,
+    /// The label set by the user. See the `set_edge_label` input of [`GraphEditor`].
+    label:       Option<ImString>,
+    /// The label currently displayed by the edge view.
+    shown_label: Option<ImString>,
+    /// The connections between the view and the Graph Editor network, made for this edge's id.
+    /// Dropped when the edge is removed, see [`ViewPool`].
+    bindings:    Option<frp::Network>,
 }
 
 #[derive(Clone, CloneRef, Copy, Debug, Default, Eq, From, Hash, Into, PartialEq)]
@@ -828,12 +1281,22 @@ pub struct EdgeId(pub Id);
 
 impl Edge {
     /// Create a new edge with no source or target set yet.
-    fn new(view: component::Edge) -> Self {
-        Self { view, color: default(), source: None, target: None, connection: None }
+    fn new(view: component::Edge, id: EdgeId, bindings: frp::Network) -> Self {
+        Self {
+            view,
+            id,
+            color: default(),
+            source: None,
+            target: None,
+            connection: None,
+            label: None,
+            shown_label: None,
+            bindings: Some(bindings),
+        }
     }
 
     fn id(&self) -> EdgeId {
-        self.view.id().into()
+        self.id
     }
 
     fn target(&self) -> Option<EdgeEndpoint> {
@@ -903,6 +1366,13 @@ impl Edge {
         }
         changed
     }
+
+    fn set_shown_label(&mut self, label: Option<ImString>) {
+        if self.shown_label != label {
+            self.shown_label = label.clone();
+            self.view.set_label.emit(label);
+        }
+    }
 }
 
 impl Display for EdgeId {
@@ -917,6 +1387,67 @@ impl Display for EdgeId {
 // === Type ===
 // ============
 
+/// The unqualified name of the most generic type in Enso.
+const ANY_TYPE: &str = "Any";
+/// The unqualified name of the type accepting all numbers.
+const NUMBER_TYPE: &str = "Number";
+/// The unqualified names of the types accepted by [`NUMBER_TYPE`].
+const NUMERIC_SUBTYPES: &[&str] = &["Integer", "Float", "Decimal"];
+
+/// The members of the union type, as their unqualified names without type arguments, e.g.
+/// `Vector` for `Standard.Base.Data.Vector.Vector (Integer | Text)`. The nested unions are
+/// flattened, but the unions in the type arguments are not split.
+fn union_members(tp: &str) -> Vec<&str> {
+    let tp = tp.trim();
+    if let Some(inner) = strip_parentheses(tp) {
+        return union_members(inner);
+    }
+    let mut members = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, character) in tp.char_indices() {
+        match character {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            '|' if depth == 0 => {
+                members.push(&tp[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    if start > 0 {
+        members.push(&tp[start..]);
+        members.into_iter().flat_map(union_members).collect()
+    } else {
+        let constructor = tp.split_whitespace().next().unwrap_or_default();
+        vec![constructor.rsplit('.').next().unwrap_or(constructor)]
+    }
+}
+
+/// The type without the parentheses enclosing it as a whole, or [`None`] if there are no such
+/// parentheses. Note that `(A) | (B)` is not enclosed as a whole.
+fn strip_parentheses(tp: &str) -> Option<&str> {
+    let inner = tp.strip_prefix('(')?.strip_suffix(')')?;
+    let mut depth = 0;
+    for character in inner.chars() {
+        match character {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+        if depth < 0 {
+            return None;
+        }
+    }
+    Some(inner)
+}
+
+/// Check whether the type name is a type variable, e.g. `a`, which may stand for any type.
+fn is_type_variable(name: &str) -> bool {
+    name.starts_with(|character: char| character.is_lowercase())
+}
+
 /// Typename information that may be associated with the given Port.
 ///
 /// `None` means that type for the port is unknown.
@@ -934,7 +1465,32 @@ impl Type {
     /// Check whether this is any type, the most generic type in Enso. The empty string is
     /// considered to be an empty type as well.
     pub fn is_any(&self) -> bool {
-        self.as_str() == "Any" || self.is_empty()
+        self.is_empty() || union_members(self).contains(&ANY_TYPE)
+    }
+
+    /// Check whether a value of the `source` type may be passed to a port of this type. The check
+    /// is optimistic, as a false mismatch is worse than a missed one: the types are compared by
+    /// their unqualified names without type arguments, [`Any`](Self::is_any) and type variables
+    /// are compatible with every type, the numeric types are accepted by `Number`, and the union
+    /// types are compatible if any of their members are.
+    /// ```
+    /// use ide_view_graph_editor::*;
+    ///
+    /// let number = Type::from("Standard.Base.Data.Numbers.Number".to_string());
+    /// assert!(number.accepts(&Type::from("Integer".to_string())));
+    /// assert!(!number.accepts(&Type::from("Text".to_string())));
+    /// ```
+    pub fn accepts(&self, source: &Type) -> bool {
+        let targets = union_members(self);
+        let member_accepts = |source: &str| {
+            targets.iter().any(|&target| {
+                let is_subtype = target == NUMBER_TYPE && NUMERIC_SUBTYPES.contains(&source);
+                target == source || is_type_variable(target) || is_subtype
+            })
+        };
+        let sources = union_members(source);
+        let source_accepted = |&source: &&str| is_type_variable(source) || member_accepts(source);
+        self.is_any() || source.is_any() || sources.iter().any(source_accepted)
     }
 
     /// If the type consists of a single identifier then we remove all module qualifiers:
@@ -995,6 +1551,115 @@ impl Display for Type {
 
 
 
+// =====================
+// === MethodPointer ===
+// =====================
+
+/// A pointer to the definition of the method called by a node's expression.
+#[derive(Clone, CloneRef, Debug, Default, Deref, Eq, PartialEq)]
+pub struct MethodPointer(pub Rc<engine_protocol::language_server::MethodPointer>);
+
+impl From<engine_protocol::language_server::MethodPointer> for MethodPointer {
+    fn from(method_pointer: engine_protocol::language_server::MethodPointer) -> Self {
+        Self(Rc::new(method_pointer))
+    }
+}
+
+
+
+// ======================
+// === VisualMetadata ===
+// ======================
+
+/// The view-only state of a node which should be persisted by the controller alongside the code.
+/// Emitted in the `visual_metadata_changed` output of [`GraphEditor`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct VisualMetadata {
+    /// The position of the node in the scene.
+    pub position:              Vector2,
+    /// The color tag assigned to the node, if any.
+    pub color_tag:             Option<color::Rgba>,
+    /// The visualization chosen for the node, whether it is currently shown or not.
+    pub visualization:         Option<visualization::Path>,
+    /// Whether the node's visualization is shown.
+    pub visualization_visible: bool,
+    /// Whether the node's visualization is pinned to its place under the node.
+    pub visualization_pinned:  bool,
+    /// The offset by which the user dragged the visualization away from its place under the node.
+    pub visualization_dock:    Vector2,
+}
+
+
+
+// ====================
+// === ViewMetadata ===
+// ====================
+
+/// The view-only state of a node, which the controller may persist to restore the node's look
+/// after reopening the project. Unlike [`VisualMetadata`], it is not emitted on every change, but
+/// exported and imported on demand, through the `export_view_metadata` and `import_view_metadata`
+/// inputs of [`GraphEditor`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ViewMetadata {
+    /// Whether the node's visualization is shown.
+    pub visualization_enabled: bool,
+    /// The visualization chosen for the node, whether it is currently shown or not.
+    pub visualization:         Option<visualization::Path>,
+    /// The size of the visualization, if it was resized by the user.
+    pub visualization_size:    Option<Vector2>,
+    /// The offset by which the user dragged the visualization away from its place under the
+    /// node, or `None` if the visualization is pinned to the node.
+    pub visualization_dock:    Option<Vector2>,
+    /// The color tag assigned to the node, if any.
+    pub color_tag:             Option<color::Rgba>,
+    /// The width of the node, if it was resized by the user.
+    pub width:                 Option<f32>,
+    /// Whether the node is collapsed.
+    pub collapsed:             bool,
+    /// The name of the user-defined group the node belongs to, if any.
+    pub group:                 Option<ImString>,
+}
+
+
+
+// ==================
+// === GraphStats ===
+// ==================
+
+/// Statistics of the displayed graph, emitted in the `graph_stats` output of [`GraphEditor`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct GraphStats {
+    /// The number of nodes.
+    pub node_count:          usize,
+    /// The number of edges, including the detached ones.
+    pub edge_count:          usize,
+    /// The number of edges not attached to a node at one of their ends.
+    pub detached_edge_count: usize,
+    /// The number of selected nodes.
+    pub selected_count:      usize,
+    /// The number of nodes on the longest chain of connected nodes.
+    pub max_depth:           usize,
+}
+
+
+
+// ====================
+// === RejectTarget ===
+// ====================
+
+/// The subject of a rejected action, used to show the rejection feedback at the right place. See
+/// the `reject_feedback` input of [`GraphEditor`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum RejectTarget {
+    /// The action was rejected because of the given node.
+    Node(NodeId),
+    /// The action is not related to any particular node, e.g. adding a new node.
+    #[default]
+    Scene,
+}
+
+
+
 // ====================
 // === EdgeEndpoint ===
 // ====================
@@ -1101,7 +1766,7 @@ pub struct CallWidgetsConfig {
 }
 
 /// A structure describing a widget update for specific argument of a function call.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ArgumentWidgetConfig {
     /// The function argument name that this widget is for.
     pub argument_name: String,
@@ -1112,6 +1777,59 @@ pub struct ArgumentWidgetConfig {
 
 
 
+// ============================
+// === PendingWidgetUpdates ===
+// ============================
+
+/// The widget updates received in the current frame, not yet applied to the nodes. When the
+/// language server floods the updates (e.g. while the user is typing), applying every one of them
+/// would rebuild the widgets over and over, so only the latest configuration of each argument is
+/// kept and all updates of a node are applied at once.
+#[derive(Debug, Default)]
+struct PendingWidgetUpdates {
+    /// For each node, the updated calls in the order of their first update, with the latest
+    /// configurations of their arguments.
+    nodes:   HashMap<NodeId, Vec<(ast::Id, Vec<ArgumentWidgetConfig>)>>,
+    /// The total number of the argument configurations replaced before being applied.
+    dropped: usize,
+}
+
+impl PendingWidgetUpdates {
+    /// Add the update, replacing the pending configurations of the same arguments.
+    fn push(&mut self, node_id: NodeId, update: &CallWidgetsConfig) {
+        let calls = self.nodes.entry(node_id).or_default();
+        let call_index = calls.iter().position(|(call_id, _)| *call_id == update.call_id);
+        let call_index = call_index.unwrap_or_else(|| {
+            calls.push((update.call_id, default()));
+            calls.len() - 1
+        });
+        let arguments = &mut calls[call_index].1;
+        for definition in update.definitions.iter() {
+            let name = &definition.argument_name;
+            match arguments.iter_mut().find(|pending| &pending.argument_name == name) {
+                Some(pending) => {
+                    pending.config = definition.config.clone();
+                    self.dropped += 1;
+                }
+                None => arguments.push(definition.clone()),
+            }
+        }
+    }
+
+    /// Take the coalesced updates, one for each updated call of each node.
+    fn take(&mut self) -> Vec<(NodeId, CallWidgetsConfig)> {
+        let nodes = mem::take(&mut self.nodes).into_iter();
+        let updates = nodes.flat_map(|(node_id, calls)| {
+            calls.into_iter().map(move |(call_id, definitions)| {
+                (node_id, CallWidgetsConfig { call_id, definitions: Rc::new(definitions) })
+            })
+        });
+        updates.collect()
+    }
+}
+
+
+
 // =============
 // === Nodes ===
 // =============
@@ -1124,6 +1842,15 @@ pub struct Nodes {
     pub selected:       SharedVec<NodeId>,
     pub grid:           Rc<RefCell<Grid>>,
     pub inputs_updated: Rc<RefCell<Vec<NodeId>>>,
+    pub breakpoints:    SharedHashSet<NodeId>,
+    /// The ids identifying the nodes across the graph reloads. See [`Nodes::set_stable_id`].
+    pub stable_ids:     SharedHashMap<NodeId, Uuid>,
+    /// The stable ids of the selected nodes removed in the current update of the graph.
+    removed_selection:  SharedHashSet<Uuid>,
+    /// The nodes whose edges are replaced by the edge stubs.
+    pub edges_hidden:   SharedHashSet<NodeId>,
+    /// The node whose hidden edges are temporarily revealed, as its edge stubs are hovered.
+    edges_revealed:     Rc<Cell<Option<NodeId>>>,
 }
 
 impl Nodes {
@@ -1298,6 +2025,88 @@ impl Nodes {
 }
 
 
+// === Stable Ids ===
+
+impl Nodes {
+    /// Assign the id identifying the node across the graph reloads. Returns `true` if a selected
+    /// node with the same stable id was removed in the current update of the graph, so the node
+    /// replaces it and should be selected as well.
+    pub fn set_stable_id(&self, node_id: NodeId, stable_id: Uuid) -> bool {
+        self.stable_ids.insert(node_id, stable_id);
+        self.removed_selection.remove(&stable_id)
+    }
+
+    /// Forget the stable id of the removed node, remembering it until the end of the current
+    /// update of the graph if the node is selected.
+    fn forget_stable_id(&self, node_id: NodeId) {
+        if let Some(stable_id) = self.stable_ids.remove(&node_id) {
+            if self.is_selected(node_id) {
+                self.removed_selection.insert(stable_id);
+            }
+        }
+    }
+
+    /// Finish the update of the graph: the selected nodes removed during the update will not be
+    /// restored anymore.
+    fn forget_removed_selection(&self) {
+        self.removed_selection.clear();
+    }
+}
+
+
+// === Hidden Edges ===
+
+impl Nodes {
+    /// Hide or show back the edges of the node. Returns `false` if nothing changed.
+    pub fn set_edges_hidden(&self, node_id: NodeId, hidden: bool) -> bool {
+        match hidden {
+            true => self.edges_hidden.insert(node_id),
+            false => self.edges_hidden.remove(&node_id),
+        }
+    }
+
+    /// Temporarily reveal the hidden edges of the node, or stop revealing them. Returns the nodes
+    /// whose edges were revealed before or are revealed now.
+    fn set_edges_revealed(&self, node_id: NodeId, revealed: bool) -> Vec<NodeId> {
+        let previous = self.edges_revealed.get();
+        let current = if revealed { Some(node_id) } else { previous.filter(|id| *id != node_id) };
+        self.edges_revealed.set(current);
+        previous.into_iter().chain(current).dedup().collect()
+    }
+
+    /// Whether the connection is replaced by the edge stubs of one of the nodes it connects.
+    pub fn is_connection_hidden(&self, connection: Connection) -> bool {
+        let nodes = [connection.source.node_id, connection.target.node_id];
+        let revealed = self.edges_revealed.get().map_or(false, |id| nodes.contains(&id));
+        !revealed && nodes.iter().any(|id| self.edges_hidden.contains(id))
+    }
+
+    fn forget_hidden_edges(&self, node_id: NodeId) {
+        self.edges_hidden.remove(&node_id);
+        if self.edges_revealed.get() == Some(node_id) {
+            self.edges_revealed.set(None);
+        }
+    }
+}
+
+
+// === Traversal ===
+
+impl Nodes {
+    /// The ids of all nodes in the reading order: from top to bottom, and from left to right in
+    /// case of nodes at the same height. The order does not depend on the order of insertion.
+    pub fn ids_in_reading_order(&self) -> Vec<NodeId> {
+        let nodes = self.all.entries().into_iter().map(|(id, node)| (id, node.position()));
+        let mut nodes = nodes.collect_vec();
+        nodes.sort_by(|(id1, pos1), (id2, pos2)| {
+            let by_y = pos2.y.total_cmp(&pos1.y);
+            by_y.then(pos1.x.total_cmp(&pos2.x)).then(id1.cmp(id2))
+        });
+        nodes.into_iter().map(|(id, _)| id).collect()
+    }
+}
+
+
 
 // =============
 // === Edges ===
@@ -1403,6 +2212,14 @@ struct Edges {
     detached: Option<EdgeId>,
 }
 
+impl Edges {
+    /// The connections between the nodes, as the pairs of the source and target node. Only the
+    /// edges attached at both ends are included.
+    fn attached_connections(&self) -> impl Iterator<Item = (NodeId, NodeId)> + '_ {
+        self.all.values().filter_map(|edge| Some((edge.source?.node_id, edge.target?.node_id)))
+    }
+}
+
 #[derive(Debug, Clone, CloneRef, Default)]
 struct Visualizations {
     /// This keeps track of the currently selected visualization. There should only ever be one
@@ -1455,6 +2272,7 @@ struct TouchState {
     background:  TouchNetwork<()>,
     input_port:  TouchNetwork<EdgeEndpoint>,
     output_port: TouchNetwork<EdgeEndpoint>,
+    edges:       TouchNetwork<EdgeId>,
 }
 
 impl TouchState {
@@ -1463,7 +2281,8 @@ impl TouchState {
         let background = TouchNetwork::new(network, scene);
         let input_port = TouchNetwork::new(network, scene);
         let output_port = TouchNetwork::new(network, scene);
-        Self { nodes, background, input_port, output_port }
+        let edges = TouchNetwork::new(network, scene);
+        Self { nodes, background, input_port, output_port, edges }
     }
 }
 
@@ -1544,6 +2363,8 @@ impl GraphEditorModel {
             } else {
                 // Otherwise, remove this edge view and its connectivity data from nodes.
                 edge.set_endpoints(None, None, &self.nodes);
+                self.portals.expand(*edge_id);
+                self.view_pool.park_edge(edge);
                 false
             }
         });
@@ -1607,15 +2428,19 @@ impl GraphEditorModel {
     }
 
     fn create_edge(&self, pointer: &EdgePointerFrp) -> Edge {
-        let edge = Edge::new(component::Edge::new(&self.app, &self.layers));
-        self.add_child(&edge);
-        let edge_id = edge.id();
-        let network = edge.view.network();
+        let view = self.view_pool.take_edge();
+        let view = view.unwrap_or_else(|| component::Edge::new(&self.app, &self.layers));
+        let edge_id = self.allocate_id(view.id()).into();
+        let network = frp::Network::new("GraphEditorEdgeBindings");
         frp::extend! { network
-            edge.view.set_hover_disabled <+ self.frp.output.has_detached_edge;
-            pointer.source_click <+ edge.view.source_click.constant(edge_id);
-            pointer.target_click <+ edge.view.target_click.constant(edge_id);
+            view.set_hover_disabled <+ self.frp.output.has_detached_edge;
+            pointer.source_click <+ view.source_click.constant(edge_id);
+            pointer.target_click <+ view.target_click.constant(edge_id);
+            pointer.hovered <+ view.hovered.map(move |hovered| (edge_id, *hovered));
         }
+        view.set_hover_disabled(self.frp.output.has_detached_edge.value());
+        let edge = Edge::new(view, edge_id, network);
+        self.add_child(&edge);
         edge
     }
 
@@ -1650,11 +2475,18 @@ impl GraphEditorModel {
 
     #[profile(Debug)]
     fn new_node(&self, ctx: &NodeCreationContext) -> Node {
-        let view = component::Node::new(&self.app, &self.layers, self.vis_registry.clone_ref());
-        let node = Node::new(view);
+        let view = self.view_pool.take_node().unwrap_or_else(|| {
+            let view =
+                component::Node::new(&self.app, &self.layers, self.vis_registry.clone_ref());
+            for action in self.custom_node_actions.borrow().iter() {
+                view.add_custom_action(action);
+            }
+            view
+        });
+        let node_id = self.allocate_id(view.id()).into();
+        let node = Node::new_with_id(view, node_id);
         let node_model = node.model();
-        let network = node.frp().network();
-        let node_id = node.id();
+        let network = frp::Network::new("GraphEditorNodeBindings");
         self.add_child(&node);
 
         let out = &self.frp.output;
@@ -1669,9 +2501,14 @@ impl GraphEditorModel {
             eval_ node.background_press(node_down.emit(node_id));
 
             out.node_hovered <+ node.output.hover.map(move |t| Switch::new(node_id,*t));
+            out.node_edge_stubs_hovered <+ node.view.edge_stubs_hovered.map(
+                move |hovered| (node_id, *hovered)
+            );
+            out.error_frame_clicked <+ node.view.error_frame_clicked;
 
             out.node_comment_set <+ node.comment.map(move |c| (node_id,c.clone()));
             node.set_output_expression_visibility <+ out.nodes_labels_visible;
+            node.show_value_preview <+ out.value_previews_visible;
 
             pointer_style <+ input_frp.pointer_style;
             eval output_frp.on_port_press ((p) output_press.emit(EdgeEndpoint::new(node_id,*p)));
@@ -1683,6 +2520,8 @@ impl GraphEditorModel {
 
             out.node_incoming_edge_updates <+ input_frp.input_edges_need_refresh.constant(node_id);
             out.node_outgoing_edge_updates <+ input_frp.width.constant(node_id);
+            out.node_outgoing_edge_updates <+ node.view.width_override.constant(node_id);
+            out.node_outgoing_edge_updates <+ input_frp.line_count.constant(node_id);
             out.node_widget_tree_rebuilt <+ input_frp.widget_tree_rebuilt.constant(node_id);
 
             let is_editing = &input_frp.editing;
@@ -1717,6 +2556,17 @@ impl GraphEditorModel {
             out.node_action_context_switch <+ node.view.context_switch.map(move |s| (node_id, *s));
             out.node_action_freeze <+ node.view.freeze.map(move |is_frozen| (node_id, *is_frozen));
             out.node_action_skip <+ node.view.skip.map(move |is_skipped| (node_id, *is_skipped));
+            out.node_color_tag_set <+ node.view.color_tag.map(move |tag| (node_id, *tag));
+            out.node_width_overridden <+ node.view.width_overridden.map(
+                move |width| (node_id, *width)
+            );
+            out.node_action_triggered <+ node.view.custom_action.map(
+                move |name| (node_id, name.clone_ref())
+            );
+            let request_description = &self.frp_public.input.request_node_description;
+            eval_ node.view.description_requested (request_description.emit(node_id));
+            let accept_remote = &self.frp_public.input.accept_remote;
+            eval_ node.view.accept_remote_requested (accept_remote.emit(node_id));
             node.set_disabled <+ node.view.skip;
 
 
@@ -1739,6 +2589,13 @@ impl GraphEditorModel {
                 });
             out.visualization_preprocessor_changed <+ preprocessor_changed;
 
+            let vis_frp = &node_model.visualization.frp;
+            out.visualization_copy_data_requested <+ vis_frp.copy_data_requested.constant(node_id);
+            out.visualization_download_requested <+
+                vis_frp.download_requested.map(move |format| (node_id, *format));
+            out.visualization_preprocessor_reload_requested <+
+                vis_frp.preprocessor_reload_requested.constant(node_id);
+
 
             metadata <- any(...);
             metadata <+ node_model.visualization.frp.preprocessor.map(visualization::Metadata::new);
@@ -1760,6 +2617,27 @@ impl GraphEditorModel {
             out.enabled_visualization_path <+ enabled_visualization_path.on_change();
 
 
+            // === Visual Metadata ===
+
+            color_tag <- any(&node.set_color_tag, &node.view.color_tag);
+            visual_metadata <- all_with6(
+                &node.view.position, &color_tag, &visualization.visualization_path,
+                &visualization.visible, &visualization.pinned, &visualization.drag_offset,
+                |position, color_tag, path, visible, pinned, dock| VisualMetadata {
+                    position:              *position,
+                    color_tag:             *color_tag,
+                    visualization:         path.clone(),
+                    visualization_visible: *visible,
+                    visualization_pinned:  *pinned,
+                    visualization_dock:    *dock,
+                }
+            );
+            visual_metadata_changed <- visual_metadata.debounce().on_change();
+            out.visual_metadata_changed <+ visual_metadata_changed.map(
+                move |metadata| (node_id, metadata.clone())
+            );
+
+
             // === Read-only mode ===
 
             node.set_read_only <+ self.frp.input.set_read_only;
@@ -1768,6 +2646,16 @@ impl GraphEditorModel {
             // === Execution Environment ===
 
             node.set_execution_environment <+ self.frp.output.execution_environment;
+
+
+            // === View Mode ===
+
+            node.set_view_mode <+ self.frp.output.view_mode;
+
+
+            // === Color Palette ===
+
+            node.set_color_palette <+ self.frp.output.color_palette;
         }
 
         let initial_metadata = visualization::Metadata {
@@ -1775,6 +2663,17 @@ impl GraphEditorModel {
         };
         metadata.emit(initial_metadata);
         init.emit(());
+        node.set_view_mode(self.frp_public.output.view_mode.value());
+        node.set_color_palette(self.frp_public.output.color_palette.value());
+        node.show_value_preview(self.frp_public.output.value_previews_visible.value());
+        // The nodes are created also after the inspector was enabled, for example when entering or
+        // collapsing a function, so their networks are added to the inspected ones here.
+        let node_networks =
+            [node.view.frp().network(), input_frp.network(), &output_frp.network, &network];
+        for node_network in node_networks {
+            self.frp_inspector.inspect(node_network);
+        }
+        *node.bindings.borrow_mut() = Some(network);
 
         self.nodes.insert(node_id, node.clone_ref());
         node
@@ -1783,6 +2682,86 @@ impl GraphEditorModel {
 
 
 
+// =================
+// === IdCounter ===
+// =================
+
+/// The source of the [`NodeId`] and [`EdgeId`] values in the deterministic mode of the Graph
+/// Editor. See [`GraphEditor::new_deterministic`].
+#[derive(Debug)]
+struct IdCounter {
+    next: Cell<usize>,
+}
+
+impl IdCounter {
+    fn new(seed: usize) -> Self {
+        Self { next: Cell::new(seed) }
+    }
+
+    /// Allocate the next id. The nodes and edges share the counter, so that each id is used once.
+    fn next(&self) -> Id {
+        let id = self.next.get();
+        self.next.set(id + 1);
+        id.into()
+    }
+}
+
+
+
+// ================
+// === ViewPool ===
+// ================
+
+/// The views of the removed nodes and edges, kept for reuse. Constructing the display hierarchy
+/// and the FRP network of a node view is the most expensive part of adding a node, so the views of
+/// the removed nodes and edges are parked here, and reclaimed when new nodes and edges are added,
+/// e.g. when the displayed graph is replaced by another one.
+///
+/// The connections between a view and the Graph Editor network refer to the id of the node or
+/// the edge the view displays, so they are made in a separate network, dropped when the view is
+/// parked. Thus, the delayed outputs of a parked view, like the end of a hover animation, are not
+/// reported under the id of the node reusing the view, which makes the connections anew. The views
+/// are also reset when parked, see [`component::Node::reset`] and [`component::Edge::reset`].
+#[derive(Debug, Default)]
+struct ViewPool {
+    nodes: RefCell<Vec<component::Node>>,
+    edges: RefCell<Vec<component::Edge>>,
+}
+
+impl ViewPool {
+    /// Disconnect and detach the view of the removed node and park it, unless
+    /// [`MAX_POOLED_NODES`] views are parked already.
+    fn park_node(&self, node: &Node) {
+        node.bindings.take();
+        node.unset_parent();
+        if self.nodes.borrow().len() < MAX_POOLED_NODES {
+            node.view.reset();
+            self.nodes.borrow_mut().push(node.view.clone_ref());
+        }
+    }
+
+    /// Disconnect and detach the view of the removed edge and park it, unless
+    /// [`MAX_POOLED_EDGES`] views are parked already.
+    fn park_edge(&self, edge: &mut Edge) {
+        edge.bindings.take();
+        edge.unset_parent();
+        if self.edges.borrow().len() < MAX_POOLED_EDGES {
+            edge.view.reset();
+            self.edges.borrow_mut().push(edge.view.clone_ref());
+        }
+    }
+
+    fn take_node(&self) -> Option<component::Node> {
+        self.nodes.borrow_mut().pop()
+    }
+
+    fn take_edge(&self) -> Option<component::Edge> {
+        self.edges.borrow_mut().pop()
+    }
+}
+
+
+
 // ========================
 // === GraphEditorModel ===
 // ========================
@@ -1790,23 +2769,47 @@ impl GraphEditorModel {
 #[derive(Debug, display::Object)]
 #[allow(missing_docs)] // FIXME[everyone] Public-facing API should be documented.
 pub struct GraphEditorModel {
-    pub display_object:   display::object::Instance,
+    pub display_object:    display::object::Instance,
     // Required for dynamically creating nodes and edges.
-    pub app:              Application,
-    layers:               GraphLayers,
-    pub nodes:            Nodes,
-    edges:                RefCell<Edges>,
-    pub vis_registry:     visualization::Registry,
-    pub drop_manager:     ensogl_drop_manager::Manager,
-    pub navigator:        Navigator,
-    pub add_node_button:  Rc<component::add_node_button::AddNodeButton>,
-    tooltip:              Tooltip,
-    touch_state:          TouchState,
-    visualizations:       Visualizations,
-    frp:                  api::Private,
-    frp_public:           api::Public,
-    styles_frp:           StyleWatchFrp,
-    selection_controller: selection::Controller,
+    pub app:               Application,
+    layers:                GraphLayers,
+    pub nodes:             Nodes,
+    edges:                 RefCell<Edges>,
+    pub vis_registry:      visualization::Registry,
+    pub drop_manager:      ensogl_drop_manager::Manager,
+    paste_listener:        clipboard::PasteListener,
+    file_drop_highlight:   file_drop::DropHighlight,
+    pub navigator:         Navigator,
+    navigation_transition: component::navigation_transition::NavigationTransition,
+    pub add_node_button:   Rc<component::add_node_button::AddNodeButton>,
+    pub watch_panel:       component::watch_panel::WatchPanel,
+    pub tab_bar:           component::tab_bar::TabBar,
+    collapse_preview:      component::collapse_preview::CollapsePreview,
+    node_peek:             component::node_peek::NodePeek,
+    hover_card:            component::hover_card::HoverCard,
+    selection_count:       component::selection_count::SelectionCount,
+    lasso:                 component::lasso::Lasso,
+    accessibility:         component::accessibility::AccessibilityTree,
+    canvas_background:     component::canvas_background::CanvasBackground,
+    grid:                  component::grid::Grid,
+    watches:               component::watch_panel::Watches,
+    portals:               component::portal::Portals,
+    remote_presences:      component::remote_presence::RemotePresences,
+    node_index:            component::node_search::NodeIndex,
+    widget_updates:        RefCell<PendingWidgetUpdates>,
+    view_pool:             ViewPool,
+    /// Records the events of the Graph Editor FRP network in the debug mode.
+    pub frp_inspector:     FrpInspector,
+    camera_bookmarks:      RefCell<HashMap<u8, Vector3>>,
+    custom_node_actions:   RefCell<Vec<node::action_bar::CustomAction>>,
+    tooltip:               Tooltip,
+    touch_state:           TouchState,
+    visualizations:        Visualizations,
+    frp:                   api::Private,
+    frp_public:            api::Public,
+    styles_frp:            StyleWatchFrp,
+    selection_controller:  selection::Controller,
+    id_counter:            Option<IdCounter>,
 }
 
 
@@ -1815,6 +2818,10 @@ pub struct GraphEditorModel {
 impl GraphEditorModel {
     #[allow(missing_docs)] // FIXME[everyone] All pub functions should have docs.
     pub fn new(app: &Application, frp: &Frp) -> Self {
+        Self::new_with_id_counter(app, frp, None)
+    }
+
+    fn new_with_id_counter(app: &Application, frp: &Frp, id_counter: Option<IdCounter>) -> Self {
         let network = frp.network();
         let scene = &app.display.default_scene;
         let display_object = display::object::Instance::new_named("GraphEditor");
@@ -1825,10 +2832,34 @@ impl GraphEditorModel {
         let touch_state = TouchState::new(network, scene);
         let app = app.clone_ref();
         let navigator = Navigator::new(scene, &scene.camera());
+        let navigation_transition =
+            component::navigation_transition::NavigationTransition::new(&app, &navigator);
         let tooltip = Tooltip::new(&app);
         let add_node_button = Rc::new(component::add_node_button::AddNodeButton::new(&app));
+        let watch_panel = component::watch_panel::WatchPanel::new(&app);
+        let tab_bar = component::tab_bar::TabBar::new(&app);
+        let collapse_preview = component::collapse_preview::CollapsePreview::new(&app);
+        let node_peek = component::node_peek::NodePeek::new(&app);
+        let hover_card = component::hover_card::HoverCard::new(&app);
+        let selection_count = component::selection_count::SelectionCount::new(&app);
+        let lasso = component::lasso::Lasso::new(&app);
+        let accessibility = component::accessibility::AccessibilityTree::new(&app);
+        // Must be created before the grid, see the `canvas_background` module docs.
+        let canvas_background = component::canvas_background::CanvasBackground::new(&app);
+        let grid = component::grid::Grid::new(&app);
+        let watches = default();
+        let portals = default();
+        let remote_presences = component::remote_presence::RemotePresences::new(&app);
+        let node_index = default();
+        let widget_updates = default();
+        let view_pool = default();
+        let frp_inspector = FrpInspector::new(network);
+        let camera_bookmarks = default();
+        let custom_node_actions = default();
         let drop_manager =
             ensogl_drop_manager::Manager::new(&scene.dom.root.clone_ref().into(), scene);
+        let paste_listener = clipboard::PasteListener::new();
+        let file_drop_highlight = file_drop::DropHighlight::new(&app);
         let styles_frp = StyleWatchFrp::new(&scene.style_sheet);
         let selection_controller = selection::Controller::new(
             frp,
@@ -1848,28 +2879,96 @@ impl GraphEditorModel {
             edges,
             vis_registry,
             drop_manager,
+            paste_listener,
+            file_drop_highlight,
             tooltip,
             touch_state,
             visualizations,
             navigator,
+            navigation_transition,
             add_node_button,
+            watch_panel,
+            tab_bar,
+            collapse_preview,
+            node_peek,
+            hover_card,
+            selection_count,
+            lasso,
+            accessibility,
+            canvas_background,
+            grid,
+            watches,
+            portals,
+            remote_presences,
+            node_index,
+            widget_updates,
+            view_pool,
+            frp_inspector,
+            camera_bookmarks,
+            custom_node_actions,
             frp: frp.private.clone_ref(),
             frp_public: frp.public.clone_ref(),
             styles_frp,
             selection_controller,
+            id_counter,
         }
         .init()
     }
 
     fn init(self) -> Self {
         self.scene().add_child(&self.tooltip);
+        self.scene().add_child(&self.hover_card);
+        self.scene().add_child(&self.selection_count);
+        self.scene().add_child(&self.lasso);
         self.add_child(&*self.add_node_button);
+        self.add_child(&self.watch_panel);
+        self.add_child(&self.tab_bar);
+        self.add_child(&self.collapse_preview);
+        self.add_child(&self.node_peek);
+        self.add_child(&self.canvas_background);
+        self.add_child(&self.grid);
+        self.add_child(&self.file_drop_highlight);
+        self.add_child(&self.navigation_transition);
+        self.add_child(&self.remote_presences);
         self
     }
 
     fn scene(&self) -> &Scene {
         &self.app.display.default_scene
     }
+
+    /// The number of nodes in the displayed graph.
+    pub fn node_count(&self) -> usize {
+        self.nodes.all.len()
+    }
+
+    /// The number of edges in the displayed graph, including the edge being currently dragged.
+    pub fn edge_count(&self) -> usize {
+        self.edges.borrow().len()
+    }
+
+    /// The id of a new node or edge, given the id of its view. In the deterministic mode, the view
+    /// id is ignored and the id is taken from the [`IdCounter`].
+    fn allocate_id(&self, view_id: Id) -> Id {
+        self.id_counter.as_ref().map_or(view_id, IdCounter::next)
+    }
+}
+
+
+// === Custom Node Actions ===
+
+impl GraphEditorModel {
+    /// Add the custom action to the action bars of all nodes, including the nodes created later.
+    /// The action triggered by the user is reported by the `node_action_triggered` output.
+    pub fn register_node_action(&self, action: node::action_bar::CustomAction) {
+        for node in self.nodes.all.raw.borrow().values() {
+            node.view.add_custom_action(&action);
+        }
+        for view in self.view_pool.nodes.borrow().iter() {
+            view.add_custom_action(&action);
+        }
+        self.custom_node_actions.borrow_mut().push(action);
+    }
 }
 
 
@@ -1952,11 +3051,40 @@ impl GraphEditorModel {
     /// Remove node and all edges connected to it.
     #[profile(Debug)]
     fn remove_node(&self, node_id: NodeId) {
-        self.nodes.remove(&node_id);
+        if let Some(node) = self.nodes.remove(&node_id) {
+            self.view_pool.park_node(&node);
+        }
+        self.nodes.forget_stable_id(node_id);
+        self.nodes.forget_hidden_edges(node_id);
         self.nodes.selected.remove_item(&node_id);
         self.frp.output.on_visualization_select.emit(Switch::Off(node_id));
     }
 
+    /// Set or remove the breakpoint on the node. Returns `false` if the node does not exist.
+    fn toggle_node_breakpoint(&self, node_id: NodeId) -> bool {
+        self.with_node(node_id, |node| {
+            let breakpoint = !self.nodes.breakpoints.remove(&node_id);
+            if breakpoint {
+                self.nodes.breakpoints.insert(node_id);
+            }
+            node.view.set_breakpoint(breakpoint);
+        })
+        .is_some()
+    }
+
+    /// All nodes with a breakpoint, sorted.
+    fn breakpoints(&self) -> Vec<NodeId> {
+        let mut breakpoints = self.nodes.breakpoints.keys();
+        breakpoints.sort();
+        breakpoints
+    }
+
+    /// Add the selected nodes to the watch panel. Returns `true` if any watch was added.
+    fn add_selected_to_watch(&self) -> bool {
+        let added = self.nodes.all_selected().into_iter().map(|id| self.watches.add(id));
+        added.fold(false, |any_added, added| any_added || added)
+    }
+
     fn node_in_edges(&self, node_id: impl Into<NodeId>) -> Vec<EdgeId> {
         self.with_node(node_id.into(), |node| node.in_edges()).unwrap_or_default()
     }
@@ -1995,37 +3123,146 @@ impl GraphEditorModel {
         self.with_node(node_id, |node| node.set_freeze_macro(freeze));
     }
 
+    /// Derive the signature of the function created by collapsing the nodes from the edges
+    /// crossing the boundary of the collapsed nodes.
+    fn collapse_signature(&self, nodes: &[NodeId]) -> component::collapse_preview::Signature {
+        use component::collapse_preview::pattern_names;
+        let collapsed = nodes.iter().copied().collect();
+        let edges = self.edges.borrow();
+        let connections = edges.values().filter_map(|edge| {
+            Some(Connection { source: edge.source?, target: edge.target? })
+        });
+        let code_of = |source: EdgeEndpoint| {
+            let output = |node: &Node| node.model().output.port_expression(source.port);
+            self.with_node(source.node_id, output).flatten().unwrap_or_default().into()
+        };
+        let patterns = nodes.iter().filter_map(|node_id| {
+            self.with_node(*node_id, |node| node.model().output.pattern()).flatten()
+        });
+        let patterns = patterns.collect_vec();
+        let bound_names = patterns.iter().flat_map(|pattern| pattern_names(pattern)).collect();
+        component::collapse_preview::Signature::new(&collapsed, connections, code_of, bound_names)
+    }
+
+    /// Check whether the predicate holds for all selected nodes. Returns `false` if no node is
+    /// selected.
+    fn all_selected_nodes_satisfy(&self, predicate: impl Fn(&Node) -> bool) -> bool {
+        let selected = self.nodes.all_selected();
+        let satisfied = selected.iter().all(|id| self.with_node(*id, &predicate) == Some(true));
+        !selected.is_empty() && satisfied
+    }
+
     fn set_node_context_switch(&self, node_id: NodeId, context_switch: &Option<bool>) {
         self.with_node(node_id, |node| node.set_context_switch(*context_switch));
     }
 
-    fn set_node_comment(&self, node_id: NodeId, comment: &ImString) {
-        self.with_node(node_id, |node| node.set_comment(comment.clone()));
+    fn set_node_color_tag(&self, node_id: NodeId, tag: Option<color::Rgba>) {
+        self.with_node(node_id, |node| node.set_color_tag(tag));
     }
-}
 
+    fn set_node_width_override(&self, node_id: NodeId, width: Option<f32>) {
+        self.with_node(node_id, |node| node.set_width_override(width));
+    }
 
-// === Connect ===
-
-impl GraphEditorModel {
-    fn set_edge_freeze(&self, edge_id: EdgeId, is_frozen: bool) {
-        self.with_edge(edge_id, |edge| edge.view.set_disabled.emit(is_frozen));
+    fn set_node_collapsed(&self, node_id: NodeId, collapsed: bool) {
+        self.with_node(node_id, |node| node.set_collapsed(collapsed));
     }
-}
 
+    fn set_node_group(&self, node_id: NodeId, group: &Option<ImString>) {
+        self.with_node(node_id, |node| node.set_group(group.clone()));
+    }
 
-// === Position ===
+    fn set_node_description_pending(&self, node_id: NodeId) {
+        self.with_node(node_id, |node| node.set_description_pending(true));
+    }
 
-impl GraphEditorModel {
-    #[allow(missing_docs)] // FIXME[everyone] All pub functions should have docs.
-    #[profile(Debug)]
-    pub fn set_node_position(&self, node_id: NodeId, position: Vector2) {
-        self.with_node(node_id, |node| node.set_xy((position.x, position.y)));
-        self.refresh_edge_positions(self.node_in_and_out_edges(node_id));
+    fn set_node_description(&self, node_id: NodeId, description: &Option<ImString>) {
+        self.with_node(node_id, |node| {
+            node.set_description_suggestion(description.clone());
+            node.set_description_pending(false);
+        });
     }
 
-    #[profile(Debug)]
-    fn set_node_expression_usage_type(
+    /// Mark the nodes affected by the ongoing area selection, and unmark the nodes no longer
+    /// affected.
+    fn set_selection_preview(&self, preview: &selection::Preview, old: &selection::Preview) {
+        let old_nodes = old.selected.iter().chain(&old.deselected);
+        for node_id in old_nodes.filter(|node_id| preview.change(**node_id).is_none()) {
+            self.with_node(*node_id, |node| node.set_selection_preview(None));
+        }
+        let nodes = preview.selected.iter().chain(&preview.deselected);
+        for node_id in nodes {
+            let change = preview.change(*node_id);
+            self.with_node(*node_id, |node| node.set_selection_preview(change));
+        }
+    }
+
+    fn set_node_comment(&self, node_id: NodeId, comment: &ImString) {
+        self.with_node(node_id, |node| node.set_comment(comment.clone()));
+    }
+
+    fn set_node_method_pointer(&self, node_id: NodeId, method_pointer: Option<MethodPointer>) {
+        self.with_node(node_id, |node| *node.method_pointer.borrow_mut() = method_pointer);
+    }
+
+    fn method_pointer_of_selected_node(&self) -> Option<(NodeId, MethodPointer)> {
+        let node_id = self.nodes.last_selected()?;
+        let method_pointer = self.with_node(node_id, |node| node.method_pointer()).flatten()?;
+        Some((node_id, method_pointer))
+    }
+
+    /// The information displayed in the node's hover card.
+    fn hover_card_content(
+        &self,
+        node_id: NodeId,
+    ) -> Option<Rc<component::hover_card::HoverCardContent>> {
+        self.with_node(node_id, |node| {
+            Rc::new(component::hover_card::HoverCardContent {
+                node_type:          node.output_type.value(),
+                error:              node.error.value(),
+                execution_duration: node.execution_duration.value(),
+                doc_summary:        node.doc_summary.value(),
+            })
+        })
+    }
+
+    /// Display the peeked graph below the node. Returns `false` if the node does not exist.
+    fn show_node_peek(
+        &self,
+        node_id: NodeId,
+        graph: &Rc<component::node_peek::PeekedGraph>,
+    ) -> bool {
+        let Some(node_bbox) = self.with_node(node_id, |n| n.bounding_box.value()) else {
+            return false;
+        };
+        self.node_peek.set_xy(Vector2(node_bbox.left(), node_bbox.bottom()));
+        self.node_peek.show(graph.clone_ref());
+        true
+    }
+}
+
+
+// === Connect ===
+
+impl GraphEditorModel {
+    fn set_edge_freeze(&self, edge_id: EdgeId, is_frozen: bool) {
+        self.with_edge(edge_id, |edge| edge.view.set_disabled.emit(is_frozen));
+    }
+}
+
+
+// === Position ===
+
+impl GraphEditorModel {
+    #[allow(missing_docs)] // FIXME[everyone] All pub functions should have docs.
+    #[profile(Debug)]
+    pub fn set_node_position(&self, node_id: NodeId, position: Vector2) {
+        self.with_node(node_id, |node| node.set_xy((position.x, position.y)));
+        self.refresh_edge_positions(self.node_in_and_out_edges(node_id));
+    }
+
+    #[profile(Debug)]
+    fn set_node_expression_usage_type(
         &self,
         node_id: NodeId,
         ast_id: ast::Id,
@@ -2050,8 +3287,18 @@ impl GraphEditorModel {
         });
     }
 
-    fn update_node_widgets(&self, node_id: NodeId, updates: &CallWidgetsConfig) {
-        self.try_with_node(node_id, |node| node.view.update_widgets.emit(updates.clone()));
+    fn queue_node_widgets_update(&self, node_id: NodeId, update: &CallWidgetsConfig) {
+        self.widget_updates.borrow_mut().push(node_id, update);
+    }
+
+    /// Apply the widget updates queued since the last call. Returns the total number of the
+    /// dropped updates, see [`PendingWidgetUpdates`].
+    fn apply_queued_widget_updates(&self) -> usize {
+        let updates = self.widget_updates.borrow_mut().take();
+        for (node_id, update) in updates {
+            self.try_with_node(node_id, |node| node.view.update_widgets.emit(update));
+        }
+        self.widget_updates.borrow().dropped
     }
 
     fn disable_grid_snapping_for(&self, node_ids: &[NodeId]) {
@@ -2063,6 +3310,39 @@ impl GraphEditorModel {
         self.with_node(node_id, |node| node.position().xy()).unwrap_or_default()
     }
 
+    /// Return the bounding box of the node at its current position. Unlike
+    /// [`Self::node_bounding_box`], it accounts for the position changes not yet propagated by
+    /// the display object hierarchy update.
+    fn current_node_bounding_box(&self, node_id: NodeId) -> Option<selection::BoundingBox> {
+        self.with_node(node_id, |node| {
+            let pending_shift = node.position().xy() - node.view.position.value();
+            collision::translated(&node.bounding_box.value(), pending_shift)
+        })
+    }
+
+    /// Compute the new positions of the nodes pushed out of the way of the dragged nodes. Only the
+    /// nodes within [`COLLISION_NEIGHBORHOOD`] of the dragged ones are considered.
+    fn push_overlapping_nodes(&self, dragged: &[NodeId]) -> Vec<(NodeId, Vector2)> {
+        let boxes = self.nodes.all.keys().into_iter();
+        let boxes = boxes.filter_map(|id| Some((id, self.current_node_bounding_box(id)?)));
+        let (fixed, others): (Vec<_>, Vec<_>) = boxes.partition(|(id, _)| dragged.contains(id));
+        let fixed = fixed.into_iter().map(|(_, bbox)| bbox).collect_vec();
+        let neighborhood = fixed.iter().map(|bbox| {
+            let mut neighborhood = *bbox;
+            neighborhood.grow_x(2.0 * COLLISION_NEIGHBORHOOD);
+            neighborhood.grow_y(2.0 * COLLISION_NEIGHBORHOOD);
+            neighborhood
+        });
+        let neighborhood = neighborhood.collect_vec();
+        let in_neighborhood = |bbox: &selection::BoundingBox| {
+            neighborhood.iter().any(|area| area.intersects(bbox))
+        };
+        let movable = others.into_iter().filter(|(_, bbox)| in_neighborhood(bbox)).collect_vec();
+        let displacements = collision::relax(&fixed, &movable);
+        let new_position = |(id, offset): (NodeId, Vector2)| (id, self.node_position(id) + offset);
+        displacements.into_iter().map(new_position).collect()
+    }
+
     /// Return the bounding box of the node identified by `node_id`, or a default bounding box if
     /// the node was not found.
     pub fn node_bounding_box(&self, node_id: NodeId) -> selection::BoundingBox {
@@ -2076,6 +3356,159 @@ impl GraphEditorModel {
         (node_id, new_position)
     }
 
+    /// The connections between the nodes, as the pairs of the source and target node. Only the
+    /// edges attached at both ends are included.
+    fn attached_connections(&self) -> Vec<(NodeId, NodeId)> {
+        self.edges.borrow().attached_connections().collect()
+    }
+
+    /// A deterministic textual description of the graph: the expressions of the nodes with their
+    /// errors, and the connections between them. The nodes are numbered in the reading order, see
+    /// [`Nodes::ids_in_reading_order`]. Intended for the screen readers and the bug reports.
+    pub fn describe_graph(&self) -> ImString {
+        use component::accessibility::DescribedNode;
+        let ids = self.nodes.ids_in_reading_order();
+        let index_of: HashMap<NodeId, usize> =
+            ids.iter().enumerate().map(|(index, id)| (*id, index)).collect();
+        let nodes = ids.iter().map(|id| {
+            let expression = self.node_index.expression(*id).unwrap_or_default();
+            let error = self.with_node(*id, |node| node.error.value()).flatten();
+            let error = error.filter(|error| error.should_display()).map(|error| {
+                let kind = match *error.kind {
+                    node::error::Kind::Panic => "Panic",
+                    node::error::Kind::Dataflow => "Dataflow error",
+                    node::error::Kind::Warning => "Warning",
+                };
+                match (error.message.as_ref(), *error.propagated) {
+                    (_, true) => format!("{kind} propagated from an upstream node"),
+                    (Some(message), false) => format!("{kind}: {message}"),
+                    (None, false) => kind.to_owned(),
+                }
+            });
+            DescribedNode { expression, error }
+        });
+        let nodes = nodes.collect_vec();
+        let edges = self.edges.borrow();
+        let connections = edges.attached_connections().filter_map(|(source, target)| {
+            Some((*index_of.get(&source)?, *index_of.get(&target)?))
+        });
+        let connections = connections.collect_vec();
+        component::accessibility::describe_graph(&nodes, &connections).into()
+    }
+
+    /// Compute the current statistics of the graph.
+    fn graph_stats(&self) -> GraphStats {
+        let edges = self.edges.borrow();
+        let connections = edges.attached_connections();
+        let nodes = self.nodes.all.keys();
+        GraphStats {
+            node_count:          nodes.len(),
+            edge_count:          edges.len(),
+            detached_edge_count: edges
+                .values()
+                .filter(|edge| edge.source.is_none() || edge.target.is_none())
+                .count(),
+            selected_count:      self.nodes.selected.len(),
+            max_depth:           max_depth(nodes, connections),
+        }
+    }
+
+    /// Send a pulse along all outgoing edges of the node, showing that its value was recomputed.
+    fn pulse_out_edges(&self, node_id: NodeId) {
+        for edge_id in self.node_out_edges(node_id) {
+            self.with_edge(edge_id, |edge| edge.view.pulse());
+        }
+    }
+
+    /// Show the visual feedback of a rejected action on its target.
+    fn show_rejection(&self, target: RejectTarget) {
+        if let RejectTarget::Node(node_id) = target {
+            self.with_node(node_id, |node| node.view.flash_rejection());
+        }
+    }
+
+    /// Dim all nodes and edges unrelated to the highlighted part of the graph: the lineage of a
+    /// node if set, the nodes found by the node search if any, or the pipeline of the selected
+    /// nodes in focus mode. The edges inside the highlighted lineage display the flow direction
+    /// indicator.
+    fn update_dimming(
+        &self,
+        focus_mode: bool,
+        lineage: Option<(NodeId, LineageDirection)>,
+        found_nodes: Option<&HashSet<NodeId>>,
+    ) {
+        let edges = self.edges.borrow();
+        let connections = || {
+            edges.values().filter_map(|edge| Some((edge.source?.node_id, edge.target?.node_id)))
+        };
+        let lineage = lineage.filter(|(root, _)| self.nodes.get_cloned_ref(root).is_some());
+        let highlighted = if let Some((root, direction)) = lineage {
+            Some(lineage_closure(root, connections(), direction))
+        } else if let Some(found_nodes) = found_nodes {
+            Some(found_nodes.clone())
+        } else {
+            let selected = self.nodes.all_selected();
+            (focus_mode && !selected.is_empty()).then(|| pipeline_closure(selected, connections()))
+        };
+        let is_dimmed = |id: &NodeId| highlighted.as_ref().map_or(false, |h| !h.contains(id));
+        for (id, node) in &*self.nodes.all.raw.borrow() {
+            node.view.set_dimmed(is_dimmed(id));
+        }
+        for edge in edges.values() {
+            let source_dimmed = edge.source.map_or(false, |s| is_dimmed(&s.node_id));
+            let target_dimmed = edge.target.map_or(false, |t| is_dimmed(&t.node_id));
+            let is_dimmed = source_dimmed || target_dimmed;
+            let is_attached = edge.source.is_some() && edge.target.is_some();
+            edge.view.set_dimmed(is_dimmed);
+            edge.view.set_flow_indicator(lineage.is_some() && is_attached && !is_dimmed);
+        }
+    }
+
+    /// Mark the values of the node and all nodes downstream of it as stale.
+    fn mark_subtree_stale(&self, root: NodeId) {
+        if self.nodes.get_cloned_ref(&root).is_none() {
+            return;
+        }
+        let edges = self.edges.borrow();
+        let connections =
+            edges.values().filter_map(|edge| Some((edge.source?.node_id, edge.target?.node_id)));
+        let subtree = lineage_closure(root, connections, LineageDirection::Downstream);
+        for node_id in subtree {
+            self.with_node(node_id, |node| node.view.set_stale(true));
+        }
+    }
+
+    /// Summarize the warnings attached to the values of all the nodes.
+    fn warnings_summary(&self) -> node::warnings::WarningsSummary {
+        let nodes = self.nodes.all.raw.borrow();
+        let counts = nodes.values().map(|node| node.view.warnings.value().len());
+        node::warnings::WarningsSummary::from_counts(counts)
+    }
+
+    /// Mark the nodes and edges using a value poisoned by an error in one of their upstream nodes.
+    /// The nodes with errors propagated from other nodes are not considered the error sources.
+    fn update_error_propagation(&self) {
+        let nodes = self.nodes.all.raw.borrow();
+        let is_error_source = |node: &Node| {
+            node.view.error.value().map_or(false, |e| e.should_display() && !*e.propagated)
+        };
+        let sources = nodes.iter().filter(|(_, node)| is_error_source(node)).map(|(id, _)| *id);
+        let edges = self.edges.borrow();
+        let connections =
+            edges.values().filter_map(|edge| Some((edge.source?.node_id, edge.target?.node_id)));
+        let sources = sources.collect_vec();
+        let poisoned = poisoned_nodes(&sources, connections);
+        for (id, node) in nodes.iter() {
+            node.view.set_poisoned(poisoned.contains(id));
+        }
+        let carries_error = |id: NodeId| sources.contains(&id) || poisoned.contains(&id);
+        for edge in edges.values() {
+            let source_poisoned = edge.source.map_or(false, |s| carries_error(s.node_id));
+            let is_attached = edge.target.is_some();
+            edge.view.set_poisoned(source_poisoned && is_attached);
+        }
+    }
+
     /// Recalculate colors for edges in specified list. Returns a set of edges that have changed
     /// their color.
     pub fn refresh_edge_colors(&self, edge_ids: impl IntoIterator<Item = EdgeId>) -> Vec<EdgeId> {
@@ -2099,6 +3532,60 @@ impl GraphEditorModel {
             .collect()
     }
 
+    /// Recalculate colors of all edges. Returns a set of edges that have changed their color.
+    pub fn refresh_all_edge_colors(&self) -> Vec<EdgeId> {
+        let edge_ids = self.edges.borrow().keys().copied().collect_vec();
+        self.refresh_edge_colors(edge_ids)
+    }
+
+    /// Get the type of the value transmitted by the edge, i.e. the type of its source port.
+    pub fn edge_source_type(&self, edge_id: EdgeId) -> Option<Type> {
+        let source = self.with_edge(edge_id, |edge| edge.source)??;
+        self.with_node(source.node_id, |node| node.model().output.port_type(source.port))?
+    }
+
+    /// Set the label of the edge set by the user. Returns `false` if the edge does not exist.
+    fn set_edge_label(&self, edge_id: EdgeId, label: &Option<ImString>) -> bool {
+        let mut edges = self.edges.borrow_mut();
+        let edge = edges.get_mut(&edge_id);
+        edge.map(|edge| edge.label = label.clone()).is_some()
+    }
+
+    /// Collapse the fully connected edge into a portal. Returns the portal number, or `None` if the
+    /// edge does not exist or is detached.
+    fn create_portal_from_edge(&self, edge_id: EdgeId) -> Option<component::portal::PortalId> {
+        let edges = self.edges.borrow();
+        let edge = edges.get(&edge_id).filter(|edge| edge.target.is_some())?;
+        let portal = self.portals.collapse(edge_id, edge.source?);
+        edge.view.set_portal(Some(portal));
+        Some(portal)
+    }
+
+    /// Expand the portals of the edge back into the edge.
+    fn expand_portal(&self, edge_id: EdgeId) {
+        if self.portals.expand(edge_id) {
+            self.with_edge(edge_id, |edge| edge.view.set_portal(None));
+        }
+    }
+
+    /// Update the labels displayed by the edges: the label set by the user, or the abbreviated
+    /// type of the transmitted value if the edge types are shown.
+    fn refresh_edge_labels(&self, edge_ids: impl IntoIterator<Item = EdgeId>) {
+        let show_types = self.frp_public.output.edge_types_visible.value();
+        for edge_id in edge_ids {
+            let user_label = self.edges.borrow().get(&edge_id).map(|edge| edge.label.clone());
+            let Some(user_label) = user_label else { continue };
+            let type_label = || {
+                let source_type = self.edge_source_type(edge_id)?;
+                (!source_type.is_any()).then(|| source_type.abbreviate().0)
+            };
+            let label = user_label.or_else(|| show_types.then(type_label).flatten());
+            if let Some(edge) = self.edges.borrow_mut().get_mut(&edge_id) {
+                edge.set_shown_label(label);
+            }
+        }
+    }
+
     /// Refresh the source and target position of the edges identified by `edge_ids`.
     pub fn refresh_edge_positions(&self, edge_ids: impl IntoIterator<Item = EdgeId>) {
         let edges = self.edges.borrow();
@@ -2109,7 +3596,7 @@ impl GraphEditorModel {
                 self.with_node(edge_source.node_id, |node| {
                     let node_width = node.model().width();
                     let node_height = node.model().height();
-                    let new_position = node.position().xy() + Vector2::new(node_width / 2.0, 0.0);
+                    let new_position = node.position().xy() + node.model().offset_to_center();
                     let prev_position = edge.position().xy();
 
                     if prev_position != new_position {
@@ -2167,6 +3654,42 @@ impl GraphEditorModel {
         self.with_edge(id, |edge| edge.connection).flatten()
     }
 
+    /// Hide the edges replaced by the edge stubs, and show the other ones.
+    fn refresh_edge_visibility(&self, edge_ids: impl IntoIterator<Item = EdgeId>) {
+        for edge_id in edge_ids {
+            self.with_edge(edge_id, |edge| {
+                let is_attached = edge.source.is_some() && edge.target.is_some();
+                let connection = edge.connection.filter(|_| is_attached);
+                let hidden = connection.map_or(false, |c| self.nodes.is_connection_hidden(c));
+                edge.view.set_hidden(hidden);
+            });
+        }
+    }
+
+    /// Update the numbers of the hidden edges displayed on the edge stubs of the nodes.
+    fn refresh_edge_stubs(&self, node_ids: impl IntoIterator<Item = NodeId>) {
+        let count_connected = |edge_ids: Vec<EdgeId>| {
+            edge_ids.into_iter().filter(|id| self.edge_connection(*id).is_some()).count()
+        };
+        for node_id in node_ids {
+            let hidden_edges = self.nodes.edges_hidden.contains(&node_id).then(|| {
+                let incoming = count_connected(self.node_in_edges(node_id));
+                let outgoing = count_connected(self.node_out_edges(node_id));
+                node::edge_stubs::HiddenEdges { incoming, outgoing }
+            });
+            self.with_node(node_id, |node| node.set_hidden_edges(hidden_edges));
+        }
+    }
+
+    /// Emphasize the nodes and the ports connected by the hovered edge, or remove the emphasis.
+    fn set_connection_emphasis(&self, connection: Connection, emphasized: bool) {
+        let Connection { source, target } = connection;
+        let source_emphasis = emphasized.then_some(node::EdgeEmphasis::Source);
+        let target_emphasis = emphasized.then_some(node::EdgeEmphasis::Target(target.port));
+        self.with_node(source.node_id, |node| node.set_edge_emphasis(source_emphasis));
+        self.with_node(target.node_id, |node| node.set_edge_emphasis(target_emphasis));
+    }
+
     fn edge_target(&self, id: EdgeId) -> Option<EdgeEndpoint> {
         self.with_edge(id, |edge| edge.target).flatten()
     }
@@ -2185,13 +3708,62 @@ impl GraphEditorModel {
         hover_target.and_then(|tgt| self.node_color(tgt.node_id))
     }
 
+    /// The position of the input port in the scene coordinates.
+    fn input_port_position(&self, port: EdgeEndpoint) -> Option<Vector2> {
+        self.try_with_node(port.node_id, |node| {
+            node.position().xy() + node.model().input.port_offset(port.port)
+        })
+    }
+
+    /// Find the input port to which the edge dragged from `source` snaps when the cursor is at
+    /// `position`. Only the ports compatible with the source are considered. The port the edge is
+    /// already `snapped` to is kept until the cursor leaves its radius extended by
+    /// [`PORT_UNSNAP_FACTOR`].
+    fn port_snap_target(
+        &self,
+        source: EdgeEndpoint,
+        position: Vector2,
+        radius: f32,
+        snapped: Option<EdgeEndpoint>,
+    ) -> Option<EdgeEndpoint> {
+        let distance = |port| self.input_port_position(port).map(|p| (p - position).norm());
+        let unsnap_radius = radius * PORT_UNSNAP_FACTOR;
+        let kept = snapped.filter(|port| distance(*port).map_or(false, |d| d < unsnap_radius));
+        kept.or_else(|| {
+            let nodes = self.nodes.all.raw.borrow();
+            let nearby_nodes = nodes.iter().filter(|(id, node)| {
+                let bbox = node.bounding_box.value();
+                **id != source.node_id && bbox.squared_distance_to_point(position) < radius * radius
+            });
+            let ports = nearby_nodes.flat_map(|(id, node)| {
+                node.model().input.port_ids().into_iter().map(|port| EdgeEndpoint::new(*id, port))
+            });
+            let in_radius = ports.filter_map(|port| Some((port, distance(port)?)));
+            let in_radius = in_radius.filter(|(_, distance)| *distance < radius);
+            let is_compatible = |port: EdgeEndpoint| self.type_mismatch(source, port).is_none();
+            let compatible = in_radius.filter(|(port, _)| is_compatible(*port));
+            compatible.min_by(|(_, a), (_, b)| a.total_cmp(b)).map(|(port, _)| port)
+        })
+    }
+
+    /// Check whether the value of the `source` port may be passed to the `target` port. Returns
+    /// the types of both ports if they are known and incompatible.
+    fn type_mismatch(&self, source: EdgeEndpoint, target: EdgeEndpoint) -> Option<(Type, Type)> {
+        let output_type = |node: &Node| node.model().output.port_type(source.port);
+        let input_type = |node: &Node| node.model().input.port_type(target.port);
+        let source_type = self.with_node(source.node_id, output_type)??;
+        let target_type = self.with_node(target.node_id, input_type)??;
+        (!target_type.accepts(&source_type)).as_some((source_type, target_type))
+    }
+
     /// Retrieve the color of the edge. Does not recomputes it, but returns the cached value.
     fn edge_color(&self, edge_id: EdgeId) -> color::Lcha {
         self.with_edge(edge_id, |edge| edge.color).unwrap_or_else(|| self.edge_fallback_color())
     }
 
     fn edge_fallback_color(&self) -> color::Lcha {
-        self.styles_frp.get_color(theme::code::types::any::selection).value().into()
+        let color = self.styles_frp.get_color(theme::code::types::any::selection).value().into();
+        self.frp_public.output.color_palette.value().adjust(color)
     }
 
     /// Pan the camera to fully fit the `target_bbox` (expressed in scene coordinates) into a
@@ -2223,6 +3795,22 @@ impl GraphEditorModel {
         self.navigator.emit_pan_event(PanEvent::new(-pan_xy * scene.camera().zoom()));
     }
 
+    fn node_center(&self, node_id: NodeId) -> Option<Vector2> {
+        self.with_node(node_id, |node| node.bounding_box.value().center())
+    }
+
+    fn save_camera_bookmark(&self, slot: u8) {
+        let position = self.scene().camera().position();
+        self.camera_bookmarks.borrow_mut().insert(slot, position);
+    }
+
+    fn restore_camera_bookmark(&self, slot: u8) {
+        let position = self.camera_bookmarks.borrow().get(&slot).copied();
+        if let Some(position) = position {
+            self.navigator.animate_to(position);
+        }
+    }
+
     fn pan_camera_to_node(&self, node_id: NodeId) {
         use theme::graph_editor::screen_margin_when_panning_camera_to_node as pan_margin;
         let Some(node_bbox) = self.with_node(node_id, |n| n.bounding_box.value()) else { return };
@@ -2244,6 +3832,199 @@ impl GraphEditorModel {
 }
 
 
+// === Hit Testing ===
+
+/// The area of the bounding box, used to prefer the innermost of the overlapping elements.
+fn area(bounding_box: &selection::BoundingBox) -> f32 {
+    bounding_box.width() * bounding_box.height()
+}
+
+impl GraphEditorModel {
+    /// Convert the position in the screen coordinates, as used by the mouse cursor, to the scene
+    /// coordinates.
+    fn screen_to_scene_position(&self, screen_position: Vector2) -> Vector2 {
+        let position = Vector3(screen_position.x, screen_position.y, 0.0);
+        self.scene().screen_to_scene_coordinates(position).xy()
+    }
+
+    /// The node displayed at the given position in the screen coordinates. The node's
+    /// visualization is considered a part of the node. If the nodes overlap, the smallest one is
+    /// returned.
+    pub fn node_at_screen_position(&self, screen_position: Vector2) -> Option<NodeId> {
+        self.node_at_scene_position(self.screen_to_scene_position(screen_position))
+    }
+
+    fn node_at_scene_position(&self, position: Vector2) -> Option<NodeId> {
+        let nodes = self.nodes.all.raw.borrow();
+        let hits = nodes.iter().filter_map(|(id, node)| {
+            let bbox = node.bounding_box.value();
+            bbox.contains_inclusive(position).as_some((*id, area(&bbox)))
+        });
+        hits.min_by(|(_, a), (_, b)| a.total_cmp(b)).map(|(id, _)| id)
+    }
+
+    /// The input port displayed at the given position in the screen coordinates. If the ports
+    /// overlap, e.g. the port of the whole expression and the ports of its arguments, the smallest
+    /// one is returned.
+    pub fn input_port_at_screen_position(&self, screen_position: Vector2) -> Option<EdgeEndpoint> {
+        self.input_port_at_scene_position(self.screen_to_scene_position(screen_position))
+    }
+
+    fn input_port_at_scene_position(&self, position: Vector2) -> Option<EdgeEndpoint> {
+        let node_id = self.node_at_scene_position(position)?;
+        self.with_node(node_id, |node| {
+            let input = &node.model().input;
+            let offset = position - node.position().xy();
+            let hits = input.port_ids().into_iter().filter_map(|port| {
+                let size = input.port_size(port);
+                let bottom_left = input.port_offset(port) - size / 2.0;
+                let bbox = selection::BoundingBox::from_bottom_left_position_and_size(
+                    bottom_left,
+                    size,
+                );
+                bbox.contains_inclusive(offset).as_some((port, area(&bbox)))
+            });
+            let port = hits.min_by(|(_, a), (_, b)| a.total_cmp(b)).map(|(port, _)| port);
+            port.map(|port| EdgeEndpoint::new(node_id, port))
+        })?
+    }
+
+    /// The edge displayed at the given position in the screen coordinates. The edges collapsed
+    /// into portals and the hidden edges are never returned.
+    pub fn edge_at_screen_position(&self, screen_position: Vector2) -> Option<EdgeId> {
+        self.edge_at_scene_position(self.screen_to_scene_position(screen_position))
+    }
+
+    fn edge_at_scene_position(&self, position: Vector2) -> Option<EdgeId> {
+        let edges = self.edges.borrow();
+        let mut hits = edges.iter().filter(|(_, edge)| edge.view.contains_scene_position(position));
+        hits.next().map(|(id, _)| *id)
+    }
+}
+
+
+// === Thumbnail ===
+
+impl GraphEditorModel {
+    /// Render the nodes and edges of the displayed graph into an image fitting in `max_size` (in
+    /// pixels), e.g. to be used as the preview of the project. The whole graph is rendered,
+    /// regardless of the camera position, and the image is never larger than the graph at zoom 1.
+    ///
+    /// The future resolves during the next frame, to [`None`] if the graph is empty or the image
+    /// could not be rendered.
+    pub fn render_graph_thumbnail(
+        &self,
+        max_size: Vector2<i32>,
+    ) -> impl std::future::Future<Output = Option<web_sys::ImageData>> {
+        let boxes = self.nodes.all.keys().into_iter().map(|id| self.node_bounding_box(id));
+        let graph_bbox = boxes.reduce(|a, b| {
+            let min = Vector2(a.left().min(b.left()), a.bottom().min(b.bottom()));
+            let max = Vector2(a.right().max(b.right()), a.top().max(b.top()));
+            selection::BoundingBox::from_corners(min, max)
+        });
+        let layout = graph_bbox.and_then(|bbox| thumbnail_layout(bbox, max_size));
+        let image = layout.map(|(center, size, scale)| {
+            let camera = display::camera::Camera2d::new();
+            camera.set_screen(size.x as f32 / scale, size.y as f32 / scale);
+            camera.mod_position(|position| {
+                position.x = center.x;
+                position.y = center.y;
+            });
+            let layers = &self.scene().layers;
+            let layers = [&layers.below_main, &layers.main, &layers.label, &layers.above_nodes];
+            let layers = layers.map(|layer| layer.clone_ref());
+            let thumbnails = self.scene().extension::<display::render::passes::Thumbnails>();
+            thumbnails.render(&layers, &camera, size)
+        });
+        async move {
+            match image {
+                Some(image) => image.await,
+                None => None,
+            }
+        }
+    }
+}
+
+
+// === View Metadata ===
+
+impl GraphEditorModel {
+    fn node_view_metadata(&self, node_id: NodeId) -> Option<ViewMetadata> {
+        self.with_node(node_id, |node| {
+            let visualization = node.visualization();
+            let size = visualization.size.value();
+            let resized = size != visualization::container::DEFAULT_SIZE;
+            let pinned = visualization.pinned.value();
+            ViewMetadata {
+                visualization_enabled: visualization.visible.value(),
+                visualization:         visualization.visualization_path.value(),
+                visualization_size:    resized.as_some(size),
+                visualization_dock:    (!pinned).as_some(visualization.drag_offset.value()),
+                color_tag:             node.view.current_color_tag.value(),
+                width:                 node.view.width_override.value(),
+                collapsed:             node.view.collapsed.value(),
+                group:                 node.view.group.value(),
+            }
+        })
+    }
+
+    fn export_view_metadata(&self) -> HashMap<NodeId, ViewMetadata> {
+        let node_ids = self.nodes.all.keys().into_iter();
+        node_ids.filter_map(|id| Some((id, self.node_view_metadata(id)?))).collect()
+    }
+
+    fn import_view_metadata(&self, metadata: &HashMap<NodeId, ViewMetadata>) {
+        for (node_id, metadata) in metadata {
+            let Some(node) = self.nodes.get_cloned_ref(node_id) else { continue };
+            let visualization = &node.model().visualization.frp;
+            let path = metadata.visualization.as_ref();
+            let definition = path.and_then(|path| self.vis_registry.definition_from_path(path));
+            if definition.is_some() {
+                visualization.set_visualization.emit(definition);
+            }
+            if let Some(size) = metadata.visualization_size {
+                visualization.set_size.emit(size);
+            }
+            visualization.set_drag_offset.emit(metadata.visualization_dock.unwrap_or_default());
+            if metadata.visualization_enabled {
+                node.enable_visualization();
+            } else {
+                node.disable_visualization();
+            }
+            node.set_color_tag(metadata.color_tag);
+            node.set_width_override(metadata.width);
+            node.set_collapsed(metadata.collapsed);
+            node.set_group(metadata.group.clone());
+        }
+    }
+}
+
+
+// === Remote Presences ===
+
+impl GraphEditorModel {
+    fn update_remote_selection_outlines(&self) {
+        let node_bounding_box = |id| self.try_with_node(id, |node| node.inner_bounding_box.value());
+        self.remote_presences.update_outlines(node_bounding_box);
+    }
+}
+
+
+// === Edit Conflicts ===
+
+impl GraphEditorModel {
+    fn accept_remote_edit(
+        &self,
+        node_id: NodeId,
+    ) -> Option<(NodeId, node::conflict::ConflictInfo)> {
+        let node = self.nodes.get_cloned_ref(&node_id)?;
+        let conflict = node.edit_conflict.value()?;
+        node.set_edit_conflict(None);
+        Some((node_id, conflict))
+    }
+}
+
+
 // === Utilities ===
 
 fn some_if_positive(x: f32) -> Option<f32> {
@@ -2254,6 +4035,132 @@ fn some_if_negative(x: f32) -> Option<f32> {
     (x < 0.0).as_some(x)
 }
 
+/// Compute the layout of the thumbnail of the graph within `graph_bbox`: the center of the
+/// rendered area, the size of the image fitting in `max_size`, and the scale of the image relative
+/// to the graph at zoom 1. The graph is surrounded by [`THUMBNAIL_MARGIN`].
+fn thumbnail_layout(
+    graph_bbox: selection::BoundingBox,
+    max_size: Vector2<i32>,
+) -> Option<(Vector2, Vector2<i32>, f32)> {
+    let width = graph_bbox.width() + 2.0 * THUMBNAIL_MARGIN;
+    let height = graph_bbox.height() + 2.0 * THUMBNAIL_MARGIN;
+    let scale = (max_size.x as f32 / width).min(max_size.y as f32 / height).min(1.0);
+    let size = Vector2((width * scale).round() as i32, (height * scale).round() as i32);
+    (size.x > 0 && size.y > 0).as_some((graph_bbox.center(), size, scale))
+}
+
+/// Compute the pipeline of given nodes: the nodes themselves, together with all nodes they depend
+/// on (upstream) and all nodes depending on them (downstream). The `connections` are pairs of
+/// source and target node of each edge. Note that nodes which are only reachable by going both
+/// upstream and downstream (e.g. other consumers of an upstream node) are not part of the pipeline.
+fn pipeline_closure(
+    roots: impl IntoIterator<Item = NodeId>,
+    connections: impl IntoIterator<Item = (NodeId, NodeId)>,
+) -> HashSet<NodeId> {
+    let (upstream, downstream) = adjacency(connections);
+    let roots = roots.into_iter().collect_vec();
+    let mut closure: HashSet<NodeId> = roots.iter().copied().collect();
+    closure.extend(reachable(&roots, &upstream));
+    closure.extend(reachable(&roots, &downstream));
+    closure
+}
+
+/// The direction of data flow in which the lineage of a node is traversed. See
+/// [`lineage_closure`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LineageDirection {
+    /// The nodes the node's value is computed from.
+    Upstream,
+    /// The nodes using the node's value.
+    Downstream,
+}
+
+/// Compute the lineage of the given node: the node itself, together with all nodes transitively
+/// connected to it in the given direction. The `connections` are pairs of source and target node
+/// of each edge.
+fn lineage_closure(
+    root: NodeId,
+    connections: impl IntoIterator<Item = (NodeId, NodeId)>,
+    direction: LineageDirection,
+) -> HashSet<NodeId> {
+    let (upstream, downstream) = adjacency(connections);
+    let neighbors = match direction {
+        LineageDirection::Upstream => &upstream,
+        LineageDirection::Downstream => &downstream,
+    };
+    reachable(&[root], neighbors)
+}
+
+/// The nodes downstream of the error sources, whose values are poisoned by the errors. The error
+/// sources themselves are not included, even if one is downstream of another.
+fn poisoned_nodes(
+    error_sources: &[NodeId],
+    connections: impl IntoIterator<Item = (NodeId, NodeId)>,
+) -> HashSet<NodeId> {
+    let (_, downstream) = adjacency(connections);
+    let mut poisoned = reachable(error_sources, &downstream);
+    poisoned.retain(|node| !error_sources.contains(node));
+    poisoned
+}
+
+/// Compute the number of nodes on the longest chain of connected nodes. The `connections` are
+/// pairs of source and target node of each edge. Nodes in a cycle, or downstream of one, are not
+/// counted.
+fn max_depth(
+    nodes: impl IntoIterator<Item = NodeId>,
+    connections: impl IntoIterator<Item = (NodeId, NodeId)>,
+) -> usize {
+    let (upstream, downstream) = adjacency(connections);
+    let mut pending_inputs: HashMap<NodeId, usize> =
+        nodes.into_iter().map(|node| (node, upstream.get(&node).map_or(0, Vec::len))).collect();
+    let mut depths: HashMap<NodeId, usize> = pending_inputs.keys().map(|node| (*node, 1)).collect();
+    let ready = pending_inputs.iter().filter(|(_, n)| **n == 0).map(|(node, _)| *node);
+    let mut ready = ready.collect_vec();
+    let mut max_depth = 0;
+    while let Some(node) = ready.pop() {
+        let depth = depths.get(&node).copied().unwrap_or(1);
+        max_depth = max_depth.max(depth);
+        for next in downstream.get(&node).into_iter().flatten() {
+            let next_depth = depths.entry(*next).or_default();
+            *next_depth = (*next_depth).max(depth + 1);
+            if let Some(pending) = pending_inputs.get_mut(next) {
+                *pending -= 1;
+                if *pending == 0 {
+                    ready.push(*next);
+                }
+            }
+        }
+    }
+    max_depth
+}
+
+/// Split the connections (pairs of source and target node) into maps of upstream and downstream
+/// neighbors of each node.
+fn adjacency(
+    connections: impl IntoIterator<Item = (NodeId, NodeId)>,
+) -> (HashMap<NodeId, Vec<NodeId>>, HashMap<NodeId, Vec<NodeId>>) {
+    let mut upstream: HashMap<NodeId, Vec<NodeId>> = default();
+    let mut downstream: HashMap<NodeId, Vec<NodeId>> = default();
+    for (source, target) in connections {
+        upstream.entry(target).or_default().push(source);
+        downstream.entry(source).or_default().push(target);
+    }
+    (upstream, downstream)
+}
+
+/// All nodes reachable from the roots (including the roots) by following the `neighbors` map.
+fn reachable(roots: &[NodeId], neighbors: &HashMap<NodeId, Vec<NodeId>>) -> HashSet<NodeId> {
+    let mut visited: HashSet<NodeId> = default();
+    let mut to_visit = roots.to_vec();
+    while let Some(node) = to_visit.pop() {
+        if visited.insert(node) {
+            let next = neighbors.get(&node).into_iter().flatten();
+            to_visit.extend(next.filter(|n| !visited.contains(n)));
+        }
+    }
+    visited
+}
+
 
 
 // ============================
@@ -2279,6 +4186,8 @@ struct BgInteractionFrp {
 struct EdgePointerFrp {
     source_click: frp::Any<EdgeId>,
     target_click: frp::Any<EdgeId>,
+    /// The mouse entered or left the edge.
+    hovered:      frp::Any<(EdgeId, bool)>,
 }
 
 /// Set of internal FRP signals initialized in [`GraphEditor::frp_init_edge_state`].
@@ -2302,10 +4211,13 @@ struct EdgeStateFrp {
     /// detached edge view, use `clear_detached_edge` signal. If there is no detached edge
     /// currently set, this signal is ignored.
     break_detached_connection: frp::Any_,
+    /// The input port to which the detached edge is currently snapped. Dropping the edge connects
+    /// it to that port, even if the cursor does not hover it.
+    snapped_target:            frp::Any<Option<EdgeEndpoint>>,
 }
 
 struct EdgeColorFrp {
-    edges_with_updated_color: frp::Stream<Vec<EdgeId>>,
+    edges_with_updated_color: frp::Any<Vec<EdgeId>>,
 }
 
 // Set of internal FRP signals initialized in [`GraphEditor::frp_init_edge_interaction`].
@@ -2336,6 +4248,17 @@ impl GraphEditor {
         GraphEditor { model, frp }.init()
     }
 
+    /// Create a Graph Editor allocating the [`NodeId`] and [`EdgeId`] values from a counter
+    /// starting at `seed`, instead of deriving them from the ids of the display objects. The ids
+    /// then depend only on the order in which the nodes and edges are created, so the emitted FRP
+    /// events are the same across runs, which makes them usable in the snapshot tests.
+    pub fn new_deterministic(app: &Application, seed: usize) -> Self {
+        let frp = Frp::new();
+        let id_counter = Some(IdCounter::new(seed));
+        let model = Rc::new(GraphEditorModel::new_with_id_counter(app, &frp, id_counter));
+        GraphEditor { model, frp }.init()
+    }
+
     /// Initialize graph editor FRP network.
     fn init(self) -> Self {
         let node_expr = self.frp_init_node_expression();
@@ -2343,6 +4266,8 @@ impl GraphEditor {
         let edge_pointer = self.frp_init_edge_pointer();
         let edge_state = self.frp_init_edge_state(edge_pointer.clone_ref());
         let edge_color = self.frp_init_edge_colors(&node_expr, &edge_state);
+        self.frp_init_edge_labels(&node_expr, &edge_state);
+        self.frp_init_hidden_edges(&edge_state);
         self.frp_init_edge_positions(&edge_state);
         self.frp_init_node_connections(&edge_state, &edge_color);
         let create_node_from_edge =
@@ -2362,6 +4287,8 @@ impl GraphEditor {
         let network = self.frp.network();
         let model = &self.model;
         let input = &self.frp.input;
+        let out = &self.frp.private.output;
+        let on_before_rendering = ensogl::animation::on_before_rendering();
 
         frp::extend! { network
             node_with_new_expression_type <- input.set_expression_usage_type
@@ -2369,7 +4296,14 @@ impl GraphEditor {
                 model.set_node_expression_usage_type(*node_id,*ast_id,maybe_type.clone());
                 *node_id
             })).batch_unique().iter();
-            eval input.update_node_widgets(((id, widgets)) model.update_node_widgets(*id, widgets));
+            widgets_updated <- input.update_node_widgets.map(
+                f!(((id, update)) model.queue_node_widgets_update(*id, update))
+            );
+            apply_widget_updates <- on_before_rendering.sync_gate(&widgets_updated);
+            widget_updates_dropped <- apply_widget_updates.map(
+                f_!(model.apply_queued_widget_updates())
+            );
+            out.widget_updates_dropped <+ widget_updates_dropped.on_change();
             eval input.set_node_expression(((id, expr)) model.set_node_expression(id, expr));
             eval input.edit_node_expression(
                 ((id, range, ins)) model.edit_node_expression(*id, range, ins)
@@ -2429,8 +4363,9 @@ impl GraphEditor {
         frp::extend! { network
             source_click <- any(...);
             target_click <- any(...);
+            hovered <- any(...);
         }
-        EdgePointerFrp { source_click, target_click }
+        EdgePointerFrp { source_click, target_click, hovered }
     }
 
     // Initialize the maintenance of edge state. Updates the edge views according to the set of
@@ -2500,6 +4435,9 @@ impl GraphEditor {
             detached_to_break <- detached_edge.sample(&break_detached_connection);
             out.connection_broken <+ detached_to_break
                 .filter_map(f!((detached) model.edge_connection(detached.as_ref()?.edge_id()?)));
+
+            // Set in [`GraphEditor::frp_init_detached_edge_position`].
+            snapped_target <- any(...);
         }
 
         EdgeStateFrp {
@@ -2508,6 +4446,7 @@ impl GraphEditor {
             set_detached_edge,
             clear_detached_edge,
             break_detached_connection,
+            snapped_target,
         }
     }
 
@@ -2519,6 +4458,7 @@ impl GraphEditor {
         bg: &BgInteractionFrp,
     ) -> EdgeInteractionFrp {
         self.frp_init_edge_click(state, pointer);
+        self.frp_init_edge_hover(pointer);
         self.frp_init_edge_creation(state);
         self.frp_init_detached_edge_position(state);
         let create_node_from_edge = self.frp_init_edge_bg_drop(state, bg);
@@ -2530,11 +4470,16 @@ impl GraphEditor {
         let input = &self.frp.private.input;
         let out = &self.frp.private.output;
         let model = &self.model;
+        let touch = &model.touch_state;
 
         frp::extend! { network
             cannot_interact <- input.set_read_only || out.has_detached_edge;
             source_click <- pointer.source_click.gate_not(&cannot_interact);
             target_click <- pointer.target_click.gate_not(&cannot_interact);
+            // Grabbing the edge detaches one of its ends right away. If the pointer is dragged
+            // before release, the edge is in the drag mode and is dropped on release.
+            grabbed_edge <- any(source_click, target_click);
+            eval grabbed_edge ((id) touch.edges.down.emit(*id));
             detach_source <- source_click.filter_map(
                 f!((id) model.with_edge(*id, Edge::as_detached_at_source).flatten())
             );
@@ -2550,12 +4495,37 @@ impl GraphEditor {
         }
     }
 
-    fn frp_init_edge_creation(&self, state: &EdgeStateFrp) {
+    /// Emphasize the nodes and ports connected by the hovered edge, making it easier to trace a
+    /// single connection.
+    fn frp_init_edge_hover(&self, pointer: &EdgePointerFrp) {
         let network = self.frp.network();
-        let input = &self.frp.private.input;
-        let out = &self.frp.private.output;
         let model = &self.model;
-        let touch = &model.touch_state;
+
+        frp::extend! { network
+            hovered_connection <- pointer.hovered.map(f!(((id, hovered))
+                hovered.then(|| model.edge_connection(*id)).flatten()
+            )).on_change();
+            // The connection is remembered, as the edge may be already removed when unhovered.
+            previous_connection <- hovered_connection.previous();
+            eval previous_connection ((connection)
+                if let Some(connection) = connection {
+                    model.set_connection_emphasis(*connection, false)
+                }
+            );
+            eval hovered_connection ((connection)
+                if let Some(connection) = connection {
+                    model.set_connection_emphasis(*connection, true)
+                }
+            );
+        }
+    }
+
+    fn frp_init_edge_creation(&self, state: &EdgeStateFrp) {
+        let network = self.frp.network();
+        let input = &self.frp.private.input;
+        let out = &self.frp.private.output;
+        let model = &self.model;
+        let touch = &model.touch_state;
         let scene = model.scene();
         let mouse = &scene.mouse.frp_deprecated;
 
@@ -2571,15 +4541,39 @@ impl GraphEditor {
 
             // Attach detached edge to node port when clicking or releasing on node port.
             port_output_mouse_up <- out.hover_node_output.sample(&mouse.up_primary).unwrap();
-            port_input_mouse_up <- out.hover_node_input.sample(&mouse.up_primary).unwrap();
+            drop_target_input <- out.hover_node_input.all_with(&state.snapped_target,
+                |hovered, snapped| hovered.or(*snapped)
+            );
+            port_input_mouse_up <- drop_target_input.sample(&mouse.up_primary).unwrap();
             attach_edge_output <- any(port_output_mouse_up, touch.output_port.selected);
             attach_edge_input <- any(port_input_mouse_up, touch.input_port.selected);
-            out.connection_made <+ attach_edge_output.map2(&state.detached_edge,
+            attached_connection <- any(...);
+            attached_connection <+ attach_edge_output.map2(&state.detached_edge,
                 |source, detached| detached.as_ref()?.connect_to_source(*source)
             ).unwrap();
-            out.connection_made <+ attach_edge_input.map2(&state.detached_edge,
+            attached_connection <+ attach_edge_input.map2(&state.detached_edge,
                 |target, detached| detached.as_ref()?.connect_to_target(*target)
             ).unwrap();
+            // A node cannot consume its own value, so such connection is rejected and the edge
+            // stays detached.
+            is_self_connection <- attached_connection.map(|c| c.source.node_id == c.target.node_id);
+            self_connection <- attached_connection.gate(&is_self_connection);
+            input.reject_feedback <+ self_connection.map(|c| RejectTarget::Node(c.target.node_id));
+            // A connection between ports of incompatible types is rejected as well.
+            is_type_mismatch <- attached_connection.map(
+                f!((c) model.type_mismatch(c.source, c.target).is_some())
+            );
+            mismatched_connection <- attached_connection.gate(&is_type_mismatch);
+            let reject_target = |c: &Connection| RejectTarget::Node(c.target.node_id);
+            input.reject_feedback <+ mismatched_connection.map(reject_target);
+            out.edge_drop_rejected <+ mismatched_connection.map2(&state.detached_edge,
+                f!((c, detached) {
+                    let (source_type, target_type) = model.type_mismatch(c.source, c.target)?;
+                    Some((detached.as_ref()?.edge_id()?, c.target, source_type, target_type))
+                })
+            ).unwrap();
+            is_rejected <- is_self_connection || is_type_mismatch;
+            out.connection_made <+ attached_connection.gate_not(&is_rejected);
 
             // Create new detached edge when clicking on node port.
             state.set_detached_edge <+ touch.output_port.down.gate(&can_create_on_output)
@@ -2611,14 +4605,82 @@ impl GraphEditor {
             edge_to_refresh_color <+ edge_state.detached_edge.sample(&port_hovered)
                 .filter_map(|&d| d?.edge_id());
             edges_to_refresh_color_batch <- edge_to_refresh_color.batch_unique();
-            edges_with_updated_color <- edges_to_refresh_color_batch.map(
+            edges_with_updated_color <- any(...);
+            edges_with_updated_color <+ edges_to_refresh_color_batch.map(
                 f!((edge_ids) model.refresh_edge_colors(edge_ids.iter().copied()))
             );
+            // The edges take the colors of the nodes, which are updated to the new palette first.
+            palette_changed <- out.color_palette.debounce();
+            edges_with_updated_color <+ palette_changed.map(f_!(model.refresh_all_edge_colors()));
         }
 
         EdgeColorFrp { edges_with_updated_color }
     }
 
+    fn frp_init_edge_labels(&self, node_expr: &NodeExpressionFrp, edge_state: &EdgeStateFrp) {
+        let network = self.frp.network();
+        let model = &self.model;
+        let input = &self.frp.private.input;
+        let out = &self.frp.private.output;
+
+        frp::extend! { network
+            out.edge_types_visible <+ input.show_edge_types.on_change();
+
+            edge_to_refresh_label <- any(...);
+            edge_to_refresh_label <+ input.set_edge_label.filter_map(
+                f!(((id, label)) model.set_edge_label(*id, label).as_some(*id))
+            );
+            edge_to_refresh_label <+ out.edge_types_visible.map(
+                f_!(model.edges.borrow().keys().copied().collect_vec())
+            ).iter();
+            edge_to_refresh_label <+ node_expr.node_with_new_expression_type.map(
+                f!((id) model.node_in_and_out_edges(*id))
+            ).iter();
+            edge_to_refresh_label <+ edge_state.maintained_edges_dirty.iter();
+            labels_to_refresh <- edge_to_refresh_label.batch_unique();
+            eval labels_to_refresh ((ids) model.refresh_edge_labels(ids.iter().copied()));
+        }
+    }
+
+    fn frp_init_hidden_edges(&self, edge_state: &EdgeStateFrp) {
+        let network = self.frp.network();
+        let model = &self.model;
+        let input = &self.frp.private.input;
+        let out = &self.frp.private.output;
+
+        frp::extend! { network
+            node_with_toggled_edges <- input.set_node_edges_hidden.filter_map(
+                f!(((id, hidden)) model.nodes.set_edges_hidden(*id, *hidden).as_some(*id))
+            );
+            node_with_revealed_edges <= out.node_edge_stubs_hovered.map(
+                f!(((id, hovered)) model.nodes.set_edges_revealed(*id, *hovered))
+            );
+
+            edge_to_refresh_visibility <- any(...);
+            edge_to_refresh_visibility <+ node_with_toggled_edges.map(
+                f!((id) model.node_in_and_out_edges(*id))
+            ).iter();
+            edge_to_refresh_visibility <+ node_with_revealed_edges.map(
+                f!((id) model.node_in_and_out_edges(*id))
+            ).iter();
+            edge_to_refresh_visibility <+ edge_state.maintained_edges_dirty.iter();
+            edges_to_refresh_visibility <- edge_to_refresh_visibility.batch_unique();
+            eval edges_to_refresh_visibility ((ids)
+                model.refresh_edge_visibility(ids.iter().copied())
+            );
+
+            // The removed edges are not reported as dirty, so the stubs of all nodes with hidden
+            // edges are refreshed after every change of the edges.
+            node_to_refresh_stubs <- any(...);
+            node_to_refresh_stubs <+ input.set_node_edges_hidden._0();
+            node_to_refresh_stubs <+ edge_state.maintained_edges_dirty.map(
+                f_!(model.nodes.edges_hidden.keys())
+            ).iter();
+            stubs_to_refresh <- node_to_refresh_stubs.batch_unique();
+            eval stubs_to_refresh ((ids) model.refresh_edge_stubs(ids.iter().copied()));
+        }
+    }
+
     fn frp_init_edge_positions(&self, state: &EdgeStateFrp) {
         let network = self.frp.network();
         let out = &self.frp.private.output;
@@ -2638,6 +4700,7 @@ impl GraphEditor {
         let network = self.frp.network();
         let model = &self.model;
         let cursor = &model.app.cursor.frp;
+        let input = &self.frp.private.input;
         let out = &self.frp.private.output;
 
         frp::extend! { network
@@ -2650,6 +4713,18 @@ impl GraphEditor {
 
             cursor_pos_on_update <- cursor.scene_position.sample(&state.detached_edge);
             refresh_cursor_pos <- any(cursor_pos_on_update, cursor.scene_position);
+
+            // Snap the detached edge to a compatible input port near the cursor, unless the cursor
+            // hovers some input port already.
+            snap_radius <- input.set_port_snap_radius.sampler();
+            snap_context <- all(detached_source_endpoint, out.hover_node_input, snap_radius);
+            new_snapped_target <- refresh_cursor_pos.map3(&snap_context, &state.snapped_target,
+                f!((position, (source, hover, radius), snapped) {
+                    let source = source.filter(|_| hover.is_none() && *radius > 0.0)?;
+                    model.port_snap_target(source, position.xy(), *radius, *snapped)
+                })
+            );
+            state.snapped_target <+ new_snapped_target.on_change();
             refresh_cursor_data <- all(refresh_cursor_pos, cursor.box_size);
 
             // Only allow hovering output ports of different nodes than the current target node.
@@ -2660,10 +4735,12 @@ impl GraphEditor {
             snap_source_to_node <- out.hover_node_output.unwrap().gate(&is_hovering_valid_output);
 
 
-            _eval <- refresh_cursor_data.map2(&detached_source_edge,
-                f!(((position, cursor_size), &edge_id) model.with_edge(edge_id?, |edge| {
+            _eval <- refresh_cursor_data.map3(&detached_source_edge, &state.snapped_target,
+                f!(((position, cursor_size), &edge_id, &snapped) model.with_edge(edge_id?, |edge| {
                     let top_of_cursor = Vector2(0.0, cursor_size.y() / 2.0 - CURSOR_EDGE_OVERLAP);
-                    edge.view.target_position.emit(position.xy() + top_of_cursor);
+                    let snapped_position = snapped.and_then(|p| model.input_port_position(p));
+                    let position = snapped_position.unwrap_or(position.xy() + top_of_cursor);
+                    edge.view.target_position.emit(position);
                 }))
             );
             _eval <- refresh_source.map2(&detached_target_edge,
@@ -2677,11 +4754,11 @@ impl GraphEditor {
                     let (node_size, node_pos) = model.with_node(target.node_id, |node| {
                         let node_width  = node.view.model().width();
                         let node_height = node.view.model().height();
-                        let node_pos    = node.position();
+                        let node_pos    = node.position().xy() + node.view.model().offset_to_center();
                         (Vector2(node_width, node_height), node_pos)
                     })?;
                     model.with_edge(edge_id?, |edge| {
-                        edge.set_xy(Vector2(node_pos.x + node_size.x/2.0, node_pos.y));
+                        edge.set_xy(node_pos);
                         edge.view.source_size.emit(node_size);
                     })
                 })
@@ -2714,6 +4791,7 @@ impl GraphEditor {
             ).on_true();
 
         }
+        input.set_port_snap_radius.emit(DEFAULT_PORT_SNAP_RADIUS);
     }
 
     fn frp_init_edge_bg_drop(
@@ -2728,8 +4806,10 @@ impl GraphEditor {
             connect_drag_mode <- any(...);
             connect_drag_mode <+ touch.output_port.down.constant(true);
             connect_drag_mode <+ touch.input_port.down.constant(true);
+            connect_drag_mode <+ touch.edges.down.constant(true);
             connect_drag_mode <+ touch.output_port.selected.constant(false);
             connect_drag_mode <+ touch.input_port.selected.constant(false);
+            connect_drag_mode <+ touch.edges.selected.constant(false);
             drop_onto_background <- bg.pointer_up.gate(&connect_drag_mode);
             perform_drop <- any_(drop_onto_background, bg.clicked_with_detached_edge);
             dropped_detached_edge <- state.detached_edge.sample(&perform_drop).unwrap();
@@ -2829,6 +4909,12 @@ fn init_remaining_graph_editor_frp(
         node_switch_to_enter <- out.node_hovered.sample(&enter_node);
         node_to_enter <- node_switch_to_enter.filter_map(|switch| switch.into_on());
         out.node_entered <+ node_to_enter;
+
+        // Animate the transition between the graphs.
+        let transition = &model.navigation_transition;
+        transition.set_enabled <+ inputs.set_navigation_animation_enabled;
+        transition.enter <+ out.node_entered.filter_map(f!((id) model.node_center(*id)));
+        transition.exit <+ out.node_exited;
     }
 
 
@@ -2843,20 +4929,25 @@ fn init_remaining_graph_editor_frp(
         node_added_with_button <- model.add_node_button.clicked.gate_not(&inputs.set_read_only);
         start_node_creation_from_port <- out.hover_node_output.sample(
             &inputs.start_node_creation_from_port
-        ).unwrap();
+        ).unwrap().gate_not(&inputs.set_read_only);
 
         input_add_node_way <- inputs.add_node.constant(WayOfCreatingNode::AddNodeEvent);
         input_start_creation_way <- inputs.start_node_creation.filter_map(f_!(
             // Only start node creation if nothing is focused. This is to prevent
             // creating nodes when we are editing texts and press enter.
             scene.focused_instance().is_none().then_some(WayOfCreatingNode::StartCreationEvent)
-        ));
+        )).gate_not(&inputs.set_read_only);
         start_creation_from_port_way <- start_node_creation_from_port.map(
             |&endpoint| WayOfCreatingNode::StartCreationFromPortEvent{ endpoint });
         add_with_button_way <- node_added_with_button.constant(WayOfCreatingNode::ClickingButton);
         add_with_edge_drop_way <- edge_interaction.create_node_from_edge.map(
             |&endpoint| WayOfCreatingNode::DroppingEdge { endpoint });
 
+        node_creation_rejected <- any_(...);
+        node_creation_rejected <+ model.add_node_button.clicked.gate(&inputs.set_read_only);
+        node_creation_rejected <+ inputs.start_node_creation.gate(&inputs.set_read_only);
+        inputs.reject_feedback <+ node_creation_rejected.constant(RejectTarget::Scene);
+
         add_node_way <- any(...);
         add_node_way <+ input_add_node_way;
         add_node_way <+ input_start_creation_way;
@@ -2895,9 +4986,13 @@ fn init_remaining_graph_editor_frp(
             if let Some(edited) = edited { edited != clicked } else { true }
         };
         node_to_edit <- clicked_and_edited_nodes.filter(not_being_edited_already)._0();
-        edit_node <- any(node_to_edit, node_to_edit_after_adding, inputs.edit_node);
+        edit_node_request <- any(node_to_edit, node_to_edit_after_adding, inputs.edit_node);
+        edit_node <- edit_node_request.gate_not(&inputs.set_read_only);
+        edit_node_rejected <- edit_node_request.gate(&inputs.set_read_only);
+        inputs.reject_feedback <+ edit_node_rejected.map(|id| RejectTarget::Node(*id));
+        stop_edit_on_read_only <- inputs.set_read_only.on_true().gate(&node_in_edit_mode);
         stop_edit_on_bg_click <- bg_interaction.clicked_without_detached_edge.gate(&node_in_edit_mode);
-        stop_edit <- any(&stop_edit_on_bg_click,&inputs.stop_editing);
+        stop_edit <- any(&stop_edit_on_bg_click,&inputs.stop_editing,&stop_edit_on_read_only);
         edit_switch <- edit_node.gate(&node_in_edit_mode);
         node_being_edited <- out.node_being_edited.map(|n| n.unwrap_or_default());
 
@@ -2967,9 +5062,14 @@ fn init_remaining_graph_editor_frp(
 
     frp::extend! { network
         all_nodes       <= inputs.remove_all_nodes.map(f_!(model.nodes.keys()));
-        selected_nodes  <= inputs.remove_selected_nodes.map(f_!(model.nodes.all_selected()));
+        remove_selected <- inputs.remove_selected_nodes.gate_not(&inputs.set_read_only);
+        selected_nodes  <= remove_selected.map(f_!(model.nodes.all_selected()));
         nodes_to_remove <- any (all_nodes, selected_nodes);
         out.node_removed <+ nodes_to_remove;
+
+        remove_rejected <- inputs.remove_selected_nodes.gate(&inputs.set_read_only);
+        kept_nodes      <= remove_rejected.map(f_!(model.nodes.all_selected()));
+        inputs.reject_feedback <+ kept_nodes.map(|id| RejectTarget::Node(*id));
     }
 
 
@@ -2978,12 +5078,18 @@ fn init_remaining_graph_editor_frp(
     // TODO [mwu] https://github.com/enso-org/ide/issues/760
     //   This is currently the provisional code to enable collapse nodes refactoring. While the APIs
     //   are as-intended, their behavior isn't. Please refer to the issue for details.
-    let empty_id       = NodeId::default();
-    let model_clone    = model.clone_ref();
-    nodes_to_collapse <- inputs.collapse_selected_nodes . map(move |_|
-        (model_clone.nodes.all_selected(),empty_id)
-    );
-    out.nodes_collapsed <+ nodes_to_collapse;
+    collapse_selected <- inputs.collapse_selected_nodes.gate_not(&inputs.set_read_only);
+    selected_to_collapse <- collapse_selected.map(f_!(model.nodes.all_selected()));
+    nodes_to_collapse <- selected_to_collapse.filter(|nodes| !nodes.is_empty());
+    let preview = &model.collapse_preview;
+    preview.show <+ nodes_to_collapse.map(f!((nodes) Rc::new(model.collapse_signature(nodes))));
+    preview.confirm <+ inputs.confirm_collapse_preview;
+    preview.cancel <+ inputs.cancel_collapse_preview;
+    preview.cancel <+ inputs.set_read_only.on_true();
+    out.collapse_preview_visible <+ preview.visible;
+    out.nodes_collapsed <+ preview.confirmed.map2(&nodes_to_collapse, |signature, nodes| {
+        (nodes.clone(), signature.clone_ref())
+    });
     }
 
 
@@ -3002,10 +5108,26 @@ fn init_remaining_graph_editor_frp(
 
     frp::extend! { network
         out.node_copied <+ inputs.copy_selected_node.map(f_!(model.nodes.last_selected())).unwrap();
-        cursor_pos_at_paste <- cursor.scene_position.sample(&inputs.paste_node).map(|v| v.xy());
+        paste_node <- inputs.paste_node.gate_not(&inputs.set_read_only);
+        paste_rejected <- inputs.paste_node.gate(&inputs.set_read_only);
+        inputs.reject_feedback <+ paste_rejected.constant(RejectTarget::Scene);
+        cursor_pos_at_paste <- cursor.scene_position.sample(&paste_node).map(|v| v.xy());
         out.request_paste_node <+ cursor_pos_at_paste.map(
             f!([model](pos) new_node_position::at_mouse_aligned_to_close_nodes(&model, *pos))
         );
+
+        // The text pasted while editing a node, or any other text area, is handled by the text
+        // area itself.
+        let pasted = &model.paste_listener.pasted;
+        pasted_on_canvas <- pasted.filter(f_!(scene.focused_instance().is_none()));
+        pasted_on_canvas <- pasted_on_canvas.gate_not(&out.node_editing);
+        paste_content <- pasted_on_canvas.gate_not(&inputs.set_read_only);
+        content_rejected <- pasted_on_canvas.gate(&inputs.set_read_only);
+        inputs.reject_feedback <+ content_rejected.constant(RejectTarget::Scene);
+        out.paste_requested <+ paste_content.map2(&cursor.scene_position, f!([model](content, pos) {
+            let pos = new_node_position::at_mouse_aligned_to_close_nodes(&model, pos.xy());
+            (content.clone(), pos)
+        }));
     }
 
 
@@ -3015,6 +5137,26 @@ fn init_remaining_graph_editor_frp(
     eval inputs.set_node_comment(((id,comment)) model.set_node_comment(*id,comment));
     }
 
+
+    // === Set Node Color Tag ===
+    frp::extend! { network
+
+    eval inputs.set_node_color_tag(((id, tag)) model.set_node_color_tag(*id, *tag));
+    }
+
+    // === Set Node Collapsed and Group ===
+    frp::extend! { network
+
+    eval inputs.set_node_collapsed(((id, collapsed)) model.set_node_collapsed(*id, *collapsed));
+    eval inputs.set_node_group(((id, group)) model.set_node_group(*id, group));
+    }
+
+    // === Set Node Width ===
+    frp::extend! { network
+
+    eval inputs.set_node_width_override(((id, width)) model.set_node_width_override(*id, *width));
+    }
+
     // === Set Node Error ===
     frp::extend! { network
 
@@ -3031,6 +5173,52 @@ fn init_remaining_graph_editor_frp(
         model.with_node(*node_id, |n| n.set_pending.emit(is_pending))
     });
 
+    eval inputs.set_node_pending([model]((node_id, is_pending)) {
+        model.with_node(*node_id, |n| n.set_computing.emit(is_pending))
+    });
+    computation_finished <- any_mut::<NodeId>();
+    computation_finished <+ inputs.set_node_execution_count._0();
+    computation_finished <+ inputs.set_node_execution_duration._0();
+    computation_finished <+ inputs.set_visualization_data._0();
+    computation_finished <+ inputs.set_value_preview_data._0();
+    eval computation_finished ((node_id) model.with_node(*node_id, |n| n.set_computing(false)));
+    stale_cleared <- any_mut::<NodeId>();
+    stale_cleared <+ computation_finished;
+    stale_cleared <+ inputs.clear_node_stale;
+    stale_cleared <+ inputs.set_node_pending.filter_map(|(id, pending)| (!pending).as_some(*id));
+    eval stale_cleared ((node_id) model.with_node(*node_id, |n| n.set_stale(false)));
+    eval inputs.mark_subtree_stale ((node_id) model.mark_subtree_stale(*node_id));
+
+    }
+
+    // === Set Node Execution Count ===
+    frp::extend! { network
+
+    eval inputs.set_node_execution_count([model]((node_id, count)) {
+        model.with_node(*node_id, |n| n.set_execution_count.emit(Some(*count)))
+    });
+
+    }
+
+    // === Set Node Warnings ===
+    frp::extend! { network
+
+    eval inputs.set_node_warnings([model]((node_id, warnings)) {
+        model.with_node(*node_id, |n| n.set_warnings.emit(warnings))
+    });
+
+    }
+
+    // === Set Node Hover Card Information ===
+    frp::extend! { network
+
+    eval inputs.set_node_execution_duration([model]((node_id, duration)) {
+        model.with_node(*node_id, |n| n.set_execution_duration.emit(*duration))
+    });
+    eval inputs.set_node_doc_summary([model]((node_id, summary)) {
+        model.with_node(*node_id, |n| n.set_doc_summary.emit(summary))
+    });
+
     }
 
 
@@ -3071,9 +5259,13 @@ fn init_remaining_graph_editor_frp(
     x_snap_strength.set_duration(300.0);
     y_snap_strength.set_duration(300.0);
 
-    _eval <- node_tgt_pos_rt.map2(&just_pressed,
-        f!([model,x_snap_strength,y_snap_strength,node_tgt_pos_anim](pos,just_pressed) {
-            let snapped = model.nodes.check_grid_magnet(*pos);
+    grid_spacing <- out.grid_spacing.sampler();
+    _eval <- node_tgt_pos_rt.map3(&just_pressed,&grid_spacing,
+        f!([model,x_snap_strength,y_snap_strength,node_tgt_pos_anim](pos,just_pressed,spacing) {
+            let snapped = match spacing {
+                Some(spacing) => component::grid::snap(*pos,*spacing).map(Some),
+                None => model.nodes.check_grid_magnet(*pos),
+            };
             let x = snapped.x.unwrap_or(pos.x);
             let y = snapped.y.unwrap_or(pos.y);
             x_snap_strength.target(if snapped.x.is_none() { 0.0 } else { 1.0 });
@@ -3108,12 +5300,32 @@ fn init_remaining_graph_editor_frp(
     out.node_position_set <+ tgt_new_pos;
 
 
+    // === Collision Avoidance ===
+
+    out.collision_avoidance <+ inputs.set_collision_avoidance;
+    dragged_nodes    <- drag_tgts.sample(&main_tgt_pos_diff).gate(&out.collision_avoidance);
+    pushed_nodes     <- dragged_nodes.map(f!((ids) model.push_overlapping_nodes(ids)));
+    pushed_node      <= pushed_nodes;
+    out.node_position_set <+ pushed_node;
+    // The pushed nodes are remembered, so their final positions can be reported in the batch update
+    // at the end of the drag.
+    pushed_during_drag <- any_mut::<Vec<NodeId>>();
+    pushed_during_drag <+ drag_tgts.constant(default());
+    pushed_during_drag <+ pushed_nodes.map2(&pushed_during_drag, |pushed, all: &Vec<NodeId>| {
+        let new = pushed.iter().map(|(id, _)| *id).filter(|id| !all.contains(id));
+        all.iter().copied().chain(new).collect_vec()
+    });
+
+
     // === Batch Update ===
 
     after_drag             <- touch.nodes.up.gate_not(&just_pressed);
     tgt_after_drag         <= drag_tgts.sample(&after_drag);
     tgt_after_drag_new_pos <- tgt_after_drag.map(f!([model](id)(*id,model.node_position(*id))));
     out.node_position_set_batched <+ tgt_after_drag_new_pos;
+    pushed_after_drag      <= pushed_during_drag.sample(&after_drag);
+    pushed_after_drag_pos  <- pushed_after_drag.map(f!([model](id)(*id,model.node_position(*id))));
+    out.node_position_set_batched <+ pushed_after_drag_pos;
 
     // === Set Node Position ===
 
@@ -3162,6 +5374,12 @@ fn init_remaining_graph_editor_frp(
         model.with_node(*node_id, |node|  node.model().error_visualization.send_data.emit(data))
     );
 
+    out.value_previews_visible <+ inputs.show_value_previews.on_change();
+    eval inputs.set_value_preview_data (((node_id, data)) {
+        let preview = component::node::value_preview::format(data);
+        model.with_node(*node_id, |node| node.set_value_preview.emit(preview));
+    });
+
     nodes_to_cycle <= inputs.cycle_visualization_for_selected_node.map(f_!(model.nodes.all_selected()));
     node_to_cycle  <- any(nodes_to_cycle,inputs.cycle_visualization);
     eval node_to_cycle ((node_id)
@@ -3261,6 +5479,20 @@ fn init_remaining_graph_editor_frp(
         vis_registry.add_default_visualizations();
     });
     out.visualization_registry_reload_requested <+ inputs.reload_visualization_registry;
+    bundle_loaded <- source::<visualization::bundle::BundleLoaded>();
+    out.visualization_bundle_loaded <+ bundle_loaded;
+    eval inputs.load_visualization_bundle ([vis_registry, bundle_loaded](url) {
+        let registry = vis_registry.clone_ref();
+        let bundle_loaded = bundle_loaded.clone_ref();
+        let url = url.clone_ref();
+        wasm_bindgen_futures::spawn_local(async move {
+            let result = registry.load_bundle(url.clone_ref()).await;
+            if let Err(err) = &result {
+                warn!("Failed to load the visualization bundle from {url}: {err}");
+            }
+            bundle_loaded.emit(visualization::bundle::BundleLoaded { url, result });
+        });
+    });
 
 
     // === Entering and Exiting Nodes ===
@@ -3268,6 +5500,88 @@ fn init_remaining_graph_editor_frp(
     node_to_enter           <= inputs.enter_selected_node.map(f_!(model.nodes.last_selected()));
     out.node_entered <+ node_to_enter;
     out.node_exited  <+ inputs.exit_node;
+    eval inputs.set_node_method_pointer(((id, pointer))
+        model.set_node_method_pointer(*id, pointer.clone())
+    );
+    out.request_enter_method <+ inputs.goto_definition_of_selected_node.filter_map(
+        f_!(model.method_pointer_of_selected_node())
+    );
+
+
+    // === Node Peek ===
+
+    selected_to_peek <= inputs.peek_selected_node.map(f_!(model.nodes.last_selected()));
+    node_to_peek <- any(&inputs.peek_node, &selected_to_peek);
+    out.node_peek_requested <+ node_to_peek.filter_map(f!((id)
+        Some((*id, model.with_node(*id, |node| node.method_pointer()).flatten()?))
+    ));
+    peeked_node <- out.node_peek_requested._0();
+    peek_received <- inputs.set_node_peek.map2(&peeked_node, |(id, graph), peeked| {
+        (*id == *peeked).then(|| (*id, graph.clone_ref()))
+    }).unwrap();
+    peek_shown <- peek_received.map(f!(((id, graph)) model.show_node_peek(*id, graph)));
+    peeked_node_removed <- out.node_removed.map2(&peeked_node, |removed, peeked| removed == peeked);
+    hide_peek <- any_(...);
+    hide_peek <+ inputs.hide_node_peek;
+    hide_peek <+ out.node_entered;
+    hide_peek <+ out.node_exited;
+    hide_peek <+ peeked_node_removed.on_true();
+    hide_peek <+ peek_shown.on_false();
+    model.node_peek.hide <+ hide_peek;
+    out.node_peek_visible <+ model.node_peek.visible;
+
+
+    // === Hover Card ===
+
+    hovered_node <- any_mut::<Option<NodeId>>();
+    hovered_node <+ out.node_hovered.map2(&hovered_node, |switch, hovered| match switch.on() {
+        Some(node_id) => Some(*node_id),
+        // The node may be left after another node was entered.
+        None => hovered.filter(|node_id| *node_id != switch.value),
+    });
+    hovered_node <+ out.node_removed.map2(&hovered_node, |removed, hovered| {
+        hovered.filter(|node_id| node_id != removed)
+    });
+    hover_card_node <- all_with(&hovered_node, &inputs.set_hover_cards_enabled,
+        |node_id, enabled| node_id.filter(|_| *enabled)
+    );
+    // The content of the open card is refreshed once per frame when the displayed node changes.
+    node_info_updated <- any_mut::<NodeId>();
+    node_info_updated <+ inputs.set_node_execution_duration._0();
+    node_info_updated <+ inputs.set_node_doc_summary._0();
+    node_info_updated <+ inputs.set_node_error_status._0();
+    node_info_updated <+ inputs.set_expression_usage_type._0();
+    hover_card_node_updated <- node_info_updated.map2(&hover_card_node,
+        |updated, shown| *shown == Some(*updated)
+    ).on_true();
+    refreshed_hover_card_node <- hover_card_node.sample(&hover_card_node_updated.debounce());
+    shown_hover_card_node <- hover_card_node.on_change();
+    shown_hover_card_node <- any(&shown_hover_card_node, &refreshed_hover_card_node);
+    model.hover_card.set_content <+ shown_hover_card_node.map(f!((node_id)
+        model.hover_card_content((*node_id)?)
+    ));
+
+
+    // === Node Description ===
+
+    out.node_description_requested <+ inputs.request_node_description.filter_map(f!((id)
+        Some((*id, model.node_index.expression(*id)?))
+    ));
+    eval out.node_description_requested (((id, _)) model.set_node_description_pending(*id));
+    eval inputs.set_node_description (((id, description))
+        model.set_node_description(*id, description)
+    );
+
+
+    // === Stable Ids ===
+
+    // The controller re-adds the nodes of a reloaded graph in the same update it removes them, so
+    // the selection is restored before the end of the current microtask.
+    out.node_selected <+ inputs.set_node_stable_id.filter_map(f!(((id, stable_id))
+        model.nodes.set_stable_id(*id, *stable_id).then_some(*id)
+    ));
+    graph_update_finished <- out.node_removed.debounce();
+    eval_ graph_update_finished (model.nodes.forget_removed_selection());
 
     // ================
     // === Node VCS ===
@@ -3297,6 +5611,10 @@ fn init_remaining_graph_editor_frp(
     // === Pointer Style ===
     // =====================
 
+    use theme::graph_editor::edge::type_check;
+    let compatible_port_color = model.styles_frp.get_color_lcha(type_check::compatible);
+    let incompatible_port_color = model.styles_frp.get_color_lcha(type_check::incompatible);
+
     frp::extend! { network
 
     cursor_on_drag_down <- touch.nodes.down.gate(&any_drag_tgt).constant(
@@ -3305,6 +5623,34 @@ fn init_remaining_graph_editor_frp(
     cursor_on_drag_up   <- touch.nodes.up.constant(cursor::Style::default());
     pointer_on_drag     <- any (&cursor_on_drag_down,&cursor_on_drag_up);
 
+    // While the detached edge hovers an input port, the port highlight is colored according to
+    // the compatibility of its type with the edge's source type.
+    hovered_port_type_check <- out.hover_node_input.all_with(&edge_state.detached_edge,
+        f!([model](hover, detached) {
+            let source = detached.as_ref()?.source_endpoint()?;
+            Some(model.type_mismatch(source, (*hover)?))
+        })
+    );
+    type_check_colors <- all(&compatible_port_color, &incompatible_port_color);
+    type_check_style <- hovered_port_type_check.all_with(&type_check_colors,
+        |check, (compatible, incompatible)| match check {
+            Some(None) => cursor::Style::new_color(*compatible),
+            Some(Some(_)) => cursor::Style::new_color(*incompatible),
+            None => default(),
+        }
+    );
+    type_mismatch <- hovered_port_type_check.map(|check| check.clone().flatten()).on_change();
+    app.frp.set_tooltip <+ type_mismatch.map(f!([model] (mismatch) match mismatch {
+        Some((source_type, target_type)) => {
+            let actual = source_type.abbreviate().to_string();
+            let expected = target_type.abbreviate().to_string();
+            let args = [("expected", expected.as_str()), ("actual", actual.as_str())];
+            let label = model.app.translator().translate_with(TYPE_MISMATCH_TOOLTIP, &args);
+            tooltip::Style::set_label(label)
+        }
+        None => tooltip::Style::unset_label(),
+    }));
+
     detached_edge_style <- edge_state.detached_edge.all_with(&edge_color.edges_with_updated_color,
         f!([model](d, _) {
             let color = d.and_then(|d| d.edge_id()).map(|id| model.edge_color(id));
@@ -3318,6 +5664,7 @@ fn init_remaining_graph_editor_frp(
         [ pointer_on_drag
         , selection_style
         , node_pointer_style
+        , type_check_style
         , detached_edge_style
         ].fold();
     }
@@ -3333,6 +5680,19 @@ fn init_remaining_graph_editor_frp(
         eval selection_controller.area_selection ((area_selection) model.nodes.show_quick_actions(!area_selection));
     }
 
+    // Preview the nodes affected by the area selection before the mouse is released.
+    frp::extend! { network
+        let selection_preview = &selection_controller.preview;
+        _eval <- selection_preview.map2(&selection_preview.previous(),
+            f!((preview, old) model.set_selection_preview(preview, old))
+        );
+        model.selection_count.set_label <+ selection_preview.map2(
+            &selection_controller.area_selection,
+            |preview, active| active.then(|| preview.label().into())
+        );
+        model.lasso.set_path <+ selection_controller.lasso_path;
+    }
+
     // === Visualization + Selection ===
 
     // Do not allow area selection while we show a fullscreen visualization.
@@ -3365,20 +5725,27 @@ fn init_remaining_graph_editor_frp(
     use theme::graph_editor::default_y_gap_between_nodes as gap_path;
     let default_gap = model.styles_frp.get_number_or(gap_path, 0.0);
     let files_received = model.drop_manager.files_received().clone_ref();
+    let files_hovered = model.drop_manager.files_hovered().clone_ref();
     frp::extend! { network
-        files_with_positions <- files_received.map2(&default_gap,
-            move |drop_event_data,default_gap| {
+        files_with_positions <- files_received.map3(&default_gap, &inputs.set_drop_templates,
+            |drop_event_data, default_gap, templates| {
                 let files = &drop_event_data.files;
-                let drop_posititon = drop_event_data.position;
-                let single_offset = default_gap + node::HEIGHT;
-                files.iter().enumerate().map(|(index,file)| {
-                    let offset = Vector2(0.0, single_offset * index as f32);
-                    (file.clone_ref(), drop_posititon + offset)
+                let positions = file_drop::column_layout(
+                    drop_event_data.position,
+                    files.len(),
+                    *default_gap,
+                );
+                files.iter().zip(positions).map(|(file, position)| {
+                    let template = file_drop::template_for(templates, &file.name);
+                    (file.clone_ref(), position, template)
                 }).collect_vec()
             }
         );
         file_dropped            <= files_with_positions;
         out.file_dropped <+ file_dropped;
+
+        model.file_drop_highlight.set_hovered <+ files_hovered;
+        model.file_drop_highlight.set_gap <+ default_gap;
     }
 
 
@@ -3406,6 +5773,426 @@ fn init_remaining_graph_editor_frp(
 
 
 
+    // ======================
+    // === Node Placement ===
+    // ======================
+
+    frp::extend! { network
+        out.node_placement_strategy <+ inputs.set_node_placement_strategy;
+    }
+
+
+
+    // =================
+    // === View Mode ===
+    // =================
+
+    let fonts = scene.extension::<text::font::Registry>();
+    frp::extend! { network
+        out.view_mode <+ inputs.set_view_mode;
+        out.color_palette <+ inputs.set_color_palette.on_change();
+        eval_ inputs.invalidate_text_cache (fonts.invalidate_text_cache());
+    }
+
+
+
+    // ============================
+    // === Recomputation Pulses ===
+    // ============================
+
+    frp::extend! { network
+        is_live <- out.execution_environment.map(|env| env.output_context_enabled);
+        recomputed_node <- inputs.notify_node_recomputed.gate(&is_live);
+        eval recomputed_node ((node_id) model.pulse_out_edges(*node_id));
+    }
+
+
+
+    // ====================
+    // === Data Lineage ===
+    // ====================
+
+    frp::extend! { network
+        upstream_lineage <- inputs.highlight_upstream_of.map(
+            |id| Some((*id, LineageDirection::Upstream))
+        );
+        downstream_lineage <- inputs.highlight_downstream_of.map(
+            |id| Some((*id, LineageDirection::Downstream))
+        );
+        lineage_cleared <- inputs.clear_lineage_highlight.constant(None);
+        out.lineage_highlight <+ any(&upstream_lineage, &downstream_lineage, &lineage_cleared);
+    }
+
+
+
+    // ==================
+    // === Focus Mode ===
+    // ==================
+
+    frp::extend! { network
+        focus_mode_toggled <- inputs.toggle_focus_mode.map2(&out.focus_mode, |_, on| !on);
+        out.focus_mode <+ any(&inputs.set_focus_mode, &focus_mode_toggled);
+
+        focus_dirty <- any_(...);
+        focus_dirty <+ out.focus_mode;
+        focus_dirty <+ out.node_selected;
+        focus_dirty <+ out.node_deselected;
+        focus_dirty <+ out.node_removed;
+        focus_dirty <+ out.connection_made;
+        focus_dirty <+ out.connection_broken;
+        focus_dirty <+ out.lineage_highlight;
+
+        // The nodes found by the node search stay highlighted until the search is cleared.
+        found_nodes <- any_mut::<Option<Rc<HashSet<NodeId>>>>();
+        found_nodes <+ out.nodes_found.map2(&inputs.search_nodes, |found, query| {
+            let found = found.iter().map(|node_match| node_match.node_id).collect();
+            (!query.trim().is_empty()).then(|| Rc::new(found))
+        });
+        found_nodes <+ inputs.clear_node_search.constant(None);
+        focus_dirty <+ found_nodes;
+
+        update_focus <- focus_dirty.debounce();
+        dimming <- all(&out.focus_mode, &out.lineage_highlight, &found_nodes);
+        update_dimming <- dimming.sample(&update_focus);
+        eval update_dimming (((focus_mode, lineage, found_nodes)) {
+            model.update_dimming(*focus_mode, *lineage, found_nodes.as_deref())
+        });
+    }
+
+
+
+    // ===================
+    // === Breakpoints ===
+    // ===================
+
+    frp::extend! { network
+        selected_breakpoints <= inputs.toggle_breakpoint_for_selected_nodes.map(
+            f_!(model.nodes.all_selected())
+        );
+        breakpoint_to_toggle <- any(&inputs.toggle_node_breakpoint, &selected_breakpoints);
+        breakpoint_toggled <- breakpoint_to_toggle.map(f!((id) model.toggle_node_breakpoint(*id)));
+        breakpoint_removed <- out.node_removed.map(f!((id) model.nodes.breakpoints.remove(id)));
+        breakpoints_dirty <- any(&breakpoint_toggled, &breakpoint_removed).on_true();
+        // Toggling the breakpoints of many selected nodes results in a single update.
+        breakpoints_update <- breakpoints_dirty.debounce();
+        out.breakpoints_changed <+ breakpoints_update.map(f_!(Rc::new(model.breakpoints())));
+    }
+
+
+
+    // ==================================
+    // === Freeze/Skip Selected Nodes ===
+    // ==================================
+
+    frp::extend! { network
+        toggled_freeze <- inputs.toggle_freeze_for_selected_nodes.map(
+            f_!(!model.all_selected_nodes_satisfy(|node| node.view.freeze.value()))
+        );
+        freeze_requested <- any(&inputs.set_selected_nodes_frozen, &toggled_freeze);
+        freeze_selected <- freeze_requested.gate_not(&inputs.set_read_only);
+        selected_to_freeze <- freeze_selected.map(f!((freeze)
+            (Rc::new(model.nodes.all_selected()), *freeze)
+        ));
+        // The batched output goes first, so the controller can handle the whole selection in a
+        // single transaction.
+        out.nodes_action_freeze <+ selected_to_freeze;
+        eval selected_to_freeze(((ids, freeze)) ids.iter().for_each(|id|
+            model.set_node_freeze(*id, *freeze)
+        ));
+
+        toggled_skip <- inputs.toggle_skip_for_selected_nodes.map(
+            f_!(!model.all_selected_nodes_satisfy(|node| node.view.skip.value()))
+        );
+        skip_requested <- any(&inputs.set_selected_nodes_skip, &toggled_skip);
+        skip_selected <- skip_requested.gate_not(&inputs.set_read_only);
+        selected_to_skip <- skip_selected.map(f!((skip)
+            (Rc::new(model.nodes.all_selected()), *skip)
+        ));
+        out.nodes_action_skip <+ selected_to_skip;
+        eval selected_to_skip(((ids, skip)) ids.iter().for_each(|id|
+            model.set_node_skip(*id, *skip)
+        ));
+    }
+
+
+
+    // ===================
+    // === Watch Panel ===
+    // ===================
+
+    frp::extend! { network
+        watch_added <- inputs.add_selected_to_watch.map(f_!(model.add_selected_to_watch()));
+        watch_removed <- inputs.remove_watch.map(f!((id) model.watches.remove(*id)));
+        watched_node_removed <- out.node_removed.map(f!((id) model.watches.forget(*id)));
+        watched_expression_set <- inputs.set_node_expression.map(
+            f!(((id, expr)) model.watches.set_expression(*id, &expr.code))
+        );
+        watched_expression_edited <- out.node_expression_set.map(
+            f!(((id, code)) model.watches.set_expression(*id, code))
+        );
+        watched_data_received <- inputs.set_visualization_data.map(
+            f!(((id, data)) model.watches.set_preview(*id, data))
+        );
+        watches_dirty <- any(&watch_added, &watch_removed, &watched_node_removed);
+        watches_dirty <+ any(&watched_expression_set, &watched_expression_edited);
+        watches_dirty <+ watched_data_received;
+        // Many expressions or values arriving in the same frame result in a single update.
+        watches_update <- watches_dirty.on_true().debounce();
+        model.watch_panel.set_watches <+ watches_update.map(f_!(model.watches.entries()));
+        eval model.watch_panel.watch_chosen ((id) model.pan_camera_to_node(*id));
+    }
+
+
+
+    // ===================
+    // === Node Search ===
+    // ===================
+
+    frp::extend! { network
+        eval inputs.set_node_expression (((id, expr)) {
+            model.node_index.set_expression(*id, &expr.code)
+        });
+        eval out.node_expression_set (((id, code)) model.node_index.set_expression(*id, code));
+        eval out.node_comment_set (((id, comment)) model.node_index.set_comment(*id, comment));
+        eval out.node_removed ((id) model.node_index.forget(*id));
+        out.nodes_found <+ inputs.search_nodes.map(f!((q) Rc::new(model.node_index.search(q))));
+        eval inputs.pan_camera_to_node ((id) model.pan_camera_to_node(*id));
+    }
+
+
+
+    // ===================
+    // === Hit Testing ===
+    // ===================
+
+    frp::extend! { network
+        out.node_at_screen_position <+ inputs.request_node_at_screen_position.map(
+            f!((pos) (*pos, model.node_at_screen_position(*pos)))
+        );
+        out.input_port_at_screen_position <+ inputs.request_input_port_at_screen_position.map(
+            f!((pos) (*pos, model.input_port_at_screen_position(*pos)))
+        );
+        out.edge_at_screen_position <+ inputs.request_edge_at_screen_position.map(
+            f!((pos) (*pos, model.edge_at_screen_position(*pos)))
+        );
+    }
+
+
+
+    // =====================
+    // === View Metadata ===
+    // =====================
+
+    frp::extend! { network
+        out.view_metadata_exported <+ inputs.export_view_metadata.map(
+            f_!(Rc::new(model.export_view_metadata()))
+        );
+        eval inputs.import_view_metadata ((metadata) model.import_view_metadata(metadata));
+    }
+
+
+
+    // ========================
+    // === Remote Presences ===
+    // ========================
+
+    frp::extend! { network
+        eval inputs.set_remote_presences ((presences) {
+            model.remote_presences.set_presences(presences.clone_ref())
+        });
+        outlines_dirty <- any_(...);
+        outlines_dirty <+ inputs.set_remote_presences;
+        outlines_dirty <+ out.node_position_set;
+        outlines_dirty <+ out.node_removed;
+        outlines_dirty <+ inputs.set_node_expression;
+        outlines_dirty <+ out.node_expression_set;
+        update_outlines <- outlines_dirty.debounce();
+        eval_ update_outlines (model.update_remote_selection_outlines());
+    }
+
+
+
+    // ======================
+    // === Edit Conflicts ===
+    // ======================
+
+    frp::extend! { network
+        eval inputs.set_node_edit_conflict (((node_id, conflict)) {
+            model.with_node(*node_id, |node| node.set_edit_conflict(conflict.clone()));
+        });
+        out.remote_edit_accepted <+ inputs.accept_remote.filter_map(
+            f!((node_id) model.accept_remote_edit(*node_id))
+        );
+    }
+
+
+
+    // =====================
+    // === Accessibility ===
+    // =====================
+
+    frp::extend! { network
+        eval_ inputs.copy_graph_description (web::clipboard::write_text(model.describe_graph()));
+        let accessibility = &model.accessibility;
+        accessibility.add_node <+ out.node_added._0();
+        accessibility.remove_node <+ out.node_removed;
+        accessibility.set_node_expression <+ inputs.set_node_expression.map(
+            |(id, expr)| (*id, expr.code.clone())
+        );
+        accessibility.set_node_expression <+ out.node_expression_set;
+        accessibility.set_node_selected <+ out.node_selected.map(|id| (*id, true));
+        accessibility.set_node_selected <+ out.node_deselected.map(|id| (*id, false));
+        accessibility.set_node_error <+ inputs.set_node_error_status.map(
+            |(id, error)| (*id, error.as_ref().map_or(false, |e| e.should_display()))
+        );
+
+        accessible_connections_dirty <- any_(...);
+        accessible_connections_dirty <+ inputs.set_connections;
+        accessible_connections_dirty <+ out.node_removed;
+        accessible_connections_dirty <+ out.connection_made;
+        accessible_connections_dirty <+ out.connection_broken;
+        update_accessible_connections <- accessible_connections_dirty.debounce();
+        accessibility.set_connections <+ update_accessible_connections.map(
+            f_!(Rc::new(model.attached_connections()))
+        );
+    }
+
+
+
+    // ========================
+    // === Camera Bookmarks ===
+    // ========================
+
+    frp::extend! { network
+        save_bookmark <- any_mut::<u8>();
+        save_bookmark <+ inputs.save_camera_bookmark;
+        save_bookmark <+ inputs.save_camera_bookmark_1.constant(1);
+        save_bookmark <+ inputs.save_camera_bookmark_2.constant(2);
+        save_bookmark <+ inputs.save_camera_bookmark_3.constant(3);
+        save_bookmark <+ inputs.save_camera_bookmark_4.constant(4);
+        save_bookmark <+ inputs.save_camera_bookmark_5.constant(5);
+        save_bookmark <+ inputs.save_camera_bookmark_6.constant(6);
+        save_bookmark <+ inputs.save_camera_bookmark_7.constant(7);
+        save_bookmark <+ inputs.save_camera_bookmark_8.constant(8);
+        save_bookmark <+ inputs.save_camera_bookmark_9.constant(9);
+        eval save_bookmark ((slot) model.save_camera_bookmark(*slot));
+
+        restore_bookmark <- any_mut::<u8>();
+        restore_bookmark <+ inputs.restore_camera_bookmark;
+        restore_bookmark <+ inputs.restore_camera_bookmark_1.constant(1);
+        restore_bookmark <+ inputs.restore_camera_bookmark_2.constant(2);
+        restore_bookmark <+ inputs.restore_camera_bookmark_3.constant(3);
+        restore_bookmark <+ inputs.restore_camera_bookmark_4.constant(4);
+        restore_bookmark <+ inputs.restore_camera_bookmark_5.constant(5);
+        restore_bookmark <+ inputs.restore_camera_bookmark_6.constant(6);
+        restore_bookmark <+ inputs.restore_camera_bookmark_7.constant(7);
+        restore_bookmark <+ inputs.restore_camera_bookmark_8.constant(8);
+        restore_bookmark <+ inputs.restore_camera_bookmark_9.constant(9);
+        eval restore_bookmark ((slot) model.restore_camera_bookmark(*slot));
+    }
+
+
+
+    // =================
+    // === Snap Grid ===
+    // =================
+
+    frp::extend! { network
+        out.grid_spacing <+ inputs.set_grid_spacing.map(|s| s.filter(|spacing| *spacing > 0.0));
+        model.grid.set_spacing <+ out.grid_spacing;
+        model.grid.set_visible <+ inputs.show_grid;
+        out.grid_visible <+ model.grid.visible;
+    }
+
+
+
+    // =========================
+    // === Canvas Background ===
+    // =========================
+
+    frp::extend! { network
+        model.canvas_background.set_background <+ inputs.set_canvas_background;
+        model.canvas_background.set_regions <+ inputs.set_background_regions;
+    }
+
+
+
+    // ===============
+    // === Portals ===
+    // ===============
+
+    frp::extend! { network
+        out.portal_created <+ inputs.create_portal_from_edge.filter_map(
+            f!((id) Some((*id, model.create_portal_from_edge(*id)?)))
+        );
+        eval inputs.expand_portal ((id) model.expand_portal(*id));
+    }
+
+
+
+    // =========================
+    // === Error Propagation ===
+    // =========================
+
+    frp::extend! { network
+        error_propagation_dirty <- any_(...);
+        error_propagation_dirty <+ inputs.set_node_error_status;
+        error_propagation_dirty <+ inputs.set_connections;
+        error_propagation_dirty <+ out.node_removed;
+        error_propagation_dirty <+ out.connection_made;
+        error_propagation_dirty <+ out.connection_broken;
+        update_error_propagation <- error_propagation_dirty.debounce();
+        eval_ update_error_propagation (model.update_error_propagation());
+    }
+
+
+
+    // ========================
+    // === Warnings Summary ===
+    // ========================
+
+    frp::extend! { network
+        warnings_summary_dirty <- any_(...);
+        warnings_summary_dirty <+ inputs.set_node_warnings;
+        warnings_summary_dirty <+ out.node_removed;
+        update_warnings_summary <- warnings_summary_dirty.debounce();
+        warnings_summary <- update_warnings_summary.map(f_!(model.warnings_summary()));
+        out.warnings_summary <+ warnings_summary.on_change();
+    }
+
+
+
+    // ==========================
+    // === Rejection Feedback ===
+    // ==========================
+
+    frp::extend! { network
+        eval inputs.reject_feedback ((target) model.show_rejection(*target));
+        out.action_rejected <+ inputs.reject_feedback;
+    }
+
+
+
+    // ========================
+    // === Graph Statistics ===
+    // ========================
+
+    frp::extend! { network
+        stats_dirty <- any_(...);
+        stats_dirty <+ out.node_added;
+        stats_dirty <+ out.node_removed;
+        stats_dirty <+ out.node_selected;
+        stats_dirty <+ out.node_deselected;
+        stats_dirty <+ out.has_detached_edge;
+        stats_dirty <+ out.connection_made;
+        stats_dirty <+ out.connection_broken;
+        stats_dirty <+ inputs.set_connections;
+        update_stats <- stats_dirty.debounce();
+        out.graph_stats <+ update_stats.map(f_!(model.graph_stats())).on_change();
+    }
+
+
+
     // ==================
     // === Debug Mode ===
     // ==================
@@ -3417,11 +6204,19 @@ fn init_remaining_graph_editor_frp(
         unlimit_max_zoom <- frp.set_debug_mode.on_true();
         eval_ limit_max_zoom (model.navigator.set_max_zoom(Some(MAX_ZOOM)));
         eval_ unlimit_max_zoom (model.navigator.set_max_zoom(None));
+
+        eval frp.set_debug_mode ((enabled) model.frp_inspector.set_enabled(*enabled));
+        eval_ frp.toggle_frp_inspector_overlay ([model] {
+            let inspector = &model.frp_inspector;
+            inspector.set_overlay_visible(!inspector.is_overlay_visible());
+        });
     }
 
     // Init defaults
     frp.edit_mode_off.emit(());
     frp.set_debug_mode.emit(false);
+    frp.set_focus_mode.emit(false);
+    frp.set_hover_cards_enabled.emit(true);
 }
 
 
@@ -3433,11 +6228,123 @@ fn init_remaining_graph_editor_frp(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use application::test_utils::ApplicationExt;
+    use crate::test_support::*;
     use ensogl::animation::test_utils::next_frame;
+    use ensogl::application::frp::API;
     use ensogl::display::scene::test_utils::MouseExt;
     use node::test_utils::NodeModelExt;
 
+
+    // === Graph Fixtures ===
+
+    /// Ids of the nodes for the tests of the graph algorithms, which need no node views.
+    fn node_ids<const N: usize>() -> [NodeId; N] {
+        std::array::from_fn(|index| NodeId(Id::from(index + 1)))
+    }
+
+    /// A graph with two sources merging into a node with two targets: `a → b → c`, `d → b` and
+    /// `b → e`.
+    fn branching_graph() -> ([NodeId; 5], [(NodeId, NodeId); 4]) {
+        let [a, b, c, d, e] = node_ids();
+        ([a, b, c, d, e], [(a, b), (b, c), (d, b), (b, e)])
+    }
+
+
+    // === Tests ===
+
+    #[test]
+    fn test_pipeline_closure() {
+        let ([a, b, c, d, e], connections) = branching_graph();
+        let closure = |roots: &[NodeId]| pipeline_closure(roots.iter().copied(), connections);
+        assert_eq!(closure(&[b]), HashSet::from([a, b, c, d, e]));
+        assert_eq!(closure(&[c]), HashSet::from([a, b, c, d]));
+        assert_eq!(closure(&[a]), HashSet::from([a, b, c, e]));
+        assert_eq!(closure(&[]), HashSet::new());
+    }
+
+    #[test]
+    fn test_coalescing_widget_updates() {
+        let [a, b] = node_ids();
+        let [first_call, second_call] = [1, 2].map(ast::Id::from_u128);
+        let update = |call_id, arguments: &[&str]| {
+            let definitions = arguments.iter().map(|name| ArgumentWidgetConfig {
+                argument_name: name.to_string(),
+                config:        None,
+            });
+            CallWidgetsConfig { call_id, definitions: Rc::new(definitions.collect()) }
+        };
+        let mut pending = PendingWidgetUpdates::default();
+        pending.push(a, &update(first_call, &["x", "y"]));
+        pending.push(a, &update(first_call, &["y", "z"]));
+        pending.push(a, &update(second_call, &["x"]));
+        pending.push(b, &update(first_call, &["x"]));
+        assert_eq!(pending.dropped, 1);
+
+        let updates = pending.take();
+        let summary = |node_id| {
+            let node_updates = updates.iter().filter(|(id, _)| *id == node_id);
+            let calls = node_updates.map(|(_, update)| {
+                let arguments = update.definitions.iter().map(|d| d.argument_name.as_str());
+                (update.call_id, arguments.collect_vec())
+            });
+            calls.collect_vec()
+        };
+        assert_eq!(summary(a), vec![(first_call, vec!["x", "y", "z"]), (second_call, vec!["x"])]);
+        assert_eq!(summary(b), vec![(first_call, vec!["x"])]);
+        assert!(pending.take().is_empty());
+        assert_eq!(pending.dropped, 1);
+    }
+
+    #[test]
+    fn test_thumbnail_layout() {
+        let bbox = selection::BoundingBox::from_corners(Vector2(0.0, 0.0), Vector2(160.0, 60.0));
+        let layout = thumbnail_layout(bbox, Vector2(100, 100));
+        assert_eq!(layout, Some((Vector2(80.0, 30.0), Vector2(100, 50), 0.5)));
+        let (_, size, scale) = thumbnail_layout(bbox, Vector2(1000, 1000)).unwrap();
+        assert_eq!((size, scale), (Vector2(200, 100), 1.0));
+        assert_eq!(thumbnail_layout(bbox, Vector2(0, 10)), None);
+    }
+
+    #[test]
+    fn test_max_depth() {
+        assert_eq!(max_depth([], []), 0);
+        let (nodes, connections) = branching_graph();
+        assert_eq!(max_depth(nodes, []), 1);
+        assert_eq!(max_depth(nodes, connections), 3);
+    }
+
+    #[test]
+    fn test_max_depth_skips_cycles() {
+        let nodes @ [a, b, c, d] = node_ids();
+        // The nodes from the cycle `b → c → b` onwards are not counted.
+        assert_eq!(max_depth(nodes, [(a, b), (b, c), (c, b), (c, d)]), 1);
+    }
+
+    #[test]
+    fn test_lineage_closure() {
+        let ([a, b, c, d, e], connections) = branching_graph();
+        let upstream = |root| lineage_closure(root, connections, LineageDirection::Upstream);
+        let downstream = |root| lineage_closure(root, connections, LineageDirection::Downstream);
+        assert_eq!(upstream(c), HashSet::from([a, b, c, d]));
+        assert_eq!(upstream(a), HashSet::from([a]));
+        assert_eq!(downstream(a), HashSet::from([a, b, c, e]));
+        assert_eq!(downstream(e), HashSet::from([e]));
+    }
+
+    #[test]
+    fn test_poisoned_nodes() {
+        let ([a, b, c, _, e], connections) = branching_graph();
+        assert_eq!(poisoned_nodes(&[a], connections), HashSet::from([b, c, e]));
+        assert_eq!(poisoned_nodes(&[c], connections), HashSet::new());
+        assert_eq!(poisoned_nodes(&[], connections), HashSet::new());
+    }
+
+    #[test]
+    fn test_poisoned_nodes_exclude_error_sources() {
+        let ([a, b, c, _, e], connections) = branching_graph();
+        assert_eq!(poisoned_nodes(&[a, b], connections), HashSet::from([c, e]));
+    }
+
     #[test]
     fn test_adding_node_by_internal_api() {
         let (_, graph_editor) = init();
@@ -3452,20 +6359,11 @@ mod tests {
         test_adding_node(press_add_node_shortcut);
     }
 
-    fn press_add_node_shortcut(editor: &GraphEditor) {
-        editor.start_node_creation();
-    }
-
     #[test]
     fn test_adding_node_by_adding_node_button() {
         test_adding_node(click_add_node_button);
     }
 
-    fn click_add_node_button(editor: &GraphEditor) {
-        let adding_node_button = &editor.model.add_node_button;
-        adding_node_button.click();
-    }
-
     fn test_adding_node(add_node: impl Fn(&GraphEditor)) {
         let (app, graph_editor) = init();
         assert_eq!(graph_editor.num_nodes(), 0);
@@ -3552,6 +6450,373 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_connecting_nodes_by_test_support() {
+        let (_, graph_editor) = init();
+        let (node_id_1, _) = graph_editor.add_node_by_api_at_pos(Vector2(0.0, 0.0));
+        let (node_id_2, _) = graph_editor.add_node_by_api_at_pos(Vector2(0.0, -100.0));
+        next_frame();
+        let edge_id = graph_editor.connect_nodes(node_id_1, node_id_2);
+        assert!(edge_id.is_some(), "Edge was not added.");
+        assert_eq!(graph_editor.num_edges(), 1);
+        graph_editor.assert_connected(node_id_1, node_id_2);
+        assert_eq!(graph_editor.node_connections(), vec![(node_id_1, node_id_2)]);
+    }
+
+    #[test]
+    fn test_deterministic_ids() {
+        let add_two_nodes = || {
+            let (_, graph_editor) = init_deterministic(100);
+            let (node_id_1, _) = graph_editor.add_node_by_api_at_pos(Vector2(0.0, 0.0));
+            let (node_id_2, _) = graph_editor.add_node_by_api_at_pos(Vector2(0.0, -100.0));
+            next_frame();
+            let edge_id = graph_editor.connect_nodes(node_id_1, node_id_2);
+            (node_id_1, node_id_2, edge_id)
+        };
+        let first_run = add_two_nodes();
+        assert_eq!(first_run.0, NodeId(Id::from(100)));
+        assert_eq!(first_run.1, NodeId(Id::from(101)));
+        assert_eq!(first_run, add_two_nodes());
+    }
+
+    #[test]
+    fn test_reusing_removed_node_view() {
+        let (_, graph_editor) = init();
+        let (node_id, node) = graph_editor.add_node_by_api_at_pos(Vector2(0.0, 0.0));
+        graph_editor.add_node_by_api_at_pos(Vector2(0.0, 100.0));
+        graph_editor.set_node_comment((node_id, ImString::from("A comment")));
+        assert_eq!(node.comment.value(), "A comment");
+        graph_editor.remove_node(node_id);
+        assert_eq!(graph_editor.num_nodes(), 1);
+        assert!(!node.has_parent());
+        assert_eq!(node.comment.value(), "");
+        let last_comment_set = graph_editor.node_comment_set.value();
+        node.set_comment(ImString::from("Changed while parked"));
+        assert_eq!(graph_editor.node_comment_set.value(), last_comment_set);
+
+        let (reused_id, reused) = graph_editor.add_node_by_api_at_pos(Vector2(0.0, -100.0));
+        assert_eq!(reused_id, node_id);
+        assert!(reused.has_parent());
+        assert_eq!(graph_editor.num_nodes(), 2);
+        reused.set_comment(ImString::from("Reused"));
+        assert_eq!(graph_editor.node_comment_set.value(), (reused_id, "Reused".into()));
+    }
+
+    #[test]
+    fn test_exporting_and_importing_view_metadata() {
+        let (_, graph_editor) = init();
+        let (node_id, _) = graph_editor.add_node_by_api_at_pos(Vector2(0.0, 0.0));
+        let tag = Some(color::Rgba::red());
+        graph_editor.set_node_color_tag((node_id, tag));
+        graph_editor.export_view_metadata();
+        let exported = graph_editor.view_metadata_exported.value();
+        let metadata = exported.get(&node_id);
+        assert_eq!(metadata.map(|m| (m.color_tag, m.visualization_enabled)), Some((tag, false)));
+        assert_eq!(metadata.and_then(|m| m.visualization_dock), None);
+
+        let dock = Vector2(40.0, -20.0);
+        let group = Some(ImString::from("Inputs"));
+        let mut docked = exported.get(&node_id).cloned().unwrap();
+        docked.visualization_dock = Some(dock);
+        docked.collapsed = true;
+        docked.group = group.clone();
+        graph_editor.set_node_color_tag((node_id, None));
+        graph_editor.import_view_metadata(Rc::new(HashMap::from([(node_id, docked)])));
+        graph_editor.export_view_metadata();
+        let reimported = graph_editor.view_metadata_exported.value();
+        let metadata = reimported.get(&node_id);
+        assert_eq!(metadata.and_then(|m| m.color_tag), tag);
+        assert_eq!(metadata.and_then(|m| m.visualization_dock), Some(dock));
+        assert_eq!(metadata.map(|m| m.collapsed), Some(true));
+        assert_eq!(metadata.and_then(|m| m.group.clone()), group);
+    }
+
+    #[test]
+    fn test_overriding_node_width() {
+        let (_, graph_editor) = init();
+        let (node_id, node) = graph_editor.add_node_by_api_at_pos(Vector2(0.0, 0.0));
+        let auto_width = node.model().width();
+        graph_editor.set_node_width_override((node_id, Some(300.0)));
+        assert_eq!(node.model().width(), 300.0);
+        graph_editor.set_node_width_override((node_id, Some(1.0)));
+        assert_eq!(node.model().width(), node::MIN_WIDTH);
+        graph_editor.export_view_metadata();
+        let exported = graph_editor.view_metadata_exported.value();
+        assert_eq!(exported.get(&node_id).and_then(|m| m.width), Some(node::MIN_WIDTH));
+
+        graph_editor.set_node_width_override((node_id, None));
+        assert_eq!(node.model().width(), auto_width);
+        graph_editor.import_view_metadata(exported);
+        assert_eq!(node.model().width(), node::MIN_WIDTH);
+    }
+
+    #[test]
+    fn test_inspecting_nodes_added_in_debug_mode() {
+        let (_, graph_editor) = init();
+        graph_editor.set_debug_mode(true);
+        let inspector = &graph_editor.model.frp_inspector;
+        let inspected_before = inspector.snapshot().nodes.len();
+        let (_, node) = graph_editor.add_node_by_api_at_pos(Vector2(0.0, 0.0));
+        let node_frp_nodes = node.view.frp().network().node_labels().len();
+        assert!(inspector.snapshot().nodes.len() >= inspected_before + node_frp_nodes);
+    }
+
+    #[test]
+    fn test_accepting_remote_edit() {
+        let (_, graph_editor) = init();
+        let (node_id, node) = graph_editor.add_node_by_api_at_pos(Vector2(0.0, 0.0));
+        let conflict = node::conflict::ConflictInfo {
+            author:            "Alice".into(),
+            remote_expression: "1 + 2".into(),
+        };
+        graph_editor.set_node_edit_conflict((node_id, Some(conflict.clone())));
+        assert_eq!(node.edit_conflict.value(), Some(conflict.clone()));
+
+        graph_editor.accept_remote(node_id);
+        assert_eq!(graph_editor.remote_edit_accepted.value(), (node_id, conflict.clone()));
+        assert_eq!(node.edit_conflict.value(), None);
+
+        // Clicking the badge accepts the remote version as well.
+        let other_conflict = node::conflict::ConflictInfo { author: "Bob".into(), ..conflict };
+        graph_editor.set_node_edit_conflict((node_id, Some(other_conflict.clone())));
+        let badge = &node.model().edit_conflict;
+        badge.private().output.accept_requested.emit(());
+        assert_eq!(graph_editor.remote_edit_accepted.value(), (node_id, other_conflict));
+        assert_eq!(node.edit_conflict.value(), None);
+    }
+
+    #[test]
+    fn test_hiding_node_edges() {
+        let (_, graph_editor) = init();
+        let (node_1_id, _) = graph_editor.add_node_by_api_at_pos(Vector2(0.0, 0.0));
+        let (node_2_id, _) = graph_editor.add_node_by_api_at_pos(Vector2(0.0, -100.0));
+        let source = EdgeEndpoint::new(node_1_id, default());
+        let target = EdgeEndpoint::new(node_2_id, default());
+        let connection = Connection { source, target };
+        let nodes = &graph_editor.model.nodes;
+        assert!(!nodes.is_connection_hidden(connection));
+
+        graph_editor.set_node_edges_hidden((node_1_id, true));
+        assert!(nodes.is_connection_hidden(connection));
+        // Hovering the stubs of any of the connected nodes reveals the edge.
+        nodes.set_edges_revealed(node_2_id, true);
+        assert!(!nodes.is_connection_hidden(connection));
+        nodes.set_edges_revealed(node_2_id, false);
+        assert!(nodes.is_connection_hidden(connection));
+
+        graph_editor.remove_node(node_1_id);
+        assert!(nodes.edges_hidden.is_empty());
+    }
+
+    #[test]
+    fn test_restoring_selection_of_reloaded_nodes() {
+        let (_, graph_editor) = init();
+        let (node_1_id, _) = graph_editor.add_node_by_api_at_pos(Vector2(0.0, 0.0));
+        let (node_2_id, _) = graph_editor.add_node_by_api_at_pos(Vector2(100.0, 0.0));
+        let stable_id_1 = Uuid::new_v4();
+        let stable_id_2 = Uuid::new_v4();
+        graph_editor.set_node_stable_id((node_1_id, stable_id_1));
+        graph_editor.set_node_stable_id((node_2_id, stable_id_2));
+        graph_editor.select_node(node_1_id);
+
+        graph_editor.remove_all_nodes();
+        let (new_node_1_id, _) = graph_editor.add_node_by_api_at_pos(Vector2(0.0, 0.0));
+        let (new_node_2_id, _) = graph_editor.add_node_by_api_at_pos(Vector2(100.0, 0.0));
+        graph_editor.set_node_stable_id((new_node_2_id, stable_id_2));
+        graph_editor.set_node_stable_id((new_node_1_id, stable_id_1));
+        assert_eq!(graph_editor.model.nodes.all_selected(), vec![new_node_1_id]);
+    }
+
+    #[test]
+    fn test_selecting_nodes_by_api() {
+        let (_, graph_editor) = init();
+        let (node_1_id, _) = graph_editor.add_node_by_api_at_pos(Vector2(0.0, 0.0));
+        let (node_2_id, _) = graph_editor.add_node_by_api_at_pos(Vector2(100.0, 0.0));
+        // The programmatic selection ignores the selection mode.
+        graph_editor.enable_node_subtract_select();
+        graph_editor.select_node(node_1_id);
+        graph_editor.select_node(node_2_id);
+        assert_eq!(graph_editor.model.nodes.all_selected(), vec![node_1_id, node_2_id]);
+        assert_eq!(graph_editor.node_selected.value(), node_2_id);
+
+        // Selecting an already selected node does not change the order of the selection.
+        graph_editor.select_node(node_1_id);
+        assert_eq!(graph_editor.model.nodes.all_selected(), vec![node_1_id, node_2_id]);
+        assert_eq!(graph_editor.node_selected.value(), node_2_id);
+
+        graph_editor.deselect_node(node_1_id);
+        assert_eq!(graph_editor.model.nodes.all_selected(), vec![node_2_id]);
+        assert_eq!(graph_editor.node_deselected.value(), node_1_id);
+        graph_editor.disable_node_subtract_select();
+        graph_editor.select_all_nodes();
+        assert_eq!(graph_editor.model.nodes.all_selected(), vec![node_2_id, node_1_id]);
+        assert_eq!(graph_editor.node_selected.value(), node_1_id);
+    }
+
+    #[test]
+    fn test_hover_card_content() {
+        let (_, graph_editor) = init();
+        let (node_id, _) = graph_editor.add_node_by_api_at_pos(Vector2(0.0, 0.0));
+        graph_editor.set_node_execution_duration((node_id, Some(12.0)));
+        graph_editor.set_node_doc_summary((node_id, Some("Sorts the rows.".into())));
+        let content = graph_editor.model.hover_card_content(node_id).expect("Node was not added");
+        assert_eq!(content.lines(), ["Executed in 12.0 ms", "Sorts the rows."]);
+    }
+
+    #[test]
+    fn test_clearing_node_pending_mark() {
+        let (_, graph_editor) = init();
+        let (node_id, node) = graph_editor.add_node_by_api_at_pos(Vector2(0.0, 0.0));
+        graph_editor.set_node_pending((node_id, true));
+        assert!(node.computing.value());
+        graph_editor.set_node_pending((node_id, false));
+        assert!(!node.computing.value());
+
+        graph_editor.set_node_pending((node_id, true));
+        graph_editor.set_node_execution_duration((node_id, Some(12.0)));
+        assert!(!node.computing.value());
+        graph_editor.set_node_pending((node_id, true));
+        graph_editor.set_value_preview_data((node_id, default()));
+        assert!(!node.computing.value());
+    }
+
+    #[test]
+    fn test_marking_subtree_stale() {
+        let (_, graph_editor) = init();
+        let (node_1_id, node_1) = graph_editor.add_node_by_api_at_pos(Vector2(0.0, 0.0));
+        let (node_2_id, node_2) = graph_editor.add_node_by_api_at_pos(Vector2(0.0, -100.0));
+        let (_, node_3) = graph_editor.add_node_by_api_at_pos(Vector2(100.0, 0.0));
+        next_frame();
+        graph_editor.connect_nodes(node_1_id, node_2_id).expect("Edge was not added.");
+
+        graph_editor.mark_subtree_stale(node_1_id);
+        assert!(node_1.stale.value());
+        assert!(node_2.stale.value());
+        assert!(!node_3.stale.value());
+
+        graph_editor.set_visualization_data((node_2_id, default()));
+        assert!(node_1.stale.value());
+        assert!(!node_2.stale.value());
+        graph_editor.set_node_execution_count((node_1_id, 1));
+        assert!(!node_1.stale.value());
+
+        graph_editor.mark_subtree_stale(node_1_id);
+        graph_editor.clear_node_stale(node_1_id);
+        assert!(!node_1.stale.value());
+        graph_editor.set_node_pending((node_2_id, true));
+        assert!(node_2.stale.value());
+        graph_editor.set_node_pending((node_2_id, false));
+        assert!(!node_2.stale.value());
+    }
+
+    #[test]
+    fn test_describing_graph() {
+        let (_, graph_editor) = init();
+        let (node_1_id, _) = graph_editor.add_node_by_api_at_pos(Vector2(100.0, 0.0));
+        let (node_2_id, _) = graph_editor.add_node_by_api_at_pos(Vector2(0.0, -100.0));
+        let (node_3_id, _) = graph_editor.add_node_by_api_at_pos(Vector2(0.0, 0.0));
+        next_frame();
+        let expressions = [(node_1_id, "b = 2"), (node_2_id, "c = a + b"), (node_3_id, "a = 1")];
+        for (node_id, expression) in expressions {
+            graph_editor.set_node_expression((node_id, node::Expression::new_plain(expression)));
+        }
+        graph_editor.connect_nodes(node_1_id, node_2_id).expect("Edge was not added.");
+        let expected = "Graph with 3 nodes and 1 connection.
+Node 1: a = 1
+Node 2: b = 2
+Node 3: c = a + b
+Connection from Node 2 to Node 3";
+        assert_eq!(graph_editor.model.describe_graph(), expected);
+    }
+
+    #[test]
+    fn test_summarizing_node_warnings() {
+        use node::warnings::Warning;
+        use node::warnings::WarningsSummary;
+        let (_, graph_editor) = init();
+        let (node_1_id, node_1) = graph_editor.add_node_by_api_at_pos(Vector2(0.0, 0.0));
+        let (node_2_id, _) = graph_editor.add_node_by_api_at_pos(Vector2(100.0, 0.0));
+        let warnings = Rc::new(vec![Warning::default(); 2]);
+        graph_editor.set_node_warnings((node_1_id, warnings.clone()));
+        graph_editor.set_node_warnings((node_2_id, Rc::new(vec![Warning::default()])));
+        assert_eq!(node_1.warnings.value(), warnings);
+        let summary = graph_editor.model.warnings_summary();
+        assert_eq!(summary, WarningsSummary { warnings: 3, nodes: 2 });
+
+        graph_editor.remove_node(node_2_id);
+        let summary = graph_editor.model.warnings_summary();
+        assert_eq!(summary, WarningsSummary { warnings: 2, nodes: 1 });
+    }
+
+    #[test]
+    fn test_accepting_generated_description() {
+        let (_, graph_editor) = init();
+        let (node_id, node) = graph_editor.add_node_by_api_at_pos(Vector2(0.0, 0.0));
+        let expression = node::Expression::new_plain("operator1.sort");
+        graph_editor.set_node_expression((node_id, expression));
+        graph_editor.request_node_description(node_id);
+        let requested = graph_editor.node_description_requested.value();
+        assert_eq!(requested, (node_id, "operator1.sort".into()));
+        assert!(node.model().description.visible.value());
+
+        let description: ImString = "Sorts the table.".into();
+        graph_editor.set_node_description((node_id, Some(description.clone())));
+        node.model().description.accept();
+        assert_eq!(graph_editor.node_comment_set.value(), (node_id, description));
+        assert!(!node.model().description.visible.value());
+    }
+
+    #[test]
+    fn test_type_compatibility() {
+        let accepts = |target: &str, source: &str| {
+            Type::from(target.to_string()).accepts(&Type::from(source.to_string()))
+        };
+        assert!(accepts("Number", "Integer"));
+        assert!(accepts("Standard.Base.Data.Numbers.Number", "Float"));
+        assert!(!accepts("Integer", "Number"));
+        assert!(accepts("Standard.Base.Any.Any", "Text"));
+        assert!(accepts("Text", "Any"));
+        assert!(accepts("", "Text"));
+        assert!(accepts("Standard.Base.Data.Text.Text", "Text"));
+        assert!(accepts("Vector", "Standard.Base.Data.Vector.Vector Integer"));
+        assert!(accepts("Vector Text", "Vector Integer"));
+        assert!(accepts("Text | Integer", "Integer"));
+        assert!(accepts("Boolean | (Text | Integer)", "Integer"));
+        assert!(accepts("Text", "(Integer | Text)"));
+        assert!(accepts("a", "Integer"));
+        assert!(!accepts("Text", "Integer"));
+        assert!(!accepts("Vector (Integer | Text)", "Text"));
+        assert!(!accepts("Table.Table", "Column.Column"));
+    }
+
+    #[test]
+    fn test_dragging_node() {
+        let (_, graph_editor) = init();
+        let (node_id, node) = graph_editor.add_node_by_api_at_pos(Vector2(0.0, 0.0));
+        next_frame();
+        graph_editor.drag_node(node_id, Vector2(40.0, -30.0));
+        assert_eq!(node.position().xy(), Vector2(40.0, -30.0));
+    }
+
+    #[test]
+    fn test_finding_nodes_at_position() {
+        let (_, graph_editor) = init();
+        let (node_1_id, node_1) = graph_editor.add_node_by_api_at_pos(Vector2(0.0, 0.0));
+        let (node_2_id, node_2) = graph_editor.add_node_by_api_at_pos(Vector2(300.0, 0.0));
+        next_frame();
+        let model = &graph_editor.model;
+        let center = |node: &Node| node.bounding_box.value().center();
+        assert_eq!(model.node_at_scene_position(center(&node_1)), Some(node_1_id));
+        assert_eq!(model.node_at_scene_position(center(&node_2)), Some(node_2_id));
+        assert_eq!(model.node_at_scene_position(Vector2(150.0, 200.0)), None);
+
+        let far_away = Vector2(5000.0, 5000.0);
+        graph_editor.request_node_at_screen_position(far_away);
+        assert_eq!(graph_editor.node_at_screen_position.value(), (far_away, None));
+        graph_editor.request_edge_at_screen_position(far_away);
+        assert_eq!(graph_editor.edge_at_screen_position.value(), (far_away, None));
+    }
+
     #[test]
     // The alignment is disabled for mouse-oriented node placement. See [`new_node_position`] docs.
     #[ignore]
@@ -3559,31 +6824,11 @@ mod tests {
         test_magnet_alignment_when_adding_node(move_mouse_and_add_node_by_shortcut);
     }
 
-    fn move_mouse_and_add_node_by_shortcut(
-        scene: &Scene,
-        editor: &GraphEditor,
-        mouse_pos: Vector2,
-    ) {
-        scene.mouse.hover_background(mouse_pos);
-        press_add_node_shortcut(editor);
-    }
-
     #[test]
     fn test_magnet_alignment_when_adding_node_by_add_node_button() {
         test_magnet_alignment_when_adding_node(move_camera_and_click_add_node_button);
     }
 
-    fn move_camera_and_click_add_node_button(
-        scene: &Scene,
-        editor: &GraphEditor,
-        camera_pos: Vector2,
-    ) {
-        let camera = &scene.camera();
-        camera.set_xy(camera_pos);
-        camera.update(scene);
-        click_add_node_button(editor);
-    }
-
     fn test_magnet_alignment_when_adding_node(add_node_at: impl Fn(&Scene, &GraphEditor, Vector2)) {
         let (app, graph_editor) = init();
         let scene = &app.display.default_scene;
@@ -3674,37 +6919,6 @@ mod tests {
     }
 
     impl GraphEditor {
-        fn num_edges(&self) -> usize {
-            self.model.edges.borrow().len()
-        }
-
-        /// Get the number of nodes currently present in the graph.
-        pub fn num_nodes(&self) -> usize {
-            self.model.nodes.len()
-        }
-
-        fn add_node_by<F: Fn(&GraphEditor)>(&self, add_node: &F) -> (NodeId, Node) {
-            let (old_node_id, ..) = self.node_added.value();
-            add_node(self);
-            let (node_id, ..) = self.node_added.value();
-            assert_ne!(node_id, old_node_id, "Node was not added.");
-            let node = self.model.nodes.get_cloned_ref(&node_id).expect("Node was not added.");
-            node.set_expression(node::Expression::new_plain("some_not_empty_expression"));
-            (node_id, node)
-        }
-
-        fn add_node_by_api(&self) -> (NodeId, Node) {
-            let add_node = |editor: &GraphEditor| editor.add_node();
-            self.add_node_by(&add_node)
-        }
-
-        fn add_node_by_api_at_pos(&self, position: Vector2) -> (NodeId, Node) {
-            let (node_id, node) = self.add_node_by_api();
-            self.stop_editing();
-            node.set_xy(position);
-            (node_id, node)
-        }
-
         fn assert(&self, case: Case) {
             let (added_node, node_source, should_edit) = self.node_added.value();
             let node_being_edited = self.node_being_edited.value();
@@ -3720,13 +6934,4 @@ mod tests {
             assert_eq!(node_source, case.node_source, "Source node does not match expected.");
         }
     }
-
-    fn init() -> (Application, GraphEditor) {
-        let app = Application::new("root");
-        app.set_screen_size_for_tests();
-        let graph_editor = GraphEditor::new(&app);
-        app.display.add_child(&graph_editor);
-        next_frame();
-        (app, graph_editor)
-    }
 }
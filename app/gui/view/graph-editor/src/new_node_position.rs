@@ -14,6 +14,7 @@ use crate::NodeId;
 use crate::WayOfCreatingNode;
 
 use ensogl_hardcoded_theme as theme;
+use ordered_float::OrderedFloat;
 
 
 // ==============
@@ -28,8 +29,86 @@ pub mod free_place_finder;
 /// === New Node Positioning ===
 /// ============================
 
-/// Return a position for a newly created node. The position is calculated by establishing a
-/// reference position and then aligning it to existing nodes.
+/// Return a position for a newly created node, using the placement strategy currently selected in
+/// the graph editor (see [`PlacementStrategy`]). Nodes added with the `add_node` FRP input are
+/// always placed at the origin.
+pub fn new_node_position(
+    graph_editor: &GraphEditorModel,
+    way: &WayOfCreatingNode,
+    mouse_position: Vector2,
+) -> Vector2 {
+    match way {
+        WayOfCreatingNode::AddNodeEvent => default(),
+        _ => {
+            let strategy = graph_editor.frp_public.output.node_placement_strategy.value();
+            strategy.strategy.position(graph_editor, way, mouse_position)
+        }
+    }
+}
+
+
+
+// ============================
+// === Placement Strategies ===
+// ============================
+
+/// A way of choosing the position for a newly created node.
+pub trait Strategy: Debug {
+    /// Return a position for a node created in the given `way`, with the mouse pointer at
+    /// `mouse_position` in the scene coordinates.
+    fn position(
+        &self,
+        graph_editor: &GraphEditorModel,
+        way: &WayOfCreatingNode,
+        mouse_position: Vector2,
+    ) -> Vector2;
+}
+
+/// The strategy of placing newly created nodes, selected with the `set_node_placement_strategy`
+/// FRP input of the graph editor. Defaults to [`BelowSource`].
+#[derive(Clone, CloneRef, Debug)]
+pub struct PlacementStrategy {
+    strategy: Rc<dyn Strategy>,
+}
+
+impl PlacementStrategy {
+    /// Constructor of a custom strategy.
+    pub fn new(strategy: impl Strategy + 'static) -> Self {
+        Self { strategy: Rc::new(strategy) }
+    }
+
+    /// See [`BelowSource`].
+    pub fn below_source() -> Self {
+        Self::new(BelowSource)
+    }
+
+    /// See [`NearestFreeSpace`].
+    pub fn nearest_free_space() -> Self {
+        Self::new(NearestFreeSpace)
+    }
+
+    /// See [`Cursor`].
+    pub fn cursor() -> Self {
+        Self::new(Cursor)
+    }
+
+    /// See [`GridFill`].
+    pub fn grid_fill() -> Self {
+        Self::new(GridFill)
+    }
+}
+
+impl Default for PlacementStrategy {
+    fn default() -> Self {
+        Self::below_source()
+    }
+}
+
+
+// === BelowSource ===
+
+/// Place the node below its source node, or below the selected nodes. The position is calculated
+/// by establishing a reference position and then aligning it to existing nodes.
 ///
 /// **Note** The aligning nodes is currently disabled for nodes which were created under mouse
 /// position (including dropping an edge), as it turned out to be confusing for users. It may be
@@ -46,7 +125,7 @@ pub mod free_place_finder;
 ///  - the selected nodes (if available),
 ///  - the node closest to the reference position (if available),
 ///  - not aligned.
-/// The choice among the options described above is governed by the `way`.
+/// The choice among the options described above is governed by the way of creating the node.
 ///
 /// The Magnet Alignment algorithm is used to calculate the final position in the following cases:
 ///  - When creating node with (+) button without nodes selected.
@@ -54,30 +133,135 @@ pub mod free_place_finder;
 ///  - When the node is pushed left due to lack of space - only horizontally.
 ///
 /// To learn more about the align algorithm, see the docs of [`aligned_if_close_to_node`].
-pub fn new_node_position(
-    graph_editor: &GraphEditorModel,
-    way: &WayOfCreatingNode,
-    mouse_position: Vector2,
-) -> Vector2 {
-    use WayOfCreatingNode::*;
-    let scene = graph_editor.scene();
-    let origin = Vector2(0.0, 0.0);
-    let screen_center = scene.screen_to_object_space(&graph_editor.display_object, origin);
-    let some_nodes_are_selected = !graph_editor.nodes.selected.is_empty();
-    match way {
-        AddNodeEvent => default(),
-        StartCreationEvent | ClickingButton if some_nodes_are_selected =>
-            under_selected_nodes(graph_editor),
-        StartCreationEvent => mouse_position,
-        ClickingButton => {
-            let pos = on_ray(graph_editor, screen_center, Vector2(0.0, -1.0)).unwrap();
-            magnet_alignment(graph_editor, pos, HorizontallyAndVertically)
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BelowSource;
+
+impl Strategy for BelowSource {
+    fn position(
+        &self,
+        graph_editor: &GraphEditorModel,
+        way: &WayOfCreatingNode,
+        mouse_position: Vector2,
+    ) -> Vector2 {
+        use WayOfCreatingNode::*;
+        let some_nodes_are_selected = !graph_editor.nodes.selected.is_empty();
+        match way {
+            AddNodeEvent => default(),
+            StartCreationEvent | ClickingButton if some_nodes_are_selected =>
+                under_selected_nodes(graph_editor),
+            StartCreationEvent => mouse_position,
+            ClickingButton => {
+                let screen_center = screen_center(graph_editor);
+                let pos = on_ray(graph_editor, screen_center, Vector2(0.0, -1.0)).unwrap();
+                magnet_alignment(graph_editor, pos, HorizontallyAndVertically)
+            }
+            DroppingEdge { .. } => mouse_position,
+            StartCreationFromPortEvent { endpoint } => under(graph_editor, endpoint.node_id),
         }
-        DroppingEdge { .. } => mouse_position,
-        StartCreationFromPortEvent { endpoint } => under(graph_editor, endpoint.node_id),
     }
 }
 
+
+// === NearestFreeSpace ===
+
+/// Place the node at the available position closest to the mouse pointer (or to the source node,
+/// or to the screen center, depending on the way of creating the node), without aligning it to
+/// other nodes.
+///
+/// Availability of a position is defined in the docs of [`on_ray`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NearestFreeSpace;
+
+impl Strategy for NearestFreeSpace {
+    fn position(
+        &self,
+        graph_editor: &GraphEditorModel,
+        way: &WayOfCreatingNode,
+        mouse_position: Vector2,
+    ) -> Vector2 {
+        use WayOfCreatingNode::*;
+        let reference = match way {
+            AddNodeEvent => default(),
+            StartCreationEvent | DroppingEdge { .. } => mouse_position,
+            ClickingButton => screen_center(graph_editor),
+            StartCreationFromPortEvent { endpoint } => graph_editor.node_position(endpoint.node_id),
+        };
+        nearest_available_position(graph_editor, reference)
+    }
+}
+
+/// Return the available position closest to the `reference` point, searching along rays in eight
+/// directions around it.
+///
+/// Availability of a position is defined in the docs of [`on_ray`].
+pub fn nearest_available_position(graph_editor: &GraphEditorModel, reference: Vector2) -> Vector2 {
+    let directions = [(0.0, -1.0), (1.0, 0.0), (-1.0, 0.0), (0.0, 1.0)];
+    let diagonals = [(1.0, -1.0), (-1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)];
+    let directions = directions.into_iter().chain(diagonals).map(|(x, y)| Vector2(x, y));
+    let candidates = directions.filter_map(|direction| on_ray(graph_editor, reference, direction));
+    let distance = |position: &Vector2| OrderedFloat((position - reference).norm_squared());
+    candidates.min_by_key(distance).unwrap_or(reference)
+}
+
+
+// === Cursor ===
+
+/// Place the node exactly at the mouse pointer, even if it overlaps other nodes.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Cursor;
+
+impl Strategy for Cursor {
+    fn position(&self, _: &GraphEditorModel, _: &WayOfCreatingNode, mouse: Vector2) -> Vector2 {
+        mouse
+    }
+}
+
+
+// === GridFill ===
+
+/// The number of columns of the grid filled by the [`GridFill`] strategy.
+const GRID_FILL_COLUMNS: usize = 4;
+
+/// Place the node in the first available cell of a grid, filled row by row, starting from the
+/// top-left-most node. The cells are big enough to fit a node with the default gaps around it. If
+/// there are no nodes, the grid starts at the screen center.
+///
+/// Availability of a position is defined in the docs of [`on_ray`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GridFill;
+
+impl Strategy for GridFill {
+    fn position(
+        &self,
+        graph_editor: &GraphEditorModel,
+        _: &WayOfCreatingNode,
+        _: Vector2,
+    ) -> Vector2 {
+        let x_gap = graph_editor.frp_public.output.default_x_gap_between_nodes.value();
+        let y_gap = graph_editor.frp_public.output.default_y_gap_between_nodes.value();
+        let min_spacing = graph_editor.frp_public.output.min_x_spacing_for_new_nodes.value();
+        let cell_size = Vector2(min_spacing + x_gap, node::HEIGHT + y_gap);
+        let nodes = graph_editor.nodes.all.raw.borrow();
+        let positions = nodes.values().map(|node| node.position().xy());
+        let origin = positions.reduce(|a, b| Vector2(min(a.x, b.x), max(a.y, b.y)));
+        let origin = origin.unwrap_or_else(|| screen_center(graph_editor));
+        let cells = (0..).flat_map(|row| (0..GRID_FILL_COLUMNS).map(move |column| (row, column)));
+        let cell_position = |(row, column): (usize, usize)| {
+            origin + Vector2(column as f32 * cell_size.x, -(row as f32) * cell_size.y)
+        };
+        let is_available = |position: &Vector2| {
+            on_ray(graph_editor, *position, Vector2(0.0, -1.0)) == Some(*position)
+        };
+        cells.map(cell_position).find(is_available).unwrap_or(origin)
+    }
+}
+
+
+
+// ===========================
+// === Placement Functions ===
+// ===========================
+
 /// Return a position for a newly created node closely below all selected nodes, or a zero vector
 /// if no nodes are selected. The position is left-aligned to the first selected node, then moved
 /// to the left to the first available position if the initial position is not available.
@@ -320,6 +504,13 @@ fn magnet_alignment(
 /// === Private Helper Functions ===
 /// ================================
 
+/// Return the point of the scene displayed in the center of the screen.
+fn screen_center(graph_editor: &GraphEditorModel) -> Vector2 {
+    let scene = graph_editor.scene();
+    let origin = Vector2(0.0, 0.0);
+    scene.screen_to_object_space(&graph_editor.display_object, origin)
+}
+
 /// Return a node nearest to the specified point.
 ///
 /// The distance between a point and a node is the distance between the point and the node's
@@ -1,5 +1,6 @@
 //! Module that contains the logic for selecting nodes. This includes selecting single nodes
-//! by clicking on them separately, as well as click+drag for selecting with a selection area.
+//! by clicking on them separately, as well as click+drag for selecting with a selection area. The
+//! selection area is either a rectangle, or a freeform lasso when the lasso mode is enabled.
 
 use ensogl::prelude::*;
 
@@ -20,6 +21,15 @@ pub use ensogl::data::bounding_box::BoundingBox;
 
 
 
+// =================
+// === Constants ===
+// =================
+
+/// The minimal distance between the consecutive points of the lasso path, in the scene units.
+const LASSO_MIN_SEGMENT_LENGTH: f32 = 4.0;
+
+
+
 // ============
 // === Mode ===
 // ============
@@ -96,6 +106,76 @@ impl Default for Mode {
 
 
 
+// ===============
+// === Preview ===
+// ===============
+
+/// The change of a node's selection state caused by the ongoing area selection.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[allow(missing_docs)]
+pub enum Change {
+    Select,
+    Deselect,
+}
+
+/// The nodes whose selection state is changed by the ongoing area selection. Displayed until the
+/// mouse is released, so the user can tell what the area selects in the current mode.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Preview {
+    /// The nodes selected by the area, which were not selected before the area selection.
+    pub selected:   Vec<NodeId>,
+    /// The nodes deselected by the area, which were selected before the area selection.
+    pub deselected: Vec<NodeId>,
+}
+
+impl Preview {
+    fn new(mode: Mode, nodes_in_area: &[TemporarySelection]) -> Self {
+        let mut preview = Self::default();
+        for node in nodes_in_area {
+            if !node.was_selected && mode.area_should_select(node.was_selected) {
+                preview.selected.push(node.node);
+            } else if node.was_selected && mode.area_should_deselect(node.was_selected) {
+                preview.deselected.push(node.node);
+            }
+        }
+        preview.selected.sort();
+        preview.deselected.sort();
+        preview
+    }
+
+    /// Return whether the area selection changes no node's selection state.
+    pub fn is_empty(&self) -> bool {
+        self.selected.is_empty() && self.deselected.is_empty()
+    }
+
+    /// The change of the node's selection state, if the node is affected by the area selection.
+    pub fn change(&self, node: NodeId) -> Option<Change> {
+        if self.selected.contains(&node) {
+            Some(Change::Select)
+        } else if self.deselected.contains(&node) {
+            Some(Change::Deselect)
+        } else {
+            None
+        }
+    }
+
+    /// The label of the badge displayed next to the cursor, counting the selected and deselected
+    /// nodes, e.g. `+3 −1`.
+    pub fn label(&self) -> String {
+        let selected = (!self.selected.is_empty()).then(|| format!("+{}", self.selected.len()));
+        let deselected =
+            (!self.deselected.is_empty()).then(|| format!("−{}", self.deselected.len()));
+        let counts: Vec<_> = [selected, deselected].into_iter().flatten().collect();
+        if counts.is_empty() {
+            "0".into()
+        } else {
+            counts.join(" ")
+        }
+    }
+}
+
+
+
 // ==========================
 // === TemporarySelection ===
 // ==========================
@@ -185,6 +265,10 @@ mod node_set {
         fn reset(&self) {
             self.set.borrow_mut().clear()
         }
+
+        fn items(&self) -> Vec<SetItem> {
+            self.set.borrow().iter().copied().collect()
+        }
     }
 
     #[derive(Clone, CloneRef, Debug)]
@@ -200,6 +284,11 @@ mod node_set {
             Self { frp, model }.init()
         }
 
+        /// All elements of the set, in arbitrary order.
+        pub fn items(&self) -> Vec<SetItem> {
+            self.model.items()
+        }
+
         fn init(self) -> Self {
             let network = &self.frp.network;
             let frp = &self.frp;
@@ -240,6 +329,39 @@ fn get_nodes_in_bounding_box(bounding_box: &BoundingBox, nodes: &Nodes) -> Vec<N
         .collect()
 }
 
+/// The nodes whose centers lie inside the polygon formed by the lasso path.
+fn get_nodes_in_lasso(path: &[Vector2], nodes: &Nodes) -> Vec<NodeId> {
+    let nodes_raw = nodes.all.raw.as_ref().borrow();
+    nodes_raw
+        .iter()
+        .filter_map(|(id, node)| {
+            polygon_contains(path, node.view.inner_bounding_box.value().center()).as_some(*id)
+        })
+        .collect()
+}
+
+/// Check whether the point lies inside the polygon, which is implicitly closed by connecting its
+/// last vertex with the first one. Uses the even-odd rule, so self-intersecting polygons are
+/// supported.
+fn polygon_contains(polygon: &[Vector2], point: Vector2) -> bool {
+    let edges = polygon.iter().zip(polygon.iter().cycle().skip(1));
+    let crossings = edges.filter(|(a, b)| {
+        let crosses_y = (a.y > point.y) != (b.y > point.y);
+        crosses_y && point.x < a.x + (point.y - a.y) * (b.x - a.x) / (b.y - a.y)
+    });
+    crossings.count() % 2 == 1
+}
+
+/// Append the point to the lasso path, unless it is too close to the last point of the path.
+fn extend_lasso_path(path: &[Vector2], point: Vector2) -> Option<Rc<Vec<Vector2>>> {
+    let is_far_enough = |last: &Vector2| (point - last).norm() >= LASSO_MIN_SEGMENT_LENGTH;
+    path.last().map_or(true, is_far_enough).then(|| {
+        let mut path = path.to_vec();
+        path.push(point);
+        Rc::new(path)
+    })
+}
+
 /// Return an FRP endpoint that indicates the current selection mode. This method sets up the logic
 /// for deriving the selection mode from the graph editor FRP.
 pub fn get_mode(network: &frp::Network, editor: &crate::Frp) -> frp::stream::Stream<Mode> {
@@ -319,6 +441,11 @@ pub(super) struct Controller {
 
     pub cursor_style:   frp::stream::Stream<cursor::Style>,
     pub area_selection: frp::stream::Stream<bool>,
+    /// The path dragged by the mouse during the lasso selection, in the scene coordinates. Empty
+    /// when there is no lasso selection.
+    pub lasso_path:     frp::stream::Stream<Rc<Vec<Vector2>>>,
+    /// The nodes affected by the ongoing area selection. Empty when there is no area selection.
+    pub preview:        frp::stream::Stream<Rc<Preview>>,
 }
 
 impl Controller {
@@ -331,6 +458,12 @@ impl Controller {
     ) -> Self {
         let network = frp::Network::new("selection::Controller");
         let selection_mode = get_mode(&network, editor);
+        let lasso_flag = enable_disable_toggle(
+            &network,
+            &editor.enable_node_lasso_select,
+            &editor.disable_node_lasso_select,
+            &editor.toggle_node_lasso_select,
+        );
         let cursor_selection_nodes = node_set::Set::new();
 
         let editor = &editor.private;
@@ -341,11 +474,20 @@ impl Controller {
             enable_area_selection  <- source();
 
             // ===  Graph Editor Internal API ===
-            eval editor.input.select_node       ((node_id) nodes.select(node_id));
-            eval editor.input.deselect_node     ((node_id) nodes.deselect(node_id));
-            eval_ editor.input.select_all_nodes (nodes.select_all());
-            editor.output.node_selected   <+  editor.input.select_node;
-            editor.output.node_deselected <+ editor.input.deselect_node;
+            // The programmatic selection ignores the selection mode: it changes the selection
+            // state of the given nodes only, leaving the other nodes selected. The outputs are
+            // emitted only for the nodes which actually change their state, and the graph editor
+            // updates the `nodes` in response to them.
+            api_nodes_to_select  <= editor.input.select_all_nodes.map(f_!(nodes.keys()));
+            api_node_to_select   <- any(&editor.input.select_node,&api_nodes_to_select);
+            api_node_to_select   <- api_node_to_select.filter(f!([nodes](id)
+                nodes.contains_key(id) && !nodes.is_selected(*id)
+            ));
+            api_node_to_deselect <- editor.input.deselect_node.filter(f!((id)
+                nodes.is_selected(*id)
+            ));
+            editor.output.node_selected   <+ api_node_to_select;
+            editor.output.node_deselected <+ api_node_to_deselect;
 
             // ===  Selection Box & Mouse IO ===
             on_press_style   <- mouse.down_primary . constant(cursor::Style::new_press());
@@ -360,11 +502,14 @@ impl Controller {
             drag_end    <- is_dragging.on_false();
             drag_start  <- is_dragging.on_true();
             let area_selection = is_dragging.clone_ref();
+            lasso_on_drag_start <- lasso_flag.sample(&drag_start);
+            lasso_on_drag_end   <- drag_end.constant(false);
+            is_lasso            <- any(lasso_on_drag_start,lasso_on_drag_end);
 
             mouse_on_down_position <- mouse.position.sample(&mouse.down_primary);
             selection_size_down    <- mouse.position.map2(&mouse_on_down_position,|m,n|{m-n});
             selection_size         <- selection_size_down.gate(&touch.background.is_down).gate(&should_area_select);
-            cursor_selection_start <- selection_size.map(|p|
+            cursor_selection_start <- selection_size.gate_not(&is_lasso).map(|p|
                     cursor::Style::new_with_all_fields_default().press().box_selection(Vector2::new(p.x,p.y)));
             cursor_selection_end   <- mouse.up_primary . constant(cursor::Style::default());
             cursor_selection       <- any (cursor_selection_start,cursor_selection_end);
@@ -373,7 +518,8 @@ impl Controller {
             should_update_drag      <- is_dragging && touch.background.is_down;
             cursor_drag_position    <- cursor.frp.scene_position.gate(&should_update_drag).on_change();
 
-            scene_bounding_box      <- cursor_drag_position.map2(&cursor_on_down_position,
+            box_drag_position       <- cursor_drag_position.gate_not(&is_lasso);
+            scene_bounding_box      <- box_drag_position.map2(&cursor_on_down_position,
                 |&m,&n|{
                 // The dragged position is the center of the bounding box. Thus we need to offset the
                 // corner point by the distance to the origin.
@@ -388,6 +534,19 @@ impl Controller {
             );
 
             nodes_in_bb <- scene_bounding_box.map(f!([nodes](bb) get_nodes_in_bounding_box(bb,&nodes)));
+
+            // The lasso path starts at the press position and is extended while dragging. It is
+            // closed implicitly, by connecting the current cursor position with the start.
+            lasso_path        <- any_mut::<Rc<Vec<Vector2>>>();
+            lasso_started     <- lasso_on_drag_start.on_true();
+            lasso_start       <- cursor_on_down_position.sample(&lasso_started);
+            lasso_path        <+ lasso_start.map(|start| Rc::new(vec![start.xy()]));
+            lasso_drag        <- cursor_drag_position.gate(&is_lasso);
+            lasso_path        <+ lasso_drag.map2(&lasso_path,|pos,path| extend_lasso_path(path,pos.xy())).unwrap();
+            lasso_path        <+ drag_end.constant(default());
+            lasso_path_update <- lasso_path.sample(&lasso_drag);
+            nodes_in_lasso    <- lasso_path_update.map(f!([nodes](path) get_nodes_in_lasso(path,&nodes)));
+            nodes_in_bb       <- any(nodes_in_bb,nodes_in_lasso);
             nodes_in_bb <- nodes_in_bb.map(f!([nodes](nodes_selected) {
                 nodes_selected.clone().into_iter().map(|node|{
                      let is_selected = nodes.is_selected(node);
@@ -427,6 +586,14 @@ impl Controller {
 
             editor.output.node_deselected <+ node_removed;
 
+            // The set keeps the selection state the nodes had before entering the area, so the
+            // preview is not affected by the selection changes made while dragging.
+            preview <- nodes_in_bb.map2(&selection_mode, f!([cursor_selection_nodes](_, mode)
+                Rc::new(Preview::new(*mode, &cursor_selection_nodes.items()))
+            ));
+            preview_reset <- drag_end.constant(default());
+            preview <- any(preview, preview_reset);
+
 
             // ===  Single Node Selection Box & Mouse IO ===
 
@@ -473,6 +640,66 @@ impl Controller {
             enable_area_selection,
             cursor_style,
             area_selection,
+            preview: preview.into(),
+            lasso_path: lasso_path.into(),
         }
     }
 }
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn previewing_area_selection() {
+        let id = |id: usize| NodeId(id.into());
+        let node = |node: usize, was_selected| TemporarySelection::new(id(node), was_selected);
+        let nodes = [node(1, false), node(2, true), node(3, false)];
+        let preview = Preview::new(Mode::Merge, &nodes);
+        assert_eq!(preview.selected, [id(1), id(3)]);
+        assert!(preview.deselected.is_empty());
+        assert_eq!(preview.label(), "+2");
+
+        let preview = Preview::new(Mode::Subtract, &nodes);
+        assert_eq!(preview.change(id(2)), Some(Change::Deselect));
+        assert_eq!(preview.change(id(1)), None);
+        assert_eq!(preview.label(), "−1");
+
+        assert_eq!(Preview::new(Mode::Inverse, &nodes).label(), "+2 −1");
+        assert_eq!(Preview::new(Mode::Subtract, &nodes[..1]).label(), "0");
+    }
+
+    #[test]
+    fn checking_points_inside_lasso() {
+        // A concave, "U"-shaped polygon.
+        let polygon = [
+            Vector2(0.0, 0.0),
+            Vector2(30.0, 0.0),
+            Vector2(30.0, 30.0),
+            Vector2(20.0, 30.0),
+            Vector2(20.0, 10.0),
+            Vector2(10.0, 10.0),
+            Vector2(10.0, 30.0),
+            Vector2(0.0, 30.0),
+        ];
+        assert!(polygon_contains(&polygon, Vector2(5.0, 20.0)));
+        assert!(polygon_contains(&polygon, Vector2(15.0, 5.0)));
+        assert!(!polygon_contains(&polygon, Vector2(15.0, 20.0)));
+        assert!(!polygon_contains(&polygon, Vector2(40.0, 5.0)));
+        assert!(!polygon_contains(&polygon[..2], Vector2(15.0, 0.0)));
+    }
+
+    #[test]
+    fn extending_lasso_path() {
+        let path = extend_lasso_path(&[], Vector2(0.0, 0.0)).unwrap();
+        assert_eq!(extend_lasso_path(&path, Vector2(1.0, 1.0)), None);
+        let path = extend_lasso_path(&path, Vector2(3.0, 4.0)).unwrap();
+        assert_eq!(*path, vec![Vector2(0.0, 0.0), Vector2(3.0, 4.0)]);
+    }
+}
@@ -27,6 +27,8 @@ pub const SHORTCUTS: &[(ensogl::application::shortcut::ActionType, &str, &str, &
     ),
     (Press, "has_detached_edge", "escape", "drop_dragged_edge"),
     (Press, "!read_only & !is_fs_visualization_displayed", "cmd g", "collapse_selected_nodes"),
+    (Press, "collapse_preview_visible", "enter", "confirm_collapse_preview"),
+    (Press, "collapse_preview_visible", "escape", "cancel_collapse_preview"),
     // === Visualization ===
     (Press, "!node_editing", "space", "press_visualization_visibility"),
     (
@@ -52,6 +54,14 @@ pub const SHORTCUTS: &[(ensogl::application::shortcut::ActionType, &str, &str, &
     (Release, "", "shift alt", "toggle_node_subtract_select"),
     (Press, "", "shift ctrl alt", "toggle_node_inverse_select"),
     (Release, "", "shift ctrl alt", "toggle_node_inverse_select"),
+    (Press, "", "alt", "enable_node_lasso_select"),
+    (Press, "", "alt left-mouse-button", "enable_node_lasso_select"),
+    (Release, "", "alt", "disable_node_lasso_select"),
+    (Release, "", "alt left-mouse-button", "disable_node_lasso_select"),
+    (Press, "!node_editing", "cmd shift f", "toggle_focus_mode"),
+    (Press, "!node_editing", "f9", "toggle_breakpoint_for_selected_nodes"),
+    (Press, "!node_editing & !read_only", "cmd alt f", "toggle_freeze_for_selected_nodes"),
+    (Press, "!node_editing & !read_only", "cmd alt s", "toggle_skip_for_selected_nodes"),
     // === Navigation ===
     (
         Press,
@@ -74,6 +84,33 @@ pub const SHORTCUTS: &[(ensogl::application::shortcut::ActionType, &str, &str, &
         "enter_selected_node",
     ),
     (Press, "!read_only & !is_fs_visualization_displayed", "alt enter", "exit_node"),
+    (
+        Press,
+        "!node_editing & !is_fs_visualization_displayed",
+        "cmd b",
+        "goto_definition_of_selected_node",
+    ),
+    (Press, "!node_editing", "alt space", "peek_selected_node"),
+    (Press, "node_peek_visible", "escape", "hide_node_peek"),
+    // === Camera Bookmarks ===
+    (Press, "!node_editing", "cmd shift 1", "save_camera_bookmark_1"),
+    (Press, "!node_editing", "cmd shift 2", "save_camera_bookmark_2"),
+    (Press, "!node_editing", "cmd shift 3", "save_camera_bookmark_3"),
+    (Press, "!node_editing", "cmd shift 4", "save_camera_bookmark_4"),
+    (Press, "!node_editing", "cmd shift 5", "save_camera_bookmark_5"),
+    (Press, "!node_editing", "cmd shift 6", "save_camera_bookmark_6"),
+    (Press, "!node_editing", "cmd shift 7", "save_camera_bookmark_7"),
+    (Press, "!node_editing", "cmd shift 8", "save_camera_bookmark_8"),
+    (Press, "!node_editing", "cmd shift 9", "save_camera_bookmark_9"),
+    (Press, "!node_editing", "cmd 1", "restore_camera_bookmark_1"),
+    (Press, "!node_editing", "cmd 2", "restore_camera_bookmark_2"),
+    (Press, "!node_editing", "cmd 3", "restore_camera_bookmark_3"),
+    (Press, "!node_editing", "cmd 4", "restore_camera_bookmark_4"),
+    (Press, "!node_editing", "cmd 5", "restore_camera_bookmark_5"),
+    (Press, "!node_editing", "cmd 6", "restore_camera_bookmark_6"),
+    (Press, "!node_editing", "cmd 7", "restore_camera_bookmark_7"),
+    (Press, "!node_editing", "cmd 8", "restore_camera_bookmark_8"),
+    (Press, "!node_editing", "cmd 9", "restore_camera_bookmark_9"),
     // === Node Editing ===
     (Press, "!read_only", "cmd", "edit_mode_on"),
     (Release, "!read_only", "cmd", "edit_mode_off"),
@@ -81,12 +118,12 @@ pub const SHORTCUTS: &[(ensogl::application::shortcut::ActionType, &str, &str, &
     (Release, "!read_only", "cmd left-mouse-button", "edit_mode_off"),
     // === Copy-paste ===
     (Press, "!node_editing", "cmd c", "copy_selected_node"),
-    (Press, "!read_only & !node_editing", "cmd v", "paste_node"),
+    // Pasting is handled by the `paste` DOM events, see the `clipboard` module.
     // === Debug ===
     (Press, "debug_mode", "ctrl d", "debug_set_test_visualization_data_for_selected_node"),
     (Press, "debug_mode", "ctrl n", "add_node_at_cursor"),
+    (Press, "debug_mode", "ctrl shift o", "toggle_frp_inspector_overlay"),
     (Press, "", "ctrl shift x", "reopen_file_in_language_server"),
     // Execution Environment
-    (Press, "", "cmd shift k", "switch_to_design_execution_environment"),
-    (Press, "", "cmd shift l", "switch_to_live_execution_environment"),
+    (Press, "", "cmd shift k", "cycle_execution_environment"),
 ];
@@ -0,0 +1,203 @@
+//! Utilities for the view-level tests of the Graph Editor: creating the editor in a headless test
+//! environment, adding nodes, simulating the mouse interactions, and asserting the graph topology.
+//!
+//! These are used by the Graph Editor's own tests, but are public, so the downstream crates can
+//! write their integration tests without re-implementing the mouse emulation.
+
+use crate::prelude::*;
+
+use crate::component::node;
+use crate::component::node::test_utils::NodeModelExt;
+use crate::EdgeId;
+use crate::GraphEditor;
+use crate::Node;
+use crate::NodeId;
+
+use ensogl::animation::test_utils::next_frame;
+use ensogl::application::test_utils::ApplicationExt;
+use ensogl::application::Application;
+use ensogl::control::io::mouse;
+use ensogl::display::scene::test_utils::MouseExt;
+use ensogl::display::Scene;
+
+
+
+// ===================
+// === Constructor ===
+// ===================
+
+/// Create a Graph Editor with the screen size set for tests, and render its first frame.
+pub fn init() -> (Application, GraphEditor) {
+    init_with(GraphEditor::new)
+}
+
+/// Like [`init`], but the ids of the nodes and edges are allocated from a counter starting at
+/// `seed`. See [`GraphEditor::new_deterministic`].
+pub fn init_deterministic(seed: usize) -> (Application, GraphEditor) {
+    init_with(|app| GraphEditor::new_deterministic(app, seed))
+}
+
+fn init_with(new: impl FnOnce(&Application) -> GraphEditor) -> (Application, GraphEditor) {
+    let app = Application::new("root");
+    app.set_screen_size_for_tests();
+    let graph_editor = new(&app);
+    app.display.add_child(&graph_editor);
+    next_frame();
+    (app, graph_editor)
+}
+
+
+
+// ===========================
+// === Node Creation Means ===
+// ===========================
+
+/// Start the node creation, as if by the keyboard shortcut.
+pub fn press_add_node_shortcut(editor: &GraphEditor) {
+    editor.start_node_creation();
+}
+
+/// Start the node creation by clicking the (+) button.
+pub fn click_add_node_button(editor: &GraphEditor) {
+    let adding_node_button = &editor.model.add_node_button;
+    adding_node_button.click();
+}
+
+/// Hover the mouse over the background at the given scene position and start the node creation,
+/// as if by the keyboard shortcut.
+pub fn move_mouse_and_add_node_by_shortcut(scene: &Scene, editor: &GraphEditor, pos: Vector2) {
+    scene.mouse.hover_background(pos);
+    press_add_node_shortcut(editor);
+}
+
+/// Center the camera at the given scene position and start the node creation by clicking the (+)
+/// button.
+pub fn move_camera_and_click_add_node_button(
+    scene: &Scene,
+    editor: &GraphEditor,
+    camera_pos: Vector2,
+) {
+    let camera = &scene.camera();
+    camera.set_xy(camera_pos);
+    camera.update(scene);
+    click_add_node_button(editor);
+}
+
+
+
+// ======================
+// === GraphEditorExt ===
+// ======================
+
+/// Additional [`GraphEditor`] API for tests.
+pub trait GraphEditorExt {
+    /// Get the number of nodes currently present in the graph.
+    fn num_nodes(&self) -> usize;
+
+    /// Get the number of edges currently present in the graph, including the detached ones.
+    fn num_edges(&self) -> usize;
+
+    /// Add a node using the given function, and set its expression to a non-empty one. Panics if
+    /// the function did not add a node.
+    fn add_node_by<F: Fn(&GraphEditor)>(&self, add_node: &F) -> (NodeId, Node);
+
+    /// Add a node using the `add_node` FRP input.
+    fn add_node_by_api(&self) -> (NodeId, Node);
+
+    /// Add a node using the `add_node` FRP input, and move it to the given position.
+    fn add_node_by_api_at_pos(&self, position: Vector2) -> (NodeId, Node);
+
+    /// Simulate dragging the node with the mouse by the given offset, in the scene coordinates.
+    fn drag_node(&self, node_id: NodeId, offset: Vector2);
+
+    /// Connect the first output port of the `source` node with the first input port of the
+    /// `target` node by clicking both of them. Returns the created edge.
+    fn connect_nodes(&self, source: NodeId, target: NodeId) -> Option<EdgeId>;
+
+    /// The connections between the nodes, as the pairs of the source and the target node. Only the
+    /// edges attached at both ends are included. The pairs are sorted, so they can be compared
+    /// directly in the assertions.
+    fn node_connections(&self) -> Vec<(NodeId, NodeId)>;
+
+    /// Assert that there is a connection from the `source` node to the `target` node.
+    fn assert_connected(&self, source: NodeId, target: NodeId) {
+        let connections = self.node_connections();
+        assert!(
+            connections.contains(&(source, target)),
+            "Expected a connection {source:?} → {target:?}, found {connections:?}."
+        );
+    }
+}
+
+impl GraphEditorExt for GraphEditor {
+    fn num_nodes(&self) -> usize {
+        self.model.nodes.len()
+    }
+
+    fn num_edges(&self) -> usize {
+        self.model.edges.borrow().len()
+    }
+
+    fn add_node_by<F: Fn(&GraphEditor)>(&self, add_node: &F) -> (NodeId, Node) {
+        let (old_node_id, ..) = self.node_added.value();
+        add_node(self);
+        let (node_id, ..) = self.node_added.value();
+        assert_ne!(node_id, old_node_id, "Node was not added.");
+        let node = self.model.nodes.get_cloned_ref(&node_id).expect("Node was not added.");
+        node.set_expression(node::Expression::new_plain("some_not_empty_expression"));
+        (node_id, node)
+    }
+
+    fn add_node_by_api(&self) -> (NodeId, Node) {
+        let add_node = |editor: &GraphEditor| editor.add_node();
+        self.add_node_by(&add_node)
+    }
+
+    fn add_node_by_api_at_pos(&self, position: Vector2) -> (NodeId, Node) {
+        let (node_id, node) = self.add_node_by_api();
+        self.stop_editing();
+        node.set_xy(position);
+        (node_id, node)
+    }
+
+    fn drag_node(&self, node_id: NodeId, offset: Vector2) {
+        let mouse = &self.model.scene().mouse;
+        let Some(start) = self.model.with_node(node_id, |node| node.position().xy()) else {
+            panic!("Node {node_id:?} does not exist.")
+        };
+        let end = start + offset;
+        mouse.hover_background(start);
+        // Pressing the node is simulated directly, as the node's pointer target is not known here.
+        self.model.touch_state.nodes.down.emit(node_id);
+        next_frame();
+        mouse.hover_background(end);
+        next_frame();
+        let release_pos = mouse.scene_to_event_position(end);
+        let release = mouse::MouseEventData::primary_at(release_pos);
+        mouse.emit_up(mouse::Up::simulated(release, mouse.screen_shape()));
+        next_frame();
+    }
+
+    fn connect_nodes(&self, source: NodeId, target: NodeId) -> Option<EdgeId> {
+        let mouse = &self.model.scene().mouse;
+        let source = self.model.nodes.get_cloned_ref(&source)?;
+        let target = self.model.nodes.get_cloned_ref(&target)?;
+        let output_port = source.model().output_port_hover_shape()?;
+        mouse.click_on(&output_port, Vector2::zero());
+        let edge_id = self.model.edges.borrow().detached?;
+        // The input ports are enabled by hovering the node, which is simulated directly.
+        target.model().input.frp.set_ports_active(true);
+        next_frame();
+        let input_port = target.model().input_port_hover_shape()?;
+        mouse.click_on(&input_port, Vector2::zero());
+        Some(edge_id)
+    }
+
+    fn node_connections(&self) -> Vec<(NodeId, NodeId)> {
+        let edges = self.model.edges.borrow();
+        let connections = edges.values().filter_map(|edge| {
+            Some((edge.source()?.node_id, edge.target()?.node_id))
+        });
+        connections.sorted().collect()
+    }
+}
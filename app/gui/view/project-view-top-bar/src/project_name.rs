@@ -220,7 +220,8 @@ impl ProjectName {
         }
         init.emit(());
 
-        frp.input.set_name.emit(UNINITIALIZED_PROJECT_NAME.to_string());
+        let uninitialized_name = app.translator().translate(UNINITIALIZED_PROJECT_NAME);
+        frp.input.set_name.emit(uninitialized_name.to_string());
 
         Self { model, frp }
     }
@@ -0,0 +1,524 @@
+//! The docking of panels (like the documentation or the profiler) at the edges of the project view.
+//!
+//! The panels are registered with [`Docking::register_panel`] and placed in the docks according to
+//! the [`DockLayout`]. The panels docked at the same edge are stacked as tabs in the dock's header,
+//! and only the active one is displayed. A dock may be collapsed to its header with the button in
+//! the header's corner, and a tab may be dragged to the zone along another edge to move its panel
+//! there.
+//!
+//! Every change of the layout is emitted through the `layout_changed` output, so it may be stored
+//! and passed to the `restore_layout` input when the project is opened again.
+
+use crate::prelude::*;
+
+use enso_frp as frp;
+use ensogl::application;
+use ensogl::application::frp::API;
+use ensogl::application::Application;
+use ensogl::control::io::mouse;
+use ensogl::display;
+use ensogl::display::shape::Rectangle;
+use ensogl::display::shape::StyleWatch;
+use ensogl::display::shape::INVISIBLE_HOVER_COLOR;
+use ensogl::gui::Widget;
+use ensogl_hardcoded_theme::application::docking as theme;
+use ensogl_text as text;
+
+
+// ==============
+// === Export ===
+// ==============
+
+pub mod layout;
+
+pub use layout::Area;
+pub use layout::DockLayout;
+pub use layout::Edge;
+pub use layout::PanelId;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+const TAB_WIDTH: f32 = 120.0;
+/// Tabs narrower than this are not displayed, e.g. in the header of a collapsed side dock.
+const MIN_TAB_WIDTH: f32 = 48.0;
+const TAB_PADDING: f32 = 10.0;
+const TEXT_SIZE: f32 = 12.0;
+/// The distance the pointer must travel with a pressed tab to drag the tab's panel instead of
+/// activating it.
+const DRAG_THRESHOLD: f32 = 4.0;
+
+
+
+// ===========
+// === FRP ===
+// ===========
+
+ensogl::define_endpoints_2! {
+    Input {
+        /// Set the size of the docking area. The origin of the area is its bottom-left corner.
+        set_size(Vector2),
+        /// Replace the layout, e.g. with the one stored in the project's metadata.
+        restore_layout(DockLayout),
+        /// Move the panel to the edge as its last tab, and display it.
+        dock_panel(PanelId, Edge),
+        /// Display the panel, expanding its dock.
+        activate_panel(PanelId),
+        /// Collapse or expand the dock at the edge.
+        toggle_collapsed(Edge),
+        /// Set the width of a side dock, or the height of the bottom dock.
+        set_dock_size(Edge, f32),
+    }
+    Output {
+        /// The layout after each change, either requested through the inputs or made by the user.
+        layout_changed(DockLayout),
+        /// The area not occupied by the docks, where the rest of the project view should be placed.
+        free_area(Area),
+        /// The size of the content area of a displayed panel. The panel's origin is placed at the
+        /// bottom-left corner of its content area.
+        panel_resized(PanelId, Vector2),
+    }
+}
+
+
+
+// ===========
+// === Tab ===
+// ===========
+
+/// A tab of a docked panel, displayed in the dock's header.
+#[derive(Debug, Clone, CloneRef, display::Object)]
+struct Tab {
+    display_object: display::object::Instance,
+    background:     Rectangle,
+    _label:         text::Text,
+    _network:       frp::Network,
+}
+
+impl Tab {
+    fn new(
+        app: &Application,
+        panel: PanelId,
+        active: bool,
+        style: &StyleWatch,
+        pressed: &frp::Any<PanelId>,
+    ) -> Self {
+        let display_object = display::object::Instance::new_named("DockTab");
+        let background = Rectangle();
+        let label = app.new_view::<text::Text>();
+        display_object.add_child(&background);
+        display_object.add_child(&label);
+        app.display.default_scene.layers.panel_text.add(&label);
+        let background_color = style.get_color(if active { theme::active_tab } else { theme::tab });
+        let text_color = style.get_color(theme::text);
+        background.set_color(background_color).set_pointer_events(true);
+        label.set_single_line_mode(true);
+        label.set_property_default(text::Size(TEXT_SIZE));
+        label.set_property_default(text_color);
+        label.set_content(panel.title());
+        label.set_xy(Vector2(TAB_PADDING, (layout::HEADER_HEIGHT + TEXT_SIZE) / 2.0));
+
+        let network = frp::Network::new("DockTab");
+        let mouse_down = background.on_event::<mouse::Down>();
+        frp::extend! { network
+            pressed <+ mouse_down.filter(|e| e.button() == mouse::PrimaryButton).constant(panel);
+        }
+        Self { display_object, background, _label: label, _network: network }
+    }
+
+    fn set_width(&self, width: f32) {
+        self.background.set_size(Vector2(width, layout::HEADER_HEIGHT));
+    }
+}
+
+
+
+// ================
+// === DockView ===
+// ================
+
+/// The view of a single dock: its background, and its header with the tabs and the collapse
+/// button.
+#[derive(Debug, Clone, CloneRef, display::Object)]
+struct DockView {
+    display_object:  display::object::Instance,
+    background:      Rectangle,
+    header:          Rectangle,
+    collapse_button: Rectangle,
+    collapse_label:  text::Text,
+    tabs:            Rc<RefCell<Vec<Tab>>>,
+    _network:        frp::Network,
+}
+
+impl DockView {
+    fn new(app: &Application, edge: Edge, collapse_pressed: &frp::Any<Edge>) -> Self {
+        let scene = &app.display.default_scene;
+        let display_object = display::object::Instance::new_named("Dock");
+        let background = Rectangle();
+        let header = Rectangle();
+        let collapse_button = Rectangle();
+        let collapse_label = app.new_view::<text::Text>();
+        display_object.add_child(&background);
+        display_object.add_child(&header);
+        display_object.add_child(&collapse_button);
+        display_object.add_child(&collapse_label);
+        scene.layers.panel.add(&display_object);
+        scene.layers.panel_text.add(&collapse_label);
+        background.set_pointer_events(true);
+        collapse_button.set_color(INVISIBLE_HOVER_COLOR).set_pointer_events(true);
+        collapse_button.set_size(Vector2(layout::HEADER_HEIGHT, layout::HEADER_HEIGHT));
+        collapse_label.set_single_line_mode(true);
+        collapse_label.set_property_default(text::Size(TEXT_SIZE));
+        let tabs = default();
+
+        let network = frp::Network::new("Dock");
+        let mouse_down = collapse_button.on_event::<mouse::Down>();
+        frp::extend! { network
+            primary_down <- mouse_down.filter(|e| e.button() == mouse::PrimaryButton);
+            collapse_pressed <+ primary_down.constant(edge);
+        }
+        Self {
+            display_object,
+            background,
+            header,
+            collapse_button,
+            collapse_label,
+            tabs,
+            _network: network,
+        }
+    }
+
+    /// Display the dock in the area, recreating its tabs.
+    fn update(&self, app: &Application, dock: &layout::Dock, area: Area, context: &ModelContext) {
+        let header_y = area.size.y - layout::HEADER_HEIGHT;
+        let button_x = area.size.x - layout::HEADER_HEIGHT;
+        self.display_object.set_xy(area.position);
+        self.background.set_size(area.size);
+        self.background.set_color(context.style.get_color(theme::background));
+        self.header.set_y(header_y);
+        self.header.set_size(Vector2(area.size.x, layout::HEADER_HEIGHT));
+        self.header.set_color(context.style.get_color(theme::header));
+        self.collapse_button.set_xy(Vector2(button_x, header_y));
+        self.collapse_label.set_content(if dock.collapsed { "+" } else { "-" });
+        self.collapse_label.set_property_default(context.style.get_color(theme::text));
+        let label_x = button_x + (layout::HEADER_HEIGHT - TEXT_SIZE / 2.0) / 2.0;
+        self.collapse_label.set_xy(Vector2(label_x, area.size.y - TEXT_SIZE / 2.0));
+
+        for tab in self.tabs.take() {
+            tab.unset_parent();
+        }
+        let tab_count = dock.panels.len().max(1) as f32;
+        let tab_width = TAB_WIDTH.min(button_x / tab_count);
+        if tab_width >= MIN_TAB_WIDTH {
+            let tabs = dock.panels.iter().enumerate().map(|(index, panel)| {
+                let active = !dock.collapsed && dock.active == Some(*panel);
+                let tab = Tab::new(app, *panel, active, &context.style, &context.tab_pressed);
+                tab.set_width(tab_width);
+                tab.set_xy(Vector2(index as f32 * tab_width, header_y));
+                self.display_object.add_child(&tab);
+                tab
+            });
+            *self.tabs.borrow_mut() = tabs.collect();
+        }
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+/// The state shared by the views of the docks.
+#[derive(Debug, Clone, CloneRef)]
+struct ModelContext {
+    style:       StyleWatch,
+    /// Receives the panel whose tab was pressed.
+    tab_pressed: frp::Any<PanelId>,
+}
+
+/// The internal model of [`Docking`].
+#[derive(Debug, Clone, CloneRef, display::Object)]
+pub struct Model {
+    app:              Application,
+    display_object:   display::object::Instance,
+    context:          ModelContext,
+    left:             DockView,
+    right:            DockView,
+    bottom:           DockView,
+    drop_indicator:   Rectangle,
+    layout:           Rc<RefCell<DockLayout>>,
+    size:             Rc<Cell<Vector2>>,
+    panels:           Rc<RefCell<HashMap<PanelId, display::object::Instance>>>,
+    /// The panel whose tab is pressed, and the pointer position at which it was pressed.
+    dragged:          Rc<Cell<Option<(PanelId, Vector2)>>>,
+    collapse_pressed: frp::Any<Edge>,
+    free_area:        frp::Any<Area>,
+    panel_resized:    frp::Any<(PanelId, Vector2)>,
+}
+
+impl Model {
+    fn new(app: &Application, network: &frp::Network) -> Self {
+        let app = app.clone_ref();
+        let scene = &app.display.default_scene;
+        let display_object = display::object::Instance::new_named("Docking");
+        frp::extend! { network
+            tab_pressed <- any_mut::<PanelId>();
+            collapse_pressed <- any_mut::<Edge>();
+            free_area <- any_mut::<Area>();
+            panel_resized <- any_mut::<(PanelId, Vector2)>();
+        }
+        let style = StyleWatch::new(&scene.style_sheet);
+        let context = ModelContext { style, tab_pressed };
+        let left = DockView::new(&app, Edge::Left, &collapse_pressed);
+        let right = DockView::new(&app, Edge::Right, &collapse_pressed);
+        let bottom = DockView::new(&app, Edge::Bottom, &collapse_pressed);
+        let drop_indicator = Rectangle();
+        drop_indicator.set_color(context.style.get_color(theme::drop_indicator));
+        scene.layers.panel.add(&drop_indicator);
+        let layout = default();
+        let size = default();
+        let panels = default();
+        let dragged = default();
+        Self {
+            app,
+            display_object,
+            context,
+            left,
+            right,
+            bottom,
+            drop_indicator,
+            layout,
+            size,
+            panels,
+            dragged,
+            collapse_pressed,
+            free_area,
+            panel_resized,
+        }
+    }
+
+    fn dock_view(&self, edge: Edge) -> &DockView {
+        match edge {
+            Edge::Left => &self.left,
+            Edge::Right => &self.right,
+            Edge::Bottom => &self.bottom,
+        }
+    }
+
+    fn register_panel(&self, panel: PanelId, object: &display::object::Instance) {
+        let previous = self.panels.borrow_mut().insert(panel, object.clone_ref());
+        if let Some(previous) = previous {
+            previous.unset_parent();
+        }
+        self.update();
+    }
+
+    fn set_size(&self, size: Vector2) {
+        self.size.set(size);
+        self.update();
+    }
+
+    /// Change the layout and update the view. Returns the new layout.
+    fn modify_layout(&self, f: impl FnOnce(&mut DockLayout)) -> DockLayout {
+        f(&mut self.layout.borrow_mut());
+        self.update();
+        self.layout.borrow().clone()
+    }
+
+    fn update(&self) {
+        let layout = self.layout.borrow();
+        let areas = layout.areas(self.size.get());
+        for edge in Edge::ALL {
+            let view = self.dock_view(edge);
+            if let Some(area) = areas.dock(edge) {
+                self.display_object.add_child(view);
+                view.update(&self.app, layout.dock(edge), area, &self.context);
+            } else {
+                view.unset_parent();
+            }
+        }
+        let mut resized = vec![];
+        for (panel, object) in &*self.panels.borrow() {
+            let edge = layout.edge_of(*panel);
+            let area = edge.and_then(|edge| areas.dock(edge));
+            let area = area.filter(|_| layout.is_displayed(*panel));
+            if let Some(area) = area {
+                let content = area.content();
+                self.display_object.add_child(object);
+                object.set_xy(content.position);
+                resized.push((*panel, content.size));
+            } else {
+                object.unset_parent();
+            }
+        }
+        drop(layout);
+        self.free_area.emit(areas.free);
+        for resized in resized {
+            self.panel_resized.emit(resized);
+        }
+    }
+
+    fn start_drag(&self, panel: PanelId, pointer: Vector2) {
+        self.dragged.set(Some((panel, pointer)));
+    }
+
+    /// Highlight the drop zone under the pointer while a tab is dragged.
+    fn update_drop_indicator(&self, pointer: Vector2) {
+        let size = self.size.get();
+        let is_dragging = self.dragged.get().map_or(false, |(_, start)| {
+            (pointer - start).norm() >= DRAG_THRESHOLD
+        });
+        let zone = is_dragging.then(|| Edge::at(size, pointer)).flatten();
+        if let Some(edge) = zone {
+            let zone = edge.drop_zone(size);
+            self.drop_indicator.set_xy(zone.position);
+            self.drop_indicator.set_size(zone.size);
+            self.display_object.add_child(&self.drop_indicator);
+        } else {
+            self.drop_indicator.unset_parent();
+        }
+    }
+
+    /// Finish pressing a tab: activate its panel if the pointer did not move, or dock the panel at
+    /// the edge whose drop zone is under the pointer. Returns the changed layout.
+    fn end_drag(&self, pointer: Vector2) -> Option<DockLayout> {
+        let (panel, start) = self.dragged.take()?;
+        self.drop_indicator.unset_parent();
+        if (pointer - start).norm() < DRAG_THRESHOLD {
+            Some(self.modify_layout(|layout| layout.activate_panel(panel)))
+        } else {
+            let edge = Edge::at(self.size.get(), pointer)?;
+            Some(self.modify_layout(|layout| layout.dock_panel(panel, edge)))
+        }
+    }
+}
+
+
+
+// ===============
+// === Docking ===
+// ===============
+
+/// The docks at the edges of the project view. See the module documentation for details.
+#[derive(Debug, Clone, CloneRef, Deref, display::Object)]
+pub struct Docking {
+    widget: Widget<Model, Frp>,
+}
+
+impl Docking {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let frp = Frp::new();
+        let network = frp.network();
+        let model = Rc::new(Model::new(app, network));
+        let input = &frp.private().input;
+        let out = &frp.private().output;
+        let scene = &app.display.default_scene;
+        let mouse = &scene.mouse.frp_deprecated;
+        let mouse_up = scene.on_event::<mouse::Up>();
+
+        frp::extend! { network
+            eval input.set_size ((size) model.set_size(*size));
+            out.layout_changed <+ input.restore_layout.map(f!([model](layout) {
+                model.modify_layout(|current| *current = layout.clone().normalized())
+            }));
+            out.layout_changed <+ input.dock_panel.map(f!([model]((panel, edge)) {
+                model.modify_layout(|layout| layout.dock_panel(*panel, *edge))
+            }));
+            out.layout_changed <+ input.activate_panel.map(f!([model](panel) {
+                model.modify_layout(|layout| layout.activate_panel(*panel))
+            }));
+            toggle_collapsed <- any(&input.toggle_collapsed, &model.collapse_pressed);
+            out.layout_changed <+ toggle_collapsed.map(f!([model](edge) {
+                model.modify_layout(|layout| layout.toggle_collapsed(*edge))
+            }));
+            out.layout_changed <+ input.set_dock_size.map(f!([model]((edge, size)) {
+                model.modify_layout(|layout| layout.set_dock_size(*edge, *size))
+            }));
+            out.free_area <+ model.free_area;
+            out.panel_resized <+ model.panel_resized;
+
+            // === Dragging Tabs ===
+
+            pointer <- mouse.position.map(f!([scene, model](position) {
+                scene.screen_to_object_space(model.display_object(), *position)
+            }));
+            drag_start <- model.context.tab_pressed.map2(&pointer, |panel, pointer| {
+                (*panel, *pointer)
+            });
+            eval drag_start (((panel, pointer)) model.start_drag(*panel, *pointer));
+            eval pointer ((pointer) model.update_drop_indicator(*pointer));
+            released <- pointer.sample(&mouse_up);
+            out.layout_changed <+ released.filter_map(f!((pointer) model.end_drag(*pointer)));
+        }
+
+        let widget = Widget::new(app, frp, model);
+        Self { widget }
+    }
+
+    /// Place the panel's content in the docks. The object is displayed only while the panel is
+    /// docked and active; it should follow its size passed through the `panel_resized` output.
+    pub fn register_panel(&self, panel: PanelId, object: &impl display::Object) {
+        self.widget.model().register_panel(panel, object.display_object());
+    }
+}
+
+impl FrpNetworkProvider for Docking {
+    fn network(&self) -> &frp::Network {
+        self.widget.frp().network()
+    }
+}
+
+impl application::View for Docking {
+    fn label() -> &'static str {
+        "Docking"
+    }
+
+    fn new(app: &Application) -> Self {
+        Self::new(app)
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ensogl::application::test_utils;
+
+    #[test]
+    fn displaying_registered_panels() {
+        let (_app, docking) = test_utils::init_component_for_test::<Docking>();
+        let documentation = display::object::Instance::new();
+        let profiler = display::object::Instance::new();
+        docking.register_panel(PanelId::Documentation, &documentation);
+        docking.register_panel(PanelId::Profiler, &profiler);
+        docking.frp().set_size(Vector2(1000.0, 800.0));
+        assert!(!documentation.has_parent());
+
+        docking.dock_panel(PanelId::Documentation, Edge::Right);
+        docking.dock_panel(PanelId::Profiler, Edge::Right);
+        let layout = docking.layout_changed.value();
+        assert_eq!(layout.right.panels, vec![PanelId::Documentation, PanelId::Profiler]);
+        assert!(!documentation.has_parent());
+        assert!(profiler.has_parent());
+        assert_eq!(docking.free_area.value().size.x, 1000.0 - layout.right.size);
+
+        docking.toggle_collapsed(Edge::Right);
+        assert!(!profiler.has_parent());
+        let mut layout = DockLayout::default();
+        layout.bottom.panels = vec![PanelId::Documentation];
+        docking.restore_layout(layout);
+        assert!(documentation.has_parent());
+        assert_eq!(docking.layout_changed.value().bottom.active, Some(PanelId::Documentation));
+        assert_eq!(docking.panel_resized.value().0, PanelId::Documentation);
+    }
+}
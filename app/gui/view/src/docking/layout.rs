@@ -0,0 +1,405 @@
+//! The serializable description of the docked panels, and the computation of the areas they
+//! occupy.
+
+use crate::prelude::*;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The height of the header of a dock, containing the tabs and the collapse button.
+pub const HEADER_HEIGHT: f32 = 28.0;
+/// The maximum fraction of the width of the docking area taken by a side dock.
+const MAX_SIDE_FRACTION: f32 = 0.4;
+/// The maximum fraction of the height of the docking area taken by the bottom dock.
+const MAX_BOTTOM_FRACTION: f32 = 0.5;
+const DEFAULT_SIDE_SIZE: f32 = 320.0;
+const DEFAULT_BOTTOM_SIZE: f32 = 240.0;
+/// The width of the zone along each edge where a dragged panel is docked when dropped.
+const DROP_ZONE_SIZE: f32 = 64.0;
+
+
+
+// ===============
+// === PanelId ===
+// ===============
+
+/// A panel which may be docked.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[allow(missing_docs)]
+pub enum PanelId {
+    #[default]
+    Documentation,
+    Searcher,
+    WatchList,
+    Profiler,
+}
+
+impl PanelId {
+    /// All panels.
+    pub const ALL: [PanelId; 4] =
+        [PanelId::Documentation, PanelId::Searcher, PanelId::WatchList, PanelId::Profiler];
+
+    /// The title displayed in the panel's tab.
+    pub fn title(self) -> &'static str {
+        match self {
+            PanelId::Documentation => "Documentation",
+            PanelId::Searcher => "Searcher",
+            PanelId::WatchList => "Watch List",
+            PanelId::Profiler => "Profiler",
+        }
+    }
+}
+
+
+
+// ============
+// === Edge ===
+// ============
+
+/// An edge of the docking area where panels may be docked.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[allow(missing_docs)]
+pub enum Edge {
+    #[default]
+    Left,
+    Right,
+    Bottom,
+}
+
+impl Edge {
+    /// All edges.
+    pub const ALL: [Edge; 3] = [Edge::Left, Edge::Right, Edge::Bottom];
+
+    /// The zone of the docking area of the given size where a dragged panel is docked at this edge
+    /// when dropped.
+    pub fn drop_zone(self, size: Vector2) -> Area {
+        match self {
+            Edge::Left => Area::new(default(), Vector2(DROP_ZONE_SIZE, size.y)),
+            Edge::Right =>
+                Area::new(Vector2(size.x - DROP_ZONE_SIZE, 0.0), Vector2(DROP_ZONE_SIZE, size.y)),
+            Edge::Bottom => Area::new(default(), Vector2(size.x, DROP_ZONE_SIZE)),
+        }
+    }
+
+    /// The edge whose drop zone contains the point. The side zones take precedence over the bottom
+    /// one in the corners.
+    pub fn at(size: Vector2, point: Vector2) -> Option<Edge> {
+        Self::ALL.into_iter().find(|edge| edge.drop_zone(size).contains(point))
+    }
+}
+
+
+
+// ============
+// === Dock ===
+// ============
+
+/// The panels docked at one edge, stacked as tabs.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Dock {
+    /// The panels in the order of their tabs.
+    pub panels:    Vec<PanelId>,
+    /// The panel whose content is displayed.
+    pub active:    Option<PanelId>,
+    /// A collapsed dock displays only its header.
+    pub collapsed: bool,
+    /// The width of a side dock, or the height of the bottom dock, when not collapsed.
+    pub size:      f32,
+}
+
+impl Dock {
+    fn new(size: f32) -> Self {
+        Self { panels: default(), active: None, collapsed: false, size }
+    }
+
+    /// The width of a side dock, or the height of the bottom dock, as displayed.
+    pub fn extent(&self) -> f32 {
+        if self.panels.is_empty() {
+            0.0
+        } else if self.collapsed {
+            HEADER_HEIGHT
+        } else {
+            self.size
+        }
+    }
+
+    fn remove(&mut self, panel: PanelId) {
+        self.panels.retain(|p| *p != panel);
+        if self.active.map_or(false, |p| !self.panels.contains(&p)) {
+            self.active = self.panels.first().copied();
+        }
+    }
+}
+
+
+
+// ==================
+// === DockLayout ===
+// ==================
+
+/// The description of the docked panels, which may be serialized to restore them later.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[allow(missing_docs)]
+pub struct DockLayout {
+    pub left:   Dock,
+    pub right:  Dock,
+    pub bottom: Dock,
+}
+
+impl Default for DockLayout {
+    fn default() -> Self {
+        let left = Dock::new(DEFAULT_SIDE_SIZE);
+        let right = Dock::new(DEFAULT_SIDE_SIZE);
+        let bottom = Dock::new(DEFAULT_BOTTOM_SIZE);
+        Self { left, right, bottom }
+    }
+}
+
+impl DockLayout {
+    /// The dock at the edge.
+    pub fn dock(&self, edge: Edge) -> &Dock {
+        match edge {
+            Edge::Left => &self.left,
+            Edge::Right => &self.right,
+            Edge::Bottom => &self.bottom,
+        }
+    }
+
+    fn dock_mut(&mut self, edge: Edge) -> &mut Dock {
+        match edge {
+            Edge::Left => &mut self.left,
+            Edge::Right => &mut self.right,
+            Edge::Bottom => &mut self.bottom,
+        }
+    }
+
+    /// The edge where the panel is docked, if any.
+    pub fn edge_of(&self, panel: PanelId) -> Option<Edge> {
+        Edge::ALL.into_iter().find(|edge| self.dock(*edge).panels.contains(&panel))
+    }
+
+    /// Check whether the panel's content is displayed.
+    pub fn is_displayed(&self, panel: PanelId) -> bool {
+        self.edge_of(panel).map_or(false, |edge| {
+            let dock = self.dock(edge);
+            !dock.collapsed && dock.active == Some(panel)
+        })
+    }
+
+    /// Move the panel to the edge as its last tab, and display it.
+    pub fn dock_panel(&mut self, panel: PanelId, edge: Edge) {
+        self.remove_panel(panel);
+        let dock = self.dock_mut(edge);
+        dock.panels.push(panel);
+        dock.active = Some(panel);
+        dock.collapsed = false;
+    }
+
+    /// Display the panel, expanding its dock.
+    pub fn activate_panel(&mut self, panel: PanelId) {
+        if let Some(edge) = self.edge_of(panel) {
+            let dock = self.dock_mut(edge);
+            dock.active = Some(panel);
+            dock.collapsed = false;
+        }
+    }
+
+    /// Remove the panel from its dock.
+    pub fn remove_panel(&mut self, panel: PanelId) {
+        for edge in Edge::ALL {
+            self.dock_mut(edge).remove(panel);
+        }
+    }
+
+    /// Collapse or expand the dock.
+    pub fn toggle_collapsed(&mut self, edge: Edge) {
+        let dock = self.dock_mut(edge);
+        dock.collapsed = !dock.collapsed;
+    }
+
+    /// Set the width of a side dock, or the height of the bottom dock.
+    pub fn set_dock_size(&mut self, edge: Edge, size: f32) {
+        self.dock_mut(edge).size = size.max(HEADER_HEIGHT);
+    }
+
+    /// Fix the inconsistencies of a layout restored from an external source: a panel docked more
+    /// than once is kept only in its first dock, and the active panels are made valid.
+    pub fn normalized(mut self) -> Self {
+        let mut seen = HashSet::new();
+        for edge in Edge::ALL {
+            let dock = self.dock_mut(edge);
+            dock.panels.retain(|panel| seen.insert(*panel));
+            if dock.active.map_or(true, |panel| !dock.panels.contains(&panel)) {
+                dock.active = dock.panels.first().copied();
+            }
+            dock.size = dock.size.max(HEADER_HEIGHT);
+        }
+        self
+    }
+
+    /// The areas occupied by the docks and the area left for the rest of the view, within the
+    /// docking area of the given size. The bottom dock spans the whole width, and the side docks
+    /// are placed above it.
+    pub fn areas(&self, size: Vector2) -> Areas {
+        let bottom_height = self.bottom.extent().min(size.y * MAX_BOTTOM_FRACTION);
+        let left_width = self.left.extent().min(size.x * MAX_SIDE_FRACTION);
+        let right_width = self.right.extent().min(size.x * MAX_SIDE_FRACTION);
+        let side_height = size.y - bottom_height;
+        let area = |x, y, width, height| {
+            (width > 0.0 && height > 0.0).then(|| Area::new(Vector2(x, y), Vector2(width, height)))
+        };
+        let free_width = size.x - left_width - right_width;
+        Areas {
+            left:   area(0.0, bottom_height, left_width, side_height),
+            right:  area(size.x - right_width, bottom_height, right_width, side_height),
+            bottom: area(0.0, 0.0, size.x, bottom_height),
+            free:   Area::new(Vector2(left_width, bottom_height), Vector2(free_width, side_height)),
+        }
+    }
+}
+
+
+
+// ============
+// === Area ===
+// ============
+
+/// A rectangular area, relative to the bottom-left corner of the docking area.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct Area {
+    pub position: Vector2,
+    pub size:     Vector2,
+}
+
+impl Area {
+    /// Constructor.
+    pub fn new(position: Vector2, size: Vector2) -> Self {
+        Self { position, size }
+    }
+
+    /// The area of the dock's header, at its top.
+    pub fn header(&self) -> Area {
+        let height = HEADER_HEIGHT.min(self.size.y);
+        let position = self.position + Vector2(0.0, self.size.y - height);
+        Area::new(position, Vector2(self.size.x, height))
+    }
+
+    /// The area of the dock's content, below its header.
+    pub fn content(&self) -> Area {
+        let height = (self.size.y - HEADER_HEIGHT).max(0.0);
+        Area::new(self.position, Vector2(self.size.x, height))
+    }
+
+    /// Check whether the point lies within the area.
+    pub fn contains(&self, point: Vector2) -> bool {
+        let end = self.position + self.size;
+        (self.position.x..end.x).contains(&point.x) && (self.position.y..end.y).contains(&point.y)
+    }
+}
+
+/// The areas of the docks, [`None`] for the docks without panels.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct Areas {
+    pub left:   Option<Area>,
+    pub right:  Option<Area>,
+    pub bottom: Option<Area>,
+    /// The area not occupied by any dock.
+    pub free:   Area,
+}
+
+impl Areas {
+    /// The area of the dock at the edge.
+    pub fn dock(&self, edge: Edge) -> Option<Area> {
+        match edge {
+            Edge::Left => self.left,
+            Edge::Right => self.right,
+            Edge::Bottom => self.bottom,
+        }
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn docking_and_activating_panels() {
+        let mut layout = DockLayout::default();
+        assert_eq!(layout.edge_of(PanelId::Documentation), None);
+        layout.dock_panel(PanelId::Documentation, Edge::Right);
+        layout.toggle_collapsed(Edge::Right);
+        assert!(!layout.is_displayed(PanelId::Documentation));
+
+        layout.activate_panel(PanelId::Documentation);
+        assert!(layout.is_displayed(PanelId::Documentation));
+        layout.dock_panel(PanelId::WatchList, Edge::Right);
+        assert_eq!(layout.right.panels, vec![PanelId::Documentation, PanelId::WatchList]);
+        assert!(layout.is_displayed(PanelId::WatchList));
+        assert!(!layout.is_displayed(PanelId::Documentation));
+
+        layout.dock_panel(PanelId::WatchList, Edge::Bottom);
+        assert_eq!(layout.right.panels, vec![PanelId::Documentation]);
+        assert_eq!(layout.right.active, Some(PanelId::Documentation));
+        assert_eq!(layout.bottom.panels, vec![PanelId::WatchList]);
+
+        layout.toggle_collapsed(Edge::Bottom);
+        assert!(!layout.is_displayed(PanelId::WatchList));
+        layout.remove_panel(PanelId::WatchList);
+        assert_eq!(layout.edge_of(PanelId::WatchList), None);
+        assert_eq!(layout.bottom.active, None);
+    }
+
+    #[test]
+    fn normalizing_restored_layout() {
+        let mut layout = DockLayout::default();
+        layout.right.panels = vec![PanelId::Documentation];
+        layout.left.panels = vec![PanelId::Profiler, PanelId::Documentation];
+        layout.left.active = Some(PanelId::Searcher);
+        let layout = layout.normalized();
+        assert_eq!(layout.left.panels, vec![PanelId::Profiler, PanelId::Documentation]);
+        assert_eq!(layout.left.active, Some(PanelId::Profiler));
+        assert!(layout.right.panels.is_empty());
+        assert_eq!(layout.right.active, None);
+    }
+
+    #[test]
+    fn dock_areas() {
+        let mut layout = DockLayout::default();
+        layout.dock_panel(PanelId::Documentation, Edge::Right);
+        layout.toggle_collapsed(Edge::Right);
+        layout.dock_panel(PanelId::Profiler, Edge::Bottom);
+        layout.set_dock_size(Edge::Bottom, 200.0);
+        let areas = layout.areas(Vector2(1000.0, 800.0));
+        assert_eq!(areas.bottom, Some(Area::new(Vector2(0.0, 0.0), Vector2(1000.0, 200.0))));
+        let right_position = Vector2(1000.0 - HEADER_HEIGHT, 200.0);
+        let right = Area::new(right_position, Vector2(HEADER_HEIGHT, 600.0));
+        assert_eq!(areas.right, Some(right));
+        assert_eq!(areas.left, None);
+        let free = Area::new(Vector2(0.0, 200.0), Vector2(1000.0 - HEADER_HEIGHT, 600.0));
+        assert_eq!(areas.free, free);
+        assert_eq!(right.header().position, Vector2(1000.0 - HEADER_HEIGHT, 800.0 - HEADER_HEIGHT));
+    }
+
+    #[test]
+    fn drop_zones() {
+        let size = Vector2(1000.0, 800.0);
+        assert_eq!(Edge::at(size, Vector2(10.0, 400.0)), Some(Edge::Left));
+        assert_eq!(Edge::at(size, Vector2(990.0, 10.0)), Some(Edge::Right));
+        assert_eq!(Edge::at(size, Vector2(500.0, 10.0)), Some(Edge::Bottom));
+        assert_eq!(Edge::at(size, Vector2(500.0, 400.0)), None);
+    }
+}
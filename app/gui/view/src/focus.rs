@@ -0,0 +1,158 @@
+//! The keyboard focus traversal between the top-level components of the project view, and the
+//! focus ring highlighting the focused component.
+
+use crate::prelude::*;
+
+use enso_frp as frp;
+use ensogl::application::Application;
+use ensogl::data::color;
+use ensogl::display;
+use ensogl::display::shape::Rectangle;
+use ensogl::display::shape::StyleWatchFrp;
+use ensogl_hardcoded_theme::application::focus_ring as theme;
+
+
+
+// =================
+// === Component ===
+// =================
+
+/// A top-level component of the project view which may take the keyboard focus.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[allow(missing_docs)]
+pub enum Component {
+    #[default]
+    Graph,
+    Breadcrumbs,
+    ExecutionSelector,
+    Searcher,
+}
+
+impl Component {
+    /// All components, in the order of the focus traversal.
+    pub const ALL: [Component; 4] = [
+        Component::Graph,
+        Component::Breadcrumbs,
+        Component::ExecutionSelector,
+        Component::Searcher,
+    ];
+
+    /// The component focused after this one, skipping the components which are not `available`
+    /// (e.g. the closed searcher). Returns [`None`] if no component is available.
+    pub fn next(self, available: impl Fn(Component) -> bool) -> Option<Component> {
+        Self::step(self, available, 1)
+    }
+
+    /// The component focused before this one. See [`Self::next`].
+    pub fn previous(self, available: impl Fn(Component) -> bool) -> Option<Component> {
+        Self::step(self, available, Self::ALL.len() - 1)
+    }
+
+    fn step(self, available: impl Fn(Component) -> bool, offset: usize) -> Option<Component> {
+        let count = Self::ALL.len();
+        let index = Self::ALL.iter().position(|c| *c == self).unwrap_or_default();
+        let candidates = (1..=count).map(|step| Self::ALL[(index + step * offset) % count]);
+        candidates.find(|c| available(*c))
+    }
+}
+
+
+
+// =================
+// === FocusRing ===
+// =================
+
+/// An outline drawn around the focused component.
+///
+/// The ring is attached as a child of the component's display object and follows its size. It is
+/// not displayed around objects without size, like the graph editor, whose focus is visible through
+/// their content. It should not be attached to objects using the auto layout, as it would be laid
+/// out with their other children.
+#[derive(Clone, CloneRef, Debug)]
+pub struct FocusRing {
+    shape:      Rectangle,
+    padding:    Rc<Cell<f32>>,
+    /// The network following the size of the object the ring is attached to.
+    attachment: Rc<RefCell<Option<frp::Network>>>,
+    _style:     StyleWatchFrp,
+    _network:   frp::Network,
+}
+
+impl FocusRing {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let shape = Rectangle();
+        shape.set_color(color::Rgba::transparent()).set_pointer_events(false);
+        let padding: Rc<Cell<f32>> = default();
+        let attachment = default();
+        let style = StyleWatchFrp::new(&app.display.default_scene.style_sheet);
+        let network = frp::Network::new("FocusRing");
+        frp::extend! { network
+            let color = style.get_color(theme::color);
+            let width = style.get_number(theme::width);
+            let corner_radius = style.get_number(theme::corner_radius);
+            let padding_style = style.get_number(theme::padding);
+            eval color ((color) shape.set_border_color(*color););
+            eval width ((width) shape.set_border_and_inset(*width););
+            eval corner_radius ((radius) shape.set_corner_radius(*radius););
+            eval padding_style ((padding) padding.set(*padding));
+        }
+        shape.set_border_color(color.value());
+        shape.set_border_and_inset(width.value());
+        shape.set_corner_radius(corner_radius.value());
+        padding.set(padding_style.value());
+        Self { shape, padding, attachment, _style: style, _network: network }
+    }
+
+    /// Display the ring around the `target`, detaching it from the previous one.
+    pub fn attach(&self, target: &impl display::Object) {
+        let target = target.display_object();
+        let shape = &self.shape;
+        let padding = &self.padding;
+        let network = frp::Network::new("FocusRing::attachment");
+        frp::extend! { network
+            eval target.on_resized ((size) Self::update_size(shape, padding.get(), *size));
+        }
+        *self.attachment.borrow_mut() = Some(network);
+        target.add_child(shape);
+        Self::update_size(shape, padding.get(), target.computed_size());
+    }
+
+    /// Remove the ring from the object it is attached to.
+    pub fn detach(&self) {
+        self.attachment.take();
+        self.shape.unset_parent();
+    }
+
+    fn update_size(shape: &Rectangle, padding: f32, target_size: Vector2) {
+        let has_size = target_size.x > 0.0 && target_size.y > 0.0;
+        let padding = if has_size { padding } else { 0.0 };
+        let size = if has_size { target_size + Vector2(padding, padding) * 2.0 } else { default() };
+        shape.set_size(size);
+        shape.set_xy(Vector2(-padding, -padding));
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn focus_traversal() {
+        let all = |_| true;
+        let without_searcher = |c| c != Component::Searcher;
+        assert_eq!(Component::Graph.next(all), Some(Component::Breadcrumbs));
+        assert_eq!(Component::Searcher.next(all), Some(Component::Graph));
+        assert_eq!(Component::Graph.previous(all), Some(Component::Searcher));
+        assert_eq!(Component::ExecutionSelector.next(without_searcher), Some(Component::Graph));
+        assert_eq!(Component::Graph.previous(without_searcher), Some(Component::ExecutionSelector));
+        assert_eq!(Component::Graph.next(|c| c == Component::Graph), Some(Component::Graph));
+        assert_eq!(Component::Graph.next(|_| false), None);
+    }
+}
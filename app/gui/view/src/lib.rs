@@ -33,10 +33,14 @@
 
 #[allow(clippy::option_map_unit_fn)]
 pub mod code_editor;
+pub mod docking;
+pub mod focus;
 pub mod notification;
+pub mod performance_hud;
 pub mod project;
 pub mod project_list;
 pub mod root;
+pub mod search_everywhere;
 pub mod searcher;
 
 pub use ide_view_component_browser as component_browser;
@@ -0,0 +1,331 @@
+//! A debug overlay displaying the rendering performance: the frame rate, the percentiles of the
+//! recent frame times, the number of draw calls, the size of the displayed graph, and the rate of
+//! the events logged by the [`profiler`] instrumentation (including the events of the profiled FRP
+//! nodes).
+//!
+//! The HUD is drawn in its own layer above all other layers. The statistics are sampled only while
+//! the HUD is visible.
+
+use crate::prelude::*;
+
+use enso_frp as frp;
+use ensogl::animation;
+use ensogl::application::frp::API;
+use ensogl::application::Application;
+use ensogl::debug::stats::Stats;
+use ensogl::display;
+use ensogl::display::scene::Layer;
+use ensogl::display::shape::Rectangle;
+use ensogl::display::shape::StyleWatchFrp;
+use ensogl::gui::Widget;
+use ensogl_hardcoded_theme::application::performance_hud as theme;
+use ensogl_text as text;
+use std::collections::VecDeque;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The number of recent frames whose times are used to compute the percentiles.
+const FRAME_TIME_SAMPLES: usize = 240;
+/// The time between the updates of the displayed values.
+const REFRESH_INTERVAL_MS: f64 = 500.0;
+const WIDTH: f32 = 320.0;
+const LINE_COUNT: usize = 5;
+const LINE_HEIGHT: f32 = 16.0;
+const TEXT_SIZE: f32 = 12.0;
+const PADDING: f32 = 8.0;
+/// The distance between the HUD and the top-right corner of the screen.
+const MARGIN: f32 = 8.0;
+
+
+
+// ==================
+// === FrameTimes ===
+// ==================
+
+/// The times of the most recent frames, in milliseconds.
+#[derive(Clone, Debug, Default)]
+pub struct FrameTimes {
+    samples: VecDeque<f64>,
+}
+
+impl FrameTimes {
+    /// Add the time of a frame, forgetting the oldest one if [`FRAME_TIME_SAMPLES`] are stored.
+    pub fn push(&mut self, time: f64) {
+        if self.samples.len() == FRAME_TIME_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(time);
+    }
+
+    /// The frame time not exceeded by the given fraction of the recent frames, e.g. the 95th
+    /// percentile for `0.95`. Returns [`None`] if no frame was sampled.
+    pub fn percentile(&self, fraction: f64) -> Option<f64> {
+        let sorted = self.samples.iter().copied().sorted_by(|a, b| a.total_cmp(b)).collect_vec();
+        let last_index = sorted.len().checked_sub(1)?;
+        let index = (last_index as f64 * fraction.clamp(0.0, 1.0)).round() as usize;
+        sorted.get(index).copied()
+    }
+}
+
+
+
+// ===================
+// === GraphCounts ===
+// ===================
+
+/// The size of the displayed graph.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub struct GraphCounts {
+    pub nodes: usize,
+    pub edges: usize,
+}
+
+
+
+// ===========
+// === FRP ===
+// ===========
+
+ensogl::define_endpoints_2! {
+    Input {
+        show(),
+        hide(),
+        toggle(),
+        set_graph_counts(GraphCounts),
+    }
+    Output {
+        is_visible(bool),
+        /// Emitted right before each update of the displayed values. The graph counts should be
+        /// set in reaction, so the update includes them.
+        refresh_requested(),
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+/// The internal model of the [`PerformanceHud`].
+#[derive(Debug, Clone, CloneRef, display::Object)]
+pub struct Model {
+    display_object: display::object::Instance,
+    /// The visible part of the HUD, attached only when the HUD is shown.
+    content:        display::object::Instance,
+    background:     Rectangle,
+    text:           text::Text,
+    _layer:         Layer,
+    _text_layer:    Layer,
+    stats:          Stats,
+    frame_times:    Rc<RefCell<FrameTimes>>,
+    graph_counts:   Rc<Cell<GraphCounts>>,
+    /// The time of the last update of the displayed values and the number of profiler events
+    /// logged until then.
+    last_refresh:   Rc<Cell<Option<(f64, usize)>>>,
+}
+
+impl Model {
+    fn new(app: &Application) -> Self {
+        let scene = &app.display.default_scene;
+        let display_object = display::object::Instance::new_named("PerformanceHud");
+        let content = display::object::Instance::new();
+        let background = Rectangle();
+        let text = app.new_view::<text::Text>();
+        let camera = scene.layers.panel.camera();
+        let layer = scene.layers.create_sublayer_with_camera("performance_hud", &camera);
+        let text_layer = layer.create_sublayer("performance_hud_text");
+        content.add_child(&background);
+        content.add_child(&text);
+        layer.add(&content);
+        text_layer.add(&text);
+        let height = LINE_COUNT as f32 * LINE_HEIGHT + PADDING * 2.0;
+        background.set_size(Vector2(WIDTH, height));
+        background.set_y(-height);
+        text.set_property_default(text::Size(TEXT_SIZE));
+        text.set_xy(Vector2(PADDING, -PADDING));
+        let stats = scene.stats.clone_ref();
+        Self {
+            display_object,
+            content,
+            background,
+            text,
+            _layer: layer,
+            _text_layer: text_layer,
+            stats,
+            frame_times: default(),
+            graph_counts: default(),
+            last_refresh: default(),
+        }
+    }
+
+    fn set_visibility(&self, visible: bool) {
+        if visible {
+            self.display_object.add_child(&self.content);
+        } else {
+            self.content.unset_parent();
+            self.last_refresh.set(None);
+        }
+    }
+
+    fn update_position(&self, shape: &display::scene::Shape) {
+        let x = shape.width / 2.0 - MARGIN - WIDTH;
+        let y = shape.height / 2.0 - MARGIN;
+        self.content.set_xy(Vector2(x, y));
+    }
+
+    /// Record the time of the previous frame. Returns `true` if the displayed values should be
+    /// updated.
+    fn sample_frame(&self, now_ms: f64) -> bool {
+        let frame_time = self.stats.borrow().stats_data.frame_time();
+        if frame_time > 0.0 {
+            self.frame_times.borrow_mut().push(frame_time);
+        }
+        self.last_refresh.get().map_or(true, |(last, _)| now_ms - last >= REFRESH_INTERVAL_MS)
+    }
+
+    fn refresh(&self, now_ms: f64) {
+        let event_count = profiler::internal::EventLog.event_count();
+        let previous = self.last_refresh.replace(Some((now_ms, event_count)));
+        let event_rate = previous.map(|(last, last_count)| {
+            let elapsed_s = (now_ms - last) / 1000.0;
+            (event_count - last_count) as f64 / elapsed_s.max(f64::EPSILON)
+        });
+        let stats = self.stats.borrow();
+        let data = &stats.stats_data;
+        let frame_times = self.frame_times.borrow();
+        let percentile = |fraction| match frame_times.percentile(fraction) {
+            Some(time) => format!("{time:.1} ms"),
+            None => "-".to_string(),
+        };
+        let counts = self.graph_counts.get();
+        let event_rate = event_rate.map_or("-".to_string(), |rate| format!("{rate:.0}/s"));
+        let lines = [
+            format!("FPS: {:.1}", data.fps()),
+            format!(
+                "Frame time: p50 {}, p95 {}, p99 {}",
+                percentile(0.5),
+                percentile(0.95),
+                percentile(0.99)
+            ),
+            format!("Draw calls: {}", data.prev_draw_call_count()),
+            format!("Nodes: {}, edges: {}", counts.nodes, counts.edges),
+            format!("Profiled events: {event_rate}"),
+        ];
+        self.text.set_content(lines.join("\n"));
+    }
+}
+
+
+
+// ======================
+// === PerformanceHud ===
+// ======================
+
+/// The rendering performance overlay. See the module documentation for details.
+#[derive(Debug, Clone, CloneRef, Deref, display::Object)]
+pub struct PerformanceHud {
+    widget: Widget<Model, Frp>,
+}
+
+impl PerformanceHud {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let model = Rc::new(Model::new(app));
+        let frp = Frp::new();
+        let network = frp.network();
+        let input = &frp.private().input;
+        let out = &frp.private().output;
+        let scene = &app.display.default_scene;
+        let scene_shape = scene.shape();
+        let on_frame_start = animation::on_frame_start();
+        let style = StyleWatchFrp::new(&scene.style_sheet);
+        let background_color = style.get_color(theme::background);
+        let text_color = style.get_color(theme::text);
+
+        frp::extend! { network
+            init <- source_();
+
+            // === Visibility ===
+
+            show <- any(&input.show, &input.toggle.gate_not(&out.is_visible));
+            hide <- any(&input.hide, &input.toggle.gate(&out.is_visible));
+            out.is_visible <+ bool(&hide, &show).on_change();
+            eval out.is_visible ((visible) model.set_visibility(*visible));
+            eval scene_shape ((shape) model.update_position(shape));
+
+
+            // === Sampling ===
+
+            frame_start <- on_frame_start.gate(&out.is_visible);
+            now_ms <- frame_start.map(|time| time.unchecked_raw() as f64);
+            refresh <- now_ms.filter(f!((now) model.sample_frame(*now)));
+            out.refresh_requested <+ refresh.constant(());
+            eval input.set_graph_counts ((counts) model.graph_counts.set(*counts));
+            eval refresh ((now) model.refresh(*now));
+
+
+            // === Style ===
+
+            background_color <- all(&background_color, &init)._0();
+            eval background_color ((color) model.background.set_color(*color););
+            text_color <- all(&text_color, &init)._0();
+            model.text.set_property_default <+ text_color.ref_into_some();
+        }
+        init.emit(());
+        model.update_position(&scene_shape.value());
+
+        let widget = Widget::new(app, frp, model);
+        Self { widget }
+    }
+}
+
+impl FrpNetworkProvider for PerformanceHud {
+    fn network(&self) -> &frp::Network {
+        self.widget.frp().network()
+    }
+}
+
+impl ensogl::application::View for PerformanceHud {
+    fn label() -> &'static str {
+        "PerformanceHud"
+    }
+
+    fn new(app: &Application) -> Self {
+        Self::new(app)
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_time_percentiles() {
+        let mut frame_times = FrameTimes::default();
+        assert_eq!(frame_times.percentile(0.5), None);
+        for time in (1..=100).rev() {
+            frame_times.push(time as f64);
+        }
+        assert_eq!(frame_times.percentile(0.0), Some(1.0));
+        assert_eq!(frame_times.percentile(0.5), Some(51.0));
+        assert_eq!(frame_times.percentile(0.95), Some(95.0));
+        assert_eq!(frame_times.percentile(1.0), Some(100.0));
+
+        for _ in 0..FRAME_TIME_SAMPLES {
+            frame_times.push(10.0);
+        }
+        assert_eq!(frame_times.percentile(1.0), Some(10.0));
+    }
+}
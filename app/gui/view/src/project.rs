@@ -7,11 +7,16 @@ use ensogl::system::web::traits::*;
 use crate::code_editor;
 use crate::component_browser;
 use crate::component_browser::component_list_panel;
+use crate::docking::Docking;
+use crate::focus;
 use crate::graph_editor::component::node::Expression;
 use crate::graph_editor::component::visualization;
 use crate::graph_editor::GraphEditor;
 use crate::graph_editor::NodeId;
+use crate::performance_hud::GraphCounts;
+use crate::performance_hud::PerformanceHud;
 use crate::project_list::ProjectList;
+use crate::search_everywhere::SearchEverywhere;
 
 use enso_config::ARGS;
 use enso_frp as frp;
@@ -22,8 +27,10 @@ use ensogl::display;
 use ensogl::display::Scene;
 use ensogl::system::web;
 use ensogl::Animation;
+use ensogl_component::command_palette::CommandPalette;
 use ensogl_component::text;
 use ensogl_component::text::selection::Selection;
+use ensogl_component::toast::Toasts;
 use ensogl_hardcoded_theme::Theme;
 use ide_view_graph_editor::NodeSource;
 use ide_view_project_view_top_bar::ProjectViewTopBar;
@@ -133,6 +140,8 @@ ensogl::define_endpoints! {
         enable_debug_mode(),
         /// Disable Debug Mode of Graph Editor.
         disable_debug_mode(),
+        /// Show or hide the rendering performance HUD. Available in the Debug Mode only.
+        toggle_performance_hud(),
         /// A set of value updates has been processed and rendered.
         values_updated(),
         /// Interrupt the running program.
@@ -155,6 +164,11 @@ ensogl::define_endpoints! {
         start_language_server_profiling(),
         /// Stop the language server profiling
         stop_language_server_profiling(),
+        /// Move the keyboard focus to the next top-level component. See [`focus::Component::ALL`]
+        /// for the order of the traversal.
+        focus_next(),
+        /// Move the keyboard focus to the previous top-level component.
+        focus_previous(),
     }
 
     Output {
@@ -178,12 +192,15 @@ ensogl::define_endpoints! {
         code_editor_shown              (bool),
         style                          (Theme),
         fullscreen_visualization_shown (bool),
+        collapse_preview_shown         (bool),
         drop_files_enabled             (bool),
         debug_mode                     (bool),
         /// The name of the command currently being handled due to shortcut being pressed.
         current_shortcut               (Option<ImString>),
         /// Request the controller to dump the suggestion database in JSON to the console.
         request_dump_suggestion_database(),
+        /// The top-level component having the keyboard focus.
+        focused_component              (focus::Component),
     }
 }
 
@@ -195,14 +212,20 @@ ensogl::define_endpoints! {
 
 #[derive(Clone, CloneRef, Debug, display::Object)]
 struct Model {
-    display_object:   display::object::Instance,
-    top_bar:          ProjectViewTopBar,
-    graph_editor:     Rc<GraphEditor>,
-    searcher:         component_browser::View,
-    code_editor:      code_editor::View,
-    fullscreen_vis:   Rc<RefCell<Option<visualization::fullscreen::Panel>>>,
-    project_list:     Rc<ProjectList>,
-    debug_mode_popup: Rc<crate::notification::View>,
+    display_object:    display::object::Instance,
+    top_bar:           ProjectViewTopBar,
+    graph_editor:      Rc<GraphEditor>,
+    searcher:          component_browser::View,
+    code_editor:       code_editor::View,
+    fullscreen_vis:    Rc<RefCell<Option<visualization::fullscreen::Panel>>>,
+    project_list:      Rc<ProjectList>,
+    debug_mode_popup:  Rc<crate::notification::View>,
+    focus_ring:        focus::FocusRing,
+    command_palette:   CommandPalette,
+    toasts:            Toasts,
+    docking:           Docking,
+    search_everywhere: SearchEverywhere,
+    performance_hud:   PerformanceHud,
 }
 
 impl Model {
@@ -215,11 +238,22 @@ impl Model {
         let debug_mode_popup = Rc::new(crate::notification::View::new(app));
         let project_view_top_bar = ProjectViewTopBar::new(app);
         let project_list = Rc::new(ProjectList::new(app));
+        let focus_ring = focus::FocusRing::new(app);
+        let command_palette = app.new_view::<CommandPalette>();
+        let toasts = app.new_view::<Toasts>();
+        let docking = app.new_view::<Docking>();
+        let search_everywhere = app.new_view::<SearchEverywhere>();
+        let performance_hud = app.new_view::<PerformanceHud>();
 
         display_object.add_child(&graph_editor);
         display_object.add_child(&code_editor);
         display_object.add_child(&searcher);
         display_object.add_child(&project_view_top_bar);
+        display_object.add_child(&command_palette);
+        display_object.add_child(&toasts);
+        display_object.add_child(&docking);
+        display_object.add_child(&search_everywhere);
+        display_object.add_child(&performance_hud);
         display_object.remove_child(&searcher);
 
         let graph_editor = Rc::new(graph_editor);
@@ -232,6 +266,12 @@ impl Model {
             fullscreen_vis,
             project_list,
             debug_mode_popup,
+            focus_ring,
+            command_palette,
+            toasts,
+            docking,
+            search_everywhere,
+            performance_hud,
         }
     }
 
@@ -293,6 +333,13 @@ impl Model {
         self.top_bar.set_xy(top_left + project_view_top_bar_origin);
     }
 
+    /// Make the docking area cover the whole scene.
+    fn position_docking(&self, scene_shape: &display::scene::Shape) {
+        let size = Vector2(scene_shape.width, scene_shape.height);
+        self.docking.set_xy(-size / 2.0);
+        self.docking.frp().set_size(size);
+    }
+
     fn show_project_list(&self) {
         self.display_object.add_child(&*self.project_list);
     }
@@ -308,6 +355,35 @@ impl Model {
     fn hide_graph_editor(&self) {
         self.display_object.remove_child(&*self.graph_editor);
     }
+
+    /// Check if the component is displayed, so it may be focused.
+    fn is_focusable(&self, component: focus::Component) -> bool {
+        match component {
+            focus::Component::Graph => self.graph_editor.has_parent(),
+            focus::Component::Breadcrumbs | focus::Component::ExecutionSelector =>
+                self.top_bar.has_parent(),
+            focus::Component::Searcher => self.searcher.has_parent(),
+        }
+    }
+
+    /// Give the keyboard focus to the component and move the focus ring around it. The graph
+    /// editor has no ring, as its focus is visible through the selected nodes.
+    fn focus_component(&self, component: focus::Component) {
+        let top_bar = &self.top_bar;
+        let selector = &top_bar.project_name_with_environment_selector.selector;
+        let target = match component {
+            focus::Component::Graph => self.graph_editor.display_object(),
+            focus::Component::Breadcrumbs => top_bar.breadcrumbs.display_object(),
+            focus::Component::ExecutionSelector => selector.display_object(),
+            focus::Component::Searcher => self.searcher.display_object(),
+        };
+        target.focus();
+        if component == focus::Component::Graph {
+            self.focus_ring.detach();
+        } else {
+            self.focus_ring.attach(target);
+        }
+    }
 }
 
 
@@ -363,6 +439,10 @@ impl View {
             .init_debug_mode_frp()
             .init_shortcut_observer(app)
             .init_execution_environment_selector_frp()
+            .init_focus_traversal_frp()
+            .init_docking_frp(scene)
+            .init_search_everywhere_frp()
+            .init_performance_hud_frp()
     }
 
     fn init_execution_environment_selector_frp(self) -> Self {
@@ -422,6 +502,7 @@ impl View {
 
             frp.source.fullscreen_visualization_shown <+
                 graph.output.visualization_fullscreen.is_some();
+            frp.source.collapse_preview_shown <+ graph.output.collapse_preview_visible;
         }
         self
     }
@@ -652,6 +733,47 @@ impl View {
         self
     }
 
+    fn init_docking_frp(self, scene: &Scene) -> Self {
+        let frp = &self.frp;
+        let network = &frp.network;
+        let model = &self.model;
+        let scene_shape = scene.shape();
+        frp::extend! { network
+            eval scene_shape ((shape) model.position_docking(shape));
+        }
+        model.position_docking(&scene_shape.value());
+        self
+    }
+
+    fn init_search_everywhere_frp(self) -> Self {
+        let network = &self.frp.network;
+        let graph = &self.model.graph_editor;
+        let search = &self.model.search_everywhere;
+        frp::extend! { network
+            graph.search_nodes <+ search.query;
+            graph.clear_node_search <+ search.is_visible.on_false();
+            search.set_local_results <+ graph.nodes_found;
+            graph.pan_camera_to_node <+ search.local_node_chosen;
+        }
+        self
+    }
+
+    fn init_performance_hud_frp(self) -> Self {
+        let frp = &self.frp;
+        let network = &frp.network;
+        let graph = &self.model.graph_editor;
+        let hud = &self.model.performance_hud;
+        frp::extend! { network
+            hud.toggle <+ frp.toggle_performance_hud;
+            hud.hide <+ frp.disable_debug_mode;
+            hud.set_graph_counts <+ hud.refresh_requested.map(f_!([graph] GraphCounts {
+                nodes: graph.model.node_count(),
+                edges: graph.model.edge_count(),
+            }));
+        }
+        self
+    }
+
     fn init_style_toggle_frp(self) -> Self {
         let frp = &self.frp;
         let network = &frp.network;
@@ -740,6 +862,32 @@ impl View {
         self
     }
 
+    fn init_focus_traversal_frp(self) -> Self {
+        let frp = &self.frp;
+        let network = &frp.network;
+        let model = &self.model;
+        let searcher = &model.searcher;
+        frp::extend! { network
+            let focused = frp.focused_component.clone_ref();
+            focus_next <- frp.focus_next.map2(&focused,
+                f!([model](_, current) current.next(|c| model.is_focusable(c)))
+            );
+            focus_previous <- frp.focus_previous.map2(&focused,
+                f!([model](_, current) current.previous(|c| model.is_focusable(c)))
+            );
+            searcher_shown <- searcher.is_visible.on_true();
+            searcher_hidden <- searcher.is_visible.on_false();
+            focus_searcher <- searcher_shown.constant(Some(focus::Component::Searcher));
+            searcher_was_focused <- focused.map(|c| *c == focus::Component::Searcher);
+            focus_graph <- searcher_hidden.gate(&searcher_was_focused);
+            focus_graph <- focus_graph.constant(Some(focus::Component::Graph));
+            new_focus <- any4(&focus_next, &focus_previous, &focus_searcher, &focus_graph);
+            frp.source.focused_component <+ new_focus.unwrap();
+            eval frp.focused_component ((component) model.focus_component(*component));
+        }
+        self
+    }
+
     fn init_shortcut_observer(self, app: &Application) -> Self {
         let frp = &self.frp;
         frp::extend! { network
@@ -773,6 +921,21 @@ impl View {
     pub fn project_list(&self) -> &ProjectList {
         &self.model.project_list
     }
+
+    /// Toast notifications displayed in the bottom-right corner.
+    pub fn toasts(&self) -> &Toasts {
+        &self.model.toasts
+    }
+
+    /// The docks with panels at the edges of the project view.
+    pub fn docking(&self) -> &Docking {
+        &self.model.docking
+    }
+
+    /// The panel searching the nodes in the whole project.
+    pub fn search_everywhere(&self) -> &SearchEverywhere {
+        &self.model.search_everywhere
+    }
 }
 
 impl FrpNetworkProvider for View {
@@ -811,13 +974,21 @@ impl application::View for View {
             // is ready.
             (Press, "", "cmd alt y", "execution_context_reload_and_restart"),
             (Press, "!is_searcher_opened", "cmd tab", "start_node_creation_with_ai_searcher"),
-            (Press, "!is_searcher_opened", "enter", "start_node_creation_with_component_browser"),
+            (
+                Press,
+                "!is_searcher_opened & !collapse_preview_shown",
+                "enter",
+                "start_node_creation_with_component_browser",
+            ),
             (Press, "is_searcher_opened", "enter", "accept_searcher_input"),
             (Press, "debug_mode", "ctrl shift enter", "debug_push_breadcrumb"),
             (Press, "debug_mode", "ctrl shift b", "debug_pop_breadcrumb"),
             (Press, "debug_mode", "ctrl shift u", "dump_suggestion_database"),
+            (Press, "debug_mode", "ctrl shift h", "toggle_performance_hud"),
             (Press, "", "cmd alt ,", "start_language_server_profiling"),
             (Press, "", "cmd alt .", "stop_language_server_profiling"),
+            (Press, "!is_searcher_opened", "tab", "focus_next"),
+            (Press, "!is_searcher_opened", "shift tab", "focus_previous"),
         ]
         .iter()
         .map(|(a, b, c, d)| Self::self_shortcut_when(*a, *c, *d, *b))
@@ -0,0 +1,366 @@
+//! The "search everywhere" panel, listing the nodes whose expressions or comments match a query,
+//! both in the displayed graph and in the other methods of the project.
+//!
+//! The matches in the displayed graph are found by the Graph Editor (see its `search_nodes` input),
+//! while the matches in the other methods are provided by the presenter through the `set_results`
+//! input, in reaction to the `query` output. The results are grouped by their methods. Choosing a
+//! result of the displayed graph pans the camera to its node; choosing any other result is left to
+//! the presenter, which should enter the method and then pan to the node.
+
+use crate::prelude::*;
+
+use crate::graph_editor::component::node_search::MatchKind;
+use crate::graph_editor::component::node_search::NodeMatch;
+use crate::graph_editor::NodeId;
+
+use enso_frp as frp;
+use ensogl::application;
+use ensogl::application::frp::API;
+use ensogl::application::shortcut::Shortcut;
+use ensogl::application::Application;
+use ensogl::display;
+use ensogl::display::shape::Rectangle;
+use ensogl::display::shape::StyleWatchFrp;
+use ensogl::gui::Widget;
+use ensogl_component::list_view;
+use ensogl_component::list_view::ListView;
+use ensogl_hardcoded_theme::application::search_everywhere as theme;
+use ensogl_text as text;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+const WIDTH: f32 = 560.0;
+/// The height of the query field above the list of results.
+const QUERY_HEIGHT: f32 = 36.0;
+const PADDING: f32 = 8.0;
+/// The distance between the top of the screen and the panel.
+const TOP_MARGIN: f32 = 80.0;
+/// The panel grows with the number of listed rows up to this many rows, then the list scrolls.
+const MAX_VISIBLE_ROWS: usize = 12;
+/// The header of the group of the results in the displayed graph.
+const CURRENT_GRAPH_HEADER: &str = "Current graph";
+
+
+
+// ====================
+// === SearchResult ===
+// ====================
+
+/// A node matching the query.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchResult {
+    /// The qualified name of the method containing the node, or [`None`] for the nodes of the
+    /// displayed graph.
+    pub method:  Option<ImString>,
+    /// The matching node. Set only for the nodes of the displayed graph; the presenter identifies
+    /// the nodes of the other methods by the method and the `text`.
+    pub node_id: Option<NodeId>,
+    #[allow(missing_docs)]
+    pub kind:    MatchKind,
+    /// The whole expression or comment containing the match.
+    pub text:    ImString,
+}
+
+impl From<&NodeMatch> for SearchResult {
+    fn from(node_match: &NodeMatch) -> Self {
+        let node_id = Some(node_match.node_id);
+        Self { method: None, node_id, kind: node_match.kind, text: node_match.text.clone() }
+    }
+}
+
+impl SearchResult {
+    fn label(&self) -> String {
+        match self.kind {
+            MatchKind::Expression => format!("    {}", self.text),
+            MatchKind::Comment => format!("    # {}", self.text),
+        }
+    }
+}
+
+/// A row of the result list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Row {
+    Header(ImString),
+    /// The index of the result.
+    Result(usize),
+}
+
+/// Group the results by their methods: the results of the displayed graph first, then the other
+/// methods in the order of their first results.
+fn group(results: &[SearchResult]) -> Vec<Row> {
+    let mut methods: Vec<Option<&ImString>> = vec![];
+    let local_first = results.iter().map(|r| r.method.as_ref()).sorted_by_key(|m| m.is_some());
+    for method in local_first {
+        if !methods.contains(&method) {
+            methods.push(method);
+        }
+    }
+    let rows = methods.into_iter().flat_map(|method| {
+        let header = method.cloned().unwrap_or_else(|| CURRENT_GRAPH_HEADER.into());
+        let members = results.iter().positions(move |r| r.method.as_ref() == method);
+        iter::once(Row::Header(header)).chain(members.map(Row::Result))
+    });
+    rows.collect()
+}
+
+
+
+// ===========
+// === FRP ===
+// ===========
+
+ensogl::define_endpoints_2! {
+    Input {
+        /// Show the panel with an empty query.
+        show(),
+        /// Hide the panel without choosing any result.
+        hide(),
+        /// Set the matches in the displayed graph.
+        set_local_results(Rc<Vec<NodeMatch>>),
+        /// Set the matches in the other methods, found by the presenter for the `query` output.
+        set_results(Rc<Vec<SearchResult>>),
+    }
+    Output {
+        is_visible(bool),
+        /// The query typed in the panel.
+        query(ImString),
+        /// The user chose the result. The results of the displayed graph are handled by panning the
+        /// camera, so only the results of the other methods need to be handled by the presenter.
+        result_chosen(SearchResult),
+        /// The user chose a result of the displayed graph.
+        local_node_chosen(NodeId),
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+/// The internal model of [`SearchEverywhere`].
+#[derive(Debug, Clone, CloneRef, display::Object)]
+pub struct Model {
+    display_object: display::object::Instance,
+    /// The visible part of the panel, attached only when the panel is shown.
+    content:        display::object::Instance,
+    background:     Rectangle,
+    query:          text::Text,
+    list:           ListView<list_view::entry::Label>,
+    local_results:  Rc<RefCell<Vec<SearchResult>>>,
+    results:        Rc<RefCell<Vec<SearchResult>>>,
+    rows:           Rc<RefCell<Vec<Row>>>,
+}
+
+impl Model {
+    fn new(app: &Application) -> Self {
+        let scene = &app.display.default_scene;
+        let display_object = display::object::Instance::new_named("SearchEverywhere");
+        let content = display::object::Instance::new();
+        let background = Rectangle();
+        let query = app.new_view::<text::Text>();
+        let list = app.new_view::<ListView<list_view::entry::Label>>();
+        content.add_child(&background);
+        content.add_child(&query);
+        content.add_child(&list);
+        scene.layers.panel.add(&content);
+        scene.layers.panel_text.add(&query);
+        list.set_label_layer(&scene.layers.panel_text);
+        query.set_single_line_mode(true);
+        query.set_xy(Vector2(-WIDTH / 2.0 + PADDING, -QUERY_HEIGHT / 2.0));
+        let local_results = default();
+        let results = default();
+        let rows = default();
+        Self { display_object, content, background, query, list, local_results, results, rows }
+    }
+
+    fn show(&self) {
+        self.display_object.add_child(&self.content);
+        self.query.set_content("");
+        self.query.focus();
+        self.list.deprecated_focus();
+    }
+
+    fn hide(&self) {
+        self.query.blur();
+        self.list.deprecated_defocus();
+        self.content.unset_parent();
+    }
+
+    fn set_local_results(&self, matches: &[NodeMatch]) {
+        *self.local_results.borrow_mut() = matches.iter().map(SearchResult::from).collect();
+        self.update_list();
+    }
+
+    fn set_results(&self, results: &[SearchResult]) {
+        *self.results.borrow_mut() = results.to_vec();
+        self.update_list();
+    }
+
+    fn all_results(&self) -> Vec<SearchResult> {
+        let local = self.local_results.borrow();
+        local.iter().chain(self.results.borrow().iter()).cloned().collect()
+    }
+
+    /// List the results grouped by their methods and resize the panel to fit them.
+    fn update_list(&self) {
+        let results = self.all_results();
+        let rows = group(&results);
+        let labels = rows.iter().map(|row| match row {
+            Row::Header(method) => method.to_string(),
+            Row::Result(index) => results[*index].label(),
+        });
+        let labels = labels.collect_vec();
+        let visible_rows = labels.len().min(MAX_VISIBLE_ROWS);
+        let list_height = visible_rows as f32 * list_view::entry::HEIGHT + PADDING * 2.0;
+        let height = QUERY_HEIGHT + list_height;
+        self.background.set_size(Vector2(WIDTH, height));
+        self.background.set_xy(Vector2(-WIDTH / 2.0, -height));
+        self.list.resize(Vector2(WIDTH, list_height));
+        self.list.set_y(-QUERY_HEIGHT - list_height / 2.0);
+        let first_result = rows.iter().position(|row| matches!(row, Row::Result(_)));
+        *self.rows.borrow_mut() = rows;
+        self.list.set_entries(list_view::entry::AnyModelProvider::new(labels));
+        self.list.select_entry(first_result);
+    }
+
+    /// The result listed in the row, or [`None`] for the group headers.
+    fn result_at(&self, row: list_view::entry::Id) -> Option<SearchResult> {
+        match self.rows.borrow().get(row)? {
+            Row::Header(_) => None,
+            Row::Result(index) => self.all_results().get(*index).cloned(),
+        }
+    }
+}
+
+
+
+// ========================
+// === SearchEverywhere ===
+// ========================
+
+/// The "search everywhere" panel. See the module documentation for details.
+#[derive(Debug, Clone, CloneRef, Deref, display::Object)]
+pub struct SearchEverywhere {
+    widget: Widget<Model, Frp>,
+}
+
+impl SearchEverywhere {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let model = Rc::new(Model::new(app));
+        let frp = Frp::new();
+        let network = frp.network();
+        let input = &frp.private().input;
+        let out = &frp.private().output;
+        let scene = &app.display.default_scene;
+        let style = StyleWatchFrp::new(&scene.style_sheet);
+        let background_color = style.get_color(theme::background);
+        let query_color = style.get_color(theme::query);
+
+        frp::extend! { network
+            init <- source_();
+
+            // === Visibility ===
+
+            show <- input.show.gate_not(&out.is_visible);
+            hide <- input.hide.gate(&out.is_visible);
+            eval_ show (model.show());
+            eval_ hide (model.hide());
+            out.is_visible <+ bool(&hide, &show);
+            position <- all_with(scene.shape(), &init, |shape, _| {
+                Vector2(0.0, shape.height / 2.0 - TOP_MARGIN)
+            });
+            eval position ((position) model.content.set_xy(*position));
+
+
+            // === Results ===
+
+            out.query <+ model.query.content.map(|content| ImString::from(content.to_string()));
+            out.query <+ show.constant(default());
+            eval input.set_local_results ((matches) model.set_local_results(matches));
+            eval input.set_results ((results) model.set_results(results));
+            chosen <- model.list.chosen_entry.filter_map(f!((row) model.result_at((*row)?)));
+            out.is_visible <+ chosen.constant(false);
+            eval_ chosen (model.hide());
+            out.result_chosen <+ chosen.filter(|result| result.method.is_some());
+            out.local_node_chosen <+ chosen.filter_map(|result| result.node_id);
+
+
+            // === Style ===
+
+            background_color <- all(&background_color, &init)._0();
+            eval background_color ((color) model.background.set_color(*color););
+            query_color <- all(&query_color, &init)._0();
+            model.query.set_property_default <+ query_color.ref_into_some();
+        }
+        init.emit(());
+
+        let widget = Widget::new(app, frp, model);
+        Self { widget }
+    }
+}
+
+impl FrpNetworkProvider for SearchEverywhere {
+    fn network(&self) -> &frp::Network {
+        self.widget.frp().network()
+    }
+}
+
+impl application::View for SearchEverywhere {
+    fn label() -> &'static str {
+        "SearchEverywhere"
+    }
+
+    fn new(app: &Application) -> Self {
+        Self::new(app)
+    }
+
+    fn global_shortcuts() -> Vec<Shortcut> {
+        use ensogl::application::shortcut::ActionType::*;
+        [(Press, "!is_visible", "cmd shift e", "show"), (Press, "is_visible", "escape", "hide")]
+            .iter()
+            .map(|(a, b, c, d)| Self::self_shortcut_when(*a, *c, *d, *b))
+            .collect()
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(method: Option<&str>, text: &str) -> SearchResult {
+        SearchResult { method: method.map(ImString::from), text: text.into(), ..default() }
+    }
+
+    #[test]
+    fn grouping_results_by_method() {
+        let results = vec![
+            result(Some("Main.main"), "a = 1"),
+            result(Some("Main.helper"), "b = 2"),
+            result(None, "c = 3"),
+            result(Some("Main.main"), "d = 4"),
+        ];
+        let expected = vec![
+            Row::Header(CURRENT_GRAPH_HEADER.into()),
+            Row::Result(2),
+            Row::Header("Main.main".into()),
+            Row::Result(0),
+            Row::Result(3),
+            Row::Header("Main.helper".into()),
+            Row::Result(1),
+        ];
+        assert_eq!(group(&results), expected);
+        assert!(group(&[]).is_empty());
+    }
+}
@@ -1,6 +1,7 @@
 /// <reference types="histoire" />
 
 import vue from '@vitejs/plugin-vue'
+import { resolve } from 'node:path'
 import { fileURLToPath } from 'node:url'
 import postcssNesting from 'postcss-nesting'
 import tailwindcss from 'tailwindcss'
@@ -18,7 +19,7 @@ const IS_CLOUD_BUILD = process.env.CLOUD_BUILD === 'true'
 // https://vitejs.dev/config/
 export default defineConfig({
   cacheDir: '../../node_modules/.cache/vite',
-  plugins: [vue(), gatewayServer(), topLevelAwait()],
+  plugins: [vue(), gatewayServer(), backendRestartReload(), topLevelAwait()],
   optimizeDeps: {
     entries: 'index.html',
   },
@@ -90,3 +91,20 @@ function gatewayServer(): Plugin {
     },
   }
 }
+
+/** Reload the page whenever the build script restarts the Project Manager, so that the GUI
+ * reconnects to it. The build script signals restarts by modifying the marker file. */
+function backendRestartReload(): Plugin {
+  return {
+    name: 'backend-restart-reload',
+    configureServer(server) {
+      const marker = process.env.ENSO_BACKEND_RESTART_MARKER
+      if (marker == null) return
+
+      server.watcher.add(marker)
+      server.watcher.on('change', (path) => {
+        if (resolve(path) === resolve(marker)) server.ws.send({ type: 'full-reload' })
+      })
+    },
+  }
+}
@@ -0,0 +1,314 @@
+//! Heuristics for selecting the parts of the repository affected by the current changes.
+//!
+//! They are used to run only the tests that may be influenced by the diff against some base
+//! revision. Whenever the build infrastructure itself is changed, everything is considered
+//! affected.
+
+use crate::prelude::*;
+
+use ide_ci::programs::git;
+use ide_ci::programs::Cargo;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The git reference against which the changes are computed by default.
+pub const DEFAULT_BASE: &str = "origin/develop";
+
+/// Paths (relative to the repository root) of the build infrastructure. Changing any of them may
+/// affect every test, so it results in a full test run.
+pub const INFRASTRUCTURE_PATHS: [&str; 12] = [
+    ".cargo",
+    ".github",
+    "build",
+    "build.sbt",
+    "build-config.yaml",
+    "Cargo.lock",
+    "Cargo.toml",
+    "package.json",
+    "package-lock.json",
+    "project",
+    "run",
+    "rust-toolchain.toml",
+];
+
+/// Directories that are never searched for the package manifests.
+const IGNORED_DIRECTORIES: [&str; 4] = ["node_modules", "target", "dist", ".git"];
+
+
+
+// ================
+// === Affected ===
+// ================
+
+/// The items (e.g. packages or test suites) affected by the changes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Affected<T> {
+    /// The build infrastructure was changed, so everything must be considered affected.
+    Everything,
+    /// Only the listed items are affected.
+    Only(BTreeSet<T>),
+}
+
+impl<T: Ord> Affected<T> {
+    /// Check if the given item is affected.
+    pub fn contains(&self, item: &T) -> bool {
+        match self {
+            Affected::Everything => true,
+            Affected::Only(items) => items.contains(item),
+        }
+    }
+}
+
+
+
+// ===============
+// === Changes ===
+// ===============
+
+/// The set of files changed against the base revision.
+#[derive(Clone, Debug, Default)]
+pub struct Changes {
+    /// The changed files, relative to the repository root.
+    pub files: Vec<PathBuf>,
+}
+
+impl Changes {
+    /// Collect the files that differ between the working tree and the `base` revision.
+    #[context("Failed to list the files changed against {}.", base)]
+    pub async fn compute(repo_root: impl AsRef<Path>, base: &str) -> Result<Self> {
+        let repo_root = repo_root.as_ref();
+        let git = git::Context::new(repo_root).await?;
+        let files = git.diff_against(base).await?;
+        let files = files
+            .into_iter()
+            .filter_map(|file| file.strip_prefix(repo_root).ok().map(Path::to_path_buf))
+            .collect_vec();
+        debug!("Files changed against {base}: {files:#?}.");
+        Ok(Self { files })
+    }
+
+    /// Check if any of the changed files is a part of the build infrastructure.
+    pub fn touches_infrastructure(&self) -> bool {
+        INFRASTRUCTURE_PATHS.iter().any(|path| self.touches(path))
+    }
+
+    /// Check if any of the changed files is the given path or is located under it.
+    pub fn touches(&self, path: impl AsRef<Path>) -> bool {
+        let path = path.as_ref();
+        self.files.iter().any(|file| file.starts_with(path))
+    }
+}
+
+
+
+// ==============
+// === Crates ===
+// ==============
+
+/// A Rust crate being a member of the Cargo workspace.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Crate {
+    /// The package name.
+    pub name: String,
+    /// The directory with the crate's manifest.
+    pub path: PathBuf,
+}
+
+/// The subset of `cargo metadata` output used to build the dependency graph.
+#[derive(Clone, Debug, Deserialize)]
+struct Metadata {
+    packages:          Vec<MetadataPackage>,
+    workspace_members: Vec<String>,
+    resolve:           Option<MetadataResolve>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct MetadataPackage {
+    id:            String,
+    name:          String,
+    manifest_path: PathBuf,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct MetadataResolve {
+    nodes: Vec<MetadataNode>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct MetadataNode {
+    id:           String,
+    dependencies: Vec<String>,
+}
+
+/// Compute the workspace crates affected by the changes: the crates containing the changed files,
+/// together with all crates that (transitively) depend on them.
+#[context("Failed to compute the crates affected by the changes.")]
+pub async fn affected_crates(
+    repo_root: impl AsRef<Path>,
+    changes: &Changes,
+) -> Result<Affected<Crate>> {
+    let repo_root = repo_root.as_ref();
+    if changes.touches_infrastructure() {
+        return Ok(Affected::Everything);
+    }
+    let stdout = Cargo
+        .cmd()?
+        .current_dir(repo_root)
+        .arg("metadata")
+        .args(["--format-version", "1"])
+        .run_stdout()
+        .await?;
+    let metadata = serde_json::from_str::<Metadata>(&stdout)?;
+    let members: HashSet<&String> = metadata.workspace_members.iter().collect();
+    let crates: HashMap<&String, Crate> = metadata
+        .packages
+        .iter()
+        .filter(|package| members.contains(&package.id))
+        .filter_map(|package| {
+            let path = package.manifest_path.parent()?.strip_prefix(repo_root).ok()?;
+            Some((&package.id, Crate { name: package.name.clone(), path: path.to_path_buf() }))
+        })
+        .collect();
+    let dependencies = metadata.resolve.map(|resolve| resolve.nodes).unwrap_or_default();
+    let dependencies = dependencies.into_iter().map(|node| (node.id, node.dependencies));
+    let changed = changes.files.iter().filter_map(|file| {
+        crates
+            .iter()
+            .filter(|(_, krate)| file.starts_with(&krate.path))
+            .max_by_key(|(_, krate)| krate.path.components().count())
+            .map(|(id, _)| (*id).clone())
+    });
+    let affected = dependents_closure(changed, dependencies);
+    let affected = affected.iter().filter_map(|id| crates.get(id).cloned()).collect();
+    Ok(Affected::Only(affected))
+}
+
+/// Compute the given packages together with all packages transitively depending on them. The
+/// `dependencies` are pairs of a package and the list of packages it depends on.
+fn dependents_closure<Id: Clone + Eq + Hash>(
+    roots: impl IntoIterator<Item = Id>,
+    dependencies: impl IntoIterator<Item = (Id, Vec<Id>)>,
+) -> HashSet<Id> {
+    let mut dependents: HashMap<Id, Vec<Id>> = default();
+    for (package, package_dependencies) in dependencies {
+        for dependency in package_dependencies {
+            dependents.entry(dependency).or_default().push(package.clone());
+        }
+    }
+    let mut visited: HashSet<Id> = default();
+    let mut to_visit = roots.into_iter().collect_vec();
+    while let Some(package) = to_visit.pop() {
+        if visited.insert(package.clone()) {
+            to_visit.extend(dependents.get(&package).into_iter().flatten().cloned());
+        }
+    }
+    visited
+}
+
+
+
+// ====================
+// === NPM Packages ===
+// ====================
+
+/// The subset of `package.json` contents used to build the dependency graph.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PackageManifest {
+    name:             Option<String>,
+    #[serde(default)]
+    dependencies:     BTreeMap<String, String>,
+    #[serde(default)]
+    dev_dependencies: BTreeMap<String, String>,
+}
+
+/// Find all NPM packages in the repository, mapping their names to their directories (relative to
+/// the repository root).
+pub fn npm_packages(repo_root: impl AsRef<Path>) -> Result<HashMap<String, PathBuf>> {
+    let repo_root = repo_root.as_ref();
+    let walker = walkdir::WalkDir::new(repo_root.join("app")).into_iter().filter_entry(|entry| {
+        let name = entry.file_name().to_string_lossy();
+        !IGNORED_DIRECTORIES.contains(&name.as_ref())
+    });
+    let mut packages = HashMap::new();
+    for entry in walker {
+        let entry = entry?;
+        if entry.file_name() == "package.json" {
+            let manifest = read_package_manifest(entry.path())?;
+            if let Some(name) = manifest.name {
+                let directory = entry.path().parent().context("Manifest has no parent.")?;
+                packages.insert(name, directory.strip_prefix(repo_root)?.to_path_buf());
+            }
+        }
+    }
+    Ok(packages)
+}
+
+/// Check if the NPM package in the given directory (relative to the repository root) is affected
+/// by the changes, i.e. if the changes touch the package or any local package it depends on.
+#[context("Failed to check if the package in {} is affected.", package.as_ref().display())]
+pub fn is_npm_package_affected(
+    repo_root: impl AsRef<Path>,
+    package: impl AsRef<Path>,
+    changes: &Changes,
+) -> Result<bool> {
+    let repo_root = repo_root.as_ref();
+    if changes.touches_infrastructure() {
+        return Ok(true);
+    }
+    let packages = npm_packages(repo_root)?;
+    let mut visited: HashSet<PathBuf> = default();
+    let mut to_visit = vec![package.as_ref().to_path_buf()];
+    while let Some(directory) = to_visit.pop() {
+        if changes.touches(&directory) {
+            return Ok(true);
+        }
+        let manifest = read_package_manifest(repo_root.join(&directory).join("package.json"))?;
+        let dependencies = manifest.dependencies.keys().chain(manifest.dev_dependencies.keys());
+        let local = dependencies.filter_map(|name| packages.get(name)).cloned().collect_vec();
+        visited.insert(directory);
+        to_visit.extend(local.into_iter().filter(|dir| !visited.contains(dir)));
+    }
+    Ok(false)
+}
+
+fn read_package_manifest(path: impl AsRef<Path>) -> Result<PackageManifest> {
+    let contents = ide_ci::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dependents_closure_follows_reverse_dependencies() {
+        // a → b → c, d → c
+        let dependencies = [("a", vec!["b"]), ("b", vec!["c"]), ("c", vec![]), ("d", vec!["c"])];
+        let closure =
+            |roots: &[&'static str]| dependents_closure(roots.to_vec(), dependencies.clone());
+        assert_eq!(closure(&["c"]), HashSet::from(["a", "b", "c", "d"]));
+        assert_eq!(closure(&["b"]), HashSet::from(["a", "b"]));
+        assert_eq!(closure(&["a"]), HashSet::from(["a"]));
+    }
+
+    #[test]
+    fn infrastructure_changes_are_detected() {
+        let changes = |files: &[&str]| Changes { files: files.iter().map(PathBuf::from).collect() };
+        assert!(changes(&["Cargo.lock"]).touches_infrastructure());
+        assert!(changes(&["build/cli/src/lib.rs"]).touches_infrastructure());
+        assert!(!changes(&["app/gui/src/lib.rs"]).touches_infrastructure());
+        assert!(!changes(&["lib/rust/build-utils/src/lib.rs"]).touches_infrastructure());
+        assert!(!changes(&["build.sbt.orig"]).touches_infrastructure());
+    }
+}
@@ -65,17 +65,23 @@ pub struct Config {
 impl Config {
     /// Check whether all the required programs are available and have the required versions.
     pub async fn check_programs(&self) -> Result {
-        let check_tasks = self
-            .required_versions
-            .iter()
-            .map(|(program, version_req)| check_program(program, version_req));
-        let results = futures::future::join_all(check_tasks).await;
-        let errors = results.into_iter().filter_map(Result::err).collect_vec();
+        let results = self.check_each_program().await;
+        let errors = results.into_iter().filter_map(|(_, _, result)| result.err()).collect_vec();
         if !(errors.is_empty()) {
             bail!("Some required programs are not available or have wrong versions: {errors:?}")
         }
         Ok(())
     }
+
+    /// Check each of the required programs, returning the outcomes in the order of program names.
+    pub async fn check_each_program(&self) -> Vec<(&RecognizedProgram, &VersionReq, Result)> {
+        let check_tasks = self.required_versions.iter().map(|(program, version_req)| async move {
+            (program, version_req, check_program(program, version_req).await)
+        });
+        let mut results = futures::future::join_all(check_tasks).await;
+        results.sort_by_key(|(program, _, _)| program.to_string());
+        results
+    }
 }
 
 impl TryFrom<ConfigRaw> for Config {
@@ -0,0 +1,401 @@
+//! Diagnosing the development environment.
+//!
+//! Unlike the regular version check that is run before each command, the diagnosis does not stop
+//! on the first problem. It reports all of them, each with a suggested fix.
+
+use crate::prelude::*;
+
+use crate::config::Config;
+use crate::config::RecognizedProgram;
+
+use byte_unit::Byte;
+use ide_ci::cache::Cache;
+use ide_ci::programs::Node;
+use ide_ci::programs::Npm;
+use ide_ci::programs::rustup::Rustup;
+use semver::VersionReq;
+use sysinfo::DiskExt;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The free disk space below which the build is likely to fail.
+pub const MIN_FREE_SPACE: u64 = 5 * 1024 * 1024 * 1024;
+
+/// The free disk space needed to build all the targets.
+pub const RECOMMENDED_FREE_SPACE: u64 = 20 * 1024 * 1024 * 1024;
+
+/// The file pinning the Rust toolchain, relative to the repository root.
+pub const RUST_TOOLCHAIN_FILE: &str = "rust-toolchain.toml";
+
+
+
+// ================
+// === Severity ===
+// ================
+
+/// How much a finding affects the ability to build the project.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Severity {
+    /// Everything is in order.
+    Ok,
+    /// Some builds may fail or be slower than necessary.
+    Warning,
+    /// The builds cannot succeed until the problem is fixed.
+    Blocker,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            Severity::Ok => "ok",
+            Severity::Warning => "warning",
+            Severity::Blocker => "blocker",
+        };
+        write!(f, "{label}")
+    }
+}
+
+
+
+// ===============
+// === Finding ===
+// ===============
+
+/// The outcome of a single check.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Finding {
+    /// What was checked, e.g. the program name.
+    pub subject:  String,
+    pub severity: Severity,
+    pub message:  String,
+    /// The suggested way of fixing the problem.
+    pub fix:      Option<String>,
+}
+
+impl Finding {
+    pub fn ok(subject: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(subject, Severity::Ok, message, None)
+    }
+
+    pub fn warning(
+        subject: impl Into<String>,
+        message: impl Into<String>,
+        fix: impl Into<String>,
+    ) -> Self {
+        Self::new(subject, Severity::Warning, message, Some(fix.into()))
+    }
+
+    pub fn blocker(
+        subject: impl Into<String>,
+        message: impl Into<String>,
+        fix: impl Into<String>,
+    ) -> Self {
+        Self::new(subject, Severity::Blocker, message, Some(fix.into()))
+    }
+
+    fn new(
+        subject: impl Into<String>,
+        severity: Severity,
+        message: impl Into<String>,
+        fix: Option<String>,
+    ) -> Self {
+        Self { subject: subject.into(), severity, message: message.into(), fix }
+    }
+}
+
+
+
+// ==============
+// === Report ===
+// ==============
+
+/// The findings of all the checks, grouped by the area of the environment.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Report {
+    pub sections: Vec<(String, Vec<Finding>)>,
+}
+
+impl Report {
+    /// Whether any of the findings prevents the builds from succeeding.
+    pub fn has_blockers(&self) -> bool {
+        self.findings().any(|finding| finding.severity == Severity::Blocker)
+    }
+
+    pub fn findings(&self) -> impl Iterator<Item = &Finding> {
+        self.sections.iter().flat_map(|(_, findings)| findings)
+    }
+
+    fn add_section(&mut self, name: impl Into<String>, findings: Vec<Finding>) {
+        self.sections.push((name.into(), findings));
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (name, findings) in &self.sections {
+            writeln!(f, "{name}:")?;
+            for Finding { subject, severity, message, fix } in findings {
+                writeln!(f, "  [{severity}] {subject}: {message}")?;
+                if let Some(fix) = fix {
+                    writeln!(f, "      Fix: {fix}")?;
+                }
+            }
+        }
+        let count = |severity| self.findings().filter(|f| f.severity == severity).count();
+        let blockers = count(Severity::Blocker);
+        let warnings = count(Severity::Warning);
+        write!(f, "Found {blockers} blocker(s) and {warnings} warning(s).")
+    }
+}
+
+
+
+// =================
+// === Diagnosis ===
+// =================
+
+/// Run all the checks of the environment.
+pub async fn diagnose(config: &Config, repo_root: &Path, cache_path: &Path) -> Report {
+    let mut report = Report::default();
+    report.add_section("Required programs", check_programs(config).await);
+    report.add_section("Node.js", check_node(repo_root).await);
+    report.add_section("Rust toolchain", check_rust_toolchain(repo_root).await);
+    report.add_section("Disk space", vec![check_disk_space(repo_root)]);
+    report.add_section("Cache", check_cache(cache_path).await);
+    report
+}
+
+async fn check_programs(config: &Config) -> Vec<Finding> {
+    let mut findings = vec![];
+    for (program, version_req, result) in config.check_each_program().await {
+        let subject = program.to_string();
+        findings.push(match result {
+            Ok(()) => Finding::ok(subject, format!("Found a version matching `{version_req}`.")),
+            Err(e) => {
+                let fix = program_fix(program, version_req);
+                Finding::blocker(subject, format!("{e:#}"), fix)
+            }
+        });
+    }
+    findings
+}
+
+/// Suggest how to install the program in the required version.
+pub fn program_fix(program: &RecognizedProgram, version_req: &VersionReq) -> String {
+    let RecognizedProgram::Other(name) = program;
+    if name == "node" {
+        "Install the Node.js version listed in `.node-version`, e.g. with `nvm install`.".to_owned()
+    } else if name.starts_with("cargo-") || name == "wasm-pack" {
+        format!("Run `cargo install {name} --version '{version_req}'`.")
+    } else {
+        format!(
+            "Install `{name}` in a version matching `{version_req}` and make sure it is in PATH, \
+            or update the requirement in `build-config.yaml`."
+        )
+    }
+}
+
+async fn check_node(repo_root: &Path) -> Vec<Finding> {
+    let mut findings = vec![];
+    match Npm.version().await {
+        Ok(version) => findings.push(Finding::ok("npm", format!("Found version {version}."))),
+        Err(e) => findings.push(Finding::blocker(
+            "npm",
+            format!("Not available: {e}"),
+            "Install npm. It is distributed together with Node.js.",
+        )),
+    }
+    if Node.lookup().is_ok() {
+        let node_modules = repo_root.join("node_modules");
+        findings.push(if node_modules.exists() {
+            Finding::ok("packages", "The npm packages are installed.")
+        } else {
+            Finding::warning(
+                "packages",
+                "The npm packages are not installed.",
+                "Run `npm install` in the repository root. Otherwise, they are installed on the \
+                first build that needs them.",
+            )
+        });
+    }
+    findings
+}
+
+/// The toolchain section of the [`RUST_TOOLCHAIN_FILE`].
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ToolchainSpec {
+    pub channel:    String,
+    pub components: Vec<String>,
+    pub targets:    Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct ToolchainFile {
+    toolchain: ToolchainSpec,
+}
+
+async fn check_rust_toolchain(repo_root: &Path) -> Vec<Finding> {
+    if let Err(e) = Rustup.lookup() {
+        let fix = "Install rustup, following the instructions at <https://rustup.rs>.";
+        return vec![Finding::blocker("rustup", format!("Not available: {e}"), fix)];
+    }
+    let spec = ide_ci::fs::read_to_string(repo_root.join(RUST_TOOLCHAIN_FILE))
+        .and_then(|text| toml::from_str::<ToolchainFile>(&text).anyhow_err());
+    let spec = match spec {
+        Ok(ToolchainFile { toolchain }) => toolchain,
+        Err(e) => {
+            let message = format!("Cannot read the pinned toolchain: {e}");
+            let fix = format!("Make sure `{RUST_TOOLCHAIN_FILE}` is in the repository root.");
+            return vec![Finding::warning("toolchain", message, fix)];
+        }
+    };
+    let channel = spec.channel.as_str();
+    let rustup_list = |args: &[&str]| {
+        let args = args.iter().map(ToString::to_string).collect_vec();
+        let repo_root = repo_root.to_owned();
+        async move {
+            let stdout = Rustup.cmd()?.current_dir(repo_root).args(args).run_stdout().await?;
+            Result::Ok(stdout.lines().map(|line| line.trim().to_owned()).collect_vec())
+        }
+    };
+
+    let mut findings = vec![];
+    let toolchains = rustup_list(&["toolchain", "list"]).await.unwrap_or_default();
+    if !toolchains.iter().any(|toolchain| toolchain.starts_with(channel)) {
+        findings.push(Finding::warning(
+            "toolchain",
+            format!("The pinned toolchain `{channel}` is not installed."),
+            format!("Run `rustup toolchain install {channel}`."),
+        ));
+        return findings;
+    }
+    findings.push(Finding::ok("toolchain", format!("The toolchain `{channel}` is installed.")));
+
+    let components =
+        rustup_list(&["component", "list", "--installed", "--toolchain", channel]).await;
+    let targets = rustup_list(&["target", "list", "--installed", "--toolchain", channel]).await;
+    let mut check_installed = |kind: &str, required: &[String], installed: Result<Vec<String>>| {
+        let installed = installed.unwrap_or_default();
+        for name in required {
+            let subject = format!("{kind} {name}");
+            // Components are listed with the host triple, like `rustfmt-x86_64-apple-darwin`.
+            let is_installed = installed.iter().any(|entry| entry.starts_with(name.as_str()));
+            findings.push(if is_installed {
+                Finding::ok(subject, "Installed.")
+            } else {
+                Finding::warning(
+                    subject,
+                    "Not installed.",
+                    format!("Run `rustup {kind} add {name} --toolchain {channel}`."),
+                )
+            });
+        }
+    };
+    check_installed("component", &spec.components, components);
+    check_installed("target", &spec.targets, targets);
+    findings
+}
+
+fn check_disk_space(repo_root: &Path) -> Finding {
+    let mut system = sysinfo::System::new();
+    system.refresh_disks_list();
+    // The disk with the repository is the one mounted at the longest prefix of its path.
+    let repo_root = repo_root.absolutize().map_or_else(|_| repo_root.to_owned(), Cow::into_owned);
+    let disk = system
+        .disks()
+        .iter()
+        .filter(|disk| repo_root.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len());
+    let Some(disk) = disk else {
+        let message = "Cannot find the disk with the repository.";
+        return Finding::warning("free space", message, "Make sure the repository path is valid.");
+    };
+    let available = disk.available_space();
+    let pretty = |bytes: u64| Byte::from_bytes(bytes.into()).get_appropriate_unit(true);
+    let message = format!("{} available.", pretty(available));
+    let recommended = pretty(RECOMMENDED_FREE_SPACE);
+    let fix = format!("Free up some disk space. A full build needs about {recommended}.");
+    if available < MIN_FREE_SPACE {
+        Finding::blocker("free space", message, fix)
+    } else if available < RECOMMENDED_FREE_SPACE {
+        Finding::warning("free space", message, fix)
+    } else {
+        Finding::ok("free space", message)
+    }
+}
+
+async fn check_cache(cache_path: &Path) -> Vec<Finding> {
+    let clean_fix = "Run `./run git-clean --cache` to remove the cache.";
+    let cache = match Cache::new(cache_path).await {
+        Ok(cache) => cache,
+        Err(e) => {
+            let message = format!("Cannot use the cache at {}: {e}", cache_path.display());
+            let fix = "Make sure the cache directory is writable, or pass another location with \
+            `--cache-path`.";
+            return vec![Finding::blocker("location", message, fix)];
+        }
+    };
+    let mut findings = vec![];
+    let location = cache.path().display();
+    // The cache directory is created when the first entry is stored.
+    findings.push(if !cache.path().exists() {
+        Finding::ok("location", format!("{location} will be created when first needed."))
+    } else {
+        match tempfile::tempfile_in(cache.path()) {
+            Ok(_) => Finding::ok("location", format!("{location} is writable.")),
+            Err(e) => Finding::blocker(
+                "location",
+                format!("{location} is not writable: {e}"),
+                "Fix the permissions of the cache directory, or pass another location with \
+                `--cache-path`.",
+            ),
+        }
+    });
+    findings.push(match cache.incomplete_entries() {
+        Ok(entries) if entries.is_empty() => Finding::ok("entries", "All entries are complete."),
+        Ok(entries) => Finding::warning(
+            "entries",
+            format!("{} entries were not completely generated.", entries.len()),
+            format!("Remove the directories without a matching `.json` file, or {clean_fix}"),
+        ),
+        Err(e) => Finding::warning("entries", format!("Cannot list the entries: {e}"), clean_fix),
+    });
+    findings
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reporting_blockers() {
+        let mut report = Report::default();
+        report.add_section("Programs", vec![Finding::ok("node", "Found.")]);
+        assert!(!report.has_blockers());
+        report.add_section("Disk space", vec![Finding::blocker("free space", "1 GiB.", "Free.")]);
+        assert!(report.has_blockers());
+        let expected = "Programs:
+  [ok] node: Found.
+Disk space:
+  [blocker] free space: 1 GiB.
+      Fix: Free.
+Found 1 blocker(s) and 0 warning(s).";
+        assert_eq!(report.to_string(), expected);
+    }
+
+    #[test]
+    fn suggesting_program_fixes() -> Result {
+        let version_req = VersionReq::parse("^0.12.1")?;
+        let wasm_pack = RecognizedProgram::Other("wasm-pack".into());
+        let fix = program_fix(&wasm_pack, &version_req);
+        assert_eq!(fix, "Run `cargo install wasm-pack --version '^0.12.1'`.");
+        Ok(())
+    }
+}
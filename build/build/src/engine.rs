@@ -112,6 +112,17 @@ impl Benchmarks {
     }
 }
 
+impl Tests {
+    /// Directories (relative to the repository root) with sources that may affect these tests.
+    pub fn source_roots(self) -> &'static [&'static str] {
+        match self {
+            Tests::Scala => &["engine", "lib/java", "lib/scala"],
+            Tests::StandardLibrary =>
+                &["distribution/lib", "engine", "lib/java", "lib/scala", "std-bits", "test"],
+        }
+    }
+}
+
 /// Describes what should be done with the backend.
 ///
 /// Basically a recipe of what to do with `sbt` and its artifacts.
@@ -5,4 +5,5 @@
 // === Export ===
 // ==============
 
+pub mod startup;
 pub mod web;
@@ -0,0 +1,125 @@
+//! Measuring the startup performance of the packaged IDE.
+//!
+//! The IDE is launched with the `profile` entry point, running a workflow that creates a new
+//! project and quits once its graph is displayed. The captured profile is summarized by the
+//! `startup` tool of the `enso-profiler-data` crate.
+
+use crate::prelude::*;
+
+use crate::project::ide::Artifact;
+
+use ide_ci::actions::workflow::is_in_env;
+use ide_ci::programs::Cargo;
+use std::time::Duration;
+use std::time::Instant;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The profiling workflow run by the IDE. See `app/gui/src/profile_workflow.rs`.
+pub const WORKFLOW: &str = "new_project";
+
+/// The name of the CI artifact with the startup report.
+pub const REPORT_ARTIFACT_NAME: &str = "ide-startup-report";
+
+
+
+// ==============
+// === Report ===
+// ==============
+
+/// The summary of the IDE startup performance. All times are in milliseconds.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Report {
+    /// The wall-clock time from launching the IDE until it quit after the graph of the new project
+    /// was displayed.
+    pub cold_start_ms:         f64,
+    /// When the WASM module was initialized, relative to the start of the GUI process.
+    pub wasm_init_ms:          f64,
+    /// When the graph of the new project was displayed, relative to the start of the GUI process.
+    pub first_graph_render_ms: f64,
+}
+
+impl Report {
+    /// Fail if the cold start took longer than `max_cold_start`.
+    pub fn check_cold_start(&self, max_cold_start: Duration) -> Result {
+        let max_cold_start_ms = max_cold_start.as_secs_f64() * 1000.0;
+        ensure!(
+            self.cold_start_ms <= max_cold_start_ms,
+            "The IDE cold start took {:.0} ms, exceeding the limit of {:.0} ms.",
+            self.cold_start_ms,
+            max_cold_start_ms
+        );
+        Ok(())
+    }
+
+    /// Write the report as a JSON file, and upload it as an artifact if running on the CI.
+    pub async fn write(&self, path: impl AsRef<Path>) -> Result {
+        let path = path.as_ref();
+        path.write_as_json(self)?;
+        if is_in_env() {
+            ide_ci::actions::artifacts::upload_single_file(path, REPORT_ARTIFACT_NAME).await?;
+        }
+        Ok(())
+    }
+}
+
+/// The startup milestones read from the profile, as reported by the `startup` tool.
+#[derive(Clone, Copy, Debug, Deserialize)]
+struct StartupTimes {
+    wasm_init_ms:          f64,
+    first_graph_render_ms: f64,
+}
+
+
+
+// ===============
+// === Running ===
+// ===============
+
+/// Launch the unpacked IDE running the startup profiling workflow, and summarize the captured
+/// profile. The profile is saved to the `profile_path`.
+#[context("Failed to profile the startup of the IDE.")]
+pub async fn profile_startup(
+    repo_root: impl AsRef<Path>,
+    ide: &Artifact,
+    profile_path: impl AsRef<Path>,
+) -> Result<Report> {
+    // The path is passed to the IDE, which may run in a different working directory.
+    let profile_path = profile_path.as_ref().absolutize()?;
+    let profile_path = profile_path.as_ref();
+    ide_ci::fs::remove_if_exists(profile_path)?;
+    let options = [
+        "--startup.entry=profile".to_owned(),
+        format!("--profile.workflow={WORKFLOW}"),
+        format!("--profile.save={}", profile_path.display()),
+    ];
+    let launched = Instant::now();
+    ide.start_unpacked(options).run_ok().await?;
+    let cold_start_ms = launched.elapsed().as_secs_f64() * 1000.0;
+    let StartupTimes { wasm_init_ms, first_graph_render_ms } =
+        summarize_profile(repo_root, profile_path).await?;
+    let report = Report { cold_start_ms, wasm_init_ms, first_graph_render_ms };
+    info!("IDE startup report: {report:#?}");
+    Ok(report)
+}
+
+/// Read the startup milestones from the profile, using the `startup` tool.
+async fn summarize_profile(
+    repo_root: impl AsRef<Path>,
+    profile_path: &Path,
+) -> Result<StartupTimes> {
+    let profile = ide_ci::fs::open(profile_path)?;
+    let stdout = Cargo
+        .cmd()?
+        .current_dir(repo_root)
+        .arg("run")
+        .args(["--quiet", "--package", "enso-profiler-data", "--bin", "startup"])
+        .stdin(profile)
+        .run_stdout()
+        .await?;
+    serde_json::from_str(&stdout).context("Failed to parse the output of the `startup` tool.")
+}
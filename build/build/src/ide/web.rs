@@ -107,6 +107,15 @@ pub mod env {
         /// so we can safely enable this option.
         CSC_FOR_PULL_REQUEST, bool;
     }
+
+    // === Notarization ===
+
+    define_env_var! {
+        /// The name of the keychain profile with the Apple notary service credentials, created
+        /// with `xcrun notarytool store-credentials`. If not set, the notarization uses the
+        /// APPLEID, APPLEIDPASS and APPLETEAMID variables.
+        APPLE_NOTARY_KEYCHAIN_PROFILE, String;
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -45,6 +45,7 @@ pub mod prelude {
     pub use ide_ci::prelude::*;
 }
 
+pub mod affected;
 pub mod aws;
 pub mod bump_version;
 pub mod changelog;
@@ -52,6 +53,7 @@ pub mod ci;
 pub mod ci_gen;
 pub mod config;
 pub mod context;
+pub mod doctor;
 pub mod engine;
 pub mod enso;
 pub mod env;
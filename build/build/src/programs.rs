@@ -7,6 +7,9 @@ pub mod project_manager {
     use std::process::Stdio;
 
     use ide_ci::define_env_var;
+    use ide_ci::extensions::child::ChildExt;
+    use std::time::Duration;
+    use tokio::process::Child;
 
     define_env_var! {
         /// Custom project root. Useful if we want to use backend without affecting user's default
@@ -31,4 +34,20 @@ pub mod project_manager {
         command.stdin(Stdio::piped());
         command
     }
+
+    /// Stop the Project Manager spawned with [`spawn_from`]. It is killed if it does not shut down
+    /// gracefully within the `timeout`.
+    pub async fn stop(project_manager: &mut Child, timeout: Duration) -> Result {
+        if let Some(stdin) = project_manager.stdin.as_mut() {
+            if let Err(e) = stdin.write_all(b"\n").await {
+                warn!("Failed to ask the Project Manager to shut down: {e}");
+            }
+        }
+        if tokio::time::timeout(timeout, project_manager.wait()).await.is_err() {
+            warn!("The Project Manager did not shut down within {timeout:?}, killing it.");
+            project_manager.kill_subtree();
+            project_manager.wait().await?;
+        }
+        Ok(())
+    }
 }
@@ -24,6 +24,7 @@ use octocrab::models::repos::Asset;
 // === Export ===
 // ==============
 
+pub mod artifact_cache;
 pub mod backend;
 pub mod engine;
 pub mod gui;
@@ -91,6 +92,10 @@ pub struct Context {
     /// Stores things like downloaded release assets to save time.
     pub cache: Cache,
 
+    /// Whether the artifacts of earlier local builds with the same inputs may be reused from the
+    /// cache. See [`IsTarget::inputs_hash`].
+    pub use_artifact_cache: bool,
+
     /// Directory being an `enso` repository's working copy.
     ///
     /// The directory is not required to be a git repository. It is allowed to use source tarballs
@@ -179,7 +184,7 @@ pub trait IsTarget: Clone + Debug + Sized + Send + Sync + 'static {
     ) -> BoxFuture<'static, Result<Self::Artifact>> {
         let span = debug_span!("Building.", ?self, ?context, ?job).entered();
         let upload_artifacts = job.should_upload_artifact;
-        let artifact_fut = self.build_internal(context, job.map(|job| job.input));
+        let artifact_fut = self.build_or_reuse(context, job.map(|job| job.input));
         let this = self.clone();
         async move {
             let artifact = artifact_fut.await.context(format!("Failed to build {this:?}."))?;
@@ -204,6 +209,43 @@ pub trait IsTarget: Clone + Debug + Sized + Send + Sync + 'static {
         }
     }
 
+    /// Produce an artifact from build inputs, reusing the cached artifact of an earlier build with
+    /// the same [inputs hash](Self::inputs_hash) if possible.
+    fn build_or_reuse(
+        &self,
+        context: Context,
+        job: WithDestination<Self::BuildInput>,
+    ) -> BoxFuture<'static, Result<Self::Artifact>> {
+        let inputs_hash = match self.inputs_hash(&context, &job.inner) {
+            Some(inputs_hash) if context.use_artifact_cache => inputs_hash,
+            _ => return self.build_internal(context, job),
+        };
+        let this = self.clone();
+        async move {
+            let WithDestination { inner, destination } = job;
+            let inputs_hash = inputs_hash.await?;
+            let cache = context.cache.clone();
+            let build = artifact_cache::CachedBuild::new(this.clone(), context, inputs_hash, inner);
+            let cached_artifact = cache.get(build).await?;
+            ide_ci::fs::mirror_directory(&cached_artifact, &destination).await?;
+            this.adapt_artifact(destination).await
+        }
+        .boxed()
+    }
+
+    /// Compute the content hash of everything the artifact built from the given input depends on,
+    /// like the sources, build flags and toolchain versions. If an artifact was already built from
+    /// inputs with the same hash, it is reused from the cache instead of being built again.
+    ///
+    /// Returns `None` if the target's artifacts should not be cached, which is the default.
+    fn inputs_hash(
+        &self,
+        _context: &Context,
+        _input: &Self::BuildInput,
+    ) -> Option<BoxFuture<'static, Result<String>>> {
+        None
+    }
+
     /// Produce an artifact from build inputs.
     fn build_internal(
         &self,
@@ -226,7 +268,7 @@ pub trait IsTarget: Clone + Debug + Sized + Send + Sync + 'static {
         ci_run: CiRunSource,
         output_path: impl AsRef<Path> + Send + Sync + 'static,
     ) -> BoxFuture<'static, Result<Self::Artifact>> {
-        let Context { octocrab, cache, repo_root: _ } = context;
+        let Context { octocrab, cache, .. } = context;
         let CiRunSource { run_id, artifact_name, repository } = ci_run;
         let repository = repository.handle(&octocrab);
         let span = info_span!("Downloading CI Artifact.", %artifact_name, %repository, target = output_path.as_ref().as_str());
@@ -285,7 +327,7 @@ pub trait IsTarget: Clone + Debug + Sized + Send + Sync + 'static {
         source: ReleaseSource,
         destination: PathBuf,
     ) -> BoxFuture<'static, Result<Self::Artifact>> {
-        let Context { octocrab, cache, repo_root: _ } = context;
+        let Context { octocrab, cache, .. } = context;
         let span = info_span!("Downloading built target from a release asset.",
             asset_id = source.asset_id.0,
             repo = %source.repository);
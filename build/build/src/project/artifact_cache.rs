@@ -0,0 +1,81 @@
+//! Reusing the artifacts of earlier local builds whose inputs did not change.
+//!
+//! The artifacts are stored in the [`Cache`], keyed by the content hash of everything the build
+//! depends on. See [`IsTarget::inputs_hash`].
+
+use crate::prelude::*;
+
+use crate::project::Context;
+use crate::project::IsTarget;
+use crate::source::WithDestination;
+
+use ide_ci::cache::Cache;
+use ide_ci::cache::Storable;
+use std::sync::Mutex;
+
+
+
+/// Identifies the artifact of a single target built from specific inputs.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Key {
+    /// The name of the built target's artifact, see [`IsTarget::artifact_name`].
+    pub artifact_name: String,
+    /// The content hash of the build inputs, see [`IsTarget::inputs_hash`].
+    pub inputs_hash:   String,
+}
+
+/// A target built directly into the cache store. The build is performed only if there is no
+/// artifact with the same key in the cache.
+#[derive(Debug)]
+pub struct CachedBuild<T: IsTarget> {
+    key:     Key,
+    target:  T,
+    context: Context,
+    /// The build input, consumed by the build.
+    input:   Mutex<Option<T::BuildInput>>,
+}
+
+impl<T: IsTarget> CachedBuild<T> {
+    pub fn new(target: T, context: Context, inputs_hash: String, input: T::BuildInput) -> Self {
+        let key = Key { artifact_name: target.artifact_name(), inputs_hash };
+        Self { key, target, context, input: Mutex::new(Some(input)) }
+    }
+}
+
+impl<T: IsTarget> Storable for CachedBuild<T> {
+    type Metadata = ();
+    type Output = PathBuf;
+    type Key = Key;
+
+    fn generate(
+        &self,
+        _cache: Cache,
+        store: PathBuf,
+    ) -> BoxFuture<'static, Result<Self::Metadata>> {
+        let input = self
+            .input
+            .lock()
+            .map(|mut input| input.take())
+            .map_err(|e| anyhow!("Failed to access the build input: {e}"));
+        let target = self.target.clone();
+        let context = self.context.clone();
+        async move {
+            let input = input?.context("The build input was already consumed.")?;
+            target.build_internal(context, WithDestination::new(input, store)).await?;
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn adapt(
+        &self,
+        cache: PathBuf,
+        _metadata: Self::Metadata,
+    ) -> BoxFuture<'static, Result<Self::Output>> {
+        ready(Result::Ok(cache)).boxed()
+    }
+
+    fn key(&self) -> Self::Key {
+        self.key.clone()
+    }
+}
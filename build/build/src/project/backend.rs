@@ -6,13 +6,33 @@ use crate::paths::TargetTriple;
 use crate::project::Context;
 use crate::project::IsArtifact;
 use crate::project::IsTarget;
+use crate::project::IsWatchable;
+use crate::project::IsWatcher;
+use crate::source::BuildSource;
+use crate::source::WatchTargetJob;
 use crate::source::WithDestination;
 use crate::version::Versions;
 
 use derivative::Derivative;
 use ide_ci::archive::is_archive_name;
 use ide_ci::extensions::os::OsExt;
+use ide_ci::fs::watch::PollWatcher;
 use octocrab::models::repos::Asset;
+use std::time::Duration;
+use tokio::process::Child;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The backend sources, relative to the repository root. The watched Project Manager is rebuilt
+/// whenever any of them changes.
+pub const SOURCE_PATHS: [&str; 5] = ["engine", "lib/scala", "lib/java", "project", "build.sbt"];
+
+/// How long the watched Project Manager is given to shut down gracefully before a restart.
+pub const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
 
 
 
@@ -179,3 +199,111 @@ impl IsTarget for Backend {
             && (name.contains("bundle") || asset.size > 200_000_000)
     }
 }
+
+
+
+// ================
+// === Watching ===
+// ================
+
+#[derive(Clone, Copy, Debug)]
+pub struct WatchInput {
+    /// How often the backend sources are checked for changes.
+    pub poll_interval: Duration,
+}
+
+/// Runs the Project Manager, rebuilding and restarting it whenever the backend sources change.
+#[derive(Debug)]
+pub struct Watcher {
+    /// The Project Manager bundle. It is updated in place on each rebuild.
+    pub artifact: Artifact,
+    /// The number of restarts so far. Lets the clients know when they need to reconnect.
+    pub restarts: tokio::sync::watch::Receiver<usize>,
+    /// The task running the Project Manager. It finishes when the Project Manager exits on its own.
+    task:         tokio::task::JoinHandle<Result>,
+}
+
+impl AsRef<Artifact> for Watcher {
+    fn as_ref(&self) -> &Artifact {
+        &self.artifact
+    }
+}
+
+impl IsWatcher<Backend> for Watcher {
+    fn wait_for_finish(&mut self) -> BoxFuture<Result> {
+        async move { (&mut self.task).await? }.boxed()
+    }
+}
+
+impl IsWatchable for Backend {
+    type Watcher = Watcher;
+    type WatchInput = WatchInput;
+
+    fn watch(
+        &self,
+        context: Context,
+        job: WatchTargetJob<Self>,
+    ) -> BoxFuture<'static, Result<Self::Watcher>> {
+        let WatchTargetJob { build, watch_input: WatchInput { poll_interval } } = job;
+        let first_build = self.build(context.clone(), build.clone());
+        let this = *self;
+        async move {
+            // The watch starts before the first build, so the changes made meanwhile are not lost.
+            let sources = PollWatcher::new(&context.repo_root, SOURCE_PATHS, poll_interval)?;
+            let artifact = first_build.await?;
+            let project_manager = spawn_project_manager(&artifact)?;
+            let (restarts_sender, restarts) = tokio::sync::watch::channel(0);
+            let WithDestination { inner: BuildSource { input, .. }, .. } = build;
+            let task = tokio::spawn(keep_restarting(
+                this,
+                context,
+                input,
+                artifact.clone(),
+                project_manager,
+                sources,
+                restarts_sender,
+            ));
+            Ok(Watcher { artifact, restarts, task })
+        }
+        .boxed()
+    }
+}
+
+fn spawn_project_manager(artifact: &Artifact) -> Result<Child> {
+    crate::programs::project_manager::spawn_from(&artifact.path).spawn_intercepting()
+}
+
+/// Rebuild the Project Manager whenever the sources change, and restart it once the build
+/// succeeds. If the build fails, the previous Project Manager keeps running.
+async fn keep_restarting(
+    backend: Backend,
+    context: Context,
+    input: BuildInput,
+    artifact: Artifact,
+    mut project_manager: Child,
+    mut sources: PollWatcher,
+    restarts: tokio::sync::watch::Sender<usize>,
+) -> Result {
+    loop {
+        tokio::select! {
+            status = project_manager.wait() => {
+                let status = status?;
+                ensure!(status.success(), "The Project Manager exited with {status}.");
+                return Ok(());
+            }
+            changed = sources.changed() => changed?,
+        }
+        info!("The backend sources have changed, rebuilding the Project Manager.");
+        let staging = tempfile::tempdir()?;
+        let job = WithDestination::new(input.clone(), staging.path().to_owned());
+        if let Err(e) = backend.build_internal(context.clone(), job).await {
+            warn!("Failed to rebuild the Project Manager, the previous one keeps running: {e:?}");
+            continue;
+        }
+        crate::programs::project_manager::stop(&mut project_manager, SHUTDOWN_TIMEOUT).await?;
+        ide_ci::fs::mirror_directory(staging.path(), &artifact).await?;
+        project_manager = spawn_project_manager(&artifact)?;
+        restarts.send_modify(|count| *count += 1);
+        info!("The Project Manager has been restarted.");
+    }
+}
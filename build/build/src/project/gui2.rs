@@ -11,12 +11,37 @@ use crate::project::IsArtifact;
 use crate::project::IsTarget;
 use crate::source::WithDestination;
 
+use futures_util::future::try_join;
 use ide_ci::ok_ready_boxed;
 use ide_ci::programs::node::NpmCommand;
 use ide_ci::programs::Npm;
 
 
 
+// =================
+// === Constants ===
+// =================
+
+/// The directory of the GUI package, relative to the repository root.
+pub const GUI_PACKAGE_PATH: &str = "app/gui2";
+
+/// The directory of the dashboard package, relative to the repository root.
+pub const DASHBOARD_PACKAGE_PATH: &str = "app/ide-desktop/lib/dashboard";
+
+pub mod env {
+    use super::*;
+
+    use ide_ci::define_env_var;
+
+    define_env_var! {
+        /// A file modified each time the build script restarts the Project Manager. The dev server
+        /// reloads the GUI when it changes.
+        ENSO_BACKEND_RESTART_MARKER, PathBuf;
+    }
+}
+
+
+
 // ===============
 // === Scripts ===
 // ===============
@@ -95,6 +120,35 @@ pub fn watch(repo_root: impl AsRef<Path>) -> BoxFuture<'static, Result> {
     install_and_run_script(Scripts::Dev, repo_root)
 }
 
+/// Run the dev server, reloading the GUI whenever the Project Manager is restarted.
+///
+/// The `restarts` are usually provided by the [backend watcher](crate::project::backend::Watcher).
+pub fn watch_with_backend_restarts(
+    repo_root: impl AsRef<Path>,
+    mut restarts: tokio::sync::watch::Receiver<usize>,
+) -> BoxFuture<'static, Result> {
+    let repo_root = repo_root.as_ref().to_owned();
+    async move {
+        crate::web::install(&repo_root).await?;
+        let marker_dir = tempfile::tempdir()?;
+        let marker = marker_dir.path().join("backend-restarts");
+        ide_ci::fs::write(&marker, restarts.borrow().to_string())?;
+        let mut dev_server = script(&repo_root, Scripts::Dev)?;
+        dev_server.set_env(env::ENSO_BACKEND_RESTART_MARKER, &marker)?;
+        let notify_restarts = async move {
+            // The loop ends when the backend watcher is gone.
+            while restarts.changed().await.is_ok() {
+                let count = *restarts.borrow();
+                ide_ci::fs::write(&marker, count.to_string())?;
+            }
+            Result::Ok(())
+        };
+        try_join(dev_server.run_ok(), notify_restarts).await?;
+        Ok(())
+    }
+    .boxed()
+}
+
 fn install_and_run_script(
     script: Scripts,
     repo_root: impl AsRef<Path>,
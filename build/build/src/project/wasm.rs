@@ -2,6 +2,8 @@
 
 use crate::prelude::*;
 
+use crate::affected::Affected;
+use crate::affected::Crate;
 use crate::paths::generated::RepoRootDistWasm;
 use crate::project::Context;
 use crate::project::IsArtifact;
@@ -13,6 +15,7 @@ use crate::source::WithDestination;
 
 use derivative::Derivative;
 use ide_ci::cache;
+use ide_ci::cache::content_hash::ContentHasher;
 use ide_ci::fs::compressed_size;
 use ide_ci::fs::copy_file_if_different;
 use ide_ci::goodies::shader_tools::ShaderTools;
@@ -50,6 +53,38 @@ pub const WASM_ARTIFACT_NAME: &str = "gui_wasm";
 
 pub const DEFAULT_TARGET_CRATE: &str = "app/gui";
 
+/// Paths (relative to the repository root) of the sources the WASM artifact is built from,
+/// together with the built crate. They are hashed to tell if a cached artifact is up to date.
+pub const INPUT_PATHS: [&str; 7] = [
+    ".cargo",
+    "app/gui",
+    "lib/rust",
+    "Cargo.lock",
+    "Cargo.toml",
+    "package-lock.json",
+    "rust-toolchain.toml",
+];
+
+/// Environment variables inherited by the WASM build that affect the built artifact, like the
+/// compiler flags. They are hashed to tell if a cached artifact is up to date.
+pub const INPUT_ENV_VARS: [&str; 4] = [
+    cargo::RUSTFLAGS.name,
+    cargo::CARGO_ENCODED_RUSTFLAGS.name,
+    "CARGO_BUILD_RUSTFLAGS",
+    "ENSO_MAX_PROFILING_LEVEL",
+];
+
+/// Prefixes of the names of the environment variables that affect the built artifact, like the
+/// Cargo profile settings. See [`INPUT_ENV_VARS`].
+pub const INPUT_ENV_VAR_PREFIXES: [&str; 2] =
+    ["CARGO_PROFILE_", "CARGO_TARGET_WASM32_UNKNOWN_UNKNOWN_"];
+
+/// Check if the environment variable affects the built artifact, see [`INPUT_ENV_VARS`].
+pub fn is_input_env_var(name: &str) -> bool {
+    INPUT_ENV_VARS.contains(&name)
+        || INPUT_ENV_VAR_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+}
+
 #[derive(
     clap::ArgEnum,
     Clone,
@@ -202,12 +237,38 @@ impl IsTarget for Wasm {
         ready(Ok(Artifact::new(path.as_ref()))).boxed()
     }
 
+    fn inputs_hash(
+        &self,
+        context: &Context,
+        input: &Self::BuildInput,
+    ) -> Option<BoxFuture<'static, Result<String>>> {
+        let repo_root = context.repo_root.to_path_buf();
+        let build_flags = format!("{input:?}");
+        let crate_path = input.crate_path.clone();
+        let hash = async move {
+            let rust_version =
+                Cargo.cmd()?.current_dir(&repo_root).arg("--version").run_stdout().await?;
+            let wasm_pack_version = WasmPack.version_string().await?;
+            let mut hasher = ContentHasher::new();
+            hasher.add_value(&build_flags)?;
+            hasher.add_value(&rust_version)?;
+            hasher.add_value(&wasm_pack_version)?;
+            hasher.add_value(&BINARYEN_VERSION_TO_INSTALL)?;
+            hasher.add_env_vars(is_input_env_var);
+            for path in INPUT_PATHS.iter().map(Path::new).chain([crate_path.as_path()]) {
+                hasher.add_path(&repo_root, path)?;
+            }
+            Ok(hasher.finish())
+        };
+        Some(hash.boxed())
+    }
+
     fn build_internal(
         &self,
         context: Context,
         job: WithDestination<Self::BuildInput>,
     ) -> BoxFuture<'static, Result<Self::Artifact>> {
-        let Context { octocrab: _, cache, repo_root } = context;
+        let Context { cache, repo_root, .. } = context;
         let WithDestination { inner, destination } = job;
         let span = info_span!("Building WASM.",
             repo = %repo_root.display(),
@@ -486,7 +547,14 @@ impl Wasm {
             .await
     }
 
-    pub async fn test(&self, repo_root: PathBuf, wasm: &[test::Browser], native: bool) -> Result {
+    /// Run the unit tests of the crates in the `affected` set.
+    pub async fn test(
+        &self,
+        repo_root: PathBuf,
+        wasm: &[test::Browser],
+        native: bool,
+        affected: &Affected<Crate>,
+    ) -> Result {
         async fn maybe_run<Fut: Future<Output = Result>>(
             name: &str,
             enabled: bool,
@@ -501,12 +569,18 @@ impl Wasm {
             }
         }
 
+        let packages = match affected {
+            Affected::Everything => vec![cargo::Options::Workspace],
+            Affected::Only(crates) =>
+                crates.iter().map(|krate| cargo::Options::Package(krate.name.clone())).collect(),
+        };
+        let native = native && !packages.is_empty();
         maybe_run("native", native, async || {
             Cargo
                 .cmd()?
                 .current_dir(repo_root.clone())
                 .apply(&cargo::Command::Test)
-                .apply(&cargo::Options::Workspace)
+                .apply_iter(packages.iter().cloned())
                 // Color needs to be passed to tests themselves separately.
                 // See: https://github.com/rust-lang/cargo/issues/1983
                 .arg("--")
@@ -516,7 +590,8 @@ impl Wasm {
         })
         .await?;
 
-        maybe_run("wasm", !wasm.is_empty(), || test::test_all(repo_root.clone(), wasm)).await?;
+        maybe_run("wasm", !wasm.is_empty(), || test::test_all(repo_root.clone(), wasm, affected))
+            .await?;
         Ok(())
     }
 
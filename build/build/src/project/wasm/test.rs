@@ -1,5 +1,8 @@
 use crate::project::*;
 
+use crate::affected::Affected;
+use crate::affected::Crate;
+
 use clap::ArgEnum;
 use ide_ci::programs::cargo;
 use ide_ci::programs::wasm_pack;
@@ -135,8 +138,12 @@ pub fn get_proc_macro(cargo_toml: toml::Value) -> Option<bool> {
 // === Running tests ===
 // =====================
 
-/// Run wasm tests on all crates in the workspace.
-pub async fn test_all(repo_root: PathBuf, browsers: &[Browser]) -> Result {
+/// Run wasm tests on all crates in the workspace that are in the `affected` set.
+pub async fn test_all(
+    repo_root: PathBuf,
+    browsers: &[Browser],
+    affected: &Affected<Crate>,
+) -> Result {
     let browser_flags = browsers.iter().copied().map_into::<wasm_pack::TestFlags>().collect_vec();
     // FIXME args
     //let wasm_pack_args = std::env::args().skip(1).collect::<Vec<_>>();
@@ -144,7 +151,14 @@ pub async fn test_all(repo_root: PathBuf, browsers: &[Browser]) -> Result {
 
     for member in all_members {
         let member_str = member.to_string_lossy();
-        if blacklisted(&member) {
+        let is_affected = match affected {
+            Affected::Everything => true,
+            Affected::Only(crates) =>
+                crates.iter().any(|krate| repo_root.join(&krate.path) == member),
+        };
+        if !is_affected {
+            info!("Skipping unaffected crate {member_str}");
+        } else if blacklisted(&member) {
             info!("Skipping blacklisted crate {member_str}");
         } else if is_proc_macro_crate(&member) {
             info!("Skipping proc-macro crate {member_str}");
@@ -29,6 +29,14 @@ use serde_json::json;
 use tempfile::tempdir;
 
 
+// ==============
+// === Export ===
+// ==============
+
+pub mod notes;
+pub mod signing;
+
+
 
 /// Get the prefix of URL of the release's asset in GitHub.
 ///
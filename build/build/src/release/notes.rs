@@ -0,0 +1,255 @@
+//! Generating the release notes from the pull requests merged between two releases.
+//!
+//! The pull requests are found by the `(#1234)` suffix that GitHub appends to the titles of the
+//! squash-merged commits. Each pull request is put in the section of the changelog that links to
+//! it. The pull requests without a changelog entry are listed separately.
+
+use crate::prelude::*;
+
+use crate::context::BuildContext;
+
+use ide_ci::github::model::ComparedCommit;
+use regex::Regex;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The title of the section listing the pull requests not mentioned in the changelog.
+pub const OTHER_CHANGES_SECTION: &str = "Other Changes";
+
+/// The header that precedes the generated notes in the release body. Everything after it is
+/// replaced when the notes are regenerated.
+pub const RELEASE_BODY_HEADER: &str = "# Release Notes";
+
+lazy_static! {
+    /// Matches the number of the pull request at the end of the squash-merged commit title.
+    static ref PULL_REQUEST_SUFFIX: Regex = Regex::new(r"\s*\(#(\d+)\)$").unwrap();
+    /// Matches the references to pull requests in the changelog entries, like `[3552]`.
+    static ref CHANGELOG_REFERENCE: Regex = Regex::new(r"\]\[(\d+)\]").unwrap();
+}
+
+
+
+// =============
+// === Entry ===
+// =============
+
+/// A single change merged between the releases.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    /// The title of the pull request, or the subject line of the commit.
+    pub title:        String,
+    /// The number of the merged pull request, if the commit has been merged through one.
+    pub pull_request: Option<u64>,
+}
+
+impl Entry {
+    /// Describe the change based on the commit message.
+    pub fn from_commit_message(message: &str) -> Self {
+        let subject = message.lines().next().unwrap_or_default().trim();
+        let pull_request = PULL_REQUEST_SUFFIX
+            .captures(subject)
+            .and_then(|captures| captures.get(1))
+            .and_then(|number| number.as_str().parse().ok());
+        let title = PULL_REQUEST_SUFFIX.replace(subject, "").into_owned();
+        Self { title, pull_request }
+    }
+
+    /// Format the change as a Markdown list item, linking to the pull request.
+    pub fn to_markdown(&self, repo: &impl IsRepo) -> String {
+        let title = &self.title;
+        match self.pull_request {
+            Some(number) => {
+                let url = format!("https://github.com/{repo}/pull/{number}");
+                format!("- {title} ([#{number}]({url}))")
+            }
+            None => format!("- {title}"),
+        }
+    }
+}
+
+
+
+// ====================
+// === ReleaseNotes ===
+// ====================
+
+/// The changes merged between the releases, grouped in sections.
+#[derive(Clone, Debug, Default)]
+pub struct ReleaseNotes {
+    /// Sections in the order of their first appearance in the changelog, each with a non-empty
+    /// list of changes.
+    pub sections: Vec<(String, Vec<Entry>)>,
+}
+
+impl ReleaseNotes {
+    /// Group the changes by the changelog sections that mention their pull requests.
+    pub fn new(entries: impl IntoIterator<Item = Entry>, changelog: &str) -> Self {
+        let section_names = changelog_sections(changelog);
+        let mut sections = section_names.iter().map(|name| (name.clone(), vec![])).collect_vec();
+        let mut other_changes = vec![];
+        let pull_request_sections = pull_request_sections(changelog);
+        for entry in entries {
+            let section = entry.pull_request.and_then(|number| pull_request_sections.get(&number));
+            let section_entries = section.and_then(|section| {
+                sections.iter_mut().find(|(name, _)| name == section).map(|(_, entries)| entries)
+            });
+            section_entries.unwrap_or(&mut other_changes).push(entry);
+        }
+        sections.push((OTHER_CHANGES_SECTION.to_owned(), other_changes));
+        sections.retain(|(_, entries)| !entries.is_empty());
+        Self { sections }
+    }
+
+    /// Format the notes as Markdown, with a second-level header for each section.
+    pub fn to_markdown(&self, repo: &impl IsRepo) -> String {
+        let sections = self.sections.iter().map(|(name, entries)| {
+            let entries = entries.iter().map(|entry| entry.to_markdown(repo)).join("\n");
+            format!("## {name}\n\n{entries}\n")
+        });
+        sections.join("\n")
+    }
+}
+
+/// Names of the changelog sections, like `Visual Environment`, in the order of appearance.
+fn changelog_sections(changelog: &str) -> Vec<String> {
+    changelog.lines().filter_map(section_name).map(ToOwned::to_owned).unique().collect()
+}
+
+/// Map the numbers of the pull requests referenced by the changelog to the sections that reference
+/// them. The newest entries are at the top of the changelog, so the first reference wins.
+fn pull_request_sections(changelog: &str) -> HashMap<u64, String> {
+    let mut ret = HashMap::new();
+    let mut current_section = None;
+    for line in changelog.lines() {
+        if let Some(section) = section_name(line) {
+            current_section = Some(section);
+        } else if let Some(section) = current_section {
+            for captures in CHANGELOG_REFERENCE.captures_iter(line) {
+                if let Ok(number) = captures[1].parse() {
+                    ret.entry(number).or_insert_with(|| section.to_owned());
+                }
+            }
+        }
+    }
+    ret
+}
+
+fn section_name(line: &str) -> Option<&str> {
+    line.strip_prefix("#### ").map(str::trim)
+}
+
+
+
+// ==================
+// === Generating ===
+// ==================
+
+/// Generate the Markdown release notes for the changes between the `from` and `to` git
+/// references, e.g. release tags.
+#[context("Failed to generate the release notes for changes between {} and {}.", from, to)]
+pub async fn generate(context: &BuildContext, from: &str, to: &str) -> Result<String> {
+    let commits = context.remote_repo_handle().compare(from, to).await?;
+    debug!("Found {} commits between {from} and {to}.", commits.len());
+    let entries = commits.iter().map(|ComparedCommit { commit, .. }| {
+        Entry::from_commit_message(&commit.message)
+    });
+    let changelog = ide_ci::fs::read_to_string(&context.repo_root.changelog_md)?;
+    let notes = ReleaseNotes::new(entries, &changelog);
+    Ok(notes.to_markdown(&context.remote_repo))
+}
+
+/// Put the notes into the body of the release draft, replacing the previously generated ones.
+///
+/// Requires the release ID to be set in the environment variable `ENSO_RELEASE_ID`.
+pub async fn update_draft_body(context: &BuildContext, notes: &str) -> Result {
+    let release_id = crate::env::ENSO_RELEASE_ID.get()?;
+    let release_handle = context.remote_repo_handle().release_handle(release_id);
+    let release = release_handle.get().await?;
+    ensure!(release.draft, "Release {} has been already published.", release.tag_name);
+    let body = release.body.unwrap_or_default();
+    let body = replace_notes_in_body(&body, notes);
+    context
+        .remote_repo_handle()
+        .repos()
+        .releases()
+        .update(release_id.0)
+        .body(&body)
+        .send()
+        .await
+        .with_context(|| format!("Failed to update the body of the release {release_id}."))?;
+    Ok(())
+}
+
+fn replace_notes_in_body(body: &str, notes: &str) -> String {
+    let preserved = body.split(RELEASE_BODY_HEADER).next().unwrap_or_default().trim_end();
+    format!("{preserved}\n\n{RELEASE_BODY_HEADER}\n\n{notes}")
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHANGELOG: &str = r"# Next Release
+
+#### Visual Environment
+
+- [Camera is panned to newly created nodes.][3552]
+
+#### Enso Standard Library
+
+- [Added `Table.join`.][3600]
+
+[3552]: https://github.com/enso-org/enso/pull/3552
+[3600]: https://github.com/enso-org/enso/pull/3600
+
+# Enso 2.0.0-alpha.18 (2021-10-12)
+
+#### Visual Environment
+
+- [Old entry.][3600]
+";
+
+    #[test]
+    fn parsing_commit_messages() {
+        let entry = Entry::from_commit_message("Add table joins (#3600)\n\nLong description.");
+        assert_eq!(entry.title, "Add table joins");
+        assert_eq!(entry.pull_request, Some(3600));
+        let entry = Entry::from_commit_message("Direct push");
+        assert_eq!(entry.pull_request, None);
+    }
+
+    #[test]
+    fn grouping_by_changelog_sections() {
+        let entries = ["Pan camera (#3552)", "Add table joins (#3600)", "Bump deps (#3601)"]
+            .map(Entry::from_commit_message);
+        let notes = ReleaseNotes::new(entries, CHANGELOG);
+        let repo = ide_ci::github::Repo::new("enso-org", "enso");
+        let expected = "## Visual Environment
+
+- Pan camera ([#3552](https://github.com/enso-org/enso/pull/3552))
+
+## Enso Standard Library
+
+- Add table joins ([#3600](https://github.com/enso-org/enso/pull/3600))
+
+## Other Changes
+
+- Bump deps ([#3601](https://github.com/enso-org/enso/pull/3601))
+";
+        assert_eq!(notes.to_markdown(&repo), expected);
+    }
+
+    #[test]
+    fn replacing_notes_in_body() {
+        let body = replace_notes_in_body("# Download\n", "First");
+        assert_eq!(body, "# Download\n\n# Release Notes\n\nFirst");
+        let body = replace_notes_in_body(&body, "Second");
+        assert_eq!(body, "# Download\n\n# Release Notes\n\nSecond");
+    }
+}
@@ -0,0 +1,288 @@
+//! Signing and checksumming the assets before they are uploaded to the release.
+//!
+//! The signing is platform-specific and hidden behind the [`Signer`] trait. Every uploaded asset
+//! is listed in a [`Manifest`] together with its SHA-256 checksum, so the whole distribution can be
+//! verified, not only the installer image.
+
+use crate::prelude::*;
+
+use crate::ide::web::env::APPLEID;
+use crate::ide::web::env::APPLE_NOTARY_KEYCHAIN_PROFILE;
+use crate::ide::web::env::APPLEIDPASS;
+use crate::ide::web::env::APPLETEAMID;
+use crate::ide::web::env::WIN_CSC_KEY_PASSWORD;
+use crate::ide::web::env::WIN_CSC_LINK;
+
+use ide_ci::programs::Bash;
+use ide_ci::programs::PwSh;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The timestamp server used when signing the Windows executables.
+///
+/// Timestamping keeps the signature valid after the certificate expires.
+pub const DEFAULT_TIMESTAMP_URL: &str = "http://timestamp.digicert.com";
+
+
+
+// ================
+// === Programs ===
+// ================
+
+/// Runner of the Xcode developer tools, like `notarytool` and `stapler`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Xcrun;
+
+impl Program for Xcrun {
+    fn executable_name(&self) -> &str {
+        "xcrun"
+    }
+}
+
+
+
+// ==============
+// === Signer ===
+// ==============
+
+/// A way of signing the assets for the distribution.
+#[async_trait]
+pub trait Signer: Debug + Send + Sync {
+    /// Sign the asset in place.
+    async fn sign(&self, asset: &Path) -> Result;
+}
+
+/// Signs the Windows executables with a code signing certificate.
+///
+/// The certificate's password is never passed as a command line argument, as the command lines
+/// are logged. Instead, the PowerShell script loading the certificate reads it from the
+/// [`WIN_CSC_KEY_PASSWORD`] environment variable inherited from this process.
+#[derive(Clone, Debug)]
+pub struct WindowsSigner {
+    /// Path to the certificate file (`*.pfx` or `*.p12`).
+    pub certificate:   PathBuf,
+    /// The RFC 3161 timestamp server.
+    pub timestamp_url: String,
+}
+
+impl WindowsSigner {
+    /// The PowerShell script signing the asset. The failed signing is reported as an error, as
+    /// `Set-AuthenticodeSignature` alone reports it only in the returned status.
+    fn script(&self, asset: &Path) -> String {
+        let password = PwSh.access_environment_variable(WIN_CSC_KEY_PASSWORD.name());
+        let certificate_type = "System.Security.Cryptography.X509Certificates.X509Certificate2";
+        let certificate = powershell_literal(self.certificate.as_str());
+        let asset = powershell_literal(asset.as_str());
+        let timestamp_url = powershell_literal(&self.timestamp_url);
+        format!(
+            "$certificate = [{certificate_type}]::new({certificate}, {password}); \
+             $signature = Set-AuthenticodeSignature -FilePath {asset} -Certificate $certificate \
+             -HashAlgorithm SHA256 -TimestampServer {timestamp_url}; \
+             if ($signature.Status -ne 'Valid') {{ throw $signature.StatusMessage }}"
+        )
+    }
+}
+
+#[async_trait]
+impl Signer for WindowsSigner {
+    async fn sign(&self, asset: &Path) -> Result {
+        PwSh.run_command()?
+            .arg(self.script(asset))
+            .run_ok()
+            .await
+            .with_context(|| format!("Failed to sign {}.", asset.display()))
+    }
+}
+
+/// Quote the text as a PowerShell string literal, in which no variables are expanded.
+fn powershell_literal(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+/// The credentials used to authenticate to the Apple notary service.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NotaryCredentials {
+    /// The name of the keychain profile, previously created with
+    /// `xcrun notarytool store-credentials`.
+    KeychainProfile(String),
+    /// The Apple ID, the app-specific password and the Team ID, read by the shell from the
+    /// [`APPLEID`], [`APPLEIDPASS`] and [`APPLETEAMID`] environment variables inherited from this
+    /// process, so they never appear in the logged command line.
+    Environment,
+}
+
+impl NotaryCredentials {
+    /// The `notarytool` arguments authenticating the request, as shell words.
+    fn shell_args(&self) -> String {
+        match self {
+            Self::KeychainProfile(profile) =>
+                format!("--keychain-profile {}", shell_literal(profile)),
+            Self::Environment => [
+                ("--apple-id", APPLEID.name()),
+                ("--password", APPLEIDPASS.name()),
+                ("--team-id", APPLETEAMID.name()),
+            ]
+            .iter()
+            .map(|(option, variable)| format!("{option} \"${variable}\""))
+            .join(" "),
+        }
+    }
+}
+
+/// Quote the text as a POSIX shell word, in which nothing is expanded.
+fn shell_literal(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+/// Submits the macOS images for notarization and staples the issued ticket to them.
+///
+/// The image is expected to be already signed with a Developer ID certificate, as done by the
+/// Electron Builder.
+#[derive(Clone, Debug)]
+pub struct MacNotarizer {
+    /// The credentials of the Apple developer account.
+    pub credentials: NotaryCredentials,
+}
+
+#[async_trait]
+impl Signer for MacNotarizer {
+    async fn sign(&self, asset: &Path) -> Result {
+        let notarize = async {
+            // The asset path is passed as the script's first positional argument, so it needs no
+            // quoting.
+            let script = format!(
+                "xcrun notarytool submit \"$1\" {} --wait",
+                self.credentials.shell_args()
+            );
+            Bash.run_command()?.arg(script).arg("bash").arg(asset).run_ok().await?;
+            Xcrun.cmd()?.args(["stapler", "staple"]).arg(asset).run_ok().await
+        };
+        notarize.await.with_context(|| format!("Failed to notarize {}.", asset.display()))
+    }
+}
+
+/// Create the signer for the assets of the given platform, using the credentials from the same
+/// environment variables as the Electron Builder. On macOS, the keychain profile given in
+/// [`APPLE_NOTARY_KEYCHAIN_PROFILE`] is preferred, if set.
+///
+/// The secrets are only checked to be present here. They are read by the signing commands
+/// themselves, so they never appear in the logged command lines.
+///
+/// Returns `None` if the platform has no signing step.
+pub fn signer_from_env(os: OS) -> Result<Option<Box<dyn Signer>>> {
+    let signer: Box<dyn Signer> = match os {
+        OS::Windows => {
+            WIN_CSC_KEY_PASSWORD.get()?;
+            Box::new(WindowsSigner {
+                certificate:   WIN_CSC_LINK.get()?.into(),
+                timestamp_url: DEFAULT_TIMESTAMP_URL.into(),
+            })
+        }
+        OS::MacOS => {
+            let credentials = if APPLE_NOTARY_KEYCHAIN_PROFILE.is_set() {
+                NotaryCredentials::KeychainProfile(APPLE_NOTARY_KEYCHAIN_PROFILE.get()?)
+            } else {
+                for variable in [&APPLEID, &APPLEIDPASS, &APPLETEAMID] {
+                    variable.get()?;
+                }
+                NotaryCredentials::Environment
+            };
+            Box::new(MacNotarizer { credentials })
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(signer))
+}
+
+
+
+// ================
+// === Manifest ===
+// ================
+
+/// Description of a single asset in the [`Manifest`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// The name of the asset in the release.
+    pub name:   String,
+    /// The size of the asset in bytes.
+    pub size:   u64,
+    /// The SHA-256 checksum of the asset, as lowercase hex digits.
+    pub sha256: String,
+}
+
+/// The list of the uploaded assets with their checksums. Uploaded as a JSON release asset.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub assets: Vec<ManifestEntry>,
+}
+
+impl Manifest {
+    /// Checksum the file and add it to the manifest under the given asset name.
+    pub async fn add_file(&mut self, path: impl AsRef<Path>, name: impl Into<String>) -> Result {
+        let path = path.as_ref();
+        let file = ide_ci::fs::tokio::open(path).await?;
+        let size = file.metadata().await?.len();
+        let sha256 = ide_ci::io::sha256(file).await?;
+        self.assets.push(ManifestEntry { name: name.into(), size, sha256 });
+        Ok(())
+    }
+}
+
+/// Write the SHA-256 checksum of the `file` to the `checksum_file`, in the same format as the
+/// checksums computed by the IDE build.
+///
+/// Signing modifies the file, so its checksum must be recomputed afterwards.
+pub async fn write_checksum(file: impl AsRef<Path>, checksum_file: impl AsRef<Path>) -> Result {
+    let checksum = ide_ci::io::sha256(ide_ci::fs::tokio::open(file).await?).await?;
+    ide_ci::fs::tokio::write(checksum_file, checksum).await
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn checksumming_assets() -> Result {
+        let dir = tempfile::tempdir()?;
+        let image = dir.path().join("enso.AppImage");
+        let checksum_file = image.with_extension("sha256");
+        ide_ci::fs::write(&image, "abc")?;
+        write_checksum(&image, &checksum_file).await?;
+        let expected = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+        assert_eq!(ide_ci::fs::read_to_string(&checksum_file)?, expected);
+
+        let mut manifest = Manifest::default();
+        manifest.add_file(&image, "ide-enso.AppImage").await?;
+        let entry =
+            ManifestEntry { name: "ide-enso.AppImage".into(), size: 3, sha256: expected.into() };
+        assert_eq!(manifest.assets, vec![entry]);
+        Ok(())
+    }
+
+    #[test]
+    fn secrets_are_not_passed_as_arguments() {
+        WIN_CSC_KEY_PASSWORD.set_raw("windows-secret");
+        APPLEIDPASS.set_raw("apple-secret");
+        let signer = WindowsSigner {
+            certificate:   "C:\\certificates\\enso's.pfx".into(),
+            timestamp_url: DEFAULT_TIMESTAMP_URL.into(),
+        };
+        let script = signer.script(Path::new("enso.exe"));
+        assert!(!script.contains("windows-secret"));
+        assert!(script.contains("$env:WIN_CSC_KEY_PASSWORD"));
+        assert!(script.contains("'C:\\certificates\\enso''s.pfx'"));
+
+        let args = NotaryCredentials::Environment.shell_args();
+        assert!(!args.contains("apple-secret"));
+        assert!(args.contains("--password \"$APPLEIDPASS\""));
+        let profile = NotaryCredentials::KeychainProfile("it's".into());
+        assert_eq!(profile.shell_args(), r"--keychain-profile 'it'\''s'");
+    }
+}
@@ -13,6 +13,7 @@ use std::hash::Hasher;
 pub mod archive;
 pub mod artifact;
 pub mod asset;
+pub mod content_hash;
 pub mod download;
 pub mod goodie;
 
@@ -128,13 +129,32 @@ impl Cache {
         &self.root
     }
 
+    /// Use the cache at the given path. The directory is created when the first entry is stored,
+    /// so that merely inspecting the cache does not leave an empty directory behind.
     pub async fn new(path: impl Into<PathBuf>) -> Result<Self> {
         let root = path.into();
-        crate::fs::tokio::create_dir_if_missing(&root).await?;
         debug!("Prepared cache in {}", root.display());
         Ok(Self { root })
     }
 
+    /// Find the entries that have no index file, usually because their generation was interrupted.
+    ///
+    /// Such entries are regenerated when requested, but until then they only waste disk space.
+    pub fn incomplete_entries(&self) -> Result<Vec<PathBuf>> {
+        let mut ret = vec![];
+        if !self.root.exists() {
+            return Ok(ret);
+        }
+        for entry in crate::fs::read_dir(&self.root)? {
+            let path = entry?.path();
+            if path.is_dir() && !path.with_appended_extension("json").exists() {
+                ret.push(path);
+            }
+        }
+        ret.sort();
+        Ok(ret)
+    }
+
     pub fn get<S>(&self, storable: S) -> BoxFuture<'static, Result<S::Output>>
     where S: Storable {
         let this = self.clone();
@@ -196,4 +216,24 @@ mod tests {
         cache.get(download_task).await?;
         Ok(())
     }
+
+    #[tokio::test]
+    async fn finding_incomplete_entries() -> Result {
+        let root = tempfile::tempdir()?;
+        let cache = Cache::new(root.path()).await?;
+        crate::fs::create_dir_if_missing(root.path().join("complete"))?;
+        crate::fs::write(root.path().join("complete.json"), "{}")?;
+        crate::fs::create_dir_if_missing(root.path().join("interrupted"))?;
+        assert_eq!(cache.incomplete_entries()?, vec![root.path().join("interrupted")]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn creating_cache_directory_lazily() -> Result {
+        let root = tempfile::tempdir()?;
+        let cache = Cache::new(root.path().join("cache")).await?;
+        assert!(!cache.path().exists());
+        assert_eq!(cache.incomplete_entries()?, Vec::<PathBuf>::new());
+        Ok(())
+    }
 }
@@ -0,0 +1,142 @@
+//! Hashing the contents of build inputs, to tell if an earlier build output is still up to date.
+
+use crate::prelude::*;
+
+use sha2::Digest;
+
+
+
+/// Names of the directories that contain only build outputs or external dependencies. They are
+/// skipped when hashing directory trees.
+pub const IGNORED_DIRECTORIES: [&str; 4] = ["node_modules", "target", "dist", ".git"];
+
+/// Incrementally computed hash of build inputs: files, directory trees and arbitrary values, like
+/// build flags or program versions.
+///
+/// Files are identified by their paths relative to the given root, so the same sources placed in
+/// different locations have the same hash.
+#[derive(Clone, Debug, Default)]
+pub struct ContentHasher {
+    digest: sha2::Sha224,
+}
+
+impl ContentHasher {
+    pub fn new() -> Self {
+        default()
+    }
+
+    /// Add a serializable value to the hash.
+    pub fn add_value(&mut self, value: &impl Serialize) -> Result {
+        let serialized = bincode::serialize(value)?;
+        self.add_bytes(&serialized);
+        Ok(())
+    }
+
+    /// Add the file or the directory tree at the `path` relative to the `root`. Missing paths are
+    /// skipped.
+    pub fn add_path(&mut self, root: impl AsRef<Path>, path: impl AsRef<Path>) -> Result {
+        let root = root.as_ref();
+        let full_path = root.join(path);
+        if !full_path.exists() {
+            trace!("Skipping missing input {}.", full_path.display());
+            return Ok(());
+        }
+        let entries = walkdir::WalkDir::new(&full_path)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| !is_ignored_directory(entry));
+        for entry in entries {
+            let entry = entry?;
+            if entry.file_type().is_file() {
+                let relative_path = entry.path().strip_prefix(root)?;
+                self.add_bytes(relative_path.as_str().as_bytes());
+                self.add_bytes(&crate::fs::read(entry.path())?);
+            }
+        }
+        Ok(())
+    }
+
+    /// Add the set environment variables whose names match the predicate, like the flags passed
+    /// to the compiler. Both the names and the values are hashed, so setting or unsetting a
+    /// matching variable changes the hash.
+    pub fn add_env_vars(&mut self, matches: impl Fn(&str) -> bool) {
+        let mut vars = std::env::vars_os()
+            .filter(|(name, _)| name.to_str().map_or(false, &matches))
+            .collect_vec();
+        vars.sort();
+        for (name, value) in vars {
+            self.add_bytes(name.to_string_lossy().as_bytes());
+            self.add_bytes(value.to_string_lossy().as_bytes());
+        }
+    }
+
+    /// The hash of everything added so far.
+    pub fn finish(self) -> String {
+        data_encoding::BASE64URL_NOPAD.encode(&self.digest.finalize())
+    }
+
+    fn add_bytes(&mut self, bytes: &[u8]) {
+        // The length prefix keeps the consecutive parts apart, so that e.g. adding "ab" and "c"
+        // gives a different hash than adding "a" and "bc".
+        self.digest.update((bytes.len() as u64).to_le_bytes());
+        self.digest.update(bytes);
+    }
+}
+
+/// Check if the entry is a directory that should be skipped, see [`IGNORED_DIRECTORIES`].
+pub fn is_ignored_directory(entry: &walkdir::DirEntry) -> bool {
+    let is_ignored_name =
+        entry.file_name().to_str().map_or(false, |name| IGNORED_DIRECTORIES.contains(&name));
+    entry.file_type().is_dir() && is_ignored_name
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash_of(root: &Path) -> Result<String> {
+        let mut hasher = ContentHasher::new();
+        hasher.add_value(&"flags")?;
+        hasher.add_path(root, "src")?;
+        hasher.add_path(root, "missing")?;
+        Ok(hasher.finish())
+    }
+
+    #[test]
+    fn hashing_directory_trees() -> Result {
+        let first = tempfile::tempdir()?;
+        let second = tempfile::tempdir()?;
+        for root in [first.path(), second.path()] {
+            crate::fs::write(root.join_iter(["src", "lib.rs"]), "fn main() {}")?;
+            crate::fs::write(root.join_iter(["src", "a", "mod.rs"]), "")?;
+        }
+        assert_eq!(hash_of(first.path())?, hash_of(second.path())?);
+
+        crate::fs::write(second.path().join_iter(["src", "target", "output"]), "built")?;
+        assert_eq!(hash_of(first.path())?, hash_of(second.path())?);
+
+        crate::fs::write(second.path().join_iter(["src", "lib.rs"]), "fn main() { }")?;
+        assert_ne!(hash_of(first.path())?, hash_of(second.path())?);
+        Ok(())
+    }
+
+    #[test]
+    fn hashing_environment_variables() {
+        const NAME: &str = "CONTENT_HASH_TEST_FLAGS";
+        let hash_of_env = || {
+            let mut hasher = ContentHasher::new();
+            hasher.add_env_vars(|name| name == NAME);
+            hasher.finish()
+        };
+        let unset = hash_of_env();
+        std::env::set_var(NAME, "-O");
+        let set = hash_of_env();
+        std::env::set_var(NAME, "-g");
+        let changed = hash_of_env();
+        std::env::remove_var(NAME);
+        assert_ne!(unset, set);
+        assert_ne!(set, changed);
+        assert_eq!(unset, hash_of_env());
+    }
+}
@@ -14,6 +14,7 @@ use fs_extra::error::ErrorKind;
 // ==============
 
 pub mod tokio;
+pub mod watch;
 pub mod wrappers;
 
 pub use enso_build_base::fs::*;
@@ -0,0 +1,109 @@
+//! Watching source trees for changes.
+//!
+//! The watcher periodically scans the watched paths, so it needs no platform-specific file
+//! notification support. Only the file metadata is read, so scanning even large trees is cheap.
+
+use crate::prelude::*;
+
+use crate::cache::content_hash::is_ignored_directory;
+use crate::cache::content_hash::ContentHasher;
+
+use std::time::Duration;
+
+
+
+/// Watches files and directory trees under a common root, reporting when any of them changes.
+///
+/// Directories with build outputs or external dependencies are skipped, see
+/// [`IGNORED_DIRECTORIES`](crate::cache::content_hash::IGNORED_DIRECTORIES).
+#[derive(Clone, Debug)]
+pub struct PollWatcher {
+    root:        PathBuf,
+    paths:       Vec<PathBuf>,
+    interval:    Duration,
+    fingerprint: String,
+}
+
+impl PollWatcher {
+    /// Start watching the `paths` relative to the `root`, scanning them every `interval`.
+    ///
+    /// Only changes made after this call are reported.
+    pub fn new(
+        root: impl Into<PathBuf>,
+        paths: impl IntoIterator<Item = impl Into<PathBuf>>,
+        interval: Duration,
+    ) -> Result<Self> {
+        let root = root.into();
+        let paths = paths.into_iter().map(Into::into).collect_vec();
+        let fingerprint = fingerprint(&root, &paths)?;
+        Ok(Self { root, paths, interval, fingerprint })
+    }
+
+    /// Wait until any of the watched files is added, removed or modified.
+    pub async fn changed(&mut self) -> Result {
+        loop {
+            tokio::time::sleep(self.interval).await;
+            let root = self.root.clone();
+            let paths = self.paths.clone();
+            let fingerprint =
+                tokio::task::spawn_blocking(move || fingerprint(&root, &paths)).await??;
+            if fingerprint != self.fingerprint {
+                self.fingerprint = fingerprint;
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Hash the names, sizes and modification times of all files under the `paths`.
+fn fingerprint(root: &Path, paths: &[PathBuf]) -> Result<String> {
+    let mut hasher = ContentHasher::new();
+    for path in paths {
+        let full_path = root.join(path);
+        let entries = walkdir::WalkDir::new(&full_path)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| !is_ignored_directory(entry));
+        for entry in entries {
+            let entry = match entry {
+                Ok(entry) => entry,
+                // Files can be removed while scanning, and the watched paths can be missing.
+                Err(e) if is_not_found(&e) => continue,
+                Err(e) => return Err(e.into()),
+            };
+            if entry.file_type().is_file() {
+                let metadata = entry.metadata()?;
+                let modified = metadata.modified()?.duration_since(std::time::UNIX_EPOCH)?;
+                let relative_path = entry.path().strip_prefix(root)?;
+                hasher.add_value(&(relative_path, metadata.len(), modified))?;
+            }
+        }
+    }
+    Ok(hasher.finish())
+}
+
+fn is_not_found(error: &walkdir::Error) -> bool {
+    error.io_error().map_or(false, |e| e.kind() == std::io::ErrorKind::NotFound)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn detecting_changes() -> Result {
+        let root = tempfile::tempdir()?;
+        crate::fs::write(root.path().join_iter(["src", "lib.rs"]), "fn main() {}")?;
+        let interval = Duration::from_millis(10);
+        let mut watcher = PollWatcher::new(root.path(), ["src", "missing"], interval)?;
+
+        crate::fs::write(root.path().join_iter(["src", "target", "output"]), "built")?;
+        let timeout = Duration::from_millis(200);
+        assert!(tokio::time::timeout(timeout, watcher.changed()).await.is_err());
+
+        crate::fs::write(root.path().join_iter(["src", "new.rs"]), "")?;
+        tokio::time::timeout(timeout, watcher.changed()).await??;
+        Ok(())
+    }
+}
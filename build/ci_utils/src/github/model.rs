@@ -50,3 +50,26 @@ impl AsRef<str> for RegistrationToken {
         &self.token
     }
 }
+
+/// Result of comparing two commits, as returned by the compare endpoint.
+///
+/// See: <https://docs.github.com/en/rest/commits/commits#compare-two-commits>
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Comparison {
+    /// Commits reachable from the head but not from the base, oldest first.
+    pub commits:       Vec<ComparedCommit>,
+    pub total_commits: usize,
+}
+
+/// A commit listed in the [`Comparison`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ComparedCommit {
+    pub sha:    String,
+    pub commit: CommitDetails,
+}
+
+/// Git-level information about the [`ComparedCommit`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CommitDetails {
+    pub message: String,
+}
@@ -414,6 +414,28 @@ impl<R: IsRepo> Handle<R> {
         crate::github::workflow::dispatch(self, workflow_id, default_branch, inputs).await
     }
 
+    /// List the commits reachable from the `head` but not from the `base`, oldest first.
+    ///
+    /// Both `base` and `head` can be any git references known to GitHub, e.g. tags or SHAs.
+    pub async fn compare(&self, base: &str, head: &str) -> Result<Vec<model::ComparedCommit>> {
+        let path = format!("/repos/{}/{}/compare/{base}...{head}", self.owner(), self.name());
+        let mut commits = Vec::new();
+        for page in 1.. {
+            let query = [("per_page", MAX_PER_PAGE.to_string()), ("page", page.to_string())];
+            let comparison: model::Comparison =
+                self.octocrab.get(&path, Some(&query)).await.with_context(|| {
+                    format!("Failed to compare {base} with {head} in the {self} repository.")
+                })?;
+            let is_last_page = comparison.commits.is_empty()
+                || commits.len() + comparison.commits.len() >= comparison.total_commits;
+            commits.extend(comparison.commits);
+            if is_last_page {
+                break;
+            }
+        }
+        Ok(commits)
+    }
+
     /// Get a handle for dealing with a release with a given id.
     pub fn release_handle(&self, id: ReleaseId) -> crate::github::release::Handle {
         let repo = Repo::new(self.owner(), self.name());
@@ -4,7 +4,9 @@ use crate::io::web::filename_from_response;
 use crate::io::web::stream_response_to_file;
 
 use reqwest::IntoUrl;
+use sha2::Digest;
 use tokio::io::AsyncRead;
+use tokio::io::AsyncReadExt;
 use web::client;
 
 
@@ -24,6 +26,20 @@ pub async fn read_length(mut read: impl AsyncRead + Unpin) -> Result<u64> {
     tokio::io::copy(&mut read, &mut sink).anyhow_err().await
 }
 
+/// Read the whole input and return its SHA-256 digest, formatted as lowercase hex digits.
+pub async fn sha256(mut read: impl AsyncRead + Unpin) -> Result<String> {
+    let mut hasher = sha2::Sha256::new();
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let read_count = read.read(&mut buffer).await?;
+        if read_count == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read_count]);
+    }
+    Ok(data_encoding::HEXLOWER.encode(&hasher.finalize()))
+}
+
 /// Get the the response body as a byte stream.
 pub async fn download(url: impl IntoUrl) -> Result<impl Stream<Item = reqwest::Result<Bytes>>> {
     client::download(&default(), url).await
@@ -93,6 +109,13 @@ mod tests {
     use crate::fs::mirror_directory;
     use tempfile::tempdir;
 
+    #[tokio::test]
+    async fn sha256_digest() -> Result {
+        let digest = sha256(b"abc".as_slice()).await?;
+        assert_eq!(digest, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        Ok(())
+    }
+
     #[tokio::test]
     #[ignore]
     async fn test_download() -> Result {
@@ -144,6 +144,9 @@ pub enum Target {
     JavaGen(java_gen::Target),
     /// Check if the changelog has been updated. Requires CI environment.
     ChangelogCheck,
+    /// Diagnose the development environment: required programs, Node.js packages, Rust toolchain,
+    /// free disk space and the cache. Fails if any problem prevents the builds from succeeding.
+    Doctor,
 }
 
 /// Build, test and package Enso Engine.
@@ -159,6 +162,11 @@ pub struct Cli {
     #[clap(long, global = true, maybe_default_os = default_cache_path(), enso_env())]
     pub cache_path: PathBuf,
 
+    /// Always build the targets, instead of reusing the cached artifacts of earlier local builds
+    /// with unchanged inputs.
+    #[clap(long, global = true, enso_env())]
+    pub no_artifact_cache: bool,
+
     /// The GitHub repository with the project. This is mainly used to manage releases (checking
     /// released versions to generate a new one, or uploading release assets).
     /// The argument should follow the format `owner/repo_name`.
@@ -291,3 +299,15 @@ pub struct WatchJob<Target: IsWatchableSource> {
     #[clap(flatten)]
     pub watch_input: Target::WatchInput,
 }
+
+/// Options for running only the tests affected by the current changes.
+#[derive(Args, Clone, Debug, PartialEq, Eq)]
+pub struct AffectedOnly {
+    /// Run only the tests of the packages affected by the changes against the `affected-base`
+    /// revision. If the build infrastructure was changed, all tests are run.
+    #[clap(long, enso_env())]
+    pub affected_only: bool,
+    /// The git revision against which the changes are computed for `affected-only`.
+    #[clap(long, enso_env(), default_value = enso_build::affected::DEFAULT_BASE)]
+    pub affected_base: String,
+}
@@ -1,8 +1,10 @@
 use enso_build::prelude::*;
 
+use crate::arg::AffectedOnly;
 use crate::arg::ArgExt;
 use crate::arg::Source;
 use crate::source_args_hlp;
+use crate::IsWatchableSource;
 
 use clap::Args;
 use clap::Subcommand;
@@ -19,6 +21,17 @@ pub struct BuildInput {
 
 source_args_hlp!(Backend, "backend", BuildInput);
 
+impl IsWatchableSource for Backend {
+    type WatchInput = WatchInput;
+}
+
+#[derive(Args, Clone, Debug, PartialEq)]
+pub struct WatchInput {
+    /// How often the backend sources are checked for changes. Supports formats like "2s".
+    #[clap(long, default_value = "2s", enso_env())]
+    pub backend_poll_interval: humantime::Duration,
+}
+
 #[derive(Subcommand, Clone, Debug, PartialEq)]
 pub enum Command {
     /// Build the backend from local sources.
@@ -45,7 +58,9 @@ pub enum Command {
     /// Run the tests.
     Test {
         #[clap(arg_enum, required = true)]
-        which: Vec<enso_build::engine::Tests>,
+        which:    Vec<enso_build::engine::Tests>,
+        #[clap(flatten)]
+        affected: AffectedOnly,
     },
     /// Run an SBT command.
     Sbt {
@@ -1,11 +1,14 @@
 use enso_build::prelude::*;
 
+use crate::arg::AffectedOnly;
 use crate::arg::BuildJob;
 use crate::arg::Source;
+use crate::arg::WatchJob;
 use crate::source_args_hlp;
 
 use clap::Args;
 use clap::Subcommand;
+use enso_build::project::backend::Backend;
 use enso_build::project::gui2::Gui2;
 
 
@@ -22,11 +25,21 @@ pub enum Command {
     /// Gets the GUI, either by compiling it from scratch or downloading from an external source.
     Get(Source<Gui2>),
     /// Runs the GUI's unit tests.
-    Test,
+    Test {
+        #[clap(flatten)]
+        affected: AffectedOnly,
+    },
     /// Run linter on the GUI's sources.
     Lint,
     /// Continuously rebuilds GUI when its sources are changed and serves it using dev-server.
-    Watch,
+    Watch {
+        /// Also build and run the Project Manager. It is rebuilt and restarted whenever the
+        /// backend sources change, and the served GUI is reloaded.
+        #[clap(long, enso_env())]
+        with_backend: bool,
+        #[clap(flatten)]
+        backend:      WatchJob<Backend>,
+    },
 }
 
 #[derive(Args, Clone, Debug)]
@@ -44,9 +44,13 @@ pub enum Command {
     },
     Upload {
         #[clap(flatten)]
-        params:     BuildInput<Gui>,
+        params:      BuildInput<Gui>,
         #[clap(long, env = *enso_build::env::ENSO_RELEASE_ID)]
-        release_id: ReleaseId,
+        release_id:  ReleaseId,
+        /// Sign the image before uploading it, using the same credentials as the Electron Builder.
+        /// On Windows the image is signed with `signtool`, on macOS it is notarized.
+        #[clap(long, enso_env())]
+        sign_assets: bool,
     },
     /// Like `Build` but automatically starts the IDE.
     Start {
@@ -57,6 +61,22 @@ pub enum Command {
         #[clap(long, allow_hyphen_values = true, enso_env())]
         ide_option: Vec<String>,
     },
+    /// Like `Build` but then launches the IDE with profiling enabled, creating a new project. The
+    /// startup performance is summarized in a JSON report.
+    ProfileStartup {
+        #[clap(flatten)]
+        params:         BuildInput<Gui>,
+        /// Where the captured profile of the IDE startup is saved.
+        #[clap(long, default_value = "startup-profile.json", enso_env())]
+        profile_path:   PathBuf,
+        /// Where the JSON report is written.
+        #[clap(long, default_value = "startup-report.json", enso_env())]
+        report_path:    PathBuf,
+        /// Fail if the cold start of the IDE takes longer. Supports formats like "30secs" or
+        /// "1min".
+        #[clap(long, enso_env())]
+        max_cold_start: Option<humantime::Duration>,
+    },
     Watch {
         #[clap(flatten)]
         gui:             WatchJob<Gui>,
@@ -25,9 +25,13 @@ pub enum Command {
     /// This command is intended for CI-use only.
     Upload {
         #[clap(flatten)]
-        params:     BuildInput,
+        params:      BuildInput,
         #[clap(long, env = *enso_build::env::ENSO_RELEASE_ID)]
-        release_id: ReleaseId,
+        release_id:  ReleaseId,
+        /// Sign the image before uploading it, using the same credentials as the Electron Builder.
+        /// On Windows the image is signed with `signtool`, on macOS it is notarized.
+        #[clap(long, enso_env())]
+        sign_assets: bool,
     },
 }
 
@@ -22,6 +22,23 @@ pub struct Promote {
     pub designation: enso_build::version::promote::Designation,
 }
 
+/// Structure that represents `notes` subcommand arguments.
+#[derive(Args, Clone, Debug)]
+pub struct Notes {
+    /// The tag of the previous release. Changes merged before it are not included.
+    #[clap(long, enso_env())]
+    pub from_tag:     String,
+    /// The tag (or any other git reference) of the release the notes are generated for.
+    #[clap(long, enso_env())]
+    pub to_tag:       String,
+    /// Write the notes to the given file instead of the standard output.
+    #[clap(long, enso_env())]
+    pub write_to:     Option<PathBuf>,
+    /// Put the notes into the body of the release draft identified by `ENSO_RELEASE_ID`.
+    #[clap(long, enso_env())]
+    pub update_draft: bool,
+}
+
 #[derive(Subcommand, Clone, Debug)]
 pub enum Action {
     /// Create a release draft on GitHub.
@@ -32,6 +49,8 @@ pub enum Action {
     DeployGui(DeployGui),
     Publish,
     Promote(Promote),
+    /// Generate the release notes from the pull requests merged between two releases.
+    Notes(Notes),
 }
 
 #[derive(Args, Clone, Debug)]
@@ -1,5 +1,6 @@
 use enso_build::prelude::*;
 
+use crate::arg::AffectedOnly;
 use crate::arg::ArgExt;
 use crate::arg::Source;
 use crate::arg::WatchJob;
@@ -119,6 +120,8 @@ pub enum Command {
         /// More than one browser can be specified.
         #[clap(long, enso_env(), arg_enum, default_values_t = [Browser::Firefox])]
         browser:   Vec<Browser>,
+        #[clap(flatten)]
+        affected:  AffectedOnly,
     },
 }
 
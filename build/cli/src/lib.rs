@@ -45,6 +45,9 @@ use anyhow::Context;
 use arg::BuildDescription;
 use clap::Parser;
 use derivative::Derivative;
+use enso_build::affected;
+use enso_build::affected::Affected;
+use enso_build::affected::Changes;
 use enso_build::config::Config;
 use enso_build::context::BuildContext;
 use enso_build::engine::context::EnginePackageProvider;
@@ -69,6 +72,7 @@ use enso_build::project::IsArtifact;
 use enso_build::project::IsTarget;
 use enso_build::project::IsWatchable;
 use enso_build::project::IsWatcher;
+use enso_build::release::signing;
 use enso_build::source::BuildSource;
 use enso_build::source::BuildTargetJob;
 use enso_build::source::CiRunSource;
@@ -105,6 +109,24 @@ fn resolve_artifact_name(input: Option<String>, project: &impl IsTarget) -> Stri
     input.unwrap_or_else(|| project.artifact_name())
 }
 
+/// Compute the changes against the base revision if only the affected tests should be run.
+/// Returns `None` if all tests should be run, including when the build infrastructure was changed.
+async fn affected_changes(
+    repo_root: &Path,
+    options: &arg::AffectedOnly,
+) -> Result<Option<Changes>> {
+    if !options.affected_only {
+        return Ok(None);
+    }
+    let changes = Changes::compute(repo_root, &options.affected_base).await?;
+    if changes.touches_infrastructure() {
+        info!("The build infrastructure was changed, running all tests.");
+        Ok(None)
+    } else {
+        Ok(Some(changes))
+    }
+}
+
 define_env_var! {
     ENSO_BUILD_KIND, enso_build::version::Kind;
 }
@@ -139,6 +161,7 @@ impl Processor {
         let context = BuildContext {
             inner: project::Context {
                 cache: Cache::new(&cli.cache_path).await?,
+                use_artifact_cache: !cli.no_artifact_cache,
                 octocrab,
                 repo_root: enso_build::paths::new_repo_root(absolute_repo_path, &triple),
             },
@@ -328,11 +351,18 @@ impl Processor {
             arg::wasm::Command::Watch(job) => self.watch_and_wait(job),
             arg::wasm::Command::Build(job) => self.build(job).void_ok().boxed(),
             arg::wasm::Command::Check => Wasm.check().boxed(),
-            arg::wasm::Command::Test { no_wasm, no_native, browser } => {
+            arg::wasm::Command::Test { no_wasm, no_native, browser, affected } => {
                 let wasm_browsers =
                     if no_wasm { default() } else { browser.into_iter().map_into().collect_vec() };
                 let root = self.repo_root.to_path_buf();
-                async move { Wasm.test(root, &wasm_browsers, !no_native).await }.boxed()
+                async move {
+                    let crates = match affected_changes(&root, &affected).await? {
+                        Some(changes) => affected::affected_crates(&root, &changes).await?,
+                        None => Affected::Everything,
+                    };
+                    Wasm.test(root, &wasm_browsers, !no_native, &crates).await
+                }
+                .boxed()
             }
             arg::wasm::Command::Get(source) => self.get(source).void_ok().boxed(),
         }
@@ -350,11 +380,44 @@ impl Processor {
         match gui.command {
             arg::gui2::Command::Build(job) => self.build(job),
             arg::gui2::Command::Get(source) => self.get(source).void_ok().boxed(),
-            arg::gui2::Command::Test =>
-                try_join(gui2::tests(&self.repo_root), gui2::dashboard_tests(&self.repo_root))
-                    .void_ok()
-                    .boxed(),
-            arg::gui2::Command::Watch => gui2::watch(&self.repo_root),
+            arg::gui2::Command::Test { affected } => {
+                let root = self.repo_root.to_path_buf();
+                async move {
+                    let changes = affected_changes(&root, &affected).await?;
+                    let is_affected = |package: &str| match &changes {
+                        Some(changes) => affected::is_npm_package_affected(&root, package, changes),
+                        None => Ok(true),
+                    };
+                    let gui_affected = is_affected(gui2::GUI_PACKAGE_PATH)?;
+                    let dashboard_affected = is_affected(gui2::DASHBOARD_PACKAGE_PATH)?;
+                    let skip = |name: &str| {
+                        info!("Skipping {name} tests, as they are not affected.");
+                        ok_ready_boxed(())
+                    };
+                    let gui_tests = if gui_affected { gui2::tests(&root) } else { skip("GUI") };
+                    let dashboard_tests = if dashboard_affected {
+                        gui2::dashboard_tests(&root)
+                    } else {
+                        skip("dashboard")
+                    };
+                    try_join(gui_tests, dashboard_tests).void_ok().await
+                }
+                .boxed()
+            }
+            arg::gui2::Command::Watch { with_backend, backend } => {
+                if !with_backend {
+                    return gui2::watch(&self.repo_root);
+                }
+                let backend_watcher = self.watch(backend);
+                let repo_root = self.repo_root.to_path_buf();
+                async move {
+                    let mut backend_watcher = backend_watcher.await?;
+                    let restarts = backend_watcher.restarts.clone();
+                    let gui = gui2::watch_with_backend_restarts(&repo_root, restarts);
+                    try_join(gui, backend_watcher.wait_for_finish()).void_ok().await
+                }
+                .boxed()
+            }
             arg::gui2::Command::Lint => gui2::lint(&self.repo_root),
         }
     }
@@ -410,17 +473,29 @@ impl Processor {
                 }
                 .boxed()
             }
-            arg::backend::Command::Test { which } => {
-                let mut config = enso_build::engine::BuildConfigurationFlags::default();
-                for arg in which {
-                    match arg {
-                        Tests::Scala => config.test_scala = true,
-                        Tests::StandardLibrary => config.test_standard_library = true,
+            arg::backend::Command::Test { which, affected } => {
+                let root = self.repo_root.to_path_buf();
+                let this = self.clone();
+                async move {
+                    let changes = affected_changes(&root, &affected).await?;
+                    let mut config = enso_build::engine::BuildConfigurationFlags::default();
+                    for arg in which {
+                        let is_affected = changes.as_ref().map_or(true, |changes| {
+                            arg.source_roots().iter().any(|path| changes.touches(path))
+                        });
+                        if !is_affected {
+                            info!("Skipping {arg} tests, as they are not affected.");
+                            continue;
+                        }
+                        match arg {
+                            Tests::Scala => config.test_scala = true,
+                            Tests::StandardLibrary => config.test_standard_library = true,
+                        }
                     }
+                    config.test_java_generated_from_rust = true;
+                    this.prepare_backend_context(config).await?.build().void_ok().await
                 }
-                config.test_java_generated_from_rust = true;
-                let context = self.prepare_backend_context(config);
-                async move { context.await?.build().void_ok().await }.boxed()
+                .boxed()
             }
             arg::backend::Command::Sbt { command } => {
                 let context = self.prepare_backend_context(default());
@@ -487,6 +562,7 @@ impl Processor {
         let paths = enso_build::paths::Paths::new_triple(&self.repo_root, self.triple.clone());
         let config = config.into();
         let octocrab = self.octocrab.clone();
+        let use_artifact_cache = self.use_artifact_cache;
         async move {
             let paths = paths?;
             let inner = crate::project::Context {
@@ -494,6 +570,7 @@ impl Processor {
                 // upload_artifacts: true,
                 octocrab,
                 cache: Cache::new_default().await?,
+                use_artifact_cache,
             };
             Ok(enso_build::engine::RunContext { inner, config, paths, external_runtime: None })
         }
@@ -506,11 +583,15 @@ impl Processor {
     }
 
     /// Upload IDE assets from the build job to the given release.
+    ///
+    /// Besides the image and its checksum, a manifest listing the checksums of all the uploaded
+    /// assets is uploaded. If `sign_assets` is set, the image is signed first.
     pub fn upload_ide_assets(
         &self,
         build_job: BoxFuture<'static, Result<ide::Artifact>>,
         release_id: ReleaseId,
         name_prefix: Option<String>,
+        sign_assets: bool,
     ) -> BoxFuture<'static, Result> {
         let release = self.release(release_id);
         let add_prefix = move |name: String| {
@@ -522,12 +603,23 @@ impl Processor {
         };
         async move {
             let artifacts = build_job.await?;
-            release
-                .upload_asset_file_with_custom_name(&artifacts.image, add_prefix.clone())
-                .await?;
-            release
-                .upload_asset_file_with_custom_name(&artifacts.image_checksum, add_prefix)
-                .await?;
+            if sign_assets {
+                if let Some(signer) = signing::signer_from_env(TARGET_OS)? {
+                    signer.sign(&artifacts.image).await?;
+                    signing::write_checksum(&artifacts.image, &artifacts.image_checksum).await?;
+                } else {
+                    warn!("There is no signing step for {TARGET_OS}, assets will not be signed.");
+                }
+            }
+            let mut manifest = signing::Manifest::default();
+            for path in [&artifacts.image, &artifacts.image_checksum] {
+                let asset =
+                    release.upload_asset_file_with_custom_name(path, add_prefix.clone()).await?;
+                manifest.add_file(path, asset.name).await?;
+            }
+            let manifest_path = artifacts.image.with_extension("manifest.json");
+            manifest_path.write_as_json(&manifest)?;
+            release.upload_asset_file_with_custom_name(&manifest_path, add_prefix).await?;
             Ok(())
         }
         .boxed()
@@ -536,9 +628,9 @@ impl Processor {
     pub fn handle_ide(&self, ide: arg::ide::Target) -> BoxFuture<'static, Result> {
         match ide.command {
             arg::ide::Command::Build { params } => self.build_old_ide(params).void_ok().boxed(),
-            arg::ide::Command::Upload { params, release_id } => {
+            arg::ide::Command::Upload { params, release_id, sign_assets } => {
                 let build_job = self.build_old_ide(params);
-                self.upload_ide_assets(build_job, release_id, None)
+                self.upload_ide_assets(build_job, release_id, None, sign_assets)
             }
             arg::ide::Command::Start { params, ide_option } => {
                 let build_job = self.build_old_ide(params);
@@ -549,6 +641,27 @@ impl Processor {
                 }
                 .boxed()
             }
+            arg::ide::Command::ProfileStartup {
+                params,
+                profile_path,
+                report_path,
+                max_cold_start,
+            } => {
+                let build_job = self.build_old_ide(params);
+                let repo_root = self.repo_root.to_path_buf();
+                async move {
+                    let ide = build_job.await?;
+                    let report =
+                        enso_build::ide::startup::profile_startup(&repo_root, &ide, &profile_path)
+                            .await?;
+                    report.write(&report_path).await?;
+                    if let Some(max_cold_start) = max_cold_start {
+                        report.check_cold_start(max_cold_start.into())?;
+                    }
+                    Ok(())
+                }
+                .boxed()
+            }
             arg::ide::Command::Watch { gui, project_manager, ide_option: ide_watch } => {
                 let context = self.context();
                 let watch_gui_job = self.resolve_watch_job(gui);
@@ -602,9 +715,10 @@ impl Processor {
     pub fn handle_ide2(&self, ide: arg::ide2::Target) -> BoxFuture<'static, Result> {
         match ide.command {
             arg::ide2::Command::Build { params } => self.build_new_ide(params).void_ok().boxed(),
-            arg::ide2::Command::Upload { params, release_id } => {
+            arg::ide2::Command::Upload { params, release_id, sign_assets } => {
                 let build_job = self.build_new_ide(params);
-                self.upload_ide_assets(build_job, release_id, Some("ide2".into()))
+                let prefix = Some("ide2".into());
+                self.upload_ide_assets(build_job, release_id, prefix, sign_assets)
             }
         }
     }
@@ -829,6 +943,15 @@ impl WatchResolvable for Wasm {
     }
 }
 
+impl WatchResolvable for Backend {
+    fn resolve_watch(
+        _ctx: &Processor,
+        from: <Self as IsWatchableSource>::WatchInput,
+    ) -> Result<<Self as IsWatchable>::WatchInput> {
+        Ok(backend::WatchInput { poll_interval: from.backend_poll_interval.into() })
+    }
+}
+
 impl WatchResolvable for Gui {
     fn resolve_watch(
         ctx: &Processor,
@@ -862,6 +985,13 @@ pub async fn main_internal(config: Option<Config>) -> Result {
         enso_build::web::assume_installed();
     }
 
+    if let Target::Doctor = cli.target {
+        let report = enso_build::doctor::diagnose(&config, &cli.repo_path, &cli.cache_path).await;
+        println!("{report}");
+        ensure!(!report.has_blockers(), "The environment has problems that block the builds.");
+        return Ok(());
+    }
+
     if !cli.skip_version_check {
         // Let's be helpful!
         let error_message = "Program requirements were not fulfilled. Please do one of the \
@@ -977,6 +1107,17 @@ pub async fn main_internal(config: Option<Config>) -> Result {
                 let crate::arg::release::Promote { designation } = args;
                 enso_build::release::promote_release(&ctx, designation).await?;
             }
+            Action::Notes(args) => {
+                let crate::arg::release::Notes { from_tag, to_tag, write_to, update_draft } = args;
+                let notes = enso_build::release::notes::generate(&ctx, &from_tag, &to_tag).await?;
+                match write_to {
+                    Some(path) => ide_ci::fs::write(path, &notes)?,
+                    None => println!("{notes}"),
+                }
+                if update_draft {
+                    enso_build::release::notes::update_draft_body(&ctx, &notes).await?;
+                }
+            }
         },
         Target::JavaGen(command) => {
             let repo_root = ctx.repo_root.clone();
@@ -998,6 +1139,7 @@ pub async fn main_internal(config: Option<Config>) -> Result {
             let ci_context = ide_ci::actions::context::Context::from_env()?;
             enso_build::changelog::check::check(ctx.repo_root.clone(), ci_context).await?;
         }
+        Target::Doctor => unreachable!("The environment diagnosis is handled before the build."),
     };
     info!("Completed main job.");
     global::complete_tasks().await?;
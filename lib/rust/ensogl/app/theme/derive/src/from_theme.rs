@@ -48,12 +48,16 @@ fn build_frp(
     }
 
     quote! {
+        // The style watch owns the callbacks updating the fields, so it is kept alive by the
+        // network. Otherwise, the style would stop updating when the caller drops the watch.
+        let style_watch = ::std::clone::Clone::clone(style);
         __ensogl_core::frp::extend! { network
             layout_update_init_debounced <- any_(...);
             layout_update_needed <- any_(...);
             layout_update_needed <+ layout_update_init_debounced.debounce();
             #frp_content
             layout_sampler <- layout_update_needed.map(move |()| {
+                let _style_watch = &style_watch;
                 #struct_name {
                     #struct_init
                 }
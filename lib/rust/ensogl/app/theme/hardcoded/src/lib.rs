@@ -122,16 +122,25 @@ macro_rules! _define_theme_wrapper_and_literals {
             use ensogl_core::data::color::Rgba;
             use ensogl_core::display::style::theme;
 
-            /// Registers the theme in the application.
-            pub fn register(theme_manager:impl AsRef<theme::Manager>) {
+            /// The name the theme is registered with.
+            pub const NAME: &str = stringify!($name);
+
+            /// Create a new instance of the theme definition. It can be used as a base for custom
+            /// themes, registered with [`Application::register_theme`].
+            pub fn definition() -> theme::Theme {
                 let $name = theme::Theme::new();
                 _define_theme_literals!{$id $name [] $($t)*}
-                theme_manager.as_ref().register(stringify!($name),$name);
+                $name
+            }
+
+            /// Registers the theme in the application.
+            pub fn register(theme_manager:impl AsRef<theme::Manager>) {
+                theme_manager.as_ref().register(NAME,definition());
             }
 
             /// Enables the current theme.
             pub fn enable(theme_manager:impl AsRef<theme::Manager>) {
-                theme_manager.as_ref().set_enabled(&[stringify!($name)]);
+                theme_manager.as_ref().set_enabled(&[NAME]);
             }
         }
     };
@@ -191,6 +200,69 @@ define_themes! { [light:0, dark:1]
             show_delay_duration_ms = 500.0, 500.0;
             hide_delay_duration_ms = 0.0, 0.0;
         }
+        focus_ring {
+            color = Rgba(0.306, 0.522, 0.953, 0.8), Rgba(0.451, 0.647, 1.0, 0.8);
+            width = 2.0, 2.0;
+            padding = 3.0, 3.0;
+            corner_radius = 8.0, 8.0;
+        }
+        command_palette {
+            background = Rgba(1.0, 1.0, 1.0, 1.0), Rgba(0.18, 0.188, 0.2, 1.0);
+            query = Rgba(0.0, 0.0, 0.0, 0.7), Rgba(1.0, 1.0, 1.0, 0.7);
+            shortcut = Rgba(0.0, 0.0, 0.0, 0.4), Rgba(1.0, 1.0, 1.0, 0.4);
+        }
+
+        toast {
+            background = Rgba(1.0, 1.0, 1.0, 1.0), Rgba(0.18, 0.188, 0.2, 1.0);
+            text = Rgba(0.0, 0.0, 0.0, 0.7), Rgba(1.0, 1.0, 1.0, 0.7);
+            action = Rgba(0.176, 0.349, 0.749, 1.0), Rgba(0.435, 0.604, 1.0, 1.0);
+            corner_radius = 8.0, 8.0;
+            severity {
+                info = Rgba(0.176, 0.349, 0.749, 1.0), Rgba(0.435, 0.604, 1.0, 1.0);
+                warning = Rgba(0.898, 0.592, 0.0, 1.0), Rgba(1.0, 0.757, 0.027, 1.0);
+                error = Rgba(0.898, 0.2, 0.2, 1.0), Rgba(1.0, 0.412, 0.412, 1.0);
+            }
+        }
+
+        dialog {
+            backdrop = Rgba(0.0, 0.0, 0.0, 0.3), Rgba(0.0, 0.0, 0.0, 0.5);
+            background = Rgba(1.0, 1.0, 1.0, 1.0), Rgba(0.18, 0.188, 0.2, 1.0);
+            title = Rgba(0.0, 0.0, 0.0, 0.85), Rgba(1.0, 1.0, 1.0, 0.85);
+            corner_radius = 12.0, 12.0;
+            button {
+                background = Rgba(0.0, 0.0, 0.0, 0.06), Rgba(1.0, 1.0, 1.0, 0.08);
+                hover_background = Rgba(0.0, 0.0, 0.0, 0.12), Rgba(1.0, 1.0, 1.0, 0.16);
+                text = Rgba(0.0, 0.0, 0.0, 0.7), Rgba(1.0, 1.0, 1.0, 0.7);
+                primary_background = Rgba(0.176, 0.349, 0.749, 1.0), Rgba(0.435, 0.604, 1.0, 1.0);
+                primary_hover_background = Rgba(0.137, 0.29, 0.647, 1.0), Rgba(0.541, 0.682, 1.0, 1.0);
+                primary_text = Rgba(1.0, 1.0, 1.0, 1.0), Rgba(1.0, 1.0, 1.0, 1.0);
+                selection = Rgba(0.176, 0.349, 0.749, 0.5), Rgba(0.435, 0.604, 1.0, 0.5);
+            }
+        }
+
+        split_pane {
+            divider = Rgba(0.0, 0.0, 0.0, 0.1), Rgba(1.0, 1.0, 1.0, 0.1);
+            divider_hover = Rgba(0.176, 0.349, 0.749, 0.6), Rgba(0.435, 0.604, 1.0, 0.6);
+        }
+
+        docking {
+            background = Rgba(1.0, 1.0, 1.0, 1.0), Rgba(0.18, 0.188, 0.2, 1.0);
+            header = Rgba(0.949, 0.949, 0.949, 1.0), Rgba(0.141, 0.149, 0.161, 1.0);
+            tab = Rgba(0.0, 0.0, 0.0, 0.0), Rgba(0.0, 0.0, 0.0, 0.0);
+            active_tab = Rgba(1.0, 1.0, 1.0, 1.0), Rgba(0.18, 0.188, 0.2, 1.0);
+            text = Rgba(0.0, 0.0, 0.0, 0.7), Rgba(1.0, 1.0, 1.0, 0.7);
+            drop_indicator = Rgba(0.176, 0.349, 0.749, 0.2), Rgba(0.435, 0.604, 1.0, 0.2);
+        }
+
+        search_everywhere {
+            background = Rgba(1.0, 1.0, 1.0, 1.0), Rgba(0.18, 0.188, 0.2, 1.0);
+            query = Rgba(0.0, 0.0, 0.0, 0.7), Rgba(1.0, 1.0, 1.0, 0.7);
+        }
+
+        performance_hud {
+            background = Rgba(0.0, 0.0, 0.0, 0.7), Rgba(0.0, 0.0, 0.0, 0.7);
+            text = Rgba(1.0, 1.0, 1.0, 0.9), Rgba(1.0, 1.0, 1.0, 0.9);
+        }
 
         top_bar {
             padding_left = 19.0, 19.0;
@@ -400,6 +472,15 @@ define_themes! { [light:0, dark:1]
                             offset_x = 0.0, 0.0;
                             offset_y = -2.0, -2.0;
                         }
+                        placeholder {
+                            height = 10.0, 10.0;
+                            corners_radius = 5.0, 5.0;
+                            color = Rgba(1.0, 1.0, 1.0, 0.16), Rgba(1.0, 1.0, 1.0, 0.16);
+                            shimmer_color = Rgba(1.0, 1.0, 1.0, 0.4), Rgba(1.0, 1.0, 1.0, 0.4);
+                            shimmer_width = 20.0, 20.0;
+                            speed = 0.08, 0.08; // px/ms
+                            offset_y = -2.0, -2.0;
+                        }
                         entry {
                             margin = 1.0, 1.0;
                             hover_color = Rgba(0.0, 0.0, 0.0, 0.0), Rgba(0.0, 0.0, 0.0, 0.0);
@@ -409,6 +490,8 @@ define_themes! { [light:0, dark:1]
                             text_size = 11.5, 11.5;
                             icon_x_offset = 2.0, 2.0;
                             icon_y_offset = 6.0, 6.0;
+                            icon_size = 17.0, 17.0;
+                            icon_color = Rgba(1.0, 1.0, 1.0, 0.7), Rgba(1.0, 1.0, 1.0, 0.7);
                             highlight_corners_radius = 15.0, 15.0;
                         }
                     }
@@ -541,6 +624,14 @@ define_themes! { [light:0, dark:1]
             to_the_left_of_node  = 25.0  , 25.0;
             to_the_right_of_node = 25.0  , 25.0;
         }
+        focus_mode {
+            // Opacity of nodes and edges not related to the selected pipeline.
+            dimmed_alpha = 0.25;
+        }
+        error_propagation {
+            // Opacity of nodes and edges using a value poisoned by an upstream error.
+            poisoned_alpha = 0.5;
+        }
         screen_margin_when_panning_camera_to_node {
             top = 40.0, 40.0;
             bottom = 80.0, 80.0;
@@ -552,10 +643,19 @@ define_themes! { [light:0, dark:1]
             port_color_tint    = Rgba(1.0,1.0,1.0,0.15), Rgba(1.0,1.0,1.0,0.15);
             text               = Lcha(0.09,0.0,0.0,1.0), Lcha(1.0,0.0,0.0,0.7);
             corner_radius = 14.0, 14.0;
+            rejection          = Rgba(0.7,0.235,0.08,1.0), Rgba(0.7,0.235,0.08,1.0);
+            breakpoint         = Rgba(0.859,0.196,0.184,1.0), Rgba(0.859,0.196,0.184,1.0);
+            computing          = Rgba(0.306,0.647,0.992,0.8), Rgba(0.306,0.647,0.992,0.8);
             selection {
                 size = 20.0 , 20.0;
                 opacity = 0.2 , 0.2;
                 hover_opacity = 0.1 , 0.1;
+                preview_select = Rgba(0.306, 0.647, 0.992, 0.8), Rgba(0.306, 0.647, 0.992, 0.8);
+                preview_deselect = Rgba(0.5, 0.5, 0.5, 0.8), Rgba(0.7, 0.7, 0.7, 0.8);
+            }
+            edge_emphasis {
+                outline = Rgba(0.306, 0.647, 0.992, 0.35), Rgba(0.306, 0.647, 0.992, 0.35);
+                port = Rgba(0.306, 0.647, 0.992, 0.8), Rgba(0.306, 0.647, 0.992, 0.8);
             }
             actions {
                 context_switch {
@@ -580,6 +680,44 @@ define_themes! { [light:0, dark:1]
             pending {
                 alpha_factor = 0.5;
             }
+            color_tag {
+                tint_alpha   = 0.5;
+                todo         = Rgba(0.93,0.55,0.12,1.0);
+                verified     = Rgba(0.27,0.67,0.33,1.0);
+                experimental = Rgba(0.55,0.35,0.85,1.0);
+                none_border  = Rgba(1.0,1.0,1.0,0.8);
+            }
+            execution_count {
+                background = Rgba(0.0,0.0,0.0,0.6), Rgba(1.0,1.0,1.0,0.6);
+                text       = Rgba(1.0,1.0,1.0,1.0), Rgba(0.0,0.0,0.0,1.0);
+            }
+            warnings {
+                background         = Rgba(1.0,0.655,0.141,1.0), Rgba(1.0,0.655,0.141,1.0);
+                text               = Rgba(0.0,0.0,0.0,0.8), Rgba(0.0,0.0,0.0,0.8);
+                listing_background = Rgba(0.0,0.0,0.0,0.75), Rgba(1.0,1.0,1.0,0.85);
+                listing_text       = Rgba(1.0,1.0,1.0,1.0), Rgba(0.0,0.0,0.0,1.0);
+            }
+            stale {
+                dash = Rgba(0.0,0.0,0.0,0.35), Rgba(1.0,1.0,1.0,0.35);
+            }
+            edge_stubs {
+                line       = Rgba(0.0,0.0,0.0,0.3), Rgba(1.0,1.0,1.0,0.3);
+                background = Rgba(0.0,0.0,0.0,0.45), Rgba(1.0,1.0,1.0,0.45);
+                text       = Rgba(1.0,1.0,1.0,1.0), Rgba(0.0,0.0,0.0,1.0);
+            }
+            edit_conflict {
+                background = Rgba(0.87,0.27,0.25,1.0), Rgba(0.87,0.27,0.25,1.0);
+                text       = Rgba(1.0,1.0,1.0,1.0), Rgba(1.0,1.0,1.0,1.0);
+            }
+            value_preview {
+                background = Rgba(0.0,0.0,0.0,0.08), Rgba(1.0,1.0,1.0,0.08);
+                text       = Rgba(0.0,0.0,0.0,0.7), Rgba(1.0,1.0,1.0,0.7);
+            }
+            description {
+                background        = Rgba(0.0,0.0,0.0,0.08), Rgba(1.0,1.0,1.0,0.08);
+                button_background = Rgba(0.0,0.0,0.0,0.08), Rgba(1.0,1.0,1.0,0.08);
+                text              = Rgba(0.0,0.0,0.0,0.7), Rgba(1.0,1.0,1.0,0.7);
+            }
             type_label {
                 offset_y = -23.0, -23.0;
             }
@@ -607,6 +745,11 @@ define_themes! { [light:0, dark:1]
                 icon       = Lcha(0.0,0.0,0.0,0.7) , Lcha(1.0,0.0,0.0,0.7);
                 text       = Lcha(0.0,0.0,0.0,0.7) , Lcha(1.0,0.0,0.0,0.7);
             }
+            fullscreen_toolbar {
+                background        = Rgba(0.929,0.941,0.953,0.95) , Lcha(0.2,0.0,0.0,0.95);
+                button_background = Lcha(0.0,0.0,0.0,0.06) , Lcha(1.0,0.0,0.0,0.1);
+                text              = Lcha(0.0,0.0,0.0,0.7) , Lcha(1.0,0.0,0.0,0.7);
+            }
             selection {
                 color = Rgba(0.306,0.647,0.992,0.14) , Rgba(0.137,0.16,0.184,1.0);
                 width = 7.0, 7.0;
@@ -621,8 +764,21 @@ define_themes! { [light:0, dark:1]
 
             }
         }
+        portal {
+            lightness = 0.6;
+            chroma    = 0.6;
+            text      = Rgba(1.0,1.0,1.0,1.0), Rgba(1.0,1.0,1.0,1.0);
+        }
+        remote_presence {
+            text = Rgba(1.0,1.0,1.0,1.0), Rgba(1.0,1.0,1.0,1.0);
+        }
         edge {
             disabled_color = Lcha(0.95,0.0,0.0,1.0), Lcha(0.95,0.0,0.0,1.0);
+            label = Lcha(0.0,0.0,0.0,0.6) , Lcha(1.0,0.0,0.0,0.6);
+            type_check {
+                compatible   = Rgba(0.357,0.702,0.373,1.0), Rgba(0.357,0.702,0.373,1.0);
+                incompatible = Rgba(0.872,0.267,0.255,1.0), Rgba(0.872,0.267,0.255,1.0);
+            }
             split {
                 lightness_factor = 1.2 , 0.2;
                 chroma_factor    = 0.8 , 1.0;
@@ -643,6 +799,54 @@ define_themes! { [light:0, dark:1]
                 color = Rgba(0.0, 0.451, 0.859, 1.0), Rgba(0.0, 0.451, 0.859, 1.0);
             }
         }
+        watch_panel {
+            width = 320.0, 320.0;
+            margin = 14.0, 14.0;
+        }
+        grid {
+            dot_color  = Lcha(0.0,0.0,0.0,0.12) , Lcha(1.0,0.0,0.0,0.12);
+            dot_radius = 1.5, 1.5;
+        }
+        canvas_background {
+            region_label = Lcha(0.0,0.0,0.0,0.5) , Lcha(1.0,0.0,0.0,0.5);
+        }
+        file_drop {
+            highlight_background = Rgba(0.0, 0.451, 0.859, 0.08), Rgba(0.0, 0.451, 0.859, 0.16);
+            highlight_border = Rgba(0.0, 0.451, 0.859, 0.6), Rgba(0.0, 0.451, 0.859, 0.6);
+        }
+        tab_bar {
+            top_offset = 48.0, 48.0;
+            margin = 14.0, 14.0;
+            background = Rgba(0.949, 0.949, 0.949, 1.0), Rgba(0.141, 0.149, 0.161, 1.0);
+            selected_background = Rgba(1.0, 1.0, 1.0, 1.0), Rgba(0.18, 0.188, 0.2, 1.0);
+            text = Rgba(0.0, 0.0, 0.0, 0.7), Rgba(1.0, 1.0, 1.0, 0.7);
+        }
+        collapse_preview {
+            background = Rgba(1.0, 1.0, 1.0, 1.0), Rgba(0.18, 0.188, 0.2, 1.0);
+            field_background = Rgba(0.949, 0.949, 0.949, 1.0), Rgba(0.141, 0.149, 0.161, 1.0);
+            button_background = Rgba(0.0, 0.0, 0.0, 0.08), Rgba(1.0, 1.0, 1.0, 0.08);
+            header = Rgba(0.0, 0.0, 0.0, 0.4), Rgba(1.0, 1.0, 1.0, 0.4);
+            text = Rgba(0.0, 0.0, 0.0, 0.7), Rgba(1.0, 1.0, 1.0, 0.7);
+            invalid_text = Rgba(0.82, 0.2, 0.2, 1.0), Rgba(0.95, 0.4, 0.4, 1.0);
+        }
+        lasso {
+            line = Rgba(0.306, 0.647, 0.992, 0.9), Rgba(0.306, 0.647, 0.992, 0.9);
+            closing_line = Rgba(0.306, 0.647, 0.992, 0.4), Rgba(0.306, 0.647, 0.992, 0.4);
+        }
+        selection_count {
+            background = Rgba(0.306, 0.647, 0.992, 1.0), Rgba(0.306, 0.647, 0.992, 1.0);
+            text = Rgba(1.0, 1.0, 1.0, 1.0), Rgba(1.0, 1.0, 1.0, 1.0);
+        }
+        hover_card {
+            background = Rgba(1.0, 1.0, 1.0, 0.95), Rgba(0.18, 0.188, 0.2, 0.95);
+            text = Rgba(0.0, 0.0, 0.0, 0.7), Rgba(1.0, 1.0, 1.0, 0.7);
+        }
+        node_peek {
+            background = Rgba(1.0, 1.0, 1.0, 0.95), Rgba(0.18, 0.188, 0.2, 0.95);
+            node = Rgba(0.0, 0.0, 0.0, 0.08), Rgba(1.0, 1.0, 1.0, 0.08);
+            text = Rgba(0.0, 0.0, 0.0, 0.7), Rgba(1.0, 1.0, 1.0, 0.7);
+            connection = Rgba(0.0, 0.0, 0.0, 0.3), Rgba(1.0, 1.0, 1.0, 0.3);
+        }
         execution_environment_selector {
             background = Rgb::from_base_255(100.0, 181.0, 38.0), Rgb::from_base_255(100.0, 181.0, 38.0);
             divider = Rgba::black_with_alpha(0.12), Rgba::black_with_alpha(0.12);
@@ -661,6 +865,9 @@ define_themes! { [light:0, dark:1]
                     color = Lch(0.8, 0.0, 0.0), Lch(0.8, 0.0, 0.0);
                     speed = 0.003, 0.003; // Radians/ms
                 }
+                progress {
+                    track = Rgba::white_with_alpha(0.25), Rgba::white_with_alpha(0.25);
+                }
             }
         }
     }
@@ -683,6 +890,7 @@ define_themes! { [light:0, dark:1]
             text = Lcha(0.0,0.0,0.0,0.7), Lcha(1.0,0.0,0.0,0.7);
             text {
                 selection = Lcha(0.7,0.0,0.125,0.7);
+                disabled  = Lcha(0.0,0.0,0.0,0.3), Lcha(1.0,0.0,0.0,0.3);
                 font      = DEFAULT_CODE_FONT;
                 size      = 12.0;
                 highlight_bold = 0.02;
@@ -694,6 +902,29 @@ define_themes! { [light:0, dark:1]
                 height = 24.0;
                 corner_radius = 12.0;
             }
+            stripe = Rgba(0.0,0.0,0.0,0.03), Rgba(1.0,1.0,1.0,0.03);
+            divider = Rgba(0.0,0.0,0.0,0.06), Rgba(1.0,1.0,1.0,0.06);
+            divider {
+                width = 1.0;
+            }
+            spinner = Lcha(0.0,0.0,0.0,0.33), Lcha(1.0,0.0,0.0,0.33);
+            checkbox = Rgba(0.0,0.45,0.9,1.0), Rgba(0.3,0.6,1.0,1.0);
+            checkbox {
+                off = Rgba(0.0,0.0,0.0,0.4), Rgba(1.0,1.0,1.0,0.4);
+                mark = Rgba(1.0,1.0,1.0,1.0), Rgba(1.0,1.0,1.0,1.0);
+                width = 14.0;
+                height = 14.0;
+                gap = 8.0;
+            }
+            toggle = Rgba(0.0,0.45,0.9,1.0), Rgba(0.3,0.6,1.0,1.0);
+            toggle {
+                off = Rgba(0.0,0.0,0.0,0.2), Rgba(1.0,1.0,1.0,0.2);
+                mark = Rgba(1.0,1.0,1.0,1.0), Rgba(1.0,1.0,1.0,1.0);
+                width = 24.0;
+                height = 14.0;
+                gap = 8.0;
+            }
+            scrollbar = Rgba(0.0,0.0,0.0,0.3), Rgba(1.0,1.0,1.0,0.3);
             padding = 5.0;
         }
         blank {
@@ -814,12 +1045,14 @@ define_themes! { [light:0, dark:1]
 // ==========================
 
 /// Default theme registration. The theme is registered and enabled in a before-main entry point in
-/// order for it to be visible when shaders are being gathered during compilation phase. This makes
-/// themes not switchable at runtime, which we might want to fix somehow in the future.
+/// order for it to be visible when shaders are being gathered during compilation phase. The other
+/// builtin themes are registered too, so they can be enabled at runtime with the `set_theme` input
+/// of the application.
 #[before_main(2)]
 pub fn enable_default_theme() {
     let themes = ensogl_core::display::world::with_context(|t| t.theme_manager.clone());
     builtin::light::register(&themes);
+    builtin::dark::register(&themes);
     builtin::light::enable(&themes);
     themes.update();
 }
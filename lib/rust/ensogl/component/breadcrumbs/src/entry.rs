@@ -7,6 +7,7 @@ use crate::Icon;
 
 use ensogl_core::application::command::FrpNetworkProvider;
 use ensogl_core::application::frp::API;
+use ensogl_core::application::tooltip;
 use ensogl_core::application::Application;
 use ensogl_core::data::color;
 use ensogl_core::display;
@@ -89,14 +90,52 @@ pub mod ellipsis {
     }
 }
 
+/// A rounded bar with a highlight sweeping over it. Displayed in place of a breadcrumb which name
+/// is not known yet.
+pub mod placeholder {
+    use super::*;
+
+    pub const WIDTH: f32 = 60.0;
+
+    ensogl_core::shape! {
+        above = [ensogl_grid_view::entry::shape];
+        pointer_events = false;
+        alignment = center;
+        (style: Style) {
+            let width = Var::<Pixels>::from("input_size.x");
+            let height = style.get_number(theme::placeholder::height);
+            let corners_radius = style.get_number(theme::placeholder::corners_radius);
+            let color = style.get_color(theme::placeholder::color);
+            let shimmer_color = style.get_color(theme::placeholder::shimmer_color);
+            let shimmer_width = style.get_number(theme::placeholder::shimmer_width);
+            let speed = style.get_number(theme::placeholder::speed);
+            let time = Var::<f32>::from("input_time");
+
+            let bar = Rect((width.clone(), height.px())).corners_radius(corners_radius.px());
+            let offset_y = style.get_number(theme::placeholder::offset_y).px();
+            let bar = bar.translate_y(offset_y);
+            let shimmer_period = width + shimmer_width.px() * 2.0;
+            let shimmer = Rect((shimmer_width.px(), height.px() * 2.0));
+            let shimmer = shimmer.repeat((shimmer_period, height.px() * 4.0));
+            let shimmer = shimmer.translate_x((time * speed).px());
+            let shimmer = shimmer.intersection(&bar).fill(shimmer_color);
+            let shape = bar.fill(color) + shimmer;
+            shape.into()
+        }
+    }
+}
+
 
 
 // =============
 // === Style ===
 // =============
 
-/// The width of the icon in a text [`Entry`].
-pub const ICON_WIDTH: f32 = 17.0;
+/// The character replacing the middle part of a text too wide to be displayed. See
+/// [`Params::max_entry_width`].
+const ELLIPSIS: char = '…';
+/// The distance from which a newly displayed entry slides into its place.
+const APPEAR_SLIDE_DISTANCE: f32 = 12.0;
 
 
 /// Stylesheet-defined portion of the entries' parameters.
@@ -115,6 +154,9 @@ pub struct Style {
     pub text_size:                f32,
     pub icon_x_offset:            f32,
     pub icon_y_offset:            f32,
+    /// The width of the icon displayed before the text.
+    pub icon_size:                f32,
+    pub icon_color:               color::Rgba,
     pub highlight_corners_radius: f32,
 }
 
@@ -134,6 +176,8 @@ pub enum Model {
         icon:    Option<Icon>,
     },
     Separator,
+    /// A breadcrumb which text is not known yet. See [`crate::Breadcrumb::placeholder`].
+    Placeholder,
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
@@ -144,6 +188,7 @@ enum State {
         has_icon: bool,
     },
     Separator,
+    Placeholder,
 }
 
 impl State {
@@ -154,6 +199,27 @@ impl State {
 
 
 
+// ==================
+// === Truncation ===
+// ==================
+
+/// Shorten the text to the given number of its characters by replacing the middle ones with an
+/// ellipsis. The beginning and the end of a module name are usually the most distinctive parts.
+fn middle_ellipsis(text: &str, kept_chars: usize) -> String {
+    let char_count = text.chars().count();
+    if kept_chars >= char_count {
+        text.to_owned()
+    } else {
+        let head_len = (kept_chars + 1) / 2;
+        let tail_len = kept_chars - head_len;
+        let head = text.chars().take(head_len);
+        let tail = text.chars().skip(char_count - tail_len);
+        head.chain(std::iter::once(ELLIPSIS)).chain(tail).collect()
+    }
+}
+
+
+
 // =============
 // === Entry ===
 // =============
@@ -168,29 +234,57 @@ impl State {
 #[derive(Clone, Debug, display::Object)]
 pub struct EntryData {
     display_object: display::object::Instance,
+    /// The parent of the entry's visual representations, shifted while the entry slides in.
+    content:        display::object::Instance,
     text:           text::Text,
     icon:           any_icon::View,
     separator:      separator::View,
     ellipsis:       ellipsis::View,
+    placeholder:    placeholder::View,
     state:          Rc<Cell<State>>,
+    icon_size:      Cell<f32>,
+    /// The text of the breadcrumb, displayed shortened if it is too wide.
+    full_text:      RefCell<ImString>,
+    /// The number of the `full_text` characters displayed.
+    kept_chars:     Cell<usize>,
+    max_width:      Cell<Option<f32>>,
 }
 
 impl EntryData {
     fn new(app: &Application, text_layer: Option<&Layer>) -> Self {
         let display_object = display::object::Instance::new();
+        let content = display::object::Instance::new();
+        display_object.add_child(&content);
         let text = app.new_view::<ensogl_text::Text>();
         if let Some(layer) = text_layer {
             layer.add(&text);
         }
         let ellipsis = ellipsis::View::new();
         let separator = separator::View::new();
+        let placeholder = placeholder::View::new();
         let state = default();
+        let full_text = default();
+        let kept_chars = default();
+        let max_width = default();
+        let icon_size = default();
         let icon: any_icon::View = default();
         ellipsis.set_size((ellipsis::ICON_WIDTH, ellipsis::ICON_WIDTH));
-        icon.set_size((ICON_WIDTH, ICON_WIDTH));
-        display_object.add_child(&icon);
-        display_object.add_child(&ellipsis);
-        Self { display_object, state, text, ellipsis, separator, icon }
+        content.add_child(&icon);
+        content.add_child(&ellipsis);
+        Self {
+            display_object,
+            content,
+            state,
+            icon_size,
+            text,
+            ellipsis,
+            separator,
+            placeholder,
+            icon,
+            full_text,
+            kept_chars,
+            max_width,
+        }
     }
 
     fn hide_current_visual_representation(&self) {
@@ -203,25 +297,28 @@ impl EntryData {
             }
             State::Separator => self.separator.unset_parent(),
             State::Ellipsis => self.ellipsis.unset_parent(),
+            State::Placeholder => self.placeholder.unset_parent(),
         }
     }
 
     fn set_model(&self, model: &Model) {
         match model {
-            Model::Text { content, icon } => self.switch_to_text(content.as_str(), icon),
+            Model::Text { content, icon } => self.switch_to_text(content, icon),
             Model::Separator => self.switch_to_separator(),
             Model::Ellipsis => self.switch_to_ellipsis(),
+            Model::Placeholder => self.switch_to_placeholder(),
         }
     }
 
-    fn switch_to_text(&self, content: &str, icon: &Option<Icon>) {
-        self.text.set_content(content);
+    fn switch_to_text(&self, content: &ImString, icon: &Option<Icon>) {
+        *self.full_text.borrow_mut() = content.clone_ref();
+        self.display_full_text();
         if !self.state.get().is_text() {
             self.hide_current_visual_representation();
-            self.display_object.add_child(&self.text);
+            self.content.add_child(&self.text);
         }
         if let Some(icon) = icon {
-            self.display_object.add_child(&self.icon);
+            self.content.add_child(&self.icon);
             self.icon.icon.set(icon.any_cached_shape_location());
         } else {
             self.icon.unset_parent();
@@ -234,7 +331,7 @@ impl EntryData {
     fn switch_to_separator(&self) {
         if self.state.get() != State::Separator {
             self.hide_current_visual_representation();
-            self.display_object.add_child(&self.separator);
+            self.content.add_child(&self.separator);
             self.state.set(State::Separator);
         }
     }
@@ -242,16 +339,78 @@ impl EntryData {
     fn switch_to_ellipsis(&self) {
         if self.state.get() != State::Ellipsis {
             self.hide_current_visual_representation();
-            self.display_object.add_child(&self.ellipsis);
+            self.content.add_child(&self.ellipsis);
             self.state.set(State::Ellipsis);
         }
     }
 
+    fn switch_to_placeholder(&self) {
+        if self.state.get() != State::Placeholder {
+            self.hide_current_visual_representation();
+            self.content.add_child(&self.placeholder);
+            self.state.set(State::Placeholder);
+        }
+    }
+
+    fn display_full_text(&self) {
+        let full_text = self.full_text.borrow();
+        self.kept_chars.set(full_text.chars().count());
+        self.text.set_content(full_text.as_str());
+    }
+
+    /// Set the maximum width of the entry. The text of a wider entry is shortened with a middle
+    /// ellipsis.
+    fn set_max_width(&self, max_width: Option<f32>) {
+        if self.max_width.replace(max_width) != max_width && self.is_text_displayed() {
+            // The text is shortened again, if needed, after its new width is known.
+            self.display_full_text();
+        }
+    }
+
+    /// Shorten the displayed text if the entry is wider than its maximum width. The width of the
+    /// shortened text is known only after it is laid out, so the text is shortened repeatedly,
+    /// proportionally to the excess width, until it fits.
+    fn truncate_text_if_too_wide(&self, text_width: f32, text_padding: f32) {
+        let max_text_width = self.max_width.get().map(|max_width| {
+            (max_width - text_padding * 2.0 - self.icon_width()).max(0.0)
+        });
+        let kept_chars = self.kept_chars.get();
+        let too_wide = max_text_width.filter(|max| text_width > *max && kept_chars > 0);
+        if let Some(max_text_width) = too_wide {
+            let fitting_chars = (kept_chars as f32 * max_text_width / text_width) as usize;
+            let kept_chars = fitting_chars.min(kept_chars - 1);
+            self.kept_chars.set(kept_chars);
+            let content = middle_ellipsis(self.full_text.borrow().as_str(), kept_chars);
+            self.text.set_content(content);
+        }
+    }
+
+    /// The full text of the entry if it is displayed shortened.
+    fn full_text_if_truncated(&self) -> Option<ImString> {
+        let full_text = self.full_text.borrow();
+        let is_truncated = self.kept_chars.get() < full_text.chars().count();
+        (self.is_text_displayed() && is_truncated).then(|| full_text.clone_ref())
+    }
+
+    /// The width of the icon displayed before the text, or zero if there is no icon.
+    fn icon_width(&self) -> f32 {
+        if self.has_icon() {
+            self.icon_size.get()
+        } else {
+            0.0
+        }
+    }
+
+    fn set_icon_size(&self, size: f32) {
+        self.icon_size.set(size);
+    }
+
     fn has_icon(&self) -> bool {
         match self.state.get() {
             State::Text { has_icon } => has_icon,
             State::Ellipsis => false,
             State::Separator => false,
+            State::Placeholder => false,
         }
     }
 
@@ -264,18 +423,24 @@ impl EntryData {
         icon_y_offset: f32,
     ) {
         let size = contour.size;
-        let icon_offset = if self.has_icon() { ICON_WIDTH } else { 0.0 };
+        let icon_size = self.icon_size.get();
+        let icon_offset = self.icon_width();
         self.text.set_xy(Vector2(icon_offset + text_padding - size.x / 2.0, text_y_offset));
         self.separator.set_size(Vector2(separator::ICON_WIDTH, size.y));
         self.ellipsis.set_size(Vector2(ellipsis::ICON_WIDTH, size.y));
-        self.icon.set_size(Vector2(ICON_WIDTH, size.y));
+        self.placeholder.set_size(Vector2(placeholder::WIDTH, size.y));
+        self.icon.set_size(Vector2(icon_size, size.y));
         self.icon.set_x(-size.x / 2.0 - icon_x_offset);
-        self.icon.set_y(-ICON_WIDTH / 2.0 - icon_y_offset);
+        self.icon.set_y(-icon_size / 2.0 - icon_y_offset);
+    }
+
+    /// Shift the entry's visual representation horizontally, relative to its place in the grid.
+    fn set_slide_offset(&self, offset: f32) {
+        self.content.set_x(offset);
     }
 
     fn set_default_color(&self, color: color::Lcha) {
         self.text.set_property_default(color);
-        self.set_icon_color(color.into());
     }
 
     fn set_icon_color(&self, color: color::Rgba) {
@@ -298,13 +463,13 @@ impl EntryData {
             Model::Text { content, icon } => {
                 let new_text = content;
                 let previous_state_was_not_text = !self.state.get().is_text();
-                let previous_text = String::from(self.text.content.value());
-                let text_was_different = previous_text.as_str() != new_text.as_str();
+                let text_was_different = *self.full_text.borrow() != *new_text;
                 let different_icon_state = self.has_icon() != icon.is_some();
                 previous_state_was_not_text || text_was_different || different_icon_state
             }
             Model::Separator => self.state.get() != State::Separator,
             Model::Ellipsis => self.state.get() != State::Ellipsis,
+            Model::Placeholder => self.state.get() != State::Placeholder,
         }
     }
 
@@ -317,6 +482,7 @@ impl EntryData {
             State::Text { .. } => self.text_width(self.text.width.value(), text_padding),
             State::Separator => separator::ICON_WIDTH,
             State::Ellipsis => ellipsis::ICON_WIDTH,
+            State::Placeholder => placeholder::WIDTH + text_padding * 2.0,
         }
     }
 
@@ -324,8 +490,7 @@ impl EntryData {
     /// [`text_padding`].
     fn text_width(&self, text_width: f32, text_padding: f32) -> f32 {
         let text_width = text_width + text_padding * 2.0;
-        let icon_width = if self.has_icon() { ICON_WIDTH } else { 0.0 };
-        text_width + icon_width
+        text_width + self.icon_width()
     }
 }
 
@@ -336,12 +501,17 @@ impl EntryData {
 #[allow(missing_docs)]
 #[derive(Clone, Debug, Default)]
 pub struct Params {
-    pub style:            Style,
+    pub style:              Style,
     /// The first greyed out column. All columns to the right will also be greyed out.
-    pub greyed_out_start: Option<Col>,
-    pub selected_color:   color::Rgba,
-    pub greyed_out_color: color::Rgba,
-    pub separator_color:  color::Rgba,
+    pub greyed_out_start:   Option<Col>,
+    pub selected_color:     color::Rgba,
+    pub greyed_out_color:   color::Rgba,
+    pub separator_color:    color::Rgba,
+    /// The maximum width of a text entry. The text of a wider entry is shortened with a middle
+    /// ellipsis, and displayed in full in a tooltip when the entry is hovered.
+    pub max_entry_width:    Option<f32>,
+    /// If `false`, the entries are displayed and greyed out immediately, without animations.
+    pub animations_enabled: bool,
 }
 
 
@@ -367,6 +537,7 @@ impl ensogl_grid_view::Entry for Entry {
         let network = frp.network();
         let color_anim = Animation::new(network);
         let appear_anim = Animation::new(network);
+        let slide_anim = Animation::<f32>::new(network);
         fn mix(c1: &color::Lcha, c2: &color::Lcha, coefficient: &f32) -> color::Lcha {
             color::mix(*c1, *c2, *coefficient)
         }
@@ -383,10 +554,14 @@ impl ensogl_grid_view::Entry for Entry {
             text_size <- input.set_params.map(|p| p.style.text_size).on_change();
             icon_x_offset <- input.set_params.map(|p| p.style.icon_x_offset).on_change();
             icon_y_offset <- input.set_params.map(|p| p.style.icon_y_offset).on_change();
+            icon_size <- input.set_params.map(|p| p.style.icon_size).on_change();
+            icon_color <- input.set_params.map(|p| p.style.icon_color).cloned_into().on_change();
             greyed_out_color <- input.set_params.map(|p| p.greyed_out_color).cloned_into().on_change();
             separator_color <- input.set_params.map(|p| p.separator_color).cloned_into().on_change();
             highlight_corners_radius <- input.set_params.map(|p| p.style.highlight_corners_radius).on_change();
             greyed_out_from <- input.set_params.map(|p| p.greyed_out_start).on_change();
+            max_entry_width <- input.set_params.map(|p| p.max_entry_width).on_change();
+            animations_enabled <- input.set_params.map(|p| p.animations_enabled).on_change();
             transparent_color <- init.constant(color::Lcha::transparent());
             new_model <- input.set_model.on_change();
 
@@ -398,6 +573,7 @@ impl ensogl_grid_view::Entry for Entry {
                 |col, from| from.map_or(false, |from| *col >= from)
             );
             color_anim.target <+ should_grey_out.map(|should| if *should { 1.0 } else { 0.0 });
+            color_anim.skip <+ should_grey_out.gate_not(&animations_enabled).constant(());
             target_color <- all_with3(&text_color, &greyed_out_color, &color_anim.value, mix);
             appear_anim.target <+ init.constant(1.0);
             model_was_set <- input.set_model.map(f!((model) data.is_state_change(model))).on_true();
@@ -406,14 +582,24 @@ impl ensogl_grid_view::Entry for Entry {
                 appear_anim.target.emit(0.0);
                 appear_anim.skip.emit(());
                 appear_anim.target.emit(1.0);
+                slide_anim.target.emit(-APPEAR_SLIDE_DISTANCE);
+                slide_anim.skip.emit(());
+                slide_anim.target.emit(0.0);
             });
+            appear_skipped <- should_appear.gate_not(&animations_enabled);
+            appear_anim.skip <+ appear_skipped;
+            slide_anim.skip <+ appear_skipped;
+            eval slide_anim.value ((offset) data.set_slide_offset(*offset));
             color <- all_with3(&transparent_color, &target_color, &appear_anim.value, mix);
+            icon_target <- all_with3(&icon_color, &greyed_out_color, &color_anim.value, mix);
+            icon_color <- all_with3(&transparent_color, &icon_target, &appear_anim.value, mix);
 
             contour <- all_with(&size, &margin, |size, margin| Contour {
                 size: *size - Vector2(*margin, *margin) * 2.0,
                 corners_radius: 0.0,
             });
             eval color((c) data.set_default_color(*c));
+            eval icon_color((c) data.set_icon_color((*c).into()));
             eval font((f) data.set_font(f.to_string()));
             eval text_size((s) data.set_default_text_size(*s));
             eval separator_color((c: &color::Rgba) data.separator.color.set(Vector4::from(c)));
@@ -431,20 +617,38 @@ impl ensogl_grid_view::Entry for Entry {
             entry_width <- text_width.map2(&text_padding, f!((w, o) data.text_width(*w, *o)));
 
 
+            // === Truncation ===
+
+            eval max_entry_width ((max) data.set_max_width(*max));
+            _truncation <- text_width.map2(&text_padding,
+                f!((width, padding) data.truncate_text_if_too_wide(*width, *padding))
+            );
+            tooltip_shown <- any(...);
+            tooltip_text <- input.set_hovered.on_true().filter_map(f_!(data.full_text_if_truncated()));
+            tooltip_shown <+ tooltip_text.constant(true);
+            unhovered <- input.set_hovered.on_false();
+            hide_tooltip <- any_(unhovered, new_model).gate(&tooltip_shown);
+            tooltip_shown <+ hide_tooltip.constant(false);
+            app.frp.set_tooltip <+ tooltip_text.map(|text| tooltip::Style::set_label(text.as_str()));
+            app.frp.set_tooltip <+ hide_tooltip.constant(tooltip::Style::unset_label());
+
+
             // === Layout ===
 
+            eval icon_size((size) data.set_icon_size(*size));
             override_column_width <- new_model.map2(&text_padding,
                 f!([data](model, text_padding) {
                     data.set_model(model);
                     data.width(*text_padding)
                 })
             );
+            relayout <- any_(&new_model, &icon_size);
 
             layout <- all6(
                 &contour,
                 &text_padding,
                 &text_y_offset,
-                &new_model,
+                &relayout,
                 &icon_x_offset,
                 &icon_y_offset);
             eval layout ((&(c, to, tyo, _, ix, iy)) data.update_layout(c, to, tyo, ix, iy));
@@ -453,7 +657,9 @@ impl ensogl_grid_view::Entry for Entry {
 
             // We need to adjust the width of the grid view column depending on the width of
             // the entry.
+            resized_icon_width <- icon_size.map2(&text_padding, f!((_, p) data.width(*p)));
             out.override_column_width <+ any(entry_width,override_column_width);
+            out.override_column_width <+ resized_icon_width;
         }
         init.emit(());
         Self { frp, data }
@@ -463,3 +669,24 @@ impl ensogl_grid_view::Entry for Entry {
         &self.frp
     }
 }
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shortening_text_with_middle_ellipsis() {
+        assert_eq!(middle_ellipsis("Standard.Base.Data", 18), "Standard.Base.Data");
+        assert_eq!(middle_ellipsis("Standard.Base.Data", 100), "Standard.Base.Data");
+        assert_eq!(middle_ellipsis("Standard.Base.Data", 9), "Stand…Data");
+        assert_eq!(middle_ellipsis("Standard.Base.Data", 8), "Stan…Data");
+        assert_eq!(middle_ellipsis("Ąęść", 2), "Ą…ć");
+        assert_eq!(middle_ellipsis("Standard", 0), "…");
+    }
+}
@@ -16,6 +16,10 @@
 //! number of columns. A custom entry type for the Grid View is implemented in the [`entry`]
 //! module. Each entry has three different representations: a text label, a separator icon, and an
 //! ellipsis icon, and it can switch between these representations if needed.
+//!
+//! Clicking a separator opens the menu of the siblings of the following breadcrumb, provided by the
+//! [`siblings::SiblingsProvider`]. Choosing a sibling replaces the breadcrumb and removes the
+//! breadcrumbs after it.
 
 #![recursion_limit = "1024"]
 // === Features ===
@@ -61,6 +65,7 @@ use grid_view::Col;
 
 
 mod entry;
+pub mod siblings;
 
 
 
@@ -128,6 +133,7 @@ struct Layers {
     main:       Layer,
     text:       Layer,
     mask:       Layer,
+    menu:       Layer,
 }
 
 impl Layers {
@@ -137,8 +143,10 @@ impl Layers {
         let mask = base_layer.create_mask_sublayer("mask");
         let main = base_layer.create_sublayer("main");
         let text = main.create_sublayer("text");
+        // The menu is not masked, as it is displayed below the breadcrumbs.
+        let menu = base_layer.create_sublayer("menu");
         main.set_mask(&mask);
-        Layers { main, text, mask, background }
+        Layers { main, text, mask, background, menu }
     }
 }
 
@@ -158,6 +166,9 @@ pub struct Model {
     mask:           mask::View,
     show_ellipsis:  Rc<Cell<bool>>,
     background:     Rectangle,
+    siblings_menu:  siblings::SiblingsMenu,
+    siblings:       Rc<RefCell<siblings::AnySiblingsProvider>>,
+    sibling_chosen: frp::Any<(BreadcrumbId, Breadcrumb)>,
 }
 
 impl Model {
@@ -181,8 +192,23 @@ impl Model {
                 })
             );
             grid.model_for_entry <+ requested_entry;
+            sibling_chosen <- any(...);
+        }
+        let siblings_menu = siblings::SiblingsMenu::new(app, &sibling_chosen);
+        display_object.add_child(&siblings_menu);
+        let siblings = default();
+        Self {
+            display_object,
+            grid,
+            entries,
+            network,
+            mask,
+            show_ellipsis,
+            background,
+            siblings_menu,
+            siblings,
+            sibling_chosen,
         }
-        Self { display_object, grid, entries, network, mask, show_ellipsis, background }
     }
 
 
@@ -191,6 +217,7 @@ impl Model {
         layers.mask.add(&self.mask);
         layers.main.add(&self.display_object);
         layers.main.add(&self.grid);
+        layers.menu.add(&self.siblings_menu);
         self.grid.set_text_layer(Some(layers.text.downgrade()));
         self.network.store(&layers);
     }
@@ -281,6 +308,9 @@ impl Model {
         } else if is_separator_index {
             entry::Model::Separator
         } else if let Some(entry) = entries.borrow().get(col / 2) {
+            if entry.is_placeholder() {
+                return entry::Model::Placeholder;
+            }
             let content = entry.text();
             let icon = entry.icon();
             entry::Model::Text { content, icon }
@@ -340,6 +370,18 @@ impl Model {
         self.grid.request_model_for_visible_entries();
     }
 
+    /// Replace the placeholder breadcrumb at a specified index with the resolved one. Does nothing
+    /// if the breadcrumb at the index is not a placeholder anymore, for example because the
+    /// breadcrumbs were changed before the name was resolved.
+    pub fn set_entry_model_async(&self, entry: &Breadcrumb, index: BreadcrumbId) {
+        let is_placeholder = self.entries.borrow().get(index).map_or(false, |e| e.is_placeholder());
+        if is_placeholder {
+            self.set_entry(entry, index);
+        } else {
+            debug!("Ignoring resolved breadcrumb for index {index} which is not a placeholder.");
+        }
+    }
+
     /// Set the breadcrumbs starting from the [`starting_from`] index. Existing entries after
     /// [`starting_from`] will be overwritten. [`self.entries`] will be extended if needed to fit
     /// all added entries.
@@ -421,6 +463,48 @@ impl Model {
         }
     }
 
+    /// The breadcrumb following the separator at the `position` in the grid's coordinates.
+    fn breadcrumb_after_separator_at(&self, position: Vector2) -> Option<BreadcrumbId> {
+        let entries_size = self.grid.entries_size.value();
+        let viewport = self.grid.viewport.value();
+        let in_viewport = (viewport.left..=viewport.right).contains(&position.x);
+        let in_row = (-entries_size.y..=0.0).contains(&position.y);
+        let last_col = self.column_of_the_last_entry().filter(|_| in_viewport && in_row)?;
+        let separator_cols = (1..last_col).step_by(2);
+        let mut clicked = separator_cols.filter(|col| {
+            let center_x = self.grid.entry_position(0, *col).x;
+            (center_x - position.x).abs() <= entry::separator::ICON_WIDTH / 2.0
+        });
+        clicked.next().map(|col| (col + 1) / 2)
+    }
+
+    /// Open the menu of the siblings of the breadcrumb below the separator preceding it. Close the
+    /// menu if it is already open for this breadcrumb, or if the breadcrumb has no siblings.
+    fn toggle_siblings_menu(&self, breadcrumb: BreadcrumbId) {
+        if self.siblings_menu.shown_for() == Some(breadcrumb) {
+            self.siblings_menu.hide();
+        } else {
+            let siblings = self.siblings.borrow().siblings(&self.entries.borrow(), breadcrumb);
+            if siblings.is_empty() {
+                self.siblings_menu.hide();
+            } else {
+                let separator_col = (breadcrumb * 2).saturating_sub(1);
+                let separator_x = self.grid.entry_position(0, separator_col).x;
+                let grid_position = self.grid.position().xy();
+                let entries_height = self.grid.entries_size.value().y;
+                let position = grid_position + Vector2(separator_x, -entries_height);
+                self.siblings_menu.show(breadcrumb, siblings, position);
+            }
+        }
+    }
+
+    /// Replace the breadcrumb with its sibling chosen in the menu, removing the breadcrumbs after
+    /// it.
+    fn enter_sibling(&self, breadcrumb: BreadcrumbId, sibling: &Breadcrumb) {
+        self.siblings_menu.hide();
+        self.set_entries(&[sibling.clone_ref()], breadcrumb);
+    }
+
     /// Clear the breadcrumbs list.
     pub fn clear(&self) {
         self.entries.borrow_mut().clear();
@@ -435,11 +519,35 @@ impl Model {
 
 pub(crate) type Icon = Rc<icon::Id>;
 
+/// The kind of the scope represented by a breadcrumb. It is displayed as an icon before the
+/// breadcrumb's label.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[allow(missing_docs)]
+pub enum Kind {
+    Module,
+    Type,
+    Function,
+    Local,
+}
+
+impl Kind {
+    /// The icon representing the kind.
+    pub fn icon(self) -> icon::Id {
+        match self {
+            Kind::Module => icon::Id::Module,
+            Kind::Type => icon::Id::Type,
+            Kind::Function => icon::Id::Function,
+            Kind::Local => icon::Id::Local,
+        }
+    }
+}
+
 /// A single breadcrumb.
 #[derive(Clone, CloneRef, Debug, Default, PartialEq)]
 pub struct Breadcrumb {
-    text: ImString,
-    icon: Option<Icon>,
+    text:        ImString,
+    icon:        Option<Icon>,
+    placeholder: bool,
 }
 
 
@@ -448,7 +556,18 @@ impl Breadcrumb {
     pub fn new(text: &str, icon: Option<icon::Id>) -> Self {
         let text = ImString::new(text);
         let icon = icon.map(Rc::new);
-        Self { text, icon }
+        Self { text, icon, placeholder: false }
+    }
+
+    /// Create a placeholder breadcrumb, displayed as a shimmering bar until it is replaced using
+    /// [`Frp::set_entry_model_async`]. Used when the breadcrumb's name is resolved slowly.
+    pub fn placeholder() -> Self {
+        Self { placeholder: true, ..default() }
+    }
+
+    /// Check if this is a placeholder breadcrumb. See [`Self::placeholder`].
+    pub fn is_placeholder(&self) -> bool {
+        self.placeholder
     }
 
     /// Create a new breadcrumb with the specified text and icon.
@@ -456,6 +575,11 @@ impl Breadcrumb {
         Self::new(text, Some(icon))
     }
 
+    /// Create a new breadcrumb with the specified text and the icon of the given kind.
+    pub fn new_with_kind(text: &str, kind: Kind) -> Self {
+        Self::new(text, Some(kind.icon()))
+    }
+
     /// Create a new breadcrumb with the specified text. The icon will be set to [`None`].
     pub fn new_without_icon(text: &str) -> Self {
         Self::new(text, None)
@@ -474,19 +598,19 @@ impl Breadcrumb {
 
 impl From<String> for Breadcrumb {
     fn from(s: String) -> Self {
-        Self { text: ImString::new(s), icon: default() }
+        Self { text: ImString::new(s), icon: default(), placeholder: false }
     }
 }
 
 impl From<&str> for Breadcrumb {
     fn from(s: &str) -> Self {
-        Self { text: ImString::new(s), icon: default() }
+        Self { text: ImString::new(s), icon: default(), placeholder: false }
     }
 }
 
 impl From<ImString> for Breadcrumb {
     fn from(text: ImString) -> Self {
-        Self { text, icon: default() }
+        Self { text, icon: default(), placeholder: false }
     }
 }
 
@@ -510,6 +634,9 @@ ensogl_core::define_endpoints_2! {
         set_entries(Vec<Breadcrumb>),
         /// Set the breadcrumb at a specified index.
         set_entry((BreadcrumbId, Breadcrumb)),
+        /// Replace the [placeholder](Breadcrumb::placeholder) at a specified index with the
+        /// resolved breadcrumb. Ignored if the breadcrumb at the index is not a placeholder.
+        set_entry_model_async((BreadcrumbId, Breadcrumb)),
         /// Enable or disable displaying of the ellipsis icon at the end of the list.
         show_ellipsis(bool),
         /// Remove all breadcrumbs.
@@ -518,6 +645,10 @@ ensogl_core::define_endpoints_2! {
         /// breadcrumbs to this size and prioritize showing the right part of the list if it
         /// can't fit in completely.
         set_size(Vector2),
+        /// Set the maximum width of a single breadcrumb. The text of wider breadcrumbs is shortened
+        /// with an ellipsis in the middle, and displayed in full in a tooltip on hover. Breadcrumbs
+        /// are not shortened if [`None`] is set, which is the default.
+        set_max_entry_width(Option<f32>),
         /// Move the selection to the previous (higher-level) breadcrumb in the list.
         move_up(),
         /// Move the selection to the next (lower-level) breadcrumb in the list.
@@ -528,6 +659,14 @@ ensogl_core::define_endpoints_2! {
         pop_multiple(usize),
         /// Remove the last `n` breadcrumbs from the list, but only up to the first `m` breadcrumbs.
         pop_multiple_but_retain((usize,usize)),
+        /// Set the source of the siblings listed in the menu opened by clicking a separator. No
+        /// menu is opened by default.
+        set_siblings_provider(siblings::AnySiblingsProvider),
+        /// Close the menu of the breadcrumb's siblings.
+        hide_siblings_menu(),
+        /// Enable or disable animating the breadcrumbs. When enabled, which is the default, added
+        /// breadcrumbs slide and fade in, and the list scrolls smoothly when its content changes.
+        set_animations_enabled(bool),
 
         // == Theming API ==
 
@@ -544,7 +683,10 @@ ensogl_core::define_endpoints_2! {
         /// Currently selected breadcrumb.
         selected(BreadcrumbId),
         /// List of displayed breadcrumbs.
-        entries(Vec<Breadcrumb>)
+        entries(Vec<Breadcrumb>),
+        /// The breadcrumb with given index was replaced with its sibling chosen in the menu. The
+        /// breadcrumbs after it were removed.
+        sibling_entered((BreadcrumbId, Breadcrumb)),
     }
 }
 
@@ -575,6 +717,8 @@ impl Breadcrumbs {
         let background_y_offset = style.get_number(theme::background_y_offset);
         let background_height = style.get_number(theme::background_height);
         let scroll_anim = Animation::new(network);
+        let scene = &app.display.default_scene;
+        let mouse = &scene.mouse.frp_deprecated;
         frp::extend! { network
             init <- source_();
             eval input.show_ellipsis((b) model.show_ellipsis(*b));
@@ -593,6 +737,9 @@ impl Breadcrumbs {
             set_entries_from <- any(set_entries_from_zero, input.set_entries_from);
             entries_set <- set_entries_from.map(f!(((entries, from)) model.set_entries(entries, *from)));
             eval input.set_entry(((index, entry)) model.set_entry(entry, *index));
+            entry_resolved <- input.set_entry_model_async.map(
+                f!(((index, entry)) model.set_entry_model_async(entry, *index))
+            ).constant(());
             out.selected <+ selected;
 
             scroll_anim.target <+ all_with6(
@@ -608,6 +755,8 @@ impl Breadcrumbs {
                     model.offset(*content_size, *size) - *background_padding_x
                 })
             );
+            scroll_skipped <- scroll_anim.target.gate_not(&input.set_animations_enabled);
+            scroll_anim.skip <+ scroll_skipped.constant(());
             eval scroll_anim.value((offset) model.grid.set_x(-offset));
             eval_ input.move_up(model.move_up());
             eval_ input.move_down(model.move_down());
@@ -616,7 +765,29 @@ impl Breadcrumbs {
             background_color <- all(&frp.set_background_color, &init)._0();
             eval background_color ((color) background.set_color(*color););
             entried_update <- any5(&init, &entry_pushed, &entry_pushed_back, &entries_poped, &entries_set);
+            sibling_entered <- out.sibling_entered.constant(());
+            entried_update <- any3(&entried_update, &entry_resolved, &sibling_entered);
             out.entries <+ entried_update.map(f_!(model.entries.as_ref().borrow().clone())).on_change();
+
+
+            // === Siblings Menu ===
+
+            eval input.set_siblings_provider ((provider)
+                *model.siblings.borrow_mut() = provider.clone_ref()
+            );
+            clicked_position <- mouse.position.sample(&mouse.down_primary);
+            separator_clicked <- clicked_position.filter_map(f!([model, scene](position) {
+                let position = scene.screen_to_object_space(&model.grid, *position);
+                model.breadcrumb_after_separator_at(position)
+            }));
+            eval separator_clicked ((breadcrumb) model.toggle_siblings_menu(*breadcrumb));
+            out.sibling_entered <+ model.sibling_chosen.map(f!(((breadcrumb, sibling)) {
+                model.enter_sibling(*breadcrumb, sibling);
+                (*breadcrumb, sibling.clone_ref())
+            }));
+            hide_siblings_menu <- any_(input.hide_siblings_menu, selected_grid_col, entries_poped);
+            eval_ hide_siblings_menu (model.siblings_menu.hide());
+            eval background_color ((color) model.siblings_menu.set_color((*color).into()));
         }
 
         //== Entry Style ==
@@ -625,25 +796,31 @@ impl Breadcrumbs {
         let style = entry::Style::from_theme(network, &style_frp);
 
         frp::extend! { network
-            style_params <- all4(
+            style_params <- all6(
                 &style,
                 &frp.set_text_selected_color,
                 &frp.set_text_greyed_out_color,
-                &frp.set_separator_color
+                &frp.set_separator_color,
+                &frp.set_max_entry_width,
+                &frp.set_animations_enabled
             );
             params <- style_params.map(
-                |(style, selected_color, greyed_out_color, separator_color)|
+                |(style, selected_color, greyed_out_color, separator_color, max_entry_width,
+                    animations_enabled)|
                 entry::Params {
                     style: style.clone(),
                     greyed_out_start: None,
                     selected_color: *selected_color,
                     greyed_out_color: *greyed_out_color,
                     separator_color: *separator_color,
+                    max_entry_width: *max_entry_width,
+                    animations_enabled: *animations_enabled,
                 });
             grid.set_entries_params <+ params;
         }
 
         init.emit(());
+        frp.set_animations_enabled(true);
 
         let widget = Widget::new(app, frp, model);
         Self { widget }
@@ -746,4 +923,64 @@ mod tests {
         drop(breadcrumb_2);
         drop(breadcrumb_3);
     }
+
+    #[test]
+    fn test_resolving_placeholders() {
+        let (_app, breadcrumbs) = test_utils::init_component_for_test::<Breadcrumbs>();
+
+        let breadcrumb_1 = Breadcrumb::new_without_icon("1");
+        let breadcrumb_2 = Breadcrumb::new_without_icon("2");
+
+        breadcrumbs.push(Breadcrumb::placeholder());
+        breadcrumbs.push(breadcrumb_2.clone());
+        breadcrumbs.set_entry_model_async((0, breadcrumb_1.clone()));
+        assert_eq!(breadcrumbs.entries.value(), vec![breadcrumb_1.clone(), breadcrumb_2.clone()]);
+        // Resolving an entry which is not a placeholder anymore is ignored.
+        breadcrumbs.set_entry_model_async((1, breadcrumb_1.clone()));
+        assert_eq!(breadcrumbs.entries.value(), vec![breadcrumb_1, breadcrumb_2]);
+    }
+
+    #[test]
+    fn test_entering_sibling() {
+        let (_app, breadcrumbs) = test_utils::init_component_for_test::<Breadcrumbs>();
+
+        let breadcrumb_1 = Breadcrumb::new_without_icon("1");
+        let breadcrumb_2 = Breadcrumb::new_without_icon("2");
+        let breadcrumb_3 = Breadcrumb::new_without_icon("3");
+        let sibling = Breadcrumb::new_without_icon("2'");
+        let provided_siblings = vec![sibling.clone()];
+        let provider = move |_: &[Breadcrumb], _| provided_siblings.clone();
+        breadcrumbs.set_siblings_provider(siblings::AnySiblingsProvider::new(provider));
+
+        breadcrumbs.push(breadcrumb_1.clone());
+        breadcrumbs.push(breadcrumb_2);
+        breadcrumbs.push(breadcrumb_3);
+        breadcrumbs.model().toggle_siblings_menu(1);
+        assert_eq!(breadcrumbs.model().siblings_menu.shown_for(), Some(1));
+        breadcrumbs.model().sibling_chosen.emit((1, sibling.clone()));
+        assert_eq!(breadcrumbs.model().siblings_menu.shown_for(), None);
+        assert_eq!(breadcrumbs.sibling_entered.value(), (1, sibling.clone()));
+        assert_eq!(breadcrumbs.entries.value(), vec![breadcrumb_1, sibling]);
+    }
+
+    #[test]
+    fn test_final_positions_without_animations() {
+        let (_app, breadcrumbs) = test_utils::init_component_for_test::<Breadcrumbs>();
+        let size = Vector2(50.0, 20.0);
+        breadcrumbs.set_animations_enabled(false);
+        breadcrumbs.set_size(size);
+        let model = breadcrumbs.model();
+        // The test application has no theme, so the content is not padded.
+        let expected_grid_x = || -model.offset(model.grid.content_size.value(), size);
+
+        for name in ["Standard", "Base", "Data", "Vector"] {
+            breadcrumbs.push(Breadcrumb::new_without_icon(name));
+            assert_eq!(model.grid.position().x, expected_grid_x());
+        }
+        breadcrumbs.move_up();
+        breadcrumbs.move_up();
+        assert_eq!(model.grid.position().x, expected_grid_x());
+        breadcrumbs.pop_multiple(2);
+        assert_eq!(model.grid.position().x, expected_grid_x());
+    }
 }
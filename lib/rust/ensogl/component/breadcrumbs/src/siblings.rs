@@ -0,0 +1,152 @@
+//! The menu of the breadcrumb's siblings, opened by clicking the separator preceding the
+//! breadcrumb. It allows entering another module or function at the same level of the stack,
+//! without going up and down again.
+
+use ensogl_core::prelude::*;
+
+use crate::Breadcrumb;
+use crate::BreadcrumbId;
+
+use enso_frp as frp;
+use ensogl_core::application::Application;
+use ensogl_core::data::color;
+use ensogl_core::display;
+use ensogl_drop_down::Dropdown;
+
+
+
+// ========================
+// === SiblingsProvider ===
+// ========================
+
+/// A source of the siblings of the breadcrumbs.
+pub trait SiblingsProvider {
+    /// The breadcrumbs which may be entered instead of the breadcrumb with given index, that is,
+    /// the other children of the preceding breadcrumb. The menu is not opened if there are none.
+    fn siblings(&self, breadcrumbs: &[Breadcrumb], index: BreadcrumbId) -> Vec<Breadcrumb>;
+}
+
+impl<F> SiblingsProvider for F
+where F: Fn(&[Breadcrumb], BreadcrumbId) -> Vec<Breadcrumb>
+{
+    fn siblings(&self, breadcrumbs: &[Breadcrumb], index: BreadcrumbId) -> Vec<Breadcrumb> {
+        self(breadcrumbs, index)
+    }
+}
+
+/// A type-erased [`SiblingsProvider`]. The default one provides no siblings.
+#[derive(Clone, CloneRef)]
+pub struct AnySiblingsProvider {
+    provider: Rc<dyn SiblingsProvider>,
+}
+
+impl AnySiblingsProvider {
+    /// Constructor.
+    pub fn new(provider: impl SiblingsProvider + 'static) -> Self {
+        Self { provider: Rc::new(provider) }
+    }
+
+    /// The siblings of the breadcrumb with given index. See [`SiblingsProvider::siblings`].
+    pub fn siblings(&self, breadcrumbs: &[Breadcrumb], index: BreadcrumbId) -> Vec<Breadcrumb> {
+        self.provider.siblings(breadcrumbs, index)
+    }
+}
+
+impl Default for AnySiblingsProvider {
+    fn default() -> Self {
+        Self::new(|_: &[Breadcrumb], _| default())
+    }
+}
+
+impl Debug for AnySiblingsProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AnySiblingsProvider")
+    }
+}
+
+
+
+// =================
+// === MenuEntry ===
+// =================
+
+/// The dropdown entry of a sibling. The dropdown requires its entries to be hashable, so the
+/// sibling is identified by its index in the menu.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct MenuEntry {
+    index: usize,
+    label: ImString,
+}
+
+impl From<&MenuEntry> for ImString {
+    fn from(entry: &MenuEntry) -> Self {
+        entry.label.clone_ref()
+    }
+}
+
+
+
+// ====================
+// === SiblingsMenu ===
+// ====================
+
+/// The dropdown listing the siblings of a breadcrumb.
+#[derive(Clone, CloneRef, Debug, display::Object)]
+pub(crate) struct SiblingsMenu {
+    #[display_object]
+    dropdown:  Dropdown<MenuEntry>,
+    _network:  frp::Network,
+    siblings:  Rc<RefCell<Vec<Breadcrumb>>>,
+    /// The breadcrumb which siblings are displayed.
+    shown_for: Rc<Cell<Option<BreadcrumbId>>>,
+}
+
+impl SiblingsMenu {
+    /// Constructor. The sibling chosen in the menu is emitted to `sibling_chosen`, along with the
+    /// index of the breadcrumb it replaces.
+    pub fn new(app: &Application, sibling_chosen: &frp::Any<(BreadcrumbId, Breadcrumb)>) -> Self {
+        let dropdown = app.new_view::<Dropdown<MenuEntry>>();
+        let network = frp::Network::new("breadcrumbs::SiblingsMenu");
+        let siblings: Rc<RefCell<Vec<Breadcrumb>>> = default();
+        let shown_for: Rc<Cell<Option<BreadcrumbId>>> = default();
+        frp::extend! { network
+            chosen_entry <- dropdown.single_selected_entry.sample(&dropdown.user_select_action);
+            chosen_sibling <- chosen_entry.filter_map(f!([siblings, shown_for](entry) {
+                let entry = entry.as_ref()?;
+                let sibling = siblings.borrow().get(entry.index)?.clone_ref();
+                Some((shown_for.get()?, sibling))
+            }));
+            sibling_chosen <+ chosen_sibling;
+        }
+        Self { dropdown, _network: network, siblings, shown_for }
+    }
+
+    /// Show the `siblings` of the breadcrumb with given index, with the top-left corner of the
+    /// menu at the `position`.
+    pub fn show(&self, breadcrumb: BreadcrumbId, siblings: Vec<Breadcrumb>, position: Vector2) {
+        let entries = siblings.iter().enumerate();
+        let entries = entries.map(|(index, sibling)| MenuEntry { index, label: sibling.text() });
+        self.dropdown.set_all_entries(entries.collect::<Vec<_>>());
+        self.dropdown.set_selected_entries(HashSet::new());
+        self.dropdown.set_xy(position);
+        self.dropdown.set_open(true);
+        *self.siblings.borrow_mut() = siblings;
+        self.shown_for.set(Some(breadcrumb));
+    }
+
+    /// Hide the menu.
+    pub fn hide(&self) {
+        self.shown_for.set(None);
+        self.dropdown.set_open(false);
+    }
+
+    /// Set the background color of the menu.
+    pub fn set_color(&self, color: color::Lcha) {
+        self.dropdown.set_color(color);
+    }
+
+    /// The breadcrumb which siblings are displayed, if the menu is shown.
+    pub fn shown_for(&self) -> Option<BreadcrumbId> {
+        self.shown_for.get()
+    }
+}
@@ -0,0 +1,118 @@
+//! The entry of the command palette's list: the command label with the characters matching the
+//! query highlighted, and the hint of the command's shortcut aligned to the right.
+
+use crate::prelude::*;
+
+use crate::search::Command;
+
+use enso_frp as frp;
+use ensogl_core::application::Application;
+use ensogl_core::display;
+use ensogl_core::display::style::Path;
+use ensogl_hardcoded_theme::application::command_palette as theme;
+use ensogl_list_view as list_view;
+use ensogl_list_view::entry::GlyphHighlightedLabel;
+use ensogl_list_view::entry::GlyphHighlightedLabelModel;
+use ensogl_text as text;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The minimum gap between the label and the shortcut hint.
+const SHORTCUT_GAP: f32 = 16.0;
+
+
+
+// =============
+// === Model ===
+// =============
+
+/// The model of the [`Entry`].
+#[derive(Clone, Debug, Default)]
+pub struct Model {
+    /// The label of the command.
+    pub label:       String,
+    /// The ranges of the label matching the query.
+    pub highlighted: Vec<text::Range<text::Byte>>,
+    /// The pattern of the command's shortcut.
+    pub shortcut:    Option<String>,
+}
+
+impl Model {
+    /// The model of the command's entry.
+    pub fn new(command: &Command, highlighted: Vec<text::Range<text::Byte>>) -> Self {
+        Self { label: command.label.clone(), highlighted, shortcut: command.shortcut.clone() }
+    }
+}
+
+
+
+// =============
+// === Entry ===
+// =============
+
+/// The [`list_view::Entry`] of a command.
+#[derive(Clone, CloneRef, Debug, display::Object)]
+pub struct Entry {
+    #[display_object]
+    label:         GlyphHighlightedLabel,
+    shortcut:      text::Text,
+    shortcut_text: frp::Source<ImString>,
+    max_width:     frp::Source<f32>,
+}
+
+impl list_view::Entry for Entry {
+    type Model = Model;
+    type Params = ();
+
+    fn new(app: &Application, style_prefix: &Path, (): &Self::Params) -> Self {
+        let label = <GlyphHighlightedLabel as list_view::Entry>::new(app, style_prefix, &());
+        let shortcut = app.new_view::<text::Text>();
+        label.add_child(&shortcut);
+        let network = &label.inner.network;
+        let style = &label.inner.style_watch;
+        let label_text = &label.inner.label;
+        let size = style.get_number(style_prefix.sub("text").sub("size"));
+        let color = style.get_color(theme::shortcut);
+        frp::extend! { network
+            init <- source_();
+            shortcut_text <- source::<ImString>();
+            max_width <- source::<f32>();
+            size <- all(&size, &init)._0();
+            color <- all(&color, &init)._0();
+            shortcut.set_property_default <+ color.ref_into_some();
+            shortcut.set_property_default <+ size.map(|v| text::Size(*v)).ref_into_some();
+            eval size ((size) shortcut.set_y(size / 2.0));
+            shortcut.set_content <+ shortcut_text;
+
+            layout <- all(&max_width, &shortcut.width);
+            eval layout ([label_text, shortcut]((max_width, shortcut_width)) {
+                shortcut.set_x(max_width - shortcut_width);
+                let gap = if *shortcut_width > 0.0 { SHORTCUT_GAP } else { 0.0 };
+                let label_width = (max_width - shortcut_width - gap).max(0.0);
+                label_text.set_view_width(Some(label_width));
+            });
+        }
+        init.emit(());
+        Self { label, shortcut, shortcut_text, max_width }
+    }
+
+    fn update(&self, model: &Self::Model) {
+        let label = model.label.clone();
+        let highlighted = model.highlighted.clone();
+        list_view::Entry::update(&self.label, &GlyphHighlightedLabelModel { label, highlighted });
+        self.shortcut_text.emit(ImString::from(model.shortcut.clone().unwrap_or_default()));
+    }
+
+    fn set_max_width(&self, max_width_px: f32) {
+        self.max_width.emit(max_width_px);
+    }
+
+    fn set_label_layer(&self, label_layer: &display::scene::Layer) {
+        list_view::Entry::set_label_layer(&self.label, label_layer);
+        label_layer.add(&self.shortcut);
+    }
+}
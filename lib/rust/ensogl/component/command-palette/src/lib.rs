@@ -0,0 +1,303 @@
+//! The command palette: an overlay listing the commands of all application views, filtered by the
+//! fuzzy query typed by the user. Choosing a command runs it, like pressing its shortcut would.
+//!
+//! The commands are read from the application's [`command::Registry`] every time the palette is
+//! shown, so each command of a view defined with `define_endpoints!` or `define_endpoints_2!` is
+//! listed without any additional registration. The shortcuts of the commands are displayed next to
+//! them as hints.
+//!
+//! [`command::Registry`]: ensogl_core::application::command::Registry
+
+#![recursion_limit = "1024"]
+// === Standard Linter Configuration ===
+#![deny(non_ascii_idents)]
+#![warn(unsafe_code)]
+#![allow(clippy::bool_to_int_with_if)]
+#![allow(clippy::let_and_return)]
+// === Non-Standard Linter Configuration ===
+#![warn(missing_copy_implementations)]
+#![warn(missing_debug_implementations)]
+#![warn(missing_docs)]
+#![warn(trivial_casts)]
+#![warn(trivial_numeric_casts)]
+#![warn(unused_import_braces)]
+#![warn(unused_qualifications)]
+
+use ensogl_core::prelude::*;
+
+use enso_frp as frp;
+use ensogl_core::application::command::FrpNetworkProvider;
+use ensogl_core::application::frp::API;
+use ensogl_core::application::shortcut::Shortcut;
+use ensogl_core::application::Application;
+use ensogl_core::display;
+use ensogl_core::display::shape::Rectangle;
+use ensogl_core::display::shape::StyleWatchFrp;
+use ensogl_core::gui::Widget;
+use ensogl_hardcoded_theme::application::command_palette as theme;
+use ensogl_list_view as list_view;
+use ensogl_list_view::ListView;
+use ensogl_text as text;
+
+
+// ==============
+// === Export ===
+// ==============
+
+pub mod entry;
+pub mod search;
+
+/// Commonly used types and functions.
+pub mod prelude {
+    pub use ensogl_core::prelude::*;
+}
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The width of the palette.
+const WIDTH: f32 = 480.0;
+/// The height of the query field above the list of commands.
+const QUERY_HEIGHT: f32 = 36.0;
+/// The padding between the palette's border and its content.
+const PADDING: f32 = 8.0;
+/// The distance between the top of the screen and the palette.
+const TOP_MARGIN: f32 = 80.0;
+/// The palette grows with the number of listed commands up to this many entries, then the list
+/// scrolls.
+const MAX_VISIBLE_ENTRIES: usize = 10;
+
+
+
+// ===========
+// === FRP ===
+// ===========
+
+ensogl_core::define_endpoints_2! {
+    Input {
+        /// Show the palette, listing the currently enabled commands.
+        show(),
+        /// Hide the palette without running any command.
+        hide(),
+    }
+    Output {
+        is_visible(bool),
+        /// The command chosen in the palette was run.
+        command_run(search::Command),
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+/// The internal model of the [`CommandPalette`].
+#[derive(Debug, Clone, CloneRef, display::Object)]
+pub struct Model {
+    app:            Application,
+    display_object: display::object::Instance,
+    /// The visible part of the palette, attached only when the palette is shown.
+    content:        display::object::Instance,
+    background:     Rectangle,
+    query:          text::Text,
+    list:           ListView<entry::Entry>,
+    commands:       Rc<RefCell<Vec<search::Command>>>,
+    /// The indices of the listed commands in [`Self::commands`], in the order of the list.
+    listed:         Rc<RefCell<Vec<usize>>>,
+}
+
+impl Model {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let app = app.clone_ref();
+        let scene = &app.display.default_scene;
+        let display_object = display::object::Instance::new_named("CommandPalette");
+        let content = display::object::Instance::new();
+        let background = Rectangle();
+        let query = app.new_view::<text::Text>();
+        let list = app.new_view::<ListView<entry::Entry>>();
+        let commands = default();
+        let listed = default();
+        content.add_child(&background);
+        content.add_child(&query);
+        content.add_child(&list);
+        scene.layers.panel.add(&content);
+        scene.layers.panel_text.add(&query);
+        list.set_label_layer(&scene.layers.panel_text);
+        query.set_single_line_mode(true);
+        query.set_xy(Vector2(-WIDTH / 2.0 + PADDING, -QUERY_HEIGHT / 2.0));
+        Self { app, display_object, content, background, query, list, commands, listed }
+    }
+
+    fn show(&self) {
+        let shortcuts = &self.app.shortcuts;
+        let commands = self.app.commands.enabled_commands().into_iter().map(|(view, name)| {
+            let shortcut = shortcuts.patterns_of(&view, &name).into_iter().next();
+            search::Command::new(view, name, shortcut)
+        });
+        *self.commands.borrow_mut() = commands.collect();
+        self.display_object.add_child(&self.content);
+        self.query.set_content("");
+        self.query.focus();
+        self.list.deprecated_focus();
+        self.update_list("");
+    }
+
+    fn hide(&self) {
+        self.query.blur();
+        self.list.deprecated_defocus();
+        self.content.unset_parent();
+    }
+
+    /// List the commands matching the query and resize the palette to fit them.
+    fn update_list(&self, query: &str) {
+        let (listed, entries): (Vec<_>, Vec<_>) =
+            search::filter(&self.commands.borrow(), query).into_iter().unzip();
+        let visible_entries = entries.len().min(MAX_VISIBLE_ENTRIES);
+        let list_height = visible_entries as f32 * list_view::entry::HEIGHT + PADDING * 2.0;
+        let height = QUERY_HEIGHT + list_height;
+        self.background.set_size(Vector2(WIDTH, height));
+        self.background.set_xy(Vector2(-WIDTH / 2.0, -height));
+        self.list.resize(Vector2(WIDTH, list_height));
+        self.list.set_y(-QUERY_HEIGHT - list_height / 2.0);
+        let first_entry = (!entries.is_empty()).then_some(0);
+        *self.listed.borrow_mut() = listed;
+        self.list.set_entries(list_view::entry::AnyModelProvider::new(entries));
+        self.list.select_entry(first_entry);
+    }
+
+    fn command_at(&self, entry: list_view::entry::Id) -> Option<search::Command> {
+        let index = *self.listed.borrow().get(entry)?;
+        self.commands.borrow().get(index).cloned()
+    }
+
+    /// Hide the palette and run the command in all alive instances of its view.
+    fn run(&self, command: &search::Command) {
+        self.hide();
+        self.app.commands.emit_command(&command.view, &command.name);
+    }
+}
+
+
+
+// ======================
+// === CommandPalette ===
+// ======================
+
+/// The command palette. See the module documentation for details.
+#[derive(Debug, Clone, CloneRef, Deref, display::Object)]
+pub struct CommandPalette {
+    widget: Widget<Model, Frp>,
+}
+
+impl CommandPalette {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let model = Rc::new(Model::new(app));
+        let frp = Frp::new();
+        let network = frp.network();
+        let input = &frp.private().input;
+        let out = &frp.private().output;
+        let scene = &app.display.default_scene;
+        let style = StyleWatchFrp::new(&scene.style_sheet);
+        let background_color = style.get_color(theme::background);
+        let query_color = style.get_color(theme::query);
+
+        frp::extend! { network
+            init <- source_();
+
+            // === Visibility ===
+
+            show <- input.show.gate_not(&out.is_visible);
+            hide <- input.hide.gate(&out.is_visible);
+            eval_ show (model.show());
+            eval_ hide (model.hide());
+            out.is_visible <+ bool(&hide, &show);
+            position <- all_with(scene.shape(), &init, |shape, _| {
+                Vector2(0.0, shape.height / 2.0 - TOP_MARGIN)
+            });
+            eval position ((position) model.content.set_xy(*position));
+
+
+            // === Filtering and Running Commands ===
+
+            query <- model.query.content.map(|content| content.to_string()).on_change();
+            eval query ((query) model.update_list(query));
+            chosen <- model.list.chosen_entry.filter_map(f!((entry) model.command_at((*entry)?)));
+            out.is_visible <+ chosen.constant(false);
+            eval chosen ((command) model.run(command));
+            out.command_run <+ chosen;
+
+
+            // === Style ===
+
+            background_color <- all(&background_color, &init)._0();
+            eval background_color ((color) model.background.set_color(*color););
+            query_color <- all(&query_color, &init)._0();
+            model.query.set_property_default <+ query_color.ref_into_some();
+        }
+        init.emit(());
+
+        let widget = Widget::new(app, frp, model);
+        Self { widget }
+    }
+}
+
+impl FrpNetworkProvider for CommandPalette {
+    fn network(&self) -> &frp::Network {
+        self.widget.frp().network()
+    }
+}
+
+impl ensogl_core::application::View for CommandPalette {
+    fn label() -> &'static str {
+        "CommandPalette"
+    }
+
+    fn new(app: &Application) -> Self {
+        Self::new(app)
+    }
+
+    fn global_shortcuts() -> Vec<Shortcut> {
+        use ensogl_core::application::shortcut::ActionType::*;
+        [(Press, "!is_visible", "cmd shift p", "show"), (Press, "is_visible", "escape", "hide")]
+            .iter()
+            .map(|(a, b, c, d)| Self::self_shortcut_when(*a, *c, *d, *b))
+            .collect()
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ensogl_core::application::test_utils;
+
+    #[test]
+    fn showing_and_filtering_commands() {
+        let (_app, palette) = test_utils::init_component_for_test::<CommandPalette>();
+        let model = palette.widget.model();
+        palette.show();
+        assert!(palette.is_visible.value());
+        let commands = model.commands.borrow().clone();
+        let own_command = commands.iter().find(|c| c.view == "CommandPalette" && c.name == "hide");
+        let own_command = own_command.expect("The palette's commands should be listed.");
+        assert_eq!(own_command.shortcut.as_deref(), Some("escape"));
+
+        model.update_list("hide");
+        let first = model.command_at(0).expect("The matching command should be listed.");
+        assert_eq!(first.name, "hide");
+        palette.hide();
+        assert!(!palette.is_visible.value());
+    }
+}
@@ -0,0 +1,178 @@
+//! Filtering the commands by the query typed in the palette.
+
+use crate::prelude::*;
+
+use crate::entry;
+
+use fuzzly::score;
+use std::cmp::Ordering;
+
+
+
+// ===============
+// === Command ===
+// ===============
+
+/// A command which may be run from the palette.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Command {
+    /// The label of the view providing the command, like `"GraphEditor"`.
+    pub view:     String,
+    /// The name of the command, like `"collapse_selected_nodes"`.
+    pub name:     String,
+    /// The text displayed in the palette and matched against the query.
+    pub label:    String,
+    /// The pattern of the shortcut triggering the command, if any.
+    pub shortcut: Option<String>,
+}
+
+impl Command {
+    /// Constructor. The label is made of the view label and the command name, with underscores
+    /// replaced by spaces.
+    pub fn new(view: impl Into<String>, name: impl Into<String>, shortcut: Option<String>) -> Self {
+        let view = view.into();
+        let name = name.into();
+        let mut words = name.replace('_', " ");
+        if let Some(first) = words.get_mut(..1) {
+            first.make_ascii_uppercase();
+        }
+        let label = format!("{view}: {words}");
+        Self { view, name, label, shortcut }
+    }
+}
+
+
+
+// ==============
+// === Search ===
+// ==============
+
+/// The entries of the commands matching the `query`, the best matches first. All commands are
+/// returned in their original order if the query is empty. Each entry is paired with the index of
+/// its command.
+pub fn filter(commands: &[Command], query: &str) -> Vec<(usize, entry::Model)> {
+    let query = query.trim();
+    if query.is_empty() {
+        let entries = commands.iter().map(|command| entry::Model::new(command, default()));
+        entries.enumerate().collect()
+    } else {
+        let mut matcher = fuzzly::Matcher::<ScoreBuilder>::default();
+        let mut matches = commands
+            .iter()
+            .enumerate()
+            .filter_map(|(index, command)| {
+                let found = matcher.search(query, &command.label)?;
+                Some((index, command, found))
+            })
+            .collect_vec();
+        matches.sort_by(|(_, _, a), (_, _, b)| b.score.cmp(&a.score));
+        let entries = matches.into_iter().map(|(index, command, found)| {
+            let highlighted = found.match_indexes.byte_ranges(&command.label).collect();
+            (index, entry::Model::new(command, highlighted))
+        });
+        entries.collect()
+    }
+}
+
+
+// === Score Builder ===
+
+/// Observes how characters are matched within a submatch. Penalizes the characters of the matched
+/// words which were skipped, so the matches of whole words are displayed first.
+#[derive(Debug, Clone, Copy, Default)]
+struct ScoreBuilder {
+    word_chars_matched_since_last_delimiter: bool,
+    penalty: u32,
+}
+
+impl score::ScoreBuilder for ScoreBuilder {
+    type SubmatchScore = Score;
+
+    fn skip_word_chars(&mut self, count: core::num::NonZeroU32) {
+        if self.word_chars_matched_since_last_delimiter {
+            self.penalty += count.get();
+        }
+    }
+
+    fn match_word_char(&mut self) {
+        self.word_chars_matched_since_last_delimiter = true;
+    }
+
+    fn match_delimiter(&mut self, _pattern: char, _value: char) {
+        self.word_chars_matched_since_last_delimiter = false;
+    }
+
+    fn skip_delimiter(&mut self, _pattern: Option<char>, _value: char) {
+        self.word_chars_matched_since_last_delimiter = false;
+    }
+
+    fn finish(&self) -> Self::SubmatchScore {
+        Score { penalty: self.penalty }
+    }
+}
+
+/// The quality of a match. The lower the penalty, the better the match.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Score {
+    penalty: u32,
+}
+
+impl PartialOrd for Score {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(Ord::cmp(self, other))
+    }
+}
+
+impl Ord for Score {
+    fn cmp(&self, other: &Self) -> Ordering {
+        Ord::cmp(&self.penalty, &other.penalty).reverse()
+    }
+}
+
+impl score::SubmatchScore for Score {
+    const ANY_PREFIX_MATCH_BEATS_ANY_INITIALS_MATCH: bool = false;
+
+    fn with_submatch_by_initials_penalty(self) -> Self {
+        Self { penalty: self.penalty + 1 }
+    }
+}
+
+impl core::ops::Add for Score {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self { penalty: self.penalty + rhs.penalty }
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command_labels() {
+        let command = Command::new("ProjectView", "show_project_list", Some("cmd o".into()));
+        assert_eq!(command.label, "ProjectView: Show project list");
+    }
+
+    #[test]
+    fn filtering_commands() {
+        let commands = vec![
+            Command::new("ProjectView", "toggle_style", None),
+            Command::new("ProjectView", "show_project_list", None),
+            Command::new("GraphEditor", "collapse_selected_nodes", None),
+        ];
+        let indices = |query| filter(&commands, query).into_iter().map(|(i, _)| i).collect_vec();
+        assert_eq!(indices(""), vec![0, 1, 2]);
+        assert_eq!(indices("list"), vec![1]);
+        assert_eq!(indices("collapse"), vec![2]);
+        assert_eq!(indices("xyz"), Vec::<usize>::new());
+        let (_, entry) = &filter(&commands, "collapse")[0];
+        assert!(!entry.highlighted.is_empty());
+    }
+}
@@ -0,0 +1,144 @@
+//! A text button of the dialog. The button may be primary (filled with the accent color), and may
+//! be selected, which means it is triggered by pressing enter.
+
+use crate::prelude::*;
+use ensogl_core::display::shape::*;
+
+use enso_frp as frp;
+use ensogl_core::application::frp::API;
+use ensogl_core::application::Application;
+use ensogl_core::control::io::mouse;
+use ensogl_core::display;
+use ensogl_core::gui::Widget;
+use ensogl_hardcoded_theme::application::dialog::button as theme;
+use ensogl_text as text;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The height of a button.
+pub const HEIGHT: f32 = 32.0;
+const PADDING: f32 = 16.0;
+const TEXT_SIZE: f32 = 13.0;
+const SELECTION_WIDTH: f32 = 2.0;
+
+
+
+// ===========
+// === FRP ===
+// ===========
+
+ensogl_core::define_endpoints_2! {
+    Input {
+        set_label(ImString),
+        set_primary(bool),
+        set_selected(bool),
+    }
+    Output {
+        clicked(),
+        width(f32),
+        is_hovered(bool),
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+/// The internal model of the [`Button`].
+#[derive(Debug, Clone, CloneRef, display::Object)]
+pub struct Model {
+    display_object: display::object::Instance,
+    background:     Rectangle,
+    label:          text::Text,
+    style:          StyleWatch,
+}
+
+impl Model {
+    fn new(app: &Application) -> Self {
+        let scene = &app.display.default_scene;
+        let display_object = display::object::Instance::new_named("DialogButton");
+        let background = Rectangle();
+        let label = app.new_view::<text::Text>();
+        let style = StyleWatch::new(&scene.style_sheet);
+        display_object.add_child(&background);
+        display_object.add_child(&label);
+        scene.layers.panel_text.add(&label);
+        background.set_corner_radius_max().set_pointer_events(true);
+        label.set_single_line_mode(true);
+        label.set_property_default(text::Size(TEXT_SIZE));
+        label.set_xy(Vector2(PADDING, HEIGHT / 2.0 + TEXT_SIZE / 2.0));
+        Self { display_object, background, label, style }
+    }
+
+    fn set_width(&self, width: f32) {
+        self.background.set_size(Vector2(width, HEIGHT));
+        self.display_object.set_size(Vector2(width, HEIGHT));
+    }
+
+    fn update_colors(&self, primary: bool, hovered: bool, selected: bool) {
+        let background = match (primary, hovered) {
+            (true, true) => theme::primary_hover_background,
+            (true, false) => theme::primary_background,
+            (false, true) => theme::hover_background,
+            (false, false) => theme::background,
+        };
+        let text = if primary { theme::primary_text } else { theme::text };
+        let text_color = self.style.get_color(text);
+        self.background.set_color(self.style.get_color(background));
+        self.background.set_border_color(self.style.get_color(theme::selection));
+        self.background.set_border_and_inset(if selected { SELECTION_WIDTH } else { 0.0 });
+        self.label.frp.set_property(.., text_color);
+        self.label.frp.set_property_default(text_color);
+    }
+}
+
+
+
+// ==============
+// === Button ===
+// ==============
+
+/// A text button of the dialog. See the module documentation for details.
+#[derive(Debug, Clone, CloneRef, Deref, display::Object)]
+pub struct Button {
+    widget: Widget<Model, Frp>,
+}
+
+impl Button {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let model = Rc::new(Model::new(app));
+        let frp = Frp::new();
+        let network = frp.network();
+        let input = &frp.private().input;
+        let out = &frp.private().output;
+        let mouse_down = model.background.on_event::<mouse::Down>();
+        let mouse_over = model.background.on_event::<mouse::Enter>();
+        let mouse_out = model.background.on_event::<mouse::Leave>();
+
+        frp::extend! { network
+            init <- source_();
+            model.label.set_content <+ input.set_label;
+            width <- model.label.width.map(|width| width + PADDING * 2.0);
+            eval width ((width) model.set_width(*width));
+            out.width <+ width;
+
+            out.clicked <+ mouse_down.filter(|e| e.button() == mouse::PrimaryButton).constant(());
+            out.is_hovered <+ bool(&mouse_out, &mouse_over);
+            colors <- all(&input.set_primary, &out.is_hovered, &input.set_selected, &init);
+            eval colors (((primary, hovered, selected, _)) {
+                model.update_colors(*primary, *hovered, *selected)
+            });
+        }
+        init.emit(());
+
+        let widget = Widget::new(app, frp, model);
+        Self { widget }
+    }
+}
@@ -0,0 +1,406 @@
+//! A modal dialog: a panel with a title, an arbitrary body and the confirm and cancel buttons,
+//! displayed above a backdrop dimming the rest of the scene.
+//!
+//! While the dialog is visible, it keeps the keyboard focus: focusing any object outside of the
+//! dialog moves the focus back to it, and the key presses are not propagated to the rest of the
+//! scene. Pressing enter triggers the selected button (the confirm button by default), tab switches
+//! the selected button, and escape cancels the dialog. The backdrop catches the mouse events, so
+//! the scene below can't be interacted with.
+
+#![recursion_limit = "1024"]
+// === Standard Linter Configuration ===
+#![deny(non_ascii_idents)]
+#![warn(unsafe_code)]
+#![allow(clippy::bool_to_int_with_if)]
+#![allow(clippy::let_and_return)]
+// === Non-Standard Linter Configuration ===
+#![warn(missing_copy_implementations)]
+#![warn(missing_debug_implementations)]
+#![warn(missing_docs)]
+#![warn(trivial_casts)]
+#![warn(trivial_numeric_casts)]
+#![warn(unused_import_braces)]
+#![warn(unused_qualifications)]
+
+use ensogl_core::display::shape::*;
+use ensogl_core::prelude::*;
+
+use enso_frp as frp;
+use ensogl_core::application::command::FrpNetworkProvider;
+use ensogl_core::application::frp::API;
+use ensogl_core::application::shortcut::Shortcut;
+use ensogl_core::application::Application;
+use ensogl_core::control::io::keyboard;
+use ensogl_core::display;
+use ensogl_core::display::object::event;
+use ensogl_core::gui::Widget;
+use ensogl_hardcoded_theme::application::dialog as theme;
+use ensogl_text as text;
+
+
+// ==============
+// === Export ===
+// ==============
+
+pub mod button;
+
+/// Commonly used types and functions.
+pub mod prelude {
+    pub use ensogl_core::prelude::*;
+}
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The width of the dialog.
+const WIDTH: f32 = 400.0;
+const PADDING: f32 = 24.0;
+const TITLE_SIZE: f32 = 16.0;
+/// The vertical gap between the title, the body and the buttons.
+const GAP: f32 = 16.0;
+/// The horizontal gap between the buttons.
+const BUTTON_GAP: f32 = 8.0;
+const DEFAULT_CONFIRM_LABEL: &str = "OK";
+const DEFAULT_CANCEL_LABEL: &str = "Cancel";
+
+
+
+// ==============
+// === Choice ===
+// ==============
+
+/// A button of the dialog.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum Choice {
+    #[default]
+    Confirm,
+    Cancel,
+}
+
+impl Choice {
+    /// The other button.
+    pub fn other(self) -> Self {
+        match self {
+            Self::Confirm => Self::Cancel,
+            Self::Cancel => Self::Confirm,
+        }
+    }
+}
+
+
+
+// ===========
+// === FRP ===
+// ===========
+
+ensogl_core::define_endpoints_2! {
+    Input {
+        /// Show the dialog with the confirm button selected.
+        show(),
+        /// Hide the dialog without emitting `confirmed` nor `cancelled`.
+        hide(),
+        set_title(ImString),
+        set_confirm_label(ImString),
+        set_cancel_label(ImString),
+        /// Hide the dialog and emit `confirmed`.
+        confirm(),
+        /// Hide the dialog and emit `cancelled`.
+        cancel(),
+        /// Trigger the selected button.
+        accept_selected_choice(),
+        /// Select the other button.
+        select_other_choice(),
+    }
+    Output {
+        is_visible(bool),
+        selected_choice(Choice),
+        confirmed(),
+        cancelled(),
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+/// The internal model of the [`Dialog`].
+#[derive(Debug, Clone, CloneRef, display::Object)]
+pub struct Model {
+    display_object: display::object::Instance,
+    /// The visible part of the dialog, attached only when the dialog is shown.
+    content:        display::object::Instance,
+    backdrop:       Rectangle,
+    background:     Rectangle,
+    title:          text::Text,
+    /// The container of the body set by [`Dialog::set_body`].
+    body:           display::object::Instance,
+    confirm:        button::Button,
+    cancel:         button::Button,
+}
+
+impl Model {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let scene = &app.display.default_scene;
+        let display_object = display::object::Instance::new_named("Dialog");
+        let content = display::object::Instance::new();
+        let backdrop = Rectangle();
+        let background = Rectangle();
+        let title = app.new_view::<text::Text>();
+        let body = display::object::Instance::new_named("DialogBody");
+        let confirm = button::Button::new(app);
+        let cancel = button::Button::new(app);
+        content.add_child(&backdrop);
+        content.add_child(&background);
+        content.add_child(&title);
+        content.add_child(&body);
+        content.add_child(&confirm);
+        content.add_child(&cancel);
+        scene.layers.panel.add(&content);
+        scene.layers.panel_text.add(&title);
+        backdrop.set_pointer_events(true);
+        background.set_pointer_events(true);
+        body.use_auto_layout();
+        title.set_single_line_mode(true);
+        title.set_property_default(text::Size(TITLE_SIZE));
+        confirm.set_primary(true);
+        confirm.set_label(DEFAULT_CONFIRM_LABEL);
+        cancel.set_label(DEFAULT_CANCEL_LABEL);
+        Self { display_object, content, backdrop, background, title, body, confirm, cancel }
+    }
+
+    fn show(&self) {
+        self.display_object.add_child(&self.content);
+        self.display_object.focus();
+    }
+
+    fn hide(&self) {
+        self.display_object.blur();
+        self.content.unset_parent();
+    }
+
+    fn set_body(&self, body: &display::object::Instance) {
+        self.body.remove_all_children();
+        self.body.add_child(body);
+    }
+
+    /// Check whether the object is the dialog or any of its descendants.
+    fn contains(&self, object: &display::object::Instance) -> bool {
+        let mut current = Some(object.clone());
+        while let Some(object) = current {
+            if object == self.display_object {
+                return true;
+            }
+            current = object.parent();
+        }
+        false
+    }
+
+    fn set_backdrop_size(&self, size: Vector2) {
+        self.backdrop.set_size(size);
+        self.backdrop.set_xy(-size / 2.0);
+    }
+
+    /// Place the panel in the center of the screen, sized to fit the body.
+    fn update_layout(&self, body_size: Vector2, confirm_width: f32, cancel_width: f32) {
+        let body_width = body_size.x.max(WIDTH - PADDING * 2.0);
+        let width = body_width + PADDING * 2.0;
+        let height = PADDING * 2.0 + TITLE_SIZE + GAP * 2.0 + body_size.y + button::HEIGHT;
+        let origin = -Vector2(width, height) / 2.0;
+        self.background.set_size(Vector2(width, height));
+        self.background.set_xy(origin);
+        self.title.set_xy(origin + Vector2(PADDING, height - PADDING));
+        let body_y = height - PADDING - TITLE_SIZE - GAP - body_size.y;
+        self.body.set_xy(origin + Vector2(PADDING, body_y));
+        let confirm_x = width - PADDING - confirm_width;
+        let cancel_x = confirm_x - BUTTON_GAP - cancel_width;
+        self.confirm.set_xy(origin + Vector2(confirm_x, PADDING));
+        self.cancel.set_xy(origin + Vector2(cancel_x, PADDING));
+    }
+}
+
+
+
+// ==============
+// === Dialog ===
+// ==============
+
+/// The modal dialog. See the module documentation for details.
+#[derive(Debug, Clone, CloneRef, Deref, display::Object)]
+pub struct Dialog {
+    widget: Widget<Model, Frp>,
+}
+
+impl Dialog {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let model = Rc::new(Model::new(app));
+        let frp = Frp::new();
+        let network = frp.network();
+        let input = &frp.private().input;
+        let out = &frp.private().output;
+        let scene = &app.display.default_scene;
+        let scene_shape = scene.shape();
+        let style = StyleWatchFrp::new(&scene.style_sheet);
+        let backdrop_color = style.get_color(theme::backdrop);
+        let background_color = style.get_color(theme::background);
+        let title_color = style.get_color(theme::title);
+        let corner_radius = style.get_number(theme::corner_radius);
+        let focus_in_scene = scene.on_event::<event::FocusIn>();
+        let key_down = model.display_object.on_event::<keyboard::KeyDown>();
+        let body = &model.body;
+
+        frp::extend! { network
+            init <- source_();
+
+            // === Visibility ===
+
+            show <- input.show.gate_not(&out.is_visible);
+            out.selected_choice <+ show.constant(Choice::Confirm);
+            eval_ show (model.show());
+            confirmed <- input.confirm.gate(&out.is_visible);
+            cancelled <- input.cancel.gate(&out.is_visible);
+            out.confirmed <+ confirmed;
+            out.cancelled <+ cancelled;
+            hide <- any_(input.hide, confirmed, cancelled).gate(&out.is_visible);
+            eval_ hide (model.hide());
+            out.is_visible <+ bool(&hide, &show);
+
+
+            // === Keyboard ===
+
+            focus_outside <- focus_in_scene.gate(&out.is_visible).filter(f!([model](e)
+                e.target().map_or(false, |target| !model.contains(&target))
+            ));
+            eval_ focus_outside (model.display_object().focus());
+            key_down <- key_down.gate(&out.is_visible);
+            eval key_down ((e) e.stop_propagation());
+            out.selected_choice <+ out.selected_choice.sample(&input.select_other_choice)
+                .map(|choice| choice.other());
+            accepted <- out.selected_choice.sample(&input.accept_selected_choice);
+            input.confirm <+ accepted.filter(|choice| *choice == Choice::Confirm).constant(());
+            input.cancel <+ accepted.filter(|choice| *choice == Choice::Cancel).constant(());
+
+
+            // === Buttons ===
+
+            model.confirm.set_label <+ input.set_confirm_label;
+            model.cancel.set_label <+ input.set_cancel_label;
+            input.confirm <+ model.confirm.clicked;
+            input.cancel <+ model.cancel.clicked;
+            model.confirm.set_selected <+ out.selected_choice.map(|c| *c == Choice::Confirm);
+            model.cancel.set_selected <+ out.selected_choice.map(|c| *c == Choice::Cancel);
+
+
+            // === Layout ===
+
+            model.title.set_content <+ input.set_title;
+            layout <- all(&body.on_resized, &model.confirm.width, &model.cancel.width, &init);
+            eval layout (((body, confirm, cancel, _)) model.update_layout(*body, *confirm, *cancel));
+            backdrop_size <- all_with(scene_shape, &init, |shape, _| {
+                Vector2(shape.width, shape.height)
+            });
+            eval backdrop_size ((size) model.set_backdrop_size(*size));
+
+
+            // === Style ===
+
+            backdrop_color <- all(&backdrop_color, &init)._0();
+            eval backdrop_color ((color) model.backdrop.set_color(*color););
+            background_color <- all(&background_color, &init)._0();
+            eval background_color ((color) model.background.set_color(*color););
+            corner_radius <- all(&corner_radius, &init)._0();
+            eval corner_radius ((radius) model.background.set_corner_radius(*radius););
+            title_color <- all(&title_color, &init)._0();
+            model.title.set_property_default <+ title_color.ref_into_some();
+        }
+        init.emit(());
+
+        let widget = Widget::new(app, frp, model);
+        Self { widget }
+    }
+
+    /// Display the object in the body of the dialog, between the title and the buttons, replacing
+    /// the previous body. The dialog grows to fit the object's size.
+    pub fn set_body(&self, body: &impl display::Object) {
+        self.widget.model().set_body(body.display_object());
+    }
+}
+
+impl FrpNetworkProvider for Dialog {
+    fn network(&self) -> &frp::Network {
+        self.widget.frp().network()
+    }
+}
+
+impl ensogl_core::application::View for Dialog {
+    fn label() -> &'static str {
+        "Dialog"
+    }
+
+    fn new(app: &Application) -> Self {
+        Self::new(app)
+    }
+
+    fn focused_shortcuts() -> Vec<Shortcut> {
+        use ensogl_core::application::shortcut::ActionType::*;
+        [
+            (Press, "enter", "accept_selected_choice"),
+            (Press, "escape", "cancel"),
+            (Press, "tab", "select_other_choice"),
+            (Press, "shift tab", "select_other_choice"),
+        ]
+        .iter()
+        .map(|(action, pattern, command)| Self::self_shortcut(*action, *pattern, *command))
+        .collect()
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ensogl_core::application::test_utils;
+
+    #[test]
+    fn confirming_and_cancelling() {
+        let (_app, dialog) = test_utils::init_component_for_test::<Dialog>();
+        let network = dialog.network();
+        frp::extend! { network
+            confirmed <- dialog.confirmed.count().sampler();
+            cancelled <- dialog.cancelled.count().sampler();
+        }
+        dialog.confirm();
+        assert_eq!(confirmed.value(), 0, "The hidden dialog should not be confirmed.");
+
+        dialog.show();
+        assert!(dialog.is_visible.value());
+        assert_eq!(dialog.selected_choice.value(), Choice::Confirm);
+        dialog.accept_selected_choice();
+        assert!(!dialog.is_visible.value());
+        assert_eq!((confirmed.value(), cancelled.value()), (1, 0));
+
+        dialog.show();
+        dialog.select_other_choice();
+        assert_eq!(dialog.selected_choice.value(), Choice::Cancel);
+        dialog.accept_selected_choice();
+        assert!(!dialog.is_visible.value());
+        assert_eq!((confirmed.value(), cancelled.value()), (1, 1));
+
+        dialog.show();
+        dialog.hide();
+        assert!(!dialog.is_visible.value());
+        assert_eq!((confirmed.value(), cancelled.value()), (1, 1));
+    }
+}
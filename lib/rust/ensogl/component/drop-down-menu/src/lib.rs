@@ -123,11 +123,16 @@ ensogl_core::define_endpoints! {
         set_width           (f32),
     }
     Output {
-        menu_visible    (bool),
-        menu_closed     (),
-        chosen_entry    (Option<list_view::entry::Id>),
-        icon_mouse_over (),
-        icon_mouse_out  (),
+        menu_visible         (bool),
+        menu_closed          (),
+        /// The selected entry, either chosen by the user or set with the `set_selected` input.
+        chosen_entry         (Option<list_view::entry::Id>),
+        /// The entry chosen by the user in the menu. Disabled entries cannot be chosen.
+        entry_chosen_by_user (list_view::entry::Id),
+        /// The entry under the mouse cursor in the opened menu.
+        hovered_entry        (Option<list_view::entry::Id>),
+        icon_mouse_over      (),
+        icon_mouse_out       (),
     }
 }
 
@@ -138,7 +143,7 @@ ensogl_core::define_endpoints! {
 // =============
 
 /// A type of Entry used in DropDownMenu's ListView.
-pub type Entry = list_view::entry::Label;
+pub type Entry = list_view::entry::DisableableLabel;
 
 #[derive(Clone, Debug, display::Object)]
 struct Model {
@@ -198,6 +203,10 @@ impl Model {
         self.content.borrow().as_ref()?.get(id?)
     }
 
+    fn is_entry_enabled(&self, id: Option<list_view::entry::Id>) -> bool {
+        self.get_content_item(id).map_or(false, |item| !item.disabled)
+    }
+
     /// Transform index of an element visible in the menu, to the index of the all the objects,
     /// accounting for the removal of the selected item.
     ///
@@ -347,11 +356,20 @@ impl DropDownMenu {
             // === Selection ===
 
             eval_ model.selection_menu.chosen_entry (hide_menu.emit(()));
-            chosen_entry_unmasked <- model.selection_menu.chosen_entry.map(f!((entry_id)
+            chosen_enabled_entry <- model.selection_menu.chosen_entry.filter(f!((entry_id)
+                model.is_entry_enabled(*entry_id))
+            );
+            chosen_entry_unmasked <- chosen_enabled_entry.map(f!((entry_id)
                 model.get_unmasked_index(*entry_id))
             );
             set_selected            <- any(frp.input.set_selected, chosen_entry_unmasked);
             frp.source.chosen_entry <+ set_selected;
+            frp.source.entry_chosen_by_user <+ chosen_entry_unmasked.unwrap();
+
+            hovered_entry <- model.selection_menu.selected_entry.map(f!((entry_id)
+                model.get_unmasked_index(*entry_id))
+            );
+            frp.source.hovered_entry <+ hovered_entry.on_change();
 
             eval set_selected([model](entry_id) {
                 if let Some(entry_id) = entry_id {
@@ -360,7 +378,7 @@ impl DropDownMenu {
                         // clear the mask.
                         content.clear_mask();
                         if let Some(item) = model.get_content_item(Some(*entry_id)) {
-                            model.set_label(&item)
+                            model.set_label(&item.label)
                         };
                         // Remove selected item from menu list
                         content.set_mask(*entry_id);
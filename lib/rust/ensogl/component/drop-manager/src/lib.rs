@@ -115,6 +115,7 @@ impl File {
 
 type DropClosure = Closure<dyn Fn(web_sys::DragEvent)>;
 type DragOverClosure = Closure<dyn Fn(web_sys::DragEvent) -> bool>;
+type DragLeaveClosure = Closure<dyn Fn(web_sys::DragEvent)>;
 
 #[derive(Clone, Debug, Default)]
 /// The data emitted by the `files_received` frp endpoint.
@@ -125,20 +126,34 @@ pub struct DropEventData {
     pub files:    Vec<File>,
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+/// The data emitted by the `files_hovered` frp endpoint.
+pub struct DragOverEventData {
+    /// The position of the dragged files in the scene coordinates.
+    pub position: Vector2,
+    /// The number of the dragged items. The browsers do not reveal whether they are files until
+    /// they are dropped.
+    pub count:    usize,
+}
+
 /// The Manager of dropped files.
 ///
 /// It adds listeners for drag and drop events to the target passed during construction. It provides
-/// the single frp endpoints emitting a signal when a file is dropped.
+/// the frp endpoints emitting a signal when a file is dropped, and when files are dragged over the
+/// target.
 // NOTE[allow_dead] We allow dead fields here, because they keep living closures and network.
 #[derive(Clone, CloneRef, Debug)]
 pub struct Manager {
     #[allow(dead_code)]
-    network:          frp::Network,
-    files_received:   frp::Source<DropEventData>,
+    network:           frp::Network,
+    files_received:    frp::Source<DropEventData>,
+    files_hovered:     frp::Source<Option<DragOverEventData>>,
+    #[allow(dead_code)]
+    drop_handle:       web::EventListenerHandle,
     #[allow(dead_code)]
-    drop_handle:      web::EventListenerHandle,
+    drag_over_handle:  web::EventListenerHandle,
     #[allow(dead_code)]
-    drag_over_handle: web::EventListenerHandle,
+    drag_leave_handle: web::EventListenerHandle,
 }
 
 impl Manager {
@@ -148,24 +163,41 @@ impl Manager {
         let network = frp::Network::new("DropFileManager");
         frp::extend! { network
             files_received <- source();
+            files_hovered <- source();
         }
 
         let target: &web::EventTarget = dom.deref();
-        let drop: DropClosure = Closure::new(f!([files_received,scene](event:web_sys::DragEvent) {
-            debug!("Dropped files.");
-            event.prevent_default();
-            Self::handle_drop_event(event, &files_received, &scene);
-        }));
+        let drop: DropClosure =
+            Closure::new(f!([files_received,files_hovered,scene](event:web_sys::DragEvent) {
+                debug!("Dropped files.");
+                event.prevent_default();
+                files_hovered.emit(None);
+                Self::handle_drop_event(event, &files_received, &scene);
+            }));
         // To mark element as a valid drop target, the `dragover` event handler should return
         // `false`. See
         // https://developer.mozilla.org/en-US/docs/Web/API/HTML_Drag_and_Drop_API/File_drag_and_drop#define_the_drop_zone
-        let drag_over: DragOverClosure = Closure::new(|event: web_sys::DragEvent| {
-            event.prevent_default();
-            false
-        });
+        let drag_over: DragOverClosure =
+            Closure::new(f!([files_hovered,scene](event: web_sys::DragEvent) {
+                event.prevent_default();
+                let position = Self::event_position(&scene, &event);
+                let count = event.data_transfer().map_or(0, |t| t.items().length() as usize);
+                files_hovered.emit(Some(DragOverEventData { position, count }));
+                false
+            }));
+        let drag_leave: DragLeaveClosure =
+            Closure::new(f!([files_hovered](_event: web_sys::DragEvent) files_hovered.emit(None)));
         let drop_handle = web::add_event_listener(target, "drop", drop);
         let drag_over_handle = web::add_event_listener(target, "dragover", drag_over);
-        Self { network, files_received, drop_handle, drag_over_handle }
+        let drag_leave_handle = web::add_event_listener(target, "dragleave", drag_leave);
+        Self {
+            network,
+            files_received,
+            files_hovered,
+            drop_handle,
+            drag_over_handle,
+            drag_leave_handle,
+        }
     }
 
     /// The frp endpoint emitting signal when a file is dropped.
@@ -173,6 +205,12 @@ impl Manager {
         &self.files_received
     }
 
+    /// The frp endpoint emitting the dragged items while they are hovering over the target, and
+    /// [`None`] once they leave the target or are dropped.
+    pub fn files_hovered(&self) -> &frp::Source<Option<DragOverEventData>> {
+        &self.files_hovered
+    }
+
     /// Retrieve the position of the drop event in the scene coordinates.
     fn event_position(scene: &Scene, event: &web_sys::DragEvent) -> Vector2 {
         let dom: WithKnownShape<web::EventTarget> = scene.dom.root.clone_ref().into();
@@ -28,6 +28,12 @@ use ensogl_text as text;
 pub struct EntryParams {
     pub bg_color:            color::Lcha,
     pub bg_margin:           f32,
+    /// The background color of entries in odd rows. If `None`, all rows use `bg_color`.
+    pub stripe_color:        Option<color::Lcha>,
+    /// The color of a hairline drawn at the bottom of each entry. If `None`, no dividers are
+    /// drawn.
+    pub divider_color:       Option<color::Lcha>,
+    pub divider_width:       f32,
     pub hover_color:         color::Lcha,
     pub selection_color:     color::Lcha,
     pub font:                ImString,
@@ -42,6 +48,9 @@ impl Default for EntryParams {
         Self {
             bg_color:            color::Lcha::transparent(),
             bg_margin:           0.0,
+            stripe_color:        None,
+            divider_color:       None,
+            divider_width:       1.0,
             hover_color:         color::Lcha::from(color::Rgba(0.9, 0.9, 0.9, 1.0)),
             selection_color:     color::Lcha::from(color::Rgba(0.8, 0.8, 0.8, 1.0)),
             font:                text::font::DEFAULT_FONT.into(),
@@ -95,6 +104,7 @@ pub struct EntryData {
     display_object: display::object::Instance,
     pub label:      text::Text,
     pub background: entry::shape::View,
+    pub divider:    entry::shape::View,
 }
 
 impl EntryData {
@@ -103,12 +113,14 @@ impl EntryData {
         let label = app.new_view::<ensogl_text::Text>();
         label.set_long_text_truncation_mode(true);
         let background = entry::shape::View::new();
+        let divider = entry::shape::View::new();
         display_object.add_child(&label);
         display_object.add_child(&background);
+        display_object.add_child(&divider);
         if let Some(layer) = text_layer {
             layer.add(&label);
         }
-        Self { display_object, label, background }
+        Self { display_object, label, background, divider }
     }
 
     fn update_layout(&self, contour: entry::Contour, text_size: text::Size, text_offset: f32) {
@@ -116,6 +128,11 @@ impl EntryData {
         let size = contour.size;
         self.label.set_xy(Vector2(text_offset - size.x / 2.0, text_size.value / 2.0));
     }
+
+    fn update_divider(&self, contour: entry::Contour, width: f32) {
+        self.divider.set_size(Vector2(contour.size.x, width));
+        self.divider.set_y((width - contour.size.y) / 2.0);
+    }
 }
 
 
@@ -143,6 +160,9 @@ impl crate::Entry for Entry {
         enso_frp::extend! { network
             size <- input.set_size.on_change();
             bg_color <- input.set_params.map(|p| p.bg_color).on_change();
+            stripe_color <- input.set_params.map(|p| p.stripe_color).on_change();
+            divider_color <- input.set_params.map(|p| p.divider_color).on_change();
+            divider_width <- input.set_params.map(|p| p.divider_width).on_change();
             bg_margin <- input.set_params.map(|p| p.bg_margin).on_change();
             hover_color <- input.set_params.map(|p| p.hover_color).on_change();
             selection_color <- input.set_params.map(|p| p.selection_color).on_change();
@@ -158,7 +178,15 @@ impl crate::Entry for Entry {
             });
             layout <- all(contour, text_size, text_offset);
             eval layout ((&(c, ts, to)) data.update_layout(c, ts, to));
-            eval bg_color ((color) data.background.color.set(color::Rgba::from(color).into()));
+            is_odd_row <- input.set_location.map(|(row, _)| row % 2 == 1).on_change();
+            row_bg_color <- all_with3(&bg_color, &stripe_color, &is_odd_row, |bg, stripe, odd| {
+                stripe.filter(|_| *odd).unwrap_or(*bg)
+            });
+            eval row_bg_color ((color) data.background.color.set(color::Rgba::from(color).into()));
+            divider_layout <- all(contour, divider_width);
+            eval divider_layout ((&(c, w)) data.update_divider(c, w));
+            divider_color <- divider_color.map(|c| c.unwrap_or_else(color::Lcha::transparent));
+            eval divider_color ((color) data.divider.color.set(color::Rgba::from(color).into()));
             disabled <- input.set_model.map(|m| *m.disabled);
             data.label.set_property_default <+ all_with3(
                 &text_color,
@@ -0,0 +1,88 @@
+//! The context menu of [`ListView`] entries: a popup with the actions available for the
+//! right-clicked entry.
+
+use crate::prelude::*;
+
+use crate::entry;
+use crate::ListView;
+use crate::SHAPE_MARGIN;
+
+use enso_frp as frp;
+use ensogl_core::application::Application;
+use ensogl_core::display;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The width of the context menu.
+pub const WIDTH: f32 = 150.0;
+
+
+
+// ===================
+// === ContextMenu ===
+// ===================
+
+/// The popup with the actions available for a [`ListView`] entry, being a nested [`ListView`] of
+/// the action labels.
+///
+/// The [`ListView`] creates its menu when it is shown for the first time. Creating it together
+/// with the [`ListView`] would create the menu of the menu, and so on.
+#[derive(Clone, CloneRef, Debug)]
+pub struct ContextMenu {
+    pub(crate) list: ListView<entry::Label>,
+    _network:        frp::Network,
+    /// The entry for which the menu is shown.
+    shown_entry:     Rc<Cell<Option<entry::Id>>>,
+}
+
+impl ContextMenu {
+    /// Constructor. The index of the action chosen in the menu is emitted to `action_chosen`,
+    /// along with the entry for which the menu is shown.
+    pub fn new(app: &Application, action_chosen: &frp::Any<(entry::Id, usize)>) -> Self {
+        let list = ListView::new(app);
+        let network = frp::Network::new("list_view::ContextMenu");
+        let shown_entry: Rc<Cell<Option<entry::Id>>> = default();
+        frp::extend! { network
+            chosen_action <- list.chosen_entry.filter_map(f!([shown_entry](action)
+                Some((shown_entry.get()?, (*action)?))
+            ));
+            action_chosen <+ chosen_action;
+        }
+        Self { list, _network: network, shown_entry }
+    }
+
+    /// Show the menu of the `entry` with the top-left corner at the `position` in the `parent`'s
+    /// coordinates.
+    pub fn show(
+        &self,
+        parent: &impl display::Object,
+        entry: entry::Id,
+        actions: &[String],
+        position: Vector2,
+    ) {
+        let height = actions.len() as f32 * entry::HEIGHT + SHAPE_MARGIN * 2.0;
+        let size = Vector2(WIDTH, height);
+        self.shown_entry.set(Some(entry));
+        self.list.set_entries(entry::AnyModelProvider::new(actions.to_vec()));
+        self.list.resize(size);
+        self.list.set_xy(position + Vector2(size.x, -size.y) / 2.0);
+        parent.display_object().add_child(&self.list);
+        self.list.deprecated_focus();
+    }
+
+    /// Hide the menu.
+    pub fn hide(&self) {
+        self.shown_entry.set(None);
+        self.list.deprecated_defocus();
+        self.list.unset_parent();
+    }
+
+    /// Check if the menu is displayed.
+    pub fn is_shown(&self) -> bool {
+        self.shown_entry.get().is_some()
+    }
+}
@@ -14,6 +14,7 @@ use ensogl_text as text;
 // === Export ===
 // ==============
 
+pub mod checkbox;
 pub mod list;
 
 
@@ -22,7 +23,8 @@ pub mod list;
 // === Constants ===
 // =================
 
-/// The overall entry's height (including padding).
+/// The overall entry's height (including padding), unless the provider gives other height, see
+/// [`ModelProvider::height`].
 pub const HEIGHT: f32 = 30.0;
 
 
@@ -34,6 +36,11 @@ pub const HEIGHT: f32 = 30.0;
 /// Entry id. 0 is the first entry in component.
 pub type Id = usize;
 
+pub use checkbox::Checkbox;
+pub use checkbox::CheckboxModel;
+pub use checkbox::CheckboxParams;
+pub use checkbox::CheckState;
+pub use checkbox::Toggle;
 pub use list::List;
 
 
@@ -65,6 +72,11 @@ pub trait Entry: CloneRef + Debug + display::Object + 'static {
     /// An Object constructor.
     fn new(app: &Application, style_prefix: &Path, params: &Self::Params) -> Self;
 
+    /// Set the id of the displayed entry. It is called before [`update`](Self::update) when the
+    /// object is going to display another entry. The entries which do not emit events concerning
+    /// their id may ignore it.
+    fn set_id(&self, _id: Id) {}
+
     /// Update content with new model.
     fn update(&self, model: &Self::Model);
 
@@ -215,6 +227,84 @@ impl Entry for GlyphHighlightedLabel {
 }
 
 
+// === DisableableLabel ===
+
+/// The model for [`DisableableLabel`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DisableableLabelModel {
+    /// Displayed text.
+    pub label:    String,
+    /// Whether the entry is greyed out. The [`crate::ListView`] still allows choosing the disabled
+    /// entries, it is up to its user to ignore them.
+    pub disabled: bool,
+}
+
+impl DisableableLabelModel {
+    /// Constructor.
+    pub fn new(label: impl Into<String>, disabled: bool) -> Self {
+        Self { label: label.into(), disabled }
+    }
+}
+
+impl From<String> for DisableableLabelModel {
+    fn from(label: String) -> Self {
+        Self::new(label, false)
+    }
+}
+
+impl From<&str> for DisableableLabelModel {
+    fn from(label: &str) -> Self {
+        Self::new(label, false)
+    }
+}
+
+/// The [`Entry`] similar to the [`Label`], but displayed with the `text.disabled` style color when
+/// disabled.
+#[allow(missing_docs)]
+#[derive(Clone, CloneRef, Debug, display::Object)]
+pub struct DisableableLabel {
+    #[display_object]
+    pub inner: Label,
+    disabled:  frp::Source<bool>,
+}
+
+impl Entry for DisableableLabel {
+    type Model = DisableableLabelModel;
+    type Params = ();
+
+    fn new(app: &Application, style_prefix: &Path, (): &Self::Params) -> Self {
+        let inner = Label::new(app, style_prefix);
+        let network = &inner.network;
+        let text_style = style_prefix.sub("text");
+        let disabled_color = inner.style_watch.get_color(text_style.sub("disabled"));
+        let color = inner.style_watch.get_color(text_style);
+        let label = &inner.label;
+
+        frp::extend! { network
+            disabled <- source::<bool>();
+            color <- all_with3(&color, &disabled_color, &disabled,
+                |color, disabled_color, disabled| if *disabled { *disabled_color } else { *color }
+            );
+            label.set_property_default <+ color.ref_into_some();
+        }
+        Self { inner, disabled }
+    }
+
+    fn update(&self, model: &Self::Model) {
+        self.inner.update(&model.label);
+        self.disabled.emit(model.disabled);
+    }
+
+    fn set_max_width(&self, max_width_px: f32) {
+        self.inner.set_max_width(max_width_px);
+    }
+
+    fn set_label_layer(&self, layer: &display::scene::Layer) {
+        self.inner.set_label_layer(layer);
+    }
+}
+
+
 
 // =======================
 // === Model Providers ===
@@ -235,6 +325,61 @@ pub trait ModelProvider<E>: Debug {
     /// requested id greater or equal to entries count.
     fn get(&self, id: Id) -> Option<E::Model>
     where E: Entry;
+
+    /// The height of the entry with given id, including padding. The heights should not change
+    /// while the provider is set in [`crate::ListView`]; the positions of entries are computed from
+    /// them once.
+    fn height(&self, _id: Id) -> f32 {
+        HEIGHT
+    }
+
+    /// The provider as a [`DynamicModelProvider`], if it loads its entries on demand.
+    fn as_dynamic(&self) -> Option<&dyn DynamicModelProvider<E>> {
+        None
+    }
+}
+
+
+// === DynamicModelProvider ===
+
+/// The Model Provider which does not know the total number of its entries upfront, for example
+/// the search results streamed from the language server.
+///
+/// The [`ModelProvider::entry_count`] is the number of entries loaded so far. Until the provider is
+/// complete, the [`crate::ListView`] displays a spinner below the loaded entries, and calls
+/// [`request_more`](Self::request_more) when the view approaches them. Once the requested entries
+/// are loaded, the `entries_loaded` input of the ListView should be emitted.
+pub trait DynamicModelProvider<E>: ModelProvider<E> {
+    /// Whether all the entries are loaded.
+    fn is_complete(&self) -> bool;
+
+    /// Request loading the entries following the loaded ones. The `from` is the id of the first
+    /// entry requested.
+    fn request_more(&self, from: Id);
+}
+
+/// A wrapper making the [`DynamicModelProvider`] available through
+/// [`ModelProvider::as_dynamic`].
+#[derive(Debug)]
+struct Dynamic<T>(T);
+
+impl<E, T: DynamicModelProvider<E>> ModelProvider<E> for Dynamic<T> {
+    fn entry_count(&self) -> usize {
+        self.0.entry_count()
+    }
+
+    fn get(&self, id: Id) -> Option<E::Model>
+    where E: Entry {
+        self.0.get(id)
+    }
+
+    fn height(&self, id: Id) -> f32 {
+        self.0.height(id)
+    }
+
+    fn as_dynamic(&self) -> Option<&dyn DynamicModelProvider<E>> {
+        Some(&self.0)
+    }
 }
 
 
@@ -260,6 +405,11 @@ impl<E> AnyModelProvider<E> {
     pub fn new<T: ModelProvider<E> + 'static>(provider: T) -> Self {
         Self(Rc::new(provider))
     }
+
+    /// Create from typed provider loading its entries on demand.
+    pub fn new_dynamic<T: DynamicModelProvider<E> + 'static>(provider: T) -> Self {
+        Self(Rc::new(Dynamic(provider)))
+    }
 }
 
 impl<E, T: ModelProvider<E> + 'static> From<Rc<T>> for AnyModelProvider<E> {
@@ -311,6 +461,71 @@ where
 }
 
 
+// === StreamedProvider ===
+
+/// A [`DynamicModelProvider`] keeping the loaded models in a vector. The owner appends the
+/// entries with [`extend`](Self::extend) as they arrive, and marks the end of the stream with
+/// [`finish`](Self::finish). Requests for more entries are passed to the callback given in the
+/// constructor.
+#[derive(CloneRef, Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = "T: Debug"))]
+pub struct StreamedProvider<T> {
+    models:       Rc<RefCell<Vec<T>>>,
+    complete:     Rc<Cell<bool>>,
+    #[derivative(Debug = "ignore")]
+    request_more: Rc<dyn Fn(Id)>,
+}
+
+impl<T> StreamedProvider<T> {
+    /// Constructor. The `request_more` callback is called with the id of the first requested
+    /// entry.
+    pub fn new(request_more: impl Fn(Id) + 'static) -> Self {
+        let models = default();
+        let complete = default();
+        let request_more = Rc::new(request_more);
+        Self { models, complete, request_more }
+    }
+
+    /// Append the loaded models.
+    pub fn extend(&self, models: impl IntoIterator<Item = T>) {
+        self.models.borrow_mut().extend(models);
+    }
+
+    /// Mark all the entries as loaded.
+    pub fn finish(&self) {
+        self.complete.set(true);
+    }
+}
+
+impl<E, T> ModelProvider<E> for StreamedProvider<T>
+where
+    E: Entry,
+    T: Debug + Clone + Into<E::Model>,
+{
+    fn entry_count(&self) -> usize {
+        self.models.borrow().len()
+    }
+
+    fn get(&self, id: Id) -> Option<E::Model> {
+        Some(self.models.borrow().get(id)?.clone().into())
+    }
+}
+
+impl<E, T> DynamicModelProvider<E> for StreamedProvider<T>
+where
+    E: Entry,
+    T: Debug + Clone + Into<E::Model>,
+{
+    fn is_complete(&self) -> bool {
+        self.complete.get()
+    }
+
+    fn request_more(&self, from: Id) {
+        (self.request_more)(from)
+    }
+}
+
+
 // === SingleMaskedProvider ===
 
 /// An Entry Model Provider that wraps a `AnyModelProvider` and allows the masking of a single item.
@@ -333,6 +548,10 @@ impl<E: Debug> ModelProvider<E> for SingleMaskedProvider<E> {
         let internal_ix = self.unmasked_index(ix);
         self.content.get(internal_ix)
     }
+
+    fn height(&self, ix: usize) -> f32 {
+        self.content.height(self.unmasked_index(ix))
+    }
 }
 
 impl<E> SingleMaskedProvider<E> {
@@ -437,4 +656,26 @@ mod tests {
         assert_eq!(provider.get(1).unwrap(), "B");
         assert_eq!(provider.get(2).unwrap(), "C");
     }
+
+    #[test]
+    fn test_streamed_provider() {
+        let requests = Rc::new(RefCell::new(Vec::new()));
+        let requests_log = requests.clone();
+        let streamed = StreamedProvider::new(move |from| requests_log.borrow_mut().push(from));
+        let provider = AnyModelProvider::<Label>::new_dynamic(streamed.clone_ref());
+        let dynamic = provider.as_dynamic().expect("Streamed provider should be dynamic.");
+        assert_eq!(provider.entry_count(), 0);
+        assert!(!dynamic.is_complete());
+
+        dynamic.request_more(0);
+        streamed.extend(["A", "B"]);
+        assert_eq!(provider.entry_count(), 2);
+        assert_eq!(provider.get(1).unwrap(), "B");
+        assert_eq!(provider.get(2), None);
+        dynamic.request_more(2);
+        streamed.finish();
+        assert!(dynamic.is_complete());
+        assert_eq!(*requests.borrow(), vec![0, 2]);
+        assert!(AnyModelProvider::<Label>::new(vec!["A"]).as_dynamic().is_none());
+    }
 }
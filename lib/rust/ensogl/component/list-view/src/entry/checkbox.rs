@@ -0,0 +1,416 @@
+//! The [`Checkbox`] and [`Toggle`] entries: labels with a control switching a setting on and off.
+//!
+//! Clicking the control does not choose the entry in the [`crate::ListView`]. Instead, the new
+//! state is emitted by the [`CheckboxParams::toggled`] stream of the entries' params.
+
+use crate::prelude::*;
+
+use crate::entry;
+use crate::entry::Entry;
+use crate::entry::Label;
+
+use enso_frp as frp;
+use ensogl_core::application::Application;
+use ensogl_core::data::color;
+use ensogl_core::display;
+use ensogl_core::display::shape::Rectangle;
+use ensogl_core::display::style::Path;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The corner radius of the checkbox control.
+const CHECKBOX_CORNER_RADIUS: f32 = 2.0;
+/// The width of the checkbox control's border.
+const CHECKBOX_BORDER_WIDTH: f32 = 1.0;
+/// The height of the mark of the indeterminate checkbox.
+const INDETERMINATE_MARK_HEIGHT: f32 = 2.0;
+/// The distance between the toggle control's knob and its edge.
+const TOGGLE_KNOB_MARGIN: f32 = 2.0;
+
+
+
+// ==================
+// === CheckState ===
+// ==================
+
+/// The state of a [`Checkbox`] or [`Toggle`] entry.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum CheckState {
+    /// The setting is off.
+    #[default]
+    Unchecked,
+    /// The setting is on.
+    Checked,
+    /// Neither checked nor unchecked, e.g. when the entry represents a group of settings, some of
+    /// which are enabled.
+    Indeterminate,
+}
+
+impl CheckState {
+    /// The state after the user clicks the control. The indeterminate entry becomes checked.
+    pub fn toggled(self) -> Self {
+        match self {
+            Self::Checked => Self::Unchecked,
+            Self::Unchecked | Self::Indeterminate => Self::Checked,
+        }
+    }
+
+    /// Check if the state is [`CheckState::Checked`].
+    pub fn is_checked(self) -> bool {
+        self == Self::Checked
+    }
+}
+
+impl From<bool> for CheckState {
+    fn from(checked: bool) -> Self {
+        if checked {
+            Self::Checked
+        } else {
+            Self::Unchecked
+        }
+    }
+}
+
+
+
+// =====================
+// === CheckboxModel ===
+// =====================
+
+/// The model of [`Checkbox`] and [`Toggle`] entries.
+#[derive(Clone, Debug, Default)]
+pub struct CheckboxModel {
+    /// Displayed text.
+    pub label: String,
+    /// The state of the control.
+    pub state: CheckState,
+}
+
+impl CheckboxModel {
+    /// Constructor.
+    pub fn new(label: impl Into<String>, state: impl Into<CheckState>) -> Self {
+        Self { label: label.into(), state: state.into() }
+    }
+}
+
+
+
+// ======================
+// === CheckboxParams ===
+// ======================
+
+/// The params of [`Checkbox`] and [`Toggle`] entries, shared by all entries of the
+/// [`crate::ListView`]. Use [`crate::ListView::entry_params`] to get the stream of the changes.
+#[derive(Clone, CloneRef, Debug)]
+pub struct CheckboxParams {
+    _network:    frp::Network,
+    /// The id of the entry whose control was clicked, and whether it is checked now.
+    pub toggled: frp::Any<(entry::Id, bool)>,
+}
+
+impl Default for CheckboxParams {
+    fn default() -> Self {
+        let network = frp::Network::new("list_view::entry::CheckboxParams");
+        frp::extend! { network
+            toggled <- any(...);
+        }
+        Self { _network: network, toggled }
+    }
+}
+
+
+
+// ===============
+// === Control ===
+// ===============
+
+/// The kind of the control displayed in the entry.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ControlKind {
+    Checkbox,
+    Toggle,
+}
+
+impl ControlKind {
+    fn style_name(self) -> &'static str {
+        match self {
+            Self::Checkbox => "checkbox",
+            Self::Toggle => "toggle",
+        }
+    }
+}
+
+/// The style of the control, read from the `checkbox` or `toggle` path of the ListView's style.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ControlStyle {
+    on_color:   color::Rgba,
+    off_color:  color::Rgba,
+    mark_color: color::Rgba,
+    size:       Vector2,
+    /// The distance between the control and the label.
+    gap:        f32,
+}
+
+/// The shapes of the control: the `frame` being the checkbox or the toggle's track, and the `mark`
+/// being the checkbox's tick or the toggle's knob.
+#[derive(Clone, CloneRef, Debug)]
+struct Control {
+    frame: Rectangle,
+    mark:  Rectangle,
+}
+
+impl Control {
+    fn new(parent: &impl display::Object) -> Self {
+        let frame = Rectangle();
+        let mark = Rectangle();
+        mark.set_pointer_events(false);
+        parent.display_object().add_child(&frame);
+        parent.display_object().add_child(&mark);
+        Self { frame, mark }
+    }
+
+    fn update(&self, kind: ControlKind, style: &ControlStyle, state: CheckState) {
+        let size = style.size;
+        self.frame.set_size(size);
+        self.frame.set_xy(Vector2(0.0, -size.y / 2.0));
+        match kind {
+            ControlKind::Checkbox => self.update_checkbox(style, state),
+            ControlKind::Toggle => self.update_toggle(style, state),
+        }
+    }
+
+    fn update_checkbox(&self, style: &ControlStyle, state: CheckState) {
+        let size = style.size;
+        let filled = state != CheckState::Unchecked;
+        let fill_color = if filled { style.on_color } else { color::Rgba::transparent() };
+        let border_color = if filled { style.on_color } else { style.off_color };
+        self.frame.set_corner_radius(CHECKBOX_CORNER_RADIUS);
+        self.frame.set_border_and_inset(CHECKBOX_BORDER_WIDTH);
+        self.frame.set_border_color(border_color);
+        self.frame.set_color(fill_color);
+        let mark_size = match state {
+            CheckState::Unchecked => Vector2(0.0, 0.0),
+            CheckState::Checked => size / 2.0,
+            CheckState::Indeterminate => Vector2(size.x / 2.0, INDETERMINATE_MARK_HEIGHT),
+        };
+        self.mark.set_size(mark_size);
+        self.mark.set_xy(Vector2(size.x / 2.0, 0.0) - mark_size / 2.0);
+        self.mark.set_color(style.mark_color);
+    }
+
+    fn update_toggle(&self, style: &ControlStyle, state: CheckState) {
+        let size = style.size;
+        let track_color = if state.is_checked() { style.on_color } else { style.off_color };
+        self.frame.set_corner_radius_max();
+        self.frame.set_color(track_color);
+        let knob_diameter = size.y - 2.0 * TOGGLE_KNOB_MARGIN;
+        let knob_x = match state {
+            CheckState::Unchecked => TOGGLE_KNOB_MARGIN,
+            CheckState::Checked => size.x - TOGGLE_KNOB_MARGIN - knob_diameter,
+            CheckState::Indeterminate => (size.x - knob_diameter) / 2.0,
+        };
+        self.mark.set_size(Vector2(knob_diameter, knob_diameter));
+        self.mark.set_corner_radius_max();
+        self.mark.set_xy(Vector2(knob_x, -knob_diameter / 2.0));
+        self.mark.set_color(style.mark_color);
+    }
+}
+
+
+
+// =======================
+// === ToggleableLabel ===
+// =======================
+
+/// The implementation of [`Checkbox`] and [`Toggle`] entries: a [`Label`] preceded by a control.
+#[allow(missing_docs)]
+#[derive(Clone, CloneRef, Debug, display::Object)]
+pub struct ToggleableLabel {
+    #[display_object]
+    pub inner:    Label,
+    control:      Control,
+    id:           Rc<Cell<Option<entry::Id>>>,
+    state:        frp::Source<CheckState>,
+    max_width_px: frp::Source<f32>,
+}
+
+impl ToggleableLabel {
+    fn new(
+        app: &Application,
+        style_prefix: &Path,
+        params: &CheckboxParams,
+        kind: ControlKind,
+    ) -> Self {
+        let inner = Label::new(app, style_prefix);
+        let control = Control::new(&inner);
+        let id: Rc<Cell<Option<entry::Id>>> = default();
+        let network = &inner.network;
+        let label = &inner.label;
+        let style_path = style_prefix.sub(kind.style_name());
+        let style_watch = &inner.style_watch;
+        let on_color = style_watch.get_color(&style_path);
+        let off_color = style_watch.get_color(style_path.sub("off"));
+        let mark_color = style_watch.get_color(style_path.sub("mark"));
+        let width = style_watch.get_number(style_path.sub("width"));
+        let height = style_watch.get_number(style_path.sub("height"));
+        let gap = style_watch.get_number(style_path.sub("gap"));
+        let toggled = &params.toggled;
+
+        frp::extend! { network
+            init <- source_();
+            set_state <- source::<CheckState>();
+            state <- any(...);
+            state <+ set_state;
+            max_width_px <- source::<f32>();
+            colors <- all3(&on_color, &off_color, &mark_color);
+            dimensions <- all3(&width, &height, &gap);
+            style <- all_with3(&colors, &dimensions, &init,
+                |&(on_color, off_color, mark_color), &(width, height, gap), _| ControlStyle {
+                    on_color,
+                    off_color,
+                    mark_color,
+                    size: Vector2(width, height),
+                    gap,
+                }
+            );
+            appearance <- all(style, state);
+            eval appearance ([control]((style, state)) control.update(kind, style, *state));
+            label_x <- style.map(|style| style.size.x + style.gap);
+            eval label_x ((x) label.set_x(*x));
+            label.set_view_width <+ all_with(&max_width_px, &label_x, |width, x| Some(width - x));
+
+            new_state <- state.sample(&control.frame.events_deprecated.mouse_down_primary);
+            new_state <- new_state.map(|state| state.toggled());
+            state <+ new_state;
+            toggled <+ new_state.filter_map(f!([id](state) Some((id.get()?, state.is_checked()))));
+        }
+        init.emit(());
+        Self { inner, control, id, state: set_state, max_width_px }
+    }
+
+    fn update(&self, model: &CheckboxModel) {
+        self.inner.update(&model.label);
+        self.state.emit(model.state);
+    }
+}
+
+
+
+// ================
+// === Checkbox ===
+// ================
+
+/// The [`Entry`] being a label preceded by a checkbox.
+#[allow(missing_docs)]
+#[derive(Clone, CloneRef, Debug, display::Object)]
+pub struct Checkbox {
+    #[display_object]
+    pub inner: ToggleableLabel,
+}
+
+impl Entry for Checkbox {
+    type Model = CheckboxModel;
+    type Params = CheckboxParams;
+
+    fn new(app: &Application, style_prefix: &Path, params: &Self::Params) -> Self {
+        let inner = ToggleableLabel::new(app, style_prefix, params, ControlKind::Checkbox);
+        Self { inner }
+    }
+
+    fn set_id(&self, id: entry::Id) {
+        self.inner.id.set(Some(id));
+    }
+
+    fn update(&self, model: &Self::Model) {
+        self.inner.update(model);
+    }
+
+    fn set_max_width(&self, max_width_px: f32) {
+        self.inner.max_width_px.emit(max_width_px);
+    }
+
+    fn set_label_layer(&self, label_layer: &display::scene::Layer) {
+        self.inner.inner.set_label_layer(label_layer);
+    }
+}
+
+
+
+// ==============
+// === Toggle ===
+// ==============
+
+/// The [`Entry`] being a label preceded by a toggle switch.
+#[allow(missing_docs)]
+#[derive(Clone, CloneRef, Debug, display::Object)]
+pub struct Toggle {
+    #[display_object]
+    pub inner: ToggleableLabel,
+}
+
+impl Entry for Toggle {
+    type Model = CheckboxModel;
+    type Params = CheckboxParams;
+
+    fn new(app: &Application, style_prefix: &Path, params: &Self::Params) -> Self {
+        let inner = ToggleableLabel::new(app, style_prefix, params, ControlKind::Toggle);
+        Self { inner }
+    }
+
+    fn set_id(&self, id: entry::Id) {
+        self.inner.id.set(Some(id));
+    }
+
+    fn update(&self, model: &Self::Model) {
+        self.inner.update(model);
+    }
+
+    fn set_max_width(&self, max_width_px: f32) {
+        self.inner.max_width_px.emit(max_width_px);
+    }
+
+    fn set_label_layer(&self, label_layer: &display::scene::Layer) {
+        self.inner.inner.set_label_layer(label_layer);
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use enso_frp::future::EventOutputExt;
+
+    #[test]
+    fn toggling_state() {
+        assert_eq!(CheckState::Unchecked.toggled(), CheckState::Checked);
+        assert_eq!(CheckState::Checked.toggled(), CheckState::Unchecked);
+        assert_eq!(CheckState::Indeterminate.toggled(), CheckState::Checked);
+    }
+
+    #[test]
+    fn clicking_control_emits_change() {
+        let app = Application::new("root");
+        let params = CheckboxParams::default();
+        let checkbox = <Checkbox as Entry>::new(&app, &Path::from("list_view"), &params);
+        checkbox.set_id(3);
+        checkbox.update(&CheckboxModel::new("Enabled", CheckState::Indeterminate));
+        let click = &checkbox.inner.control.frame.events_deprecated.mouse_down_primary;
+
+        let toggled = params.toggled.next_event();
+        click.emit(());
+        assert_eq!(toggled.expect(), (3, true));
+        let toggled = params.toggled.next_event();
+        click.emit(());
+        assert_eq!(toggled.expect(), (3, false));
+    }
+}
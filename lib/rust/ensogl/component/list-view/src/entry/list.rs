@@ -6,13 +6,30 @@ use crate::entry;
 use crate::entry::Entry;
 
 use ensogl_core::application::Application;
+use ensogl_core::data::color;
 use ensogl_core::display;
 use ensogl_core::display::scene::layer::Layer;
 use ensogl_core::display::scene::layer::WeakLayer;
+use ensogl_core::display::shape::Rectangle;
 use ensogl_core::display::style;
 
 
 
+// =================
+// === Constants ===
+// =================
+
+/// The number of entries below the visible ones which should be loaded. When a dynamic provider
+/// has fewer entries loaded, more are requested.
+pub const PREFETCHED_ENTRIES: usize = 10;
+/// The scale of the spinner displayed below the entries of an incomplete dynamic provider.
+const SPINNER_SCALE: f32 = 2.0;
+/// The y coordinate of the top of the first entry, relative to Entry List position. With entries
+/// of the default height, the first entry is centered at the List position.
+const TOP_Y: f32 = entry::HEIGHT / 2.0;
+
+
+
 // ======================
 // === DisplayedEntry ===
 // ======================
@@ -26,8 +43,27 @@ use ensogl_core::display::style;
 #[derive(Clone, CloneRef, Debug)]
 #[clone_ref(bound = "E:CloneRef")]
 pub struct DisplayedEntry<E> {
-    pub id:    Rc<Cell<Option<entry::Id>>>,
-    pub entry: E,
+    pub id:         Rc<Cell<Option<entry::Id>>>,
+    pub entry:      E,
+    pub background: Rectangle,
+    pub divider:    Rectangle,
+}
+
+
+
+// =====================
+// === RowDecoration ===
+// =====================
+
+/// The decorations drawn under the displayed entries to improve readability of long lists.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct RowDecoration {
+    /// The background color of entries with odd ids. If `None`, no zebra striping is drawn.
+    pub stripe_color:  Option<color::Rgba>,
+    /// The color of a hairline drawn between adjacent entries. If `None`, no dividers are drawn.
+    pub divider_color: Option<color::Rgba>,
+    /// The width of the hairline between adjacent entries.
+    pub divider_width: f32,
 }
 
 
@@ -74,6 +110,17 @@ pub struct ListData<E, P> {
     entry_params:   Rc<RefCell<P>>,
     provider:       Rc<CloneRefCell<entry::AnyModelProvider<E>>>,
     label_layer:    Rc<RefCell<WeakLayer>>,
+    row_decoration: Rc<Cell<RowDecoration>>,
+    entry_width:    Rc<Cell<f32>>,
+    /// The distances between the top of the list and the tops of the entries, followed by the
+    /// total height of the entries. These are the prefix sums of the entries' heights, cached to
+    /// not ask the provider for heights of all preceding entries.
+    offsets:        Rc<RefCell<Vec<f32>>>,
+    /// The spinner displayed below the entries of an incomplete dynamic provider.
+    spinner:        ensogl_spinner::View,
+    /// The id of the first entry requested from the dynamic provider the last time, used to not
+    /// request the same entries again.
+    requested_from: Rc<Cell<Option<entry::Id>>>,
 }
 
 impl<E, P: Default> ListData<E, P> {
@@ -87,7 +134,26 @@ impl<E, P: Default> ListData<E, P> {
         let provider = default();
         let label_layer =
             Rc::new(RefCell::new(app.display.default_scene.layers.above_nodes_text.downgrade()));
-        Self { app, display_object, entries, entries_range, entry_params, provider, label_layer }
+        let row_decoration = default();
+        let entry_width = default();
+        let offsets = Rc::new(RefCell::new(vec![0.0]));
+        let spinner = ensogl_spinner::View::new();
+        spinner.scale.set(SPINNER_SCALE);
+        let requested_from = default();
+        Self {
+            app,
+            display_object,
+            entries,
+            entries_range,
+            entry_params,
+            provider,
+            label_layer,
+            row_decoration,
+            entry_width,
+            offsets,
+            spinner,
+            requested_from,
+        }
     }
 }
 
@@ -97,43 +163,88 @@ impl<E, P> ListData<E, P> {
         self.provider.get().entry_count()
     }
 
+    /// Check if all entries are known. Only a dynamic provider may be incomplete, see
+    /// [`entry::DynamicModelProvider`].
+    pub fn is_complete(&self) -> bool {
+        self.provider.get().as_dynamic().map_or(true, |provider| provider.is_complete())
+    }
+
     /// The number of all displayed entries in List.
     pub fn visible_entry_count(&self) -> usize {
         self.entries_range.get().len()
     }
 
+    /// Update the cached offsets of the entries after the number of the provider's entries
+    /// changed. The heights of the already cached entries are assumed to not change; the provider
+    /// with changed heights should be set again.
+    pub fn update_offsets(&self) {
+        let provider = self.provider.get();
+        let entry_count = provider.entry_count();
+        let cached_count = self.offsets.borrow().len() - 1;
+        if entry_count < cached_count {
+            self.offsets.borrow_mut().truncate(entry_count + 1);
+        } else if entry_count > cached_count {
+            // The provider is provided by user, so we should not keep any borrow when calling its
+            // methods.
+            let heights = (cached_count..entry_count).map(|id| provider.height(id)).collect_vec();
+            let mut offsets = self.offsets.borrow_mut();
+            let mut offset = offsets.last().copied().unwrap_or_default();
+            for height in heights {
+                offset += height;
+                offsets.push(offset);
+            }
+        }
+    }
+
+    /// The distance between the top of the list and the top of the entry with given id. The
+    /// entries not cached yet are assumed to have the default [`entry::HEIGHT`].
+    fn offset_of_entry(&self, id: entry::Id) -> f32 {
+        let offsets = self.offsets.borrow();
+        let cached_count = offsets.len() - 1;
+        match offsets.get(id) {
+            Some(offset) => *offset,
+            None => offsets[cached_count] + (id - cached_count) as f32 * entry::HEIGHT,
+        }
+    }
+
+    /// The height of entry with given id.
+    pub fn height_of_entry(&self, id: entry::Id) -> f32 {
+        self.offset_of_entry(id + 1) - self.offset_of_entry(id)
+    }
+
     /// Y position of entry with given id, relative to Entry List position.
-    pub fn position_y_of_entry(id: entry::Id) -> f32 {
-        id as f32 * -entry::HEIGHT
+    pub fn position_y_of_entry(&self, id: entry::Id) -> f32 {
+        let range = self.y_range_of_entry(id);
+        (range.start + range.end) / 2.0
     }
 
     /// Y range of entry with given id, relative to Entry List position.
-    pub fn y_range_of_entry(id: entry::Id) -> Range<f32> {
-        let position = Self::position_y_of_entry(id);
-        (position - entry::HEIGHT / 2.0)..(position + entry::HEIGHT / 2.0)
+    pub fn y_range_of_entry(&self, id: entry::Id) -> Range<f32> {
+        let top = TOP_Y - self.offset_of_entry(id);
+        (top - self.height_of_entry(id))..top
     }
 
     /// Y range of all entries in this list, including not displayed.
-    pub fn y_range_of_all_entries(entry_count: usize) -> Range<f32> {
-        let start = if entry_count > 0 {
-            Self::position_y_of_entry(entry_count - 1) - entry::HEIGHT / 2.0
-        } else {
-            entry::HEIGHT / 2.0
-        };
-        let end = entry::HEIGHT / 2.0;
-        start..end
+    pub fn y_range_of_all_entries(&self) -> Range<f32> {
+        let total_height = self.offsets.borrow().last().copied().unwrap_or_default();
+        (TOP_Y - total_height)..TOP_Y
     }
 
     /// Get the entry id which lays on given y coordinate.
-    pub fn entry_at_y_position(y: f32, entry_count: usize) -> IdAtYPosition {
+    pub fn entry_at_y_position(&self, y: f32) -> IdAtYPosition {
         use IdAtYPosition::*;
-        let all_entries_start = Self::y_range_of_all_entries(entry_count).start;
-        if y > entry::HEIGHT / 2.0 {
-            AboveFirst
-        } else if y < all_entries_start {
-            UnderLast
-        } else {
-            Entry((-y / entry::HEIGHT + 0.5) as entry::Id)
+        let offsets = self.offsets.borrow();
+        let offset = TOP_Y - y;
+        let total_height = offsets.last().copied().unwrap_or_default();
+        let last_entry = offsets.len().checked_sub(2);
+        match last_entry {
+            _ if offset < 0.0 => AboveFirst,
+            Some(last_entry) if offset <= total_height => {
+                // The offsets are sorted, so the entry may be found with binary search.
+                let id = offsets.partition_point(|entry_offset| *entry_offset <= offset) - 1;
+                Entry(id.min(last_entry))
+            }
+            _ => UnderLast,
         }
     }
 }
@@ -146,6 +257,67 @@ impl<E: Entry, P> ListData<E, P> {
         }
         self.label_layer.replace(label_layer.downgrade());
     }
+
+    /// Set the zebra striping and row dividers drawn under the displayed entries.
+    pub fn set_row_decoration(&self, decoration: RowDecoration) {
+        self.row_decoration.set(decoration);
+        self.update_row_decorations();
+    }
+
+    /// Set the color of the spinner displayed below the entries of an incomplete dynamic provider.
+    pub fn set_spinner_color(&self, color: color::Rgba) {
+        self.spinner.rgba.set(color.into());
+    }
+
+    /// Show the spinner below the last entry if the provider is incomplete.
+    fn update_spinner(&self) {
+        if self.is_complete() {
+            self.spinner.unset_parent();
+        } else {
+            let width = self.entry_width.get();
+            let y = self.position_y_of_entry(self.entry_count());
+            self.spinner.set_size(Vector2(width, entry::HEIGHT));
+            self.spinner.set_xy(Vector2(width / 2.0, y));
+            self.add_child(&self.spinner);
+        }
+    }
+
+    /// Request more entries from an incomplete dynamic provider, if the displayed `range` is
+    /// closer to the end of loaded entries than [`PREFETCHED_ENTRIES`].
+    fn request_more_entries_if_needed(&self, range: &Range<entry::Id>) {
+        let provider = self.provider.get();
+        let loaded = provider.entry_count();
+        let incomplete = provider.as_dynamic().filter(|provider| !provider.is_complete());
+        let near_end = range.end + PREFETCHED_ENTRIES >= loaded;
+        let already_requested = self.requested_from.get() == Some(loaded);
+        if let Some(provider) = incomplete && near_end && !already_requested {
+            self.requested_from.set(Some(loaded));
+            provider.request_more(loaded);
+        }
+    }
+
+    /// Update backgrounds and dividers of all displayed entries to match their current ids.
+    fn update_row_decorations(&self) {
+        let decoration = self.row_decoration.get();
+        let width = self.entry_width.get();
+        let last_id = self.entry_count().checked_sub(1);
+        for entry in &*self.entries.borrow() {
+            let id = entry.id.get();
+            let row_bottom = id.map_or(0.0, |id| self.y_range_of_entry(id).start);
+            let row_height = id.map_or(entry::HEIGHT, |id| self.height_of_entry(id));
+            let is_odd = id.map_or(false, |id| id % 2 == 1);
+            let stripe_color = decoration.stripe_color.filter(|_| is_odd);
+            entry.background.set_color(stripe_color.unwrap_or_else(color::Rgba::transparent));
+            entry.background.set_size(Vector2(width, row_height));
+            entry.background.set_y(row_bottom);
+            let is_last = id.is_none() || id == last_id;
+            let divider_color = decoration.divider_color.filter(|_| !is_last);
+            let divider_width = decoration.divider_width;
+            entry.divider.set_color(divider_color.unwrap_or_else(color::Rgba::transparent));
+            entry.divider.set_size(Vector2(width, divider_width));
+            entry.divider.set_y(row_bottom - divider_width / 2.0);
+        }
+    }
 }
 
 impl<E: Entry> ListData<E, E::Params> {
@@ -158,6 +330,7 @@ impl<E: Entry> ListData<E, E::Params> {
         max_width_px: f32,
         style_prefix: &style::Path,
     ) {
+        self.update_offsets();
         range.end = range.end.min(self.provider.get().entry_count());
         if range != self.entries_range.get() {
             debug!("Update entries for {range:?}");
@@ -176,7 +349,7 @@ impl<E: Entry> ListData<E, E::Params> {
                     |e: &DisplayedEntry<E>| e.id.get().map_or(true, |i| !range.contains(&i));
                 let outdated = entries.iter().filter(|e| is_outdated(e));
                 for (entry, (id, model)) in outdated.zip(models) {
-                    Self::update_entry(entry, id, &model);
+                    self.update_entry(entry, id, &model);
                 }
             });
             self.entries_range.set(range);
@@ -184,6 +357,10 @@ impl<E: Entry> ListData<E, E::Params> {
         for entry in self.entries.borrow().iter() {
             entry.entry.set_max_width(max_width_px);
         }
+        self.entry_width.set(max_width_px);
+        self.update_row_decorations();
+        self.update_spinner();
+        self.request_more_entries_if_needed(&self.entries_range.get());
     }
 
     /// Recreate the displayed entries to make them use the styles located at the `style_prefix`
@@ -196,10 +373,12 @@ impl<E: Entry> ListData<E, E::Params> {
             let new_entry = self.create_new_entry(&style_prefix);
             if let Some(id) = entry.id.get() {
                 let model = provider.get(id);
-                Self::update_entry(&new_entry, id, &model);
+                self.update_entry(&new_entry, id, &model);
             }
             *entry = new_entry;
         }
+        drop(entries);
+        self.update_row_decorations();
     }
 
     /// Set params used in the displayed entries and recreate all displayed entries. The entries
@@ -218,16 +397,10 @@ impl<E: Entry> ListData<E, E::Params> {
         self.entry_params.borrow().clone_ref()
     }
 
-    /// Update displayed entries, giving new provider. New entries created by the function have
-    /// their maximum width set to `max_width_px` and use the styles located at the `style_prefix`
-    /// path.
-    pub fn update_entries_new_provider(
-        &self,
-        provider: impl Into<entry::AnyModelProvider<E>> + 'static,
-        mut range: Range<entry::Id>,
-        max_width_px: f32,
-        style_prefix: style::Path,
-    ) {
+    /// Set the new provider and update the cached entry offsets. The displayed entries are not
+    /// updated until [`update_all_entries`](Self::update_all_entries) is called, which may be
+    /// given the range of entries computed with the new offsets.
+    pub fn set_provider(&self, provider: impl Into<entry::AnyModelProvider<E>> + 'static) {
         const MAX_SAFE_ENTRIES_COUNT: usize = 1000;
         let provider = provider.into();
         if provider.entry_count() > MAX_SAFE_ENTRIES_COUNT {
@@ -237,8 +410,24 @@ impl<E: Entry> ListData<E, E::Params> {
             issues/757 or https://github.com/enso-org/ide/issues/758"
             );
         }
+        self.provider.set(provider);
+        self.offsets.replace(vec![0.0]);
+        self.requested_from.set(None);
+        self.update_offsets();
+    }
+
+    /// Update all displayed entries to show the given range, after setting a new provider with
+    /// [`set_provider`](Self::set_provider). New entries created by the function have their maximum
+    /// width set to `max_width_px` and use the styles located at the `style_prefix` path.
+    pub fn update_all_entries(
+        &self,
+        mut range: Range<entry::Id>,
+        max_width_px: f32,
+        style_prefix: style::Path,
+    ) {
+        let provider = self.provider.get();
         range.end = range.end.min(provider.entry_count());
-        let models = range.clone().map(|id| (id, provider.get(id)));
+        let models = range.clone().map(|id| (id, provider.get(id))).collect_vec();
         let mut entries = self.entries.borrow_mut();
         let create_new_entry_with_max_width = || {
             let entry = self.create_new_entry(&style_prefix);
@@ -247,10 +436,14 @@ impl<E: Entry> ListData<E, E::Params> {
         };
         entries.resize_with(range.len(), create_new_entry_with_max_width);
         for (entry, (id, model)) in entries.iter().zip(models) {
-            Self::update_entry(entry, id, &model);
+            self.update_entry(entry, id, &model);
         }
-        self.entries_range.set(range);
-        self.provider.set(provider);
+        self.entries_range.set(range.clone());
+        drop(entries);
+        self.entry_width.set(max_width_px);
+        self.update_row_decorations();
+        self.update_spinner();
+        self.request_more_entries_if_needed(&range);
     }
 
     fn create_new_entry(&self, style_prefix: &style::Path) -> DisplayedEntry<E> {
@@ -263,15 +456,23 @@ impl<E: Entry> ListData<E, E::Params> {
             layers.main.clone_ref()
         });
         let entry = E::new(&self.app, style_prefix, &self.entry_params.borrow());
-        let entry = DisplayedEntry { id: default(), entry };
+        let background = Rectangle();
+        let divider = Rectangle();
+        for shape in [&background, &divider] {
+            shape.set_pointer_events(false);
+            shape.set_color(color::Rgba::transparent());
+            self.add_child(shape);
+        }
+        let entry = DisplayedEntry { id: default(), entry, background, divider };
         entry.entry.set_label_layer(&layer);
         self.add_child(&entry.entry);
         entry
     }
 
-    fn update_entry(entry: &DisplayedEntry<E>, id: entry::Id, model: &Option<E::Model>) {
+    fn update_entry(&self, entry: &DisplayedEntry<E>, id: entry::Id, model: &Option<E::Model>) {
         debug!("Setting new model {:?} for entry {}; old entry: {:?}.", model, id, entry.id.get());
         entry.id.set(Some(id));
+        entry.entry.set_id(id);
         match model {
             Some(model) => entry.entry.update(model),
             None => {
@@ -279,6 +480,6 @@ impl<E: Entry> ListData<E, E::Params> {
                 entry.entry.update(&default());
             }
         };
-        entry.entry.set_y(Self::position_y_of_entry(id));
+        entry.entry.set_y(self.position_y_of_entry(id));
     }
 }
@@ -5,6 +5,7 @@
 
 #![recursion_limit = "1024"]
 // === Features ===
+#![feature(let_chains)]
 #![feature(option_result_contains)]
 #![feature(trait_alias)]
 // === Standard Linter Configuration ===
@@ -26,7 +27,10 @@
 // === Export ===
 // ==============
 
+pub mod context_menu;
 pub mod entry;
+pub mod scroll;
+pub mod scrollbar;
 
 
 
@@ -41,6 +45,7 @@ use enso_frp as frp;
 use ensogl_core::application;
 use ensogl_core::application::shortcut;
 use ensogl_core::application::Application;
+use ensogl_core::control::io::mouse;
 use ensogl_core::data::color;
 use ensogl_core::display;
 use ensogl_core::display::scene::layer::Layer;
@@ -59,6 +64,16 @@ pub use entry::Entry;
 
 const DEFAULT_STYLE_PATH: &str = theme::widget::list_view::HERE.str;
 
+/// The prefix of the style values read by the list view in the given scope. The values not
+/// overridden in the scope cascade to the global ones, see [`StyleScope`].
+fn scoped_style_path(scope: Option<&StyleScope>) -> String {
+    let path = style::Path::from(DEFAULT_STYLE_PATH);
+    match scope {
+        Some(scope) => path.nested_in(scope.path()).to_string(),
+        None => path.to_string(),
+    }
+}
+
 /// The additional padding inside list view background and selection, added for better antialiasing
 pub const SHAPE_MARGIN: f32 = 5.0;
 
@@ -118,18 +133,24 @@ impl Default for JumpTarget {
 /// The Model of Select Component.
 #[derive(Clone, CloneRef, Debug, display::Object)]
 struct Model<E: Entry> {
-    entries:        entry::List<E>,
-    selection:      Selection,
-    background:     Rectangle,
-    scrolled_area:  display::object::Instance,
-    display_object: display::object::Instance,
+    app:                  Application,
+    entries:              entry::List<E>,
+    selection:            Selection,
+    background:           Rectangle,
+    scrolled_area:        display::object::Instance,
+    scrollbar:            scrollbar::Scrollbar,
+    kinetic_scroll:       scroll::Kinetic,
+    display_object:       display::object::Instance,
+    context_menu:         Rc<RefCell<Option<context_menu::ContextMenu>>>,
+    context_menu_actions: Rc<RefCell<Vec<String>>>,
 }
 
 impl<E: Entry> Model<E> {
     fn new(app: &Application) -> Self {
+        let app = app.clone_ref();
         let display_object = display::object::Instance::new();
         let scrolled_area = display::object::Instance::new();
-        let entries = entry::List::new(app);
+        let entries = entry::List::new(&app);
         let background = Rectangle();
         background.set_border_color(color::Rgba::transparent());
         let selection = Selection::default();
@@ -138,7 +159,23 @@ impl<E: Entry> Model<E> {
         display_object.add_child(&scrolled_area);
         scrolled_area.add_child(&entries);
         scrolled_area.add_child(&selection);
-        Model { entries, selection, background, scrolled_area, display_object }
+        let scrollbar = scrollbar::Scrollbar::new();
+        display_object.add_child(&scrollbar);
+        let kinetic_scroll = default();
+        let context_menu = default();
+        let context_menu_actions = default();
+        Model {
+            app,
+            entries,
+            selection,
+            background,
+            scrolled_area,
+            scrollbar,
+            kinetic_scroll,
+            display_object,
+            context_menu,
+            context_menu_actions,
+        }
     }
 
     /// Update the displayed entries list when _view_ has changed - the list was scrolled or
@@ -150,7 +187,8 @@ impl<E: Entry> Model<E> {
         entry_padding: f32,
         style_prefix: &display::style::Path,
     ) {
-        let visible_entries = Self::visible_entries(view, self.entries.entry_count());
+        self.entries.update_offsets();
+        let visible_entries = self.visible_entries(view);
         let padding = Vector2(2.0 * padding, 2.0 * padding);
         let entry_width = view.size.x - 2.0 * entry_padding;
         self.entries.set_x(-view.size.x / 2.0 + entry_padding);
@@ -167,18 +205,20 @@ impl<E: Entry> Model<E> {
         view: &View,
         style_prefix: display::style::Path,
     ) {
-        let visible_entries = Self::visible_entries(view, provider.entry_count());
-        let entry_width = view.size.x;
         let entries = &self.entries;
-        entries.update_entries_new_provider(provider, visible_entries, entry_width, style_prefix);
+        entries.set_provider(provider);
+        let visible_entries = self.visible_entries(view);
+        let entry_width = view.size.x;
+        entries.update_all_entries(visible_entries, entry_width, style_prefix);
     }
 
-    fn visible_entries(View { position_y, size }: &View, entry_count: usize) -> Range<entry::Id> {
+    fn visible_entries(&self, View { position_y, size }: &View) -> Range<entry::Id> {
+        let entry_count = self.entries.entry_count();
         if entry_count == 0 {
             0..0
         } else {
             let entry_at_y_saturating =
-                |y: f32| match entry::List::<E>::entry_at_y_position(y, entry_count) {
+                |y: f32| match self.entries.entry_at_y_position(y) {
                     entry::list::IdAtYPosition::AboveFirst => 0,
                     entry::list::IdAtYPosition::UnderLast => entry_count - 1,
                     entry::list::IdAtYPosition::Entry(id) => id,
@@ -189,6 +229,121 @@ impl<E: Entry> Model<E> {
         }
     }
 
+    /// The height of the selection highlighting the entry with given id. The selection of an entry
+    /// taller than the default [`entry::HEIGHT`] is taller by the same amount than the
+    /// `style_height`.
+    fn selection_height_of_entry(&self, id: entry::Id, style_height: f32) -> f32 {
+        style_height + self.entries.height_of_entry(id) - entry::HEIGHT
+    }
+
+    /// The top and bottom of the selection highlighting the entry with given id.
+    fn selection_y_range_of_entry(&self, id: entry::Id, style_height: f32) -> Range<f32> {
+        let center = self.entries.position_y_of_entry(id);
+        let half_height = self.selection_height_of_entry(id, style_height) / 2.0;
+        (center - half_height)..(center + half_height)
+    }
+
+    /// The range of the scroll positions at which the view is not scrolled beyond the entries.
+    fn scroll_range(&self, view_height: f32, style_selection_height: f32) -> Range<f32> {
+        let max = style_selection_height / 2.0;
+        let last_entry = self.entries.entry_count().checked_sub(1);
+        let selection_of = |id| self.selection_y_range_of_entry(id, style_selection_height);
+        let bottom = last_entry.map(selection_of);
+        let min = bottom.map_or(max, |bottom| (bottom.start + view_height).min(max));
+        min..max
+    }
+
+    /// The scroll position closest to the `current` one at which the entry with given id is
+    /// visible. If the entry is taller than the view, its top is visible.
+    fn scroll_position_revealing_entry(
+        &self,
+        id: entry::Id,
+        current: f32,
+        view_height: f32,
+        style_selection_height: f32,
+    ) -> f32 {
+        let selection = self.selection_y_range_of_entry(id, style_selection_height);
+        current.min(selection.start + view_height).max(selection.end)
+    }
+
+    /// The scroll position after the kinetic scrolling moved in the frame. The scrolling stops
+    /// when reaching the top or bottom of the list.
+    fn kinetic_scroll_step(
+        &self,
+        frame_time: f32,
+        current: f32,
+        view_height: f32,
+        style_selection_height: f32,
+    ) -> f32 {
+        let range = self.scroll_range(view_height, style_selection_height);
+        let new_position = current + self.kinetic_scroll.step(frame_time);
+        if new_position <= range.start || new_position >= range.end {
+            self.kinetic_scroll.stop();
+        }
+        new_position.max(range.start).min(range.end)
+    }
+
+    /// The scroll position after the scrollbar's thumb was dragged by the `distance` (positive
+    /// when dragged up).
+    fn scroll_position_after_thumb_drag(
+        &self,
+        distance: f32,
+        current: f32,
+        view_height: f32,
+        style_selection_height: f32,
+    ) -> f32 {
+        let range = self.scroll_range(view_height, style_selection_height);
+        let thumb = scrollbar::Thumb::new(range.clone(), current, view_height);
+        let scroll_ratio = thumb.map_or(0.0, |thumb| thumb.scroll_ratio);
+        (current + distance * scroll_ratio).max(range.start).min(range.end)
+    }
+
+    /// Update the scrollbar's thumb after the list was scrolled or resized. Returns [`None`] if all
+    /// the entries fit in the view, so there is no thumb.
+    fn update_scrollbar(
+        &self,
+        position: f32,
+        size: Vector2,
+        padding: f32,
+        style_selection_height: f32,
+    ) -> Option<scrollbar::Thumb> {
+        let view_height = size.y - 2.0 * padding;
+        let range = self.scroll_range(view_height, style_selection_height);
+        let thumb = scrollbar::Thumb::new(range, position, view_height);
+        self.scrollbar.update(thumb, size, padding);
+        thumb
+    }
+
+    /// Show the context menu of the entry at the `position`, unless there are no actions set. The
+    /// menu is created when shown for the first time. Returns `true` if the menu was shown.
+    fn show_context_menu(
+        &self,
+        entry: entry::Id,
+        position: Vector2,
+        action_chosen: &frp::Any<(entry::Id, usize)>,
+    ) -> bool {
+        let actions = self.context_menu_actions.borrow().clone();
+        if actions.is_empty() {
+            self.hide_context_menu();
+            false
+        } else {
+            let menu = self
+                .context_menu
+                .borrow_mut()
+                .get_or_insert_with(|| context_menu::ContextMenu::new(&self.app, action_chosen))
+                .clone_ref();
+            menu.show(self, entry, &actions, position);
+            true
+        }
+    }
+
+    fn hide_context_menu(&self) {
+        let menu = self.context_menu.borrow().clone();
+        if let Some(menu) = menu {
+            menu.hide();
+        }
+    }
+
     fn jump_target(&self, current_entry: Option<entry::Id>, jump: isize) -> JumpTarget {
         if jump < 0 {
             match current_entry.and_then(|entry| entry.checked_sub(-jump as usize)) {
@@ -197,10 +352,15 @@ impl<E: Entry> Model<E> {
             }
         } else {
             let new_entry = current_entry.map_or(0, |entry| entry + jump as usize);
-            if new_entry >= self.entries.entry_count() {
-                JumpTarget::BelowAll
-            } else {
+            let entry_count = self.entries.entry_count();
+            let last_loaded = entry_count.checked_sub(1).filter(|_| !self.entries.is_complete());
+            if new_entry < entry_count {
                 JumpTarget::Entry(new_entry)
+            } else if let Some(last_loaded) = last_loaded {
+                // More entries are being loaded, so the selection stops at the last loaded one.
+                JumpTarget::Entry(last_loaded)
+            } else {
+                JumpTarget::BelowAll
             }
         }
     }
@@ -255,12 +415,27 @@ ensogl_core::define_endpoints! {
 
         resize(Vector2<f32>),
         scroll_jump(f32),
+        /// Scroll the list by the minimal distance making the entry with given id visible.
+        scroll_to_entry(entry::Id),
         set_entries(entry::AnyModelProvider<E>),
+        /// Refresh the displayed entries after the dynamic provider loaded more of them or was
+        /// completed. See [`entry::DynamicModelProvider`].
+        entries_loaded(),
         select_entry(Option<entry::Id>),
         chose_entry(entry::Id),
-        set_style_prefix(String),
+        /// Read the style values overridden in the given scope, falling back to the global ones.
+        /// The global values are read if `None`.
+        set_style_scope(Option<StyleScope>),
         set_background_corners_radius(f32),
         set_background_color(color::Rgba),
+        /// Draw every other entry with a background of the `stripe` style color.
+        set_zebra_striping(bool),
+        /// Draw hairlines of the `divider` style color between adjacent entries.
+        set_row_dividers(bool),
+        /// Set the actions displayed in the context menu of the right-clicked entry. If empty, no
+        /// menu is displayed, but the `entry_context_menu_requested` output is still emitted.
+        set_context_menu_actions(Vec<String>),
+        hide_context_menu(),
     }
 
     Output {
@@ -269,6 +444,9 @@ ensogl_core::define_endpoints! {
         chosen_entry(Option<entry::Id>),
         size(Vector2<f32>),
         scroll_position(f32),
+        /// Whether the scrollbar is displayed. It fades in when the entries do not fit in the list
+        /// and the list is hovered or scrolled.
+        scrollbar_visible(bool),
         /// The position where the selection widget  is animated to. May be used in cases where the
         /// ListView user want to manage the selection widget (e.g. when the selection is shared
         /// between many lists).
@@ -280,13 +458,17 @@ ensogl_core::define_endpoints! {
         tried_to_move_out_above(),
         tried_to_move_out_below(),
         style_prefix(String),
+        /// The entry was right-clicked at the given position, relative to the ListView.
+        entry_context_menu_requested(entry::Id, Vector2<f32>),
+        /// The action with given index was chosen in the context menu of the entry.
+        context_menu_action_chosen(entry::Id, usize),
     }
 }
 
 /// A structure containing FRP nodes connected to appropriate style values.
 ///
 /// [`ListView`] is a general-use component, and it different places it could be styled differently.
-/// Therefore the [`ListView`] users (developers) may set the style prefix from where the
+/// Therefore the [`ListView`] users (developers) may set the style scope from where the
 /// style values will be read. This structure keeps a network connecting a style values from a
 /// particular prefix with its fields. It allows also reconnecting to another prefix without losing
 /// the fields (so the connections from them will remain intact).
@@ -299,6 +481,11 @@ struct StyleFrp {
     selection_height:         frp::Any<f32>,
     padding:                  frp::Any<f32>,
     entry_padding:            frp::Any<f32>,
+    stripe_color:             frp::Any<color::Rgba>,
+    divider_color:            frp::Any<color::Rgba>,
+    divider_width:            frp::Any<f32>,
+    spinner_color:            frp::Any<color::Rgba>,
+    scrollbar_color:          frp::Any<color::Rgba>,
 }
 
 impl StyleFrp {
@@ -311,6 +498,11 @@ impl StyleFrp {
             selection_height <- any(...);
             padding <- any(...);
             entry_padding <- any(...);
+            stripe_color <- any(...);
+            divider_color <- any(...);
+            divider_width <- any(...);
+            spinner_color <- any(...);
+            scrollbar_color <- any(...);
         }
         Self {
             style_connection_network,
@@ -320,6 +512,11 @@ impl StyleFrp {
             selection_height,
             padding,
             entry_padding,
+            stripe_color,
+            divider_color,
+            divider_width,
+            spinner_color,
+            scrollbar_color,
         }
     }
 
@@ -334,6 +531,11 @@ impl StyleFrp {
         let selection_height = style.get_number(prefix.sub("highlight").sub("height"));
         let padding = style.get_number(prefix.sub("padding"));
         let entry_padding = style.get_number(prefix.sub("entry").sub("padding"));
+        let stripe_color = style.get_color(prefix.sub("stripe"));
+        let divider_color = style.get_color(prefix.sub("divider"));
+        let divider_width = style.get_number(prefix.sub("divider").sub("width"));
+        let spinner_color = style.get_color(prefix.sub("spinner"));
+        let scrollbar_color = style.get_color(prefix.sub("scrollbar"));
         frp::extend! { style_connection_network
             init <- source_();
             self.background_color <+ all(&background_color, &init)._0();
@@ -342,6 +544,11 @@ impl StyleFrp {
             self.selection_height <+ all(&selection_height, &init)._0();
             self.padding <+ all(&padding, &init)._0();
             self.entry_padding <+ all(&entry_padding, &init)._0();
+            self.stripe_color <+ all(&stripe_color, &init)._0();
+            self.divider_color <+ all(&divider_color, &init)._0();
+            self.divider_width <+ all(&divider_width, &init)._0();
+            self.spinner_color <+ all(&spinner_color, &init)._0();
+            self.scrollbar_color <+ all(&scrollbar_color, &init)._0();
         }
         // At this point the old network is dropped, and old connections are removed.
         self.style_connection_network.set(Some(style_connection_network));
@@ -390,6 +597,7 @@ where E::Model: Default
         let view_y = Animation::<f32>::new(network);
         let selection_y = Animation::<f32>::new(network);
         let selection_height = Animation::<f32>::new(network);
+        let scrollbar_opacity = Animation::<f32>::new(network);
         let style_watch = StyleWatchFrp::new(&scene.style_sheet);
         let style = &self.style_frp;
 
@@ -406,6 +614,23 @@ where E::Model: Default
             eval background_color ((color) model.background.color.set(color.into()));
 
 
+            // === Row Decorations ===
+
+            stripe_color <- all_with(&style.stripe_color, &frp.set_zebra_striping,
+                |color, enabled| enabled.then_some(*color));
+            divider_color <- all_with(&style.divider_color, &frp.set_row_dividers,
+                |color, enabled| enabled.then_some(*color));
+            row_decoration <- all_with3(&stripe_color, &divider_color, &style.divider_width,
+                |stripe_color, divider_color, divider_width| entry::list::RowDecoration {
+                    stripe_color:  *stripe_color,
+                    divider_color: *divider_color,
+                    divider_width: *divider_width,
+                }
+            );
+            eval row_decoration ((decoration) model.entries.set_row_decoration(*decoration));
+            eval style.spinner_color ((color) model.entries.set_spinner_color(*color));
+
+
             // === Mouse Position ===
 
             let mouse_events = &model.background.events_deprecated;
@@ -418,7 +643,7 @@ where E::Model: Default
                 scene.screen_to_object_space(&model.scrolled_area,*pos).y
             }));
             mouse_pointed_entry <- mouse_y_in_scroll.map(f!([model](y)
-                entry::List::<E>::entry_at_y_position(*y,model.entries.entry_count()).entry()
+                model.entries.entry_at_y_position(*y).entry()
             ));
             mouse_selected_entry <- mouse_pointed_entry.sample(&can_select).filter(|e| e.is_some());
 
@@ -490,17 +715,54 @@ where E::Model: Default
             frp.source.chosen_entry   <+ opt_selected_entry_chosen.gate(&any_entry_selected);
 
 
-            // === Selection Size and Position ===
+            // === Context Menu ===
 
-            selection_y.target <+ frp.selected_entry.filter_map(|id|
-                id.map(entry::List::<E>::position_y_of_entry)
+            mouse_position <- mouse.position.map(f!([model,scene](pos) {
+                scene.screen_to_object_space(&model.display_object,*pos)
+            }));
+            right_clicked <- mouse_pointed_entry.sample(&mouse.down_secondary).gate(&mouse_in);
+            right_clicked_entry <- right_clicked.filter_map(|entry| *entry);
+            frp.source.entry_context_menu_requested <+ right_clicked_entry.map2(&mouse_position,
+                |entry, position| (*entry, *position)
+            );
+            eval frp.set_context_menu_actions ([model](actions)
+                *model.context_menu_actions.borrow_mut() = actions.clone()
             );
-            selection_height.target <+ all_with(&frp.selected_entry, &style.selection_height, |id, h|
-                if id.is_some() {*h} else {-SHAPE_MARGIN}
+            context_menu_action_chosen <- any(...);
+            context_menu_shown <- frp.entry_context_menu_requested.map(
+                f!([model, context_menu_action_chosen]((entry, position))
+                    model.show_context_menu(*entry, *position, &context_menu_action_chosen)
+                )
             );
+            // The menu takes the keyboard focus, so the navigation does not move the selection of
+            // both lists.
+            frp.deprecated_defocus <+ context_menu_shown.on_true();
+            frp.source.context_menu_action_chosen <+ context_menu_action_chosen;
+            focus_regained <- frp.focused.on_true();
+            hide_context_menu <- any_(
+                frp.hide_context_menu,
+                frp.context_menu_action_chosen,
+                frp.set_entries,
+                focus_regained
+            );
+            eval_ hide_context_menu (model.hide_context_menu());
+
+
+            // === Selection Size and Position ===
+
+            selection_y.target <+ frp.selected_entry.filter_map(f!([model](id)
+                id.map(|id| model.entries.position_y_of_entry(id))
+            ));
+            selected_height <- all_with(&frp.selected_entry, &style.selection_height,
+                f!((id, h) id.map(|id| model.selection_height_of_entry(id, *h)))
+            );
+            selection_height.target <+ selected_height.map(|h| h.unwrap_or(-SHAPE_MARGIN));
+            // The height of the last selected entry's selection, to which the selection sprite is
+            // aligned when it appears or disappears.
+            full_selection_height <- selected_height.filter_map(|h| *h);
             selection_y.skip <+ frp.set_entries.constant(());
             selection_height.skip <+ frp.set_entries.constant(());
-            selection_sprite_y <- all_with3(&selection_y.value, &selection_height.value, &style.selection_height,
+            selection_sprite_y <- all_with3(&selection_y.value, &selection_height.value, &full_selection_height,
                 |y, h, max_h| y + (max_h - h) / 2.0
             );
             frp.source.selection_size <+ all_with3(&frp.size, &style.padding, &selection_height.value, f!([](size, padding, height) {
@@ -517,29 +779,27 @@ where E::Model: Default
             // === Scrolling ===
 
             max_scroll <- style.selection_height.map(|h| *h / 2.0).sampler();
-            selection_top_after_move_up <- selected_entry_after_move_up.map2(&style.selection_height, |id, h|
-                id.map(|id| entry::List::<E>::position_y_of_entry(id) + *h / 2.0)
-            );
-            min_scroll_after_move_up <- selection_top_after_move_up.map2(&max_scroll, |top, max_scroll|
-                top.unwrap_or(*max_scroll)
+            view_height <- all_with(&frp.size, &style.padding, |size, pad| size.y - 2.0 * pad);
+            view_height <- view_height.sampler();
+            scroll_after_move <- selected_entry_after_move.map4(
+                &frp.scroll_position,
+                &view_height,
+                &style.selection_height,
+                f!([model](id, current, view_h, h) match id {
+                    Some(id) => model.scroll_position_revealing_entry(*id, *current, *view_h, *h),
+                    None => *h / 2.0,
+                })
             );
-            scroll_after_move_up <- min_scroll_after_move_up.map2(&frp.scroll_position,|min,current|
-                current.max(*min)
+            scroll_to_entry <- frp.scroll_to_entry.map4(
+                &frp.scroll_position,
+                &view_height,
+                &style.selection_height,
+                f!((id, current, view_height, h)
+                    model.scroll_position_revealing_entry(*id, *current, *view_height, *h)
+                )
             );
-            selection_bottom_after_move_down <- selected_entry_after_move_down.map2(&style.selection_height, |id, h|
-                id.map(|id| entry::List::<E>::position_y_of_entry(id) - *h / 2.0)
-            );
-            max_scroll_after_move_down <- selection_bottom_after_move_down.map4(
-                &frp.size,
-                &style.padding,
-                &max_scroll,
-                |y, size, padding, max_scroll| y.map_or(*max_scroll, |y| y + size.y - 2.0 * padding)
-            );
-            scroll_after_move_down <- max_scroll_after_move_down.map2(&frp.scroll_position,
-                |max_scroll,current| current.min(*max_scroll)
-            );
-            frp.source.scroll_position <+ scroll_after_move_up;
-            frp.source.scroll_position <+ scroll_after_move_down;
+            frp.source.scroll_position <+ scroll_after_move;
+            frp.source.scroll_position <+ scroll_to_entry;
             frp.source.scroll_position <+ frp.scroll_jump;
             frp.source.scroll_position <+ max_scroll.sample(&frp.set_entries);
             view_y.target <+ frp.scroll_position;
@@ -549,6 +809,55 @@ where E::Model: Default
             view_y.skip <+ init;
 
 
+            // === Kinetic Scrolling ===
+
+            let thumb_events = &model.scrollbar.thumb.events_deprecated;
+            thumb_hovered <- bool(&thumb_events.mouse_out, &thumb_events.mouse_over);
+            list_hovered <- all_with(&mouse_in, &thumb_hovered, |list, thumb| *list || *thumb);
+            let wheel = model.display_object.on_event::<mouse::Wheel>();
+            wheel_delta <- wheel.gate(&list_hovered).map(|event| event.delta_y());
+            kinetic_scroll_moving <- any(...);
+            kinetic_scroll_moving <+ init.constant(false);
+            kinetic_scroll_moving <+ wheel_delta.map(f!([model](delta) {
+                // Positive delta scrolls down, towards the lower scroll positions.
+                model.kinetic_scroll.push(-delta);
+                model.kinetic_scroll.is_moving()
+            }));
+            kinetic_scroll_frame <- scene.frp.frame_time.gate(&kinetic_scroll_moving);
+            kinetic_scroll_position <- kinetic_scroll_frame.map4(
+                &frp.scroll_position,
+                &view_height,
+                &style.selection_height,
+                f!((time, current, view_height, h)
+                    model.kinetic_scroll_step(*time, *current, *view_height, *h)
+                )
+            );
+            frp.source.scroll_position <+ kinetic_scroll_position;
+            kinetic_scroll_moving <+ kinetic_scroll_position.map(
+                f_!(model.kinetic_scroll.is_moving())
+            );
+
+
+            // === Scrollbar Dragging ===
+
+            thumb_pressed <- thumb_events.mouse_down_primary.constant(());
+            dragging_thumb <- bool(&mouse.up_0, &thumb_pressed);
+            thumb_drag <- mouse.translation.gate(&dragging_thumb);
+            thumb_dragged_position <- thumb_drag.map4(
+                &frp.scroll_position,
+                &view_height,
+                &style.selection_height,
+                f!((translation, current, view_h, h)
+                    model.scroll_position_after_thumb_drag(translation.y, *current, *view_h, *h)
+                )
+            );
+            frp.source.scroll_position <+ thumb_dragged_position;
+            scroll_set <- any_(frp.scroll_jump, frp.scroll_to_entry, selected_entry_after_move);
+            stop_kinetic_scroll <- any_(scroll_set, frp.set_entries, thumb_pressed);
+            eval_ stop_kinetic_scroll (model.kinetic_scroll.stop());
+            kinetic_scroll_moving <+ stop_kinetic_scroll.constant(false);
+
+
             // === Resize ===
             frp.source.size <+ frp.resize;
 
@@ -559,15 +868,19 @@ where E::Model: Default
                 let padding = Vector2(2.0 * padding, 2.0 * padding);
                 View { position_y: y, size: size - padding }
             });
-            default_style_prefix <- init.constant(DEFAULT_STYLE_PATH.to_string());
-            style_prefix <- any(&default_style_prefix,&frp.set_style_prefix);
+            default_style_scope <- init.constant(None);
+            style_scope <- any(&default_style_scope,&frp.set_style_scope);
+            style_prefix <- style_scope.map(|scope| scoped_style_path(scope.as_ref()));
+            frp.source.style_prefix <+ style_prefix;
             eval style_prefix ([model, style, style_watch](path) {
                 style.connect_with_prefix(&style_watch, &path.into());
                 model.entries.recreate_entries_with_style_prefix(path.into());
             });
             view_and_style <- all(view_info, style.padding, style.entry_padding, style_prefix);
+            view_and_style_after_load <- view_and_style.sample(&frp.entries_loaded);
+            view_update <- any(view_and_style, view_and_style_after_load);
             // This should go before handling mouse events to have proper checking of
-            eval view_and_style (((view, padding, entry_padding, style))
+            eval view_update (((view, padding, entry_padding, style))
                 model.update_after_view_change(view, *padding, *entry_padding, &style.into()));
             _new_entries <- frp.set_entries.map2(&view_and_style, f!((entries, (view, _, _, style))
                 model.set_entries(entries.clone_ref(), view, style.into())
@@ -580,6 +893,33 @@ where E::Model: Default
                 &style.padding,
                 |sel_y, view_y, size, padding| Vector2(0.0, (size.y / 2.0 - padding) - view_y + sel_y)
             );
+
+
+            // === Scrollbar ===
+
+            scrollbar_layout <-
+                all(&view_y.value, &frp.size, &style.padding, &style.selection_height);
+            scrollbar_layout_after_load <- scrollbar_layout.sample(&frp.entries_loaded);
+            scrollbar_layout_after_new_entries <- scrollbar_layout.sample(&frp.set_entries);
+            scrollbar_update <- any(
+                scrollbar_layout,
+                scrollbar_layout_after_load,
+                scrollbar_layout_after_new_entries
+            );
+            scrollbar_thumb <- scrollbar_update.map(f!(((position, size, padding, h))
+                model.update_scrollbar(*position, *size, *padding, *h)
+            ));
+            has_scrollbar_thumb <- scrollbar_thumb.map(|thumb| thumb.is_some());
+            scrollbar_active <- all_with3(&list_hovered, &dragging_thumb, &kinetic_scroll_moving,
+                |hovered, dragging, scrolling| *hovered || *dragging || *scrolling
+            );
+            scrollbar_visible <- all_with(&has_scrollbar_thumb, &scrollbar_active,
+                |has_thumb, active| *has_thumb && *active
+            );
+            frp.source.scrollbar_visible <+ scrollbar_visible.on_change();
+            scrollbar_opacity.target <+ frp.scrollbar_visible.map(|v| if *v { 1.0 } else { 0.0 });
+            eval scrollbar_opacity.value ((opacity) model.scrollbar.set_opacity(*opacity));
+            eval style.scrollbar_color ((color) model.scrollbar.set_color(*color));
             eval style.selection_color ((color) model.selection.shape.color.set(color.into()));
             eval style.selection_corner_radius ((radius) model.selection.shape.corner_radius.set(*radius));
         }
@@ -725,4 +1065,143 @@ mod tests {
         assert_relative_eq!(list_view.selection_position_target.value().x, 0.0);
         assert_relative_eq!(list_view.selection_position_target.value().y, -entry::HEIGHT);
     }
+
+    #[test]
+    fn choosing_context_menu_action() {
+        let app = Application::new("root");
+        let list_view = ListView::<entry::Label>::new(&app);
+        list_view.set_entries(AnyModelProvider::new(vec!["Entry 1", "Entry 2"]));
+        let request_menu = || list_view.frp.source.entry_context_menu_requested.emit((1, zero()));
+
+        // Without actions, only the request is emitted.
+        request_menu();
+        assert!(list_view.model.context_menu.borrow().is_none());
+
+        list_view.set_context_menu_actions(vec!["Rename".to_owned(), "Remove".to_owned()]);
+        request_menu();
+        let menu = list_view.model.context_menu.borrow().clone().expect("Menu should be created.");
+        assert!(menu.is_shown());
+        let action_chosen = list_view.context_menu_action_chosen.next_event();
+        menu.list.chose_entry(1);
+        assert_eq!(action_chosen.expect(), (1, 1));
+        assert!(!menu.is_shown());
+    }
+
+    #[test]
+    fn scrolling_to_entry() {
+        use ensogl_hardcoded_theme::widget::list_view as theme;
+        let app = Application::new("root");
+        let style_sheet = &app.display.default_scene.style_sheet;
+        style_sheet.set(theme::highlight::height, entry::HEIGHT);
+        style_sheet.set(theme::padding, 0.0);
+        let list_view = ListView::<entry::Label>::new(&app);
+        let entries = (0..10).map(|i| format!("Entry {i}")).collect::<Vec<_>>();
+        let size = Vector2(100.0, entry::HEIGHT * 3.0);
+        list_view.resize(size);
+        list_view.set_entries(AnyModelProvider::new(entries));
+        let top = entry::HEIGHT / 2.0;
+        assert_relative_eq!(list_view.scroll_position.value(), top);
+
+        list_view.scroll_to_entry(5);
+        assert_relative_eq!(list_view.scroll_position.value(), top - entry::HEIGHT * 3.0);
+        // The entry is already visible.
+        list_view.scroll_to_entry(4);
+        assert_relative_eq!(list_view.scroll_position.value(), top - entry::HEIGHT * 3.0);
+        list_view.scroll_to_entry(0);
+        assert_relative_eq!(list_view.scroll_position.value(), top);
+
+        let thumb = list_view.model.update_scrollbar(top, size, 0.0, entry::HEIGHT);
+        let thumb = thumb.expect("The entries should not fit in the list.");
+        assert_relative_eq!(thumb.length, entry::HEIGHT * 0.9);
+        assert_relative_eq!(thumb.center_y, entry::HEIGHT * 0.45);
+    }
+
+    /// A provider of three entries, the middle one twice as tall as the others.
+    #[derive(Debug)]
+    struct TallMiddleEntryProvider;
+
+    impl entry::ModelProvider<entry::Label> for TallMiddleEntryProvider {
+        fn entry_count(&self) -> usize {
+            3
+        }
+
+        fn get(&self, id: entry::Id) -> Option<String> {
+            (id < 3).then(|| format!("Entry {id}"))
+        }
+
+        fn height(&self, id: entry::Id) -> f32 {
+            if id == 1 {
+                entry::HEIGHT * 2.0
+            } else {
+                entry::HEIGHT
+            }
+        }
+    }
+
+    #[test]
+    fn entries_with_different_heights() {
+        use entry::list::IdAtYPosition;
+        use ensogl_hardcoded_theme::widget::list_view as theme;
+        let app = Application::new("root");
+        let style_sheet = &app.display.default_scene.style_sheet;
+        style_sheet.set(theme::highlight::height, entry::HEIGHT);
+        let list_view = ListView::<entry::Label>::new(&app);
+        list_view.resize(Vector2(100.0, entry::HEIGHT * 4.0));
+        list_view.set_entries(AnyModelProvider::new(TallMiddleEntryProvider));
+
+        let entries = &list_view.model.entries;
+        assert_relative_eq!(entries.position_y_of_entry(1), -entry::HEIGHT * 1.5);
+        assert_relative_eq!(entries.position_y_of_entry(2), -entry::HEIGHT * 3.0);
+        let all_entries = entries.y_range_of_all_entries();
+        assert_relative_eq!(all_entries.start, -entry::HEIGHT * 3.5);
+        assert_relative_eq!(all_entries.end, entry::HEIGHT / 2.0);
+        assert_eq!(entries.entry_at_y_position(0.0).entry(), Some(0));
+        assert_eq!(entries.entry_at_y_position(-entry::HEIGHT).entry(), Some(1));
+        assert_eq!(entries.entry_at_y_position(-entry::HEIGHT * 2.0).entry(), Some(1));
+        assert_eq!(entries.entry_at_y_position(-entry::HEIGHT * 3.0).entry(), Some(2));
+        assert_eq!(entries.entry_at_y_position(entry::HEIGHT), IdAtYPosition::AboveFirst);
+        assert_eq!(entries.entry_at_y_position(-entry::HEIGHT * 4.0), IdAtYPosition::UnderLast);
+
+        list_view.select_entry(Some(0));
+        assert_relative_eq!(list_view.selection_position_target.value().y, entry::HEIGHT * 1.5);
+        list_view.move_selection_down();
+        assert_relative_eq!(list_view.selection_position_target.value().y, 0.0);
+        list_view.move_selection_down();
+        assert_relative_eq!(list_view.selection_position_target.value().y, -entry::HEIGHT * 1.5);
+    }
+
+    #[test]
+    fn requesting_more_entries_from_dynamic_provider() {
+        let app = Application::new("root");
+        let list_view = ListView::<entry::Label>::new(&app);
+        list_view.resize(Vector2(100.0, entry::HEIGHT * 3.0));
+        let requests = Rc::new(RefCell::new(Vec::new()));
+        let requests_log = requests.clone();
+        let streamed =
+            entry::StreamedProvider::new(move |from| requests_log.borrow_mut().push(from));
+        list_view.set_entries(AnyModelProvider::new_dynamic(streamed.clone_ref()));
+        assert_eq!(*requests.borrow(), vec![0]);
+
+        // The entries close to the view are loaded, so more are requested.
+        streamed.extend((0..5).map(|i| format!("Entry {i}")));
+        list_view.entries_loaded();
+        assert_eq!(*requests.borrow(), vec![0, 5]);
+        // The selection stops at the last loaded entry.
+        list_view.move_selection_to_last();
+        let tried_to_move_out_below = list_view.tried_to_move_out_below.next_event();
+        list_view.move_selection_down();
+        assert_eq!(list_view.selected_entry.value(), Some(4));
+        tried_to_move_out_below.expect_not();
+
+        // Nothing is requested from the complete provider.
+        streamed.extend((5..8).map(|i| format!("Entry {i}")));
+        streamed.finish();
+        list_view.entries_loaded();
+        assert_eq!(*requests.borrow(), vec![0, 5]);
+        list_view.move_selection_to_last();
+        let tried_to_move_out_below = list_view.tried_to_move_out_below.next_event();
+        list_view.move_selection_down();
+        assert_eq!(list_view.selected_entry.value(), Some(7));
+        tried_to_move_out_below.expect();
+    }
 }
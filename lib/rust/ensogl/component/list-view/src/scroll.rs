@@ -0,0 +1,108 @@
+//! The kinetic scrolling of the [`ListView`](crate::ListView) with the mouse wheel or touchpad.
+
+use crate::prelude::*;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The time constant of the exponential decay of the scrolling velocity, in milliseconds. The
+/// higher it is, the longer the list keeps scrolling after the wheel stopped.
+const DECAY_TIME_MS: f32 = 120.0;
+
+/// The scrolling stops when the distance it would still travel is lower than this, in pixels.
+const MIN_REMAINING_DISTANCE: f32 = 0.5;
+
+
+
+// ===============
+// === Kinetic ===
+// ===============
+
+/// The state of the kinetic scrolling.
+///
+/// Every wheel event gives the scrolling a velocity, which decays exponentially in the subsequent
+/// frames. The velocity is chosen so the total distance travelled is equal to the wheel event's
+/// delta, so the list scrolls as far as it would without inertia, but smoothly.
+#[derive(Clone, CloneRef, Debug, Default)]
+pub struct Kinetic {
+    /// The velocity in pixels per millisecond.
+    velocity:        Rc<Cell<f32>>,
+    last_frame_time: Rc<Cell<Option<f32>>>,
+}
+
+impl Kinetic {
+    /// Add the `distance` to the distance the scrolling will travel.
+    pub fn push(&self, distance: f32) {
+        if !self.is_moving() {
+            self.last_frame_time.set(None);
+        }
+        self.velocity.set(self.velocity.get() + distance / DECAY_TIME_MS);
+    }
+
+    /// Stop the scrolling immediately.
+    pub fn stop(&self) {
+        self.velocity.set(0.0);
+        self.last_frame_time.set(None);
+    }
+
+    /// Check if the scrolling is in progress.
+    pub fn is_moving(&self) -> bool {
+        self.velocity.get() != 0.0
+    }
+
+    /// The distance travelled since the previous frame. The first frame after the scrolling
+    /// started does not move the list, as there is no previous frame time.
+    pub fn step(&self, frame_time: f32) -> f32 {
+        let time_delta = self.last_frame_time.replace(Some(frame_time)).map(|t| frame_time - t);
+        let time_delta = time_delta.unwrap_or_default().max(0.0);
+        let velocity = self.velocity.get();
+        let decay = (-time_delta / DECAY_TIME_MS).exp();
+        let distance = velocity * DECAY_TIME_MS * (1.0 - decay);
+        let new_velocity = velocity * decay;
+        if new_velocity.abs() * DECAY_TIME_MS < MIN_REMAINING_DISTANCE {
+            self.stop();
+        } else {
+            self.velocity.set(new_velocity);
+        }
+        distance
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn kinetic_scrolling_travels_pushed_distance() {
+        let kinetic = Kinetic::default();
+        assert!(!kinetic.is_moving());
+        kinetic.push(-100.0);
+        kinetic.push(-20.0);
+        assert!(kinetic.is_moving());
+        let mut travelled = 0.0;
+        let mut frame_time = 1000.0;
+        while kinetic.is_moving() {
+            travelled += kinetic.step(frame_time);
+            frame_time += 16.0;
+        }
+        assert!(frame_time < 3000.0);
+        assert_abs_diff_eq!(travelled, -120.0, epsilon = MIN_REMAINING_DISTANCE);
+
+        kinetic.push(50.0);
+        assert_eq!(kinetic.step(5000.0), 0.0);
+        assert!(kinetic.step(5016.0) > 0.0);
+        kinetic.stop();
+        assert!(!kinetic.is_moving());
+    }
+}
@@ -0,0 +1,188 @@
+//! The overlay scrollbar of the [`ListView`](crate::ListView), displayed over the right edge of the
+//! list when its entries do not fit.
+
+use crate::prelude::*;
+use ensogl_core::display::shape::*;
+
+use ensogl_core::data::color;
+use ensogl_core::display;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The width of the scrollbar's thumb.
+pub const WIDTH: f32 = 6.0;
+
+/// The distance between the thumb and the right edge of the list.
+pub const MARGIN: f32 = 3.0;
+
+/// The thumb is displayed with at least this length, so it is still visible and easy to drag in
+/// very long lists.
+const MIN_THUMB_LENGTH: f32 = 16.0;
+
+
+
+// =============
+// === Shape ===
+// =============
+
+/// The thumb of the scrollbar: a vertical bar with rounded ends.
+pub mod thumb {
+    use super::*;
+
+    ensogl_core::shape! {
+        above = [display::shape::compound::rectangle::shape];
+        alignment = center;
+        (style: Style, color: Vector4) {
+            let width: Var<Pixels> = "input_size.x".into();
+            let height: Var<Pixels> = "input_size.y".into();
+            let radius = &width / 2.0;
+            let shape = Rect((&width, &height)).corners_radius(radius);
+            let shape = shape.fill(color);
+            shape.into()
+        }
+    }
+}
+
+
+
+// =============
+// === Thumb ===
+// =============
+
+/// The length and position of the scrollbar's thumb.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Thumb {
+    /// The length of the thumb.
+    pub length:       f32,
+    /// The distance between the top of the view and the thumb's center.
+    pub center_y:     f32,
+    /// The scroll distance corresponding to moving the thumb by one pixel.
+    pub scroll_ratio: f32,
+}
+
+impl Thumb {
+    /// The thumb of the view of given height, scrolled to the `position` of the `scroll_range`.
+    /// Returns [`None`] if the whole content fits in the view.
+    pub fn new(scroll_range: Range<f32>, position: f32, view_height: f32) -> Option<Self> {
+        let scroll_length = scroll_range.end - scroll_range.start;
+        (scroll_length > 0.0 && view_height > 0.0).then(|| {
+            let content_height = view_height + scroll_length;
+            let length = view_height * view_height / content_height;
+            let length = length.max(MIN_THUMB_LENGTH).min(view_height);
+            let travel = view_height - length;
+            let progress = ((scroll_range.end - position) / scroll_length).clamp(0.0, 1.0);
+            let center_y = progress * travel + length / 2.0;
+            let scroll_ratio = if travel > 0.0 { scroll_length / travel } else { 0.0 };
+            Self { length, center_y, scroll_ratio }
+        })
+    }
+}
+
+
+
+// =================
+// === Scrollbar ===
+// =================
+
+/// The scrollbar's thumb, fading in when the list is hovered or scrolled.
+#[derive(Clone, CloneRef, Debug, display::Object)]
+pub struct Scrollbar {
+    display_object: display::object::Instance,
+    /// The thumb, which may be dragged to scroll the list.
+    pub thumb:      thumb::View,
+    color:          Rc<Cell<color::Rgba>>,
+    opacity:        Rc<Cell<f32>>,
+    /// Whether the entries do not fit in the list, so the thumb should be displayed.
+    has_thumb:      Rc<Cell<bool>>,
+}
+
+impl Scrollbar {
+    /// Constructor.
+    pub fn new() -> Self {
+        let display_object = display::object::Instance::new();
+        let thumb = thumb::View::new();
+        let color = default();
+        let opacity = default();
+        let has_thumb = default();
+        Self { display_object, thumb, color, opacity, has_thumb }
+    }
+
+    /// Update the thumb of the list of given size. The thumb is hidden if it is [`None`].
+    pub fn update(&self, thumb: Option<Thumb>, list_size: Vector2, padding: f32) {
+        if let Some(thumb) = thumb {
+            let x = list_size.x / 2.0 - MARGIN - WIDTH / 2.0;
+            let y = list_size.y / 2.0 - padding - thumb.center_y;
+            self.thumb.set_size(Vector2(WIDTH, thumb.length));
+            self.thumb.set_xy(Vector2(x, y));
+        }
+        self.has_thumb.set(thumb.is_some());
+        self.update_visibility();
+    }
+
+    /// Set the color of the thumb, when fully visible.
+    pub fn set_color(&self, color: color::Rgba) {
+        self.color.set(color);
+        self.update_color();
+    }
+
+    /// Set the opacity of the thumb, used to fade it in and out.
+    pub fn set_opacity(&self, opacity: f32) {
+        self.opacity.set(opacity);
+        self.update_color();
+        self.update_visibility();
+    }
+
+    fn update_color(&self) {
+        let color = self.color.get().multiply_alpha(self.opacity.get());
+        self.thumb.color.set(color.into());
+    }
+
+    /// Remove the thumb when it is transparent, so it does not catch the mouse events.
+    fn update_visibility(&self) {
+        if self.has_thumb.get() && self.opacity.get() > 0.0 {
+            self.display_object.add_child(&self.thumb);
+        } else {
+            self.thumb.unset_parent();
+        }
+    }
+}
+
+impl Default for Scrollbar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn thumb_geometry() {
+        assert_eq!(Thumb::new(10.0..10.0, 10.0, 100.0), None);
+
+        let at_top = Thumb::new(-290.0..10.0, 10.0, 100.0).unwrap();
+        assert_abs_diff_eq!(at_top.length, 25.0);
+        assert_abs_diff_eq!(at_top.center_y, 12.5);
+        assert_abs_diff_eq!(at_top.scroll_ratio, 4.0);
+        let in_middle = Thumb::new(-290.0..10.0, -140.0, 100.0).unwrap();
+        assert_abs_diff_eq!(in_middle.center_y, 50.0);
+        let at_bottom = Thumb::new(-290.0..10.0, -290.0, 100.0).unwrap();
+        assert_abs_diff_eq!(at_bottom.center_y, 87.5);
+
+        let very_long = Thumb::new(-9990.0..10.0, 10.0, 100.0).unwrap();
+        assert_abs_diff_eq!(very_long.length, MIN_THUMB_LENGTH);
+    }
+}
@@ -0,0 +1,368 @@
+//! A container splitting its area between two panes, separated by a divider which may be dragged
+//! to resize them.
+//!
+//! The panes are placed along the orientation axis: side by side for [`Axis2::X`] (the first pane
+//! on the left), and one above another for [`Axis2::Y`] (the first pane on top). The division is
+//! described by the ratio of the first pane's size to the size of both panes, constrained by the
+//! minimum sizes of the panes. The objects hosted in the panes are not resized automatically; they
+//! should follow the `first_size` and `second_size` outputs.
+
+#![recursion_limit = "1024"]
+// === Standard Linter Configuration ===
+#![deny(non_ascii_idents)]
+#![warn(unsafe_code)]
+#![allow(clippy::bool_to_int_with_if)]
+#![allow(clippy::let_and_return)]
+// === Non-Standard Linter Configuration ===
+#![warn(missing_copy_implementations)]
+#![warn(missing_debug_implementations)]
+#![warn(missing_docs)]
+#![warn(trivial_casts)]
+#![warn(trivial_numeric_casts)]
+#![warn(unused_import_braces)]
+#![warn(unused_qualifications)]
+
+use ensogl_core::display::shape::*;
+use ensogl_core::prelude::*;
+
+use enso_frp as frp;
+use ensogl_core::application::frp::API;
+use ensogl_core::application::Application;
+use ensogl_core::control::io::mouse;
+use ensogl_core::display;
+use ensogl_core::gui::Widget;
+use ensogl_hardcoded_theme::application::split_pane as theme;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The width of the divider between the panes.
+pub const DIVIDER_WIDTH: f32 = 1.0;
+/// The width of the area around the divider which may be dragged.
+const DIVIDER_HOVER_WIDTH: f32 = 8.0;
+/// The ratio of a newly created split pane.
+const DEFAULT_RATIO: f32 = 0.5;
+
+
+
+// ==============
+// === Layout ===
+// ==============
+
+/// The position and size of a part of the split pane, relative to its bottom-left corner.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct Placement {
+    pub position: Vector2,
+    pub size:     Vector2,
+}
+
+/// The placement of the panes and the divider.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Layout {
+    /// The left or top pane.
+    pub first:   Placement,
+    /// The divider between the panes.
+    pub divider: Placement,
+    /// The right or bottom pane.
+    pub second:  Placement,
+    /// The axis along which the panes are placed.
+    pub axis:    Axis2,
+    /// The ratio of the first pane's size to the size of both panes, after applying the minimum
+    /// sizes of the panes.
+    pub ratio:   f32,
+}
+
+impl Layout {
+    /// Divide the area of the given `size` along the `axis`. If the minimum sizes can't both be
+    /// satisfied, the first pane's minimum size takes precedence.
+    pub fn new(size: Vector2, axis: Axis2, ratio: f32, min_sizes: (f32, f32)) -> Self {
+        let (first_min, second_min) = min_sizes;
+        let available = (size.get_dim(axis) - DIVIDER_WIDTH).max(0.0);
+        let across = size.get_dim(axis.orthogonal());
+        let first_max = (available - second_min).max(0.0);
+        let first = ratio.clamp(0.0, 1.0) * available;
+        let first = first.min(first_max).max(first_min.min(available));
+        let second = available - first;
+        let ratio = if available > 0.0 { first / available } else { ratio };
+        let placement = |x, y, width, height| Placement {
+            position: Vector2(x, y),
+            size:     Vector2(width, height),
+        };
+        match axis {
+            Axis2::X => Self {
+                first: placement(0.0, 0.0, first, across),
+                divider: placement(first, 0.0, DIVIDER_WIDTH, across),
+                second: placement(first + DIVIDER_WIDTH, 0.0, second, across),
+                axis,
+                ratio,
+            },
+            Axis2::Y => Self {
+                first: placement(0.0, second + DIVIDER_WIDTH, across, first),
+                divider: placement(0.0, second, across, DIVIDER_WIDTH),
+                second: placement(0.0, 0.0, across, second),
+                axis,
+                ratio,
+            },
+        }
+    }
+
+    /// The ratio placing the center of the divider at the `position`, relative to the split
+    /// pane's bottom-left corner. The minimum sizes are not applied.
+    pub fn ratio_at(size: Vector2, axis: Axis2, position: Vector2) -> f32 {
+        let available = (size.get_dim(axis) - DIVIDER_WIDTH).max(0.0);
+        let first = match axis {
+            Axis2::X => position.x - DIVIDER_WIDTH / 2.0,
+            Axis2::Y => size.y - position.y - DIVIDER_WIDTH / 2.0,
+        };
+        if available > 0.0 {
+            (first / available).clamp(0.0, 1.0)
+        } else {
+            DEFAULT_RATIO
+        }
+    }
+}
+
+
+
+// ===========
+// === FRP ===
+// ===========
+
+ensogl_core::define_endpoints_2! {
+    Input {
+        set_size(Vector2),
+        /// The axis along which the panes are placed.
+        set_orientation(Axis2),
+        /// Set the ratio of the first pane's size to the size of both panes.
+        set_ratio(f32),
+        set_first_min_size(f32),
+        set_second_min_size(f32),
+    }
+    Output {
+        /// The ratio of the first pane's size to the size of both panes, after applying the
+        /// minimum sizes.
+        ratio(f32),
+        /// The ratio set by the user by dragging the divider, emitted when the dragging ends. It
+        /// is meant to be persisted and restored with the `set_ratio` input.
+        ratio_committed(f32),
+        first_size(Vector2),
+        second_size(Vector2),
+        is_dragged(bool),
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+/// The internal model of the [`SplitPane`].
+#[derive(Debug, Clone, CloneRef, display::Object)]
+pub struct Model {
+    display_object: display::object::Instance,
+    first:          display::object::Instance,
+    second:         display::object::Instance,
+    divider:        Rectangle,
+    divider_hover:  Rectangle,
+}
+
+impl Model {
+    /// Constructor.
+    pub fn new() -> Self {
+        let display_object = display::object::Instance::new_named("SplitPane");
+        let first = display::object::Instance::new_named("SplitPane::first");
+        let second = display::object::Instance::new_named("SplitPane::second");
+        let divider = Rectangle();
+        let divider_hover = Rectangle();
+        display_object.add_child(&first);
+        display_object.add_child(&second);
+        display_object.add_child(&divider);
+        divider.add_child(&divider_hover);
+        divider_hover.set_color(INVISIBLE_HOVER_COLOR).set_pointer_events(true);
+        Self { display_object, first, second, divider, divider_hover }
+    }
+
+    fn set_pane(pane: &display::object::Instance, content: &display::object::Instance) {
+        pane.remove_all_children();
+        pane.add_child(content);
+    }
+
+    fn apply_layout(&self, layout: &Layout) {
+        let axis = layout.axis;
+        for (object, placement) in [(&self.first, layout.first), (&self.second, layout.second)] {
+            object.set_xy(placement.position);
+            object.set_size(placement.size);
+        }
+        self.divider.set_xy(layout.divider.position);
+        self.divider.set_size(layout.divider.size);
+        let mut hover_size = layout.divider.size;
+        hover_size.set_dim(axis, DIVIDER_HOVER_WIDTH);
+        let mut hover_offset = Vector2::default();
+        hover_offset.set_dim(axis, (DIVIDER_WIDTH - DIVIDER_HOVER_WIDTH) / 2.0);
+        self.divider_hover.set_size(hover_size);
+        self.divider_hover.set_xy(hover_offset);
+    }
+}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+
+// =================
+// === SplitPane ===
+// =================
+
+/// A container split between two panes. See the module documentation for details.
+#[derive(Debug, Clone, CloneRef, Deref, display::Object)]
+pub struct SplitPane {
+    widget: Widget<Model, Frp>,
+}
+
+impl SplitPane {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let model = Rc::new(Model::new());
+        let frp = Frp::new();
+        let network = frp.network();
+        let input = &frp.private().input;
+        let out = &frp.private().output;
+        let scene = &app.display.default_scene;
+        let mouse = &scene.mouse.frp_deprecated;
+        let style = StyleWatchFrp::new(&scene.style_sheet);
+        let divider_color = style.get_color(theme::divider);
+        let divider_hover_color = style.get_color(theme::divider_hover);
+        let divider_down = model.divider_hover.on_event::<mouse::Down>();
+        let divider_enter = model.divider_hover.on_event::<mouse::Enter>();
+        let divider_leave = model.divider_hover.on_event::<mouse::Leave>();
+        let mouse_up = scene.on_event::<mouse::Up>();
+
+        frp::extend! { network
+            init <- source_();
+
+            // === Layout ===
+
+            dragged_ratio <- any_mut::<f32>();
+            ratio <- any(&input.set_ratio, &dragged_ratio);
+            min_sizes <- all(&input.set_first_min_size, &input.set_second_min_size);
+            layout <- all_with4(&input.set_size, &input.set_orientation, &ratio, &min_sizes,
+                |size, axis, ratio, min_sizes| Layout::new(*size, *axis, *ratio, *min_sizes)
+            );
+            eval layout ((layout) model.apply_layout(layout));
+            eval input.set_size ((size) model.display_object.set_size(*size););
+            out.ratio <+ layout.map(|layout| layout.ratio).on_change();
+            out.first_size <+ layout.map(|layout| layout.first.size).on_change();
+            out.second_size <+ layout.map(|layout| layout.second.size).on_change();
+
+
+            // === Dragging ===
+
+            drag_start <- divider_down.filter(|e| e.button() == mouse::PrimaryButton).constant(());
+            drag_end <- mouse_up.gate(&out.is_dragged).constant(());
+            out.is_dragged <+ bool(&drag_end, &drag_start);
+            dragged_pointer <- mouse.position.gate(&out.is_dragged);
+            dragged_pointer <- dragged_pointer.map(f!([scene, model](position) {
+                scene.screen_to_object_space(model.display_object(), *position)
+            }));
+            dragged_ratio <+ dragged_pointer.map3(&input.set_size, &input.set_orientation,
+                |position, size, axis| Layout::ratio_at(*size, *axis, *position)
+            );
+            out.ratio_committed <+ out.ratio.sample(&drag_end);
+
+
+            // === Style ===
+
+            is_hovered <- bool(&divider_leave, &divider_enter);
+            is_highlighted <- all_with(&is_hovered, &out.is_dragged, |hovered, dragged| {
+                *hovered || *dragged
+            });
+            divider_color <- all_with4(&is_highlighted, &divider_color, &divider_hover_color,
+                &init, |highlighted, color, hover_color, _| {
+                    if *highlighted { *hover_color } else { *color }
+                }
+            );
+            eval divider_color ((color) model.divider.set_color(*color););
+        }
+        init.emit(());
+        frp.set_ratio(DEFAULT_RATIO);
+
+        let widget = Widget::new(app, frp, model);
+        Self { widget }
+    }
+
+    /// Display the object in the first (left or top) pane, replacing its previous content.
+    pub fn set_first(&self, content: &impl display::Object) {
+        Model::set_pane(&self.widget.model().first, content.display_object());
+    }
+
+    /// Display the object in the second (right or bottom) pane, replacing its previous content.
+    pub fn set_second(&self, content: &impl display::Object) {
+        Model::set_pane(&self.widget.model().second, content.display_object());
+    }
+}
+
+impl FrpNetworkProvider for SplitPane {
+    fn network(&self) -> &frp::Network {
+        self.widget.frp().network()
+    }
+}
+
+impl ensogl_core::application::View for SplitPane {
+    fn label() -> &'static str {
+        "SplitPane"
+    }
+
+    fn new(app: &Application) -> Self {
+        Self::new(app)
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_with_min_sizes() {
+        let size = Vector2(101.0, 50.0);
+        let layout = Layout::new(size, Axis2::X, 0.5, (0.0, 0.0));
+        assert_eq!(layout.first.size, Vector2(50.0, 50.0));
+        assert_eq!(layout.divider.position, Vector2(50.0, 0.0));
+        assert_eq!(layout.second.position, Vector2(51.0, 0.0));
+        assert_eq!(layout.second.size, Vector2(50.0, 50.0));
+
+        let layout = Layout::new(size, Axis2::X, 0.1, (30.0, 0.0));
+        assert_eq!(layout.first.size.x, 30.0);
+        assert_eq!(layout.ratio, 0.3);
+        let layout = Layout::new(size, Axis2::X, 0.9, (0.0, 40.0));
+        assert_eq!(layout.second.size.x, 40.0);
+        let layout = Layout::new(size, Axis2::X, 0.5, (80.0, 80.0));
+        assert_eq!((layout.first.size.x, layout.second.size.x), (80.0, 20.0));
+
+        let layout = Layout::new(Vector2(50.0, 101.0), Axis2::Y, 0.2, (0.0, 0.0));
+        assert_eq!(layout.first.position, Vector2(0.0, 81.0));
+        assert_eq!(layout.first.size, Vector2(50.0, 20.0));
+        assert_eq!(layout.second.size, Vector2(50.0, 80.0));
+    }
+
+    #[test]
+    fn ratio_at_pointer() {
+        let size = Vector2(101.0, 101.0);
+        assert_eq!(Layout::ratio_at(size, Axis2::X, Vector2(25.5, 0.0)), 0.25);
+        assert_eq!(Layout::ratio_at(size, Axis2::Y, Vector2(0.0, 75.5)), 0.25);
+        assert_eq!(Layout::ratio_at(size, Axis2::X, Vector2(-10.0, 0.0)), 0.0);
+    }
+}
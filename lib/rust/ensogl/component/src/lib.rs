@@ -13,6 +13,8 @@
 
 pub use ensogl_breadcrumbs as breadcrumbs;
 pub use ensogl_button as button;
+pub use ensogl_command_palette as command_palette;
+pub use ensogl_dialog as dialog;
 pub use ensogl_drop_down as drop_down;
 pub use ensogl_drop_down_menu as drop_down_menu;
 pub use ensogl_drop_manager as drop_manager;
@@ -26,6 +28,8 @@ pub use ensogl_scrollbar as scrollbar;
 pub use ensogl_selector as selector;
 pub use ensogl_shadow as shadow;
 pub use ensogl_spinner as spinner;
+pub use ensogl_split_pane as split_pane;
 pub use ensogl_text as text;
+pub use ensogl_toast as toast;
 pub use ensogl_toggle_button as toggle_button;
 pub use ensogl_tooltip as tooltip;
@@ -670,6 +670,7 @@ impl Text {
         let m = &self.data;
         let network = self.frp.network();
         let out = &self.frp.private.output;
+        let fonts = m.scene.extension::<font::Registry>();
 
         frp::extend! { network
             m.buffer.frp.set_first_view_line <+ self.frp.set_first_view_line;
@@ -681,6 +682,7 @@ impl Text {
 
             out.long_text_truncation_mode <+ self.frp.set_long_text_truncation_mode;
             eval_ self.frp.set_long_text_truncation_mode (m.redraw());
+            eval_ fonts.text_cache_invalidated (m.redraw());
         }
     }
 
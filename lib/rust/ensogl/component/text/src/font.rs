@@ -947,16 +947,45 @@ pub struct FontWithGpuData {
     pub opacity_increase: gpu::Uniform<f32>,
     pub opacity_exponent: gpu::Uniform<f32>,
     context:              Rc<RefCell<Option<Context>>>,
+    pixel_ratio:          f32,
+    /// The hinting computed for the zoom buckets visited so far.
+    hinting_cache:        Rc<RefCell<HashMap<ZoomBucket, Hinting>>>,
 }
 
 impl FontWithGpuData {
-    fn new(font: Font, hinting: Hinting) -> Self {
-        let Hinting { opacity_increase, opacity_exponent } = hinting;
+    fn new(font: Font, pixel_ratio: f32) -> Self {
+        let Hinting { opacity_increase, opacity_exponent } =
+            Hinting::for_font(font.name(), pixel_ratio);
         let opacity_increase = gpu::Uniform::new(opacity_increase);
         let opacity_exponent = gpu::Uniform::new(opacity_exponent);
         let atlas = gpu::Uniform::new(default());
         let context = default();
-        Self { font, atlas, opacity_exponent, opacity_increase, context }
+        let hinting_cache = default();
+        Self {
+            font,
+            atlas,
+            opacity_exponent,
+            opacity_increase,
+            context,
+            pixel_ratio,
+            hinting_cache,
+        }
+    }
+
+    /// Adjust the hinting to the zoom bucket. The hinting of each bucket is computed once and
+    /// cached until [`Self::clear_hinting_cache`] is called.
+    fn set_zoom_bucket(&self, bucket: ZoomBucket) {
+        let pixel_ratio = self.pixel_ratio * bucket.zoom();
+        let mut cache = self.hinting_cache.borrow_mut();
+        let hinting = *cache
+            .entry(bucket)
+            .or_insert_with(|| Hinting::for_font(self.font.name(), pixel_ratio));
+        self.opacity_increase.set(hinting.opacity_increase);
+        self.opacity_exponent.set(hinting.opacity_exponent);
+    }
+
+    fn clear_hinting_cache(&self) {
+        self.hinting_cache.borrow_mut().clear();
     }
 
     fn set_context_and_update(&self, context: Option<&Context>) {
@@ -1004,11 +1033,23 @@ impl FontWithGpuData {
 // ================
 
 /// Stores all loaded fonts.
+///
+/// The glyphs are rendered from the MSDF atlas, which does not depend on the zoom level, so zooming
+/// the camera neither re-rasterizes the glyphs nor re-shapes the text. The shaped lines are cached
+/// by each text area and are recomputed only when its content or formatting changes, or when the
+/// cache is invalidated with [`Registry::invalidate_text_cache`]. The only zoom-dependent values,
+/// the hinting of the fonts, are computed once per [`ZoomBucket`] and cached, so they are updated
+/// only when the zoom crosses a bucket boundary.
 #[derive(Clone, CloneRef, Debug)]
 pub struct Registry {
-    network:            frp::Network,
-    fonts:              Rc<HashMap<Name, FontWithGpuData>>,
-    set_context_handle: ensogl_core::display::world::ContextHandler,
+    network:                    frp::Network,
+    fonts:                      Rc<HashMap<Name, FontWithGpuData>>,
+    set_context_handle:         ensogl_core::display::world::ContextHandler,
+    invalidate_text_cache:      frp::Source,
+    /// Emitted when all text areas in the scene should re-shape and redraw their text.
+    pub text_cache_invalidated: frp::Stream,
+    /// The bucket of the current camera zoom. Emitted only when the bucket changes.
+    pub zoom_bucket:            frp::Sampler<ZoomBucket>,
 }
 
 impl Registry {
@@ -1032,18 +1073,24 @@ impl Registry {
         self.fonts.get(&name).cloned()
     }
 
+    /// Make all text areas in the scene re-shape and redraw their text. Should be called when
+    /// anything affecting the text layout changes outside of the text areas, for example when the
+    /// theme or the font data changes. This is a costly operation.
+    pub fn invalidate_text_cache(&self) {
+        self.invalidate_text_cache.emit(());
+    }
+
     fn new(
         scene: &ensogl_core::display::Scene,
         fonts: impl IntoIterator<Item = (Name, Font)>,
     ) -> Self {
         let context = scene.context.borrow();
         let context = context.as_ref();
-        let scene_shape = scene.shape().value();
+        let pixel_ratio = scene.shape().value().pixel_ratio;
         let fonts: HashMap<_, _> = fonts
             .into_iter()
             .map(|(name, font)| {
-                let hinting = Hinting::for_font(&name, scene_shape);
-                let font = FontWithGpuData::new(font, hinting);
+                let font = FontWithGpuData::new(font, pixel_ratio);
                 font.set_context_and_update(context);
                 (name, font)
             })
@@ -1057,10 +1104,29 @@ impl Registry {
         });
         let network = frp::Network::new("font::Registry");
         let on_before_rendering = ensogl_core::animation::on_before_rendering();
+        let camera = scene.camera();
         frp::extend! { network
             eval_ on_before_rendering([fonts] Self::update(&fonts));
+            invalidate_text_cache <- source();
+            eval_ invalidate_text_cache ([fonts] {
+                fonts.values().for_each(|font| font.clear_hinting_cache())
+            });
+            zoom <- scene.frp.camera_changed.map(move |_| camera.zoom());
+            zoom_bucket <- zoom.map(|zoom| ZoomBucket::new(*zoom)).on_change().sampler();
+            current_bucket <- any(&zoom_bucket, &zoom_bucket.sample(&invalidate_text_cache));
+            eval current_bucket ([fonts] (bucket) {
+                fonts.values().for_each(|font| font.set_zoom_bucket(*bucket))
+            });
+        }
+        let text_cache_invalidated = invalidate_text_cache.clone_ref().into();
+        Self {
+            network,
+            fonts,
+            set_context_handle,
+            invalidate_text_cache,
+            text_cache_invalidated,
+            zoom_bucket,
         }
-        Self { network, fonts, set_context_handle }
     }
 
     fn update(fonts: impl AsRef<HashMap<Name, FontWithGpuData>>) {
@@ -1094,8 +1160,9 @@ struct Hinting {
 }
 
 impl Hinting {
-    fn for_font(font_name: &str, shape: scene::Shape) -> Self {
-        let pixel_ratio = shape.pixel_ratio;
+    /// The hinting for the font displayed with the given number of device pixels per scene unit,
+    /// i.e. the pixel ratio of the screen multiplied by the camera zoom.
+    fn for_font(font_name: &str, pixel_ratio: f32) -> Self {
         // The optimal hinting values must be found by testing. The [`text_area`] debug scene
         // supports trying different values at runtime.
         match font_name {
@@ -1115,6 +1182,31 @@ impl Default for Hinting {
 
 
 
+// ==================
+// === ZoomBucket ===
+// ==================
+
+/// The camera zoom quantized into buckets an eighth of an octave wide. The zoom-dependent text
+/// rendering values are computed once per bucket, instead of on every zoom change.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ZoomBucket(i32);
+
+impl ZoomBucket {
+    const PER_OCTAVE: f32 = 8.0;
+
+    /// The bucket containing the given zoom.
+    pub fn new(zoom: f32) -> Self {
+        Self((zoom.max(f32::MIN_POSITIVE).log2() * Self::PER_OCTAVE).round() as i32)
+    }
+
+    /// The zoom in the middle of the bucket.
+    pub fn zoom(self) -> f32 {
+        (self.0 as f32 / Self::PER_OCTAVE).exp2()
+    }
+}
+
+
+
 // =========================
 // === Embedded Registry ===
 // =========================
@@ -1231,4 +1323,17 @@ mod tests {
         })
         .unwrap();
     }
+
+    #[test]
+    fn zoom_buckets() {
+        assert_eq!(ZoomBucket::new(1.0), ZoomBucket::default());
+        assert_eq!(ZoomBucket::new(1.02), ZoomBucket::new(0.98));
+        assert_ne!(ZoomBucket::new(1.1), ZoomBucket::new(1.0));
+        assert_eq!(ZoomBucket::new(0.0), ZoomBucket::new(f32::MIN_POSITIVE));
+        for zoom in [0.1, 0.5, 1.0, 3.0, 10.0] {
+            let bucket = ZoomBucket::new(zoom);
+            assert_eq!(ZoomBucket::new(bucket.zoom()), bucket);
+            assert!((bucket.zoom() / zoom).log2().abs() <= 0.5 / ZoomBucket::PER_OCTAVE);
+        }
+    }
 }
@@ -0,0 +1,183 @@
+//! A single displayed toast: the notification text with a stripe colored by its severity and an
+//! optional action button. The toast fades in when created and fades out when dismissed.
+
+use crate::prelude::*;
+use ensogl_core::display::shape::*;
+
+use crate::Id;
+use crate::Notification;
+use crate::Severity;
+
+use enso_frp as frp;
+use ensogl_core::animation::delayed::DelayedAnimation;
+use ensogl_core::application::Application;
+use ensogl_core::control::io::mouse;
+use ensogl_core::data::color;
+use ensogl_core::display;
+use ensogl_core::Animation;
+use ensogl_hardcoded_theme::application::toast as theme;
+use ensogl_text as text;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The width of a toast.
+pub const WIDTH: f32 = 360.0;
+/// The height of a toast.
+pub const HEIGHT: f32 = 48.0;
+const PADDING: f32 = 16.0;
+const STRIPE_WIDTH: f32 = 4.0;
+const TEXT_SIZE: f32 = 12.0;
+/// The minimum gap between the text and the action button.
+const ACTION_GAP: f32 = 16.0;
+/// The opacity animation is considered finished when this close to the target.
+const OPACITY_PRECISION: f32 = 0.001;
+
+
+
+// ==============
+// === Colors ===
+// ==============
+
+/// The colors of a toast, read from the theme when the toast is created.
+#[derive(Clone, Copy, Debug)]
+struct Colors {
+    background: color::Rgba,
+    text:       color::Rgba,
+    action:     color::Rgba,
+    severity:   color::Rgba,
+}
+
+impl Colors {
+    fn new(style: &StyleWatch, severity: Severity) -> Self {
+        let severity_color = match severity {
+            Severity::Info => theme::severity::info,
+            Severity::Warning => theme::severity::warning,
+            Severity::Error => theme::severity::error,
+        };
+        Self {
+            background: style.get_color(theme::background),
+            text:       style.get_color(theme::text),
+            action:     style.get_color(theme::action),
+            severity:   style.get_color(severity_color),
+        }
+    }
+}
+
+
+
+// ============
+// === Item ===
+// ============
+
+/// A single displayed toast.
+#[derive(Clone, CloneRef, Debug, display::Object)]
+pub struct Item {
+    display_object: display::object::Instance,
+    id:             Id,
+    dismiss:        frp::Source,
+    _network:       frp::Network,
+}
+
+impl Item {
+    /// Constructor. The `closed` stream receives the toast's id when it finishes fading out, and
+    /// the `action_clicked` stream when its action button is clicked.
+    pub fn new(
+        app: &Application,
+        id: Id,
+        notification: &Notification,
+        style: &StyleWatch,
+        closed: &frp::Any<Id>,
+        action_clicked: &frp::Any<Id>,
+    ) -> Self {
+        let scene = &app.display.default_scene;
+        let display_object = display::object::Instance::new_named("Toast");
+        let background = Rectangle();
+        let stripe = Rectangle();
+        let text = app.new_view::<text::Text>();
+        let action = app.new_view::<text::Text>();
+        let action_area = Rectangle();
+        display_object.add_child(&background);
+        display_object.add_child(&stripe);
+        display_object.add_child(&text);
+        display_object.add_child(&action);
+        display_object.add_child(&action_area);
+        scene.layers.panel.add(&display_object);
+        scene.layers.panel_text.add(&text);
+        scene.layers.panel_text.add(&action);
+
+        let corner_radius = style.get_number(theme::corner_radius);
+        background.set_size(Vector2(WIDTH, HEIGHT)).set_corner_radius(corner_radius);
+        stripe.set_size(Vector2(STRIPE_WIDTH, HEIGHT - corner_radius * 2.0));
+        stripe.set_xy(Vector2(0.0, corner_radius));
+        let has_action = notification.action.is_some();
+        action_area.set_color(INVISIBLE_HOVER_COLOR).set_pointer_events(has_action);
+        for label in [&text, &action] {
+            label.set_single_line_mode(true);
+            label.set_property_default(text::Size(TEXT_SIZE));
+            label.set_y(HEIGHT / 2.0 + TEXT_SIZE / 2.0);
+        }
+        text.set_x(PADDING);
+        text.set_content(notification.text.clone());
+        action.set_content(notification.action.clone().unwrap_or_default());
+
+        let colors = Colors::new(style, notification.severity);
+        let network = frp::Network::new("Toast");
+        let opacity = Animation::<f32>::new(&network);
+        let timeout = DelayedAnimation::new(&network);
+        let action_click = action_area.on_event::<mouse::Down>();
+        frp::extend! { network
+            dismiss <- source_();
+            eval opacity.value ([background, stripe, text, action](opacity) {
+                background.set_color(colors.background.multiply_alpha(*opacity));
+                stripe.set_color(colors.severity.multiply_alpha(*opacity));
+                let text_color = colors.text.multiply_alpha(*opacity);
+                text.frp.set_property(.., text_color);
+                text.frp.set_property_default(text_color);
+                let action_color = colors.action.multiply_alpha(*opacity);
+                action.frp.set_property(.., action_color);
+                action.frp.set_property_default(action_color);
+            });
+
+            action_width <- action.width.map(move |w| if has_action { *w } else { 0.0 });
+            eval action_width ([text, action, action_area](width) {
+                action.set_x(WIDTH - PADDING - width);
+                action_area.set_size(Vector2(width + PADDING * 2.0, HEIGHT));
+                action_area.set_x(WIDTH - width - PADDING * 2.0);
+                let gap = if *width > 0.0 { ACTION_GAP } else { 0.0 };
+                text.set_view_width(Some(WIDTH - PADDING * 2.0 - width - gap));
+            });
+
+            clicked <- action_click.filter(|e| e.button() == mouse::PrimaryButton).constant(id);
+            action_clicked <+ clicked;
+            fade_out <- any_(dismiss, timeout.on_end, clicked);
+            is_fading_out <- fade_out.constant(true);
+            opacity.target <+ fade_out.constant(0.0);
+            closed <+ opacity.on_end.gate(&is_fading_out).constant(id);
+        }
+        opacity.precision.emit(OPACITY_PRECISION);
+        opacity.target.emit(1.0);
+        if let Some(timeout_ms) = notification.timeout {
+            timeout.set_delay(timeout_ms);
+            timeout.set_duration(0.0);
+            timeout.start();
+        }
+        network.store(&opacity);
+        network.store(&timeout);
+
+        Self { display_object, id, dismiss, _network: network }
+    }
+
+    /// The identifier of the displayed notification.
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    /// Fade the toast out. The `closed` stream receives its id when it disappears.
+    pub fn dismiss(&self) {
+        self.dismiss.emit(());
+    }
+}
@@ -0,0 +1,366 @@
+//! Toast notifications: short messages stacked in the bottom-right corner of the screen, which
+//! disappear after a timeout or when dismissed.
+//!
+//! At most [`MAX_VISIBLE`] toasts are displayed at once. The notifications shown while the stack
+//! is full are queued and displayed when the older toasts disappear.
+
+#![recursion_limit = "1024"]
+// === Standard Linter Configuration ===
+#![deny(non_ascii_idents)]
+#![warn(unsafe_code)]
+#![allow(clippy::bool_to_int_with_if)]
+#![allow(clippy::let_and_return)]
+// === Non-Standard Linter Configuration ===
+#![warn(missing_copy_implementations)]
+#![warn(missing_debug_implementations)]
+#![warn(missing_docs)]
+#![warn(trivial_casts)]
+#![warn(trivial_numeric_casts)]
+#![warn(unused_import_braces)]
+#![warn(unused_qualifications)]
+
+use ensogl_core::prelude::*;
+
+use enso_frp as frp;
+use ensogl_core::application::command::FrpNetworkProvider;
+use ensogl_core::application::frp::API;
+use ensogl_core::application::Application;
+use ensogl_core::display;
+use ensogl_core::display::shape::StyleWatch;
+use ensogl_core::gui::Widget;
+use std::collections::VecDeque;
+
+
+// ==============
+// === Export ===
+// ==============
+
+pub mod item;
+
+/// Commonly used types and functions.
+pub mod prelude {
+    pub use ensogl_core::prelude::*;
+}
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The maximum number of toasts displayed at once.
+pub const MAX_VISIBLE: usize = 3;
+/// The time after which a notification disappears, unless specified otherwise.
+pub const DEFAULT_TIMEOUT_MS: f32 = 5_000.0;
+/// The distance between the toasts and the edges of the screen.
+const MARGIN: f32 = 16.0;
+/// The vertical gap between the stacked toasts.
+const GAP: f32 = 8.0;
+
+
+
+// ====================
+// === Notification ===
+// ====================
+
+/// The severity of a notification, determining the color of its toast.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum Severity {
+    #[default]
+    Info,
+    Warning,
+    Error,
+}
+
+/// A notification displayed as a toast.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Notification {
+    /// The message of the notification.
+    pub text:     ImString,
+    /// The severity of the notification.
+    pub severity: Severity,
+    /// The time in milliseconds after which the toast disappears. If [`None`], the toast stays
+    /// until dismissed.
+    pub timeout:  Option<f32>,
+    /// The label of the action button. Clicking it emits the `action_clicked` output.
+    pub action:   Option<ImString>,
+}
+
+impl Notification {
+    /// An informative notification disappearing after [`DEFAULT_TIMEOUT_MS`].
+    pub fn new(text: impl Into<ImString>) -> Self {
+        Self { text: text.into(), timeout: Some(DEFAULT_TIMEOUT_MS), ..default() }
+    }
+
+    /// Set the severity of the notification.
+    pub fn with_severity(self, severity: Severity) -> Self {
+        Self { severity, ..self }
+    }
+
+    /// Set the time in milliseconds after which the toast disappears. See [`Self::timeout`].
+    pub fn with_timeout(self, timeout: Option<f32>) -> Self {
+        Self { timeout, ..self }
+    }
+
+    /// Add an action button with the given label.
+    pub fn with_action(self, label: impl Into<ImString>) -> Self {
+        Self { action: Some(label.into()), ..self }
+    }
+}
+
+/// The identifier of a notification shown by a [`Toasts`] component.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Id(usize);
+
+
+
+// ===========
+// === FRP ===
+// ===========
+
+ensogl_core::define_endpoints_2! {
+    Input {
+        /// Display the notification, or queue it if [`MAX_VISIBLE`] toasts are already displayed.
+        show(Notification),
+        /// Hide the toast of the notification, or remove it from the queue.
+        dismiss(Id),
+        /// Hide all toasts and clear the queue.
+        dismiss_all(),
+    }
+    Output {
+        /// The identifier assigned to the last notification passed to the `show` input.
+        added(Id),
+        /// The notification disappeared or was removed from the queue.
+        dismissed(Id),
+        /// The action button of the notification was clicked.
+        action_clicked(Id),
+    }
+}
+
+
+
+// =============
+// === Model ===
+// =============
+
+/// The internal model of [`Toasts`].
+#[derive(Debug, Clone, CloneRef, display::Object)]
+pub struct Model {
+    app:            Application,
+    display_object: display::object::Instance,
+    style:          StyleWatch,
+    /// The displayed toasts, the oldest first.
+    items:          Rc<RefCell<Vec<item::Item>>>,
+    queue:          Rc<RefCell<VecDeque<(Id, Notification)>>>,
+    next_id:        Rc<Cell<usize>>,
+    screen_size:    Rc<Cell<Vector2>>,
+    /// Receives the id of each notification which disappeared or was removed from the queue.
+    closed:         frp::Any<Id>,
+    action_clicked: frp::Any<Id>,
+}
+
+impl Model {
+    /// Constructor.
+    pub fn new(app: &Application, network: &frp::Network) -> Self {
+        let app = app.clone_ref();
+        let display_object = display::object::Instance::new_named("Toasts");
+        let style = StyleWatch::new(&app.display.default_scene.style_sheet);
+        frp::extend! { network
+            closed <- any_mut::<Id>();
+            action_clicked <- any_mut::<Id>();
+        }
+        let items = default();
+        let queue = default();
+        let next_id = default();
+        let screen_size = default();
+        Self {
+            app,
+            display_object,
+            style,
+            items,
+            queue,
+            next_id,
+            screen_size,
+            closed,
+            action_clicked,
+        }
+    }
+
+    fn add(&self, notification: &Notification) -> Id {
+        let id = Id(self.next_id.get());
+        self.next_id.set(id.0 + 1);
+        if self.items.borrow().len() < MAX_VISIBLE {
+            self.display(id, notification);
+        } else {
+            self.queue.borrow_mut().push_back((id, notification.clone()));
+        }
+        id
+    }
+
+    fn display(&self, id: Id, notification: &Notification) {
+        let item = item::Item::new(
+            &self.app,
+            id,
+            notification,
+            &self.style,
+            &self.closed,
+            &self.action_clicked,
+        );
+        self.display_object.add_child(&item);
+        self.items.borrow_mut().push(item);
+        self.update_layout();
+    }
+
+    fn dismiss(&self, id: Id) {
+        let item = self.items.borrow().iter().find(|item| item.id() == id).cloned();
+        if let Some(item) = item {
+            item.dismiss();
+        } else {
+            let removed = self.remove_from_queue(|queued| queued == id);
+            for id in removed {
+                self.closed.emit(id);
+            }
+        }
+    }
+
+    fn dismiss_all(&self) {
+        for id in self.remove_from_queue(|_| true) {
+            self.closed.emit(id);
+        }
+        let items = self.items.borrow().clone();
+        for item in items {
+            item.dismiss();
+        }
+    }
+
+    fn remove_from_queue(&self, condition: impl Fn(Id) -> bool) -> Vec<Id> {
+        let mut queue = self.queue.borrow_mut();
+        let removed = queue.iter().map(|(id, _)| *id).filter(|id| condition(*id)).collect_vec();
+        queue.retain(|(id, _)| !condition(*id));
+        removed
+    }
+
+    /// Remove the toast which disappeared and display the next queued notification in its place.
+    fn remove(&self, id: Id) {
+        let removed = self.items.borrow().iter().position(|item| item.id() == id);
+        if let Some(index) = removed {
+            let item = self.items.borrow_mut().remove(index);
+            item.unset_parent();
+            let next = self.queue.borrow_mut().pop_front();
+            if let Some((id, notification)) = next {
+                self.display(id, &notification);
+            } else {
+                self.update_layout();
+            }
+        }
+    }
+
+    fn set_screen_size(&self, size: Vector2) {
+        self.screen_size.set(size);
+        self.update_layout();
+    }
+
+    /// Stack the toasts in the bottom-right corner, the newest at the bottom.
+    fn update_layout(&self) {
+        let screen_size = self.screen_size.get();
+        let x = screen_size.x / 2.0 - MARGIN - item::WIDTH;
+        let bottom = -screen_size.y / 2.0 + MARGIN;
+        for (index, item) in self.items.borrow().iter().rev().enumerate() {
+            item.set_xy(Vector2(x, bottom + index as f32 * (item::HEIGHT + GAP)));
+        }
+    }
+}
+
+
+
+// ==============
+// === Toasts ===
+// ==============
+
+/// The stack of toast notifications. See the module documentation for details.
+#[derive(Debug, Clone, CloneRef, Deref, display::Object)]
+pub struct Toasts {
+    widget: Widget<Model, Frp>,
+}
+
+impl Toasts {
+    /// Constructor.
+    pub fn new(app: &Application) -> Self {
+        let frp = Frp::new();
+        let network = frp.network();
+        let model = Rc::new(Model::new(app, network));
+        let input = &frp.private().input;
+        let out = &frp.private().output;
+        let scene_shape = app.display.default_scene.shape();
+
+        frp::extend! { network
+            out.added <+ input.show.map(f!((notification) model.add(notification)));
+            eval input.dismiss ((id) model.dismiss(*id));
+            eval_ input.dismiss_all (model.dismiss_all());
+            eval model.closed ((id) model.remove(*id));
+            out.dismissed <+ model.closed;
+            out.action_clicked <+ model.action_clicked;
+            eval scene_shape ((shape) model.set_screen_size(Vector2(shape.width, shape.height)));
+        }
+        let shape = scene_shape.value();
+        model.set_screen_size(Vector2(shape.width, shape.height));
+
+        let widget = Widget::new(app, frp, model);
+        Self { widget }
+    }
+}
+
+impl FrpNetworkProvider for Toasts {
+    fn network(&self) -> &frp::Network {
+        self.widget.frp().network()
+    }
+}
+
+impl ensogl_core::application::View for Toasts {
+    fn label() -> &'static str {
+        "Toasts"
+    }
+
+    fn new(app: &Application) -> Self {
+        Self::new(app)
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ensogl_core::application::test_utils;
+
+    #[test]
+    fn queueing_notifications() {
+        let (_app, toasts) = test_utils::init_component_for_test::<Toasts>();
+        let model = toasts.widget.model();
+        let visible = || model.items.borrow().iter().map(|item| item.id()).collect_vec();
+        let queued = || model.queue.borrow().iter().map(|(id, _)| *id).collect_vec();
+        let ids = (0..5)
+            .map(|i| {
+                toasts.show(Notification::new(format!("Notification {i}")));
+                toasts.added.value()
+            })
+            .collect_vec();
+        assert_eq!(visible(), ids[..3]);
+        assert_eq!(queued(), ids[3..]);
+
+        toasts.dismiss(ids[4]);
+        assert_eq!(toasts.dismissed.value(), ids[4]);
+        assert_eq!(queued(), ids[3..4]);
+
+        // Simulate the first toast finishing its fade-out animation.
+        model.closed.emit(ids[0]);
+        assert_eq!(toasts.dismissed.value(), ids[0]);
+        assert_eq!(visible(), vec![ids[1], ids[2], ids[3]]);
+        assert!(queued().is_empty());
+    }
+}
@@ -6,6 +6,8 @@ use enso_web::traits::*;
 use crate::application::command::FrpNetworkProvider;
 use crate::display;
 use crate::display::scene::DomPath;
+use crate::display::style::theme;
+use crate::display::world;
 use crate::display::world::World;
 use crate::gui::cursor::Cursor;
 use crate::system::web;
@@ -17,6 +19,7 @@ use crate::system::web;
 
 pub mod command;
 pub mod frp;
+pub mod i18n;
 pub mod shortcut;
 pub mod tooltip;
 pub mod view;
@@ -44,10 +47,18 @@ crate::define_endpoints_2! {
         hide_system_cursor(),
         /// Show a notification.
         show_notification(String),
+        /// Set the locale the user-visible strings are translated to. See [`i18n`].
+        set_locale(i18n::LanguageTag),
+        /// Enable the theme of the given name, registered with [`Application::register_theme`].
+        /// Unknown themes are ignored with a warning.
+        set_theme(ImString),
     }
     Output {
         tooltip(tooltip::Style),
         notification(String),
+        locale(i18n::LanguageTag),
+        /// The name of the theme enabled with the `set_theme` input.
+        theme(ImString),
     }
 }
 
@@ -74,6 +85,7 @@ pub struct ApplicationData {
     pub commands:  command::Registry,
     pub shortcuts: shortcut::Registry,
     pub views:     view::Registry,
+    pub i18n:      i18n::Catalog,
     pub frp:       Frp,
 }
 
@@ -89,9 +101,10 @@ impl Application {
         let views = view::Registry::create(&commands, &shortcuts);
         let cursor = Cursor::new(&display.default_scene);
         display.add_child(&cursor);
+        let i18n = default();
         let frp = Frp::new();
 
-        let data = ApplicationData { cursor, display, commands, shortcuts, views, frp };
+        let data = ApplicationData { cursor, display, commands, shortcuts, views, i18n, frp };
 
         Self { inner: Rc::new(data) }.init()
     }
@@ -105,7 +118,15 @@ impl Application {
             eval_ frp.private.input.show_system_cursor([] Self::show_system_cursor(true));
             eval_ frp.private.input.hide_system_cursor([] Self::show_system_cursor(false));
 
+            frp.private.output.locale <+ frp.private.input.set_locale.on_change();
             frp.private.output.tooltip <+ frp.private.input.set_tooltip;
+
+            is_theme_known <- frp.private.input.set_theme.map(|name| Self::is_theme_known(name));
+            known_theme <- frp.private.input.set_theme.gate(&is_theme_known);
+            unknown_theme <- frp.private.input.set_theme.gate_not(&is_theme_known);
+            eval known_theme ((name) Self::theme_manager().set_enabled([name]));
+            eval unknown_theme ((name) warn!("Cannot enable unknown theme \"{name}\"."));
+            frp.private.output.theme <+ known_theme;
         }
         // We hide the system cursor to replace it with the EnsoGL-provided one.
         self.frp.hide_system_cursor();
@@ -123,6 +144,26 @@ impl Application {
     pub fn new_view<T: View>(&self) -> T {
         self.views.new_view(self)
     }
+
+    fn theme_manager() -> theme::Manager {
+        world::with_context(|t| t.theme_manager.clone_ref())
+    }
+
+    fn is_theme_known(name: &str) -> bool {
+        Self::theme_manager().get(name).is_some()
+    }
+
+    /// Register a theme, so it can be enabled with the `set_theme` input. Registering a theme under
+    /// the name of the enabled one replaces its values, and all the styles read from the theme are
+    /// updated in the next frame. The builtin themes can be used as a base of the custom ones.
+    pub fn register_theme(&self, name: impl Str, theme: impl Into<theme::Theme>) {
+        Self::theme_manager().register(name, theme)
+    }
+
+    /// The message catalog bound to the current locale. See [`i18n`].
+    pub fn translator(&self) -> i18n::Translator {
+        i18n::Translator::new(&self.i18n, self.frp.locale.value())
+    }
 }
 
 
@@ -175,8 +216,36 @@ pub mod test_utils {
 mod tests {
     use super::*;
 
+    use crate::display::style::data::DataMatch;
+
     #[test]
     fn native_compilation_in_test_mode() {
         let _app = Application::new("root");
     }
+
+    #[test]
+    fn switching_themes() {
+        let app = Application::new("root");
+        let path = "test.switching_themes.value";
+        let var = app.display.default_scene.style_sheet.var(path);
+        for (name, value) in [("first", 1.0), ("second", 2.0)] {
+            let theme = theme::Theme::new();
+            theme.set(path, value);
+            app.register_theme(name, theme);
+        }
+
+        app.frp.set_theme(ImString::from("first"));
+        assert_eq!(app.frp.theme.value(), "first");
+        assert_eq!(var.value().number(), Some(1.0));
+        app.frp.set_theme(ImString::from("unknown"));
+        assert_eq!(app.frp.theme.value(), "first");
+        app.frp.set_theme(ImString::from("second"));
+        assert_eq!(var.value().number(), Some(2.0));
+
+        let replaced = theme::Theme::new();
+        replaced.set(path, 3.0);
+        app.register_theme("second", replaced);
+        Application::theme_manager().update();
+        assert_eq!(var.value().number(), Some(3.0));
+    }
 }
@@ -220,4 +220,46 @@ impl Registry {
     pub fn set_command_enabled<T: View>(&self, instance: &T, name: impl AsRef<str>, enabled: bool) {
         self.with_command_mut(instance, name, |command| command.enabled = enabled)
     }
+
+    /// The commands enabled in any alive instance of a registered view, as pairs of the view label
+    /// and the command name. The result is sorted and contains no duplicates.
+    pub fn enabled_commands(&self) -> Vec<(String, String)> {
+        let name_map = self.name_map.borrow();
+        let mut commands = name_map
+            .iter()
+            .flat_map(|(label, instances)| {
+                let alive = instances.iter().filter(|instance| instance.check_alive());
+                alive.flat_map(move |instance| {
+                    let command_map = instance.command_map.borrow();
+                    let enabled = command_map.iter().filter(|(_, command)| command.enabled);
+                    enabled.map(|(name, _)| (label.clone(), name.clone())).collect_vec()
+                })
+            })
+            .collect_vec();
+        commands.sort();
+        commands.dedup();
+        commands
+    }
+
+    /// Emit the command in all alive instances of the view having it enabled, the same way as a
+    /// shortcut bound to the command does.
+    pub fn emit_command(&self, label: &str, name: &str) {
+        let targets = {
+            let name_map = self.name_map.borrow();
+            let instances = name_map.get(label).map(Vec::as_slice).unwrap_or_default();
+            let alive = instances.iter().filter(|instance| instance.check_alive());
+            let commands = alive.filter_map(|instance| {
+                let command_map = instance.command_map.borrow();
+                let command = command_map.get(name).filter(|command| command.enabled)?;
+                Some(command.frp.clone_ref())
+            });
+            commands.collect_vec()
+        };
+        if targets.is_empty() {
+            warn!("The command {name} is not enabled in any instance of {label}.");
+        }
+        for target in targets {
+            target.emit(());
+        }
+    }
 }
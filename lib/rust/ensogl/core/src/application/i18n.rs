@@ -0,0 +1,179 @@
+//! Localization of the user-visible strings. The messages are identified by their English text,
+//! which is also displayed when the catalog has no translation for the current locale. The current
+//! locale is set with the `set_locale` input of the [`Application`](super::Application) FRP.
+
+use crate::prelude::*;
+
+
+
+// ===================
+// === LanguageTag ===
+// ===================
+
+/// The BCP 47 language tag identifying the locale, like `en`, `pl` or `pt-BR`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct LanguageTag {
+    tag: ImString,
+}
+
+impl LanguageTag {
+    /// Constructor.
+    pub fn new(tag: impl Into<ImString>) -> Self {
+        Self { tag: tag.into() }
+    }
+
+    /// The tag of the English language, in which the messages are written.
+    pub fn english() -> Self {
+        Self::new("en")
+    }
+
+    /// The tag without the region and script subtags, e.g. `pt` for `pt-BR`.
+    pub fn primary_language(&self) -> Self {
+        let primary = self.tag.split(['-', '_']).next().unwrap_or_default();
+        Self::new(primary)
+    }
+
+    /// The tag as a string.
+    pub fn as_str(&self) -> &str {
+        &self.tag
+    }
+}
+
+impl Default for LanguageTag {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+impl Display for LanguageTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.tag, f)
+    }
+}
+
+
+
+// ===============
+// === Catalog ===
+// ===============
+
+/// The translations of the messages to the supported locales.
+#[derive(Clone, CloneRef, Debug, Default)]
+pub struct Catalog {
+    translations: Rc<RefCell<HashMap<LanguageTag, HashMap<ImString, ImString>>>>,
+}
+
+impl Catalog {
+    /// Register the translation of the English `message` to the `locale`.
+    pub fn add_translation(
+        &self,
+        locale: &LanguageTag,
+        message: impl Into<ImString>,
+        translation: impl Into<ImString>,
+    ) {
+        let mut translations = self.translations.borrow_mut();
+        let messages = translations.entry(locale.clone()).or_default();
+        messages.insert(message.into(), translation.into());
+    }
+
+    /// Register many translations to the `locale`, given as the pairs of the English message and
+    /// its translation.
+    pub fn add_translations<'a>(
+        &self,
+        locale: &LanguageTag,
+        translations: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) {
+        for (message, translation) in translations {
+            self.add_translation(locale, message, translation);
+        }
+    }
+
+    /// Translate the English `message` to the `locale`. If there is no translation for the exact
+    /// locale, the translation to its primary language is used. The message is returned
+    /// unchanged if neither is registered.
+    pub fn lookup(&self, locale: &LanguageTag, message: &str) -> ImString {
+        let translations = self.translations.borrow();
+        let translate = |locale: &LanguageTag| translations.get(locale)?.get(message).cloned();
+        let translation = translate(locale).or_else(|| translate(&locale.primary_language()));
+        translation.unwrap_or_else(|| message.into())
+    }
+}
+
+
+
+// ==================
+// === Translator ===
+// ==================
+
+/// The message [`Catalog`] bound to a locale.
+#[derive(Clone, CloneRef, Debug, Default)]
+pub struct Translator {
+    catalog: Catalog,
+    locale:  Rc<LanguageTag>,
+}
+
+impl Translator {
+    /// Constructor.
+    pub fn new(catalog: &Catalog, locale: LanguageTag) -> Self {
+        Self { catalog: catalog.clone_ref(), locale: Rc::new(locale) }
+    }
+
+    /// Translate the English `message`. See [`Catalog::lookup`].
+    pub fn translate(&self, message: &str) -> ImString {
+        self.catalog.lookup(&self.locale, message)
+    }
+
+    /// Translate the English `message` and substitute its `{name}` placeholders with the
+    /// arguments. See [`format`].
+    pub fn translate_with(&self, message: &str, args: &[(&str, &str)]) -> String {
+        format(&self.translate(message), args)
+    }
+}
+
+
+
+// ==============
+// === Format ===
+// ==============
+
+/// Substitute the `{name}` placeholders in the message with the values of the arguments. The
+/// placeholders allow the translations to order the arguments differently than the English
+/// message.
+pub fn format(message: &str, args: &[(&str, &str)]) -> String {
+    args.iter().fold(message.to_owned(), |message, (name, value)| {
+        message.replace(&format!("{{{name}}}"), value)
+    })
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup_falls_back_to_primary_language_and_english() {
+        let catalog = Catalog::default();
+        let polish = LanguageTag::new("pl");
+        let brazilian = LanguageTag::new("pt-BR");
+        catalog.add_translations(&polish, [("Freeze", "Zamroź"), ("Skip", "Pomiń")]);
+        catalog.add_translation(&LanguageTag::new("pt"), "Freeze", "Congelar");
+        assert_eq!(catalog.lookup(&polish, "Skip"), "Pomiń");
+        assert_eq!(catalog.lookup(&LanguageTag::new("pl-PL"), "Freeze"), "Zamroź");
+        assert_eq!(catalog.lookup(&brazilian, "Freeze"), "Congelar");
+        assert_eq!(catalog.lookup(&brazilian, "Skip"), "Skip");
+        assert_eq!(catalog.lookup(&LanguageTag::english(), "Freeze"), "Freeze");
+    }
+
+    #[test]
+    fn formatting_placeholders() {
+        let message = "Expected {expected}, but got {actual}.";
+        let args = [("expected", "Number"), ("actual", "Text")];
+        assert_eq!(format(message, &args), "Expected Number, but got Text.");
+        assert_eq!(format("{a}{a}{b}", &[("a", "x")]), "xx{b}");
+    }
+}
@@ -11,6 +11,16 @@ use super::command;
 use enso_shortcuts as shortcuts;
 
 
+
+// =================
+// === Constants ===
+// =================
+
+/// The conditions with more variables are assumed to overlap, as checking all the combinations of
+/// variable values would be too costly.
+const MAX_CHECKED_CONDITION_VARIABLES: usize = 10;
+
+
 // ==============
 // === Export ===
 // ==============
@@ -128,6 +138,59 @@ impl Condition {
     }
 }
 
+impl Condition {
+    /// Evaluate the condition, given the values of the variables.
+    pub fn eval(&self, is_set: &impl Fn(&str) -> bool) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::When(name) => is_set(name),
+            Self::Not(a) => !a.eval(is_set),
+            Self::Or(a, b) => a.eval(is_set) || b.eval(is_set),
+            Self::And(a, b) => a.eval(is_set) && b.eval(is_set),
+        }
+    }
+
+    fn collect_variables<'a>(&'a self, variables: &mut Vec<&'a str>) {
+        match self {
+            Self::Always | Self::Never => {}
+            Self::When(name) => variables.push(name),
+            Self::Not(a) => a.collect_variables(variables),
+            Self::Or(a, b) | Self::And(a, b) => {
+                a.collect_variables(variables);
+                b.collect_variables(variables);
+            }
+        }
+    }
+
+    /// Check whether both conditions can be true at the same time, when evaluated with the same
+    /// variable values. The check is conservative: it returns `false` only if the conditions are
+    /// provably disjoint.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        let mut variables = Vec::new();
+        self.collect_variables(&mut variables);
+        other.collect_variables(&mut variables);
+        variables.sort_unstable();
+        variables.dedup();
+        if variables.len() > MAX_CHECKED_CONDITION_VARIABLES {
+            return true;
+        }
+        (0..1_u32 << variables.len()).any(|values| {
+            let is_set = |name: &str| {
+                let index = variables.iter().position(|variable| *variable == name);
+                index.map_or(false, |index| values & (1 << index) != 0)
+            };
+            self.eval(&is_set) && other.eval(&is_set)
+        })
+    }
+
+    /// Check whether the condition can be true for some variable values. The check is
+    /// conservative: it returns `false` only if the condition is provably never met.
+    pub fn is_satisfiable(&self) -> bool {
+        self.overlaps(&Self::Always)
+    }
+}
+
 impl From<&str> for Condition {
     fn from(s: &str) -> Self {
         Self::parse(s)
@@ -210,10 +273,41 @@ impl Shortcut {
 
 
 
+// ================
+// === Conflict ===
+// ================
+
+/// Two shortcuts of different commands, triggered by the same keys under overlapping conditions.
+/// When the keys are pressed, both commands are executed, so one of the views usually reacts to a
+/// shortcut meant for the other.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[allow(missing_docs)]
+pub struct Conflict {
+    pub first:  Shortcut,
+    pub second: Shortcut,
+}
+
+impl Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let describe = |shortcut: &Shortcut| {
+            let action = &shortcut.action;
+            format!("{}.{} ({:?})", action.target, *action.command, shortcut.rule.pattern)
+        };
+        write!(f, "{} conflicts with {}", describe(&self.first), describe(&self.second))
+    }
+}
+
+
+
+
 // ================
 // === Registry ===
 // ================
 
+/// The shortcuts of all the registries of the application: the global one and the ones bound to
+/// view instances. The registries of the dropped instances are skipped.
+type RegistriesShortcuts = Rc<RefCell<Vec<Weak<RefCell<Vec<Shortcut>>>>>>;
+
 /// Shortcut registry. See `Shortcut` to learn more.
 ///
 /// You can add new shortcuts by using the `add` method and get a `Handle` back. When `Handle` is
@@ -231,6 +325,8 @@ pub struct Registry {
     /// An FRP node that contains the name of the command currently being executed.
     /// `None` means that no command is being executed.
     pub currently_handled: frp::Source<Option<ImString>>,
+    /// Emitted when an added shortcut conflicts with a previously added one. See [`Conflict`].
+    pub conflict_detected: frp::Source<Conflict>,
 }
 
 /// Internal representation of `Registry`.
@@ -239,7 +335,12 @@ pub struct RegistryModel {
     mouse:              Mouse_DEPRECATED,
     command_registry:   command::Registry,
     shortcuts_registry: shortcuts::HashSetRegistry<Shortcut>,
+    /// All the added shortcuts, used to display them next to the commands they trigger.
+    shortcuts:          Rc<RefCell<Vec<Shortcut>>>,
+    /// The shortcuts of all the registries, used to detect the conflicts between them.
+    all_shortcuts:      RegistriesShortcuts,
     currently_handled:  frp::Source<Option<ImString>>,
+    conflict_detected:  frp::Source<Conflict>,
     /// If present, this is the receiver of commands.
     target:             Option<frp::NetworkId>,
 }
@@ -254,10 +355,18 @@ impl Registry {
     ) -> Self {
         frp::new_network! { network
             def currently_handled = source();
+            def conflict_detected = source();
         }
-        let model = RegistryModel::new(mouse, cmd_registry, currently_handled.clone_ref(), None);
+        let model = RegistryModel::new(
+            mouse,
+            cmd_registry,
+            default(),
+            currently_handled.clone_ref(),
+            conflict_detected.clone_ref(),
+            None,
+        );
         Self::extend_network(&network, &model, keyboard_target, global_keyboard_target);
-        Self { model, network, currently_handled }
+        Self { model, network, currently_handled, conflict_detected }
     }
 
     /// Create a shortcut registry inheriting global parameters, bound to the given instance, and
@@ -271,8 +380,18 @@ impl Registry {
     ) -> RegistryModel {
         let mouse = &self.mouse;
         let cmd_registry = &self.command_registry;
+        let all_shortcuts = self.all_shortcuts.clone_ref();
         let currently_handled = self.currently_handled.clone_ref();
-        let model = RegistryModel::new(mouse, cmd_registry, currently_handled, Some(instance));
+        let conflict_detected = self.conflict_detected.clone_ref();
+        let target = Some(instance);
+        let model = RegistryModel::new(
+            mouse,
+            cmd_registry,
+            all_shortcuts,
+            currently_handled,
+            conflict_detected,
+            target,
+        );
         Self::extend_network(network, &model, keyboard_target, global_keyboard_target);
         model
     }
@@ -309,13 +428,78 @@ impl RegistryModel {
     pub fn new(
         mouse: &Mouse_DEPRECATED,
         command_registry: &command::Registry,
+        all_shortcuts: RegistriesShortcuts,
         currently_handled: frp::Source<Option<ImString>>,
+        conflict_detected: frp::Source<Conflict>,
         target: Option<frp::NetworkId>,
     ) -> Self {
         let mouse = mouse.clone_ref();
         let command_registry = command_registry.clone_ref();
         let shortcuts_registry = default();
-        Self { mouse, command_registry, shortcuts_registry, currently_handled, target }
+        let shortcuts: Rc<RefCell<Vec<Shortcut>>> = default();
+        all_shortcuts.borrow_mut().retain(|shortcuts| shortcuts.strong_count() > 0);
+        all_shortcuts.borrow_mut().push(Rc::downgrade(&shortcuts));
+        Self {
+            mouse,
+            command_registry,
+            shortcuts_registry,
+            shortcuts,
+            all_shortcuts,
+            currently_handled,
+            conflict_detected,
+            target,
+        }
+    }
+
+    /// The shortcuts added to all the registries of the application.
+    fn registered_shortcuts(&self) -> Vec<Shortcut> {
+        let registries = self.all_shortcuts.borrow();
+        let registries = registries.iter().filter_map(Weak::upgrade);
+        registries.flat_map(|shortcuts| shortcuts.borrow().clone()).collect()
+    }
+
+    /// All pairs of the shortcuts added to any of the registries of the application, conflicting
+    /// with each other. See [`Conflict`].
+    pub fn shortcut_conflicts(&self) -> Vec<Conflict> {
+        let shortcuts = self.registered_shortcuts();
+        let pairs = shortcuts.iter().enumerate().flat_map(|(index, first)| {
+            shortcuts[index + 1..].iter().map(move |second| (first, second))
+        });
+        let conflicting = pairs.filter(|(first, second)| self.conflicting(first, second));
+        let to_conflict = |(first, second): (&Shortcut, &Shortcut)| Conflict {
+            first:  first.clone(),
+            second: second.clone(),
+        };
+        conflicting.map(to_conflict).collect()
+    }
+
+    /// Check whether both shortcuts may trigger different commands on the same key press. The
+    /// conditions of the shortcuts of the same view are checked against the status of the same
+    /// instance. The conditions of different views are checked against the statuses of different
+    /// instances, so their variables are independent, e.g. `focused` and `!focused` overlap.
+    fn conflicting(&self, first: &Shortcut, second: &Shortcut) -> bool {
+        let registry = &self.shortcuts_registry;
+        let conditions_overlap = if first.action.target == second.action.target {
+            first.condition.overlaps(&second.condition)
+        } else {
+            first.condition.is_satisfiable() && second.condition.is_satisfiable()
+        };
+        let same_command = first.target == second.target && first.command == second.command;
+        first.rule.tp == second.rule.tp
+            && !same_command
+            && conditions_overlap
+            && registry.expressions_overlap(&first.rule.pattern, &second.rule.pattern)
+    }
+
+    /// The patterns of the shortcuts pressing which triggers the command of the view with given
+    /// label, like `"cmd shift p"`.
+    pub fn patterns_of(&self, label: &str, command: &str) -> Vec<String> {
+        let shortcuts = self.shortcuts.borrow();
+        let matching = shortcuts.iter().filter(|shortcut| {
+            let is_press = shortcut.rule.tp != ActionType::Release;
+            is_press && shortcut.action.target == label && shortcut.action.command.name == command
+        });
+        matching.map(|shortcut| shortcut.rule.pattern.clone()).collect()
     }
 
     fn process_rules(&self, stop_propagation: impl FnOnce<()>, rules: &[Shortcut]) {
@@ -369,21 +553,87 @@ impl RegistryModel {
         condition: &Condition,
         status: &Rc<RefCell<HashMap<String, frp::Sampler<bool>>>>,
     ) -> bool {
-        use Condition::*;
-        match condition {
-            Always => true,
-            Never => false,
-            When(name) => status.borrow().get(name).map(|t| t.value()).unwrap_or(false),
-            Not(a) => !Self::condition_checker(a, status),
-            Or(a, b) => Self::condition_checker(a, status) || Self::condition_checker(b, status),
-            And(a, b) => Self::condition_checker(a, status) && Self::condition_checker(b, status),
-        }
+        condition.eval(&|name| status.borrow().get(name).map(|t| t.value()).unwrap_or(false))
     }
 }
 
 impl Add<Shortcut> for &RegistryModel {
     type Output = ();
     fn add(self, shortcut: Shortcut) {
+        let added = self.registered_shortcuts();
+        let conflicts = added.iter().filter_map(|added| {
+            self.conflicting(added, &shortcut)
+                .then(|| Conflict { first: added.clone(), second: shortcut.clone() })
+        });
+        let conflicts = conflicts.collect_vec();
         self.shortcuts_registry.add(shortcut.rule.tp, &shortcut.rule.pattern, shortcut.clone());
+        self.shortcuts.borrow_mut().push(shortcut);
+        for conflict in conflicts {
+            warn!("Shortcut conflict: {conflict}.");
+            self.conflict_detected.emit(conflict);
+        }
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::application::Application;
+
+    #[test]
+    fn overlapping_conditions() {
+        let overlaps = |a: &str, b: &str| Condition::from(a).overlaps(&b.into());
+        assert!(overlaps("", "focused"));
+        assert!(overlaps("focused", "focused & !editing"));
+        assert!(overlaps("a | b", "!a"));
+        assert!(!overlaps("focused", "!focused"));
+        assert!(!overlaps("a & b", "!a | !b"));
+        assert!(Condition::from("a | !a").is_satisfiable());
+        assert!(!Condition::from("a & !a").is_satisfiable());
+    }
+
+    #[test]
+    fn detecting_shortcut_conflicts() {
+        let app = Application::new("root");
+        let registry = &app.shortcuts;
+        let shortcut = |target, pattern, condition| {
+            Shortcut::new_when(Rule::new(ActionType::Press, pattern), target, "cmd", condition)
+        };
+        let other_command = Rule::new(ActionType::Press, "a ctrl");
+        registry.add(shortcut("Left", "ctrl a", "focused"));
+        registry.add(Shortcut::new_when(other_command, "Left", "other", "!focused"));
+        registry.add(shortcut("Left", "ctrl b", ""));
+        registry.add(shortcut("Left", "ctrl b", "editing"));
+        registry.add(shortcut("Right", "ctrl b", "a & !a"));
+        let is_tested = |conflict: &Conflict| conflict.first.target == "Left";
+        assert!(!registry.shortcut_conflicts().iter().any(is_tested));
+
+        // The conditions of different views are checked against different instances.
+        let conflicting = shortcut("Right", "a control", "!focused");
+        registry.add(conflicting.clone());
+        let conflicts = registry.shortcut_conflicts();
+        let conflicts = conflicts.iter().filter(|conflict| is_tested(conflict)).collect_vec();
+        assert_eq!(conflicts.len(), 2);
+        assert!(conflicts.iter().all(|conflict| conflict.second == conflicting));
+
+        // The shortcuts of the registries bound to view instances are checked as well.
+        let network = frp::Network::new("test");
+        let view = crate::display::object::Instance::new();
+        let scene = &app.display.default_scene;
+        let bound = registry.instance_bound_child_in_network(network.id(), &view, scene, &network);
+        bound.add(shortcut("Right", "ctrl b", "focused"));
+        let conflicts = registry.shortcut_conflicts();
+        let conflicts = conflicts.iter().filter(|conflict| is_tested(conflict)).collect_vec();
+        assert_eq!(conflicts.len(), 4);
+        drop(bound);
+        drop(network);
+        assert_eq!(registry.shortcut_conflicts().iter().filter(|c| is_tested(c)).count(), 2);
     }
 }
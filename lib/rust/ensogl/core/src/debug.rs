@@ -5,6 +5,7 @@
 // === Export ===
 // ==============
 
+pub mod frp_inspector;
 pub mod monitor;
 pub mod stats;
 
@@ -0,0 +1,374 @@
+//! The FRP network inspector, counting the events emitted by every node of an FRP network and
+//! remembering the last emitted values. It helps to track down the event storms: the nodes emitting
+//! far more events than expected.
+//!
+//! Besides the main network, the inspector can follow the networks created later, like the networks
+//! of the components created when entering a function, see [`FrpInspector::inspect`].
+//!
+//! The inspector is disabled by default. When enabled, the [`FrpInspector::snapshot`] lists the
+//! nodes of the networks sorted by the number of the emitted events, and the optional DOM overlay
+//! displays the busiest nodes, refreshed periodically. The events are recorded only in debug
+//! builds, see the [`frp::recorder`] docs.
+
+use crate::prelude::*;
+use crate::system::web::traits::*;
+
+use crate::animation;
+use crate::system::web;
+
+use enso_frp as frp;
+use frp::recorder::NodeEvents;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The number of the busiest nodes displayed in the overlay.
+const OVERLAY_NODE_COUNT: usize = 30;
+/// The time between the refreshes of the overlay.
+const OVERLAY_REFRESH_INTERVAL_MS: f32 = 500.0;
+/// The debug representations of the values longer than this are truncated in the overlay.
+const MAX_VALUE_LENGTH: usize = 80;
+const PADDING: f64 = 8.0;
+
+
+
+// ================
+// === Snapshot ===
+// ================
+
+/// The events emitted by the nodes of the inspected network, the busiest nodes first.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    /// The label of the inspected network.
+    pub network: String,
+    #[allow(missing_docs)]
+    pub nodes:   Vec<(frp::Id, NodeEvents)>,
+}
+
+impl Snapshot {
+    fn new(network: String, mut nodes: Vec<(frp::Id, NodeEvents)>) -> Self {
+        nodes.sort_by(|(_, a), (_, b)| b.count.cmp(&a.count).then(a.label.cmp(b.label)));
+        Self { network, nodes }
+    }
+
+    /// The total number of the events emitted by the nodes.
+    pub fn total_count(&self) -> usize {
+        self.nodes.iter().map(|(_, events)| events.count).sum()
+    }
+
+    /// The records of the nodes with the given label.
+    pub fn with_label<'a>(&'a self, label: &'a str) -> impl Iterator<Item = &'a NodeEvents> {
+        self.nodes.iter().map(|(_, events)| events).filter(move |events| events.label == label)
+    }
+}
+
+impl Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "FRP network \"{}\": {} events", self.network, self.total_count())?;
+        let emitting = self.nodes.iter().filter(|(_, events)| events.count > 0);
+        for (_, events) in emitting.take(OVERLAY_NODE_COUNT) {
+            let value = events.last_value.as_deref().unwrap_or_default();
+            let value = match value.char_indices().nth(MAX_VALUE_LENGTH) {
+                Some((end, _)) => format!("{}…", &value[..end]),
+                None => value.to_owned(),
+            };
+            writeln!(f, "{:>8}  {}  {value}", events.count, events.label)?;
+        }
+        Ok(())
+    }
+}
+
+
+
+// ===============
+// === Overlay ===
+// ===============
+
+/// The DOM element displaying the snapshot in the bottom-left corner of the screen.
+#[derive(Debug)]
+struct Overlay {
+    root: web::HtmlDivElement,
+}
+
+impl Overlay {
+    fn new() -> Self {
+        let root = web::document.create_div_or_panic();
+        root.set_class_name("frp-inspector");
+        root.set_style_or_warn("position", "absolute");
+        root.set_style_or_warn("z-index", "100");
+        root.set_style_or_warn("left", format!("{PADDING}px"));
+        root.set_style_or_warn("bottom", format!("{PADDING}px"));
+        root.set_style_or_warn("padding", format!("{PADDING}px"));
+        root.set_style_or_warn("pointer-events", "none");
+        root.set_style_or_warn("white-space", "pre");
+        root.set_style_or_warn("font-family", "monospace");
+        root.set_style_or_warn("font-size", "11px");
+        root.set_style_or_warn("color", "#ffffff");
+        root.set_style_or_warn("background", "#000000b0");
+        root.set_style_or_warn("border-radius", "6px");
+        web::document.body_or_panic().append_or_warn(&root);
+        Self { root }
+    }
+
+    fn update(&self, snapshot: &Snapshot) {
+        self.root.set_inner_text(&snapshot.to_string());
+    }
+}
+
+impl Drop for Overlay {
+    fn drop(&mut self) {
+        self.root.remove()
+    }
+}
+
+
+
+// ====================
+// === FrpInspector ===
+// ====================
+
+/// An inspected network and its nodes being watched.
+#[derive(Debug)]
+struct InspectedNetwork {
+    id:      frp::NetworkId,
+    network: frp::WeakNetwork,
+    /// The nodes being watched, empty if the inspector is disabled.
+    watched: Vec<frp::Id>,
+}
+
+impl InspectedNetwork {
+    fn new(network: &frp::Network) -> Self {
+        Self { id: network.id(), network: network.downgrade(), watched: default() }
+    }
+
+    /// Watch all the nodes currently registered in the network, or stop watching them.
+    fn set_watched(&mut self, watched: bool) {
+        frp::recorder::unwatch(mem::take(&mut self.watched));
+        if let Some(network) = self.network.upgrade().filter(|_| watched) {
+            let nodes = network.node_labels();
+            self.watched = nodes.iter().map(|(id, _)| *id).collect();
+            frp::recorder::watch(nodes);
+        }
+    }
+}
+
+/// Internal state of the [`FrpInspector`].
+#[derive(Debug)]
+struct Model {
+    network_name: String,
+    /// The main network first, followed by the networks added with [`FrpInspector::inspect`].
+    networks:     RefCell<Vec<InspectedNetwork>>,
+    enabled:      Cell<bool>,
+    overlay:      RefCell<Option<Overlay>>,
+    last_refresh: Cell<Option<f32>>,
+}
+
+impl Model {
+    fn set_enabled(&self, enabled: bool) {
+        self.enabled.set(enabled);
+        self.forget_dropped_networks();
+        for network in &mut *self.networks.borrow_mut() {
+            network.set_watched(enabled);
+        }
+    }
+
+    fn inspect(&self, network: &frp::Network) {
+        self.forget_dropped_networks();
+        let mut networks = self.networks.borrow_mut();
+        let index = networks.iter().position(|inspected| inspected.id == network.id());
+        let index = index.unwrap_or_else(|| {
+            networks.push(InspectedNetwork::new(network));
+            networks.len() - 1
+        });
+        // The already inspected network is watched again, as it may have new nodes registered.
+        if self.enabled.get() {
+            networks[index].set_watched(true);
+        }
+    }
+
+    /// Stop watching the nodes of the dropped networks. Their ids may be reused by the nodes
+    /// created later.
+    fn forget_dropped_networks(&self) {
+        self.networks.borrow_mut().retain_mut(|inspected| {
+            let is_alive = inspected.network.upgrade().is_some();
+            if !is_alive {
+                inspected.set_watched(false);
+            }
+            is_alive
+        });
+    }
+
+    fn watched(&self) -> Vec<frp::Id> {
+        let networks = self.networks.borrow();
+        networks.iter().flat_map(|inspected| inspected.watched.iter().copied()).collect()
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        self.forget_dropped_networks();
+        let records = frp::recorder::records(self.watched());
+        Snapshot::new(self.network_name.clone(), records)
+    }
+
+    fn refresh_overlay(&self, time_ms: f32) {
+        if let Some(overlay) = &*self.overlay.borrow() {
+            let last_refresh = self.last_refresh.get();
+            let is_due = last_refresh.map_or(true, |t| time_ms - t >= OVERLAY_REFRESH_INTERVAL_MS);
+            if is_due {
+                self.last_refresh.set(Some(time_ms));
+                overlay.update(&self.snapshot());
+            }
+        }
+    }
+}
+
+impl Drop for Model {
+    fn drop(&mut self) {
+        frp::recorder::unwatch(self.watched());
+    }
+}
+
+/// The inspector of an FRP network. See the module docs to learn more.
+#[derive(Clone, CloneRef, Debug)]
+pub struct FrpInspector {
+    model:   Rc<Model>,
+    network: frp::Network,
+}
+
+impl FrpInspector {
+    /// Constructor. The inspector does not keep the inspected network alive.
+    pub fn new(inspected: &frp::Network) -> Self {
+        let network_name = inspected.label().to_owned();
+        let model = Rc::new(Model {
+            network_name,
+            networks: RefCell::new(vec![InspectedNetwork::new(inspected)]),
+            enabled: default(),
+            overlay: default(),
+            last_refresh: default(),
+        });
+        let network = frp::Network::new("FrpInspector");
+        let on_frame_start = animation::on_frame_start();
+        frp::extend! { network
+            eval on_frame_start ((time) model.refresh_overlay(time.unchecked_raw()));
+        }
+        Self { model, network }
+    }
+
+    /// Start or stop recording the events. Stopping forgets the recorded events and hides the
+    /// overlay.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.model.set_enabled(enabled);
+        if !enabled {
+            self.set_overlay_visible(false);
+        }
+    }
+
+    /// Inspect also the given network, for example the network of a component created after the
+    /// inspector. If the inspector is enabled, the nodes of the network are watched immediately.
+    /// The network is forgotten once dropped.
+    pub fn inspect(&self, network: &frp::Network) {
+        self.model.inspect(network);
+    }
+
+    /// Check whether the events are being recorded.
+    pub fn is_enabled(&self) -> bool {
+        self.model.enabled.get()
+    }
+
+    /// The events emitted by the nodes of the network since the inspector was enabled or reset.
+    pub fn snapshot(&self) -> Snapshot {
+        self.model.snapshot()
+    }
+
+    /// Forget the recorded events, without disabling the inspector.
+    pub fn reset(&self) {
+        frp::recorder::clear(self.model.watched());
+    }
+
+    /// Show or hide the overlay. The overlay can be shown only when the inspector is enabled.
+    pub fn set_overlay_visible(&self, visible: bool) {
+        let visible = visible && self.is_enabled();
+        let mut overlay = self.model.overlay.borrow_mut();
+        if visible && overlay.is_none() {
+            *overlay = Some(Overlay::new());
+            self.model.last_refresh.set(None);
+        } else if !visible {
+            *overlay = None;
+        }
+    }
+
+    /// Check whether the overlay is displayed.
+    pub fn is_overlay_visible(&self) -> bool {
+        self.model.overlay.borrow().is_some()
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frp::HasId;
+
+    #[test]
+    fn inspecting_network() {
+        frp::new_network! { network
+            def source = source::<usize>();
+            def doubled = source.map(|value| value * 2);
+            def _noop = source.filter(|_| false);
+        }
+        let inspector = FrpInspector::new(&network);
+        source.emit(1);
+        assert!(inspector.snapshot().nodes.is_empty());
+
+        inspector.set_enabled(true);
+        source.emit(2);
+        source.emit(3);
+        let snapshot = inspector.snapshot();
+        assert_eq!(snapshot.nodes.len(), network.node_labels().len());
+        if cfg!(debug_assertions) {
+            assert_eq!(snapshot.total_count(), 4);
+            let busiest = snapshot.nodes.iter().take(2).map(|(id, _)| *id).collect_vec();
+            assert!(busiest.contains(&doubled.id()));
+            assert_eq!(snapshot.nodes[2].1.count, 0);
+        }
+
+        inspector.reset();
+        assert_eq!(inspector.snapshot().total_count(), 0);
+        inspector.set_enabled(false);
+        assert!(!inspector.is_enabled());
+        assert!(inspector.snapshot().nodes.is_empty());
+    }
+
+    #[test]
+    fn inspecting_networks_created_later() {
+        frp::new_network! { network
+            def _source = source::<usize>();
+        }
+        let inspector = FrpInspector::new(&network);
+        inspector.set_enabled(true);
+        let main_node_count = network.node_labels().len();
+
+        frp::new_network! { component_network
+            def component_source = source::<usize>();
+        }
+        inspector.inspect(&component_network);
+        inspector.inspect(&component_network);
+        component_source.emit(1);
+        let snapshot = inspector.snapshot();
+        assert_eq!(snapshot.nodes.len(), main_node_count + 1);
+        if cfg!(debug_assertions) {
+            assert_eq!(snapshot.nodes[0].0, component_source.id());
+            assert_eq!(snapshot.total_count(), 1);
+        }
+
+        drop(component_network);
+        assert_eq!(inspector.snapshot().nodes.len(), main_node_count);
+    }
+}
@@ -114,6 +114,7 @@ impl StatsInternal {
     /// Clean the per-frame statistics, such as the per-frame number of draw calls. This function
     /// should be called before any rendering calls were made.
     fn reset_per_frame_statistics(&mut self) {
+        self.stats_data.prev_draw_call_count = self.stats_data.draw_calls.len();
         self.stats_data.draw_calls.clear();
         self.stats_data.shader_compile_count = 0;
         self.stats_data.data_upload_count = 0;
@@ -205,6 +206,9 @@ gen_stats! {
     wasm_memory_usage    : u32,
     gpu_memory_usage     : u32,
     draw_calls           : Vec<&'static str>,
+    // The number of draw calls of the previous frame, available after the per-frame statistics
+    // are reset.
+    prev_draw_call_count : usize,
     buffer_count         : usize,
     data_upload_count    : usize,
     data_upload_size     : u32,
@@ -234,6 +234,19 @@ impl NavigatorModel {
     pub fn emit_pan_event(&self, event: PanEvent) {
         self.events.emit_pan_event(event);
     }
+
+    /// Smoothly move the camera to the given position. The `z` coordinate is the distance of the
+    /// camera from the scene, thus it determines the zoom.
+    pub fn animate_to(&self, position: Vector3) {
+        self.simulator.set_target_value(position);
+    }
+
+    /// Move the camera to the given position immediately, stopping any ongoing camera movement.
+    pub fn jump_to(&self, position: Vector3) {
+        self.simulator.set_value(position);
+        self.simulator.set_target_value(position);
+        self.simulator.set_velocity(default());
+    }
 }
 
 
@@ -9,6 +9,7 @@ pub mod cache_shapes;
 pub mod pixel_read;
 pub mod screen;
 pub mod symbols;
+pub mod thumbnail;
 
 
 
@@ -19,5 +20,6 @@ pub mod types {
     pub use pixel_read::*;
     pub use screen::*;
     pub use symbols::*;
+    pub use thumbnail::*;
 }
 pub use types::*;
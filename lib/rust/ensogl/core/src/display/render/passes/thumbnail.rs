@@ -0,0 +1,240 @@
+//! Pass rendering layers into offscreen framebuffers of a reduced resolution, used to create the
+//! thumbnails of the scene (e.g. the previews of the projects).
+
+use crate::prelude::*;
+use crate::system::gpu::*;
+
+use crate::display::camera::Camera2d;
+use crate::display::render::pass;
+use crate::display::scene;
+use crate::display::scene::layer;
+use crate::display::scene::Layer;
+use crate::display::scene::Scene;
+use crate::display::scene::UpdateStatus;
+use crate::display::world;
+use crate::system::gpu::context::ContextLost;
+
+use futures::channel::oneshot;
+use std::future::Future;
+use wasm_bindgen::Clamped;
+use web_sys::ImageData;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+const BYTES_PER_PIXEL: usize = 4;
+
+
+
+// ==================
+// === Thumbnails ===
+// ==================
+
+/// A request for a thumbnail, handled during the next run of the [`ThumbnailPass`].
+#[derive(Debug)]
+struct Request {
+    layers: Vec<Layer>,
+    camera: Camera2d,
+    size:   Vector2<i32>,
+    sender: oneshot::Sender<Option<ImageData>>,
+}
+
+/// The queue of the thumbnail requests, shared between the scene and the [`ThumbnailPass`].
+#[derive(Clone, CloneRef, Debug, Default)]
+pub struct Thumbnails {
+    requests: Rc<RefCell<Vec<Request>>>,
+}
+
+impl Thumbnails {
+    /// Render the layers (in the given order) and their sublayers, as seen by the camera, into an
+    /// image of the given size in device pixels. The camera is used for all the sublayers, and its
+    /// screen should have the aspect ratio of the image.
+    ///
+    /// The image is rendered during the next frame. The returned future resolves to [`None`] if the
+    /// image could not be rendered, e.g. because the GPU context was lost.
+    pub fn render(
+        &self,
+        layers: &[Layer],
+        camera: &Camera2d,
+        size: Vector2<i32>,
+    ) -> impl Future<Output = Option<ImageData>> {
+        let (sender, receiver) = oneshot::channel();
+        let layers = layers.iter().map(|layer| layer.clone_ref()).collect();
+        let camera = camera.clone_ref();
+        self.requests.borrow_mut().push(Request { layers, camera, size, sender });
+        async move { receiver.await.ok().flatten() }
+    }
+
+    fn take_requests(&self) -> Vec<Request> {
+        mem::take(&mut *self.requests.borrow_mut())
+    }
+}
+
+impl scene::Extension for Thumbnails {
+    fn init(_scene: &Scene) -> Self {
+        default()
+    }
+}
+
+
+
+// ========================
+// === ThumbnailPassDef ===
+// ========================
+
+/// Definition of the pass rendering the thumbnails. See [`ThumbnailPass`] for pass implementation.
+#[derive(Clone, Debug)]
+pub struct ThumbnailPassDef {
+    thumbnails: Thumbnails,
+}
+
+impl ThumbnailPassDef {
+    /// Constructor. The pass handles the requests made through the given [`Thumbnails`].
+    pub fn new(thumbnails: &Thumbnails) -> Self {
+        Self { thumbnails: thumbnails.clone_ref() }
+    }
+}
+
+impl pass::Definition for ThumbnailPassDef {
+    fn instantiate(
+        &self,
+        instance: pass::InstanceInfo,
+    ) -> Result<Box<dyn pass::Instance>, ContextLost> {
+        Ok(Box::new(ThumbnailPass { thumbnails: self.thumbnails.clone_ref(), instance }))
+    }
+}
+
+
+
+// =====================
+// === ThumbnailPass ===
+// =====================
+
+/// Pass rendering the requested thumbnails. Every thumbnail is rendered into its own framebuffer,
+/// created only for the time of rendering, so the pass costs nothing when no thumbnail is
+/// requested. The shapes are rendered with the same symbols as on the screen, but without the
+/// masks and the scissor boxes of the layers.
+#[derive(Debug)]
+pub struct ThumbnailPass {
+    thumbnails: Thumbnails,
+    instance:   pass::InstanceInfo,
+}
+
+impl pass::Instance for ThumbnailPass {
+    fn run(&mut self, _update_status: UpdateStatus) {
+        for request in self.thumbnails.take_requests() {
+            let image = self.render(&request).ok().flatten();
+            // The requester could drop the future; there is nobody to inform then.
+            let _ = request.sender.send(image);
+        }
+    }
+
+    fn resize(&mut self, width: i32, height: i32, pixel_ratio: f32) {
+        self.instance.width = width;
+        self.instance.height = height;
+        self.instance.pixel_ratio = pixel_ratio;
+    }
+}
+
+impl ThumbnailPass {
+    fn render(&self, request: &Request) -> Result<Option<ImageData>, ContextLost> {
+        let context = &self.instance.context;
+        let width = request.size.x;
+        let height = request.size.y;
+        if width <= 0 || height <= 0 {
+            return Ok(None);
+        }
+        let output = pass::OutputDefinition::new_rgba("thumbnail");
+        let texture = self.instance.new_texture(&output, width, height);
+        let framebuffer = self.instance.new_framebuffer(&[&texture])?;
+        request.camera.update(&world::scene());
+        let mut pixels = vec![0; width as usize * height as usize * BYTES_PER_PIXEL];
+        let read = framebuffer.with_bound(|| {
+            self.instance.with_viewport(width, height, || {
+                let zero = [0.0, 0.0, 0.0, 0.0];
+                context.clear_bufferfv_with_f32_array(*Context::COLOR, 0, &zero);
+                for layer in &request.layers {
+                    self.render_layer(layer, &request.camera);
+                }
+                let format = *Context::RGBA;
+                let typ = *Context::UNSIGNED_BYTE;
+                context.read_pixels_with_opt_u8_array(
+                    0,
+                    0,
+                    width,
+                    height,
+                    format,
+                    typ,
+                    Some(&mut pixels),
+                )
+            })
+        });
+        if let Err(error) = read {
+            warn!("Failed to read the thumbnail pixels: {error:?}.");
+            return Ok(None);
+        }
+        let pixels = to_image_data_layout(&pixels, width as usize);
+        Ok(ImageData::new_with_u8_clamped_array_and_sh(
+            Clamped(&pixels),
+            width as u32,
+            height as u32,
+        )
+        .ok())
+    }
+
+    fn render_layer(&self, layer: &Layer, camera: &Camera2d) {
+        if layer.has_symbols() {
+            world::with_context(|t| {
+                t.set_camera(camera);
+                layer.blend_mode().apply_to_context(&self.instance.context);
+                t.render_symbols(&layer.symbols());
+            });
+        }
+        layer.for_each_sublayer(|sublayer| {
+            if sublayer.flags.contains(layer::LayerFlags::MAIN_PASS_VISIBLE) {
+                self.render_layer(&sublayer, camera);
+            }
+        });
+    }
+}
+
+/// Convert the pixels read from the framebuffer to the layout of the [`ImageData`]: the rows go
+/// from top to bottom (the framebuffer rows go from bottom to top), and the colors are not
+/// premultiplied by the alpha.
+fn to_image_data_layout(pixels: &[u8], width: usize) -> Vec<u8> {
+    let row_length = width * BYTES_PER_PIXEL;
+    let rows = pixels.chunks_exact(row_length).rev();
+    let pixels = rows.flat_map(|row| row.chunks_exact(BYTES_PER_PIXEL));
+    let unpremultiplied = pixels.flat_map(|pixel| {
+        let alpha = pixel[3];
+        let unpremultiply = |channel: u8| match alpha {
+            0 => 0,
+            _ => (channel as u32 * 255 / alpha as u32).min(255) as u8,
+        };
+        [unpremultiply(pixel[0]), unpremultiply(pixel[1]), unpremultiply(pixel[2]), alpha]
+    });
+    unpremultiplied.collect()
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converting_to_image_data_layout() {
+        let bottom_row = [255, 0, 0, 255, 0, 0, 0, 0];
+        let top_row = [0, 64, 0, 128, 10, 20, 30, 255];
+        let pixels = bottom_row.iter().chain(top_row.iter()).copied().collect_vec();
+        let expected = vec![0, 127, 0, 128, 10, 20, 30, 255, 255, 0, 0, 255, 0, 0, 0, 0];
+        assert_eq!(to_image_data_layout(&pixels, 2), expected);
+    }
+}
@@ -33,11 +33,16 @@ const FALLBACK_COLOR: color::Rgba = color::Rgba::new(1.0, 0.0, 0.0, 0.5);
 
 /// FRP-based style watch utility. Whenever a style sheet value is accessed, the value reference is
 /// being remembered and tracked. Whenever it changes, the FRP event is emitted.
+///
+/// A watch created with [`StyleWatchFrp::new_scoped`] reads the values overridden in the
+/// [`StyleScope`], falling back to the global ones.
 #[derive(Clone, CloneRef, Derivative)]
 #[derivative(Debug)]
 pub struct StyleWatchFrp {
     network:  frp::Network,
     sheet:    style::Sheet,
+    /// The prefix of all the accessed paths. Empty for the unscoped watch.
+    scope:    Rc<Path>,
     vars:     Rc<RefCell<Vec<style::Var>>>,
     handles:  Rc<RefCell<Vec<callback::Handle>>>,
     #[derivative(Debug = "ignore")]
@@ -50,10 +55,16 @@ impl StyleWatchFrp {
     pub fn new(sheet: &style::Sheet) -> Self {
         let network = frp::Network::new("style_watch");
         let sheet = sheet.clone_ref();
+        let scope = Rc::new(Path::empty());
         let vars = default();
         let handles = default();
         let callback = Rc::new(RefCell::new(Box::new(|| {}) as Box<dyn Fn()>));
-        Self { network, sheet, vars, handles, callback }
+        Self { network, sheet, scope, vars, handles, callback }
+    }
+
+    /// Constructor of a watch reading the values overridden in the given scope.
+    pub fn new_scoped(scope: &StyleScope) -> Self {
+        Self { scope: Rc::new(scope.path().clone()), ..Self::new(&scope.model.sheet) }
     }
 
     fn get_internal(
@@ -64,7 +75,7 @@ impl StyleWatchFrp {
         frp::extend! { network
             source <- source::<Option<style::Data>>();
         }
-        let path = path.into();
+        let path = path.into().nested_in(&self.scope);
         let var = self.sheet.var(path);
         let current = var.value();
         let handle = var.on_change(f!((data:&Option<style::Data>) source.emit(data.clone())));
@@ -130,6 +141,91 @@ impl StyleWatchFrp {
     }
 }
 
+
+
+// ==================
+// === StyleScope ===
+// ==================
+
+/// An override scope of the style sheet. The values set in the scope are visible only to the
+/// watches created with [`StyleWatchFrp::new_scoped`], e.g. to the views of a single graph rendered
+/// with a different palette. The other values are read from the enclosing scope, or from the
+/// global style sheet.
+///
+/// The scope is implemented with the cascading queries of the style sheet: a watch in the scope
+/// `diff` reads `theme.node.color` from `diff.theme.node.color` if it is set, and from
+/// `theme.node.color` otherwise. The path of a nested scope is prepended to the path of its parent,
+/// so a watch in the scope `nested` nested in `diff` queries `nested.diff.theme.node.color`, which
+/// falls back to `diff.theme.node.color` and then to `theme.node.color`. The overrides are removed
+/// when the last reference to the scope is dropped.
+#[derive(Clone, CloneRef, Debug)]
+pub struct StyleScope {
+    model: Rc<StyleScopeModel>,
+}
+
+#[derive(Debug)]
+struct StyleScopeModel {
+    sheet:     style::Sheet,
+    path:      Path,
+    overrides: RefCell<Vec<Path>>,
+}
+
+impl StyleScope {
+    /// Constructor of a top-level scope of the given name.
+    pub fn new(sheet: &style::Sheet, name: impl Into<String>) -> Self {
+        Self::new_with_path(sheet, Path::empty().into_sub(name))
+    }
+
+    fn new_with_path(sheet: &style::Sheet, path: Path) -> Self {
+        let sheet = sheet.clone_ref();
+        let overrides = default();
+        Self { model: Rc::new(StyleScopeModel { sheet, path, overrides }) }
+    }
+
+    /// Create a scope nested in this one. Its watches read the values overridden in the nested
+    /// scope first, then the ones overridden in this scope.
+    pub fn sub_scope(&self, name: impl Into<String>) -> Self {
+        let path = self.model.path.nested_in(&Path::empty().into_sub(name));
+        Self::new_with_path(&self.model.sheet, path)
+    }
+
+    /// The prefix of the style paths read by the watches in the scope. It may be used to query the
+    /// scoped values by the components reading the style sheet with a path prefix.
+    pub fn path(&self) -> &Path {
+        &self.model.path
+    }
+
+    /// Override the style value within the scope.
+    pub fn set(&self, path: impl Into<Path>, value: impl Into<style::Value>) {
+        let path = path.into().nested_in(&self.model.path);
+        let mut overrides = self.model.overrides.borrow_mut();
+        if !overrides.contains(&path) {
+            overrides.push(path.clone());
+        }
+        self.model.sheet.set(path, value);
+    }
+
+    /// Remove the override of the style value, so the value of the enclosing scope is used.
+    pub fn unset(&self, path: impl Into<Path>) {
+        let path = path.into().nested_in(&self.model.path);
+        self.model.overrides.borrow_mut().retain(|overridden| overridden != &path);
+        self.model.sheet.unset(path);
+    }
+}
+
+impl Drop for StyleScopeModel {
+    fn drop(&mut self) {
+        let overrides = mem::take(&mut *self.overrides.borrow_mut());
+        self.sheet.apply_changes(overrides.into_iter().map(|path| style::Change::new(path, None)));
+    }
+}
+
+
+
+// ===================
+// === ThemeAccess ===
+// ===================
+
 /// Defines a way for a value of given type to be accessed from the style sheet.
 pub trait ThemeAccess: Debug + Clone + Default + 'static {
     /// Convert raw style data to a value of given type. Uses `path_str` to report a warning in case
@@ -324,3 +420,39 @@ impl StyleWatch {
         self.get(path).color()
     }
 }
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overriding_styles_in_scope() {
+        let sheet = style::Sheet::new();
+        sheet.set("panel.size", style::data(1.0));
+        let scope = StyleScope::new(&sheet, "diff");
+        let nested = scope.sub_scope("nested");
+        let watches = [
+            StyleWatchFrp::new(&sheet),
+            StyleWatchFrp::new_scoped(&scope),
+            StyleWatchFrp::new_scoped(&nested),
+        ];
+        let sizes = watches.iter().map(|watch| watch.get_number("panel.size")).collect_vec();
+        let values = || sizes.iter().map(|size| size.value()).collect_vec();
+        assert_eq!(values(), [1.0, 1.0, 1.0]);
+
+        scope.set("panel.size", style::data(2.0));
+        assert_eq!(values(), [1.0, 2.0, 2.0]);
+        nested.set("panel.size", style::data(3.0));
+        assert_eq!(values(), [1.0, 2.0, 3.0]);
+        scope.unset("panel.size");
+        assert_eq!(values(), [1.0, 1.0, 3.0]);
+        drop(nested);
+        assert_eq!(values(), [1.0, 1.0, 1.0]);
+    }
+}
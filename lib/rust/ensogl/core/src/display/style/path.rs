@@ -90,6 +90,13 @@ impl Path {
         self.rev_segments.reverse();
         self
     }
+
+    /// Return a new path with the segments of `prefix` prepended, e.g. `button.size` nested in
+    /// `panel` is `panel.button.size`.
+    pub fn nested_in(&self, prefix: &Path) -> Self {
+        let rev_segments = self.rev_segments.iter().chain(&prefix.rev_segments).cloned().collect();
+        Self { rev_segments }
+    }
 }
 
 impl AsRef<Path> for Path {
@@ -273,8 +273,13 @@ impl Manager {
     fn register_internal(&self, name: String, theme: Theme) {
         let dirty = self.current_dirty.clone_ref();
         let handle = theme.on_mut(move || dirty.set());
+        let is_enabled = self.data.borrow().enabled.contains(&name);
         self.data.borrow_mut().register(&name, theme);
         self.handles.borrow_mut().insert(name, handle);
+        // Re-registering an enabled theme replaces its values, like modifying it would.
+        if is_enabled {
+            self.current_dirty.set();
+        }
     }
 
     /// Sets a new set of enabled themes.
@@ -337,7 +342,8 @@ impl AsRef<Manager> for Manager {
 // =================
 
 /// A struct containing a set of predefined styles that can be constructed from theme. This trait
-/// can be derived using `#[derive(FromTheme)]`.
+/// can be derived using `#[derive(FromTheme)]`. The derived style is re-created whenever any of its
+/// values changes, e.g. when another theme is enabled.
 ///
 /// ```ignore
 /// #[derive(Clone, Debug, Default, FromTheme)]
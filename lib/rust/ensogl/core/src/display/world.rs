@@ -17,6 +17,8 @@ use crate::display;
 use crate::display::garbage;
 use crate::display::render::cache_shapes::CacheShapesPassDef;
 use crate::display::render::passes::SymbolsRenderPassDef;
+use crate::display::render::passes::ThumbnailPassDef;
+use crate::display::render::passes::Thumbnails;
 use crate::display::scene::DomPath;
 use crate::display::scene::Scene;
 use crate::display::scene::UpdateStatus;
@@ -549,11 +551,13 @@ impl WorldData {
     }
 
     fn init_composer(&self) {
+        let thumbnails = self.default_scene.extension::<Thumbnails>();
         self.default_scene.renderer.set_pipeline(Pipeline::new(Rc::new([
             Box::new(SymbolsRenderPassDef::new(&self.default_scene.layers)),
             Box::new(ScreenRenderPass::new()),
             self.init_pixel_read_pass(),
             Box::new(CacheShapesPassDef::new()),
+            Box::new(ThumbnailPassDef::new(&thumbnails)),
         ])));
     }
 
@@ -177,6 +177,7 @@ pub mod microtasks;
 pub mod network;
 pub mod node;
 pub mod nodes;
+pub mod recorder;
 pub mod stream;
 
 pub use network::*;
@@ -89,6 +89,11 @@ impl Network {
         Self { data }
     }
 
+    /// Label of this network.
+    pub fn label(&self) -> &str {
+        &self.data.label
+    }
+
     /// Get the weak version.
     pub fn downgrade(&self) -> WeakNetwork {
         WeakNetwork { data: Rc::downgrade(&self.data) }
@@ -153,6 +158,11 @@ impl Network {
         self.data.bridges.borrow_mut().push(sub_network.clone_ref())
     }
 
+    /// The ids and labels of the nodes registered in this network.
+    pub fn node_labels(&self) -> Vec<(Id, Label)> {
+        self.data.nodes.borrow().iter().map(|node| (node.id(), node.label())).collect()
+    }
+
     /// Draw the network using GraphViz.
     pub fn draw(&self) {
         let mut viz = debug::Graphviz::default();
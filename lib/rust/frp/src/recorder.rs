@@ -0,0 +1,142 @@
+//! Recording of the events emitted by the watched FRP nodes, used by the debugging tools looking
+//! for the nodes emitting far more events than expected.
+//!
+//! The events are recorded only in debug builds. In release builds, the nodes can be watched, but
+//! their records stay empty, so emitting events is not slowed down.
+
+use crate::prelude::*;
+
+use crate::node::*;
+
+
+
+// ==================
+// === NodeEvents ===
+// ==================
+
+/// The events emitted by a watched node since it started to be watched.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NodeEvents {
+    #[allow(missing_docs)]
+    pub label:      Label,
+    /// The number of the emitted events.
+    pub count:      usize,
+    /// The debug representation of the last emitted value, or [`None`] if no event was emitted.
+    pub last_value: Option<String>,
+}
+
+
+
+// ================
+// === Recorder ===
+// ================
+
+thread_local! {
+    static RECORDS: RefCell<HashMap<Id, NodeEvents>> = default();
+    /// Whether any node is watched. Checked before accessing the records, so emitting the events of
+    /// the not watched nodes stays cheap.
+    static IS_ACTIVE: Cell<bool> = default();
+}
+
+/// Start recording the events of the nodes. The records of the nodes already being watched are
+/// kept.
+pub fn watch(nodes: impl IntoIterator<Item = (Id, Label)>) {
+    RECORDS.with(|records| {
+        let mut records = records.borrow_mut();
+        for (id, label) in nodes {
+            records.entry(id).or_insert_with(|| NodeEvents { label, ..default() });
+        }
+        IS_ACTIVE.with(|is_active| is_active.set(!records.is_empty()));
+    })
+}
+
+/// Stop recording the events of the nodes and forget their records.
+pub fn unwatch(nodes: impl IntoIterator<Item = Id>) {
+    RECORDS.with(|records| {
+        let mut records = records.borrow_mut();
+        for id in nodes {
+            records.remove(&id);
+        }
+        IS_ACTIVE.with(|is_active| is_active.set(!records.is_empty()));
+    })
+}
+
+/// The records of the nodes. The nodes not being watched are skipped.
+pub fn records(nodes: impl IntoIterator<Item = Id>) -> Vec<(Id, NodeEvents)> {
+    RECORDS.with(|records| {
+        let records = records.borrow();
+        nodes.into_iter().filter_map(|id| Some((id, records.get(&id)?.clone()))).collect()
+    })
+}
+
+/// Clear the records of the nodes, without unwatching them.
+pub fn clear(nodes: impl IntoIterator<Item = Id>) {
+    RECORDS.with(|records| {
+        let mut records = records.borrow_mut();
+        for id in nodes {
+            if let Some(record) = records.get_mut(&id) {
+                *record = NodeEvents { label: record.label, ..default() };
+            }
+        }
+    })
+}
+
+/// Record the event emitted by the node, if the node is watched.
+#[inline(always)]
+pub(crate) fn record(id: Id, value: &dyn Debug) {
+    if cfg!(debug_assertions) && IS_ACTIVE.with(|is_active| is_active.get()) {
+        record_watched(id, value)
+    }
+}
+
+/// The non-inlined part of [`record`].
+#[inline(never)]
+fn record_watched(id: Id, value: &dyn Debug) {
+    RECORDS.with(|records| {
+        // The records may be borrowed if the debug representation of the value emits events.
+        if let Ok(mut records) = records.try_borrow_mut() {
+            if let Some(record) = records.get_mut(&id) {
+                record.count += 1;
+                record.last_value = Some(format!("{value:?}"));
+            }
+        }
+    })
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as frp;
+
+    #[test]
+    fn recording_watched_nodes() {
+        frp::new_network! { network
+            def source = source::<usize>();
+            def doubled = source.map(|value| value * 2);
+        }
+        let watched = network.node_labels().into_iter().filter(|(id, _)| *id == doubled.id());
+        watch(watched);
+        source.emit(1);
+        source.emit(2);
+
+        let recorded = records([source.id(), doubled.id()]);
+        assert_eq!(recorded.len(), 1);
+        let (id, events) = &recorded[0];
+        assert_eq!(*id, doubled.id());
+        if cfg!(debug_assertions) {
+            assert_eq!(events.count, 2);
+            assert_eq!(events.last_value.as_deref(), Some("4"));
+        }
+
+        clear([doubled.id()]);
+        assert_eq!(records([doubled.id()])[0].1.count, 0);
+        unwatch([doubled.id()]);
+        assert!(records([doubled.id()]).is_empty());
+    }
+}
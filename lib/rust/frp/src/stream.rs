@@ -5,6 +5,7 @@ use crate::node::*;
 use crate::prelude::*;
 
 use crate::data::watch;
+use crate::recorder;
 
 
 
@@ -274,6 +275,12 @@ impl<Out: Default> NodeData<Out> {
     fn use_caching(&self) -> bool {
         !self.watch_counter.is_zero()
     }
+
+    /// The id of the node, the same as the id of the [`Stream`] referring to it.
+    fn id(&self) -> Id {
+        let raw = self as *const Self as *const () as usize;
+        raw.into()
+    }
 }
 
 impl<Out: Data> HasOutput for NodeData<Out> {
@@ -290,6 +297,7 @@ impl<Out: Data> EventEmitter for NodeData<Out> {
             warn!("{}", backtrace())
         } else {
             self.ongoing_evaluations.set(self.ongoing_evaluations.get() + 1);
+            recorder::record(self.id(), value);
             if self.use_caching() {
                 *self.value_cache.borrow_mut() = value.clone();
             }
@@ -0,0 +1,84 @@
+//! Tool that summarizes the startup performance of the IDE from profiling data.
+//!
+//! # Usage
+//!
+//! The tool reads a JSON-formatted event log from stdin, and writes a JSON report to stdout. The
+//! log should be captured by running the `new_project` profiling workflow, whose highlighted region
+//! ends when the graph of the created project is displayed.
+//!
+//! For example:
+//!
+//! ```console
+//! ~/git/enso/data $ cargo run --bin startup < profile.json
+//! ```
+
+// === Standard Linter Configuration ===
+#![deny(non_ascii_idents)]
+#![warn(unsafe_code)]
+#![allow(clippy::bool_to_int_with_if)]
+#![allow(clippy::let_and_return)]
+// === Non-Standard Linter Configuration ===
+#![deny(unconditional_recursion)]
+#![warn(missing_copy_implementations)]
+#![warn(missing_debug_implementations)]
+#![warn(missing_docs)]
+#![warn(trivial_casts)]
+#![warn(trivial_numeric_casts)]
+#![warn(unused_import_braces)]
+
+use enso_profiler_data as profiler_data;
+use profiler_data::Class;
+use profiler_data::OpaqueMetadata;
+use profiler_data::Profile;
+
+
+
+// ====================
+// === StartupTimes ===
+// ====================
+
+/// The milestones of the IDE startup, in milliseconds since the time origin of the GUI process.
+#[derive(Copy, Clone, Debug, serde::Serialize)]
+pub struct StartupTimes {
+    /// When the first profiler was started, right after the WASM module was initialized.
+    pub wasm_init_ms:          f64,
+    /// When the highlighted region of the profiling workflow ended, i.e. when the graph of the
+    /// created project was displayed.
+    pub first_graph_render_ms: f64,
+}
+
+impl StartupTimes {
+    /// Summarize the profile. Returns [`None`] if the profile has no finished highlighted region,
+    /// e.g. because it was captured by another process than the GUI.
+    pub fn new(profile: &Profile<OpaqueMetadata>) -> Option<Self> {
+        let root = profile.root_measurement_id();
+        let measurements = || profile.measurement_ids().filter(|id| *id != root);
+        let wasm_init = measurements().map(|id| profile[id].created).min()?;
+        let is_highlight = |id: &_| profile[*id].classify() == Class::Highlight;
+        let highlight = measurements().find(is_highlight)?;
+        let last_interval = profile[highlight].intervals.last()?;
+        let first_graph_render = profile[*last_interval].interval.end?;
+        Some(Self {
+            wasm_init_ms:          wasm_init.into_ms(),
+            first_graph_render_ms: first_graph_render.into_ms(),
+        })
+    }
+}
+
+
+
+// ============
+// === Main ===
+// ============
+
+fn main() {
+    use std::io::Read;
+
+    let mut log = String::new();
+    std::io::stdin().read_to_string(&mut log).unwrap();
+    let times = profiler_data::parse_multiprocess_profile::<OpaqueMetadata>(&log)
+        .map(|profile| profile.expect("Failed to parse the profile."))
+        .find_map(|profile| StartupTimes::new(&profile))
+        .expect("Incomplete profile: No process has a finished highlighted region.");
+    println!("{}", serde_json::to_string_pretty(&times).unwrap());
+}
@@ -256,6 +256,12 @@ impl EventLog {
         self.log_event(Event::Metadata { timestamp, data })
     }
 
+    /// The number of events logged so far. Sampling it periodically gives the rate of the profiled
+    /// operations, including the events of the profiled FRP nodes.
+    pub fn event_count(self) -> usize {
+        EVENTS.len()
+    }
+
     #[inline(always)]
     fn log_event(self, event: Event) -> EventId {
         let id = EventId(EVENTS.len() as u32);
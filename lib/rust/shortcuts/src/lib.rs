@@ -331,6 +331,16 @@ impl<T> HashSetRegistry<T> {
     }
 }
 
+impl<T: HashSetRegistryItem> HashSetRegistry<T> {
+    /// Check whether both expressions are triggered by the same combination of keys, like
+    /// `"cmd a"` and `"a ctrl-left"` on the platforms where `cmd` is an alias of `ctrl`.
+    pub fn expressions_overlap(&self, expr1: impl AsRef<str>, expr2: impl AsRef<str>) -> bool {
+        let model = self.rc.borrow();
+        let exprs1 = model.possible_exprs(expr1);
+        model.possible_exprs(expr2).iter().any(|expr| exprs1.contains(expr))
+    }
+}
+
 impl<T: HashSetRegistryItem> Registry<T> for HashSetRegistry<T> {
     fn add(&self, action_type: ActionType, expr: impl AsRef<str>, action: impl Into<T>) {
         self.rc.borrow_mut().add(action_type, expr, action)
@@ -556,6 +566,18 @@ mod tests {
         assert_eq!(registry.on_press("a"), vec![0]);
         registry
     }
+
+
+    // === Overlapping Expressions ===
+
+    #[test]
+    fn overlapping_expressions() {
+        let registry = HashSetRegistry::<i32>::new();
+        assert!(registry.expressions_overlap("ctrl a", "a control"));
+        assert!(registry.expressions_overlap("ctrl a", "ctrl-left a"));
+        assert!(!registry.expressions_overlap("ctrl-left a", "ctrl-right a"));
+        assert!(!registry.expressions_overlap("ctrl a", "ctrl shift a"));
+    }
 }
 
 
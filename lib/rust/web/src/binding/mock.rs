@@ -655,6 +655,7 @@ mock_data! { Element => Node
     fn set_class_name(&self, value: &str);
     fn set_id(&self, value: &str);
     fn set_attribute(&self, name: &str, value: &str) -> Result<(), JsValue>;
+    fn scroll_top(&self) -> i32;
     fn set_scroll_top(&self, value: i32);
     fn prepend_with_node_0(&self) -> Result<(), JsValue>;
     fn prepend_with_node_1(&self, n1: &Node) -> Result<(), JsValue>;